@@ -0,0 +1,11 @@
+-- Migration: create_rate_limit_buckets
+-- Created at: Qua 20 Ago 2025 09:30:15 -03
+
+-- Buckets do rate limit (usado quando RATE_LIMIT_BACKEND=postgres)
+CREATE TABLE IF NOT EXISTS rate_limit_buckets (
+    key TEXT PRIMARY KEY,
+    tokens DOUBLE PRECISION NOT NULL,
+    dt_refilled TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_rate_limit_buckets_dt_refilled ON rate_limit_buckets(dt_refilled);
@@ -0,0 +1,37 @@
+-- Migration: create_categories
+-- Created at: Sex 22 Ago 2025 09:45:00 -03
+
+-- Árvore de categorias por tenant (materialized path: "/<raiz>/<filha>/.../<id>/")
+CREATE TABLE IF NOT EXISTS categories (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    parent_id UUID REFERENCES categories(id) ON DELETE RESTRICT,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL,
+    description TEXT,
+    path TEXT NOT NULL,
+    depth INTEGER NOT NULL DEFAULT 0 CHECK (depth >= 0),
+    position INTEGER NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    dt_updated TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    dt_deleted TIMESTAMP
+);
+
+-- Slug único por tenant entre as categorias não removidas
+CREATE UNIQUE INDEX IF NOT EXISTS uq_categories_tenant_slug
+    ON categories(tenant_id, slug) WHERE dt_deleted IS NULL;
+CREATE INDEX IF NOT EXISTS idx_categories_tenant_id ON categories(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_categories_parent_id ON categories(parent_id);
+-- text_pattern_ops permite usar o índice na busca por prefixo (path LIKE '/a/b/%')
+CREATE INDEX IF NOT EXISTS idx_categories_path ON categories(path text_pattern_ops);
+
+-- Produtos x categorias (N:N)
+CREATE TABLE IF NOT EXISTS product_categories (
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    category_id UUID NOT NULL REFERENCES categories(id) ON DELETE CASCADE,
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    PRIMARY KEY (product_id, category_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_product_categories_category_id ON product_categories(category_id);
@@ -0,0 +1,67 @@
+-- Migration: create_product_slug_history
+-- Created at: Sáb 23 Ago 2025 11:00:00 -03
+
+-- Normaliza os slugs existentes (antes eram uma cópia crua do nome)
+UPDATE products
+SET slug = COALESCE(
+    NULLIF(
+        trim(BOTH '-' FROM regexp_replace(
+            translate(lower(name), 'áàâãäåéèêëíìîïóòôõöúùûüçñýÿ', 'aaaaaaeeeeiiiiooooouuuucnyy'),
+            '[^a-z0-9]+', '-', 'g'
+        )),
+        ''
+    ),
+    'produto'
+);
+
+-- Resolve colisões por tenant com sufixo numérico (o mais antigo fica com o slug base).
+-- O sufixo é escolhido contra os slugs já ocupados: "foo", "foo", "foo-2" vira "foo", "foo-3", "foo-2"
+DO $$
+DECLARE
+    dup RECORD;
+    n INT;
+BEGIN
+    FOR dup IN
+        SELECT id, tenant_id, slug
+        FROM (
+            SELECT id,
+                   tenant_id,
+                   slug,
+                   ROW_NUMBER() OVER (PARTITION BY tenant_id, slug ORDER BY dt_created, id) AS rn
+            FROM products
+            WHERE dt_deleted IS NULL
+        ) ranked
+        WHERE rn > 1
+        ORDER BY tenant_id, slug, rn
+    LOOP
+        n := 2;
+        WHILE EXISTS (
+            SELECT 1 FROM products
+            WHERE tenant_id = dup.tenant_id
+              AND slug = dup.slug || '-' || n
+              AND dt_deleted IS NULL
+        ) LOOP
+            n := n + 1;
+        END LOOP;
+
+        UPDATE products SET slug = dup.slug || '-' || n WHERE id = dup.id;
+    END LOOP;
+END $$;
+
+-- Slug único por tenant entre os produtos não removidos
+CREATE UNIQUE INDEX IF NOT EXISTS uq_products_tenant_slug
+    ON products(tenant_id, slug) WHERE dt_deleted IS NULL;
+
+-- Slugs antigos de cada produto (respondem com redirect para o slug atual)
+CREATE TABLE IF NOT EXISTS product_slug_history (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    slug TEXT NOT NULL,
+    dt_created TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_product_slug_history_tenant_slug
+    ON product_slug_history(tenant_id, slug);
+CREATE INDEX IF NOT EXISTS idx_product_slug_history_product_id
+    ON product_slug_history(product_id);
@@ -0,0 +1,37 @@
+-- Migration: create_media
+-- Created at: Dom 24 Ago 2025 09:30:00 -03
+
+-- Arquivos enviados (o conteúdo fica no storage: disco local ou bucket S3)
+CREATE TABLE IF NOT EXISTS media_assets (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    uploaded_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    backend TEXT NOT NULL,
+    storage_key TEXT NOT NULL,
+    thumbnail_key TEXT,
+    visibility TEXT NOT NULL DEFAULT 'public' CHECK (visibility IN ('public', 'private')),
+    content_type TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes > 0),
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    original_filename TEXT,
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    dt_deleted TIMESTAMP
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_media_assets_storage_key ON media_assets(storage_key);
+CREATE INDEX IF NOT EXISTS idx_media_assets_tenant_id ON media_assets(tenant_id);
+
+-- Galeria ordenada de cada produto
+CREATE TABLE IF NOT EXISTS product_images (
+    id UUID PRIMARY KEY,
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    media_id UUID NOT NULL REFERENCES media_assets(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL DEFAULT 0 CHECK (position >= 0),
+    alt_text TEXT,
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    dt_updated TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_product_images_product_media ON product_images(product_id, media_id);
+CREATE INDEX IF NOT EXISTS idx_product_images_product_position ON product_images(product_id, position);
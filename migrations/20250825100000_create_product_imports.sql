@@ -0,0 +1,38 @@
+-- Migration: create_product_imports
+-- Created at: Seg 25 Ago 2025 10:00:00 -03
+
+-- SKU do lojista: chave de upsert na importação, único por tenant entre os produtos ativos
+ALTER TABLE products ADD COLUMN IF NOT EXISTS sku TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_products_tenant_sku
+    ON products (tenant_id, sku)
+    WHERE sku IS NOT NULL AND dt_deleted IS NULL;
+
+-- Ordem estável para exportação em lotes (keyset)
+CREATE INDEX IF NOT EXISTS idx_products_tenant_created
+    ON products (tenant_id, dt_created, id)
+    WHERE dt_deleted IS NULL;
+
+-- Importações em lote (CSV/JSONL) com progresso e erros por linha
+CREATE TABLE IF NOT EXISTS product_import_jobs (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    format TEXT NOT NULL CHECK (format IN ('csv', 'jsonl')),
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'running', 'completed', 'failed')),
+    total_rows INTEGER NOT NULL DEFAULT 0,
+    processed_rows INTEGER NOT NULL DEFAULT 0,
+    created_count INTEGER NOT NULL DEFAULT 0,
+    updated_count INTEGER NOT NULL DEFAULT 0,
+    failed_count INTEGER NOT NULL DEFAULT 0,
+    -- [{ "row": 3, "key": "SKU-1", "errors": [{ "field": "price", "message": "..." }] }]
+    row_errors JSONB NOT NULL DEFAULT '[]'::jsonb,
+    error_message TEXT,
+    dt_created TIMESTAMP NOT NULL DEFAULT NOW(),
+    dt_started TIMESTAMP,
+    dt_finished TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_product_import_jobs_tenant
+    ON product_import_jobs (tenant_id, dt_created DESC);
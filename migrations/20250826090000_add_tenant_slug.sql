@@ -0,0 +1,16 @@
+-- Migration: add_tenant_slug
+-- Created at: Ter 26 Ago 2025 09:00:00 -03
+
+-- Identificador público da loja, usado nas rotas da vitrine (/store/{tenant_slug}/...)
+ALTER TABLE tenants ADD COLUMN slug TEXT;
+
+UPDATE tenants SET slug = 'loja-' || left(replace(id::text, '-', ''), 12);
+
+ALTER TABLE tenants ALTER COLUMN slug SET NOT NULL;
+
+CREATE UNIQUE INDEX uq_tenants_slug ON tenants(slug);
+
+-- Listagem da vitrine: produtos ativos do tenant, mais recentes primeiro
+CREATE INDEX idx_products_tenant_active_created
+    ON products(tenant_id, dt_created DESC, id DESC)
+    WHERE is_active = true AND dt_deleted IS NULL;
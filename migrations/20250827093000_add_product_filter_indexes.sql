@@ -0,0 +1,9 @@
+-- Migration: add_product_filter_indexes
+-- Created at: Qua 27 Ago 2025 09:30:00 -03
+
+-- Filtros por atributo (attributes @> '{"cor": "azul"}') na listagem de produtos
+CREATE INDEX idx_products_attributes ON products USING GIN (attributes jsonb_path_ops);
+
+-- Ordenação por preço e filtro updated_since dentro do tenant
+CREATE INDEX idx_products_tenant_price ON products(tenant_id, price);
+CREATE INDEX idx_products_tenant_updated ON products(tenant_id, dt_updated);
@@ -0,0 +1,32 @@
+-- Migration: create_product_prices
+-- Created at: Qui 28 Ago 2025 09:00:00 -03
+
+-- Histórico de preços: cada alteração do preço base e cada preço agendado (promoções).
+-- products.price continua sendo o preço base; o preço efetivo é resolvido na leitura.
+CREATE TABLE product_prices (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('base', 'scheduled')),
+    price NUMERIC(12,2) NOT NULL CHECK (price >= 0),
+    -- Preço "de" exibido riscado; sem valor, a promoção compara com o preço base
+    compare_at_price NUMERIC(12,2) CHECK (compare_at_price IS NULL OR compare_at_price > price),
+    starts_at TIMESTAMP NOT NULL,
+    ends_at TIMESTAMP CHECK (ends_at IS NULL OR ends_at > starts_at),
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    -- Agendamento cancelado
+    dt_deleted TIMESTAMP
+);
+
+CREATE INDEX idx_product_prices_product ON product_prices(product_id, dt_created DESC);
+
+-- Resolução do preço efetivo: agendamentos vigentes do produto
+CREATE INDEX idx_product_prices_scheduled
+    ON product_prices(product_id, starts_at)
+    WHERE kind = 'scheduled' AND dt_deleted IS NULL;
+
+-- Preço atual dos produtos existentes como primeiro registro do histórico
+INSERT INTO product_prices (id, tenant_id, product_id, kind, price, starts_at, dt_created)
+SELECT gen_random_uuid(), tenant_id, id, 'base', price, dt_created, dt_created
+FROM products;
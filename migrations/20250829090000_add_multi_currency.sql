@@ -0,0 +1,34 @@
+-- Migration: add_multi_currency
+-- Created at: Sex 29 Ago 2025 09:00:00 -03
+
+-- Moeda base da loja: products.price e o histórico de preços estão nela
+ALTER TABLE tenants ADD COLUMN base_currency TEXT NOT NULL DEFAULT 'BRL'
+    CHECK (base_currency ~ '^[A-Z]{3}$');
+
+-- Tabela de preços por moeda: valor fixo do produto em outra moeda (sem conversão).
+-- Sem escala fixa: cada moeda guarda as próprias casas decimais (JPY 0, BRL 2, KWD 3)
+CREATE TABLE product_currency_prices (
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    currency TEXT NOT NULL CHECK (currency ~ '^[A-Z]{3}$'),
+    price NUMERIC NOT NULL CHECK (price >= 0),
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    dt_updated TIMESTAMP NOT NULL DEFAULT now(),
+    PRIMARY KEY (product_id, currency)
+);
+
+-- Cotações: 1 base_currency = rate quote_currency, válidas a partir de effective_at.
+-- Vale a mais recente de cada par; o histórico fica para consulta.
+CREATE TABLE exchange_rates (
+    id UUID PRIMARY KEY,
+    base_currency TEXT NOT NULL CHECK (base_currency ~ '^[A-Z]{3}$'),
+    quote_currency TEXT NOT NULL CHECK (quote_currency ~ '^[A-Z]{3}$'),
+    rate NUMERIC(24,10) NOT NULL CHECK (rate > 0),
+    -- Origem da cotação: "import" ou o nome do provedor
+    source TEXT NOT NULL,
+    effective_at TIMESTAMP NOT NULL,
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    CHECK (base_currency <> quote_currency)
+);
+
+CREATE INDEX idx_exchange_rates_pair ON exchange_rates(base_currency, quote_currency, effective_at DESC);
@@ -0,0 +1,10 @@
+-- Migration: create_money_value_type
+-- Created at: Sáb 30 Ago 2025 09:00:00 -03
+
+-- Valor monetário (utils::money::Money): unidade mínima da moeda + código ISO 4217.
+-- As tabelas continuam com colunas separadas; as consultas montam o valor com
+-- ROW(valor, moeda)::money_value
+CREATE TYPE money_value AS (
+    amount_minor BIGINT,
+    currency TEXT
+);
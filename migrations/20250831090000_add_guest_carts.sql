@@ -0,0 +1,22 @@
+-- Migration: add_guest_carts
+-- Created at: Dom 31 Ago 2025 09:00:00 -03
+
+-- Carrinho de visitante incorporado ao carrinho da conta no login
+ALTER TYPE cart_status ADD VALUE IF NOT EXISTS 'MERGED';
+
+-- Visitante não tem tenant nem usuário: é identificado pelo guest_id do token do carrinho
+ALTER TABLE carts
+    ALTER COLUMN tenant_id DROP NOT NULL,
+    ALTER COLUMN user_id DROP NOT NULL,
+    ADD COLUMN IF NOT EXISTS guest_id UUID;
+
+ALTER TABLE carts
+    ADD CONSTRAINT chk_carts_owner CHECK (
+        (guest_id IS NULL AND tenant_id IS NOT NULL AND user_id IS NOT NULL)
+        OR (guest_id IS NOT NULL AND tenant_id IS NULL AND user_id IS NULL)
+    );
+
+-- Garante 1 carrinho ACTIVE por visitante
+CREATE UNIQUE INDEX IF NOT EXISTS uq_active_cart_per_guest
+  ON carts(guest_id)
+  WHERE status = 'ACTIVE' AND dt_deleted IS NULL AND guest_id IS NOT NULL;
@@ -0,0 +1,75 @@
+-- Migration: create_wishlists
+-- Created at: Seg 01 Set 2025 09:00:00 -03
+
+-- Listas de desejos nomeadas e a lista "salvos para depois" (uma por usuário)
+CREATE TYPE wishlist_kind AS ENUM ('WISHLIST', 'SAVED_FOR_LATER');
+
+CREATE TABLE IF NOT EXISTS wishlists (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    kind wishlist_kind NOT NULL DEFAULT 'WISHLIST',
+    -- Link público somente leitura; vazio quando a lista não está compartilhada
+    share_token VARCHAR(64),
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    dt_updated TIMESTAMP NOT NULL DEFAULT now(),
+    dt_deleted TIMESTAMP
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_wishlist_name_per_user
+  ON wishlists(tenant_id, user_id, lower(name))
+  WHERE kind = 'WISHLIST' AND dt_deleted IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_saved_for_later_per_user
+  ON wishlists(tenant_id, user_id)
+  WHERE kind = 'SAVED_FOR_LATER' AND dt_deleted IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_wishlist_share_token
+  ON wishlists(share_token)
+  WHERE share_token IS NOT NULL;
+
+-- Itens guardam variante e atributos como estavam no carrinho
+CREATE TABLE IF NOT EXISTS wishlist_items (
+    id UUID PRIMARY KEY,
+    wishlist_id UUID NOT NULL REFERENCES wishlists(id) ON DELETE CASCADE,
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    variant_id UUID,
+    attributes_snapshot JSONB NOT NULL DEFAULT '{}'::jsonb,
+    attributes_hash TEXT GENERATED ALWAYS AS (md5(attributes_snapshot::text)) STORED,
+    quantity INT NOT NULL DEFAULT 1 CHECK (quantity > 0),
+
+    -- Avisos pedidos pelo usuário e o último estado visto pelo worker de alertas
+    notify_price_drop BOOLEAN NOT NULL DEFAULT false,
+    notify_back_in_stock BOOLEAN NOT NULL DEFAULT false,
+    last_seen_price BIGINT,
+    last_seen_currency TEXT,
+    last_seen_in_stock BOOLEAN,
+
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    dt_updated TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_wishlist_item_dedup
+  ON wishlist_items(wishlist_id, product_id, COALESCE(variant_id, '00000000-0000-0000-0000-000000000000'::uuid), attributes_hash);
+
+CREATE INDEX IF NOT EXISTS idx_wishlist_items_alerts
+  ON wishlist_items(product_id)
+  WHERE notify_price_drop OR notify_back_in_stock;
+
+-- Avisos gerados para o usuário (queda de preço, volta ao estoque)
+CREATE TABLE IF NOT EXISTS wishlist_notifications (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    wishlist_item_id UUID NOT NULL REFERENCES wishlist_items(id) ON DELETE CASCADE,
+    product_id UUID NOT NULL REFERENCES products(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('price_drop', 'back_in_stock')),
+    old_price BIGINT,
+    new_price BIGINT,
+    currency TEXT,
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    dt_read TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_wishlist_notifications_user
+  ON wishlist_notifications(user_id, dt_created DESC);
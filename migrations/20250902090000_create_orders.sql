@@ -0,0 +1,78 @@
+-- Migration: create_orders
+-- Created at: Ter 02 Set 2025 09:00:00 -03
+
+-- 1) Ciclo de vida do pedido (transições permitidas em OrderStatus::can_transition_to)
+CREATE TYPE order_status AS ENUM (
+    'PENDING_PAYMENT',
+    'PAID',
+    'FULFILLING',
+    'SHIPPED',
+    'DELIVERED',
+    'CANCELLED',
+    'REFUNDED'
+);
+
+-- 2) Pedidos: o checkout gera um pedido por loja vendedora do carrinho
+CREATE TABLE IF NOT EXISTS orders (
+    id UUID PRIMARY KEY,
+    cart_id UUID NOT NULL REFERENCES carts(id),
+    buyer_tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    buyer_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    seller_tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+
+    status order_status NOT NULL DEFAULT 'PENDING_PAYMENT',
+    currency CHAR(3) NOT NULL,
+
+    -- valores na unidade mínima da moeda
+    subtotal BIGINT NOT NULL DEFAULT 0 CHECK (subtotal >= 0),
+    discount_total BIGINT NOT NULL DEFAULT 0 CHECK (discount_total >= 0),
+    tax_total BIGINT NOT NULL DEFAULT 0 CHECK (tax_total >= 0),
+    shipping_total BIGINT NOT NULL DEFAULT 0 CHECK (shipping_total >= 0),
+    grand_total BIGINT GENERATED ALWAYS AS (
+        subtotal - discount_total + tax_total + shipping_total
+    ) STORED,
+
+    -- Preenchidos quando a loja despacha o pedido
+    tracking_code VARCHAR(100),
+    carrier VARCHAR(100),
+
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    dt_updated TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_orders_buyer
+  ON orders(buyer_tenant_id, buyer_user_id, dt_created DESC);
+CREATE INDEX IF NOT EXISTS idx_orders_seller
+  ON orders(seller_tenant_id, dt_created DESC);
+CREATE INDEX IF NOT EXISTS idx_orders_cart ON orders(cart_id);
+
+-- 3) Itens: cópia da linha do carrinho no momento do checkout
+CREATE TABLE IF NOT EXISTS order_items (
+    id UUID PRIMARY KEY,
+    order_id UUID NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    product_id UUID NOT NULL REFERENCES products(id),
+    variant_id UUID,
+    attributes_snapshot JSONB NOT NULL DEFAULT '{}'::jsonb,
+    product_name TEXT NOT NULL,
+    unit_price BIGINT NOT NULL CHECK (unit_price >= 0),
+    quantity INT NOT NULL CHECK (quantity > 0),
+    line_total BIGINT GENERATED ALWAYS AS (unit_price * quantity) STORED,
+    dt_created TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_order_items_order ON order_items(order_id);
+
+-- 4) Histórico de status (append-only): quem mudou, de onde para onde e quando
+CREATE TABLE IF NOT EXISTS order_status_history (
+    id UUID PRIMARY KEY,
+    order_id UUID NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    from_status order_status,
+    to_status order_status NOT NULL,
+    actor_id UUID,
+    actor_role TEXT NOT NULL CHECK (actor_role IN ('buyer', 'seller', 'system')),
+    note TEXT,
+    dt_created TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_order_status_history_order
+  ON order_status_history(order_id, dt_created);
@@ -0,0 +1,81 @@
+-- Migration: create_order_returns
+-- Created at: Qua 03 Set 2025 09:00:00 -03
+
+-- 1) Devoluções: solicitadas pelo comprador por linha do pedido, decididas pela loja
+CREATE TYPE return_status AS ENUM (
+    'REQUESTED',
+    'APPROVED',
+    'REJECTED',
+    'RECEIVED'
+);
+
+CREATE TYPE return_reason AS ENUM (
+    'DAMAGED',
+    'WRONG_ITEM',
+    'NOT_AS_DESCRIBED',
+    'NO_LONGER_NEEDED',
+    'OTHER'
+);
+
+CREATE TABLE IF NOT EXISTS return_requests (
+    id UUID PRIMARY KEY,
+    order_id UUID NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    order_item_id UUID NOT NULL REFERENCES order_items(id) ON DELETE CASCADE,
+    buyer_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    quantity INT NOT NULL CHECK (quantity > 0),
+    reason return_reason NOT NULL,
+    description TEXT,
+    status return_status NOT NULL DEFAULT 'REQUESTED',
+
+    -- Decisão da loja (aprovação ou recusa) e confirmação do recebimento
+    decision_note TEXT,
+    decided_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    dt_decided TIMESTAMP,
+    received_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    dt_received TIMESTAMP,
+
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    dt_updated TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_return_requests_order ON return_requests(order_id, dt_created);
+CREATE INDEX IF NOT EXISTS idx_return_requests_item ON return_requests(order_item_id);
+CREATE INDEX IF NOT EXISTS idx_return_requests_status ON return_requests(status, dt_created DESC);
+
+-- 2) Reembolsos feitos pelo provedor de pagamento (PAYMENT_PROVIDER)
+CREATE TABLE IF NOT EXISTS payment_refunds (
+    id UUID PRIMARY KEY,
+    order_id UUID NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    provider TEXT NOT NULL,
+    provider_reference TEXT NOT NULL,
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    currency CHAR(3) NOT NULL,
+    dt_created TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_payment_refunds_order ON payment_refunds(order_id);
+
+-- 3) Notas de crédito: uma por devolução aprovada, com numeração sequencial
+CREATE SEQUENCE IF NOT EXISTS credit_note_number_seq;
+
+CREATE TABLE IF NOT EXISTS credit_notes (
+    id UUID PRIMARY KEY,
+    number BIGINT NOT NULL UNIQUE DEFAULT nextval('credit_note_number_seq'),
+    order_id UUID NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+    return_request_id UUID NOT NULL UNIQUE REFERENCES return_requests(id) ON DELETE CASCADE,
+    refund_id UUID NOT NULL REFERENCES payment_refunds(id),
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    currency CHAR(3) NOT NULL,
+    dt_created TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_credit_notes_order ON credit_notes(order_id, number);
+
+-- 4) Total já devolvido ao comprador, nunca acima do total do pedido
+ALTER TABLE orders
+    ADD COLUMN IF NOT EXISTS refunded_total BIGINT NOT NULL DEFAULT 0
+        CHECK (refunded_total >= 0);
+
+ALTER TABLE orders
+    ADD CONSTRAINT orders_refunded_within_total
+        CHECK (refunded_total <= subtotal - discount_total + tax_total + shipping_total);
@@ -0,0 +1,113 @@
+-- Migration: create_marketplace_ledger
+-- Created at: Qui 04 Set 2025 09:00:00 -03
+
+-- 1) Comissão da plataforma em pontos-base (1000 = 10%), por loja ou por categoria.
+--    Sem regra vale MARKETPLACE_COMMISSION_BPS.
+CREATE TABLE IF NOT EXISTS commission_rates (
+    id UUID PRIMARY KEY,
+    tenant_id UUID REFERENCES tenants(id) ON DELETE CASCADE,
+    category_id UUID REFERENCES categories(id) ON DELETE CASCADE,
+    rate_bps INT NOT NULL CHECK (rate_bps BETWEEN 0 AND 10000),
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    dt_updated TIMESTAMP NOT NULL DEFAULT now(),
+    CHECK ((tenant_id IS NULL) <> (category_id IS NULL))
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_commission_rates_tenant
+  ON commission_rates(tenant_id) WHERE tenant_id IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS uq_commission_rates_category
+  ON commission_rates(category_id) WHERE category_id IS NOT NULL;
+
+-- 2) Comissão de cada linha, fixada no checkout
+ALTER TABLE order_items
+    ADD COLUMN IF NOT EXISTS commission_bps INT NOT NULL DEFAULT 0
+        CHECK (commission_bps BETWEEN 0 AND 10000),
+    ADD COLUMN IF NOT EXISTS commission_amount BIGINT NOT NULL DEFAULT 0
+        CHECK (commission_amount >= 0);
+
+-- 3) Lotes de repasse às lojas; conciliados contra o extrato bancário
+CREATE TYPE payout_batch_status AS ENUM (
+    'PENDING',
+    'RECONCILED'
+);
+
+CREATE TABLE IF NOT EXISTS payout_batches (
+    id UUID PRIMARY KEY,
+    currency CHAR(3) NOT NULL,
+    status payout_batch_status NOT NULL DEFAULT 'PENDING',
+    total BIGINT NOT NULL CHECK (total > 0),
+    reference TEXT,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reconciled_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    dt_reconciled TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_payout_batches_created ON payout_batches(dt_created DESC);
+
+CREATE TABLE IF NOT EXISTS payouts (
+    id UUID PRIMARY KEY,
+    batch_id UUID NOT NULL REFERENCES payout_batches(id) ON DELETE CASCADE,
+    seller_tenant_id UUID NOT NULL REFERENCES tenants(id) ON DELETE CASCADE,
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    currency CHAR(3) NOT NULL,
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    UNIQUE (batch_id, seller_tenant_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_payouts_seller ON payouts(seller_tenant_id, dt_created DESC);
+
+-- 4) Razão em partidas dobradas: cada lançamento tem lançamentos de débito (+) e crédito (-)
+--    que somam zero por moeda
+CREATE TABLE IF NOT EXISTS ledger_entries (
+    id UUID PRIMARY KEY,
+    kind TEXT NOT NULL CHECK (
+        kind IN ('sale', 'sale_reversal', 'refund', 'payout', 'payout_settlement')
+    ),
+    order_id UUID REFERENCES orders(id) ON DELETE CASCADE,
+    credit_note_id UUID REFERENCES credit_notes(id) ON DELETE CASCADE,
+    payout_batch_id UUID REFERENCES payout_batches(id) ON DELETE CASCADE,
+    description TEXT NOT NULL,
+    dt_created TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_entries_order ON ledger_entries(order_id);
+CREATE INDEX IF NOT EXISTS idx_ledger_entries_batch ON ledger_entries(payout_batch_id);
+
+CREATE TABLE IF NOT EXISTS ledger_postings (
+    id UUID PRIMARY KEY,
+    entry_id UUID NOT NULL REFERENCES ledger_entries(id) ON DELETE CASCADE,
+    account TEXT NOT NULL CHECK (
+        account IN ('platform_cash', 'platform_commission', 'seller_payable', 'payout_clearing')
+    ),
+    -- Só a conta a pagar da loja é por tenant
+    tenant_id UUID REFERENCES tenants(id) ON DELETE CASCADE,
+    amount BIGINT NOT NULL CHECK (amount <> 0),
+    currency CHAR(3) NOT NULL,
+    dt_created TIMESTAMP NOT NULL DEFAULT now(),
+    CHECK ((account = 'seller_payable') = (tenant_id IS NOT NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_postings_entry ON ledger_postings(entry_id);
+CREATE INDEX IF NOT EXISTS idx_ledger_postings_account
+  ON ledger_postings(account, tenant_id, currency, dt_created DESC);
+
+-- Confere no fim da transação que o lançamento fecha (débitos = créditos)
+CREATE OR REPLACE FUNCTION ledger_check_entry_balanced() RETURNS trigger AS $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM ledger_postings
+        WHERE entry_id = NEW.entry_id
+        GROUP BY currency
+        HAVING SUM(amount) <> 0
+    ) THEN
+        RAISE EXCEPTION 'Lançamento % não fecha: débitos diferentes dos créditos', NEW.entry_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE CONSTRAINT TRIGGER trg_ledger_entry_balanced
+    AFTER INSERT OR UPDATE ON ledger_postings
+    DEFERRABLE INITIALLY DEFERRED
+    FOR EACH ROW EXECUTE FUNCTION ledger_check_entry_balanced();
@@ -0,0 +1,11 @@
+-- Migration: add_product_prices_overlap_constraint
+-- Created at: Sex 05 Set 2025 09:00:00 -03
+
+-- Agendamentos do mesmo produto não se sobrepõem. A checagem no serviço responde com uma
+-- mensagem amigável, mas duas requisições simultâneas passariam por ela; a restrição garante.
+CREATE EXTENSION IF NOT EXISTS btree_gist;
+
+ALTER TABLE product_prices
+    ADD CONSTRAINT ex_product_prices_scheduled_overlap
+    EXCLUDE USING gist (product_id WITH =, tsrange(starts_at, ends_at) WITH &&)
+    WHERE (kind = 'scheduled' AND dt_deleted IS NULL);
@@ -0,0 +1,8 @@
+-- Migration: unscaled_product_prices
+-- Created at: Sáb 06 Set 2025 09:00:00 -03
+
+-- Preços na moeda base da loja sem escala fixa, como em product_currency_prices:
+-- NUMERIC(12,2) truncava moedas de 3 casas (KWD, BHD)
+ALTER TABLE products ALTER COLUMN price TYPE NUMERIC;
+ALTER TABLE product_prices ALTER COLUMN price TYPE NUMERIC;
+ALTER TABLE product_prices ALTER COLUMN compare_at_price TYPE NUMERIC;
@@ -0,0 +1,16 @@
+-- Migration: add_pending_payment_refunds
+-- Created at: Dom 07 Set 2025 09:00:00 -03
+
+-- Reembolso gravado junto com a aprovação, antes da chamada ao provedor: sem
+-- provider_reference enquanto o provedor não confirmou. Uma falha depois disso deixa
+-- o registro para a nova tentativa concluir, em vez de um reembolso sem rastro.
+ALTER TABLE payment_refunds
+    ADD COLUMN IF NOT EXISTS return_request_id UUID UNIQUE
+        REFERENCES return_requests(id) ON DELETE CASCADE;
+
+ALTER TABLE payment_refunds ALTER COLUMN provider_reference DROP NOT NULL;
+
+UPDATE payment_refunds r
+SET return_request_id = c.return_request_id
+FROM credit_notes c
+WHERE c.refund_id = r.id AND r.return_request_id IS NULL;
@@ -0,0 +1,14 @@
+-- Migration: resumable_product_imports
+-- Created at: Seg 08 Set 2025 09:00:00 -03
+
+-- Importação cortada pelo prazo de desligamento fica 'interrupted' com o arquivo guardado;
+-- o próximo servidor retoma a partir de processed_rows. O arquivo é descartado ao terminar.
+ALTER TABLE product_import_jobs ADD COLUMN IF NOT EXISTS source TEXT;
+
+ALTER TABLE product_import_jobs DROP CONSTRAINT IF EXISTS product_import_jobs_status_check;
+ALTER TABLE product_import_jobs ADD CONSTRAINT product_import_jobs_status_check
+    CHECK (status IN ('pending', 'running', 'interrupted', 'completed', 'failed'));
+
+CREATE INDEX IF NOT EXISTS idx_product_import_jobs_interrupted
+    ON product_import_jobs (dt_created)
+    WHERE status = 'interrupted';
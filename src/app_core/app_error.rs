@@ -1,5 +1,11 @@
-use actix_web::{HttpResponse, ResponseError};
+use crate::app_core::request_id::current_request_id;
+use actix_web::error::{JsonPayloadError, PathError, QueryPayloadError};
+use actix_web::http::StatusCode;
+use actix_web::{HttpRequest, HttpResponse, ResponseError};
 use derive_more::Display; // essa macro implementa Display por você!
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use validator::{ValidationErrors, ValidationErrorsKind};
 // use std::fmt::{self, Display, Formatter};
 
 #[derive(Debug, Display)]
@@ -23,6 +29,19 @@ pub enum AppError {
     #[display(fmt = "Requisição inválida")]
     BadRequest(Option<String>),
 
+    #[display(fmt = "Dados inválidos")]
+    Validation(ValidationErrors),
+
+    /// Falha ao extrair JSON, path ou query string da requisição
+    #[display(fmt = "{message}")]
+    InvalidRequest { message: String, reason: String },
+
+    #[display(fmt = "Muitas requisições")]
+    TooManyRequests(Option<String>),
+
+    #[display(fmt = "Conteúdo muito grande")]
+    PayloadTooLarge(Option<String>),
+
     #[display(fmt = "Erro interno do servidor")]
     InternalError(Option<String>),
 }
@@ -49,6 +68,21 @@ impl AppError {
         AppError::BadRequest(Some(msg.into()))
     }
 
+    pub fn too_many_requests<S: Into<String>>(msg: S) -> Self {
+        AppError::TooManyRequests(Some(msg.into()))
+    }
+
+    pub fn payload_too_large<S: Into<String>>(msg: S) -> Self {
+        AppError::PayloadTooLarge(Some(msg.into()))
+    }
+
+    pub fn invalid_request<M: Into<String>, R: Into<String>>(message: M, reason: R) -> Self {
+        AppError::InvalidRequest {
+            message: message.into(),
+            reason: reason.into(),
+        }
+    }
+
     pub fn internal<S: Into<String>>(msg: S) -> Self {
         AppError::InternalError(Some(msg.into()))
     }
@@ -77,28 +111,170 @@ impl From<sqlx::Error> for AppError {
     }
 }
 
-impl ResponseError for AppError {
-    fn error_response(&self) -> HttpResponse {
+/// Envelope único de erro retornado pela API
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub code: String,
+    pub message: String,
+    pub details: Option<Value>,
+    pub request_id: Option<String>,
+}
+
+impl AppError {
+    /// Código estável, para uso dos clientes (não muda junto com as mensagens)
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Conflict(_) => "CONFLICT",
+            AppError::DatabaseError(_) => "DATABASE_ERROR",
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::Unauthorized(_) => "UNAUTHORIZED",
+            AppError::Forbidden(_) => "FORBIDDEN",
+            AppError::BadRequest(_) => "BAD_REQUEST",
+            AppError::Validation(_) => "VALIDATION_ERROR",
+            AppError::InvalidRequest { .. } => "INVALID_REQUEST",
+            AppError::TooManyRequests(_) => "TOO_MANY_REQUESTS",
+            AppError::PayloadTooLarge(_) => "PAYLOAD_TOO_LARGE",
+            AppError::InternalError(_) => "INTERNAL_ERROR",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            AppError::Conflict(msg) => msg.as_deref().unwrap_or("Conflito de dados").to_string(),
+            AppError::DatabaseError(msg) => msg
+                .as_deref()
+                .unwrap_or("Erro no banco de dados")
+                .to_string(),
+            AppError::NotFound(msg) => msg
+                .as_deref()
+                .unwrap_or("Recurso não encontrado")
+                .to_string(),
+            AppError::Unauthorized(msg) => msg.as_deref().unwrap_or("Não autorizado").to_string(),
+            AppError::Forbidden(msg) => msg.as_deref().unwrap_or("Acesso negado").to_string(),
+            AppError::BadRequest(msg) => {
+                msg.as_deref().unwrap_or("Requisição inválida").to_string()
+            }
+            AppError::Validation(_) => "Dados inválidos".to_string(),
+            AppError::InvalidRequest { message, .. } => message.clone(),
+            AppError::TooManyRequests(msg) => {
+                msg.as_deref().unwrap_or("Muitas requisições").to_string()
+            }
+            AppError::PayloadTooLarge(msg) => msg
+                .as_deref()
+                .unwrap_or("Conteúdo muito grande")
+                .to_string(),
+            AppError::InternalError(msg) => msg
+                .as_deref()
+                .unwrap_or("Erro interno do servidor")
+                .to_string(),
+        }
+    }
+
+    pub fn details(&self) -> Option<Value> {
         match self {
-            AppError::Conflict(msg) => {
-                HttpResponse::Conflict().json(msg.as_deref().unwrap_or("Conflito de dados"))
+            AppError::Validation(errors) => Some(validation_details(errors)),
+            AppError::InvalidRequest { reason, .. } => Some(json!({ "reason": reason })),
+            _ => None,
+        }
+    }
+
+    pub fn to_error_response(&self) -> ErrorResponse {
+        ErrorResponse {
+            code: self.code().to_string(),
+            message: self.message(),
+            details: self.details(),
+            request_id: current_request_id(),
+        }
+    }
+}
+
+/// Erros do validator por campo: `{ "campo": [{ "code": .., "message": .. }] }`.
+/// Campos de structs aninhadas/listas usam caminho com ponto (ex: `profile.phone`, `items.0.quantity`)
+pub fn validation_details(errors: &ValidationErrors) -> Value {
+    let mut fields = Map::new();
+    collect_validation_errors(errors, None, &mut fields);
+    Value::Object(fields)
+}
+
+fn collect_validation_errors(
+    errors: &ValidationErrors,
+    prefix: Option<&str>,
+    fields: &mut Map<String, Value>,
+) {
+    for (field, kind) in errors.errors() {
+        let path = match prefix {
+            Some(prefix) => format!("{}.{}", prefix, field),
+            None => field.to_string(),
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                let items = field_errors
+                    .iter()
+                    .map(|error| {
+                        json!({
+                            "code": error.code,
+                            "message": error
+                                .message
+                                .as_ref()
+                                .map(|m| m.to_string())
+                                .unwrap_or_else(|| format!("Valor inválido para {}", path)),
+                        })
+                    })
+                    .collect();
+                fields.insert(path, Value::Array(items));
             }
-            AppError::DatabaseError(msg) => HttpResponse::InternalServerError()
-                .json(msg.as_deref().unwrap_or("Erro no banco de dados")),
-            AppError::NotFound(msg) => {
-                HttpResponse::NotFound().json(msg.as_deref().unwrap_or("Recurso não encontrado"))
+            ValidationErrorsKind::Struct(inner) => {
+                collect_validation_errors(inner, Some(&path), fields);
             }
-            AppError::Unauthorized(msg) => {
-                HttpResponse::Unauthorized().json(msg.as_deref().unwrap_or("Não autorizado"))
+            ValidationErrorsKind::List(items) => {
+                for (index, inner) in items {
+                    collect_validation_errors(inner, Some(&format!("{}.{}", path, index)), fields);
+                }
             }
-            AppError::Forbidden(msg) => {
-                HttpResponse::Forbidden().json(msg.as_deref().unwrap_or("Acesso negado"))
+        }
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        AppError::Validation(errors)
+    }
+}
+
+impl ResponseError for AppError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::DatabaseError(_) | AppError::InternalError(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
             }
-            AppError::BadRequest(msg) => {
-                HttpResponse::BadRequest().json(msg.as_deref().unwrap_or("Requisição inválida"))
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::BadRequest(_) | AppError::Validation(_) | AppError::InvalidRequest { .. } => {
+                StatusCode::BAD_REQUEST
             }
-            AppError::InternalError(msg) => HttpResponse::InternalServerError()
-                .json(msg.as_deref().unwrap_or("Erro interno do servidor")),
+            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
         }
     }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(self.to_error_response())
+    }
+}
+
+// Handlers dos extractors do actix para que falhas de JSON/path/query usem o mesmo envelope
+
+pub fn json_error_handler(err: JsonPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    AppError::invalid_request("JSON inválido", err.to_string()).into()
+}
+
+pub fn path_error_handler(err: PathError, _req: &HttpRequest) -> actix_web::Error {
+    AppError::invalid_request("Parâmetro de rota inválido", err.to_string()).into()
+}
+
+pub fn query_error_handler(err: QueryPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    AppError::invalid_request("Parâmetros de consulta inválidos", err.to_string()).into()
 }
@@ -8,3 +8,12 @@ pub struct Claims {
     pub access_level: String,
     pub tenant_id: Uuid,
 }
+
+/// Claims do token de carrinho de visitante. Sem `access_level`/`tenant_id`, não é aceito
+/// pelo AuthMiddleware.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GuestCartClaims {
+    pub sub: String, // guest_id do carrinho
+    pub exp: usize,
+    pub scope: String,
+}
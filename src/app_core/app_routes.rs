@@ -1,85 +1,334 @@
+use crate::app_core::app_error::{json_error_handler, path_error_handler, query_error_handler};
 use crate::app_core::auth_middleware::AuthMiddleware;
+use crate::app_core::health::{health_live, health_ready};
+use crate::app_core::rate_limit::{RateLimitMiddleware, RouteGroup};
+use crate::app_core::request_id::RequestIdMiddleware;
+use crate::app_core::telemetry::MetricsMiddleware;
+use crate::apps::audit::routes::list_audit_events;
 use crate::apps::cart::routes::{
-    add_product_cart, create_cart, delete_cart, delete_product_cart, get_card_by_tenant, get_cards,
+    add_product_cart, bulk_update_cart, bulk_update_guest_cart, clear_cart, clear_guest_cart,
+    create_cart, create_guest_cart, delete_cart, delete_product_cart, get_card_by_tenant,
+    get_cards, get_guest_cart, get_member_cart, revalidate_cart, revalidate_guest_cart,
+    update_cart_item, update_guest_cart_item,
+};
+use crate::apps::category::routes::{
+    create_category, delete_category, get_category, list_categories, set_product_categories,
+    update_category,
+};
+use crate::apps::commission::routes::{
+    delete_commission_rate, list_commission_rates, set_commission_rate,
+};
+use crate::apps::exchange_rate::routes::{
+    import_exchange_rates, list_exchange_rates, sync_exchange_rates,
+};
+use crate::apps::ledger::routes::{get_seller_balance, get_seller_statement};
+use crate::apps::media::routes::{
+    add_product_image, delete_media, delete_product_image, get_media, list_product_images,
+    reorder_product_images, serve_media_file, update_product_image, upload_avatar, upload_media,
 };
 use crate::apps::orchestrator::routes::{
     authorize_app, create_orchestrator, delete_orchestrator, get_orchestrator, list_orchestrators,
     sync_all_users_with_app,
 };
+use crate::apps::order::routes::{
+    cancel_order, checkout, get_order, get_sale, list_orders, list_sales, ship_sale,
+    update_sale_status,
+};
+use crate::apps::order_return::routes::{
+    approve_return, create_return, list_order_credit_notes, list_order_returns,
+    list_sale_credit_notes, list_seller_returns, receive_return, reject_return,
+};
+use crate::apps::payout::routes::{
+    create_payout_batch, get_payout_batch, list_payout_batches, list_seller_payouts,
+    reconcile_payout_batch,
+};
 use crate::apps::product::routes::{
-    create_product, delete_product, get_product, list_products, update_product,
+    create_product, delete_product, get_product, get_product_by_slug, get_store_product,
+    list_products, list_store_products, update_product,
+};
+use crate::apps::product_bulk::routes::{export_products, get_import_job, import_products};
+use crate::apps::product_price::routes::{
+    cancel_product_price, list_product_currency_prices, list_product_prices,
+    schedule_product_price, set_product_currency_prices,
 };
+use crate::apps::tenant::routes::{get_tenant, update_tenant};
 use crate::apps::user::keycloak::routes::login_keycloak;
 use crate::apps::user::routes::{
     change_password, confirm_email, create_user, delete_user, forgot_password, get_me, list_users,
     login, update_profile, update_user,
 };
-use actix_web::{HttpResponse, Responder, Scope, web};
-
-async fn health_check() -> impl Responder {
-    HttpResponse::Ok().json(serde_json::json!({
-        "status": "ok",
-        "message": "Servidor Actix Web funcionando!",
-        "timestamp": chrono::Utc::now().to_rfc3339()
-    }))
-}
+use crate::apps::wishlist::routes::{
+    add_wishlist_item, create_wishlist, delete_wishlist, delete_wishlist_item, get_saved_wishlist,
+    get_shared_wishlist, get_wishlist, list_wishlist_notifications, list_wishlists,
+    move_wishlist_item_to_cart, read_wishlist_notifications, rename_wishlist,
+    save_cart_item_for_later, share_wishlist, unshare_wishlist, update_wishlist_item,
+};
+use actix_web::{Scope, web};
 
 pub fn api_v1_scope() -> Scope {
     web::scope("/api/v1")
-        .route("/health/", web::get().to(health_check))
-        // Rotas públicas do user (sem autenticação)
-        .service(
-            web::scope("/auth")
-                .route("/register/", web::post().to(create_user))
-                .route("/login/", web::post().to(login))
-                .route("/login-keycloak/", web::post().to(login_keycloak))
-                .route("/confirm-email/{code}/", web::get().to(confirm_email))
-                .route("/forgot-password/", web::post().to(forgot_password))
-                .route("/change-password/", web::post().to(change_password)),
-        )
-        // Rota pública do orchestrator para autorização de apps
-        .service(
-            web::scope("/orchestrator")
-                .route("/authorize/{app_token}/", web::get().to(authorize_app)),
-        )
+        // Erros de extração (JSON, path, query) no mesmo envelope de AppError
+        .app_data(web::JsonConfig::default().error_handler(json_error_handler))
+        .app_data(web::PathConfig::default().error_handler(path_error_handler))
+        .app_data(web::QueryConfig::default().error_handler(query_error_handler))
         .service(
             web::scope("") // escopo vazio herda o "/api/v1"
-                .wrap(AuthMiddleware)
-                // .service(get_logs)
-                // Rotas privadas do user (com autenticação)
+                // X-Request-Id aceito ou gerado para toda a API (logs, respostas e chamadas de saída)
+                .wrap(RequestIdMiddleware)
+                // Latência por rota (http_request_duration_seconds)
+                .wrap(MetricsMiddleware)
+                // Liveness (processo de pé) e readiness (dependências verificadas)
+                .route("/health/", web::get().to(health_live))
+                .route("/health/live/", web::get().to(health_live))
+                .route("/health/ready/", web::get().to(health_ready))
+                // Rotas públicas do user (sem autenticação)
+                .service(
+                    web::scope("/auth")
+                        .wrap(RateLimitMiddleware::new(RouteGroup::Auth))
+                        .route("/register/", web::post().to(create_user))
+                        .route("/login/", web::post().to(login))
+                        .route("/login-keycloak/", web::post().to(login_keycloak))
+                        .route("/confirm-email/{code}/", web::get().to(confirm_email))
+                        .route("/forgot-password/", web::post().to(forgot_password))
+                        .route("/change-password/", web::post().to(change_password)),
+                )
+                // Arquivos do storage local (privados exigem URL assinada)
+                .route("/media/files/{key:.*}", web::get().to(serve_media_file))
+                // Vitrine pública da loja (sem autenticação, só produtos ativos)
+                .service(
+                    web::scope("/store/{tenant_slug}")
+                        .wrap(RateLimitMiddleware::new(RouteGroup::Products))
+                        .route("/products/", web::get().to(list_store_products))
+                        .route("/products/{slug}/", web::get().to(get_store_product)),
+                )
+                // Carrinho de visitante (identificado pelo token do carrinho, não pelo JWT)
                 .service(
-                    web::scope("/users")
-                        .route("/me/", web::get().to(get_me))
-                        .route("/profile/", web::patch().to(update_profile))
-                        .route("/", web::get().to(list_users))
-                        .route("/", web::patch().to(update_user))
-                        .route("/", web::delete().to(delete_user)),
+                    web::scope("/carts/guest")
+                        .wrap(RateLimitMiddleware::new(RouteGroup::Carts))
+                        .route("/", web::post().to(create_guest_cart))
+                        .route("/", web::get().to(get_guest_cart))
+                        .route("/items/{id}/", web::patch().to(update_guest_cart_item))
+                        .route("/bulk/", web::post().to(bulk_update_guest_cart))
+                        .route("/clear/", web::post().to(clear_guest_cart))
+                        .route("/revalidate/", web::post().to(revalidate_guest_cart)),
                 )
-                // Rotas privadas do orchestrator (apenas super_admin)
+                // Lista de desejos compartilhada (somente leitura, pelo token do link)
                 .service(
-                    web::scope("/apps-orchestrator")
-                        .route("/", web::get().to(list_orchestrators))
-                        .route("/", web::post().to(create_orchestrator))
-                        .route("/{id}/", web::get().to(get_orchestrator))
-                        .route("/{id}/", web::delete().to(delete_orchestrator))
-                        .route("/sync-users/", web::post().to(sync_all_users_with_app)),
+                    web::scope("/wishlists/shared")
+                        .wrap(RateLimitMiddleware::new(RouteGroup::Carts))
+                        .route("/{token}/", web::get().to(get_shared_wishlist)),
                 )
+                // Rota pública do orchestrator para autorização de apps
                 .service(
-                    web::scope("/products")
-                        .route("/", web::get().to(list_products))
-                        .route("/", web::post().to(create_product))
-                        .route("/{id}/", web::get().to(get_product))
-                        .route("/{id}/", web::put().to(update_product))
-                        .route("/{id}/", web::delete().to(delete_product)),
+                    web::scope("/orchestrator")
+                        .route("/authorize/{app_token}/", web::get().to(authorize_app)),
                 )
                 .service(
-                    web::scope("/carts")
-                        .route("/", web::get().to(get_card_by_tenant))
-                        .route("/all/", web::get().to(get_cards))
-                        .route("/", web::post().to(create_cart))
-                        .route("/add-product/", web::post().to(add_product_cart))
-                        .route("/delete-product/", web::post().to(delete_product_cart))
-                        .route("/{id}/", web::delete().to(delete_cart)),
+                    web::scope("") // escopo vazio herda o "/api/v1"
+                        .wrap(AuthMiddleware)
+                        // .service(get_logs)
+                        // Rotas privadas do user (com autenticação)
+                        .service(
+                            web::scope("/users")
+                                .route("/me/", web::get().to(get_me))
+                                .route("/profile/", web::patch().to(update_profile))
+                                .route("/avatar/", web::post().to(upload_avatar))
+                                .route("/", web::get().to(list_users))
+                                .route("/", web::patch().to(update_user))
+                                .route("/", web::delete().to(delete_user)),
+                        )
+                        // Rotas privadas do orchestrator (apenas super_admin)
+                        .service(
+                            web::scope("/apps-orchestrator")
+                                .route("/", web::get().to(list_orchestrators))
+                                .route("/", web::post().to(create_orchestrator))
+                                .route("/{id}/", web::get().to(get_orchestrator))
+                                .route("/{id}/", web::delete().to(delete_orchestrator))
+                                .route("/sync-users/", web::post().to(sync_all_users_with_app)),
+                        )
+                        // Configurações da loja (moeda base)
+                        .service(
+                            web::scope("/tenant")
+                                .route("/", web::get().to(get_tenant))
+                                .route("/", web::patch().to(update_tenant)),
+                        )
+                        // Cotações de câmbio (importação e sincronização apenas super_admin)
+                        .service(
+                            web::scope("/exchange-rates")
+                                .route("/", web::get().to(list_exchange_rates))
+                                .route("/import/", web::post().to(import_exchange_rates))
+                                .route("/sync/", web::post().to(sync_exchange_rates)),
+                        )
+                        // Comissões do marketplace (super_admin)
+                        .service(
+                            web::scope("/commissions")
+                                .route("/", web::get().to(list_commission_rates))
+                                .route("/", web::put().to(set_commission_rate))
+                                .route("/{id}/", web::delete().to(delete_commission_rate)),
+                        )
+                        // Saldo, extrato e repasses da loja; lotes de repasse (super_admin)
+                        .service(
+                            web::scope("/payouts")
+                                .route("/", web::get().to(list_seller_payouts))
+                                .route("/balance/", web::get().to(get_seller_balance))
+                                .route("/statement/", web::get().to(get_seller_statement))
+                                .route("/batches/", web::get().to(list_payout_batches))
+                                .route("/batches/", web::post().to(create_payout_batch))
+                                .route("/batches/{id}/", web::get().to(get_payout_batch))
+                                .route(
+                                    "/batches/{id}/reconcile/",
+                                    web::post().to(reconcile_payout_batch),
+                                ),
+                        )
+                        // Trilha de auditoria (super_admin ou dono do tenant)
+                        .service(
+                            web::scope("/audit-events")
+                                .route("/", web::get().to(list_audit_events)),
+                        )
+                        .service(
+                            web::scope("/products")
+                                .wrap(RateLimitMiddleware::new(RouteGroup::Products))
+                                .route("/", web::get().to(list_products))
+                                .route("/", web::post().to(create_product))
+                                .route("/by-slug/{slug}/", web::get().to(get_product_by_slug))
+                                // Importação/exportação em lote (CSV e JSONL)
+                                .route("/import/", web::post().to(import_products))
+                                .route("/import/{id}/", web::get().to(get_import_job))
+                                .route("/export/", web::get().to(export_products))
+                                .route("/{id}/", web::get().to(get_product))
+                                .route("/{id}/", web::put().to(update_product))
+                                .route("/{id}/", web::delete().to(delete_product))
+                                .route("/{id}/categories/", web::put().to(set_product_categories))
+                                // Histórico e agendamento de preços
+                                .route("/{id}/prices/", web::get().to(list_product_prices))
+                                .route("/{id}/prices/", web::post().to(schedule_product_price))
+                                .route(
+                                    "/{id}/prices/{price_id}/",
+                                    web::delete().to(cancel_product_price),
+                                )
+                                // Preços fixos em outras moedas
+                                .route(
+                                    "/{id}/currency-prices/",
+                                    web::get().to(list_product_currency_prices),
+                                )
+                                .route(
+                                    "/{id}/currency-prices/",
+                                    web::put().to(set_product_currency_prices),
+                                )
+                                .route("/{id}/images/", web::get().to(list_product_images))
+                                .route("/{id}/images/", web::post().to(add_product_image))
+                                .route("/{id}/images/order/", web::put().to(reorder_product_images))
+                                .route(
+                                    "/{id}/images/{image_id}/",
+                                    web::patch().to(update_product_image),
+                                )
+                                .route(
+                                    "/{id}/images/{image_id}/",
+                                    web::delete().to(delete_product_image),
+                                ),
+                        )
+                        // Upload de mídia (imagens com miniatura)
+                        .service(
+                            web::scope("/media")
+                                .route("/", web::post().to(upload_media))
+                                .route("/{id}/", web::get().to(get_media))
+                                .route("/{id}/", web::delete().to(delete_media)),
+                        )
+                        // Árvore de categorias do tenant
+                        .service(
+                            web::scope("/categories")
+                                .route("/", web::get().to(list_categories))
+                                .route("/", web::post().to(create_category))
+                                .route("/{id}/", web::get().to(get_category))
+                                .route("/{id}/", web::put().to(update_category))
+                                .route("/{id}/", web::delete().to(delete_category)),
+                        )
+                        .service(
+                            web::scope("/carts")
+                                .wrap(RateLimitMiddleware::new(RouteGroup::Carts))
+                                .route("/", web::get().to(get_card_by_tenant))
+                                .route("/all/", web::get().to(get_cards))
+                                .route("/all/{id}/", web::get().to(get_member_cart))
+                                .route("/", web::post().to(create_cart))
+                                .route("/add-product/", web::post().to(add_product_cart))
+                                .route("/delete-product/", web::post().to(delete_product_cart))
+                                .route("/items/{id}/", web::patch().to(update_cart_item))
+                                .route("/bulk/", web::post().to(bulk_update_cart))
+                                .route("/clear/", web::post().to(clear_cart))
+                                .route("/revalidate/", web::post().to(revalidate_cart))
+                                .route(
+                                    "/items/{id}/save-for-later/",
+                                    web::post().to(save_cart_item_for_later),
+                                )
+                                .route("/{id}/", web::delete().to(delete_cart)),
+                        )
+                        // Pedidos do comprador e vendas da loja
+                        .service(
+                            web::scope("/orders")
+                                .wrap(RateLimitMiddleware::new(RouteGroup::Carts))
+                                .route("/", web::get().to(list_orders))
+                                .route("/checkout/", web::post().to(checkout))
+                                .route("/sales/", web::get().to(list_sales))
+                                .route("/sales/returns/", web::get().to(list_seller_returns))
+                                .route(
+                                    "/sales/returns/{id}/approve/",
+                                    web::post().to(approve_return),
+                                )
+                                .route("/sales/returns/{id}/reject/", web::post().to(reject_return))
+                                .route(
+                                    "/sales/returns/{id}/receive/",
+                                    web::post().to(receive_return),
+                                )
+                                .route("/sales/{id}/", web::get().to(get_sale))
+                                .route(
+                                    "/sales/{id}/credit-notes/",
+                                    web::get().to(list_sale_credit_notes),
+                                )
+                                .route("/sales/{id}/status/", web::post().to(update_sale_status))
+                                .route("/sales/{id}/ship/", web::post().to(ship_sale))
+                                .route("/{id}/", web::get().to(get_order))
+                                .route("/{id}/cancel/", web::post().to(cancel_order))
+                                .route("/{id}/returns/", web::get().to(list_order_returns))
+                                .route("/{id}/returns/", web::post().to(create_return))
+                                .route(
+                                    "/{id}/credit-notes/",
+                                    web::get().to(list_order_credit_notes),
+                                ),
+                        )
+                        // Listas de desejos e "salvos para depois"
+                        .service(
+                            web::scope("/wishlists")
+                                .wrap(RateLimitMiddleware::new(RouteGroup::Carts))
+                                .route("/", web::get().to(list_wishlists))
+                                .route("/", web::post().to(create_wishlist))
+                                .route("/saved/", web::get().to(get_saved_wishlist))
+                                .route(
+                                    "/notifications/",
+                                    web::get().to(list_wishlist_notifications),
+                                )
+                                .route(
+                                    "/notifications/read/",
+                                    web::post().to(read_wishlist_notifications),
+                                )
+                                .route("/{id}/", web::get().to(get_wishlist))
+                                .route("/{id}/", web::patch().to(rename_wishlist))
+                                .route("/{id}/", web::delete().to(delete_wishlist))
+                                .route("/{id}/share/", web::post().to(share_wishlist))
+                                .route("/{id}/share/", web::delete().to(unshare_wishlist))
+                                .route("/{id}/items/", web::post().to(add_wishlist_item))
+                                .route(
+                                    "/{id}/items/{item_id}/",
+                                    web::patch().to(update_wishlist_item),
+                                )
+                                .route(
+                                    "/{id}/items/{item_id}/",
+                                    web::delete().to(delete_wishlist_item),
+                                )
+                                .route(
+                                    "/{id}/items/{item_id}/move-to-cart/",
+                                    web::post().to(move_wishlist_item_to_cart),
+                                ),
+                        ),
                 ),
         )
 }
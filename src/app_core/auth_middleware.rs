@@ -1,8 +1,9 @@
+use crate::app_core::app_error::AppError;
 use crate::app_core::app_model::Claims;
 use crate::app_core::init_settings::get_settings;
 use actix_web::body::BoxBody;
 use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
-use actix_web::{Error, HttpMessage, HttpResponse};
+use actix_web::{Error, HttpMessage, ResponseError};
 use futures::future::{LocalBoxFuture, Ready, ok};
 use jsonwebtoken::{DecodingKey, Validation, decode};
 use std::rc::Rc;
@@ -58,9 +59,7 @@ where
 
         if token.is_empty() {
             warn!("Tentativa de acesso sem token");
-            let response = HttpResponse::Unauthorized()
-                .body("Token não fornecido")
-                .map_into_boxed_body();
+            let response = AppError::unauthorized("Token não fornecido").error_response();
             return Box::pin(async { Ok(req.into_response(response)) });
         }
 
@@ -79,16 +78,14 @@ where
                 );
                 req.extensions_mut().insert(token_data.claims);
                 let fut = self.service.call(req);
-                Box::pin(async move { fut.await })
+                Box::pin(fut)
             }
             Err(err) => {
                 error!(
                     error = %err,
                     "Token inválido"
                 );
-                let response = HttpResponse::Unauthorized()
-                    .body("Token inválido")
-                    .map_into_boxed_body();
+                let response = AppError::unauthorized("Token inválido").error_response();
                 Box::pin(async { Ok(req.into_response(response)) })
             }
         }
@@ -0,0 +1,104 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::apps::exchange_rate::provider::configured_provider;
+use crate::apps::exchange_rate::services::ExchangeRateService;
+use crate::apps::product_bulk::commands;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Sem subcomando, sobe o servidor HTTP
+#[derive(Debug, Parser)]
+#[command(about = "API de e-commerce multi-tenant")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Importa produtos de um arquivo CSV ou JSONL (upsert por SKU ou slug)
+    ImportProducts {
+        #[arg(long)]
+        tenant: Uuid,
+        #[arg(long)]
+        file: PathBuf,
+        /// csv ou jsonl; padrão: extensão do arquivo
+        #[arg(long)]
+        format: Option<String>,
+    },
+    /// Exporta o catálogo do tenant em CSV ou JSONL
+    ExportProducts {
+        #[arg(long)]
+        tenant: Uuid,
+        #[arg(long)]
+        output: PathBuf,
+        /// csv ou jsonl; padrão: extensão do arquivo
+        #[arg(long)]
+        format: Option<String>,
+    },
+    /// Importa cotações de um CSV (base_currency,quote_currency,rate[,effective_at])
+    ImportExchangeRates {
+        #[arg(long)]
+        file: PathBuf,
+    },
+    /// Busca as cotações no provedor configurado (EXCHANGE_RATE_PROVIDER)
+    SyncExchangeRates,
+}
+
+/// Executa o subcomando e imprime o resultado
+pub async fn run(command: Command, app_state: &AppState) -> Result<(), AppError> {
+    match command {
+        Command::ImportProducts {
+            tenant,
+            file,
+            format,
+        } => {
+            let job =
+                commands::import_products(app_state, tenant, &file, format.as_deref()).await?;
+            println!(
+                "Importação {}: {} criados, {} atualizados, {} com erro (de {} linhas)",
+                job.id, job.created_count, job.updated_count, job.failed_count, job.total_rows
+            );
+            if job.failed_count > 0 {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&job.row_errors).unwrap_or_default()
+                );
+            }
+        }
+        Command::ExportProducts {
+            tenant,
+            output,
+            format,
+        } => {
+            let written =
+                commands::export_products(app_state, tenant, &output, format.as_deref()).await?;
+            println!(
+                "Catálogo exportado em {} ({} bytes)",
+                output.display(),
+                written
+            );
+        }
+        Command::ImportExchangeRates { file } => {
+            let input = tokio::fs::read_to_string(&file).await.map_err(|e| {
+                AppError::bad_request(format!("Erro ao ler {}: {}", file.display(), e))
+            })?;
+            let report = ExchangeRateService::import_csv(app_state, &input).await?;
+            println!("{} cotações importadas", report.imported);
+        }
+        Command::SyncExchangeRates => {
+            let provider = configured_provider().ok_or_else(|| {
+                AppError::bad_request(
+                    "Nenhum provedor de câmbio configurado (EXCHANGE_RATE_PROVIDER)",
+                )
+            })?;
+            let report = ExchangeRateService::sync(app_state, provider.as_ref()).await?;
+            println!(
+                "{} cotações sincronizadas (provedor {})",
+                report.imported, report.source
+            );
+        }
+    }
+    Ok(())
+}
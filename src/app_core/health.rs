@@ -0,0 +1,246 @@
+use crate::app_core::app_state::AppState;
+use crate::app_core::databases::elasticsearch::get_elastic_client;
+use crate::app_core::init_settings::get_settings;
+use crate::app_core::workers::supervisor;
+use actix_web::{HttpResponse, Responder, web};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::{BTreeMap, HashSet};
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadinessStatus {
+    /// Todas as dependências respondendo
+    Ok,
+    /// Alguma dependência opcional fora do ar (continua recebendo tráfego)
+    Degraded,
+    /// Alguma dependência obrigatória fora do ar (503)
+    Unavailable,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyCheck {
+    pub status: CheckStatus,
+    pub required: bool,
+    pub duration_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadinessResponse {
+    pub status: ReadinessStatus,
+    pub timestamp: String,
+    pub checks: BTreeMap<String, DependencyCheck>,
+}
+
+impl ReadinessResponse {
+    pub fn from_checks(checks: BTreeMap<String, DependencyCheck>) -> Self {
+        let down = |required: bool| {
+            checks
+                .values()
+                .any(|c| c.required == required && c.status == CheckStatus::Down)
+        };
+
+        let status = if down(true) {
+            ReadinessStatus::Unavailable
+        } else if down(false) {
+            ReadinessStatus::Degraded
+        } else {
+            ReadinessStatus::Ok
+        };
+
+        Self {
+            status,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            checks,
+        }
+    }
+}
+
+/// Executa uma verificação com timeout, medindo o tempo gasto
+async fn run_check<F>(required: bool, timeout: Duration, check: F) -> DependencyCheck
+where
+    F: Future<Output = Result<Option<serde_json::Value>, String>>,
+{
+    let started = Instant::now();
+    let result = match tokio::time::timeout(timeout, check).await {
+        Ok(result) => result,
+        Err(_) => Err(format!("Timeout após {}ms", timeout.as_millis())),
+    };
+    let duration_ms = started.elapsed().as_millis();
+
+    match result {
+        Ok(details) => DependencyCheck {
+            status: CheckStatus::Up,
+            required,
+            duration_ms,
+            details,
+            error: None,
+        },
+        Err(error) => DependencyCheck {
+            status: CheckStatus::Down,
+            required,
+            duration_ms,
+            details: None,
+            error: Some(error),
+        },
+    }
+}
+
+async fn check_postgres(db: &PgPool) -> Result<Option<serde_json::Value>, String> {
+    sqlx::query("SELECT 1")
+        .execute(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(Some(serde_json::json!({
+        "pool_size": db.size(),
+        "pool_idle": db.num_idle(),
+    })))
+}
+
+/// Compara as migrations embutidas no binário com as aplicadas no banco
+async fn check_migrations(db: &PgPool) -> Result<Option<serde_json::Value>, String> {
+    let applied: HashSet<i64> =
+        sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success = true")
+            .fetch_all(db)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .collect();
+
+    let pending: Vec<i64> = sqlx::migrate!("./migrations")
+        .iter()
+        .map(|m| m.version)
+        .filter(|version| !applied.contains(version))
+        .collect();
+
+    if pending.is_empty() {
+        Ok(Some(serde_json::json!({ "pending": 0 })))
+    } else {
+        Err(format!(
+            "{} migration(s) pendente(s): {:?}",
+            pending.len(),
+            pending
+        ))
+    }
+}
+
+async fn check_elasticsearch() -> Result<Option<serde_json::Value>, String> {
+    let client = get_elastic_client().map_err(|e| e.to_string())?;
+    let response = client.ping().send().await.map_err(|e| e.to_string())?;
+
+    if response.status_code().is_success() {
+        Ok(None)
+    } else {
+        Err(format!("Status {}", response.status_code()))
+    }
+}
+
+async fn check_mongo(uri: &str) -> Result<Option<serde_json::Value>, String> {
+    let client = mongodb::Client::with_uri_str(uri)
+        .await
+        .map_err(|e| e.to_string())?;
+    client
+        .database("admin")
+        .run_command(mongodb::bson::doc! { "ping": 1 }, None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(None)
+}
+
+/// Workers em background; durante o desligamento a instância deixa de receber tráfego
+fn check_workers() -> DependencyCheck {
+    let supervisor = supervisor();
+    let shutting_down = supervisor.is_shutting_down();
+
+    DependencyCheck {
+        status: if shutting_down {
+            CheckStatus::Down
+        } else {
+            CheckStatus::Up
+        },
+        required: true,
+        duration_ms: 0,
+        details: Some(serde_json::json!({ "workers": supervisor.status() })),
+        error: shutting_down.then(|| "Servidor em desligamento".to_string()),
+    }
+}
+
+/// Verifica todas as dependências em paralelo
+pub async fn readiness(app_state: &AppState) -> ReadinessResponse {
+    let settings = get_settings();
+    let timeout = Duration::from_millis(settings.health.timeout_ms);
+
+    let mongo = async {
+        match &settings.mongo {
+            Some(mongo) => Some(
+                run_check(
+                    settings.health.require_mongo,
+                    timeout,
+                    check_mongo(&mongo.uri),
+                )
+                .await,
+            ),
+            None => None,
+        }
+    };
+
+    let (postgres, migrations, elasticsearch, mongo) = tokio::join!(
+        run_check(true, timeout, check_postgres(&app_state.db)),
+        run_check(true, timeout, check_migrations(&app_state.db)),
+        run_check(
+            settings.health.require_elasticsearch,
+            timeout,
+            check_elasticsearch()
+        ),
+        mongo,
+    );
+
+    let mut checks = BTreeMap::new();
+    checks.insert("postgres".to_string(), postgres);
+    checks.insert("migrations".to_string(), migrations);
+    checks.insert("elasticsearch".to_string(), elasticsearch);
+    checks.insert("workers".to_string(), check_workers());
+    if let Some(mongo) = mongo {
+        checks.insert("mongo".to_string(), mongo);
+    }
+
+    ReadinessResponse::from_checks(checks)
+}
+
+/// GET /health/live/ - o processo está de pé (não toca em dependências)
+pub async fn health_live() -> impl Responder {
+    HttpResponse::Ok().json(serde_json::json!({
+        "status": "ok",
+        "message": "Servidor Actix Web funcionando!",
+        "timestamp": chrono::Utc::now().to_rfc3339()
+    }))
+}
+
+/// GET /health/ready/ - pronto para receber tráfego (503 se dependência obrigatória cair)
+pub async fn health_ready(app_state: web::Data<AppState>) -> impl Responder {
+    let response = readiness(&app_state).await;
+
+    match response.status {
+        ReadinessStatus::Unavailable => {
+            warn!(checks = ?response.checks, "Readiness falhou");
+            HttpResponse::ServiceUnavailable().json(response)
+        }
+        _ => HttpResponse::Ok().json(response),
+    }
+}
@@ -1,20 +1,43 @@
-use crate::app_core::settings::Settings;
+use crate::app_core::settings::{Settings, TelemetrySettings};
+use crate::app_core::telemetry::{init_tracer_provider, otel_layer};
 use once_cell::sync::OnceCell;
+use opentelemetry_sdk::trace::TracerProvider;
 use tracing::info;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{Layer, layer::SubscriberExt, util::SubscriberInitExt};
 
 static SETTINGS: OnceCell<Settings> = OnceCell::new();
 
-pub fn setup_development_logging() -> Result<(), String> {
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "rust_template=debug,tower_http=debug".into()),
+/// Configura os logs no console e, se OTEL_ENABLED=true, a exportação dos spans via OTLP.
+/// O provider retornado deve ser finalizado no shutdown para enviar os spans pendentes.
+pub fn setup_development_logging(
+    telemetry: &TelemetrySettings,
+) -> Result<Option<TracerProvider>, String> {
+    let fmt_filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| "rust_template=debug,tower_http=debug".into());
+
+    let provider = if telemetry.tracing_enabled {
+        Some(init_tracer_provider(telemetry)?)
+    } else {
+        None
+    };
+
+    // Cada método de repositório abre um span de cliente (db.system=postgresql) filho do span
+    // da requisição; as queries do sqlx entram nele como eventos
+    let otel = provider.as_ref().map(|provider| {
+        otel_layer(provider, &telemetry.service_name).with_filter(
+            tracing_subscriber::EnvFilter::new(
+                "rust_template=debug,sqlx=debug,tracing_actix_web=info",
+            ),
         )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
-    
-    Ok(())
+    });
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_filter(fmt_filter))
+        .with(otel)
+        .try_init()
+        .map_err(|e| format!("Erro ao configurar logging: {}", e))?;
+
+    Ok(provider)
 }
 
 pub fn init_settings() -> Result<(), String> {
@@ -4,6 +4,12 @@ pub mod app_model;
 pub mod app_routes;
 pub mod app_state;
 pub mod auth_middleware;
+pub mod cli;
 pub mod databases;
+pub mod health;
 pub mod init_settings;
+pub mod rate_limit;
+pub mod request_id;
 pub mod settings;
+pub mod telemetry;
+pub mod workers;
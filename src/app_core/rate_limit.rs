@@ -0,0 +1,391 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_model::Claims;
+use crate::app_core::app_state::AppState;
+use crate::app_core::init_settings::get_settings;
+use crate::app_core::settings::{RateLimitBackend, RateLimitKey, RateLimitRule};
+use crate::app_core::workers::WorkerSupervisor;
+use actix_web::body::BoxBody;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
+use actix_web::http::header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER};
+use actix_web::{Error, HttpMessage, ResponseError, web};
+use futures::future::{LocalBoxFuture, Ready, ok};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, error, instrument, warn};
+
+/// Grupos de rotas com limites independentes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteGroup {
+    Auth,
+    Products,
+    Carts,
+}
+
+impl RouteGroup {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RouteGroup::Auth => "auth",
+            RouteGroup::Products => "products",
+            RouteGroup::Carts => "carts",
+        }
+    }
+
+    fn rule(&self) -> &'static RateLimitRule {
+        let settings = &get_settings().rate_limit;
+        match self {
+            RouteGroup::Auth => &settings.auth,
+            RouteGroup::Products => &settings.products,
+            RouteGroup::Carts => &settings.carts,
+        }
+    }
+}
+
+/// Resultado da tentativa de consumir um token do bucket
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// Segundos até o bucket ficar cheio novamente
+    pub reset_after: u64,
+    /// Segundos até o próximo token disponível (apenas quando bloqueado)
+    pub retry_after: Option<u64>,
+}
+
+/// Token bucket: a capacidade é `rule.requests` e os tokens são repostos
+/// continuamente na taxa de `rule.requests / rule.period` por segundo
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenBucket {
+    pub tokens: f64,
+}
+
+impl TokenBucket {
+    pub fn full(rule: &RateLimitRule) -> Self {
+        Self {
+            tokens: rule.requests as f64,
+        }
+    }
+
+    /// Repõe os tokens referentes a `elapsed_secs` e tenta consumir um
+    pub fn take(&mut self, elapsed_secs: f64, rule: &RateLimitRule) -> RateLimitDecision {
+        let capacity = rule.requests as f64;
+        let rate = capacity / rule.period as f64;
+
+        self.tokens = (self.tokens + elapsed_secs.max(0.0) * rate).min(capacity);
+
+        let allowed = self.tokens >= 1.0;
+        if allowed {
+            self.tokens -= 1.0;
+        }
+
+        let retry_after = if allowed {
+            None
+        } else {
+            Some((((1.0 - self.tokens) / rate).ceil() as u64).max(1))
+        };
+
+        RateLimitDecision {
+            allowed,
+            limit: rule.requests,
+            remaining: self.tokens.floor() as u32,
+            reset_after: ((capacity - self.tokens) / rate).ceil() as u64,
+            retry_after,
+        }
+    }
+}
+
+// ===== BACKEND EM MEMÓRIA =====
+
+/// Quantidade de buckets a partir da qual os inativos são descartados
+const MEMORY_PRUNE_THRESHOLD: usize = 10_000;
+
+struct MemoryEntry {
+    bucket: TokenBucket,
+    last_seen: Instant,
+    period: u64,
+}
+
+static MEMORY_BUCKETS: Lazy<Mutex<HashMap<String, MemoryEntry>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn take_from_memory(key: &str, rule: &RateLimitRule) -> RateLimitDecision {
+    let mut buckets = MEMORY_BUCKETS.lock().unwrap_or_else(|e| e.into_inner());
+    let now = Instant::now();
+
+    if buckets.len() >= MEMORY_PRUNE_THRESHOLD {
+        // Um bucket parado por mais de um período já está cheio, equivale a não existir
+        buckets.retain(|_, entry| now.duration_since(entry.last_seen).as_secs() < entry.period);
+    }
+
+    let entry = buckets
+        .entry(key.to_string())
+        .or_insert_with(|| MemoryEntry {
+            bucket: TokenBucket::full(rule),
+            last_seen: now,
+            period: rule.period,
+        });
+
+    let elapsed = now.duration_since(entry.last_seen).as_secs_f64();
+    entry.last_seen = now;
+    entry.period = rule.period;
+    entry.bucket.take(elapsed, rule)
+}
+
+// ===== BACKEND POSTGRES =====
+
+/// Intervalo da limpeza de buckets no Postgres
+const BUCKET_CLEANUP_INTERVAL_SECS: u64 = 600;
+
+/// Tempo mínimo sem uso antes de um bucket ser removido
+const BUCKET_MIN_IDLE_SECS: u64 = 3600;
+
+pub struct RateLimitRepository<'a> {
+    app_state: &'a AppState,
+}
+
+impl<'a> RateLimitRepository<'a> {
+    pub fn new(app_state: &'a AppState) -> Self {
+        Self { app_state }
+    }
+
+    /// Consome um token do bucket de forma atômica (linha bloqueada durante a transação)
+    #[instrument(name = "RateLimitRepository::take", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn take(
+        &self,
+        key: &str,
+        rule: &RateLimitRule,
+    ) -> Result<RateLimitDecision, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO rate_limit_buckets (key, tokens, dt_refilled)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (key) DO NOTHING
+            "#,
+            key,
+            rule.requests as f64
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                tokens,
+                EXTRACT(EPOCH FROM (NOW() - dt_refilled))::float8 AS "elapsed!"
+            FROM rate_limit_buckets
+            WHERE key = $1
+            FOR UPDATE
+            "#,
+            key
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mut bucket = TokenBucket { tokens: row.tokens };
+        let decision = bucket.take(row.elapsed, rule);
+
+        sqlx::query!(
+            r#"
+            UPDATE rate_limit_buckets
+            SET tokens = $2, dt_refilled = NOW()
+            WHERE key = $1
+            "#,
+            key,
+            bucket.tokens
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(decision)
+    }
+
+    /// Remove buckets sem uso há mais de `max_idle_secs` segundos
+    #[instrument(name = "RateLimitRepository::delete_stale", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete_stale(&self, max_idle_secs: f64) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM rate_limit_buckets
+            WHERE dt_refilled < NOW() - make_interval(secs => $1)
+            "#,
+            max_idle_secs
+        )
+        .execute(&self.app_state.db)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+/// Worker que limpa periodicamente os buckets parados no Postgres
+pub fn spawn_bucket_cleanup(supervisor: &WorkerSupervisor, app_state: web::Data<AppState>) {
+    // Um bucket parado por mais de um período já está cheio, equivale a não existir
+    let settings = &get_settings().rate_limit;
+    let max_idle_secs = [&settings.auth, &settings.products, &settings.carts]
+        .iter()
+        .map(|rule| rule.period)
+        .fold(BUCKET_MIN_IDLE_SECS, u64::max) as f64;
+
+    supervisor.spawn_periodic(
+        "rate_limit_cleanup",
+        Duration::from_secs(BUCKET_CLEANUP_INTERVAL_SECS),
+        move || {
+            let app_state = app_state.clone();
+            async move {
+                match RateLimitRepository::new(&app_state)
+                    .delete_stale(max_idle_secs)
+                    .await
+                {
+                    Ok(removed) => debug!(removed, "Buckets de rate limit removidos"),
+                    Err(e) => error!("Erro ao limpar buckets de rate limit: {}", e),
+                }
+            }
+        },
+    );
+}
+
+// ===== MIDDLEWARE =====
+
+pub struct RateLimitMiddleware {
+    group: RouteGroup,
+}
+
+impl RateLimitMiddleware {
+    pub fn new(group: RouteGroup) -> Self {
+        Self { group }
+    }
+}
+
+impl<S> Transform<S, ServiceRequest> for RateLimitMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<BoxBody>, Error = Error> + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RateLimitMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RateLimitMiddlewareService {
+            service: Rc::new(service),
+            group: self.group,
+        })
+    }
+}
+
+pub struct RateLimitMiddlewareService<S> {
+    service: Rc<S>,
+    group: RouteGroup,
+}
+
+impl<S> Service<ServiceRequest> for RateLimitMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<BoxBody>, Error = Error> + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let settings = &get_settings().rate_limit;
+        if !settings.enabled {
+            return Box::pin(self.service.call(req));
+        }
+
+        let group = self.group;
+        let rule = group.rule();
+        let key = format!("{}:{}", group.as_str(), client_key(&req, rule.key));
+        let app_state = req.app_data::<web::Data<AppState>>().cloned();
+        let backend = settings.backend;
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let decision = match (backend, app_state) {
+                (RateLimitBackend::Postgres, Some(app_state)) => {
+                    match RateLimitRepository::new(&app_state).take(&key, rule).await {
+                        Ok(decision) => Some(decision),
+                        Err(err) => {
+                            // Falha no backend não deve derrubar a API: a requisição segue sem limite
+                            error!(error = %err, key = %key, "Erro ao consultar rate limit");
+                            None
+                        }
+                    }
+                }
+                (RateLimitBackend::Postgres, None) => {
+                    error!("AppState indisponível para o rate limit no Postgres");
+                    None
+                }
+                (RateLimitBackend::Memory, _) => Some(take_from_memory(&key, rule)),
+            };
+
+            let Some(decision) = decision else {
+                return service.call(req).await;
+            };
+
+            if !decision.allowed {
+                warn!(key = %key, "Limite de requisições excedido");
+                let mut response = AppError::too_many_requests(
+                    "Limite de requisições excedido. Tente novamente mais tarde",
+                )
+                .error_response();
+                insert_headers(response.headers_mut(), &decision);
+                return Ok(req.into_response(response));
+            }
+
+            let mut res = service.call(req).await?;
+            insert_headers(res.headers_mut(), &decision);
+            Ok(res)
+        })
+    }
+}
+
+/// Identifica o cliente conforme a chave configurada; sem token, cai para o IP
+fn client_key(req: &ServiceRequest, key: RateLimitKey) -> String {
+    let identity = {
+        let extensions = req.extensions();
+        extensions.get::<Claims>().and_then(|claims| match key {
+            RateLimitKey::Ip => None,
+            RateLimitKey::User => Some(format!("user:{}", claims.sub)),
+            RateLimitKey::Tenant => Some(format!("tenant:{}", claims.tenant_id)),
+        })
+    };
+
+    identity.unwrap_or_else(|| format!("ip:{}", client_ip(req)))
+}
+
+fn client_ip(req: &ServiceRequest) -> String {
+    if get_settings().rate_limit.trust_proxy
+        && let Some(ip) = req.connection_info().realip_remote_addr()
+    {
+        return ip.to_string();
+    }
+
+    req.peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn insert_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    let values = [
+        ("ratelimit-limit", decision.limit as u64),
+        ("ratelimit-remaining", decision.remaining as u64),
+        ("ratelimit-reset", decision.reset_after),
+    ];
+
+    for (name, value) in values {
+        headers.insert(HeaderName::from_static(name), HeaderValue::from(value));
+    }
+
+    if let Some(retry_after) = decision.retry_after {
+        headers.insert(RETRY_AFTER, HeaderValue::from(retry_after));
+    }
+}
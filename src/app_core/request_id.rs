@@ -0,0 +1,111 @@
+use actix_web::body::BoxBody;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::{Error, HttpMessage};
+use futures::future::{LocalBoxFuture, Ready, ok};
+use std::rc::Rc;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Tamanho máximo aceito para um X-Request-Id vindo do cliente
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Id da requisição em andamento (disponível em handlers, services e no AppError)
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Id da requisição, também disponível nas extensions do request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// Aceita o X-Request-Id do cliente apenas se for curto e com caracteres seguros para logs
+pub fn sanitize_request_id(value: Option<&str>) -> Option<String> {
+    let value = value?.trim();
+    let valid = !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+
+    valid.then(|| value.to_string())
+}
+
+/// Encaminha o X-Request-Id da requisição atual nas chamadas HTTP de saída
+pub trait RequestIdExt {
+    fn with_request_id(self) -> Self;
+}
+
+impl RequestIdExt for reqwest::RequestBuilder {
+    fn with_request_id(self) -> Self {
+        match current_request_id() {
+            Some(id) => self.header(REQUEST_ID_HEADER, id),
+            None => self,
+        }
+    }
+}
+
+pub struct RequestIdMiddleware;
+
+impl<S> Transform<S, ServiceRequest> for RequestIdMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<BoxBody>, Error = Error> + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequestIdMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RequestIdMiddlewareService {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct RequestIdMiddlewareService<S> {
+    service: Rc<S>,
+}
+
+impl<S> Service<ServiceRequest> for RequestIdMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<BoxBody>, Error = Error> + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let request_id = sanitize_request_id(
+            req.headers()
+                .get(REQUEST_ID_HEADER)
+                .and_then(|h| h.to_str().ok()),
+        )
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+        // Sobrescreve o id gerado pelo TracingLogger no span raiz
+        tracing::Span::current().record("request_id", request_id.as_str());
+        req.extensions_mut().insert(RequestId(request_id.clone()));
+
+        // Middlewares internos podem responder já no `call` (ex: AuthMiddleware),
+        // por isso o id também precisa estar disponível de forma síncrona
+        let fut = REQUEST_ID.sync_scope(request_id.clone(), || self.service.call(req));
+
+        Box::pin(REQUEST_ID.scope(request_id.clone(), async move {
+            let mut res = fut.await?;
+            if let Ok(value) = HeaderValue::from_str(&request_id) {
+                res.headers_mut()
+                    .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+            }
+            Ok(res)
+        }))
+    }
+}
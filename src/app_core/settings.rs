@@ -1,3 +1,4 @@
+use bigdecimal::BigDecimal;
 use serde::Deserialize;
 use std::env;
 use std::net::IpAddr;
@@ -36,6 +37,13 @@ pub struct ServerSettings {
     pub host: IpAddr,
     #[validate(range(min = 1, max = 65535, message = "Porta deve estar entre 1 e 65535"))]
     pub port: u16,
+    /// Prazo (segundos) para requisições e workers terminarem após SIGTERM
+    #[validate(range(
+        min = 1,
+        max = 300,
+        message = "SERVER_SHUTDOWN_TIMEOUT deve estar entre 1 e 300 segundos"
+    ))]
+    pub shutdown_timeout_secs: u64,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -45,6 +53,340 @@ pub struct ElasticsearchSettings {
     pub index_prefix: String, // novo campo
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+pub enum RateLimitBackend {
+    Memory,
+    Postgres,
+}
+
+impl FromStr for RateLimitBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "memory" => Ok(RateLimitBackend::Memory),
+            "postgres" => Ok(RateLimitBackend::Postgres),
+            _ => Err(format!("Backend de rate limit inválido: {}", s)),
+        }
+    }
+}
+
+/// Identidade usada para agrupar as requisições de um mesmo cliente
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+pub enum RateLimitKey {
+    Ip,
+    User,
+    Tenant,
+}
+
+impl FromStr for RateLimitKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ip" => Ok(RateLimitKey::Ip),
+            "user" => Ok(RateLimitKey::User),
+            "tenant" => Ok(RateLimitKey::Tenant),
+            _ => Err(format!("Chave de rate limit inválida: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct RateLimitRule {
+    #[validate(range(min = 1, message = "O limite de requisições deve ser maior que zero"))]
+    pub requests: u32,
+    #[validate(range(min = 1, message = "O período do rate limit deve ser maior que zero"))]
+    pub period: u64,
+    pub key: RateLimitKey,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct RateLimitSettings {
+    pub enabled: bool,
+    pub backend: RateLimitBackend,
+    /// Usa X-Forwarded-For/Forwarded para descobrir o IP (apenas atrás de proxy confiável)
+    pub trust_proxy: bool,
+    #[validate]
+    pub auth: RateLimitRule,
+    #[validate]
+    pub products: RateLimitRule,
+    #[validate]
+    pub carts: RateLimitRule,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct TelemetrySettings {
+    /// Exporta spans via OTLP (HTTP/protobuf)
+    pub tracing_enabled: bool,
+    #[validate(url(message = "OTEL_EXPORTER_OTLP_ENDPOINT deve ser uma URL válida"))]
+    pub otlp_endpoint: String,
+    pub service_name: String,
+    /// Expõe /metrics no formato Prometheus
+    pub metrics_enabled: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MongoSettings {
+    pub uri: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct HealthSettings {
+    /// Tempo máximo de cada verificação do /health/ready
+    #[validate(range(
+        min = 1,
+        max = 30000,
+        message = "HEALTH_CHECK_TIMEOUT_MS deve estar entre 1 e 30000"
+    ))]
+    pub timeout_ms: u64,
+    /// Dependências opcionais que derrubam a readiness (503) quando fora do ar
+    pub require_elasticsearch: bool,
+    pub require_mongo: bool,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+pub enum StorageBackendKind {
+    Local,
+    S3,
+}
+
+impl FromStr for StorageBackendKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "local" => Ok(StorageBackendKind::Local),
+            "s3" => Ok(StorageBackendKind::S3),
+            _ => Err(format!("Backend de storage inválido: {}", s)),
+        }
+    }
+}
+
+/// Bucket S3 ou compatível (MinIO, R2...) quando `endpoint` é informado
+#[derive(Debug, Clone, Deserialize)]
+pub struct S3Settings {
+    pub bucket: String,
+    pub region: String,
+    pub endpoint: Option<String>,
+    pub force_path_style: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct MediaSettings {
+    pub backend: StorageBackendKind,
+    /// Diretório dos arquivos no backend local
+    pub local_root: String,
+    /// Base das URLs públicas (rota /media/files/ no backend local, CDN/bucket no S3)
+    #[validate(url(message = "MEDIA_PUBLIC_BASE_URL deve ser uma URL válida"))]
+    pub public_base_url: String,
+    #[validate(length(
+        min = 32,
+        message = "MEDIA_SIGNING_SECRET deve ter pelo menos 32 caracteres"
+    ))]
+    pub signing_secret: String,
+    /// Validade das URLs assinadas de arquivos privados
+    #[validate(range(
+        min = 60,
+        max = 604800,
+        message = "MEDIA_URL_TTL deve estar entre 60 e 604800 segundos"
+    ))]
+    pub url_ttl_secs: u64,
+    #[validate(range(
+        min = 1024,
+        max = 104857600,
+        message = "MEDIA_MAX_UPLOAD_BYTES deve estar entre 1KB e 100MB"
+    ))]
+    pub max_upload_bytes: usize,
+    /// Maior lado (px) das miniaturas geradas
+    #[validate(range(
+        min = 32,
+        max = 2048,
+        message = "MEDIA_THUMBNAIL_SIZE deve estar entre 32 e 2048"
+    ))]
+    pub thumbnail_size: u32,
+    /// Obrigatório quando MEDIA_STORAGE_BACKEND=s3
+    pub s3: Option<S3Settings>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ProductImportSettings {
+    #[validate(range(
+        min = 1024,
+        max = 524288000,
+        message = "PRODUCT_IMPORT_MAX_BYTES deve estar entre 1KB e 500MB"
+    ))]
+    pub max_bytes: usize,
+    #[validate(range(
+        min = 1,
+        max = 1000000,
+        message = "PRODUCT_IMPORT_MAX_ROWS deve estar entre 1 e 1000000"
+    ))]
+    pub max_rows: usize,
+    /// Arquivos com até este número de linhas são importados na própria requisição;
+    /// acima disso viram um job em background
+    #[validate(range(
+        max = 10000,
+        message = "PRODUCT_IMPORT_SYNC_ROWS deve ser no máximo 10000"
+    ))]
+    pub sync_max_rows: usize,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+pub enum ExchangeRateProviderKind {
+    /// Cotações só entram por importação
+    None,
+    /// Cotações fixas definidas em EXCHANGE_RATES_FIXED
+    Fixed,
+}
+
+impl FromStr for ExchangeRateProviderKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(ExchangeRateProviderKind::None),
+            "fixed" => Ok(ExchangeRateProviderKind::Fixed),
+            _ => Err(format!("Provedor de câmbio inválido: {}", s)),
+        }
+    }
+}
+
+/// Cotação fixa "USD:BRL=5.43" (1 USD = 5.43 BRL)
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct FixedExchangeRate {
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub rate: BigDecimal,
+}
+
+impl FromStr for FixedExchangeRate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Cotação inválida em EXCHANGE_RATES_FIXED: {}", s);
+        let (pair, rate) = s.trim().split_once('=').ok_or_else(invalid)?;
+        let (base, quote) = pair.split_once(':').ok_or_else(invalid)?;
+        let is_code = |code: &str| code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic());
+        if !is_code(base.trim()) || !is_code(quote.trim()) {
+            return Err(invalid());
+        }
+        let rate = BigDecimal::from_str(rate.trim()).map_err(|_| invalid())?;
+        if rate <= BigDecimal::from(0) {
+            return Err(invalid());
+        }
+
+        Ok(FixedExchangeRate {
+            base_currency: base.trim().to_ascii_uppercase(),
+            quote_currency: quote.trim().to_ascii_uppercase(),
+            rate,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurrencySettings {
+    pub exchange_rate_provider: ExchangeRateProviderKind,
+    /// Usadas quando EXCHANGE_RATE_PROVIDER=fixed
+    pub fixed_rates: Vec<FixedExchangeRate>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+pub enum PaymentProviderKind {
+    /// Reembolso feito pela loja fora do sistema; só fica registrado
+    Manual,
+}
+
+impl FromStr for PaymentProviderKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "manual" => Ok(PaymentProviderKind::Manual),
+            _ => Err(format!("Provedor de pagamento inválido: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaymentSettings {
+    pub provider: PaymentProviderKind,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct MarketplaceSettings {
+    /// Comissão da plataforma em pontos-base (1000 = 10%) quando não há regra
+    /// para a loja ou para a categoria do produto
+    #[validate(range(
+        min = 0,
+        max = 10000,
+        message = "MARKETPLACE_COMMISSION_BPS deve estar entre 0 e 10000"
+    ))]
+    pub default_commission_bps: i32,
+}
+
+/// O que fazer quando o produto do carrinho de visitante já está no carrinho da conta
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+pub enum CartMergeQuantityRule {
+    /// Soma as quantidades
+    Sum,
+    /// Fica a maior das duas
+    Max,
+    /// Mantém a linha da conta
+    KeepUser,
+    /// Substitui pela linha do visitante
+    KeepGuest,
+}
+
+impl FromStr for CartMergeQuantityRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sum" => Ok(CartMergeQuantityRule::Sum),
+            "max" => Ok(CartMergeQuantityRule::Max),
+            "keep_user" => Ok(CartMergeQuantityRule::KeepUser),
+            "keep_guest" => Ok(CartMergeQuantityRule::KeepGuest),
+            _ => Err(format!("Regra de quantidade inválida: {}", s)),
+        }
+    }
+}
+
+/// O que fazer quando a quantidade resultante passa do estoque
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+pub enum CartMergeStockRule {
+    /// Limita ao estoque disponível
+    Clamp,
+    /// Ignora a linha do visitante (a da conta fica como está)
+    Skip,
+}
+
+impl FromStr for CartMergeStockRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "clamp" => Ok(CartMergeStockRule::Clamp),
+            "skip" => Ok(CartMergeStockRule::Skip),
+            _ => Err(format!("Regra de estoque inválida: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct CartSettings {
+    #[validate(range(
+        min = 60,
+        max = 31536000,
+        message = "CART_GUEST_TOKEN_TTL deve estar entre 60 segundos e 1 ano"
+    ))]
+    pub guest_token_ttl_secs: u64,
+    /// Moeda do carrinho de visitante criado sem moeda explícita
+    pub guest_currency: String,
+    pub merge_quantity_rule: CartMergeQuantityRule,
+    pub merge_stock_rule: CartMergeStockRule,
+}
+
 #[derive(Debug, Clone, Deserialize, Validate)]
 pub struct Settings {
     pub elasticsearch: ElasticsearchSettings,
@@ -54,6 +396,24 @@ pub struct Settings {
     pub jwt: JwtSettings,
     #[validate]
     pub server: ServerSettings,
+    #[validate]
+    pub rate_limit: RateLimitSettings,
+    #[validate]
+    pub telemetry: TelemetrySettings,
+    #[validate]
+    pub health: HealthSettings,
+    #[validate]
+    pub media: MediaSettings,
+    #[validate]
+    pub product_import: ProductImportSettings,
+    pub currency: CurrencySettings,
+    #[validate]
+    pub cart: CartSettings,
+    pub payment: PaymentSettings,
+    #[validate]
+    pub marketplace: MarketplaceSettings,
+    /// Configurado apenas quando MONGO_URI está definida
+    pub mongo: Option<MongoSettings>,
     pub environment: Environment,
 }
 
@@ -77,6 +437,124 @@ impl FromStr for Environment {
     }
 }
 
+/// Lê uma regra de rate limit no formato RATE_LIMIT_{GRUPO}_{REQUESTS|PERIOD|KEY},
+/// usando RATE_LIMIT_REQUESTS/RATE_LIMIT_PERIOD como padrão
+fn load_rate_limit_rule(group: &str, default_key: &str) -> Result<RateLimitRule, String> {
+    let requests = env::var(format!("RATE_LIMIT_{}_REQUESTS", group))
+        .or_else(|_| env::var("RATE_LIMIT_REQUESTS"))
+        .unwrap_or_else(|_| "100".to_string())
+        .parse()
+        .map_err(|_| format!("RATE_LIMIT_{}_REQUESTS deve ser um número", group))?;
+    let period = env::var(format!("RATE_LIMIT_{}_PERIOD", group))
+        .or_else(|_| env::var("RATE_LIMIT_PERIOD"))
+        .unwrap_or_else(|_| "60".to_string())
+        .parse()
+        .map_err(|_| format!("RATE_LIMIT_{}_PERIOD deve ser um número", group))?;
+    let key = env::var(format!("RATE_LIMIT_{}_KEY", group))
+        .unwrap_or_else(|_| default_key.to_string())
+        .parse()?;
+
+    Ok(RateLimitRule {
+        requests,
+        period,
+        key,
+    })
+}
+
+fn load_media_settings(jwt_secret: &str) -> Result<MediaSettings, String> {
+    let backend: StorageBackendKind = env::var("MEDIA_STORAGE_BACKEND")
+        .unwrap_or_else(|_| "local".to_string())
+        .parse()?;
+
+    let s3 = match backend {
+        StorageBackendKind::S3 => Some(S3Settings {
+            bucket: env::var("S3_BUCKET").map_err(|_| "S3_BUCKET não definida")?,
+            region: env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+            endpoint: env::var("S3_ENDPOINT")
+                .ok()
+                .filter(|endpoint| !endpoint.trim().is_empty()),
+            force_path_style: env::var("S3_FORCE_PATH_STYLE")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .map_err(|_| "S3_FORCE_PATH_STYLE deve ser true ou false")?,
+        }),
+        StorageBackendKind::Local => None,
+    };
+
+    Ok(MediaSettings {
+        backend,
+        local_root: env::var("MEDIA_LOCAL_ROOT").unwrap_or_else(|_| "./media".to_string()),
+        public_base_url: env::var("MEDIA_PUBLIC_BASE_URL")
+            .unwrap_or_else(|_| "http://localhost:8080/api/v1/media/files".to_string())
+            .trim_end_matches('/')
+            .to_string(),
+        // Sem segredo próprio, assina com o JWT_SECRET
+        signing_secret: env::var("MEDIA_SIGNING_SECRET")
+            .ok()
+            .filter(|secret| !secret.is_empty())
+            .unwrap_or_else(|| jwt_secret.to_string()),
+        url_ttl_secs: env::var("MEDIA_URL_TTL")
+            .unwrap_or_else(|_| "900".to_string())
+            .parse()
+            .map_err(|_| "MEDIA_URL_TTL deve ser um número")?,
+        max_upload_bytes: env::var("MEDIA_MAX_UPLOAD_BYTES")
+            .unwrap_or_else(|_| "10485760".to_string())
+            .parse()
+            .map_err(|_| "MEDIA_MAX_UPLOAD_BYTES deve ser um número")?,
+        thumbnail_size: env::var("MEDIA_THUMBNAIL_SIZE")
+            .unwrap_or_else(|_| "320".to_string())
+            .parse()
+            .map_err(|_| "MEDIA_THUMBNAIL_SIZE deve ser um número")?,
+        s3,
+    })
+}
+
+fn load_currency_settings() -> Result<CurrencySettings, String> {
+    let exchange_rate_provider: ExchangeRateProviderKind = env::var("EXCHANGE_RATE_PROVIDER")
+        .unwrap_or_else(|_| "none".to_string())
+        .parse()?;
+
+    let fixed_rates = env::var("EXCHANGE_RATES_FIXED")
+        .unwrap_or_default()
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<FixedExchangeRate>, String>>()?;
+
+    if exchange_rate_provider == ExchangeRateProviderKind::Fixed && fixed_rates.is_empty() {
+        return Err("EXCHANGE_RATES_FIXED não definida para o provedor fixed".to_string());
+    }
+
+    Ok(CurrencySettings {
+        exchange_rate_provider,
+        fixed_rates,
+    })
+}
+
+fn load_cart_settings() -> Result<CartSettings, String> {
+    let guest_currency = env::var("CART_GUEST_CURRENCY")
+        .unwrap_or_else(|_| "BRL".to_string())
+        .trim()
+        .to_ascii_uppercase();
+    if guest_currency.len() != 3 || !guest_currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(format!("CART_GUEST_CURRENCY inválida: {}", guest_currency));
+    }
+
+    Ok(CartSettings {
+        guest_token_ttl_secs: env::var("CART_GUEST_TOKEN_TTL")
+            .unwrap_or_else(|_| "2592000".to_string())
+            .parse()
+            .map_err(|_| "CART_GUEST_TOKEN_TTL deve ser um número")?,
+        guest_currency,
+        merge_quantity_rule: env::var("CART_MERGE_QUANTITY_RULE")
+            .unwrap_or_else(|_| "sum".to_string())
+            .parse()?,
+        merge_stock_rule: env::var("CART_MERGE_STOCK_RULE")
+            .unwrap_or_else(|_| "clamp".to_string())
+            .parse()?,
+    })
+}
+
 fn validate_ip(ip: &IpAddr) -> Result<(), validator::ValidationError> {
     if ip.is_unspecified() {
         let mut err = validator::ValidationError::new("invalid_ip");
@@ -93,6 +571,8 @@ impl Settings {
             .unwrap_or_else(|_| "development".to_string())
             .parse()?;
 
+        let jwt_secret = env::var("JWT_SECRET").map_err(|_| "JWT_SECRET não definida")?;
+
         let settings = Settings {
             elasticsearch: ElasticsearchSettings {
                 url: env::var("ELASTICSEARCH_URL").map_err(|_| "ELASTICSEARCH_URL não definida")?,
@@ -108,7 +588,7 @@ impl Settings {
                 test_url: env::var("DATABASE_URL_TEST").ok(),
             },
             jwt: JwtSettings {
-                secret: env::var("JWT_SECRET").map_err(|_| "JWT_SECRET não definida")?,
+                secret: jwt_secret.clone(),
                 expires_in: env::var("JWT_EXPIRES_IN")
                     .unwrap_or_else(|_| "86400".to_string())
                     .parse()
@@ -123,7 +603,87 @@ impl Settings {
                     .unwrap_or_else(|_| "8080".to_string())
                     .parse()
                     .map_err(|_| "SERVER_PORT deve ser um número")?,
+                shutdown_timeout_secs: env::var("SERVER_SHUTDOWN_TIMEOUT")
+                    .unwrap_or_else(|_| "30".to_string())
+                    .parse()
+                    .map_err(|_| "SERVER_SHUTDOWN_TIMEOUT deve ser um número")?,
+            },
+            rate_limit: RateLimitSettings {
+                enabled: env::var("RATE_LIMIT_ENABLED")
+                    .unwrap_or_else(|_| "true".to_string())
+                    .parse()
+                    .map_err(|_| "RATE_LIMIT_ENABLED deve ser true ou false")?,
+                backend: env::var("RATE_LIMIT_BACKEND")
+                    .unwrap_or_else(|_| "memory".to_string())
+                    .parse()?,
+                trust_proxy: env::var("RATE_LIMIT_TRUST_PROXY")
+                    .unwrap_or_else(|_| "false".to_string())
+                    .parse()
+                    .map_err(|_| "RATE_LIMIT_TRUST_PROXY deve ser true ou false")?,
+                auth: load_rate_limit_rule("AUTH", "ip")?,
+                products: load_rate_limit_rule("PRODUCTS", "tenant")?,
+                carts: load_rate_limit_rule("CARTS", "user")?,
+            },
+            telemetry: TelemetrySettings {
+                tracing_enabled: env::var("OTEL_ENABLED")
+                    .unwrap_or_else(|_| "false".to_string())
+                    .parse()
+                    .map_err(|_| "OTEL_ENABLED deve ser true ou false")?,
+                otlp_endpoint: env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+                    .unwrap_or_else(|_| "http://localhost:4318/v1/traces".to_string()),
+                service_name: env::var("OTEL_SERVICE_NAME")
+                    .unwrap_or_else(|_| "rust_ecommerce".to_string()),
+                metrics_enabled: env::var("METRICS_ENABLED")
+                    .unwrap_or_else(|_| "true".to_string())
+                    .parse()
+                    .map_err(|_| "METRICS_ENABLED deve ser true ou false")?,
+            },
+            health: HealthSettings {
+                timeout_ms: env::var("HEALTH_CHECK_TIMEOUT_MS")
+                    .unwrap_or_else(|_| "2000".to_string())
+                    .parse()
+                    .map_err(|_| "HEALTH_CHECK_TIMEOUT_MS deve ser um número")?,
+                require_elasticsearch: env::var("HEALTH_REQUIRE_ELASTICSEARCH")
+                    .unwrap_or_else(|_| "false".to_string())
+                    .parse()
+                    .map_err(|_| "HEALTH_REQUIRE_ELASTICSEARCH deve ser true ou false")?,
+                require_mongo: env::var("HEALTH_REQUIRE_MONGO")
+                    .unwrap_or_else(|_| "false".to_string())
+                    .parse()
+                    .map_err(|_| "HEALTH_REQUIRE_MONGO deve ser true ou false")?,
+            },
+            media: load_media_settings(&jwt_secret)?,
+            product_import: ProductImportSettings {
+                max_bytes: env::var("PRODUCT_IMPORT_MAX_BYTES")
+                    .unwrap_or_else(|_| "20971520".to_string())
+                    .parse()
+                    .map_err(|_| "PRODUCT_IMPORT_MAX_BYTES deve ser um número")?,
+                max_rows: env::var("PRODUCT_IMPORT_MAX_ROWS")
+                    .unwrap_or_else(|_| "50000".to_string())
+                    .parse()
+                    .map_err(|_| "PRODUCT_IMPORT_MAX_ROWS deve ser um número")?,
+                sync_max_rows: env::var("PRODUCT_IMPORT_SYNC_ROWS")
+                    .unwrap_or_else(|_| "100".to_string())
+                    .parse()
+                    .map_err(|_| "PRODUCT_IMPORT_SYNC_ROWS deve ser um número")?,
+            },
+            currency: load_currency_settings()?,
+            cart: load_cart_settings()?,
+            payment: PaymentSettings {
+                provider: env::var("PAYMENT_PROVIDER")
+                    .unwrap_or_else(|_| "manual".to_string())
+                    .parse()?,
+            },
+            marketplace: MarketplaceSettings {
+                default_commission_bps: env::var("MARKETPLACE_COMMISSION_BPS")
+                    .unwrap_or_else(|_| "1000".to_string())
+                    .parse()
+                    .map_err(|_| "MARKETPLACE_COMMISSION_BPS deve ser um número")?,
             },
+            mongo: env::var("MONGO_URI")
+                .ok()
+                .filter(|uri| !uri.trim().is_empty())
+                .map(|uri| MongoSettings { uri }),
             environment,
         };
 
@@ -0,0 +1,295 @@
+use crate::app_core::app_state::AppState;
+use crate::app_core::init_settings::get_settings;
+use crate::app_core::settings::TelemetrySettings;
+use actix_web::body::BoxBody;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
+use actix_web::{Error, HttpResponse, Responder, web};
+use futures::future::{LocalBoxFuture, Ready, ok};
+use once_cell::sync::Lazy;
+use opentelemetry::KeyValue;
+use opentelemetry::global;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::TracerProvider;
+use opentelemetry_sdk::{Resource, runtime};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Instant;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+// ===== TRACING (OTLP) =====
+
+/// Cria o provider OTLP e registra o propagador W3C (traceparent) global
+pub fn init_tracer_provider(settings: &TelemetrySettings) -> Result<TracerProvider, String> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(settings.otlp_endpoint.clone())
+        .build()
+        .map_err(|e| format!("Erro ao criar exporter OTLP: {}", e))?;
+
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new(vec![KeyValue::new(
+            "service.name",
+            settings.service_name.clone(),
+        )]))
+        .build();
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(provider.clone());
+
+    Ok(provider)
+}
+
+/// Layer do tracing que exporta os spans (handlers, acesso ao banco, chamadas de saída)
+pub fn otel_layer<S>(
+    provider: &TracerProvider,
+    service_name: &str,
+) -> tracing_opentelemetry::OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer(service_name.to_string()))
+}
+
+/// Propaga o contexto do trace atual (header traceparent) nas chamadas HTTP de saída
+pub trait TraceContextPropagationExt {
+    fn with_trace_context(self) -> Self;
+}
+
+impl TraceContextPropagationExt for reqwest::RequestBuilder {
+    fn with_trace_context(self) -> Self {
+        let context = tracing::Span::current().context();
+        let mut headers: HashMap<String, String> = HashMap::new();
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&context, &mut headers)
+        });
+
+        headers
+            .into_iter()
+            .fold(self, |builder, (name, value)| builder.header(name, value))
+    }
+}
+
+// ===== MÉTRICAS (PROMETHEUS) =====
+
+pub struct Metrics {
+    pub registry: Registry,
+    pub http_request_duration: HistogramVec,
+    pub db_pool_connections: IntGaugeVec,
+    pub orchestrator_sync_total: IntCounterVec,
+    pub cart_operations_total: IntCounterVec,
+    pub order_operations_total: IntCounterVec,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Latência das requisições HTTP por rota",
+            ),
+            &["method", "route", "status"],
+        )
+        .expect("métrica http_request_duration_seconds inválida");
+
+        let db_pool_connections = IntGaugeVec::new(
+            Opts::new(
+                "db_pool_connections",
+                "Conexões do pool do Postgres por estado",
+            ),
+            &["state"],
+        )
+        .expect("métrica db_pool_connections inválida");
+
+        let orchestrator_sync_total = IntCounterVec::new(
+            Opts::new(
+                "orchestrator_sync_total",
+                "Sincronizações de usuários com orchestrators",
+            ),
+            &["app_name", "result"],
+        )
+        .expect("métrica orchestrator_sync_total inválida");
+
+        let cart_operations_total = IntCounterVec::new(
+            Opts::new("cart_operations_total", "Operações de carrinho"),
+            &["operation"],
+        )
+        .expect("métrica cart_operations_total inválida");
+
+        let order_operations_total = IntCounterVec::new(
+            Opts::new("order_operations_total", "Operações de pedidos"),
+            &["operation"],
+        )
+        .expect("métrica order_operations_total inválida");
+
+        for collector in [
+            Box::new(http_request_duration.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(db_pool_connections.clone()),
+            Box::new(orchestrator_sync_total.clone()),
+            Box::new(cart_operations_total.clone()),
+            Box::new(order_operations_total.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("métrica registrada em duplicidade");
+        }
+
+        Self {
+            registry,
+            http_request_duration,
+            db_pool_connections,
+            orchestrator_sync_total,
+            cart_operations_total,
+            order_operations_total,
+        }
+    }
+
+    /// Atualiza os gauges do pool antes de cada coleta
+    pub fn observe_db_pool(&self, app_state: &AppState) {
+        let size = app_state.db.size() as i64;
+        let idle = app_state.db.num_idle() as i64;
+
+        self.db_pool_connections
+            .with_label_values(&["max"])
+            .set(get_settings().database.max_connections as i64);
+        self.db_pool_connections
+            .with_label_values(&["open"])
+            .set(size);
+        self.db_pool_connections
+            .with_label_values(&["idle"])
+            .set(idle);
+        self.db_pool_connections
+            .with_label_values(&["in_use"])
+            .set((size - idle).max(0));
+    }
+
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        let encoder = TextEncoder::new();
+        if let Err(e) = encoder.encode(&self.registry.gather(), &mut buffer) {
+            tracing::error!("Erro ao serializar métricas: {}", e);
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+static METRICS: Lazy<Metrics> = Lazy::new(Metrics::new);
+
+pub fn metrics() -> &'static Metrics {
+    &METRICS
+}
+
+fn metrics_enabled() -> bool {
+    get_settings().telemetry.metrics_enabled
+}
+
+/// Incrementa um contador de negócio (no-op quando as métricas estão desligadas)
+pub fn record_cart_operation(operation: &str) {
+    if metrics_enabled() {
+        metrics()
+            .cart_operations_total
+            .with_label_values(&[operation])
+            .inc();
+    }
+}
+
+pub fn record_order_operation(operation: &str) {
+    if metrics_enabled() {
+        metrics()
+            .order_operations_total
+            .with_label_values(&[operation])
+            .inc();
+    }
+}
+
+pub fn record_orchestrator_sync(app_name: &str, success: bool) {
+    if metrics_enabled() {
+        let result = if success { "success" } else { "failure" };
+        metrics()
+            .orchestrator_sync_total
+            .with_label_values(&[app_name, result])
+            .inc();
+    }
+}
+
+/// GET /metrics
+pub async fn metrics_handler(app_state: web::Data<AppState>) -> impl Responder {
+    if !metrics_enabled() {
+        return HttpResponse::NotFound().finish();
+    }
+
+    let metrics = metrics();
+    metrics.observe_db_pool(&app_state);
+
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(metrics.render())
+}
+
+/// Mede a latência de cada requisição usando o padrão da rota (ex: /api/v1/products/{id}/)
+pub struct MetricsMiddleware;
+
+impl<S> Transform<S, ServiceRequest> for MetricsMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<BoxBody>, Error = Error> + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = MetricsMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(MetricsMiddlewareService {
+            service: Rc::new(service),
+        })
+    }
+}
+
+pub struct MetricsMiddlewareService<S> {
+    service: Rc<S>,
+}
+
+impl<S> Service<ServiceRequest> for MetricsMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<BoxBody>, Error = Error> + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        if !metrics_enabled() {
+            return Box::pin(self.service.call(req));
+        }
+
+        let started = Instant::now();
+        let method = req.method().to_string();
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let res = fut.await?;
+            // Rotas não encontradas ficam agrupadas para não explodir a cardinalidade
+            let route = res
+                .request()
+                .match_pattern()
+                .unwrap_or_else(|| "unmatched".to_string());
+
+            metrics()
+                .http_request_duration
+                .with_label_values(&[&method, &route, res.status().as_str()])
+                .observe(started.elapsed().as_secs_f64());
+
+            Ok(res)
+        })
+    }
+}
@@ -0,0 +1,269 @@
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{error, info, warn};
+
+/// Sinal de desligamento entregue a cada worker
+#[derive(Clone)]
+pub struct ShutdownSignal {
+    receiver: watch::Receiver<bool>,
+    deadline: watch::Receiver<Option<Instant>>,
+}
+
+impl ShutdownSignal {
+    /// Para workers em lote checarem entre um item e outro
+    pub fn is_shutdown(&self) -> bool {
+        *self.receiver.borrow()
+    }
+
+    /// Resolve quando o desligamento é solicitado
+    pub async fn cancelled(&mut self) {
+        // Erro só acontece se o supervisor for descartado: trata como desligamento
+        let _ = self.receiver.wait_for(|stop| *stop).await;
+    }
+
+    /// Momento em que o supervisor aborta quem não terminou; só é conhecido quando
+    /// `shutdown` começa a esperar pelos workers
+    pub fn deadline(&self) -> Option<Instant> {
+        *self.deadline.borrow()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkerStatus {
+    pub name: String,
+    pub running: bool,
+}
+
+struct Worker {
+    name: String,
+    handle: JoinHandle<()>,
+    /// Tarefa avulsa: sai da lista quando termina
+    transient: bool,
+}
+
+/// Resultado do desligamento: workers que terminaram no prazo e os abortados
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ShutdownReport {
+    pub finished: Vec<String>,
+    pub aborted: Vec<String>,
+}
+
+/// Dono das tasks em background: avisa sobre o desligamento e espera até o prazo
+pub struct WorkerSupervisor {
+    shutdown_tx: watch::Sender<bool>,
+    shutdown_started: Mutex<Option<Instant>>,
+    deadline_tx: watch::Sender<Option<Instant>>,
+    workers: Mutex<Vec<Worker>>,
+    /// Runtime das tasks; sem ele, o da chamada
+    runtime: Mutex<Option<Handle>>,
+}
+
+impl Default for WorkerSupervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorkerSupervisor {
+    pub fn new() -> Self {
+        let (shutdown_tx, _) = watch::channel(false);
+        let (deadline_tx, _) = watch::channel(None);
+        Self {
+            shutdown_tx,
+            shutdown_started: Mutex::new(None),
+            deadline_tx,
+            workers: Mutex::new(Vec::new()),
+            runtime: Mutex::new(None),
+        }
+    }
+
+    /// Runtime onde as tasks passam a rodar. O servidor passa o do `main`: uma tarefa iniciada
+    /// num handler cairia no runtime do worker do actix, descartado antes de `shutdown`, e
+    /// seria cancelada em vez de drenada.
+    pub fn set_runtime(&self, runtime: Handle) {
+        *self.runtime.lock().unwrap() = Some(runtime);
+    }
+
+    pub fn signal(&self) -> ShutdownSignal {
+        ShutdownSignal {
+            receiver: self.shutdown_tx.subscribe(),
+            deadline: self.deadline_tx.subscribe(),
+        }
+    }
+
+    /// Inicia um worker; ele deve encerrar por conta própria ao receber o sinal
+    pub fn spawn<F, Fut>(&self, name: &str, worker: F)
+    where
+        F: FnOnce(ShutdownSignal) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.start(name, worker, false);
+    }
+
+    /// Inicia uma tarefa avulsa (ex: importação em lote). Também recebe o sinal e é
+    /// aguardada no desligamento, mas deixa de aparecer no status depois de terminar.
+    pub fn spawn_task<F, Fut>(&self, name: &str, task: F)
+    where
+        F: FnOnce(ShutdownSignal) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.start(name, task, true);
+    }
+
+    fn start<F, Fut>(&self, name: &str, worker: F, transient: bool)
+    where
+        F: FnOnce(ShutdownSignal) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        if self.is_shutting_down() {
+            warn!(
+                worker = name,
+                "Worker não iniciado: servidor em desligamento"
+            );
+            return;
+        }
+
+        let future = worker(self.signal());
+        let handle = match self.runtime.lock().unwrap().as_ref() {
+            Some(runtime) => runtime.spawn(future),
+            None => tokio::spawn(future),
+        };
+        info!(worker = name, "Worker iniciado");
+
+        let mut workers = self.workers.lock().unwrap();
+        workers.retain(|w| !(w.transient && w.handle.is_finished()));
+        workers.push(Worker {
+            name: name.to_string(),
+            handle,
+            transient,
+        });
+    }
+
+    /// Executa `task` a cada `period`; uma execução em andamento sempre termina antes de parar
+    pub fn spawn_periodic<F, Fut>(&self, name: &str, period: Duration, task: F)
+    where
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn(name, move |mut signal| async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    _ = signal.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
+                task().await;
+            }
+        });
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        *self.shutdown_tx.borrow()
+    }
+
+    pub fn status(&self) -> Vec<WorkerStatus> {
+        self.workers
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|w| !(w.transient && w.handle.is_finished()))
+            .map(|w| WorkerStatus {
+                name: w.name.clone(),
+                running: !w.handle.is_finished(),
+            })
+            .collect()
+    }
+
+    /// Avisa os workers (e a readiness) que o desligamento começou. O prazo conta a partir daqui.
+    pub fn begin_shutdown(&self) {
+        let mut started = self.shutdown_started.lock().unwrap();
+        if started.is_none() {
+            *started = Some(Instant::now());
+            self.shutdown_tx.send_replace(true);
+            info!("Desligamento iniciado: workers notificados");
+        }
+    }
+
+    /// Espera os workers terminarem até `deadline` após o início do desligamento e aborta o resto
+    pub async fn shutdown(&self, deadline: Duration) -> ShutdownReport {
+        self.begin_shutdown();
+        let started = self
+            .shutdown_started
+            .lock()
+            .unwrap()
+            .unwrap_or_else(Instant::now);
+        let deadline = started + deadline;
+        self.deadline_tx.send_replace(Some(deadline));
+
+        let workers = std::mem::take(&mut *self.workers.lock().unwrap());
+        let mut report = ShutdownReport::default();
+
+        for Worker {
+            name, mut handle, ..
+        } in workers
+        {
+            match tokio::time::timeout_at(deadline, &mut handle).await {
+                Ok(Ok(())) => report.finished.push(name),
+                Ok(Err(e)) => {
+                    error!(worker = %name, error = %e, "Worker terminou com erro");
+                    report.finished.push(name);
+                }
+                Err(_) => {
+                    handle.abort();
+                    warn!(worker = %name, "Worker abortado: prazo de desligamento excedido");
+                    report.aborted.push(name);
+                }
+            }
+        }
+
+        info!(
+            finished = report.finished.len(),
+            aborted = report.aborted.len(),
+            "Workers finalizados"
+        );
+        report
+    }
+}
+
+static SUPERVISOR: Lazy<WorkerSupervisor> = Lazy::new(WorkerSupervisor::new);
+
+/// Supervisor global usado pelo servidor e pela readiness
+pub fn supervisor() -> &'static WorkerSupervisor {
+    &SUPERVISOR
+}
+
+/// Resolve no primeiro SIGTERM (container) ou Ctrl+C
+pub async fn wait_for_shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = sigterm.recv() => info!("SIGTERM recebido"),
+                    _ = tokio::signal::ctrl_c() => info!("Ctrl+C recebido"),
+                }
+            }
+            Err(e) => {
+                error!("Erro ao registrar SIGTERM: {}", e);
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        info!("Ctrl+C recebido");
+    }
+}
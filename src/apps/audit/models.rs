@@ -0,0 +1,198 @@
+use crate::app_core::app_model::Claims;
+use actix_web::{HttpMessage, HttpRequest};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+/// Campos que nunca devem ser gravados na trilha de auditoria
+pub const SENSITIVE_FIELDS: &[&str] = &["password", "app_token", "token"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+    Login,
+    PasswordChange,
+    EmailConfirm,
+    Sync,
+    /// Carrinho de visitante juntado ao da conta
+    Merge,
+    Checkout,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+            AuditAction::Login => "login",
+            AuditAction::PasswordChange => "password_change",
+            AuditAction::EmailConfirm => "email_confirm",
+            AuditAction::Sync => "sync",
+            AuditAction::Merge => "merge",
+            AuditAction::Checkout => "checkout",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEntity {
+    User,
+    Profile,
+    Product,
+    ProductPrice,
+    Category,
+    Media,
+    Orchestrator,
+    Tenant,
+    Cart,
+}
+
+impl AuditEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEntity::User => "user",
+            AuditEntity::Profile => "profile",
+            AuditEntity::Product => "product",
+            AuditEntity::ProductPrice => "product_price",
+            AuditEntity::Category => "category",
+            AuditEntity::Media => "media",
+            AuditEntity::Orchestrator => "orchestrator",
+            AuditEntity::Tenant => "tenant",
+            AuditEntity::Cart => "cart",
+        }
+    }
+}
+
+/// Quem executou a ação e de onde ela veio
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    pub actor_id: Option<Uuid>,
+    pub tenant_id: Option<Uuid>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl AuditContext {
+    /// Monta o contexto a partir da requisição (claims do token, IP e User-Agent)
+    pub fn from_request(req: &HttpRequest) -> Self {
+        let (actor_id, tenant_id) = req
+            .extensions()
+            .get::<Claims>()
+            .map(|claims| (Uuid::parse_str(&claims.sub).ok(), Some(claims.tenant_id)))
+            .unwrap_or((None, None));
+
+        Self {
+            actor_id,
+            tenant_id,
+            ip_address: req
+                .connection_info()
+                .realip_remote_addr()
+                .map(|ip| ip.to_string()),
+            user_agent: req
+                .headers()
+                .get("User-Agent")
+                .and_then(|h| h.to_str().ok())
+                .map(|ua| ua.to_string()),
+        }
+    }
+
+    /// Usado em rotas públicas, onde o ator só é conhecido após a operação (ex: login)
+    pub fn with_actor(mut self, actor_id: Uuid, tenant_id: Option<Uuid>) -> Self {
+        self.actor_id = Some(actor_id);
+        if tenant_id.is_some() {
+            self.tenant_id = tenant_id;
+        }
+        self
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuditEvent {
+    pub id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub tenant_id: Option<Uuid>,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: Option<Uuid>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub changes: Value,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub dt_created: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct NewAuditEvent {
+    pub actor_id: Option<Uuid>,
+    pub tenant_id: Option<Uuid>,
+    pub action: AuditAction,
+    pub entity_type: AuditEntity,
+    pub entity_id: Option<Uuid>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub changes: Value,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct AuditEventListParams {
+    pub actor_id: Option<Uuid>,
+    pub tenant_id: Option<Uuid>,
+    pub action: Option<String>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<Uuid>,
+    pub dt_from: Option<DateTime<Utc>>,
+    pub dt_to: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    /// Paginação por cursor: vazio na primeira página, depois `next`/`prev` da resposta
+    pub cursor: Option<String>,
+    pub include_count: Option<bool>,
+}
+
+/// Remove campos sensíveis (recursivamente) de um snapshot
+pub fn redact(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(key, _)| !SENSITIVE_FIELDS.contains(&key.as_str()))
+                .map(|(key, value)| (key, redact(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact).collect()),
+        other => other,
+    }
+}
+
+/// Diferença campo a campo entre dois snapshots: `{ "campo": { "before": .., "after": .. } }`
+pub fn diff(before: Option<&Value>, after: Option<&Value>) -> Value {
+    let empty = Map::new();
+    let before_map = before.and_then(Value::as_object).unwrap_or(&empty);
+    let after_map = after.and_then(Value::as_object).unwrap_or(&empty);
+
+    let mut changes = Map::new();
+    for key in before_map.keys().chain(after_map.keys()) {
+        if changes.contains_key(key) {
+            continue;
+        }
+
+        let old = before_map.get(key).unwrap_or(&Value::Null);
+        let new = after_map.get(key).unwrap_or(&Value::Null);
+        if old != new {
+            changes.insert(
+                key.clone(),
+                serde_json::json!({ "before": old, "after": new }),
+            );
+        }
+    }
+
+    Value::Object(changes)
+}
@@ -0,0 +1,187 @@
+use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::{AuditEvent, AuditEventListParams, NewAuditEvent};
+use crate::utils::pagination::{CursorPage, Page, PageRequest, PaginatedResponse, push_keyset};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{Postgres, QueryBuilder, Row, postgres::PgRow};
+use tracing::instrument;
+use uuid::Uuid;
+
+/// Filtros da listagem (depois de "WHERE 1 = 1"), compartilhados entre a página e a contagem
+fn push_filters(qb: &mut QueryBuilder<'_, Postgres>, params: &AuditEventListParams) {
+    if let Some(actor_id) = params.actor_id {
+        qb.push(" AND actor_id = ").push_bind(actor_id);
+    }
+
+    if let Some(tenant_id) = params.tenant_id {
+        qb.push(" AND tenant_id = ").push_bind(tenant_id);
+    }
+
+    if let Some(action) = params.action.as_ref().filter(|s| !s.trim().is_empty()) {
+        qb.push(" AND action = ").push_bind(action.clone());
+    }
+
+    if let Some(entity_type) = params.entity_type.as_ref().filter(|s| !s.trim().is_empty()) {
+        qb.push(" AND entity_type = ")
+            .push_bind(entity_type.clone());
+    }
+
+    if let Some(entity_id) = params.entity_id {
+        qb.push(" AND entity_id = ").push_bind(entity_id);
+    }
+
+    if let Some(dt_from) = params.dt_from {
+        qb.push(" AND dt_created >= ")
+            .push_bind(dt_from.naive_utc());
+    }
+
+    if let Some(dt_to) = params.dt_to {
+        qb.push(" AND dt_created <= ").push_bind(dt_to.naive_utc());
+    }
+}
+
+pub struct AuditRepository<'a> {
+    app_state: &'a AppState,
+}
+
+impl<'a> AuditRepository<'a> {
+    pub fn new(app_state: &'a AppState) -> Self {
+        Self { app_state }
+    }
+
+    #[instrument(name = "AuditRepository::create", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create(&self, event: NewAuditEvent) -> Result<Uuid, sqlx::Error> {
+        let id = Uuid::new_v4();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO audit_events (
+                id, actor_id, tenant_id, action, entity_type, entity_id,
+                before, after, changes, ip_address, user_agent, dt_created
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, NOW())
+            "#,
+            id,
+            event.actor_id,
+            event.tenant_id,
+            event.action.as_str(),
+            event.entity_type.as_str(),
+            event.entity_id,
+            event.before,
+            event.after,
+            event.changes,
+            event.ip_address,
+            event.user_agent
+        )
+        .execute(&self.app_state.db)
+        .await?;
+
+        Ok(id)
+    }
+
+    #[instrument(name = "AuditRepository::find_all", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_all(
+        &self,
+        params: AuditEventListParams,
+        page: &PageRequest,
+    ) -> Result<Page<AuditEvent>, sqlx::Error> {
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT
+            id,
+            actor_id,
+            tenant_id,
+            action,
+            entity_type,
+            entity_id,
+            before,
+            after,
+            changes,
+            ip_address,
+            user_agent,
+            dt_created",
+        );
+
+        // Total na própria consulta só na paginação por offset; por cursor é opcional
+        if let PageRequest::Offset { .. } = page {
+            qb.push(", COUNT(*) OVER() AS total_count");
+        }
+        qb.push(" FROM audit_events WHERE 1 = 1");
+        push_filters(&mut qb, &params);
+
+        match page {
+            PageRequest::Offset { limit, offset } => {
+                qb.push(" ORDER BY dt_created DESC, id DESC");
+                qb.push(" LIMIT ").push_bind(*limit);
+                qb.push(" OFFSET ").push_bind(*offset);
+            }
+            PageRequest::Cursor { limit, cursor, .. } => {
+                push_keyset(&mut qb, "dt_created", "id", cursor.as_ref(), *limit);
+            }
+        }
+
+        let rows: Vec<PgRow> = qb.build().fetch_all(&self.app_state.db).await?;
+
+        let total = match page {
+            PageRequest::Offset { .. } => rows
+                .first()
+                .map(|r| r.get::<i64, _>("total_count"))
+                .unwrap_or(0),
+            _ => 0,
+        };
+
+        let items: Vec<AuditEvent> = rows
+            .into_iter()
+            .map(|row| AuditEvent {
+                id: row.get("id"),
+                actor_id: row.get("actor_id"),
+                tenant_id: row.get("tenant_id"),
+                action: row.get("action"),
+                entity_type: row.get("entity_type"),
+                entity_id: row.get("entity_id"),
+                before: row.get("before"),
+                after: row.get("after"),
+                changes: row.get("changes"),
+                ip_address: row.get("ip_address"),
+                user_agent: row.get("user_agent"),
+                dt_created: DateTime::from_naive_utc_and_offset(
+                    row.get::<NaiveDateTime, _>("dt_created"),
+                    Utc,
+                ),
+            })
+            .collect();
+
+        Ok(match page {
+            PageRequest::Offset { limit, offset } => Page::Offset(PaginatedResponse {
+                count: total,
+                results: items,
+                limit: *limit,
+                offset: *offset,
+            }),
+            PageRequest::Cursor {
+                limit,
+                cursor,
+                include_count,
+            } => {
+                let count = match include_count {
+                    true => {
+                        let mut qb: QueryBuilder<Postgres> =
+                            QueryBuilder::new("SELECT COUNT(*) FROM audit_events WHERE 1 = 1");
+                        push_filters(&mut qb, &params);
+                        Some(
+                            qb.build_query_scalar()
+                                .fetch_one(&self.app_state.db)
+                                .await?,
+                        )
+                    }
+                    false => None,
+                };
+                Page::Cursor(CursorPage::from_rows(
+                    items,
+                    *limit,
+                    cursor.as_ref(),
+                    |e| (e.dt_created, e.id),
+                    count,
+                ))
+            }
+        })
+    }
+}
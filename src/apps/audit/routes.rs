@@ -0,0 +1,27 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_extensions::RequestUserExt;
+use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::AuditEventListParams;
+use crate::apps::audit::services::AuditService;
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+
+pub async fn list_audit_events(
+    app_state: web::Data<AppState>,
+    query: web::Query<AuditEventListParams>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = req.user_id()?;
+    let tenant_id = req.tenant_id()?;
+    let access_level = req.access_level()?;
+
+    let result = AuditService::list_events(
+        &app_state,
+        query.into_inner(),
+        user_id,
+        tenant_id,
+        &access_level,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
@@ -0,0 +1,97 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::{
+    AuditAction, AuditContext, AuditEntity, AuditEvent, AuditEventListParams, NewAuditEvent, diff,
+    redact,
+};
+use crate::apps::audit::repositories::AuditRepository;
+use crate::apps::tenant::repositories::TenantRepository;
+use crate::utils::pagination::{Page, PageRequest};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::error;
+use uuid::Uuid;
+
+pub struct AuditService;
+
+impl AuditService {
+    /// Converte uma entidade em snapshot JSON sem os campos sensíveis
+    pub fn snapshot<T: Serialize>(value: &T) -> Option<Value> {
+        serde_json::to_value(value).ok().map(redact)
+    }
+
+    /// Registra um evento de auditoria. Falhas são logadas e não interrompem a operação auditada
+    pub async fn record(
+        app_state: &AppState,
+        context: &AuditContext,
+        action: AuditAction,
+        entity_type: AuditEntity,
+        entity_id: Option<Uuid>,
+        before: Option<Value>,
+        after: Option<Value>,
+    ) {
+        let event = NewAuditEvent {
+            actor_id: context.actor_id,
+            tenant_id: context.tenant_id,
+            action,
+            entity_type,
+            entity_id,
+            changes: diff(before.as_ref(), after.as_ref()),
+            before,
+            after,
+            ip_address: context.ip_address.clone(),
+            user_agent: context.user_agent.clone(),
+        };
+
+        let repository = AuditRepository::new(app_state);
+        if let Err(e) = repository.create(event).await {
+            error!(
+                action = action.as_str(),
+                entity_type = entity_type.as_str(),
+                "Erro ao registrar evento de auditoria: {}",
+                e
+            );
+        }
+    }
+
+    /// Lista eventos: super_admin vê tudo, dono do tenant vê apenas os eventos do seu tenant
+    pub async fn list_events(
+        app_state: &AppState,
+        mut params: AuditEventListParams,
+        user_id: Uuid,
+        tenant_id: Uuid,
+        access_level: &str,
+    ) -> Result<Page<AuditEvent>, AppError> {
+        let page = PageRequest::new(
+            params.limit.unwrap_or(20),
+            params.offset,
+            params.cursor.as_deref(),
+            params.include_count,
+        )?;
+
+        if access_level != "super_admin" {
+            let repository_tenant = TenantRepository::new(app_state);
+            let tenant = repository_tenant
+                .find_by_id(tenant_id)
+                .await
+                .map_err(|e| AppError::database_error(e.to_string()))?;
+
+            match tenant {
+                Some(tenant) if tenant.user_id == user_id => {
+                    params.tenant_id = Some(tenant.id);
+                }
+                _ => {
+                    return Err(AppError::forbidden(
+                        "Acesso negado. Apenas super_admin ou o dono do tenant podem consultar a auditoria.",
+                    ));
+                }
+            }
+        }
+
+        let repository = AuditRepository::new(app_state);
+        repository
+            .find_all(params, &page)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use crate::apps::audit::models::{AuditAction, AuditEntity, diff, redact};
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_only_contains_changed_fields() {
+        let before = json!({"name": "Camiseta", "price": "10.00", "stock_quantity": 5});
+        let after = json!({"name": "Camiseta", "price": "12.00", "stock_quantity": 5});
+
+        let changes = diff(Some(&before), Some(&after));
+
+        assert_eq!(
+            changes,
+            json!({"price": {"before": "10.00", "after": "12.00"}})
+        );
+    }
+
+    #[test]
+    fn test_diff_on_create_and_delete() {
+        let entity = json!({"name": "Camiseta"});
+
+        assert_eq!(
+            diff(None, Some(&entity)),
+            json!({"name": {"before": null, "after": "Camiseta"}})
+        );
+        assert_eq!(
+            diff(Some(&entity), None),
+            json!({"name": {"before": "Camiseta", "after": null}})
+        );
+    }
+
+    #[test]
+    fn test_redact_removes_sensitive_fields() {
+        let value = json!({
+            "app_name": "loja",
+            "app_token": "segredo",
+            "user": {"email": "a@b.com", "password": "hash"}
+        });
+
+        assert_eq!(
+            redact(value),
+            json!({"app_name": "loja", "user": {"email": "a@b.com"}})
+        );
+    }
+
+    #[test]
+    fn test_action_and_entity_names() {
+        assert_eq!(AuditAction::PasswordChange.as_str(), "password_change");
+        assert_eq!(
+            serde_json::to_value(AuditAction::EmailConfirm).unwrap(),
+            json!("email_confirm")
+        );
+        assert_eq!(AuditEntity::Orchestrator.as_str(), "orchestrator");
+        assert_eq!(AuditEntity::ProductPrice.as_str(), "product_price");
+        assert_eq!(AuditEntity::Tenant.as_str(), "tenant");
+        assert_eq!(AuditEntity::Cart.as_str(), "cart");
+        assert_eq!(AuditAction::Checkout.as_str(), "checkout");
+    }
+}
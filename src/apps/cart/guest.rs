@@ -0,0 +1,91 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::app_core::init_settings::get_settings;
+use crate::apps::audit::models::AuditContext;
+use crate::apps::cart::models::CartOwner;
+use crate::apps::cart::services::CartService;
+use crate::utils::jwt::verify_guest_cart_token;
+use actix_web::HttpRequest;
+use actix_web::cookie::time::Duration;
+use actix_web::cookie::{Cookie, SameSite};
+use tracing::warn;
+use uuid::Uuid;
+
+/// Cabeçalho com o token do carrinho de visitante (tem precedência sobre o cookie)
+pub const CART_TOKEN_HEADER: &str = "X-Cart-Token";
+pub const CART_TOKEN_COOKIE: &str = "cart_token";
+
+fn cart_token(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get(CART_TOKEN_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+        .or_else(|| req.cookie(CART_TOKEN_COOKIE).map(|c| c.value().to_string()))
+        .filter(|token| !token.trim().is_empty())
+}
+
+/// Dono do carrinho de visitante identificado pelo token da requisição
+pub fn guest_owner(req: &HttpRequest) -> Result<CartOwner, AppError> {
+    let token =
+        cart_token(req).ok_or_else(|| AppError::unauthorized("Token do carrinho não fornecido"))?;
+    let guest_id = verify_guest_cart_token(&token)
+        .map_err(|_| AppError::unauthorized("Token do carrinho inválido"))?;
+
+    Ok(CartOwner::Guest { guest_id })
+}
+
+/// Cookie HttpOnly com o token, válido pelo mesmo tempo do token
+pub fn cart_cookie(token: String) -> Cookie<'static> {
+    Cookie::build(CART_TOKEN_COOKIE, token)
+        .path("/")
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .max_age(Duration::seconds(
+            get_settings().cart.guest_token_ttl_secs as i64,
+        ))
+        .finish()
+}
+
+/// Cookie que apaga o token no navegador depois da junção
+pub fn expired_cart_cookie() -> Cookie<'static> {
+    let mut cookie = Cookie::build(CART_TOKEN_COOKIE, "").path("/").finish();
+    cookie.make_removal();
+    cookie
+}
+
+/// Junta o carrinho de visitante da requisição, se houver, ao carrinho da conta que acabou de
+/// entrar. Falhas só vão para o log: login e cadastro não dependem do carrinho.
+/// Devolve true quando o token não serve mais (carrinho juntado ou já fechado) e o cookie pode
+/// ser apagado; numa falha o carrinho de visitante continua ativo e o cookie fica.
+pub async fn merge_guest_cart_on_login(
+    app_state: &AppState,
+    req: &HttpRequest,
+    tenant_id: Uuid,
+    user_id: Uuid,
+) -> bool {
+    let Some(token) = cart_token(req) else {
+        return false;
+    };
+    let guest_id = match verify_guest_cart_token(&token) {
+        Ok(guest_id) => guest_id,
+        Err(err) => {
+            warn!(error = %err, "Token de carrinho inválido no login");
+            return false;
+        }
+    };
+
+    // Rota pública: o ator é a conta que acabou de entrar
+    let audit = AuditContext::from_request(req).with_actor(user_id, Some(tenant_id));
+    match CartService::merge_guest_cart(app_state, guest_id, tenant_id, user_id, &audit).await {
+        Ok(_) => true,
+        Err(err) => {
+            warn!(
+                guest_id = %guest_id,
+                user_id = %user_id,
+                error = %err,
+                "Falha ao juntar o carrinho de visitante"
+            );
+            false
+        }
+    }
+}
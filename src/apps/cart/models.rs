@@ -1,39 +1,53 @@
-use bigdecimal::BigDecimal;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use serde_with::{DisplayFromStr, serde_as};
 use sqlx::Type;
 use uuid::Uuid;
 
+use crate::utils::money::Money;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
 #[sqlx(type_name = "cart_status", rename_all = "SCREAMING_SNAKE_CASE")]
-#[allow(non_camel_case_types)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 pub enum CartStatus {
     ACTIVE,
     CHECKOUT_IN_PROGRESS,
     CONVERTED_TO_ORDER,
     ABANDONED,
     CANCELLED,
+    MERGED,
+}
+
+/// Dono do carrinho: usuário autenticado ou visitante (token de carrinho)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CartOwner {
+    User { tenant_id: Uuid, user_id: Uuid },
+    Guest { guest_id: Uuid },
+}
+
+impl CartOwner {
+    /// Tenant do comprador, que não pode comprar os próprios produtos
+    pub fn buyer_tenant_id(&self) -> Option<Uuid> {
+        match self {
+            CartOwner::User { tenant_id, .. } => Some(*tenant_id),
+            CartOwner::Guest { .. } => None,
+        }
+    }
 }
 
-#[serde_as]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Cart {
     pub id: Uuid,
-    pub tenant_id: Uuid,
-    pub user_id: Uuid,
+    /// Vazios no carrinho de visitante
+    pub tenant_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub guest_id: Option<Uuid>,
     pub status: CartStatus,
     pub currency: String,
-    #[serde_as(as = "DisplayFromStr")]
-    pub subtotal: BigDecimal,
-    #[serde_as(as = "DisplayFromStr")]
-    pub discount_total: BigDecimal,
-    #[serde_as(as = "DisplayFromStr")]
-    pub tax_total: BigDecimal,
-    #[serde_as(as = "DisplayFromStr")]
-    pub shipping_total: BigDecimal,
-    #[serde_as(as = "DisplayFromStr")]
-    pub grand_total: BigDecimal,
+    pub subtotal: Money,
+    pub discount_total: Money,
+    pub tax_total: Money,
+    pub shipping_total: Money,
+    pub grand_total: Money,
     pub version: i32,
     pub expires_at: Option<DateTime<Utc>>,
     pub dt_created: DateTime<Utc>,
@@ -41,6 +55,25 @@ pub struct Cart {
     pub dt_deleted: Option<DateTime<Utc>>,
 }
 
+/// POST /carts/ - corpo opcional; sem moeda, vale a moeda base da loja do comprador
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CreateCartRequest {
+    pub currency: Option<String>,
+}
+
+/// GET /carts/all/ - filtro opcional por membro da loja
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct CartListParams {
+    pub user_id: Option<Uuid>,
+}
+
+/// POST /carts/guest/ - token para os próximos pedidos (`X-Cart-Token` ou cookie)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GuestCartResponse {
+    pub token: String,
+    pub cart: CartWithItems,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AddProductCart {
     pub product_id: Uuid,
@@ -52,17 +85,125 @@ pub struct DeleteProductCart {
     pub product_id: Uuid,
 }
 
+/// PATCH /carts/items/{id}/ - quantidade exata da linha; 0 remove
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpdateCartItemRequest {
+    pub quantity: i32,
+}
+
+/// Operação do POST /carts/bulk/, identificada pelo campo `op`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum CartOperation {
+    /// Soma à quantidade da linha (cria se não existir)
+    Add { product_id: Uuid, quantity: i32 },
+    /// Remove a linha do produto
+    Remove { product_id: Uuid },
+    /// Define a quantidade exata; 0 remove a linha
+    Set { product_id: Uuid, quantity: i32 },
+}
+
+/// POST /carts/bulk/ - operações aplicadas em ordem, numa única transação
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkCartRequest {
+    pub operations: Vec<CartOperation>,
+}
+
+/// Divergência entre a linha do carrinho e o estado atual do produto
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum CartWarning {
+    /// Preço atual (na moeda do carrinho) difere do gravado na linha
+    PriceChanged {
+        item_id: Uuid,
+        product_id: Uuid,
+        old_price: Money,
+        new_price: Money,
+    },
+    /// Estoque menor que a quantidade da linha
+    InsufficientStock {
+        item_id: Uuid,
+        product_id: Uuid,
+        requested: i32,
+        available: i32,
+    },
+    OutOfStock {
+        item_id: Uuid,
+        product_id: Uuid,
+    },
+    /// Produto inativo, removido ou sem preço na moeda do carrinho
+    Unavailable {
+        item_id: Uuid,
+        product_id: Uuid,
+    },
+}
+
+impl CartWarning {
+    pub fn item_id(&self) -> Uuid {
+        match self {
+            CartWarning::PriceChanged { item_id, .. }
+            | CartWarning::InsufficientStock { item_id, .. }
+            | CartWarning::OutOfStock { item_id, .. }
+            | CartWarning::Unavailable { item_id, .. } => *item_id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RevalidationMode {
+    /// Só atualiza o preço das linhas para o preço atual
+    AcceptPrices,
+    /// Atualiza os preços, limita as quantidades ao estoque e remove linhas indisponíveis
+    Adjust,
+}
+
+/// POST /carts/revalidate/
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RevalidateCartRequest {
+    pub mode: RevalidationMode,
+}
+
+/// Alteração de linha já validada e precificada, gravada pelo repositório
+#[derive(Debug, Clone)]
+pub enum CartLineChange {
+    Insert {
+        product_id: Uuid,
+        variant_id: Uuid,
+        attributes_snapshot: serde_json::Value,
+        unit_price: Money,
+        quantity: i32,
+    },
+    Update {
+        item_id: Uuid,
+        unit_price: Money,
+        quantity: i32,
+    },
+    Delete {
+        item_id: Uuid,
+    },
+}
+
+/// Linha guardada fora do carrinho (ex: "salvos para depois") que pode voltar para ele
+#[derive(Debug, Clone)]
+pub struct CartLineSnapshot {
+    pub product_id: Uuid,
+    pub variant_id: Option<Uuid>,
+    pub attributes_snapshot: serde_json::Value,
+    pub quantity: i32,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CartItem {
     pub id: Uuid,
     pub cart_id: Uuid,
     pub product_id: Uuid,
     pub variant_id: Option<Uuid>,
-    pub unit_price: i64, // em centavos
+    pub unit_price: Money, // na moeda do carrinho
     pub quantity: i32,
-    pub line_discount_total: i64, // em centavos
-    pub line_tax_total: i64,      // em centavos
-    pub line_total: i64,          // em centavos - calculado automaticamente
+    pub line_discount_total: Money,
+    pub line_tax_total: Money,
+    pub line_total: Money, // calculado automaticamente
     pub attributes_snapshot: serde_json::Value,
     pub attributes_hash: String, // calculado automaticamente
     pub dt_created: DateTime<Utc>,
@@ -70,25 +211,21 @@ pub struct CartItem {
     pub dt_deleted: Option<DateTime<Utc>>,
 }
 
-#[serde_as]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CartWithItems {
     // Dados do carrinho
     pub id: Uuid,
-    pub tenant_id: Uuid,
-    pub user_id: Uuid,
+    /// Vazios no carrinho de visitante
+    pub tenant_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub guest_id: Option<Uuid>,
     pub status: CartStatus,
     pub currency: String,
-    #[serde_as(as = "DisplayFromStr")]
-    pub subtotal: BigDecimal,
-    #[serde_as(as = "DisplayFromStr")]
-    pub discount_total: BigDecimal,
-    #[serde_as(as = "DisplayFromStr")]
-    pub tax_total: BigDecimal,
-    #[serde_as(as = "DisplayFromStr")]
-    pub shipping_total: BigDecimal,
-    #[serde_as(as = "DisplayFromStr")]
-    pub grand_total: BigDecimal,
+    pub subtotal: Money,
+    pub discount_total: Money,
+    pub tax_total: Money,
+    pub shipping_total: Money,
+    pub grand_total: Money,
     pub version: i32,
     pub expires_at: Option<DateTime<Utc>>,
     pub dt_created: DateTime<Utc>,
@@ -101,16 +238,82 @@ pub struct CartWithItems {
     // Metadados úteis
     pub item_count: usize,      // Quantidade total de itens
     pub unique_products: usize, // Quantidade de produtos únicos
+
+    /// Divergências com o estado atual dos produtos (vazio quando o carrinho está em dia)
+    pub warnings: Vec<CartWarning>,
+}
+
+/// Estado do carrinho gravado na auditoria: totais e linhas, sem os dados dos produtos
+#[derive(Debug, Serialize, Clone)]
+pub struct CartAuditSnapshot {
+    pub status: CartStatus,
+    pub currency: String,
+    pub subtotal: Money,
+    pub version: i32,
+    pub items: Vec<CartAuditLine>,
+    /// Pedidos gerados pelo checkout
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub order_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CartAuditLine {
+    pub product_id: Uuid,
+    pub variant_id: Option<Uuid>,
+    pub quantity: i32,
+    pub unit_price: Money,
+}
+
+impl CartAuditSnapshot {
+    pub fn from_cart(cart: &Cart, items: &[CartItem]) -> Self {
+        Self {
+            status: cart.status,
+            currency: cart.currency.clone(),
+            subtotal: cart.subtotal.clone(),
+            version: cart.version,
+            items: items
+                .iter()
+                .map(|item| CartAuditLine {
+                    product_id: item.product_id,
+                    variant_id: item.variant_id,
+                    quantity: item.quantity,
+                    unit_price: item.unit_price.clone(),
+                })
+                .collect(),
+            order_ids: vec![],
+        }
+    }
+
+    pub fn from_cart_with_items(cart: &CartWithItems) -> Self {
+        Self {
+            status: cart.status,
+            currency: cart.currency.clone(),
+            subtotal: cart.subtotal.clone(),
+            version: cart.version,
+            items: cart
+                .items
+                .iter()
+                .map(|item| CartAuditLine {
+                    product_id: item.product_id,
+                    variant_id: item.variant_id,
+                    quantity: item.quantity,
+                    unit_price: item.unit_price.clone(),
+                })
+                .collect(),
+            order_ids: vec![],
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[allow(dead_code)]
 pub struct CartItemResponse {
     pub id: Uuid,
     pub product_id: Uuid,
     pub variant_id: Option<Uuid>,
-    pub unit_price: i64, // em centavos
+    pub unit_price: Money,
     pub quantity: i32,
-    pub line_total: i64, // em centavos
+    pub line_total: Money,
     pub attributes_snapshot: serde_json::Value,
     pub dt_created: DateTime<Utc>,
 }
@@ -123,11 +326,11 @@ pub struct CartItemWithProduct {
     pub cart_id: Uuid,
     pub product_id: Uuid,
     pub variant_id: Option<Uuid>,
-    pub unit_price: i64, // em centavos
+    pub unit_price: Money,
     pub quantity: i32,
-    pub line_discount_total: i64, // em centavos
-    pub line_tax_total: i64,      // em centavos
-    pub line_total: i64,          // em centavos
+    pub line_discount_total: Money,
+    pub line_tax_total: Money,
+    pub line_total: Money,
     pub attributes_snapshot: serde_json::Value,
     pub attributes_hash: String,
     pub dt_created: DateTime<Utc>,
@@ -139,22 +342,20 @@ pub struct CartItemWithProduct {
     pub product_slug: String,
     pub product_short_description: Option<String>,
     pub product_description: Option<String>,
-    pub product_price: BigDecimal,
+    pub product_price: Money,
     pub product_stock_quantity: i32,
     pub product_is_active: bool,
 }
 
-#[serde_as]
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[allow(dead_code)]
 pub struct CartSummary {
     pub id: Uuid,
     pub tenant_id: Uuid,
     pub status: CartStatus,
     pub currency: String,
-    #[serde_as(as = "DisplayFromStr")]
-    pub subtotal: BigDecimal,
-    #[serde_as(as = "DisplayFromStr")]
-    pub grand_total: BigDecimal,
+    pub subtotal: Money,
+    pub grand_total: Money,
     pub item_count: usize,
     pub dt_updated: DateTime<Utc>,
 }
@@ -171,6 +372,7 @@ impl CartWithItems {
             id: cart.id,
             tenant_id: cart.tenant_id,
             user_id: cart.user_id,
+            guest_id: cart.guest_id,
             status: cart.status,
             currency: cart.currency,
             subtotal: cart.subtotal,
@@ -186,6 +388,7 @@ impl CartWithItems {
             items: vec![], // Será preenchido depois
             item_count,
             unique_products,
+            warnings: Vec::new(),
         }
     }
 
@@ -238,6 +441,7 @@ impl CartWithItems {
             id: cart.id,
             tenant_id: cart.tenant_id,
             user_id: cart.user_id,
+            guest_id: cart.guest_id,
             status: cart.status,
             currency: cart.currency,
             subtotal: cart.subtotal,
@@ -253,6 +457,7 @@ impl CartWithItems {
             items: items_with_products,
             item_count,
             unique_products,
+            warnings: Vec::new(),
         }
     }
 
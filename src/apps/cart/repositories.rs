@@ -1,13 +1,22 @@
 use crate::app_core::app_state::AppState;
-use crate::apps::cart::models::{Cart, CartItem, CartStatus};
-use bigdecimal::{BigDecimal, ToPrimitive};
-// use chrono::NaiveDateTime;
-use chrono::{DateTime, Utc};
-// use sqlx::Row;
-use serde_json::Value;
-use sqlx::types::Json;
+use crate::apps::cart::models::{Cart, CartItem, CartLineChange, CartOwner, CartStatus};
+use crate::utils::money::Money;
+use crate::utils::pagination::{CursorPage, Page, PageRequest, PaginatedResponse, push_keyset};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{Postgres, QueryBuilder, Row, Transaction, postgres::PgRow};
+use tracing::instrument;
 use uuid::Uuid;
 
+/// Resultado da junção do carrinho de visitante; nada é gravado fora de `Merged`
+#[derive(Debug, PartialEq, Eq)]
+pub enum MergeOutcome {
+    Merged,
+    /// Carrinho de visitante já não estava ativo, por exemplo num login concorrente
+    GuestCartClosed,
+    /// Carrinho da conta mudou depois da leitura em que a junção foi planejada
+    CartChanged,
+}
+
 pub struct CartRepository<'a> {
     app_state: &'a AppState,
 }
@@ -17,60 +26,179 @@ impl<'a> CartRepository<'a> {
         Self { app_state }
     }
 
-    pub async fn find_all(&self, tenant_id: Uuid) -> Result<Vec<Cart>, sqlx::Error> {
-        let rows = sqlx::query_as!(
+    /// Carrinhos do tenant; `user_id` restringe a um membro
+    #[instrument(name = "CartRepository::find_all", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_all(
+        &self,
+        tenant_id: Uuid,
+        user_id: Option<Uuid>,
+        page: &PageRequest,
+    ) -> Result<Page<Cart>, sqlx::Error> {
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
+            r#"
+            SELECT
+                id,
+                tenant_id,
+                user_id,
+                guest_id,
+                status,
+                currency,
+                ROW(subtotal, currency)::money_value AS subtotal,
+                ROW(discount_total, currency)::money_value AS discount_total,
+                ROW(tax_total, currency)::money_value AS tax_total,
+                ROW(shipping_total, currency)::money_value AS shipping_total,
+                ROW(grand_total, currency)::money_value AS grand_total,
+                version,
+                expires_at,
+                dt_created,
+                dt_updated,
+                dt_deleted"#,
+        );
+
+        // Total na própria consulta só na paginação por offset; por cursor é opcional
+        if let PageRequest::Offset { .. } = page {
+            qb.push(", COUNT(*) OVER() AS total_count");
+        }
+        qb.push(" FROM carts WHERE tenant_id = ")
+            .push_bind(tenant_id);
+        if let Some(user_id) = user_id {
+            qb.push(" AND user_id = ").push_bind(user_id);
+        }
+
+        match page {
+            PageRequest::Offset { limit, offset } => {
+                qb.push(" ORDER BY dt_created DESC, id DESC LIMIT ")
+                    .push_bind(*limit)
+                    .push(" OFFSET ")
+                    .push_bind(*offset);
+            }
+            PageRequest::Cursor { limit, cursor, .. } => {
+                push_keyset(&mut qb, "dt_created", "id", cursor.as_ref(), *limit);
+            }
+        }
+
+        let rows: Vec<PgRow> = qb.build().fetch_all(&self.app_state.db).await?;
+
+        let total = match page {
+            PageRequest::Offset { .. } => rows
+                .first()
+                .map(|r| r.get::<i64, _>("total_count"))
+                .unwrap_or(0),
+            _ => 0,
+        };
+
+        let carts: Vec<Cart> = rows
+            .into_iter()
+            .map(|row| Cart {
+                id: row.get("id"),
+                tenant_id: row.get("tenant_id"),
+                user_id: row.get("user_id"),
+                guest_id: row.get("guest_id"),
+                status: row.get("status"),
+                currency: row.get("currency"),
+                subtotal: row.get("subtotal"),
+                discount_total: row.get("discount_total"),
+                tax_total: row.get("tax_total"),
+                shipping_total: row.get("shipping_total"),
+                grand_total: row.get("grand_total"),
+                version: row.get("version"),
+                expires_at: row
+                    .get::<Option<NaiveDateTime>, _>("expires_at")
+                    .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+                dt_created: DateTime::from_naive_utc_and_offset(
+                    row.get::<NaiveDateTime, _>("dt_created"),
+                    Utc,
+                ),
+                dt_updated: DateTime::from_naive_utc_and_offset(
+                    row.get::<NaiveDateTime, _>("dt_updated"),
+                    Utc,
+                ),
+                dt_deleted: row
+                    .get::<Option<NaiveDateTime>, _>("dt_deleted")
+                    .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            })
+            .collect();
+
+        Ok(match page {
+            PageRequest::Offset { limit, offset } => Page::Offset(PaginatedResponse {
+                count: total,
+                results: carts,
+                limit: *limit,
+                offset: *offset,
+            }),
+            PageRequest::Cursor {
+                limit,
+                cursor,
+                include_count,
+            } => {
+                let count = match include_count {
+                    true => Some(
+                        sqlx::query_scalar!(
+                            r#"
+                            SELECT COUNT(*) AS "count!" FROM carts
+                            WHERE tenant_id = $1 AND ($2::uuid IS NULL OR user_id = $2)
+                            "#,
+                            tenant_id,
+                            user_id
+                        )
+                        .fetch_one(&self.app_state.db)
+                        .await?,
+                    ),
+                    false => None,
+                };
+                Page::Cursor(CursorPage::from_rows(
+                    carts,
+                    *limit,
+                    cursor.as_ref(),
+                    |c| (c.dt_created, c.id),
+                    count,
+                ))
+            }
+        })
+    }
+
+    /// Carrinho ativo do usuário dentro do tenant
+    #[instrument(name = "CartRepository::find_by_user", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_user(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Cart>, sqlx::Error> {
+        let row = sqlx::query_as!(
             Cart,
             r#"
             SELECT
                 id,
                 tenant_id,
                 user_id,
+                guest_id,
                 status as "status: CartStatus",
                 currency,
-                (subtotal)::numeric as "subtotal!: BigDecimal",
-                (discount_total)::numeric as "discount_total!: BigDecimal",
-                (tax_total)::numeric as "tax_total!: BigDecimal",
-                (shipping_total)::numeric as "shipping_total!: BigDecimal",
-                (grand_total)::numeric as "grand_total!: BigDecimal",
+                ROW(subtotal, currency)::money_value as "subtotal!: Money",
+                ROW(discount_total, currency)::money_value as "discount_total!: Money",
+                ROW(tax_total, currency)::money_value as "tax_total!: Money",
+                ROW(shipping_total, currency)::money_value as "shipping_total!: Money",
+                ROW(grand_total, currency)::money_value as "grand_total!: Money",
                 version,
                 (expires_at AT TIME ZONE 'UTC') as "expires_at?: DateTime<Utc>",
                 (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
                 (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>",
                 (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
             FROM carts
-            WHERE tenant_id = $1
-            ORDER BY dt_updated desc
+            WHERE dt_deleted IS NULL AND status = 'ACTIVE' AND tenant_id = $1 AND user_id = $2
             "#,
-            tenant_id
+            tenant_id,
+            user_id
         )
-        .fetch_all(&self.app_state.db)
+        .fetch_optional(&self.app_state.db)
         .await?;
 
-        let carts = rows
-            .into_iter()
-            .map(|row| Cart {
-                id: row.id,
-                tenant_id: row.tenant_id,
-                user_id: row.user_id,
-                status: row.status,
-                currency: row.currency,
-                subtotal: row.subtotal,
-                discount_total: row.discount_total,
-                tax_total: row.tax_total,
-                shipping_total: row.shipping_total,
-                grand_total: row.grand_total,
-                version: row.version,
-                expires_at: row.expires_at,
-                dt_created: row.dt_created,
-                dt_updated: row.dt_updated,
-                dt_deleted: row.dt_deleted,
-            })
-            .collect();
-
-        Ok(carts)
+        Ok(row)
     }
 
-    pub async fn find_by_tenant_id(&self, tenant_id: Uuid) -> Result<Option<Cart>, sqlx::Error> {
+    /// Qualquer carrinho não removido do tenant, de qualquer membro (consulta do admin)
+    #[instrument(name = "CartRepository::find_by_id", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_id(&self, id: Uuid, tenant_id: Uuid) -> Result<Option<Cart>, sqlx::Error> {
         let row = sqlx::query_as!(
             Cart,
             r#"
@@ -78,21 +206,23 @@ impl<'a> CartRepository<'a> {
                 id,
                 tenant_id,
                 user_id,
+                guest_id,
                 status as "status: CartStatus",
                 currency,
-                (subtotal)::numeric as "subtotal!: BigDecimal",
-                (discount_total)::numeric as "discount_total!: BigDecimal",
-                (tax_total)::numeric as "tax_total!: BigDecimal",
-                (shipping_total)::numeric as "shipping_total!: BigDecimal",
-                (grand_total)::numeric as "grand_total!: BigDecimal",
+                ROW(subtotal, currency)::money_value as "subtotal!: Money",
+                ROW(discount_total, currency)::money_value as "discount_total!: Money",
+                ROW(tax_total, currency)::money_value as "tax_total!: Money",
+                ROW(shipping_total, currency)::money_value as "shipping_total!: Money",
+                ROW(grand_total, currency)::money_value as "grand_total!: Money",
                 version,
                 (expires_at AT TIME ZONE 'UTC') as "expires_at?: DateTime<Utc>",
                 (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
                 (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>",
                 (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
             FROM carts
-            WHERE dt_deleted IS NULL AND status = 'ACTIVE' AND tenant_id = $1
+            WHERE dt_deleted IS NULL AND id = $1 AND tenant_id = $2
             "#,
+            id,
             tenant_id
         )
         .fetch_optional(&self.app_state.db)
@@ -101,9 +231,47 @@ impl<'a> CartRepository<'a> {
         Ok(row)
     }
 
-    pub async fn create(&self, tenant_id: Uuid, user_id: Uuid) -> Result<Cart, sqlx::Error> {
+    #[instrument(name = "CartRepository::find_by_guest_id", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_guest_id(&self, guest_id: Uuid) -> Result<Option<Cart>, sqlx::Error> {
+        let row = sqlx::query_as!(
+            Cart,
+            r#"
+            SELECT
+                id,
+                tenant_id,
+                user_id,
+                guest_id,
+                status as "status: CartStatus",
+                currency,
+                ROW(subtotal, currency)::money_value as "subtotal!: Money",
+                ROW(discount_total, currency)::money_value as "discount_total!: Money",
+                ROW(tax_total, currency)::money_value as "tax_total!: Money",
+                ROW(shipping_total, currency)::money_value as "shipping_total!: Money",
+                ROW(grand_total, currency)::money_value as "grand_total!: Money",
+                version,
+                (expires_at AT TIME ZONE 'UTC') as "expires_at?: DateTime<Utc>",
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>",
+                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            FROM carts
+            WHERE dt_deleted IS NULL AND status = 'ACTIVE' AND guest_id = $1
+            "#,
+            guest_id
+        )
+        .fetch_optional(&self.app_state.db)
+        .await?;
+
+        Ok(row)
+    }
+
+    #[instrument(name = "CartRepository::create", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create(&self, owner: &CartOwner, currency: &str) -> Result<Cart, sqlx::Error> {
         let id = Uuid::new_v4();
         let now = Utc::now().naive_utc();
+        let (tenant_id, user_id, guest_id) = match *owner {
+            CartOwner::User { tenant_id, user_id } => (Some(tenant_id), Some(user_id), None),
+            CartOwner::Guest { guest_id } => (None, None, Some(guest_id)),
+        };
 
         let row = sqlx::query_as!(
             Cart,
@@ -112,6 +280,7 @@ impl<'a> CartRepository<'a> {
                 id,
                 tenant_id,
                 user_id,
+                guest_id,
                 status,
                 currency,
                 subtotal,
@@ -122,18 +291,19 @@ impl<'a> CartRepository<'a> {
                 dt_created,
                 dt_updated
             )
-            VALUES ($1, $2, $3, $4, $5, 0, 0, 0, 0, 0, $6, $7)
+            VALUES ($1, $2, $3, $4, $5, $6, 0, 0, 0, 0, 0, $7, $8)
             RETURNING
                 id,
                 tenant_id,
                 user_id,
+                guest_id,
                 status        as "status: CartStatus",
                 currency,
-                (subtotal)::numeric        as "subtotal!: BigDecimal",
-                (discount_total)::numeric  as "discount_total!: BigDecimal",
-                (tax_total)::numeric       as "tax_total!: BigDecimal",
-                (shipping_total)::numeric  as "shipping_total!: BigDecimal",
-                (grand_total)::numeric     as "grand_total!: BigDecimal",
+                ROW(subtotal, currency)::money_value as "subtotal!: Money",
+                ROW(discount_total, currency)::money_value as "discount_total!: Money",
+                ROW(tax_total, currency)::money_value as "tax_total!: Money",
+                ROW(shipping_total, currency)::money_value as "shipping_total!: Money",
+                ROW(grand_total, currency)::money_value as "grand_total!: Money",
                 version,
                 (expires_at AT TIME ZONE 'UTC') as "expires_at?: DateTime<Utc>",
                 (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
@@ -143,8 +313,9 @@ impl<'a> CartRepository<'a> {
             id,
             tenant_id,
             user_id,
+            guest_id,
             CartStatus::ACTIVE as _, // ou passar status
-            "BRL",                   // currency
+            currency,
             now,
             now
         )
@@ -154,48 +325,26 @@ impl<'a> CartRepository<'a> {
         Ok(row)
     }
 
-    pub async fn delete(&self, id: Uuid, tenant_id: Uuid) -> Result<bool, sqlx::Error> {
-        let exists = sqlx::query_as!(
-            Cart,
-            r#"
-            SELECT
-                id,
-                tenant_id,
-                user_id,
-                status as "status: CartStatus",
-                currency,
-                (subtotal)::numeric as "subtotal!: BigDecimal",
-                (discount_total)::numeric as "discount_total!: BigDecimal",
-                (tax_total)::numeric as "tax_total!: BigDecimal",
-                (shipping_total)::numeric as "shipping_total!: BigDecimal",
-                (grand_total)::numeric as "grand_total!: BigDecimal",
-                version,
-                (expires_at AT TIME ZONE 'UTC') as "expires_at?: DateTime<Utc>",
-                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
-                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>",
-                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
-            FROM carts
-            WHERE dt_deleted IS NULL AND id = $1 AND tenant_id = $2
-            "#,
-            id,
-            tenant_id
-        )
-        .fetch_optional(&self.app_state.db)
-        .await?;
-
-        // Se não existe, retorna false direto
-        if exists.is_none() {
-            return Ok(false);
-        }
-
+    /// Cancela o carrinho; só o dono (tenant e usuário) pode removê-lo
+    #[instrument(name = "CartRepository::delete", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete(
+        &self,
+        id: Uuid,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
         let now = Utc::now();
 
         let result = sqlx::query!(
-            "UPDATE carts SET dt_deleted = $1, status = $2 WHERE id = $3 AND tenant_id = $4 AND dt_deleted IS NULL",
+            r#"
+            UPDATE carts SET dt_deleted = $1, status = $2
+            WHERE id = $3 AND tenant_id = $4 AND user_id = $5 AND dt_deleted IS NULL
+            "#,
             now.naive_utc(),
             CartStatus::CANCELLED as _,
             id,
-            tenant_id
+            tenant_id,
+            user_id
         )
         .execute(&self.app_state.db)
         .await?;
@@ -203,138 +352,198 @@ impl<'a> CartRepository<'a> {
         Ok(result.rows_affected() > 0)
     }
 
-    pub async fn update_cart_data(
+    /// Grava as alterações de linhas e o novo subtotal numa única transação. O subtotal só é
+    /// gravado se estiver na moeda do carrinho. Devolve false (sem gravar nada) se o carrinho
+    /// mudou depois da leitura em que as alterações foram planejadas.
+    #[instrument(name = "CartRepository::apply_line_changes", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn apply_line_changes(
         &self,
-        cart_id: Uuid,
-        new_subtotal: &BigDecimal,
-        discount_total: &BigDecimal,
-        tax_total: &BigDecimal,
-        shipping_total: &BigDecimal,
+        cart: &Cart,
+        changes: &[CartLineChange],
+        subtotal: &Money,
     ) -> Result<bool, sqlx::Error> {
-        let now = Utc::now().naive_utc();
+        let mut tx = self.app_state.db.begin().await?;
+        if !Self::write_line_changes(&mut tx, cart, changes, subtotal).await? {
+            return Ok(false);
+        }
+        tx.commit().await?;
+        Ok(true)
+    }
 
-        // Converter BigDecimal para i64 (centavos)
-        let subtotal_cents = (new_subtotal * BigDecimal::from(100)).to_i64().unwrap_or(0);
-        let discount_total_cents = (discount_total * BigDecimal::from(100))
-            .to_i64()
-            .unwrap_or(0);
-        let tax_cents = (tax_total * BigDecimal::from(100)).to_i64().unwrap_or(0);
-        let shipping_cents = (shipping_total * BigDecimal::from(100))
-            .to_i64()
-            .unwrap_or(0);
+    /// Marca o carrinho de visitante como MERGED e grava as linhas no carrinho da conta, na
+    /// mesma transação
+    #[instrument(name = "CartRepository::merge_guest_cart", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn merge_guest_cart(
+        &self,
+        guest_cart_id: Uuid,
+        cart: &Cart,
+        changes: &[CartLineChange],
+        subtotal: &Money,
+    ) -> Result<MergeOutcome, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
 
-        let result = sqlx::query!(
+        let closed = sqlx::query!(
             r#"
-            UPDATE carts 
-            SET 
-                subtotal = $1,
-                tax_total = $2,
-                shipping_total = $3,
-                discount_total = $4,
-                dt_updated = $5
-            WHERE id = $6 AND dt_deleted IS NULL
+            UPDATE carts
+            SET status = $1, dt_updated = $2
+            WHERE id = $3 AND status = 'ACTIVE' AND dt_deleted IS NULL
             "#,
-            subtotal_cents,
-            tax_cents,
-            shipping_cents,
-            discount_total_cents,
-            now,
-            cart_id
+            CartStatus::MERGED as _,
+            Utc::now().naive_utc(),
+            guest_cart_id
         )
-        .execute(&self.app_state.db)
+        .execute(&mut *tx)
         .await?;
+        if closed.rows_affected() == 0 {
+            return Ok(MergeOutcome::GuestCartClosed);
+        }
 
-        Ok(result.rows_affected() > 0)
+        if !Self::write_line_changes(&mut tx, cart, changes, subtotal).await? {
+            return Ok(MergeOutcome::CartChanged);
+        }
+        tx.commit().await?;
+        Ok(MergeOutcome::Merged)
     }
 
-    pub async fn create_cart_item(
-        &self,
-        cart_id: Uuid,
-        product_id: Uuid,
-        variant_id: Uuid,
-        unit_price: i64,
-        quantity: i32,
-        line_discount_total: i64,
-        line_tax_total: i64,
-        attributes_snapshot: Json<Value>,
+    /// Grava as alterações de linhas e o subtotal dentro da transação de quem chama (ex: mover
+    /// a linha para "salvos para depois" junto com a remoção do carrinho).
+    /// As alterações foram planejadas sobre as linhas lidas na versão `cart.version`: a versão
+    /// é conferida e incrementada antes de tudo (trava a linha do carrinho), e false indica que
+    /// outra requisição gravou no meio. Quem chama descarta a transação.
+    #[instrument(name = "CartRepository::write_line_changes", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn write_line_changes(
+        tx: &mut Transaction<'_, Postgres>,
+        cart: &Cart,
+        changes: &[CartLineChange],
+        subtotal: &Money,
     ) -> Result<bool, sqlx::Error> {
-        let id = Uuid::new_v4();
         let now = Utc::now().naive_utc();
+        let cart_id = cart.id;
 
-        sqlx::query!(
+        let claimed = sqlx::query!(
             r#"
-            INSERT INTO cart_items (
-                id,
-                cart_id,
-                product_id,
-                variant_id,
-                unit_price,
-                quantity,
-                line_discount_total,
-                line_tax_total,
-                attributes_snapshot,
-                dt_created,
-                dt_updated
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-            RETURNING
-                id,
-                cart_id,
-                product_id,
-                variant_id,
-                unit_price,
-                quantity,
-                line_discount_total,
-                line_tax_total,
-                line_total,
-                attributes_snapshot,
-                attributes_hash,
-                dt_created,
-                dt_updated,
-                dt_deleted
+            UPDATE carts
+            SET subtotal = CASE WHEN currency = ($1::money_value).currency
+                               THEN ($1::money_value).amount_minor ELSE subtotal END,
+                version = version + 1,
+                dt_updated = $2
+            WHERE id = $3 AND version = $4 AND dt_deleted IS NULL
             "#,
-            id,
-            cart_id,
-            product_id,
-            variant_id,
-            unit_price,
-            quantity,
-            line_discount_total,
-            line_tax_total,
-            attributes_snapshot.0,
+            subtotal as _,
             now,
-            now
+            cart_id,
+            cart.version
         )
-        .fetch_one(&self.app_state.db)
+        .execute(&mut **tx)
         .await?;
+        if claimed.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        for change in changes {
+            match change {
+                CartLineChange::Insert {
+                    product_id,
+                    variant_id,
+                    attributes_snapshot,
+                    unit_price,
+                    quantity,
+                } => {
+                    sqlx::query!(
+                        r#"
+                        INSERT INTO cart_items (
+                            id,
+                            cart_id,
+                            product_id,
+                            variant_id,
+                            attributes_snapshot,
+                            unit_price,
+                            quantity,
+                            dt_created,
+                            dt_updated
+                        )
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                        "#,
+                        Uuid::new_v4(),
+                        cart_id,
+                        product_id,
+                        variant_id,
+                        attributes_snapshot,
+                        unit_price.amount_minor,
+                        quantity,
+                        now,
+                        now
+                    )
+                    .execute(&mut **tx)
+                    .await?;
+                }
+                CartLineChange::Update {
+                    item_id,
+                    unit_price,
+                    quantity,
+                } => {
+                    sqlx::query!(
+                        r#"
+                        UPDATE cart_items
+                        SET quantity = $1, unit_price = $2, dt_updated = $3
+                        WHERE id = $4 AND cart_id = $5 AND dt_deleted IS NULL
+                        "#,
+                        quantity,
+                        unit_price.amount_minor,
+                        now,
+                        item_id,
+                        cart_id
+                    )
+                    .execute(&mut **tx)
+                    .await?;
+                }
+                CartLineChange::Delete { item_id } => {
+                    sqlx::query!(
+                        r#"
+                        UPDATE cart_items
+                        SET dt_deleted = $1, dt_updated = $1
+                        WHERE id = $2 AND cart_id = $3 AND dt_deleted IS NULL
+                        "#,
+                        now,
+                        item_id,
+                        cart_id
+                    )
+                    .execute(&mut **tx)
+                    .await?;
+                }
+            }
+        }
 
         Ok(true)
     }
 
+    #[instrument(name = "CartRepository::list_cart_items", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn list_cart_items(&self, cart_id: Uuid) -> Result<Vec<CartItem>, sqlx::Error> {
         let rows = sqlx::query_as!(
             CartItem,
             r#"
             SELECT
-                id as "id!",
-                cart_id as "cart_id!",
-                product_id as "product_id!",
-                variant_id as "variant_id?",
-                unit_price as "unit_price!",
-                quantity as "quantity!",
-                line_discount_total as "line_discount_total!",
-                line_tax_total as "line_tax_total!",
-                line_total as "line_total!",
-                attributes_snapshot as "attributes_snapshot!",
-                attributes_hash as "attributes_hash!",
-                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
-                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>",
-                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+                ci.id as "id!",
+                ci.cart_id as "cart_id!",
+                ci.product_id as "product_id!",
+                ci.variant_id as "variant_id?",
+                ROW(ci.unit_price, c.currency)::money_value as "unit_price!: Money",
+                ci.quantity as "quantity!",
+                ROW(ci.line_discount_total, c.currency)::money_value as "line_discount_total!: Money",
+                ROW(ci.line_tax_total, c.currency)::money_value as "line_tax_total!: Money",
+                ROW(ci.line_total, c.currency)::money_value as "line_total!: Money",
+                ci.attributes_snapshot as "attributes_snapshot!",
+                ci.attributes_hash as "attributes_hash!",
+                (ci.dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (ci.dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>",
+                (ci.dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
             FROM
-                cart_items
+                cart_items ci
+                JOIN carts c ON c.id = ci.cart_id
             WHERE
-                cart_id = $1
-                AND dt_deleted IS NULL
+                ci.cart_id = $1
+                AND ci.dt_deleted IS NULL
+            ORDER BY ci.dt_created, ci.id
             "#,
             cart_id
         )
@@ -343,55 +552,4 @@ impl<'a> CartRepository<'a> {
 
         Ok(rows)
     }
-
-    /// Atualiza a quantidade de um item do carrinho
-    pub async fn update_cart_item_quantity(
-        &self,
-        item_id: Uuid,
-        new_quantity: i32,
-        unit_price: i64,
-    ) -> Result<bool, sqlx::Error> {
-        let now = Utc::now().naive_utc();
-
-        let result = sqlx::query!(
-            r#"
-            UPDATE cart_items 
-            SET 
-                quantity = $1,
-                unit_price = $2,
-                dt_updated = $3
-            WHERE id = $4 AND dt_deleted IS NULL
-            "#,
-            new_quantity,
-            unit_price,
-            now,
-            item_id
-        )
-        .execute(&self.app_state.db)
-        .await?;
-
-        Ok(result.rows_affected() > 0)
-    }
-
-    /// Remove um item do carrinho (soft delete)
-    pub async fn delete_cart_item(&self, item_id: Uuid) -> Result<bool, sqlx::Error> {
-        let now = Utc::now().naive_utc();
-
-        let result = sqlx::query!(
-            r#"
-            UPDATE cart_items 
-            SET 
-                dt_deleted = $1,
-                dt_updated = $2
-            WHERE id = $3 AND dt_deleted IS NULL
-            "#,
-            now,
-            now,
-            item_id
-        )
-        .execute(&self.app_state.db)
-        .await?;
-
-        Ok(result.rows_affected() > 0)
-    }
 }
@@ -1,42 +1,86 @@
 use crate::app_core::app_error::AppError;
 use crate::app_core::app_extensions::RequestUserExt;
 use crate::app_core::app_state::AppState;
-use crate::apps::cart::models::{AddProductCart, DeleteProductCart};
+use crate::apps::audit::models::AuditContext;
+use crate::apps::cart::guest::{cart_cookie, guest_owner};
+use crate::apps::cart::models::{
+    AddProductCart, BulkCartRequest, CartListParams, CartOwner, CreateCartRequest,
+    DeleteProductCart, RevalidateCartRequest, UpdateCartItemRequest,
+};
 use crate::apps::cart::services::CartService;
+use crate::utils::pagination::PaginationParams;
 use actix_web::web::Json;
 use actix_web::{HttpRequest, HttpResponse, Responder, web};
 use uuid::Uuid;
 
+/// GET /carts/all/ - carrinhos dos membros da loja (dono ou super_admin); `?user_id=` filtra
 pub async fn get_cards(
     app_state: web::Data<AppState>,
+    query: web::Query<PaginationParams>,
+    filter: web::Query<CartListParams>,
     req: HttpRequest,
 ) -> Result<impl Responder, AppError> {
     let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let access_level = req.access_level()?;
+    let page = query.page_request()?;
 
-    let result = CartService::list_cards(&app_state, tenant_id).await?;
+    let result = CartService::list_cards(
+        &app_state,
+        tenant_id,
+        user_id,
+        &access_level,
+        filter.into_inner(),
+        &page,
+    )
+    .await?;
 
     Ok(HttpResponse::Ok().json(serde_json::json!(result)))
 }
 
-pub async fn get_card_by_tenant(
+/// GET /carts/all/{id}/ - carrinho de um membro da loja, com os itens
+pub async fn get_member_cart(
     app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
     req: HttpRequest,
 ) -> Result<impl Responder, AppError> {
     let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let access_level = req.access_level()?;
+    let id = path.into_inner();
 
-    let result = CartService::get_cart(&app_state, tenant_id).await?;
+    let result =
+        CartService::get_member_cart(&app_state, tenant_id, user_id, &access_level, id).await?;
 
     Ok(HttpResponse::Ok().json(serde_json::json!(result)))
 }
 
+pub async fn get_card_by_tenant(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let owner = CartOwner::User {
+        tenant_id: req.tenant_id()?,
+        user_id: req.user_id()?,
+    };
+
+    let result = CartService::get_cart(&app_state, owner).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /carts/ - corpo opcional `{"currency": "USD"}`
 pub async fn create_cart(
     app_state: web::Data<AppState>,
+    payload: Option<Json<CreateCartRequest>>,
     req: HttpRequest,
 ) -> Result<impl Responder, AppError> {
     let user_id = req.user_id()?;
     let tenant_id = req.tenant_id()?;
+    let audit = AuditContext::from_request(&req);
+    let dto = payload.map(Json::into_inner).unwrap_or_default();
 
-    let result = CartService::create_cart(&app_state, user_id, tenant_id).await?;
+    let result = CartService::create_cart(&app_state, user_id, tenant_id, dto, &audit).await?;
 
     Ok(HttpResponse::Ok().json(serde_json::json!(result)))
 }
@@ -47,9 +91,11 @@ pub async fn delete_cart(
     req: HttpRequest,
 ) -> Result<impl Responder, AppError> {
     let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let audit = AuditContext::from_request(&req);
     let id = path.into_inner();
 
-    CartService::delete_cart(&app_state, id, tenant_id).await?;
+    CartService::delete_cart(&app_state, id, tenant_id, user_id, &audit).await?;
 
     Ok(HttpResponse::NoContent().finish())
 }
@@ -61,10 +107,12 @@ pub async fn add_product_cart(
 ) -> Result<impl Responder, AppError> {
     let tenant_id = req.tenant_id()?;
     let user_id = req.user_id()?;
+    let audit = AuditContext::from_request(&req);
     let dto = payload.into_inner();
 
     let result =
-        CartService::add_product_cart_by_tenant(&app_state, dto, tenant_id, user_id).await?;
+        CartService::add_product_cart_by_tenant(&app_state, dto, tenant_id, user_id, &audit)
+            .await?;
 
     Ok(HttpResponse::Ok().json(serde_json::json!(result)))
 }
@@ -76,10 +124,170 @@ pub async fn delete_product_cart(
 ) -> Result<impl Responder, AppError> {
     let tenant_id = req.tenant_id()?;
     let user_id = req.user_id()?;
+    let audit = AuditContext::from_request(&req);
     let dto = payload.into_inner();
 
     let result =
-        CartService::delete_product_cart_by_tenant(&app_state, dto, tenant_id, user_id).await?;
+        CartService::delete_product_cart_by_tenant(&app_state, dto, tenant_id, user_id, &audit)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// PATCH /carts/items/{id}/ - `{"quantity": 3}`; 0 remove a linha
+pub async fn update_cart_item(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Json<UpdateCartItemRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let owner = CartOwner::User {
+        tenant_id: req.tenant_id()?,
+        user_id: req.user_id()?,
+    };
+    let item_id = path.into_inner();
+    let audit = AuditContext::from_request(&req);
+    let dto = payload.into_inner();
+
+    let result = CartService::update_cart_item(&app_state, owner, item_id, dto, &audit).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /carts/bulk/ - `{"operations": [{"op": "add", "product_id": "...", "quantity": 1}]}`
+pub async fn bulk_update_cart(
+    app_state: web::Data<AppState>,
+    payload: Json<BulkCartRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let owner = CartOwner::User {
+        tenant_id: req.tenant_id()?,
+        user_id: req.user_id()?,
+    };
+    let audit = AuditContext::from_request(&req);
+    let dto = payload.into_inner();
+
+    let result = CartService::bulk_update(&app_state, owner, dto, &audit).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn clear_cart(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let owner = CartOwner::User {
+        tenant_id: req.tenant_id()?,
+        user_id: req.user_id()?,
+    };
+
+    let audit = AuditContext::from_request(&req);
+
+    let result = CartService::clear_cart(&app_state, owner, &audit).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /carts/revalidate/ - `{"mode": "accept_prices"}` ou `{"mode": "adjust"}`
+pub async fn revalidate_cart(
+    app_state: web::Data<AppState>,
+    payload: Json<RevalidateCartRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let owner = CartOwner::User {
+        tenant_id: req.tenant_id()?,
+        user_id: req.user_id()?,
+    };
+    let audit = AuditContext::from_request(&req);
+    let dto = payload.into_inner();
+
+    let result = CartService::revalidate_cart(&app_state, owner, dto, &audit).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+// ===== CARRINHO DE VISITANTE (sem autenticação, token em X-Cart-Token ou cookie) =====
+
+/// POST /carts/guest/ - corpo opcional `{"currency": "USD"}`; devolve o token e grava o cookie
+pub async fn create_guest_cart(
+    app_state: web::Data<AppState>,
+    payload: Option<Json<CreateCartRequest>>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let audit = AuditContext::from_request(&req);
+    let dto = payload.map(Json::into_inner).unwrap_or_default();
+
+    let result = CartService::create_guest_cart(&app_state, dto, &audit).await?;
+
+    Ok(HttpResponse::Created()
+        .cookie(cart_cookie(result.token.clone()))
+        .json(serde_json::json!(result)))
+}
+
+pub async fn get_guest_cart(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let owner = guest_owner(&req)?;
+
+    let result = CartService::get_cart(&app_state, owner).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn update_guest_cart_item(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Json<UpdateCartItemRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let owner = guest_owner(&req)?;
+    let item_id = path.into_inner();
+    let audit = AuditContext::from_request(&req);
+    let dto = payload.into_inner();
+
+    let result = CartService::update_cart_item(&app_state, owner, item_id, dto, &audit).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn bulk_update_guest_cart(
+    app_state: web::Data<AppState>,
+    payload: Json<BulkCartRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let owner = guest_owner(&req)?;
+    let audit = AuditContext::from_request(&req);
+    let dto = payload.into_inner();
+
+    let result = CartService::bulk_update(&app_state, owner, dto, &audit).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn clear_guest_cart(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let owner = guest_owner(&req)?;
+
+    let audit = AuditContext::from_request(&req);
+
+    let result = CartService::clear_cart(&app_state, owner, &audit).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn revalidate_guest_cart(
+    app_state: web::Data<AppState>,
+    payload: Json<RevalidateCartRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let owner = guest_owner(&req)?;
+    let audit = AuditContext::from_request(&req);
+    let dto = payload.into_inner();
+
+    let result = CartService::revalidate_cart(&app_state, owner, dto, &audit).await?;
 
     Ok(HttpResponse::Ok().json(serde_json::json!(result)))
 }
@@ -1,86 +1,199 @@
 use crate::app_core::app_error::AppError;
 use crate::app_core::app_state::AppState;
-use crate::apps::cart::models::{AddProductCart, Cart, CartWithItems, DeleteProductCart};
-use crate::apps::cart::repositories::CartRepository;
+use crate::app_core::init_settings::get_settings;
+use crate::app_core::settings::{CartMergeQuantityRule, CartMergeStockRule};
+use crate::app_core::telemetry::record_cart_operation;
+use crate::apps::audit::models::{AuditAction, AuditContext, AuditEntity};
+use crate::apps::audit::services::AuditService;
+use crate::apps::cart::models::{
+    AddProductCart, BulkCartRequest, Cart, CartAuditSnapshot, CartItem, CartLineChange,
+    CartLineSnapshot, CartListParams, CartOperation, CartOwner, CartWarning, CartWithItems,
+    CreateCartRequest, DeleteProductCart, GuestCartResponse, RevalidateCartRequest,
+    RevalidationMode, UpdateCartItemRequest,
+};
+use crate::apps::cart::repositories::{CartRepository, MergeOutcome};
+use crate::apps::product::models::Product;
 use crate::apps::product::repositories::ProductRepository;
-use bigdecimal::{BigDecimal, ToPrimitive};
-use sqlx::types::Json;
+use crate::apps::product_price::services::ProductPriceService;
+use crate::apps::tenant::services::TenantService;
+use crate::utils::currency::normalize_currency;
+use crate::utils::jwt::generate_guest_cart_token;
+use crate::utils::money::{Money, MoneyError};
+use crate::utils::pagination::{Page, PageRequest};
+use std::collections::HashMap;
+use tracing::warn;
 use uuid::Uuid;
 
+/// Máximo de operações num POST /carts/bulk/
+const MAX_BULK_OPERATIONS: usize = 100;
+
 pub struct CartService;
 
 impl CartService {
-    pub async fn list_cards(app_state: &AppState, tenant_id: Uuid) -> Result<Vec<Cart>, AppError> {
+    /// GET /carts/all/ - carrinhos dos membros da loja; só para quem administra o tenant
+    pub async fn list_cards(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+        params: CartListParams,
+        page: &PageRequest,
+    ) -> Result<Page<Cart>, AppError> {
+        Self::ensure_cart_admin(app_state, tenant_id, user_id, access_level).await?;
+
         let repository = CartRepository::new(app_state);
         let carts = repository
-            .find_all(tenant_id)
+            .find_all(tenant_id, params.user_id, page)
             .await
             .map_err(|e| AppError::database_error(e.to_string()))?;
 
         Ok(carts)
     }
 
-    pub async fn get_cart(
+    /// GET /carts/all/{id}/ - carrinho de um membro da loja, com os itens
+    pub async fn get_member_cart(
         app_state: &AppState,
         tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+        id: Uuid,
     ) -> Result<CartWithItems, AppError> {
-        let repository = CartRepository::new(app_state);
-        let cart = repository
-            .find_by_tenant_id(tenant_id)
-            .await
-            .map_err(|e| AppError::database_error(e.to_string()))?;
-
-        let cart = match cart {
-            Some(cart) => cart,
-            None => return Err(AppError::not_found("Carrinho não encontrado")),
-        };
-
-        let list_cart_items = repository.list_cart_items(cart.id).await?;
+        Self::ensure_cart_admin(app_state, tenant_id, user_id, access_level).await?;
 
-        // Buscar os produtos dos itens do carrinho
-        let product_ids: Vec<Uuid> = list_cart_items.iter().map(|item| item.product_id).collect();
+        let cart = CartRepository::new(app_state)
+            .find_by_id(id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Carrinho não encontrado"))?;
 
-        let products = if !product_ids.is_empty() {
-            let repo_product = ProductRepository::new(app_state);
-            repo_product
-                .find_by_ids(&product_ids)
-                .await
-                .map_err(|e| AppError::database_error(e.to_string()))?
-        } else {
-            vec![]
-        };
+        Self::with_items(app_state, cart).await
+    }
 
-        let cart_with_items =
-            CartWithItems::from_cart_and_items_with_products(cart, list_cart_items, products);
+    pub async fn get_cart(
+        app_state: &AppState,
+        owner: CartOwner,
+    ) -> Result<CartWithItems, AppError> {
+        let cart = Self::find_active(app_state, owner)
+            .await?
+            .ok_or_else(|| AppError::not_found("Carrinho não encontrado"))?;
 
-        Ok(cart_with_items)
+        Self::with_items(app_state, cart).await
     }
 
     pub async fn create_cart(
         app_state: &AppState,
         user_id: Uuid,
         tenant_id: Uuid,
+        request: CreateCartRequest,
+        audit: &AuditContext,
     ) -> Result<Cart, AppError> {
+        let currency = match request.currency {
+            Some(currency) => normalize_currency(&currency)?,
+            None => TenantService::base_currency(app_state, tenant_id).await?,
+        };
+
+        // Um carrinho ativo por usuário (`uq_active_cart_per_user`)
+        let owner = CartOwner::User { tenant_id, user_id };
+        if Self::find_active(app_state, owner).await?.is_some() {
+            return Err(AppError::Conflict(Some(
+                "Usuário já possui um carrinho ativo".into(),
+            )));
+        }
+
         let repository = CartRepository::new(app_state);
-        repository
-            .create(tenant_id, user_id)
+        let cart = repository
+            .create(&owner, &currency)
             .await
-            .map_err(|e| AppError::database_error(e.to_string()))
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        Self::record_audit(
+            app_state,
+            audit,
+            AuditAction::Create,
+            cart.id,
+            None,
+            Some(&CartAuditSnapshot::from_cart(&cart, &[])),
+        )
+        .await;
+
+        record_cart_operation("create");
+        Ok(cart)
+    }
+
+    /// POST /carts/guest/ - carrinho de visitante e o token que o identifica
+    pub async fn create_guest_cart(
+        app_state: &AppState,
+        request: CreateCartRequest,
+        audit: &AuditContext,
+    ) -> Result<GuestCartResponse, AppError> {
+        let currency = match request.currency {
+            Some(currency) => normalize_currency(&currency)?,
+            None => get_settings().cart.guest_currency.clone(),
+        };
+
+        let guest_id = Uuid::new_v4();
+        let cart = CartRepository::new(app_state)
+            .create(&CartOwner::Guest { guest_id }, &currency)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        let token = generate_guest_cart_token(guest_id)
+            .map_err(|e| AppError::internal(format!("Erro ao gerar token do carrinho: {}", e)))?;
+
+        Self::record_audit(
+            app_state,
+            audit,
+            AuditAction::Create,
+            cart.id,
+            None,
+            Some(&CartAuditSnapshot::from_cart(&cart, &[])),
+        )
+        .await;
+
+        record_cart_operation("create_guest");
+        Ok(GuestCartResponse {
+            token,
+            cart: Self::with_items(app_state, cart).await?,
+        })
     }
 
     pub async fn delete_cart(
         app_state: &AppState,
         id: Uuid,
         tenant_id: Uuid,
+        user_id: Uuid,
+        audit: &AuditContext,
     ) -> Result<bool, AppError> {
         let repository = CartRepository::new(app_state);
+        let before = match repository
+            .find_by_id(id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+        {
+            Some(cart) if cart.user_id == Some(user_id) => {
+                let items = repository.list_cart_items(cart.id).await?;
+                Some(CartAuditSnapshot::from_cart(&cart, &items))
+            }
+            _ => None,
+        };
         let deleted = repository
-            .delete(id, tenant_id)
+            .delete(id, tenant_id, user_id)
             .await
             .map_err(|e| AppError::database_error(e.to_string()))?;
 
         match deleted {
-            true => Ok(deleted),
+            true => {
+                Self::record_audit(
+                    app_state,
+                    audit,
+                    AuditAction::Delete,
+                    id,
+                    before.as_ref(),
+                    None,
+                )
+                .await;
+                record_cart_operation("delete");
+                Ok(deleted)
+            }
             false => Err(AppError::not_found("Carrinho não encontrado")),
         }
     }
@@ -90,242 +203,869 @@ impl CartService {
         request: AddProductCart,
         tenant_id: Uuid,
         user_id: Uuid,
+        audit: &AuditContext,
     ) -> Result<CartWithItems, AppError> {
-        let repo_cart = CartRepository::new(app_state);
-        let repo_product = ProductRepository::new(app_state);
+        let owner = CartOwner::User { tenant_id, user_id };
+        let cart = Self::get_or_create_cart(app_state, owner, audit).await?;
+        let operations = [CartOperation::Add {
+            product_id: request.product_id,
+            quantity: request.quantity,
+        }];
+        let cart_with_items =
+            Self::apply_operations(app_state, cart, owner, &operations, audit).await?;
 
-        let product_id = request.product_id;
-        let quantity = request.quantity;
+        record_cart_operation("add_item");
+        Ok(cart_with_items)
+    }
 
-        let product = repo_product
-            .find_by_id(product_id)
-            .await
-            .map_err(|e| AppError::database_error(e.to_string()))?;
+    pub async fn delete_product_cart_by_tenant(
+        app_state: &AppState,
+        request: DeleteProductCart,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<CartWithItems, AppError> {
+        let owner = CartOwner::User { tenant_id, user_id };
+        let cart = Self::get_or_create_cart(app_state, owner, audit).await?;
+        let operations = [CartOperation::Remove {
+            product_id: request.product_id,
+        }];
+        let cart_with_items =
+            Self::apply_operations(app_state, cart, owner, &operations, audit).await?;
 
-        let product = match product {
-            Some(product) => product,
-            None => return Err(AppError::not_found("Produto não encontrado")),
-        };
+        record_cart_operation("remove_item");
+        Ok(cart_with_items)
+    }
 
-        if product.stock_quantity < quantity {
-            return Err(AppError::bad_request("Estoque insuficiente"));
+    /// PATCH /carts/items/{id}/ - quantidade exata da linha; 0 remove
+    pub async fn update_cart_item(
+        app_state: &AppState,
+        owner: CartOwner,
+        item_id: Uuid,
+        request: UpdateCartItemRequest,
+        audit: &AuditContext,
+    ) -> Result<CartWithItems, AppError> {
+        let cart = Self::find_active(app_state, owner)
+            .await?
+            .ok_or_else(|| AppError::not_found("Carrinho não encontrado"))?;
+
+        let items = CartRepository::new(app_state)
+            .list_cart_items(cart.id)
+            .await?;
+        let item = items
+            .iter()
+            .find(|item| item.id == item_id)
+            .ok_or_else(|| AppError::not_found("Item não encontrado no carrinho"))?;
+
+        let operations = [CartOperation::Set {
+            product_id: item.product_id,
+            quantity: request.quantity,
+        }];
+        let cart_with_items =
+            Self::apply_operations(app_state, cart, owner, &operations, audit).await?;
+
+        record_cart_operation("update_item");
+        Ok(cart_with_items)
+    }
+
+    /// POST /carts/bulk/ - tudo ou nada: qualquer operação inválida descarta as demais
+    pub async fn bulk_update(
+        app_state: &AppState,
+        owner: CartOwner,
+        request: BulkCartRequest,
+        audit: &AuditContext,
+    ) -> Result<CartWithItems, AppError> {
+        if request.operations.is_empty() {
+            return Err(AppError::bad_request("Informe ao menos uma operação"));
+        }
+        if request.operations.len() > MAX_BULK_OPERATIONS {
+            return Err(AppError::bad_request(format!(
+                "Máximo de {} operações por requisição",
+                MAX_BULK_OPERATIONS
+            )));
         }
 
-        if product.tenant_id == tenant_id {
-            return Err(AppError::bad_request("Produto pertence a sua conta"));
+        let cart = Self::get_or_create_cart(app_state, owner, audit).await?;
+        let cart_with_items =
+            Self::apply_operations(app_state, cart, owner, &request.operations, audit).await?;
+
+        record_cart_operation("bulk");
+        Ok(cart_with_items)
+    }
+
+    /// POST /carts/clear/ - remove todas as linhas do carrinho ativo
+    pub async fn clear_cart(
+        app_state: &AppState,
+        owner: CartOwner,
+        audit: &AuditContext,
+    ) -> Result<CartWithItems, AppError> {
+        let cart = Self::find_active(app_state, owner)
+            .await?
+            .ok_or_else(|| AppError::not_found("Carrinho não encontrado"))?;
+
+        let repository = CartRepository::new(app_state);
+        let items = repository.list_cart_items(cart.id).await?;
+        let changes: Vec<CartLineChange> = items
+            .iter()
+            .map(|item| CartLineChange::Delete { item_id: item.id })
+            .collect();
+        if !repository
+            .apply_line_changes(&cart, &changes, &Money::zero(cart.currency.as_str()))
+            .await?
+        {
+            return Err(cart_changed());
         }
 
-        let cart = Self::get_or_create_cart(app_state, tenant_id, user_id).await?;
+        let cart_with_items = Self::reload(app_state, owner).await?;
+        Self::record_audit(
+            app_state,
+            audit,
+            AuditAction::Update,
+            cart.id,
+            Some(&CartAuditSnapshot::from_cart(&cart, &items)),
+            Some(&CartAuditSnapshot::from_cart_with_items(&cart_with_items)),
+        )
+        .await;
+
+        record_cart_operation("clear");
+        Ok(cart_with_items)
+    }
+
+    /// POST /carts/revalidate/ - aplica as divergências atuais: aceita os novos preços ou ajusta
+    /// o carrinho (preços, quantidades ao estoque e remoção das linhas indisponíveis)
+    pub async fn revalidate_cart(
+        app_state: &AppState,
+        owner: CartOwner,
+        request: RevalidateCartRequest,
+        audit: &AuditContext,
+    ) -> Result<CartWithItems, AppError> {
+        let cart = Self::find_active(app_state, owner)
+            .await?
+            .ok_or_else(|| AppError::not_found("Carrinho não encontrado"))?;
+
+        let repository = CartRepository::new(app_state);
+        let items = repository.list_cart_items(cart.id).await?;
+        let (_, warnings) = Self::current_state(app_state, &cart, &items).await?;
 
-        // Verificar se o produto já existe no carrinho
-        let existing_items = repo_cart.list_cart_items(cart.id).await?;
-        let existing_item = existing_items
+        let (changes, subtotal) =
+            plan_revalidation(&items, &warnings, request.mode, &cart.currency)?;
+        if changes.is_empty() {
+            record_cart_operation("revalidate");
+            return Self::reload(app_state, owner).await;
+        }
+        if !repository
+            .apply_line_changes(&cart, &changes, &subtotal)
+            .await?
+        {
+            return Err(cart_changed());
+        }
+
+        let cart_with_items = Self::reload(app_state, owner).await?;
+        Self::record_audit(
+            app_state,
+            audit,
+            AuditAction::Update,
+            cart.id,
+            Some(&CartAuditSnapshot::from_cart(&cart, &items)),
+            Some(&CartAuditSnapshot::from_cart_with_items(&cart_with_items)),
+        )
+        .await;
+
+        record_cart_operation("revalidate");
+        Ok(cart_with_items)
+    }
+
+    /// Tira a linha do carrinho sem gravar: devolve o carrinho, a linha e as alterações, para
+    /// quem grava junto com o destino dela (ex: "salvos para depois")
+    pub async fn plan_line_removal(
+        app_state: &AppState,
+        owner: CartOwner,
+        item_id: Uuid,
+    ) -> Result<(Cart, CartItem, Vec<CartLineChange>, Money), AppError> {
+        let cart = Self::find_active(app_state, owner)
+            .await?
+            .ok_or_else(|| AppError::not_found("Carrinho não encontrado"))?;
+        let items = CartRepository::new(app_state)
+            .list_cart_items(cart.id)
+            .await?;
+        let item = items
             .iter()
-            .find(|item| item.product_id == product_id);
+            .find(|item| item.id == item_id)
+            .cloned()
+            .ok_or_else(|| AppError::not_found("Item não encontrado no carrinho"))?;
 
-        match existing_item {
-            Some(existing_item) => {
-                // PRODUTO JÁ EXISTE: Atualizar quantidade
-                println!("🔄 Produto já existe no carrinho. Atualizando quantidade...");
+        let operations = [CartOperation::Remove {
+            product_id: item.product_id,
+        }];
+        let (changes, subtotal) =
+            plan_line_changes(&items, &operations, &HashMap::new(), &cart.currency)?;
+        Ok((cart, item, changes, subtotal))
+    }
 
-                let new_quantity = existing_item.quantity + quantity;
+    /// Devolve ao carrinho (criado se preciso) uma linha guardada, sem gravar. Produto já no
+    /// carrinho soma a quantidade; linha nova volta com a variante e os atributos guardados.
+    pub async fn plan_line_restore(
+        app_state: &AppState,
+        owner: CartOwner,
+        line: &CartLineSnapshot,
+        audit: &AuditContext,
+    ) -> Result<(Cart, Vec<CartLineChange>, Money), AppError> {
+        let cart = Self::get_or_create_cart(app_state, owner, audit).await?;
+        let operations = [CartOperation::Add {
+            product_id: line.product_id,
+            quantity: line.quantity,
+        }];
+        let (_, mut changes, subtotal) =
+            Self::plan_operations(app_state, &cart, owner, &operations).await?;
 
-                // Verificar estoque novamente com a nova quantidade total
-                if product.stock_quantity < new_quantity {
-                    return Err(AppError::bad_request(
-                        "Estoque insuficiente para a quantidade solicitada",
-                    ));
-                }
+        for change in &mut changes {
+            if let CartLineChange::Insert {
+                product_id,
+                variant_id,
+                attributes_snapshot,
+                ..
+            } = change
+                && *product_id == line.product_id
+            {
+                *variant_id = line.variant_id.unwrap_or_else(Uuid::nil);
+                *attributes_snapshot = line.attributes_snapshot.clone();
+            }
+        }
+        Ok((cart, changes, subtotal))
+    }
+
+    /// Junta o carrinho de visitante ao carrinho ativo da conta (criado se preciso), pelas
+    /// regras CART_MERGE_QUANTITY_RULE e CART_MERGE_STOCK_RULE. Linhas que não podem ir para
+    /// a conta (produto inativo, da própria loja ou sem preço na moeda) ficam de fora.
+    /// Devolve false quando não havia carrinho de visitante ativo; se o carrinho da conta mudar
+    /// no meio, nada é gravado e o carrinho de visitante continua ativo.
+    pub async fn merge_guest_cart(
+        app_state: &AppState,
+        guest_id: Uuid,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<bool, AppError> {
+        let repository = CartRepository::new(app_state);
+        let Some(guest_cart) = repository.find_by_guest_id(guest_id).await? else {
+            return Ok(false);
+        };
+        let guest_items = repository.list_cart_items(guest_cart.id).await?;
+
+        let owner = CartOwner::User { tenant_id, user_id };
+        let cart = Self::get_or_create_cart(app_state, owner, audit).await?;
+        let items = repository.list_cart_items(cart.id).await?;
 
-                // Atualizar quantidade do item existente
-                repo_cart
-                    .update_cart_item_quantity(
-                        existing_item.id,
-                        new_quantity,
-                        (product.price * BigDecimal::from(100))
-                            .to_i64()
-                            .unwrap_or(0),
-                    )
-                    .await?;
-
-                println!(
-                    "✅ Quantidade atualizada: {} -> {}",
-                    existing_item.quantity, new_quantity
-                );
+        let product_ids: Vec<Uuid> = guest_items.iter().map(|item| item.product_id).collect();
+        let mut catalog = HashMap::new();
+        for product in Self::find_products(app_state, &product_ids).await? {
+            match Self::price_product(app_state, &product, owner, &cart.currency).await {
+                Ok(priced) => {
+                    catalog.insert(product.id, priced);
+                }
+                Err(err) => warn!(
+                    product_id = %product.id,
+                    error = %err,
+                    "Linha do carrinho de visitante ignorada na junção"
+                ),
             }
-            None => {
-                // PRODUTO NÃO EXISTE: Criar novo item
-                println!("🆕 Produto não existe no carrinho. Criando novo item...");
-
-                repo_cart
-                    .create_cart_item(
-                        cart.id,
-                        product.id,
-                        Uuid::nil(),
-                        (product.price * BigDecimal::from(100))
-                            .to_i64()
-                            .unwrap_or(0),
-                        quantity,
-                        0,
-                        0,
-                        Json(serde_json::json!({})),
-                    )
-                    .await?;
-
-                println!("✅ Novo item criado com quantidade: {}", quantity);
+        }
+
+        let settings = &get_settings().cart;
+        let operations = plan_merge_operations(
+            &guest_items,
+            &items,
+            &catalog,
+            settings.merge_quantity_rule,
+            settings.merge_stock_rule,
+        );
+        let (changes, subtotal) = plan_line_changes(&items, &operations, &catalog, &cart.currency)?;
+
+        match repository
+            .merge_guest_cart(guest_cart.id, &cart, &changes, &subtotal)
+            .await?
+        {
+            MergeOutcome::Merged => {
+                let cart_with_items = Self::reload(app_state, owner).await?;
+                Self::record_audit(
+                    app_state,
+                    audit,
+                    AuditAction::Merge,
+                    cart.id,
+                    Some(&CartAuditSnapshot::from_cart(&cart, &items)),
+                    Some(&CartAuditSnapshot::from_cart_with_items(&cart_with_items)),
+                )
+                .await;
+
+                record_cart_operation("merge_guest");
+                Ok(true)
             }
+            MergeOutcome::GuestCartClosed => Ok(false),
+            MergeOutcome::CartChanged => Err(cart_changed()),
+        }
+    }
+
+    /// Precifica os produtos envolvidos, planeja as linhas em memória e grava tudo (linhas e
+    /// subtotal, calculado uma única vez) numa transação
+    async fn apply_operations(
+        app_state: &AppState,
+        cart: Cart,
+        owner: CartOwner,
+        operations: &[CartOperation],
+        audit: &AuditContext,
+    ) -> Result<CartWithItems, AppError> {
+        let (items, changes, subtotal) =
+            Self::plan_operations(app_state, &cart, owner, operations).await?;
+        if !CartRepository::new(app_state)
+            .apply_line_changes(&cart, &changes, &subtotal)
+            .await?
+        {
+            return Err(cart_changed());
         }
 
-        // Recalcular totais do carrinho
-        let updated_items = repo_cart.list_cart_items(cart.id).await?;
-        let new_subtotal: BigDecimal = updated_items
+        let cart_with_items = Self::reload(app_state, owner).await?;
+        Self::record_audit(
+            app_state,
+            audit,
+            AuditAction::Update,
+            cart.id,
+            Some(&CartAuditSnapshot::from_cart(&cart, &items)),
+            Some(&CartAuditSnapshot::from_cart_with_items(&cart_with_items)),
+        )
+        .await;
+        Ok(cart_with_items)
+    }
+
+    /// Registra a alteração do carrinho na trilha de auditoria (totais e linhas antes e depois)
+    pub async fn record_audit(
+        app_state: &AppState,
+        audit: &AuditContext,
+        action: AuditAction,
+        cart_id: Uuid,
+        before: Option<&CartAuditSnapshot>,
+        after: Option<&CartAuditSnapshot>,
+    ) {
+        AuditService::record(
+            app_state,
+            audit,
+            action,
+            AuditEntity::Cart,
+            Some(cart_id),
+            before.and_then(AuditService::snapshot),
+            after.and_then(AuditService::snapshot),
+        )
+        .await;
+    }
+
+    /// Linhas atuais, alterações e subtotal das operações, sem gravar
+    async fn plan_operations(
+        app_state: &AppState,
+        cart: &Cart,
+        owner: CartOwner,
+        operations: &[CartOperation],
+    ) -> Result<(Vec<CartItem>, Vec<CartLineChange>, Money), AppError> {
+        let items = CartRepository::new(app_state)
+            .list_cart_items(cart.id)
+            .await?;
+
+        let product_ids: Vec<Uuid> = operations
             .iter()
-            .map(|item| {
-                let item_price = BigDecimal::from(item.unit_price) / BigDecimal::from(100);
-                item_price * BigDecimal::from(item.quantity)
+            .filter_map(|operation| match operation {
+                CartOperation::Add { product_id, .. } => Some(*product_id),
+                CartOperation::Set {
+                    product_id,
+                    quantity,
+                } if *quantity > 0 => Some(*product_id),
+                _ => None,
             })
-            .sum();
-
-        // Atualizar dados do carrinho no banco
-        repo_cart
-            .update_cart_data(
-                cart.id,
-                &new_subtotal,
-                &cart.discount_total,
-                &cart.tax_total,
-                &cart.shipping_total,
-            )
-            .await?;
+            .collect();
+
+        let mut catalog = HashMap::new();
+        for product in Self::find_products(app_state, &product_ids).await? {
+            let priced = Self::price_product(app_state, &product, owner, &cart.currency).await?;
+            catalog.insert(product.id, priced);
+        }
+
+        let (changes, subtotal) = plan_line_changes(&items, operations, &catalog, &cart.currency)?;
+        Ok((items, changes, subtotal))
+    }
 
-        let cart = repo_cart.find_by_tenant_id(tenant_id).await?;
+    /// Produtos ativos entre os informados (sem repetir a consulta para ids duplicados)
+    async fn find_products(
+        app_state: &AppState,
+        product_ids: &[Uuid],
+    ) -> Result<Vec<Product>, AppError> {
+        let mut product_ids = product_ids.to_vec();
+        product_ids.sort();
+        product_ids.dedup();
 
-        let cart = match cart {
-            Some(cart) => cart,
-            None => return Err(AppError::not_found("Produto não encontrado")),
+        if product_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        ProductRepository::new(app_state)
+            .find_by_ids(&product_ids)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    async fn price_product(
+        app_state: &AppState,
+        product: &Product,
+        owner: CartOwner,
+        currency: &str,
+    ) -> Result<PricedProduct, AppError> {
+        if owner.buyer_tenant_id() == Some(product.tenant_id) {
+            return Err(AppError::bad_request("Produto pertence a sua conta"));
+        }
+
+        // Linha é precificada na moeda do carrinho: preço efetivo (promoção vigente ou
+        // preço base), preço fixo da moeda ou conversão pela cotação
+        let unit_price =
+            ProductPriceService::price_in_currency(app_state, product, currency).await?;
+        Ok(PricedProduct {
+            stock_quantity: product.stock_quantity,
+            unit_price,
+        })
+    }
+
+    async fn find_active(app_state: &AppState, owner: CartOwner) -> Result<Option<Cart>, AppError> {
+        let repository = CartRepository::new(app_state);
+        let cart = match owner {
+            CartOwner::User { tenant_id, user_id } => {
+                repository.find_by_user(tenant_id, user_id).await
+            }
+            CartOwner::Guest { guest_id } => repository.find_by_guest_id(guest_id).await,
         };
 
-        let list_cart_items = repo_cart.list_cart_items(cart.id).await?;
+        cart.map_err(|e| AppError::database_error(e.to_string()))
+    }
 
-        // Buscar os produtos dos itens do carrinho
-        let product_ids: Vec<Uuid> = list_cart_items.iter().map(|item| item.product_id).collect();
+    async fn ensure_cart_admin(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+    ) -> Result<(), AppError> {
+        TenantService::ensure_admin(
+            app_state,
+            tenant_id,
+            user_id,
+            access_level,
+            "Acesso negado. Apenas o dono da loja pode consultar os carrinhos dos membros.",
+        )
+        .await
+    }
 
-        let products = if !product_ids.is_empty() {
-            repo_product
-                .find_by_ids(&product_ids)
+    /// Carrinho ativo recém-gravado, com os produtos populados
+    async fn reload(app_state: &AppState, owner: CartOwner) -> Result<CartWithItems, AppError> {
+        let cart = Self::find_active(app_state, owner)
+            .await?
+            .ok_or_else(|| AppError::not_found("Carrinho não encontrado"))?;
+
+        Self::with_items(app_state, cart).await
+    }
+
+    async fn with_items(app_state: &AppState, cart: Cart) -> Result<CartWithItems, AppError> {
+        let list_cart_items = CartRepository::new(app_state)
+            .list_cart_items(cart.id)
+            .await?;
+
+        // Produtos em qualquer situação: inativos continuam na resposta, com aviso
+        let (products, warnings) = Self::current_state(app_state, &cart, &list_cart_items).await?;
+
+        let mut cart_with_items =
+            CartWithItems::from_cart_and_items_with_products(cart, list_cart_items, products);
+        cart_with_items.warnings = warnings;
+        Ok(cart_with_items)
+    }
+
+    /// Produtos das linhas (inclusive inativos e removidos) e as divergências com o estado atual
+    async fn current_state(
+        app_state: &AppState,
+        cart: &Cart,
+        items: &[CartItem],
+    ) -> Result<(Vec<Product>, Vec<CartWarning>), AppError> {
+        let product_ids: Vec<Uuid> = items.iter().map(|item| item.product_id).collect();
+        let products = match product_ids.is_empty() {
+            true => vec![],
+            false => ProductRepository::new(app_state)
+                .find_by_ids_any_status(&product_ids)
                 .await
-                .map_err(|e| AppError::database_error(e.to_string()))?
-        } else {
-            vec![]
+                .map_err(|e| AppError::database_error(e.to_string()))?,
         };
 
-        let cart_with_items =
-            CartWithItems::from_cart_and_items_with_products(cart, list_cart_items, products);
+        let mut states = HashMap::with_capacity(products.len());
+        for product in &products {
+            let available = product.is_active && product.dt_deleted.is_none();
+            let price = match available {
+                true => {
+                    match ProductPriceService::price_in_currency(app_state, product, &cart.currency)
+                        .await
+                    {
+                        Ok(price) => Some(price),
+                        // Sem preço fixo nem cotação para a moeda do carrinho
+                        Err(AppError::BadRequest(_)) => None,
+                        Err(err) => return Err(err),
+                    }
+                }
+                false => None,
+            };
+            states.insert(
+                product.id,
+                ProductState {
+                    available,
+                    stock_quantity: product.stock_quantity,
+                    price,
+                },
+            );
+        }
 
-        Ok(cart_with_items)
+        Ok((products, revalidate_items(items, &states)))
     }
 
-    pub async fn delete_product_cart_by_tenant(
+    async fn get_or_create_cart(
         app_state: &AppState,
-        request: DeleteProductCart,
-        tenant_id: Uuid,
-        user_id: Uuid,
-    ) -> Result<CartWithItems, AppError> {
-        let repo_cart = CartRepository::new(app_state);
+        owner: CartOwner,
+        audit: &AuditContext,
+    ) -> Result<Cart, AppError> {
+        if let Some(cart) = Self::find_active(app_state, owner).await? {
+            return Ok(cart);
+        }
 
-        let product_id = request.product_id;
+        // Carrinho novo na moeda base da loja do comprador (visitante: moeda padrão)
+        let currency = match owner {
+            CartOwner::User { tenant_id, .. } => {
+                TenantService::base_currency(app_state, tenant_id).await?
+            }
+            CartOwner::Guest { .. } => get_settings().cart.guest_currency.clone(),
+        };
+        let cart = CartRepository::new(app_state)
+            .create(&owner, &currency)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
 
-        let cart = Self::get_or_create_cart(app_state, tenant_id, user_id).await?;
+        Self::record_audit(
+            app_state,
+            audit,
+            AuditAction::Create,
+            cart.id,
+            None,
+            Some(&CartAuditSnapshot::from_cart(&cart, &[])),
+        )
+        .await;
+        Ok(cart)
+    }
+}
 
-        // Verificar se o produto existe no carrinho
-        let existing_items = repo_cart.list_cart_items(cart.id).await?;
-        let existing_item = existing_items
-            .iter()
-            .find(|item| item.product_id == product_id);
+/// Gravação planejada sobre uma leitura que outra requisição já alterou
+pub fn cart_changed() -> AppError {
+    AppError::Conflict(Some(
+        "Carrinho alterado por outra requisição; tente novamente".into(),
+    ))
+}
 
-        match existing_item {
-            Some(existing_item) => {
-                // PRODUTO EXISTE: Remover do carrinho
-                println!("🗑️ Removendo produto do carrinho: {}", product_id);
+/// Produto já validado para o carrinho, com o preço na moeda dele
+#[derive(Debug, Clone)]
+pub struct PricedProduct {
+    pub stock_quantity: i32,
+    pub unit_price: Money,
+}
+
+/// Linha no estado final planejado; `item_id` vazio é linha nova
+struct PlannedLine {
+    item_id: Option<Uuid>,
+    product_id: Uuid,
+    unit_price: Money,
+    quantity: i32,
+    touched: bool,
+}
 
-                repo_cart.delete_cart_item(existing_item.id).await?;
+/// Aplica as operações em ordem sobre as linhas atuais e devolve as alterações a gravar e o
+/// subtotal final. Linhas tocadas são reprecificadas e conferidas contra o estoque.
+pub fn plan_line_changes(
+    items: &[CartItem],
+    operations: &[CartOperation],
+    catalog: &HashMap<Uuid, PricedProduct>,
+    currency: &str,
+) -> Result<(Vec<CartLineChange>, Money), AppError> {
+    let mut lines: Vec<PlannedLine> = items
+        .iter()
+        .map(|item| PlannedLine {
+            item_id: Some(item.id),
+            product_id: item.product_id,
+            unit_price: item.unit_price.clone(),
+            quantity: item.quantity,
+            touched: false,
+        })
+        .collect();
+    let mut deleted: Vec<Uuid> = Vec::new();
 
-                println!("✅ Produto removido com sucesso");
+    for operation in operations {
+        match operation {
+            CartOperation::Add {
+                product_id,
+                quantity,
+            } => {
+                if *quantity < 1 {
+                    return Err(AppError::bad_request("Quantidade deve ser maior que zero"));
+                }
+                let line = upsert_line(&mut lines, *product_id, catalog)?;
+                line.quantity = line.quantity.checked_add(*quantity).ok_or_else(|| {
+                    AppError::bad_request("Quantidade fora do intervalo suportado")
+                })?;
+            }
+            CartOperation::Set {
+                product_id,
+                quantity,
+            } => {
+                if *quantity < 0 {
+                    return Err(AppError::bad_request("Quantidade não pode ser negativa"));
+                }
+                if *quantity == 0 {
+                    remove_line(&mut lines, &mut deleted, *product_id);
+                } else {
+                    upsert_line(&mut lines, *product_id, catalog)?.quantity = *quantity;
+                }
+            }
+            CartOperation::Remove { product_id } => {
+                if !remove_line(&mut lines, &mut deleted, *product_id) {
+                    return Err(AppError::not_found("Produto não encontrado no carrinho"));
+                }
             }
-            None => {
-                // PRODUTO NÃO EXISTE: Retornar erro
-                return Err(AppError::not_found("Produto não encontrado no carrinho"));
+        }
+    }
+
+    for line in lines.iter().filter(|line| line.touched) {
+        let stock_quantity = catalog
+            .get(&line.product_id)
+            .map(|product| product.stock_quantity)
+            .unwrap_or(0);
+        if stock_quantity < line.quantity {
+            return Err(AppError::bad_request("Estoque insuficiente"));
+        }
+    }
+
+    let line_totals = lines
+        .iter()
+        .map(|line| line.unit_price.checked_mul(line.quantity as i64))
+        .collect::<Result<Vec<_>, MoneyError>>()?;
+    let subtotal = Money::checked_sum(&line_totals, currency)?;
+
+    let mut changes: Vec<CartLineChange> = deleted
+        .into_iter()
+        .map(|item_id| CartLineChange::Delete { item_id })
+        .collect();
+    changes.extend(
+        lines
+            .into_iter()
+            .filter(|line| line.touched)
+            .map(|line| match line.item_id {
+                Some(item_id) => CartLineChange::Update {
+                    item_id,
+                    unit_price: line.unit_price,
+                    quantity: line.quantity,
+                },
+                None => CartLineChange::Insert {
+                    product_id: line.product_id,
+                    variant_id: Uuid::nil(),
+                    attributes_snapshot: serde_json::json!({}),
+                    unit_price: line.unit_price,
+                    quantity: line.quantity,
+                },
+            }),
+    );
+
+    Ok((changes, subtotal))
+}
+
+/// Linha do produto (criada com quantidade 0 se ainda não existir), já com o preço atual
+fn upsert_line<'a>(
+    lines: &'a mut Vec<PlannedLine>,
+    product_id: Uuid,
+    catalog: &HashMap<Uuid, PricedProduct>,
+) -> Result<&'a mut PlannedLine, AppError> {
+    let product = catalog
+        .get(&product_id)
+        .ok_or_else(|| AppError::not_found("Produto não encontrado"))?;
+
+    let index = match lines.iter().position(|line| line.product_id == product_id) {
+        Some(index) => index,
+        None => {
+            lines.push(PlannedLine {
+                item_id: None,
+                product_id,
+                unit_price: product.unit_price.clone(),
+                quantity: 0,
+                touched: true,
+            });
+            lines.len() - 1
+        }
+    };
+
+    let line = &mut lines[index];
+    line.unit_price = product.unit_price.clone();
+    line.touched = true;
+    Ok(line)
+}
+
+/// Tira a linha do produto do plano; devolve false se ela não existia
+fn remove_line(lines: &mut Vec<PlannedLine>, deleted: &mut Vec<Uuid>, product_id: Uuid) -> bool {
+    match lines.iter().position(|line| line.product_id == product_id) {
+        Some(index) => {
+            if let Some(item_id) = lines.remove(index).item_id {
+                deleted.push(item_id);
             }
+            true
         }
+        None => false,
+    }
+}
+
+/// Operações que levam as linhas do visitante para o carrinho da conta. Só entram produtos
+/// presentes no catálogo (já validados e precificados na moeda da conta).
+pub fn plan_merge_operations(
+    guest_items: &[CartItem],
+    user_items: &[CartItem],
+    catalog: &HashMap<Uuid, PricedProduct>,
+    quantity_rule: CartMergeQuantityRule,
+    stock_rule: CartMergeStockRule,
+) -> Vec<CartOperation> {
+    let mut operations = Vec::new();
 
-        // Recalcular totais do carrinho
-        let updated_items = repo_cart.list_cart_items(cart.id).await?;
-        let new_subtotal: BigDecimal = updated_items
+    for guest_item in guest_items {
+        let Some(product) = catalog.get(&guest_item.product_id) else {
+            continue;
+        };
+        let current = user_items
             .iter()
-            .map(|item| {
-                let item_price = BigDecimal::from(item.unit_price) / BigDecimal::from(100);
-                item_price * BigDecimal::from(item.quantity)
-            })
-            .sum();
-
-        // Atualizar dados do carrinho no banco
-        repo_cart
-            .update_cart_data(
-                cart.id,
-                &new_subtotal,
-                &cart.discount_total,
-                &cart.tax_total,
-                &cart.shipping_total,
-            )
-            .await?;
+            .find(|item| item.product_id == guest_item.product_id)
+            .map(|item| item.quantity)
+            .unwrap_or(0);
 
-        // Buscar carrinho atualizado e retornar com produtos populados
-        let updated_cart = repo_cart.find_by_tenant_id(tenant_id).await?;
-        let cart = match updated_cart {
-            Some(cart) => cart,
-            None => return Err(AppError::not_found("Carrinho não encontrado")),
+        let mut quantity = match quantity_rule {
+            CartMergeQuantityRule::Sum => current.saturating_add(guest_item.quantity),
+            CartMergeQuantityRule::Max => current.max(guest_item.quantity),
+            CartMergeQuantityRule::KeepUser if current > 0 => current,
+            CartMergeQuantityRule::KeepUser | CartMergeQuantityRule::KeepGuest => {
+                guest_item.quantity
+            }
         };
+        if quantity > product.stock_quantity {
+            match stock_rule {
+                CartMergeStockRule::Clamp => quantity = product.stock_quantity,
+                CartMergeStockRule::Skip => continue,
+            }
+        }
+
+        if quantity > 0 && quantity != current {
+            operations.push(CartOperation::Set {
+                product_id: guest_item.product_id,
+                quantity,
+            });
+        }
+    }
 
-        let list_cart_items = repo_cart.list_cart_items(cart.id).await?;
+    operations
+}
 
-        // Buscar os produtos dos itens restantes
-        let product_ids: Vec<Uuid> = list_cart_items.iter().map(|item| item.product_id).collect();
-        let products = if !product_ids.is_empty() {
-            let repo_product = ProductRepository::new(app_state);
-            repo_product
-                .find_by_ids(&product_ids)
-                .await
-                .map_err(|e| AppError::database_error(e.to_string()))?
-        } else {
-            vec![]
+/// Estado atual do produto de uma linha; `price` vazio quando não há preço na moeda do carrinho
+#[derive(Debug, Clone)]
+pub struct ProductState {
+    pub available: bool,
+    pub stock_quantity: i32,
+    pub price: Option<Money>,
+}
+
+/// Compara cada linha com o produto atual. Produto indisponível gera só `unavailable`; os
+/// demais podem gerar aviso de estoque e de preço ao mesmo tempo.
+pub fn revalidate_items(
+    items: &[CartItem],
+    states: &HashMap<Uuid, ProductState>,
+) -> Vec<CartWarning> {
+    let mut warnings = Vec::new();
+
+    for item in items {
+        let (item_id, product_id) = (item.id, item.product_id);
+        let state = states.get(&product_id).filter(|state| state.available);
+        let Some((state, price)) = state.and_then(|s| s.price.as_ref().map(|p| (s, p))) else {
+            warnings.push(CartWarning::Unavailable {
+                item_id,
+                product_id,
+            });
+            continue;
         };
 
-        let cart_with_items =
-            CartWithItems::from_cart_and_items_with_products(cart, list_cart_items, products);
+        if state.stock_quantity <= 0 {
+            warnings.push(CartWarning::OutOfStock {
+                item_id,
+                product_id,
+            });
+        } else if state.stock_quantity < item.quantity {
+            warnings.push(CartWarning::InsufficientStock {
+                item_id,
+                product_id,
+                requested: item.quantity,
+                available: state.stock_quantity,
+            });
+        }
 
-        Ok(cart_with_items)
+        if *price != item.unit_price {
+            warnings.push(CartWarning::PriceChanged {
+                item_id,
+                product_id,
+                old_price: item.unit_price.clone(),
+                new_price: price.clone(),
+            });
+        }
     }
 
-    async fn get_or_create_cart(
-        app_state: &AppState,
-        tenant_id: Uuid,
-        user_id: Uuid,
-    ) -> Result<Cart, AppError> {
-        let repository = CartRepository::new(app_state);
+    warnings
+}
 
-        let cart = repository
-            .find_by_tenant_id(tenant_id)
-            .await
-            .map_err(|e| AppError::database_error(e.to_string()))?;
+/// Alterações que resolvem os avisos conforme o modo, e o subtotal resultante
+pub fn plan_revalidation(
+    items: &[CartItem],
+    warnings: &[CartWarning],
+    mode: RevalidationMode,
+    currency: &str,
+) -> Result<(Vec<CartLineChange>, Money), AppError> {
+    let mut changes = Vec::new();
+    let mut line_totals = Vec::with_capacity(items.len());
+
+    for item in items {
+        let mut unit_price = item.unit_price.clone();
+        let mut quantity = item.quantity;
+        let mut remove = false;
 
-        match cart {
-            Some(cart) => Ok(cart),
-            None => {
-                // Criar novo carrinho
-                repository
-                    .create(tenant_id, user_id)
-                    .await
-                    .map_err(|e| AppError::database_error(e.to_string()))
+        for warning in warnings.iter().filter(|w| w.item_id() == item.id) {
+            match (warning, mode) {
+                (CartWarning::PriceChanged { new_price, .. }, _) => unit_price = new_price.clone(),
+                (CartWarning::InsufficientStock { available, .. }, RevalidationMode::Adjust) => {
+                    quantity = *available
+                }
+                (
+                    CartWarning::OutOfStock { .. } | CartWarning::Unavailable { .. },
+                    RevalidationMode::Adjust,
+                ) => remove = true,
+                _ => {}
             }
         }
+
+        if remove {
+            changes.push(CartLineChange::Delete { item_id: item.id });
+            continue;
+        }
+
+        line_totals.push(unit_price.checked_mul(quantity as i64)?);
+        if unit_price != item.unit_price || quantity != item.quantity {
+            changes.push(CartLineChange::Update {
+                item_id: item.id,
+                unit_price,
+                quantity,
+            });
+        }
     }
+
+    let subtotal = Money::checked_sum(&line_totals, currency)?;
+    Ok((changes, subtotal))
 }
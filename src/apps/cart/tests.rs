@@ -1,7 +1,20 @@
 #[cfg(test)]
+#[allow(unused_imports, clippy::assertions_on_constants)]
 mod tests {
     use super::*;
-    use crate::apps::cart::models::Cart;
+    use crate::app_core::settings::{CartMergeQuantityRule, CartMergeStockRule};
+    use crate::apps::cart::models::{
+        Cart, CartItem, CartLineChange, CartOperation, CartWarning, RevalidationMode,
+    };
+    use crate::apps::cart::services::{
+        PricedProduct, ProductState, plan_line_changes, plan_merge_operations, plan_revalidation,
+        revalidate_items,
+    };
+    use crate::utils::money::Money;
+    use actix_web::ResponseError;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use uuid::Uuid;
 
     #[test]
     fn test_cart_creation() {
@@ -14,4 +27,313 @@ mod tests {
         // Adicione seus testes aqui
         assert!(true);
     }
+
+    fn item(product_id: Uuid, unit_price: i64, quantity: i32) -> CartItem {
+        CartItem {
+            id: Uuid::new_v4(),
+            cart_id: Uuid::nil(),
+            product_id,
+            variant_id: None,
+            unit_price: Money::new(unit_price, "BRL"),
+            quantity,
+            line_discount_total: Money::zero("BRL"),
+            line_tax_total: Money::zero("BRL"),
+            line_total: Money::new(unit_price * quantity as i64, "BRL"),
+            attributes_snapshot: serde_json::json!({}),
+            attributes_hash: String::new(),
+            dt_created: Utc::now(),
+            dt_updated: Utc::now(),
+            dt_deleted: None,
+        }
+    }
+
+    fn priced(unit_price: i64, stock_quantity: i32) -> PricedProduct {
+        PricedProduct {
+            stock_quantity,
+            unit_price: Money::new(unit_price, "BRL"),
+        }
+    }
+
+    #[test]
+    fn test_plan_line_changes_applies_operations_in_order() {
+        let (kept, bumped, removed, added) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let items = vec![
+            item(kept, 500, 1),
+            item(bumped, 1000, 1),
+            item(removed, 300, 2),
+        ];
+        // Preço atual de `bumped` mudou: a linha tocada é reprecificada
+        let catalog = HashMap::from([(bumped, priced(1200, 10)), (added, priced(250, 5))]);
+
+        let operations = vec![
+            CartOperation::Add {
+                product_id: bumped,
+                quantity: 2,
+            },
+            CartOperation::Remove {
+                product_id: removed,
+            },
+            CartOperation::Add {
+                product_id: added,
+                quantity: 1,
+            },
+            CartOperation::Set {
+                product_id: added,
+                quantity: 4,
+            },
+        ];
+        let (changes, subtotal) = plan_line_changes(&items, &operations, &catalog, "BRL").unwrap();
+
+        // 500 + 3 x 1200 + 4 x 250
+        assert_eq!(subtotal, Money::new(5100, "BRL"));
+        assert_eq!(changes.len(), 3);
+        assert!(matches!(
+            &changes[0],
+            CartLineChange::Delete { item_id } if *item_id == items[2].id
+        ));
+        assert!(matches!(
+            &changes[1],
+            CartLineChange::Update { item_id, quantity: 3, unit_price }
+                if *item_id == items[1].id && unit_price.amount_minor == 1200
+        ));
+        assert!(matches!(
+            &changes[2],
+            CartLineChange::Insert { product_id, quantity: 4, .. } if *product_id == added
+        ));
+
+        // Quantidade 0 remove a linha; o carrinho vazio tem subtotal zero
+        let operations: Vec<CartOperation> = items
+            .iter()
+            .map(|item| CartOperation::Set {
+                product_id: item.product_id,
+                quantity: 0,
+            })
+            .collect();
+        let (changes, subtotal) = plan_line_changes(&items, &operations, &catalog, "BRL").unwrap();
+        assert_eq!(changes.len(), 3);
+        assert_eq!(subtotal, Money::zero("BRL"));
+    }
+
+    #[test]
+    fn test_plan_line_changes_rejects_invalid_operations() {
+        let product = Uuid::new_v4();
+        let items = vec![item(product, 500, 1)];
+        let catalog = HashMap::from([(product, priced(500, 3))]);
+        let plan =
+            |operation: CartOperation| plan_line_changes(&items, &[operation], &catalog, "BRL");
+
+        let status = |result: Result<_, crate::app_core::app_error::AppError>| {
+            result.err().map(|e| e.status_code().as_u16())
+        };
+        assert_eq!(
+            status(plan(CartOperation::Add {
+                product_id: product,
+                quantity: 3
+            })),
+            Some(400)
+        );
+        assert_eq!(
+            status(plan(CartOperation::Add {
+                product_id: product,
+                quantity: 0
+            })),
+            Some(400)
+        );
+        assert_eq!(
+            status(plan(CartOperation::Set {
+                product_id: product,
+                quantity: -1
+            })),
+            Some(400)
+        );
+        assert_eq!(
+            status(plan(CartOperation::Remove {
+                product_id: Uuid::new_v4()
+            })),
+            Some(404)
+        );
+        assert_eq!(
+            status(plan(CartOperation::Add {
+                product_id: Uuid::new_v4(),
+                quantity: 1
+            })),
+            Some(404)
+        );
+        assert_eq!(
+            status(plan(CartOperation::Set {
+                product_id: product,
+                quantity: 3
+            })),
+            None
+        );
+    }
+
+    #[test]
+    fn test_plan_merge_operations_rules() {
+        let (shared, guest_only, unavailable) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let guest_items = vec![
+            item(shared, 500, 3),
+            item(guest_only, 200, 2),
+            item(unavailable, 100, 1),
+        ];
+        let user_items = vec![item(shared, 500, 2)];
+        // `unavailable` não está no catálogo (inativo, da própria loja ou sem preço)
+        let catalog = HashMap::from([(shared, priced(500, 4)), (guest_only, priced(200, 10))]);
+
+        let plan = |quantity_rule, stock_rule| {
+            plan_merge_operations(
+                &guest_items,
+                &user_items,
+                &catalog,
+                quantity_rule,
+                stock_rule,
+            )
+            .into_iter()
+            .map(|operation| match operation {
+                CartOperation::Set {
+                    product_id,
+                    quantity,
+                } => (product_id, quantity),
+                other => panic!("Operação inesperada: {:?}", other),
+            })
+            .collect::<Vec<_>>()
+        };
+
+        // Soma 2 + 3 passa do estoque (4): limita ou ignora a linha do visitante
+        assert_eq!(
+            plan(CartMergeQuantityRule::Sum, CartMergeStockRule::Clamp),
+            vec![(shared, 4), (guest_only, 2)]
+        );
+        assert_eq!(
+            plan(CartMergeQuantityRule::Sum, CartMergeStockRule::Skip),
+            vec![(guest_only, 2)]
+        );
+        assert_eq!(
+            plan(CartMergeQuantityRule::Max, CartMergeStockRule::Clamp),
+            vec![(shared, 3), (guest_only, 2)]
+        );
+        assert_eq!(
+            plan(CartMergeQuantityRule::KeepUser, CartMergeStockRule::Clamp),
+            vec![(guest_only, 2)]
+        );
+        assert_eq!(
+            plan(CartMergeQuantityRule::KeepGuest, CartMergeStockRule::Clamp),
+            vec![(shared, 3), (guest_only, 2)]
+        );
+    }
+
+    fn state(price: Option<i64>, stock_quantity: i32, available: bool) -> ProductState {
+        ProductState {
+            available,
+            stock_quantity,
+            price: price.map(|price| Money::new(price, "BRL")),
+        }
+    }
+
+    #[test]
+    fn test_revalidate_items_detects_changes() {
+        let (same, repriced, low, empty, inactive, unpriced, deleted) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let items = vec![
+            item(same, 100, 2),
+            item(repriced, 100, 1),
+            item(low, 100, 5),
+            item(empty, 100, 1),
+            item(inactive, 100, 1),
+            item(unpriced, 100, 1),
+            item(deleted, 100, 1),
+        ];
+        let states = HashMap::from([
+            (same, state(Some(100), 2, true)),
+            (repriced, state(Some(90), 10, true)),
+            (low, state(Some(100), 3, true)),
+            (empty, state(Some(100), 0, true)),
+            (inactive, state(Some(100), 10, false)),
+            (unpriced, state(None, 10, true)),
+        ]);
+
+        let warnings = revalidate_items(&items, &states);
+
+        let summary: Vec<(Uuid, &str)> = warnings
+            .iter()
+            .map(|warning| match warning {
+                CartWarning::PriceChanged { product_id, .. } => (*product_id, "price"),
+                CartWarning::InsufficientStock { product_id, .. } => (*product_id, "stock"),
+                CartWarning::OutOfStock { product_id, .. } => (*product_id, "out"),
+                CartWarning::Unavailable { product_id, .. } => (*product_id, "unavailable"),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (repriced, "price"),
+                (low, "stock"),
+                (empty, "out"),
+                (inactive, "unavailable"),
+                (unpriced, "unavailable"),
+                (deleted, "unavailable"),
+            ]
+        );
+        assert!(matches!(
+            &warnings[1],
+            CartWarning::InsufficientStock {
+                requested: 5,
+                available: 3,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_plan_revalidation_modes() {
+        let (repriced, low, gone) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let items = vec![item(repriced, 100, 2), item(low, 50, 4), item(gone, 300, 1)];
+        let states = HashMap::from([
+            (repriced, state(Some(120), 10, true)),
+            (low, state(Some(60), 1, true)),
+            (gone, state(Some(300), 0, true)),
+        ]);
+        let warnings = revalidate_items(&items, &states);
+
+        // Aceitar preços: só o preço unitário muda
+        let (changes, subtotal) =
+            plan_revalidation(&items, &warnings, RevalidationMode::AcceptPrices, "BRL").unwrap();
+        assert_eq!(subtotal, Money::new(120 * 2 + 60 * 4 + 300, "BRL"));
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().all(|change| matches!(
+            change,
+            CartLineChange::Update { quantity, .. } if *quantity == 2 || *quantity == 4
+        )));
+
+        // Ajustar: também limita ao estoque e remove o que esgotou
+        let (changes, subtotal) =
+            plan_revalidation(&items, &warnings, RevalidationMode::Adjust, "BRL").unwrap();
+        assert_eq!(subtotal, Money::new(120 * 2 + 60, "BRL"));
+        assert!(matches!(
+            &changes[1],
+            CartLineChange::Update { quantity: 1, unit_price, .. } if *unit_price == Money::new(60, "BRL")
+        ));
+        assert!(matches!(
+            &changes[2],
+            CartLineChange::Delete { item_id } if *item_id == items[2].id
+        ));
+
+        // Sem avisos não há o que alterar
+        let (changes, subtotal) =
+            plan_revalidation(&items, &[], RevalidationMode::Adjust, "BRL").unwrap();
+        assert!(changes.is_empty());
+        assert_eq!(subtotal, Money::new(200 + 200 + 300, "BRL"));
+    }
 }
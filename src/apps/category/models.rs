@@ -0,0 +1,171 @@
+use crate::utils::validation::validate_slug;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Profundidade máxima da árvore (raiz = 0)
+pub const MAX_CATEGORY_DEPTH: i32 = 8;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Category {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    /// Materialized path com os ids da raiz até a própria categoria: "/<raiz>/.../<id>/"
+    pub path: String,
+    pub depth: i32,
+    pub position: i32,
+    pub is_active: bool,
+    pub dt_created: DateTime<Utc>,
+    pub dt_updated: DateTime<Utc>,
+    pub dt_deleted: Option<DateTime<Utc>>,
+}
+
+impl Category {
+    /// Path de uma categoria a partir do path do pai
+    pub fn build_path(parent_path: Option<&str>, id: Uuid) -> String {
+        format!("{}{}/", parent_path.unwrap_or("/"), id)
+    }
+
+    /// Ids da raiz até a própria categoria, na ordem do path
+    pub fn ancestor_ids(&self) -> Vec<Uuid> {
+        self.path
+            .split('/')
+            .filter_map(|segment| Uuid::parse_str(segment).ok())
+            .collect()
+    }
+
+    /// Verdadeiro se `other` está na subárvore desta categoria (incluindo ela mesma)
+    pub fn contains(&self, other: &Category) -> bool {
+        other.path.starts_with(&self.path)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CategoryBreadcrumb {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+}
+
+impl From<&Category> for CategoryBreadcrumb {
+    fn from(category: &Category) -> Self {
+        Self {
+            id: category.id,
+            name: category.name.clone(),
+            slug: category.slug.clone(),
+        }
+    }
+}
+
+/// Breadcrumbs (raiz -> categoria) a partir de um mapa com os ancestrais já carregados
+pub fn breadcrumbs_for(
+    category: &Category,
+    ancestors: &HashMap<Uuid, Category>,
+) -> Vec<CategoryBreadcrumb> {
+    category
+        .ancestor_ids()
+        .iter()
+        .filter_map(|id| ancestors.get(id))
+        .map(CategoryBreadcrumb::from)
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+pub struct CategoryNode {
+    #[serde(flatten)]
+    pub category: Category,
+    pub children: Vec<CategoryNode>,
+}
+
+/// Monta a árvore a partir da lista plana, ordenando irmãos por posição e nome
+pub fn build_tree(categories: Vec<Category>) -> Vec<CategoryNode> {
+    let mut by_parent: HashMap<Option<Uuid>, Vec<Category>> = HashMap::new();
+    for category in categories {
+        by_parent
+            .entry(category.parent_id)
+            .or_default()
+            .push(category);
+    }
+
+    fn attach(
+        parent_id: Option<Uuid>,
+        by_parent: &mut HashMap<Option<Uuid>, Vec<Category>>,
+    ) -> Vec<CategoryNode> {
+        let mut children = by_parent.remove(&parent_id).unwrap_or_default();
+        children.sort_by(|a, b| a.position.cmp(&b.position).then(a.name.cmp(&b.name)));
+
+        children
+            .into_iter()
+            .map(|category| {
+                let children = attach(Some(category.id), by_parent);
+                CategoryNode { category, children }
+            })
+            .collect()
+    }
+
+    attach(None, &mut by_parent)
+}
+
+#[derive(Debug, Serialize)]
+pub struct CategoryDetail {
+    #[serde(flatten)]
+    pub category: Category,
+    pub breadcrumbs: Vec<CategoryBreadcrumb>,
+    pub children: Vec<Category>,
+}
+
+/// Categoria atribuída a um produto, com o caminho até a raiz
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductCategory {
+    pub id: Uuid,
+    pub name: String,
+    pub slug: String,
+    pub breadcrumbs: Vec<CategoryBreadcrumb>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateCategoryRequest {
+    #[validate(length(
+        min = 1,
+        max = 120,
+        message = "O nome deve ter entre 1 e 120 caracteres"
+    ))]
+    pub name: String,
+    /// Gerado a partir do nome quando não informado
+    #[validate(custom = "validate_slug")]
+    pub slug: Option<String>,
+    pub parent_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub position: Option<i32>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateCategoryRequest {
+    #[validate(length(
+        min = 1,
+        max = 120,
+        message = "O nome deve ter entre 1 e 120 caracteres"
+    ))]
+    pub name: Option<String>,
+    #[validate(custom = "validate_slug")]
+    pub slug: Option<String>,
+    /// Ausente: mantém o pai; `null`: move para a raiz; id: move para baixo de outra categoria
+    #[serde(default, with = "::serde_with::rust::double_option")]
+    pub parent_id: Option<Option<Uuid>>,
+    pub description: Option<String>,
+    pub position: Option<i32>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetProductCategoriesRequest {
+    #[validate(length(max = 50, message = "Um produto pode ter no máximo 50 categorias"))]
+    pub category_ids: Vec<Uuid>,
+}
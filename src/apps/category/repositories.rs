@@ -0,0 +1,335 @@
+use crate::app_core::app_state::AppState;
+use crate::apps::category::models::Category;
+use chrono::{DateTime, Utc};
+use tracing::instrument;
+use uuid::Uuid;
+
+pub struct CategoryRepository<'a> {
+    app_state: &'a AppState,
+}
+
+impl<'a> CategoryRepository<'a> {
+    pub fn new(app_state: &'a AppState) -> Self {
+        Self { app_state }
+    }
+
+    #[instrument(name = "CategoryRepository::find_all", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_all(&self, tenant_id: Uuid) -> Result<Vec<Category>, sqlx::Error> {
+        sqlx::query_as!(
+            Category,
+            r#"
+            SELECT
+                id, tenant_id, parent_id, name, slug, description, path, depth, position, is_active,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>",
+                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            FROM categories
+            WHERE tenant_id = $1 AND dt_deleted IS NULL
+            ORDER BY depth, position, name
+            "#,
+            tenant_id
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "CategoryRepository::find_by_id", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_id(
+        &self,
+        id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<Option<Category>, sqlx::Error> {
+        sqlx::query_as!(
+            Category,
+            r#"
+            SELECT
+                id, tenant_id, parent_id, name, slug, description, path, depth, position, is_active,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>",
+                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            FROM categories
+            WHERE id = $1 AND tenant_id = $2 AND dt_deleted IS NULL
+            "#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "CategoryRepository::find_by_ids", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Category>, sqlx::Error> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        sqlx::query_as!(
+            Category,
+            r#"
+            SELECT
+                id, tenant_id, parent_id, name, slug, description, path, depth, position, is_active,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>",
+                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            FROM categories
+            WHERE id = ANY($1) AND dt_deleted IS NULL
+            "#,
+            ids
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "CategoryRepository::find_children", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_children(&self, id: Uuid) -> Result<Vec<Category>, sqlx::Error> {
+        sqlx::query_as!(
+            Category,
+            r#"
+            SELECT
+                id, tenant_id, parent_id, name, slug, description, path, depth, position, is_active,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>",
+                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            FROM categories
+            WHERE parent_id = $1 AND dt_deleted IS NULL
+            ORDER BY position, name
+            "#,
+            id
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    /// Maior profundidade relativa dentro da subárvore (0 quando não há descendentes)
+    #[instrument(name = "CategoryRepository::subtree_height", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn subtree_height(&self, category: &Category) -> Result<i32, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"
+            SELECT COALESCE(MAX(depth), $3) as "max_depth!"
+            FROM categories
+            WHERE tenant_id = $1 AND path LIKE $2 || '%' AND dt_deleted IS NULL
+            "#,
+            category.tenant_id,
+            category.path,
+            category.depth
+        )
+        .fetch_one(&self.app_state.db)
+        .await?;
+
+        Ok(row.max_depth - category.depth)
+    }
+
+    #[instrument(name = "CategoryRepository::create", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create(&self, category: &Category) -> Result<Category, sqlx::Error> {
+        sqlx::query_as!(
+            Category,
+            r#"
+            INSERT INTO categories (
+                id, tenant_id, parent_id, name, slug, description, path, depth, position,
+                is_active, dt_created, dt_updated
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, NOW(), NOW())
+            RETURNING
+                id, tenant_id, parent_id, name, slug, description, path, depth, position, is_active,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>",
+                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            "#,
+            category.id,
+            category.tenant_id,
+            category.parent_id,
+            category.name,
+            category.slug,
+            category.description,
+            category.path,
+            category.depth,
+            category.position,
+            category.is_active
+        )
+        .fetch_one(&self.app_state.db)
+        .await
+    }
+
+    /// Atualiza a categoria e, se ela mudou de pai, reescreve o path de toda a subárvore
+    #[instrument(name = "CategoryRepository::update", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn update(
+        &self,
+        category: &Category,
+        old_path: &str,
+        old_depth: i32,
+    ) -> Result<Category, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+
+        let updated = sqlx::query_as!(
+            Category,
+            r#"
+            UPDATE categories
+            SET parent_id = $3, name = $4, slug = $5, description = $6, path = $7, depth = $8,
+                position = $9, is_active = $10, dt_updated = NOW()
+            WHERE id = $1 AND tenant_id = $2 AND dt_deleted IS NULL
+            RETURNING
+                id, tenant_id, parent_id, name, slug, description, path, depth, position, is_active,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>",
+                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            "#,
+            category.id,
+            category.tenant_id,
+            category.parent_id,
+            category.name,
+            category.slug,
+            category.description,
+            category.path,
+            category.depth,
+            category.position,
+            category.is_active
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if updated.path != old_path {
+            sqlx::query!(
+                r#"
+                UPDATE categories
+                SET path = $3 || substr(path, length($2) + 1),
+                    depth = depth + $4,
+                    dt_updated = NOW()
+                WHERE tenant_id = $1 AND path LIKE $2 || '%' AND id <> $5
+                "#,
+                updated.tenant_id,
+                old_path,
+                updated.path,
+                updated.depth - old_depth,
+                updated.id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(updated)
+    }
+
+    #[instrument(name = "CategoryRepository::has_children", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn has_children(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM categories WHERE parent_id = $1 AND dt_deleted IS NULL
+            ) as "exists!"
+            "#,
+            id
+        )
+        .fetch_one(&self.app_state.db)
+        .await?;
+
+        Ok(row.exists)
+    }
+
+    #[instrument(name = "CategoryRepository::delete", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete(&self, id: Uuid, tenant_id: Uuid) -> Result<bool, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+
+        let result = sqlx::query!(
+            "UPDATE categories SET dt_deleted = NOW() WHERE id = $1 AND tenant_id = $2 AND dt_deleted IS NULL",
+            id,
+            tenant_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // Produtos deixam de pertencer à categoria removida
+        sqlx::query!("DELETE FROM product_categories WHERE category_id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Categorias atribuídas a cada produto: pares (product_id, categoria)
+    #[instrument(name = "CategoryRepository::find_by_products", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_products(
+        &self,
+        product_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Category)>, sqlx::Error> {
+        if product_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                pc.product_id,
+                c.id, c.tenant_id, c.parent_id, c.name, c.slug, c.description, c.path, c.depth,
+                c.position, c.is_active,
+                (c.dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (c.dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>",
+                (c.dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            FROM product_categories pc
+            JOIN categories c ON c.id = pc.category_id
+            WHERE pc.product_id = ANY($1) AND c.dt_deleted IS NULL
+            ORDER BY c.depth, c.position, c.name
+            "#,
+            product_ids
+        )
+        .fetch_all(&self.app_state.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    row.product_id,
+                    Category {
+                        id: row.id,
+                        tenant_id: row.tenant_id,
+                        parent_id: row.parent_id,
+                        name: row.name,
+                        slug: row.slug,
+                        description: row.description,
+                        path: row.path,
+                        depth: row.depth,
+                        position: row.position,
+                        is_active: row.is_active,
+                        dt_created: row.dt_created,
+                        dt_updated: row.dt_updated,
+                        dt_deleted: row.dt_deleted,
+                    },
+                )
+            })
+            .collect())
+    }
+
+    /// Substitui as categorias de um produto
+    #[instrument(name = "CategoryRepository::set_product_categories", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn set_product_categories(
+        &self,
+        product_id: Uuid,
+        category_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+
+        sqlx::query!(
+            "DELETE FROM product_categories WHERE product_id = $1",
+            product_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO product_categories (product_id, category_id, dt_created)
+            SELECT $1, category_id, NOW() FROM UNNEST($2::uuid[]) AS category_id
+            ON CONFLICT DO NOTHING
+            "#,
+            product_id,
+            category_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_extensions::RequestUserExt;
+use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::AuditContext;
+use crate::apps::category::models::{
+    CreateCategoryRequest, SetProductCategoriesRequest, UpdateCategoryRequest,
+};
+use crate::apps::category::services::CategoryService;
+use actix_web::web::Json;
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+use uuid::Uuid;
+
+pub async fn list_categories(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let result = CategoryService::list_categories(&app_state, tenant_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn get_category(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let result = CategoryService::get_category(&app_state, id, tenant_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn create_category(
+    app_state: web::Data<AppState>,
+    payload: Json<CreateCategoryRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let audit = AuditContext::from_request(&req);
+    let dto = payload.into_inner();
+    let result = CategoryService::create_category(&app_state, dto, tenant_id, &audit).await?;
+
+    Ok(HttpResponse::Created().json(serde_json::json!(result)))
+}
+
+pub async fn update_category(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Json<UpdateCategoryRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let audit = AuditContext::from_request(&req);
+    let dto = payload.into_inner();
+    let result = CategoryService::update_category(&app_state, id, tenant_id, dto, &audit).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn delete_category(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let audit = AuditContext::from_request(&req);
+    CategoryService::delete_category(&app_state, id, tenant_id, &audit).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn set_product_categories(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Json<SetProductCategoriesRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let product_id = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let audit = AuditContext::from_request(&req);
+    let dto = payload.into_inner();
+    let result =
+        CategoryService::set_product_categories(&app_state, product_id, tenant_id, dto, &audit)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
@@ -0,0 +1,385 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::{AuditAction, AuditContext, AuditEntity};
+use crate::apps::audit::services::AuditService;
+use crate::apps::category::models::{
+    Category, CategoryDetail, CategoryNode, CreateCategoryRequest, MAX_CATEGORY_DEPTH,
+    ProductCategory, SetProductCategoriesRequest, UpdateCategoryRequest, breadcrumbs_for,
+    build_tree,
+};
+use crate::apps::category::repositories::CategoryRepository;
+use crate::apps::product::repositories::ProductRepository;
+use crate::utils::formatter::slugify;
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Slug duplicado vira 409 com mensagem específica; demais erros seguem como erro de banco
+fn map_write_error(e: sqlx::Error) -> AppError {
+    match e.as_database_error().and_then(|db| db.code()) {
+        Some(code) if code == "23505" => {
+            AppError::Conflict(Some("Já existe uma categoria com este slug".into()))
+        }
+        _ => AppError::database_error(e.to_string()),
+    }
+}
+
+pub struct CategoryService;
+
+impl CategoryService {
+    pub async fn list_categories(
+        app_state: &AppState,
+        tenant_id: Uuid,
+    ) -> Result<Vec<CategoryNode>, AppError> {
+        let repository = CategoryRepository::new(app_state);
+        let categories = repository
+            .find_all(tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        Ok(build_tree(categories))
+    }
+
+    pub async fn get_category(
+        app_state: &AppState,
+        id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<CategoryDetail, AppError> {
+        let repository = CategoryRepository::new(app_state);
+        let category = Self::find_category(&repository, id, tenant_id).await?;
+
+        let ancestors = Self::load_ancestors(&repository, std::slice::from_ref(&category)).await?;
+        let children = repository
+            .find_children(category.id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        Ok(CategoryDetail {
+            breadcrumbs: breadcrumbs_for(&category, &ancestors),
+            category,
+            children,
+        })
+    }
+
+    pub async fn create_category(
+        app_state: &AppState,
+        request: CreateCategoryRequest,
+        tenant_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<Category, AppError> {
+        request.validate()?;
+        let repository = CategoryRepository::new(app_state);
+
+        let parent = match request.parent_id {
+            Some(parent_id) => Some(
+                Self::find_category(&repository, parent_id, tenant_id)
+                    .await
+                    .map_err(|_| AppError::bad_request("Categoria pai não encontrada"))?,
+            ),
+            None => None,
+        };
+
+        let depth = parent.as_ref().map(|p| p.depth + 1).unwrap_or(0);
+        if depth > MAX_CATEGORY_DEPTH {
+            return Err(AppError::bad_request(format!(
+                "A árvore de categorias permite no máximo {} níveis",
+                MAX_CATEGORY_DEPTH + 1
+            )));
+        }
+
+        let slug = request.slug.unwrap_or_else(|| slugify(&request.name));
+        if slug.is_empty() {
+            return Err(AppError::bad_request(
+                "Não foi possível gerar o slug a partir do nome; informe o slug",
+            ));
+        }
+
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        let category = Category {
+            id,
+            tenant_id,
+            parent_id: parent.as_ref().map(|p| p.id),
+            name: request.name,
+            slug,
+            description: request.description,
+            path: Category::build_path(parent.as_ref().map(|p| p.path.as_str()), id),
+            depth,
+            position: request.position.unwrap_or(0),
+            is_active: request.is_active.unwrap_or(true),
+            dt_created: now,
+            dt_updated: now,
+            dt_deleted: None,
+        };
+
+        let category = repository
+            .create(&category)
+            .await
+            .map_err(map_write_error)?;
+
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Create,
+            AuditEntity::Category,
+            Some(category.id),
+            None,
+            AuditService::snapshot(&category),
+        )
+        .await;
+
+        Ok(category)
+    }
+
+    pub async fn update_category(
+        app_state: &AppState,
+        id: Uuid,
+        tenant_id: Uuid,
+        request: UpdateCategoryRequest,
+        audit: &AuditContext,
+    ) -> Result<Category, AppError> {
+        request.validate()?;
+        let repository = CategoryRepository::new(app_state);
+        let before = Self::find_category(&repository, id, tenant_id).await?;
+        let mut category = before.clone();
+
+        if let Some(name) = request.name {
+            category.name = name;
+        }
+        if let Some(slug) = request.slug {
+            category.slug = slug;
+        }
+        if let Some(description) = request.description {
+            category.description = Some(description);
+        }
+        if let Some(position) = request.position {
+            category.position = position;
+        }
+        if let Some(is_active) = request.is_active {
+            category.is_active = is_active;
+        }
+
+        // Mudança de pai: recalcula path/profundidade e valida ciclos e limite de níveis
+        if let Some(parent_id) = request.parent_id
+            && parent_id != before.parent_id
+        {
+            let parent = match parent_id {
+                Some(parent_id) => {
+                    let parent = Self::find_category(&repository, parent_id, tenant_id)
+                        .await
+                        .map_err(|_| AppError::bad_request("Categoria pai não encontrada"))?;
+                    if before.contains(&parent) {
+                        return Err(AppError::bad_request(
+                            "Uma categoria não pode ser movida para dentro dela mesma",
+                        ));
+                    }
+                    Some(parent)
+                }
+                None => None,
+            };
+
+            let height = repository
+                .subtree_height(&before)
+                .await
+                .map_err(|e| AppError::database_error(e.to_string()))?;
+            let depth = parent.as_ref().map(|p| p.depth + 1).unwrap_or(0);
+            if depth + height > MAX_CATEGORY_DEPTH {
+                return Err(AppError::bad_request(format!(
+                    "A árvore de categorias permite no máximo {} níveis",
+                    MAX_CATEGORY_DEPTH + 1
+                )));
+            }
+
+            category.parent_id = parent.as_ref().map(|p| p.id);
+            category.path = Category::build_path(parent.as_ref().map(|p| p.path.as_str()), id);
+            category.depth = depth;
+        }
+
+        let category = repository
+            .update(&category, &before.path, before.depth)
+            .await
+            .map_err(map_write_error)?;
+
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Update,
+            AuditEntity::Category,
+            Some(category.id),
+            AuditService::snapshot(&before),
+            AuditService::snapshot(&category),
+        )
+        .await;
+
+        Ok(category)
+    }
+
+    pub async fn delete_category(
+        app_state: &AppState,
+        id: Uuid,
+        tenant_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<bool, AppError> {
+        let repository = CategoryRepository::new(app_state);
+        let before = Self::find_category(&repository, id, tenant_id).await?;
+
+        let has_children = repository
+            .has_children(id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        if has_children {
+            return Err(AppError::Conflict(Some(
+                "Remova ou mova as subcategorias antes de excluir a categoria".into(),
+            )));
+        }
+
+        let deleted = repository
+            .delete(id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        match deleted {
+            true => {
+                AuditService::record(
+                    app_state,
+                    audit,
+                    AuditAction::Delete,
+                    AuditEntity::Category,
+                    Some(id),
+                    AuditService::snapshot(&before),
+                    None,
+                )
+                .await;
+
+                Ok(deleted)
+            }
+            false => Err(AppError::not_found("Categoria não encontrada")),
+        }
+    }
+
+    /// Substitui as categorias de um produto do tenant
+    pub async fn set_product_categories(
+        app_state: &AppState,
+        product_id: Uuid,
+        tenant_id: Uuid,
+        request: SetProductCategoriesRequest,
+        audit: &AuditContext,
+    ) -> Result<Vec<ProductCategory>, AppError> {
+        request.validate()?;
+
+        let product = ProductRepository::new(app_state)
+            .find_by_id(product_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        if product.is_none_or(|p| p.tenant_id != tenant_id) {
+            return Err(AppError::not_found("Produto não encontrado"));
+        }
+
+        let category_ids: Vec<Uuid> = request
+            .category_ids
+            .into_iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        let repository = CategoryRepository::new(app_state);
+        let found = repository
+            .find_by_ids(&category_ids)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        if found.len() != category_ids.len() || found.iter().any(|c| c.tenant_id != tenant_id) {
+            return Err(AppError::bad_request("Categoria inválida para este tenant"));
+        }
+
+        let before = Self::categories_for_products(app_state, &[product_id])
+            .await?
+            .remove(&product_id)
+            .unwrap_or_default();
+
+        repository
+            .set_product_categories(product_id, &category_ids)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        let after = Self::categories_for_products(app_state, &[product_id])
+            .await?
+            .remove(&product_id)
+            .unwrap_or_default();
+
+        let category_ids = |categories: &[ProductCategory]| {
+            let ids: Vec<Uuid> = categories.iter().map(|c| c.id).collect();
+            Some(serde_json::json!({ "category_ids": ids }))
+        };
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Update,
+            AuditEntity::Product,
+            Some(product_id),
+            category_ids(&before),
+            category_ids(&after),
+        )
+        .await;
+
+        Ok(after)
+    }
+
+    /// Categorias (com breadcrumbs) de cada produto, carregadas em lote
+    pub async fn categories_for_products(
+        app_state: &AppState,
+        product_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<ProductCategory>>, AppError> {
+        let repository = CategoryRepository::new(app_state);
+        let assignments = repository
+            .find_by_products(product_ids)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        let categories: Vec<Category> = assignments.iter().map(|(_, c)| c.clone()).collect();
+        let ancestors = Self::load_ancestors(&repository, &categories).await?;
+
+        let mut result: HashMap<Uuid, Vec<ProductCategory>> = HashMap::new();
+        for (product_id, category) in assignments {
+            result.entry(product_id).or_default().push(ProductCategory {
+                id: category.id,
+                breadcrumbs: breadcrumbs_for(&category, &ancestors),
+                name: category.name,
+                slug: category.slug,
+            });
+        }
+
+        Ok(result)
+    }
+
+    async fn find_category(
+        repository: &CategoryRepository<'_>,
+        id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<Category, AppError> {
+        repository
+            .find_by_id(id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Categoria não encontrada"))
+    }
+
+    async fn load_ancestors(
+        repository: &CategoryRepository<'_>,
+        categories: &[Category],
+    ) -> Result<HashMap<Uuid, Category>, AppError> {
+        let ids: Vec<Uuid> = categories
+            .iter()
+            .flat_map(|c| c.ancestor_ids())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        let ancestors = repository
+            .find_by_ids(&ids)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        Ok(ancestors.into_iter().map(|c| (c.id, c)).collect())
+    }
+}
@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use crate::apps::category::models::{
+        Category, CreateCategoryRequest, UpdateCategoryRequest, breadcrumbs_for, build_tree,
+    };
+    use crate::utils::formatter::slugify;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+    use validator::Validate;
+
+    fn category(name: &str, parent: Option<&Category>, position: i32) -> Category {
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        Category {
+            id,
+            tenant_id: Uuid::nil(),
+            parent_id: parent.map(|p| p.id),
+            name: name.to_string(),
+            slug: slugify(name),
+            description: None,
+            path: Category::build_path(parent.map(|p| p.path.as_str()), id),
+            depth: parent.map(|p| p.depth + 1).unwrap_or(0),
+            position,
+            is_active: true,
+            dt_created: now,
+            dt_updated: now,
+            dt_deleted: None,
+        }
+    }
+
+    #[test]
+    fn test_path_and_ancestors() {
+        let root = category("Moda", None, 0);
+        let child = category("Feminina", Some(&root), 0);
+        let leaf = category("Vestidos", Some(&child), 0);
+
+        assert_eq!(root.path, format!("/{}/", root.id));
+        assert_eq!(leaf.ancestor_ids(), vec![root.id, child.id, leaf.id]);
+        assert!(root.contains(&leaf));
+        assert!(root.contains(&root));
+        assert!(!leaf.contains(&root));
+    }
+
+    #[test]
+    fn test_breadcrumbs_follow_path_order() {
+        let root = category("Moda", None, 0);
+        let child = category("Feminina", Some(&root), 0);
+        let leaf = category("Vestidos", Some(&child), 0);
+
+        let ancestors: HashMap<Uuid, Category> = [leaf.clone(), root.clone(), child.clone()]
+            .into_iter()
+            .map(|c| (c.id, c))
+            .collect();
+
+        let slugs: Vec<String> = breadcrumbs_for(&leaf, &ancestors)
+            .into_iter()
+            .map(|b| b.slug)
+            .collect();
+        assert_eq!(slugs, vec!["moda", "feminina", "vestidos"]);
+    }
+
+    #[test]
+    fn test_build_tree_orders_siblings_by_position() {
+        let root = category("Casa", None, 0);
+        let second = category("Banheiro", Some(&root), 2);
+        let first = category("Cozinha", Some(&root), 1);
+        let other_root = category("Esporte", None, 1);
+
+        let tree = build_tree(vec![second, other_root, root, first]);
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].category.name, "Casa");
+        let children: Vec<&str> = tree[0]
+            .children
+            .iter()
+            .map(|n| n.category.name.as_str())
+            .collect();
+        assert_eq!(children, vec!["Cozinha", "Banheiro"]);
+        assert!(tree[1].children.is_empty());
+    }
+
+    #[test]
+    fn test_category_request_validation() {
+        let valid = CreateCategoryRequest {
+            name: "Moda Feminina".to_string(),
+            slug: Some("moda-feminina".to_string()),
+            parent_id: None,
+            description: None,
+            position: None,
+            is_active: None,
+        };
+        assert!(valid.validate().is_ok());
+
+        let invalid = CreateCategoryRequest {
+            name: "".to_string(),
+            slug: Some("Moda Feminina".to_string()),
+            ..valid
+        };
+        let errors = invalid.validate().unwrap_err();
+        assert!(errors.field_errors().contains_key("name"));
+        assert!(errors.field_errors().contains_key("slug"));
+    }
+
+    #[test]
+    fn test_update_request_distinguishes_null_parent() {
+        let keep: UpdateCategoryRequest = serde_json::from_str(r#"{"name": "Nova"}"#).unwrap();
+        assert_eq!(keep.parent_id, None);
+
+        let to_root: UpdateCategoryRequest =
+            serde_json::from_str(r#"{"parent_id": null}"#).unwrap();
+        assert_eq!(to_root.parent_id, Some(None));
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("  Cama, Mesa & Banho "), "cama-mesa-banho");
+        assert_eq!(slugify("TV 4K"), "tv-4k");
+        assert_eq!(slugify("---"), "");
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Comissão da plataforma para uma loja ou uma categoria (um dos dois)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CommissionRate {
+    pub id: Uuid,
+    pub tenant_id: Option<Uuid>,
+    pub category_id: Option<Uuid>,
+    /// Pontos-base: 1000 = 10%
+    pub rate_bps: i32,
+    pub dt_created: DateTime<Utc>,
+    pub dt_updated: DateTime<Utc>,
+}
+
+/// PUT /commissions/ - `{"tenant_id": "...", "rate_bps": 800}` ou `{"category_id": ..., ...}`
+#[derive(Debug, Deserialize, Clone)]
+pub struct SetCommissionRateRequest {
+    pub tenant_id: Option<Uuid>,
+    pub category_id: Option<Uuid>,
+    pub rate_bps: i32,
+}
@@ -0,0 +1,139 @@
+use crate::app_core::app_state::AppState;
+use crate::apps::commission::models::CommissionRate;
+use chrono::{DateTime, Utc};
+use tracing::instrument;
+use uuid::Uuid;
+
+pub struct CommissionRepository<'a> {
+    app_state: &'a AppState,
+}
+
+impl<'a> CommissionRepository<'a> {
+    pub fn new(app_state: &'a AppState) -> Self {
+        Self { app_state }
+    }
+
+    #[instrument(name = "CommissionRepository::find_all", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_all(&self) -> Result<Vec<CommissionRate>, sqlx::Error> {
+        sqlx::query_as!(
+            CommissionRate,
+            r#"
+            SELECT
+                id,
+                tenant_id,
+                category_id,
+                rate_bps,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            FROM commission_rates
+            ORDER BY dt_created, id
+            "#
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "CommissionRepository::upsert_for_tenant", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn upsert_for_tenant(
+        &self,
+        tenant_id: Uuid,
+        rate_bps: i32,
+    ) -> Result<CommissionRate, sqlx::Error> {
+        let now = Utc::now().naive_utc();
+
+        sqlx::query_as!(
+            CommissionRate,
+            r#"
+            INSERT INTO commission_rates (id, tenant_id, rate_bps, dt_created, dt_updated)
+            VALUES ($1, $2, $3, $4, $4)
+            ON CONFLICT (tenant_id) WHERE tenant_id IS NOT NULL
+            DO UPDATE SET rate_bps = EXCLUDED.rate_bps, dt_updated = EXCLUDED.dt_updated
+            RETURNING
+                id,
+                tenant_id,
+                category_id,
+                rate_bps,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            "#,
+            Uuid::new_v4(),
+            tenant_id,
+            rate_bps,
+            now
+        )
+        .fetch_one(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "CommissionRepository::upsert_for_category", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn upsert_for_category(
+        &self,
+        category_id: Uuid,
+        rate_bps: i32,
+    ) -> Result<CommissionRate, sqlx::Error> {
+        let now = Utc::now().naive_utc();
+
+        sqlx::query_as!(
+            CommissionRate,
+            r#"
+            INSERT INTO commission_rates (id, category_id, rate_bps, dt_created, dt_updated)
+            VALUES ($1, $2, $3, $4, $4)
+            ON CONFLICT (category_id) WHERE category_id IS NOT NULL
+            DO UPDATE SET rate_bps = EXCLUDED.rate_bps, dt_updated = EXCLUDED.dt_updated
+            RETURNING
+                id,
+                tenant_id,
+                category_id,
+                rate_bps,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            "#,
+            Uuid::new_v4(),
+            category_id,
+            rate_bps,
+            now
+        )
+        .fetch_one(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "CommissionRepository::delete", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM commission_rates WHERE id = $1", id)
+            .execute(&self.app_state.db)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Regra que vale para cada produto: a da loja vendedora ou, sem ela, a maior entre as
+    /// categorias do produto. `None` quando nenhuma se aplica (vale o padrão).
+    #[instrument(name = "CommissionRepository::resolve_for_products", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn resolve_for_products(
+        &self,
+        product_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Option<i32>)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                p.id,
+                COALESCE(
+                    (SELECT cr.rate_bps FROM commission_rates cr WHERE cr.tenant_id = p.tenant_id),
+                    (
+                        SELECT MAX(cr.rate_bps)
+                        FROM commission_rates cr
+                        JOIN product_categories pc ON pc.category_id = cr.category_id
+                        WHERE pc.product_id = p.id
+                    )
+                ) as rate_bps
+            FROM products p
+            WHERE p.id = ANY($1)
+            "#,
+            product_ids
+        )
+        .fetch_all(&self.app_state.db)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| (row.id, row.rate_bps)).collect())
+    }
+}
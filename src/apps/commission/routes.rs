@@ -0,0 +1,52 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_extensions::RequestUserExt;
+use crate::app_core::app_state::AppState;
+use crate::apps::commission::models::SetCommissionRateRequest;
+use crate::apps::commission::services::CommissionService;
+use actix_web::web::Json;
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+use uuid::Uuid;
+
+fn ensure_super_admin(req: &HttpRequest) -> Result<(), AppError> {
+    if req.access_level()? != "super_admin" {
+        return Err(AppError::forbidden(
+            "Acesso negado. Apenas super_admin pode gerenciar as comissões.".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// GET /commissions/
+pub async fn list_commission_rates(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_super_admin(&req)?;
+    let result = CommissionService::list_rates(&app_state).await?;
+
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// PUT /commissions/ - `{"tenant_id": "...", "rate_bps": 800}`
+pub async fn set_commission_rate(
+    app_state: web::Data<AppState>,
+    payload: Json<SetCommissionRateRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_super_admin(&req)?;
+    let result = CommissionService::set_rate(&app_state, payload.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// DELETE /commissions/{id}/ - volta a valer a regra da categoria ou o padrão
+pub async fn delete_commission_rate(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_super_admin(&req)?;
+    CommissionService::delete_rate(&app_state, path.into_inner()).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
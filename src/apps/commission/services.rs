@@ -0,0 +1,119 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::app_core::init_settings::get_settings;
+use crate::apps::commission::models::{CommissionRate, SetCommissionRateRequest};
+use crate::apps::commission::repositories::CommissionRepository;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// 100% em pontos-base
+pub const MAX_COMMISSION_BPS: i32 = 10000;
+
+pub struct CommissionService;
+
+impl CommissionService {
+    /// GET /commissions/ - regras por loja e por categoria, mais o padrão da configuração
+    pub async fn list_rates(app_state: &AppState) -> Result<serde_json::Value, AppError> {
+        let rates = CommissionRepository::new(app_state)
+            .find_all()
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        Ok(serde_json::json!({
+            "default_rate_bps": get_settings().marketplace.default_commission_bps,
+            "rates": rates,
+        }))
+    }
+
+    /// PUT /commissions/ - cria ou troca a regra da loja ou da categoria
+    pub async fn set_rate(
+        app_state: &AppState,
+        request: SetCommissionRateRequest,
+    ) -> Result<CommissionRate, AppError> {
+        if !(0..=MAX_COMMISSION_BPS).contains(&request.rate_bps) {
+            return Err(AppError::bad_request(format!(
+                "rate_bps deve estar entre 0 e {}",
+                MAX_COMMISSION_BPS
+            )));
+        }
+
+        let repository = CommissionRepository::new(app_state);
+        let result = match (request.tenant_id, request.category_id) {
+            (Some(tenant_id), None) => {
+                repository
+                    .upsert_for_tenant(tenant_id, request.rate_bps)
+                    .await
+            }
+            (None, Some(category_id)) => {
+                repository
+                    .upsert_for_category(category_id, request.rate_bps)
+                    .await
+            }
+            _ => {
+                return Err(AppError::bad_request(
+                    "Informe tenant_id ou category_id (apenas um)",
+                ));
+            }
+        };
+
+        result.map_err(unknown_target)
+    }
+
+    pub async fn delete_rate(app_state: &AppState, id: Uuid) -> Result<(), AppError> {
+        let deleted = CommissionRepository::new(app_state)
+            .delete(id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        match deleted {
+            true => Ok(()),
+            false => Err(AppError::not_found("Regra de comissão não encontrada")),
+        }
+    }
+
+    /// Comissão de cada produto (pontos-base): regra da loja, depois da categoria, depois
+    /// MARKETPLACE_COMMISSION_BPS
+    pub async fn resolve_rates(
+        app_state: &AppState,
+        product_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, i32>, AppError> {
+        let default_bps = get_settings().marketplace.default_commission_bps;
+
+        Ok(CommissionRepository::new(app_state)
+            .resolve_for_products(product_ids)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .into_iter()
+            .map(|(product_id, rate_bps)| (product_id, rate_bps.unwrap_or(default_bps)))
+            .collect())
+    }
+}
+
+/// Comissão sobre o valor da linha, arredondada para a unidade mínima mais próxima
+pub fn commission_amount(line_total_minor: i64, rate_bps: i32) -> i64 {
+    let scaled = line_total_minor as i128 * rate_bps as i128;
+    ((scaled + MAX_COMMISSION_BPS as i128 / 2) / MAX_COMMISSION_BPS as i128) as i64
+}
+
+/// Parte da comissão da linha que volta na devolução de `quantity` unidades, depois de
+/// `already_returned` já devolvidas. Devolver a linha toda em partes devolve a comissão exata.
+pub fn returned_commission(
+    line_commission: i64,
+    line_quantity: i32,
+    already_returned: i32,
+    quantity: i32,
+) -> i64 {
+    if line_quantity <= 0 {
+        return 0;
+    }
+    let share = |units: i32| line_commission as i128 * units as i128 / line_quantity as i128;
+    (share(already_returned + quantity) - share(already_returned)) as i64
+}
+
+/// Loja ou categoria inexistente (`commission_rates` FK)
+fn unknown_target(error: sqlx::Error) -> AppError {
+    match error.as_database_error().and_then(|e| e.code()).as_deref() {
+        Some("23503") => AppError::not_found("Loja ou categoria não encontrada"),
+        _ => AppError::database_error(error.to_string()),
+    }
+}
@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod tests {
+    use crate::apps::commission::services::{commission_amount, returned_commission};
+
+    #[test]
+    fn test_commission_amount_rounds_half_up() {
+        assert_eq!(commission_amount(10000, 1000), 1000);
+        assert_eq!(commission_amount(1005, 1000), 101);
+        assert_eq!(commission_amount(1004, 1000), 100);
+        assert_eq!(commission_amount(999, 0), 0);
+        assert_eq!(commission_amount(999, 10000), 999);
+    }
+
+    #[test]
+    fn test_returned_commission_sums_to_line_commission() {
+        // 3 unidades com 100 de comissão: devolvidas uma a uma voltam 33 + 33 + 34
+        let parts: Vec<i64> = (0..3)
+            .map(|done| returned_commission(100, 3, done, 1))
+            .collect();
+        assert_eq!(parts, vec![33, 33, 34]);
+        assert_eq!(parts.iter().sum::<i64>(), 100);
+
+        assert_eq!(returned_commission(100, 3, 0, 3), 100);
+        assert_eq!(returned_commission(100, 3, 1, 2), 67);
+        assert_eq!(returned_commission(0, 3, 0, 1), 0);
+    }
+}
@@ -0,0 +1,8 @@
+pub mod models;
+pub mod provider;
+pub mod repositories;
+pub mod routes;
+pub mod services;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,38 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+use uuid::Uuid;
+
+/// Origem das cotações carregadas pelo arquivo de importação
+pub const IMPORT_SOURCE: &str = "import";
+
+/// 1 `base_currency` = `rate` `quote_currency`, válida a partir de `effective_at`
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExchangeRate {
+    pub id: Uuid,
+    pub base_currency: String,
+    pub quote_currency: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub rate: BigDecimal,
+    /// "import" ou o nome do provedor
+    pub source: String,
+    pub effective_at: DateTime<Utc>,
+    pub dt_created: DateTime<Utc>,
+}
+
+/// Cotação lida do arquivo ou do provedor, ainda não gravada
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewExchangeRate {
+    pub base_currency: String,
+    pub quote_currency: String,
+    pub rate: BigDecimal,
+    pub effective_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExchangeRateImportReport {
+    pub source: String,
+    pub imported: usize,
+}
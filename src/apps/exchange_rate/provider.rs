@@ -0,0 +1,59 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::init_settings::get_settings;
+use crate::app_core::settings::{ExchangeRateProviderKind, FixedExchangeRate};
+use crate::apps::exchange_rate::models::NewExchangeRate;
+use async_trait::async_trait;
+use chrono::Utc;
+
+/// Fonte de cotações usada pela sincronização (`sync-exchange-rates` ou POST /exchange-rates/sync/).
+/// Provedores externos (banco central, APIs pagas) entram implementando este trait.
+#[async_trait]
+pub trait ExchangeRateProvider: Send + Sync {
+    /// Gravado em `exchange_rates.source`
+    fn name(&self) -> &'static str;
+
+    async fn fetch_rates(&self) -> Result<Vec<NewExchangeRate>, AppError>;
+}
+
+/// Cotações fixas da configuração (EXCHANGE_RATES_FIXED), úteis em desenvolvimento e testes
+pub struct FixedRateProvider {
+    rates: Vec<FixedExchangeRate>,
+}
+
+impl FixedRateProvider {
+    pub fn new(rates: Vec<FixedExchangeRate>) -> Self {
+        Self { rates }
+    }
+}
+
+#[async_trait]
+impl ExchangeRateProvider for FixedRateProvider {
+    fn name(&self) -> &'static str {
+        "fixed"
+    }
+
+    async fn fetch_rates(&self) -> Result<Vec<NewExchangeRate>, AppError> {
+        let now = Utc::now();
+        Ok(self
+            .rates
+            .iter()
+            .map(|rate| NewExchangeRate {
+                base_currency: rate.base_currency.clone(),
+                quote_currency: rate.quote_currency.clone(),
+                rate: rate.rate.clone(),
+                effective_at: now,
+            })
+            .collect())
+    }
+}
+
+/// Provedor definido em EXCHANGE_RATE_PROVIDER; `None` quando as cotações só entram por importação
+pub fn configured_provider() -> Option<Box<dyn ExchangeRateProvider>> {
+    let settings = &get_settings().currency;
+    match settings.exchange_rate_provider {
+        ExchangeRateProviderKind::None => None,
+        ExchangeRateProviderKind::Fixed => Some(Box::new(FixedRateProvider::new(
+            settings.fixed_rates.clone(),
+        ))),
+    }
+}
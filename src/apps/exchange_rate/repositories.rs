@@ -0,0 +1,95 @@
+use crate::app_core::app_state::AppState;
+use crate::apps::exchange_rate::models::{ExchangeRate, NewExchangeRate};
+use chrono::{DateTime, Utc};
+use tracing::instrument;
+use uuid::Uuid;
+
+pub struct ExchangeRateRepository<'a> {
+    app_state: &'a AppState,
+}
+
+impl<'a> ExchangeRateRepository<'a> {
+    pub fn new(app_state: &'a AppState) -> Self {
+        Self { app_state }
+    }
+
+    /// Grava o lote inteiro numa transação: ou entram todas as cotações, ou nenhuma
+    #[instrument(name = "ExchangeRateRepository::insert_many", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn insert_many(
+        &self,
+        rates: &[NewExchangeRate],
+        source: &str,
+    ) -> Result<usize, sqlx::Error> {
+        let now = Utc::now().naive_utc();
+        let mut tx = self.app_state.db.begin().await?;
+
+        for rate in rates {
+            sqlx::query!(
+                r#"
+                INSERT INTO exchange_rates (id, base_currency, quote_currency, rate, source, effective_at, dt_created)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#,
+                Uuid::new_v4(),
+                rate.base_currency,
+                rate.quote_currency,
+                rate.rate,
+                source,
+                rate.effective_at.naive_utc(),
+                now
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(rates.len())
+    }
+
+    /// Cotação mais recente de cada par já vigente
+    #[instrument(name = "ExchangeRateRepository::find_latest", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_latest(&self) -> Result<Vec<ExchangeRate>, sqlx::Error> {
+        sqlx::query_as!(
+            ExchangeRate,
+            r#"
+            SELECT DISTINCT ON (base_currency, quote_currency)
+                id, base_currency, quote_currency, rate, source,
+                (effective_at AT TIME ZONE 'UTC') as "effective_at!: DateTime<Utc>",
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>"
+            FROM exchange_rates
+            WHERE effective_at <= $1
+            ORDER BY base_currency, quote_currency, effective_at DESC, dt_created DESC
+            "#,
+            Utc::now().naive_utc()
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    /// Cotação vigente do par em `at`
+    #[instrument(name = "ExchangeRateRepository::find_rate", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_rate(
+        &self,
+        base_currency: &str,
+        quote_currency: &str,
+        at: DateTime<Utc>,
+    ) -> Result<Option<ExchangeRate>, sqlx::Error> {
+        sqlx::query_as!(
+            ExchangeRate,
+            r#"
+            SELECT
+                id, base_currency, quote_currency, rate, source,
+                (effective_at AT TIME ZONE 'UTC') as "effective_at!: DateTime<Utc>",
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>"
+            FROM exchange_rates
+            WHERE base_currency = $1 AND quote_currency = $2 AND effective_at <= $3
+            ORDER BY effective_at DESC, dt_created DESC
+            LIMIT 1
+            "#,
+            base_currency,
+            quote_currency,
+            at.naive_utc()
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+}
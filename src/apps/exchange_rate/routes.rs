@@ -0,0 +1,50 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_extensions::RequestUserExt;
+use crate::app_core::app_state::AppState;
+use crate::apps::exchange_rate::provider::configured_provider;
+use crate::apps::exchange_rate::services::ExchangeRateService;
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+
+fn ensure_super_admin(req: &HttpRequest) -> Result<(), AppError> {
+    if req.access_level()? != "super_admin" {
+        return Err(AppError::forbidden(
+            "Acesso negado. Apenas super_admin pode alterar as cotações.".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// GET /exchange-rates/ - cotação vigente de cada par
+pub async fn list_exchange_rates(
+    app_state: web::Data<AppState>,
+) -> Result<impl Responder, AppError> {
+    let result = ExchangeRateService::list_latest(&app_state).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /exchange-rates/import/ - corpo é o CSV `base_currency,quote_currency,rate[,effective_at]`
+pub async fn import_exchange_rates(
+    app_state: web::Data<AppState>,
+    body: String,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_super_admin(&req)?;
+    let result = ExchangeRateService::import_csv(&app_state, &body).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /exchange-rates/sync/ - busca as cotações no provedor configurado
+pub async fn sync_exchange_rates(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_super_admin(&req)?;
+    let provider = configured_provider().ok_or_else(|| {
+        AppError::bad_request("Nenhum provedor de câmbio configurado (EXCHANGE_RATE_PROVIDER)")
+    })?;
+    let result = ExchangeRateService::sync(&app_state, provider.as_ref()).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
@@ -0,0 +1,164 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::apps::exchange_rate::models::{
+    ExchangeRate, ExchangeRateImportReport, IMPORT_SOURCE, NewExchangeRate,
+};
+use crate::apps::exchange_rate::provider::ExchangeRateProvider;
+use crate::apps::exchange_rate::repositories::ExchangeRateRepository;
+use crate::utils::csv;
+use crate::utils::currency::{self, normalize_currency};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
+
+/// Colunas do arquivo de cotações; `effective_at` é opcional (vazio = agora)
+const RATE_COLUMNS: [&str; 3] = ["base_currency", "quote_currency", "rate"];
+
+/// Lê o CSV de cotações (`base_currency,quote_currency,rate[,effective_at]`).
+/// Qualquer linha inválida rejeita o arquivo inteiro, com o número da linha.
+pub fn parse_rates_csv(input: &str, now: DateTime<Utc>) -> Result<Vec<NewExchangeRate>, AppError> {
+    let records = csv::parse(input).map_err(|e| AppError::bad_request(e.to_string()))?;
+    let Some((header, rows)) = records.split_first() else {
+        return Err(AppError::bad_request("Arquivo de cotações vazio"));
+    };
+
+    let column = |name: &str| {
+        header
+            .fields
+            .iter()
+            .position(|field| field.trim().eq_ignore_ascii_case(name))
+    };
+    let mut positions = Vec::with_capacity(RATE_COLUMNS.len());
+    for name in RATE_COLUMNS {
+        positions.push(column(name).ok_or_else(|| {
+            AppError::bad_request(format!("Coluna obrigatória ausente: {}", name))
+        })?);
+    }
+    let effective_at_column = column("effective_at");
+
+    rows.iter()
+        .map(|record| {
+            let field = |position: usize| {
+                record
+                    .fields
+                    .get(position)
+                    .map(|value| value.trim())
+                    .unwrap_or_default()
+            };
+            let line_error = |message: String| {
+                AppError::bad_request(format!("linha {}: {}", record.line, message))
+            };
+
+            let base_currency =
+                normalize_currency(field(positions[0])).map_err(|e| line_error(e.message()))?;
+            let quote_currency =
+                normalize_currency(field(positions[1])).map_err(|e| line_error(e.message()))?;
+            if base_currency == quote_currency {
+                return Err(line_error("moedas do par devem ser diferentes".to_string()));
+            }
+
+            let rate = BigDecimal::from_str(field(positions[2]))
+                .ok()
+                .filter(|rate| rate > &BigDecimal::from(0))
+                .ok_or_else(|| line_error(format!("cotação inválida: {}", field(positions[2]))))?;
+
+            let effective_at = match effective_at_column.map(field).filter(|v| !v.is_empty()) {
+                Some(value) => DateTime::parse_from_rfc3339(value)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|_| line_error(format!("effective_at inválido: {}", value)))?,
+                None => now,
+            };
+
+            Ok(NewExchangeRate {
+                base_currency,
+                quote_currency,
+                rate,
+                effective_at,
+            })
+        })
+        .collect()
+}
+
+pub struct ExchangeRateService;
+
+impl ExchangeRateService {
+    pub async fn list_latest(app_state: &AppState) -> Result<Vec<ExchangeRate>, AppError> {
+        ExchangeRateRepository::new(app_state)
+            .find_latest()
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    pub async fn import_csv(
+        app_state: &AppState,
+        input: &str,
+    ) -> Result<ExchangeRateImportReport, AppError> {
+        let rates = parse_rates_csv(input, Utc::now())?;
+        Self::store(app_state, &rates, IMPORT_SOURCE).await
+    }
+
+    /// Busca as cotações no provedor e grava como novas vigências
+    pub async fn sync(
+        app_state: &AppState,
+        provider: &dyn ExchangeRateProvider,
+    ) -> Result<ExchangeRateImportReport, AppError> {
+        let rates = provider.fetch_rates().await?;
+        Self::store(app_state, &rates, provider.name()).await
+    }
+
+    /// Quanto vale 1 `from` em `to`: par direto ou o inverso do par contrário
+    pub async fn rate(app_state: &AppState, from: &str, to: &str) -> Result<BigDecimal, AppError> {
+        if from == to {
+            return Ok(BigDecimal::from(1));
+        }
+
+        let repository = ExchangeRateRepository::new(app_state);
+        let now = Utc::now();
+        if let Some(direct) = repository
+            .find_rate(from, to, now)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+        {
+            return Ok(direct.rate);
+        }
+
+        match repository
+            .find_rate(to, from, now)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+        {
+            Some(reverse) => Ok(BigDecimal::from(1) / reverse.rate),
+            None => Err(AppError::bad_request(format!(
+                "Sem cotação cadastrada para {} -> {}",
+                from, to
+            ))),
+        }
+    }
+
+    /// Converte o valor e arredonda para a moeda de destino (meio para o par)
+    pub async fn convert(
+        app_state: &AppState,
+        amount: &BigDecimal,
+        from: &str,
+        to: &str,
+    ) -> Result<BigDecimal, AppError> {
+        let rate = Self::rate(app_state, from, to).await?;
+        Ok(currency::convert(amount, &rate, to))
+    }
+
+    async fn store(
+        app_state: &AppState,
+        rates: &[NewExchangeRate],
+        source: &str,
+    ) -> Result<ExchangeRateImportReport, AppError> {
+        let imported = ExchangeRateRepository::new(app_state)
+            .insert_many(rates, source)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        Ok(ExchangeRateImportReport {
+            source: source.to_string(),
+            imported,
+        })
+    }
+}
@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod tests {
+    use crate::apps::exchange_rate::services::parse_rates_csv;
+    use bigdecimal::BigDecimal;
+    use chrono::{TimeZone, Utc};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_rates_csv() {
+        let now = Utc.with_ymd_and_hms(2025, 8, 29, 12, 0, 0).unwrap();
+        let input = "base_currency,quote_currency,rate,effective_at\n\
+                     usd,BRL,5.4321,\n\
+                     EUR,BRL,5.9,2025-09-01T00:00:00Z\n";
+
+        let rates = parse_rates_csv(input, now).unwrap();
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates[0].base_currency, "USD");
+        assert_eq!(rates[0].rate, BigDecimal::from_str("5.4321").unwrap());
+        assert_eq!(rates[0].effective_at, now);
+        assert_eq!(
+            rates[1].effective_at,
+            Utc.with_ymd_and_hms(2025, 9, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_rates_csv_rejects_invalid_rows() {
+        let now = Utc::now();
+
+        let missing = parse_rates_csv("base_currency,rate\nUSD,5\n", now).unwrap_err();
+        assert!(missing.message().contains("quote_currency"));
+
+        let zero =
+            parse_rates_csv("base_currency,quote_currency,rate\nUSD,BRL,0\n", now).unwrap_err();
+        assert!(zero.message().starts_with("linha 2"));
+
+        let same =
+            parse_rates_csv("base_currency,quote_currency,rate\nBRL,brl,1\n", now).unwrap_err();
+        assert!(same.message().contains("diferentes"));
+    }
+}
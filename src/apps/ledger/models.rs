@@ -0,0 +1,276 @@
+use crate::apps::order::models::Order;
+use crate::utils::money::Money;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Contas do razão. Débito entra positivo e crédito negativo em `ledger_postings.amount`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerAccount {
+    /// Dinheiro recebido dos compradores e ainda com a plataforma
+    PlatformCash,
+    /// Receita da plataforma
+    PlatformCommission,
+    /// Quanto a plataforma deve à loja (por tenant)
+    SellerPayable,
+    /// Repasses em lote aguardando a confirmação do banco
+    PayoutClearing,
+}
+
+impl LedgerAccount {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LedgerAccount::PlatformCash => "platform_cash",
+            LedgerAccount::PlatformCommission => "platform_commission",
+            LedgerAccount::SellerPayable => "seller_payable",
+            LedgerAccount::PayoutClearing => "payout_clearing",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerEntryKind {
+    /// Pedido pago: parte da loja e comissão
+    Sale,
+    /// Pedido pago cancelado antes do envio
+    SaleReversal,
+    /// Nota de crédito de devolução
+    Refund,
+    /// Lote de repasse criado
+    Payout,
+    /// Lote de repasse conciliado com o banco
+    PayoutSettlement,
+}
+
+impl LedgerEntryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LedgerEntryKind::Sale => "sale",
+            LedgerEntryKind::SaleReversal => "sale_reversal",
+            LedgerEntryKind::Refund => "refund",
+            LedgerEntryKind::Payout => "payout",
+            LedgerEntryKind::PayoutSettlement => "payout_settlement",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "sale" => Some(LedgerEntryKind::Sale),
+            "sale_reversal" => Some(LedgerEntryKind::SaleReversal),
+            "refund" => Some(LedgerEntryKind::Refund),
+            "payout" => Some(LedgerEntryKind::Payout),
+            "payout_settlement" => Some(LedgerEntryKind::PayoutSettlement),
+            _ => None,
+        }
+    }
+}
+
+/// Lançamento de uma conta; `tenant_id` só na conta da loja
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewPosting {
+    pub account: LedgerAccount,
+    pub tenant_id: Option<Uuid>,
+    pub amount_minor: i64,
+}
+
+impl NewPosting {
+    fn new(account: LedgerAccount, amount_minor: i64) -> Self {
+        Self {
+            account,
+            tenant_id: None,
+            amount_minor,
+        }
+    }
+
+    fn seller(tenant_id: Uuid, amount_minor: i64) -> Self {
+        Self {
+            account: LedgerAccount::SellerPayable,
+            tenant_id: Some(tenant_id),
+            amount_minor,
+        }
+    }
+}
+
+/// Lançamento a gravar, com todas as partidas numa só moeda
+#[derive(Debug, Clone)]
+pub struct NewLedgerEntry {
+    pub kind: LedgerEntryKind,
+    pub order_id: Option<Uuid>,
+    pub credit_note_id: Option<Uuid>,
+    pub payout_batch_id: Option<Uuid>,
+    pub description: String,
+    pub currency: String,
+    pub postings: Vec<NewPosting>,
+}
+
+impl NewLedgerEntry {
+    /// Pedido pago: entra o total no caixa, a loja fica com o total menos a comissão
+    pub fn sale(order: &Order, commission_minor: i64) -> Self {
+        let total = order.grand_total.amount_minor;
+        Self::for_order(
+            LedgerEntryKind::Sale,
+            order,
+            format!("Venda do pedido {}", order.id),
+            vec![
+                NewPosting::new(LedgerAccount::PlatformCash, total),
+                NewPosting::seller(order.seller_tenant_id, -(total - commission_minor)),
+                NewPosting::new(LedgerAccount::PlatformCommission, -commission_minor),
+            ],
+        )
+    }
+
+    /// Estorno do lançamento de venda (pedido pago e cancelado)
+    pub fn sale_reversal(order: &Order, commission_minor: i64) -> Self {
+        let sale = Self::sale(order, commission_minor);
+        Self::for_order(
+            LedgerEntryKind::SaleReversal,
+            order,
+            format!("Cancelamento do pedido {}", order.id),
+            sale.postings
+                .into_iter()
+                .map(|posting| NewPosting {
+                    amount_minor: -posting.amount_minor,
+                    ..posting
+                })
+                .collect(),
+        )
+    }
+
+    /// Reembolso de devolução: sai do caixa, a loja e a comissão devolvem cada uma a sua parte
+    pub fn refund(
+        order: &Order,
+        credit_note_id: Uuid,
+        amount_minor: i64,
+        commission_minor: i64,
+    ) -> Self {
+        let mut entry = Self::for_order(
+            LedgerEntryKind::Refund,
+            order,
+            format!("Devolução do pedido {}", order.id),
+            vec![
+                NewPosting::new(LedgerAccount::PlatformCash, -amount_minor),
+                NewPosting::seller(order.seller_tenant_id, amount_minor - commission_minor),
+                NewPosting::new(LedgerAccount::PlatformCommission, commission_minor),
+            ],
+        );
+        entry.credit_note_id = Some(credit_note_id);
+        entry
+    }
+
+    /// Lote criado: o saldo de cada loja passa para repasse em trânsito
+    pub fn payout(batch_id: Uuid, currency: &str, payouts: &[(Uuid, i64)]) -> Self {
+        let total: i64 = payouts.iter().map(|(_, amount)| amount).sum();
+        let mut postings: Vec<NewPosting> = payouts
+            .iter()
+            .map(|(tenant_id, amount)| NewPosting::seller(*tenant_id, *amount))
+            .collect();
+        postings.push(NewPosting::new(LedgerAccount::PayoutClearing, -total));
+
+        Self::for_batch(
+            LedgerEntryKind::Payout,
+            batch_id,
+            currency,
+            format!("Lote de repasse {}", batch_id),
+            postings,
+        )
+    }
+
+    /// Lote conciliado: o dinheiro saiu de fato do caixa
+    pub fn payout_settlement(batch_id: Uuid, currency: &str, total_minor: i64) -> Self {
+        Self::for_batch(
+            LedgerEntryKind::PayoutSettlement,
+            batch_id,
+            currency,
+            format!("Conciliação do lote de repasse {}", batch_id),
+            vec![
+                NewPosting::new(LedgerAccount::PayoutClearing, total_minor),
+                NewPosting::new(LedgerAccount::PlatformCash, -total_minor),
+            ],
+        )
+    }
+
+    /// Débitos iguais aos créditos
+    pub fn is_balanced(&self) -> bool {
+        self.postings
+            .iter()
+            .map(|p| p.amount_minor as i128)
+            .sum::<i128>()
+            == 0
+    }
+
+    fn for_order(
+        kind: LedgerEntryKind,
+        order: &Order,
+        description: String,
+        postings: Vec<NewPosting>,
+    ) -> Self {
+        Self {
+            kind,
+            order_id: Some(order.id),
+            credit_note_id: None,
+            payout_batch_id: None,
+            description,
+            currency: order.currency.clone(),
+            postings: without_zero(postings),
+        }
+    }
+
+    fn for_batch(
+        kind: LedgerEntryKind,
+        batch_id: Uuid,
+        currency: &str,
+        description: String,
+        postings: Vec<NewPosting>,
+    ) -> Self {
+        Self {
+            kind,
+            order_id: None,
+            credit_note_id: None,
+            payout_batch_id: Some(batch_id),
+            description,
+            currency: currency.to_string(),
+            postings: without_zero(postings),
+        }
+    }
+}
+
+/// Partidas zeradas (ex.: comissão 0%) não entram no razão
+fn without_zero(postings: Vec<NewPosting>) -> Vec<NewPosting> {
+    postings
+        .into_iter()
+        .filter(|posting| posting.amount_minor != 0)
+        .collect()
+}
+
+/// Linha do extrato da loja: positivo aumenta o saldo a receber, negativo diminui
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StatementLine {
+    pub id: Uuid,
+    pub entry_id: Uuid,
+    pub kind: LedgerEntryKind,
+    pub order_id: Option<Uuid>,
+    pub payout_batch_id: Option<Uuid>,
+    pub description: String,
+    pub amount: Money,
+    pub dt_created: DateTime<Utc>,
+}
+
+/// Saldo da loja numa moeda
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SellerBalance {
+    pub currency: String,
+    /// A receber e ainda fora de lote
+    pub available: Money,
+    /// Em lote aguardando conciliação
+    pub in_payout: Money,
+    /// Já repassado (lotes conciliados)
+    pub paid_out: Money,
+}
+
+/// GET /payouts/statement/ - `?currency=BRL` filtra
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct StatementParams {
+    pub currency: Option<String>,
+}
@@ -0,0 +1,325 @@
+use crate::app_core::app_state::AppState;
+use crate::apps::commission::services::returned_commission;
+use crate::apps::ledger::models::{LedgerEntryKind, NewLedgerEntry, SellerBalance, StatementLine};
+use crate::apps::order::models::Order;
+use crate::utils::money::Money;
+use crate::utils::pagination::{CursorPage, Page, PageRequest, PaginatedResponse, push_keyset};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{Postgres, QueryBuilder, Row, Transaction, postgres::PgRow};
+use tracing::instrument;
+use uuid::Uuid;
+
+pub struct LedgerRepository<'a> {
+    app_state: &'a AppState,
+}
+
+impl<'a> LedgerRepository<'a> {
+    pub fn new(app_state: &'a AppState) -> Self {
+        Self { app_state }
+    }
+
+    /// Grava o lançamento e as partidas na transação de quem originou o movimento.
+    /// O banco confere no commit que o lançamento fecha (`trg_ledger_entry_balanced`).
+    #[instrument(name = "LedgerRepository::post_entry", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn post_entry(
+        tx: &mut Transaction<'_, Postgres>,
+        entry: &NewLedgerEntry,
+    ) -> Result<(), sqlx::Error> {
+        if entry.postings.is_empty() {
+            return Ok(());
+        }
+        debug_assert!(entry.is_balanced(), "lançamento desbalanceado: {:?}", entry);
+
+        let now = Utc::now().naive_utc();
+        let entry_id = Uuid::new_v4();
+        sqlx::query!(
+            r#"
+            INSERT INTO ledger_entries (
+                id, kind, order_id, credit_note_id, payout_batch_id, description, dt_created
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            entry_id,
+            entry.kind.as_str(),
+            entry.order_id,
+            entry.credit_note_id,
+            entry.payout_batch_id,
+            entry.description,
+            now
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        for posting in &entry.postings {
+            sqlx::query!(
+                r#"
+                INSERT INTO ledger_postings (
+                    id, entry_id, account, tenant_id, amount, currency, dt_created
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#,
+                Uuid::new_v4(),
+                entry_id,
+                posting.account.as_str(),
+                posting.tenant_id,
+                posting.amount_minor,
+                entry.currency,
+                now
+            )
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Pedido pago: divide o total entre a loja e a comissão fixada no checkout
+    #[instrument(name = "LedgerRepository::record_sale", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn record_sale(
+        tx: &mut Transaction<'_, Postgres>,
+        order: &Order,
+    ) -> Result<(), sqlx::Error> {
+        let commission = Self::order_commission(tx, order.id).await?;
+        Self::post_entry(tx, &NewLedgerEntry::sale(order, commission)).await
+    }
+
+    /// Pedido pago e cancelado: desfaz a venda
+    #[instrument(name = "LedgerRepository::record_sale_reversal", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn record_sale_reversal(
+        tx: &mut Transaction<'_, Postgres>,
+        order: &Order,
+    ) -> Result<(), sqlx::Error> {
+        let commission = Self::order_commission(tx, order.id).await?;
+        Self::post_entry(tx, &NewLedgerEntry::sale_reversal(order, commission)).await
+    }
+
+    /// Reembolso da devolução: a comissão volta na proporção das unidades devolvidas.
+    /// Deve ser chamado depois de gravar a nota de crédito da devolução.
+    #[instrument(name = "LedgerRepository::record_refund", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn record_refund(
+        tx: &mut Transaction<'_, Postgres>,
+        order: &Order,
+        return_request_id: Uuid,
+        order_item_id: Uuid,
+        quantity: i32,
+        credit_note_id: Uuid,
+        amount: &Money,
+    ) -> Result<(), sqlx::Error> {
+        // Trava a linha para que devoluções simultâneas não dividam a comissão duas vezes
+        let item = sqlx::query!(
+            r#"
+            SELECT quantity, commission_amount
+            FROM order_items
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            order_item_id
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        let already_returned = sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(SUM(r.quantity), 0)::int as "quantity!"
+            FROM return_requests r
+            JOIN credit_notes cn ON cn.return_request_id = r.id
+            WHERE r.order_item_id = $1 AND r.id <> $2
+            "#,
+            order_item_id,
+            return_request_id
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        let commission = returned_commission(
+            item.commission_amount,
+            item.quantity,
+            already_returned,
+            quantity,
+        );
+        let entry = NewLedgerEntry::refund(order, credit_note_id, amount.amount_minor, commission);
+        Self::post_entry(tx, &entry).await
+    }
+
+    /// Saldo da loja por moeda: a receber, em lote e já repassado
+    #[instrument(name = "LedgerRepository::balances", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn balances(&self, tenant_id: Uuid) -> Result<Vec<SellerBalance>, sqlx::Error> {
+        sqlx::query_as!(
+            SellerBalance,
+            r#"
+            WITH payable AS (
+                SELECT currency, -SUM(amount)::bigint AS available
+                FROM ledger_postings
+                WHERE account = 'seller_payable' AND tenant_id = $1
+                GROUP BY currency
+            ),
+            paid AS (
+                SELECT
+                    p.currency,
+                    COALESCE(SUM(p.amount) FILTER (WHERE b.status = 'PENDING'), 0)::bigint
+                        AS in_payout,
+                    COALESCE(SUM(p.amount) FILTER (WHERE b.status = 'RECONCILED'), 0)::bigint
+                        AS paid_out
+                FROM payouts p
+                JOIN payout_batches b ON b.id = p.batch_id
+                WHERE p.seller_tenant_id = $1
+                GROUP BY p.currency
+            ),
+            balances AS (
+                SELECT
+                    COALESCE(a.currency, b.currency)::text AS currency,
+                    COALESCE(a.available, 0) AS available,
+                    COALESCE(b.in_payout, 0) AS in_payout,
+                    COALESCE(b.paid_out, 0) AS paid_out
+                FROM payable a
+                FULL JOIN paid b ON b.currency = a.currency
+            )
+            SELECT
+                currency as "currency!",
+                ROW(available, currency)::money_value as "available!: Money",
+                ROW(in_payout, currency)::money_value as "in_payout!: Money",
+                ROW(paid_out, currency)::money_value as "paid_out!: Money"
+            FROM balances
+            ORDER BY currency
+            "#,
+            tenant_id
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    /// Extrato da conta a pagar da loja, mais novos primeiro
+    #[instrument(name = "LedgerRepository::statement", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn statement(
+        &self,
+        tenant_id: Uuid,
+        currency: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<Page<StatementLine>, sqlx::Error> {
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
+            r#"
+            SELECT
+                p.id,
+                p.entry_id,
+                e.kind,
+                e.order_id,
+                e.payout_batch_id,
+                e.description,
+                ROW(-p.amount, p.currency)::money_value AS amount,
+                p.dt_created"#,
+        );
+
+        if let PageRequest::Offset { .. } = page {
+            qb.push(", COUNT(*) OVER() AS total_count");
+        }
+        qb.push(" FROM ledger_postings p JOIN ledger_entries e ON e.id = p.entry_id WHERE ");
+        push_statement_filters(&mut qb, tenant_id, currency);
+
+        match page {
+            PageRequest::Offset { limit, offset } => {
+                qb.push(" ORDER BY p.dt_created DESC, p.id DESC LIMIT ")
+                    .push_bind(*limit)
+                    .push(" OFFSET ")
+                    .push_bind(*offset);
+            }
+            PageRequest::Cursor { limit, cursor, .. } => {
+                push_keyset(&mut qb, "p.dt_created", "p.id", cursor.as_ref(), *limit);
+            }
+        }
+
+        let rows: Vec<PgRow> = qb.build().fetch_all(&self.app_state.db).await?;
+
+        let total = match page {
+            PageRequest::Offset { .. } => rows
+                .first()
+                .map(|r| r.get::<i64, _>("total_count"))
+                .unwrap_or(0),
+            _ => 0,
+        };
+        let lines: Vec<StatementLine> = rows.into_iter().filter_map(line_from_row).collect();
+
+        Ok(match page {
+            PageRequest::Offset { limit, offset } => Page::Offset(PaginatedResponse {
+                count: total,
+                results: lines,
+                limit: *limit,
+                offset: *offset,
+            }),
+            PageRequest::Cursor {
+                limit,
+                cursor,
+                include_count,
+            } => {
+                let count = match include_count {
+                    true => {
+                        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
+                            "SELECT COUNT(*) FROM ledger_postings p \
+                             JOIN ledger_entries e ON e.id = p.entry_id WHERE ",
+                        );
+                        push_statement_filters(&mut qb, tenant_id, currency);
+                        Some(
+                            qb.build_query_scalar::<i64>()
+                                .fetch_one(&self.app_state.db)
+                                .await?,
+                        )
+                    }
+                    false => None,
+                };
+                Page::Cursor(CursorPage::from_rows(
+                    lines,
+                    *limit,
+                    cursor.as_ref(),
+                    |l| (l.dt_created, l.id),
+                    count,
+                ))
+            }
+        })
+    }
+
+    #[instrument(name = "LedgerRepository::order_commission", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    async fn order_commission(
+        tx: &mut Transaction<'_, Postgres>,
+        order_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(SUM(commission_amount), 0)::bigint as "commission!"
+            FROM order_items
+            WHERE order_id = $1
+            "#,
+            order_id
+        )
+        .fetch_one(&mut **tx)
+        .await
+    }
+}
+
+fn push_statement_filters(
+    qb: &mut QueryBuilder<'_, Postgres>,
+    tenant_id: Uuid,
+    currency: Option<&str>,
+) {
+    qb.push("p.account = 'seller_payable' AND p.tenant_id = ")
+        .push_bind(tenant_id);
+    if let Some(currency) = currency {
+        qb.push(" AND p.currency = ")
+            .push_bind(currency.to_string());
+    }
+}
+
+/// Tipos fora do enum (não deveria acontecer) ficam fora do extrato
+fn line_from_row(row: PgRow) -> Option<StatementLine> {
+    Some(StatementLine {
+        id: row.get("id"),
+        entry_id: row.get("entry_id"),
+        kind: LedgerEntryKind::parse(row.get("kind"))?,
+        order_id: row.get("order_id"),
+        payout_batch_id: row.get("payout_batch_id"),
+        description: row.get("description"),
+        amount: row.get("amount"),
+        dt_created: DateTime::from_naive_utc_and_offset(
+            row.get::<NaiveDateTime, _>("dt_created"),
+            Utc,
+        ),
+    })
+}
@@ -0,0 +1,47 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_extensions::RequestUserExt;
+use crate::app_core::app_state::AppState;
+use crate::apps::ledger::models::StatementParams;
+use crate::apps::ledger::services::LedgerService;
+use crate::utils::pagination::PaginationParams;
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+
+/// GET /payouts/balance/ - dono da loja ou super_admin
+pub async fn get_seller_balance(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let access_level = req.access_level()?;
+
+    let result =
+        LedgerService::seller_balance(&app_state, tenant_id, user_id, &access_level).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// GET /payouts/statement/ - `?currency=BRL` filtra
+pub async fn get_seller_statement(
+    app_state: web::Data<AppState>,
+    query: web::Query<PaginationParams>,
+    filter: web::Query<StatementParams>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let access_level = req.access_level()?;
+    let page = query.page_request()?;
+
+    let result = LedgerService::seller_statement(
+        &app_state,
+        tenant_id,
+        user_id,
+        &access_level,
+        filter.into_inner(),
+        &page,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
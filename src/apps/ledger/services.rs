@@ -0,0 +1,65 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::apps::ledger::models::{SellerBalance, StatementLine, StatementParams};
+use crate::apps::ledger::repositories::LedgerRepository;
+use crate::apps::tenant::services::TenantService;
+use crate::utils::currency::normalize_currency;
+use crate::utils::pagination::{Page, PageRequest};
+use uuid::Uuid;
+
+pub struct LedgerService;
+
+impl LedgerService {
+    /// GET /payouts/balance/ - saldo da loja em cada moeda
+    pub async fn seller_balance(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+    ) -> Result<Vec<SellerBalance>, AppError> {
+        Self::ensure_seller(app_state, tenant_id, user_id, access_level).await?;
+
+        LedgerRepository::new(app_state)
+            .balances(tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    /// GET /payouts/statement/ - vendas, cancelamentos, devoluções e repasses da loja
+    pub async fn seller_statement(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+        params: StatementParams,
+        page: &PageRequest,
+    ) -> Result<Page<StatementLine>, AppError> {
+        let currency = params
+            .currency
+            .as_deref()
+            .map(normalize_currency)
+            .transpose()?;
+        Self::ensure_seller(app_state, tenant_id, user_id, access_level).await?;
+
+        LedgerRepository::new(app_state)
+            .statement(tenant_id, currency.as_deref(), page)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    async fn ensure_seller(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+    ) -> Result<(), AppError> {
+        TenantService::ensure_admin(
+            app_state,
+            tenant_id,
+            user_id,
+            access_level,
+            "Acesso negado. Apenas o dono da loja pode ver o saldo e o extrato.",
+        )
+        .await
+    }
+}
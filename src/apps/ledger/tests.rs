@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use crate::apps::ledger::models::{LedgerAccount, LedgerEntryKind, NewLedgerEntry};
+    use crate::apps::order::models::{Order, OrderStatus};
+    use crate::utils::money::Money;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn order(grand_total: i64) -> Order {
+        Order {
+            id: Uuid::new_v4(),
+            cart_id: Uuid::new_v4(),
+            buyer_tenant_id: Uuid::new_v4(),
+            buyer_user_id: Uuid::new_v4(),
+            seller_tenant_id: Uuid::new_v4(),
+            status: OrderStatus::PAID,
+            currency: "BRL".to_string(),
+            subtotal: Money::new(grand_total, "BRL"),
+            discount_total: Money::zero("BRL"),
+            tax_total: Money::zero("BRL"),
+            shipping_total: Money::zero("BRL"),
+            grand_total: Money::new(grand_total, "BRL"),
+            refunded_total: Money::zero("BRL"),
+            tracking_code: None,
+            carrier: None,
+            dt_created: Utc::now(),
+            dt_updated: Utc::now(),
+        }
+    }
+
+    fn amount(entry: &NewLedgerEntry, account: LedgerAccount) -> Option<i64> {
+        entry
+            .postings
+            .iter()
+            .find(|p| p.account == account)
+            .map(|p| p.amount_minor)
+    }
+
+    #[test]
+    fn test_sale_splits_total_between_seller_and_commission() {
+        let order = order(10000);
+        let entry = NewLedgerEntry::sale(&order, 1000);
+
+        assert!(entry.is_balanced());
+        assert_eq!(entry.kind, LedgerEntryKind::Sale);
+        assert_eq!(amount(&entry, LedgerAccount::PlatformCash), Some(10000));
+        assert_eq!(amount(&entry, LedgerAccount::SellerPayable), Some(-9000));
+        assert_eq!(
+            amount(&entry, LedgerAccount::PlatformCommission),
+            Some(-1000)
+        );
+        let seller = entry
+            .postings
+            .iter()
+            .find(|p| p.account == LedgerAccount::SellerPayable)
+            .unwrap();
+        assert_eq!(seller.tenant_id, Some(order.seller_tenant_id));
+    }
+
+    #[test]
+    fn test_zero_commission_is_omitted() {
+        let entry = NewLedgerEntry::sale(&order(5000), 0);
+
+        assert!(entry.is_balanced());
+        assert_eq!(entry.postings.len(), 2);
+        assert_eq!(amount(&entry, LedgerAccount::PlatformCommission), None);
+    }
+
+    #[test]
+    fn test_sale_reversal_negates_sale() {
+        let order = order(10000);
+        let sale = NewLedgerEntry::sale(&order, 1500);
+        let reversal = NewLedgerEntry::sale_reversal(&order, 1500);
+
+        assert!(reversal.is_balanced());
+        assert_eq!(reversal.kind, LedgerEntryKind::SaleReversal);
+        for (a, b) in sale.postings.iter().zip(&reversal.postings) {
+            assert_eq!(a.account, b.account);
+            assert_eq!(a.amount_minor, -b.amount_minor);
+        }
+    }
+
+    #[test]
+    fn test_refund_and_payout_entries_balance() {
+        let order = order(10000);
+        let credit_note_id = Uuid::new_v4();
+        let refund = NewLedgerEntry::refund(&order, credit_note_id, 4000, 400);
+        assert!(refund.is_balanced());
+        assert_eq!(refund.credit_note_id, Some(credit_note_id));
+        assert_eq!(amount(&refund, LedgerAccount::SellerPayable), Some(3600));
+
+        let batch_id = Uuid::new_v4();
+        let sellers = [(Uuid::new_v4(), 5400), (Uuid::new_v4(), 1200)];
+        let payout = NewLedgerEntry::payout(batch_id, "BRL", &sellers);
+        assert!(payout.is_balanced());
+        assert_eq!(amount(&payout, LedgerAccount::PayoutClearing), Some(-6600));
+
+        let settlement = NewLedgerEntry::payout_settlement(batch_id, "BRL", 6600);
+        assert!(settlement.is_balanced());
+        assert_eq!(
+            amount(&settlement, LedgerAccount::PlatformCash),
+            Some(-6600)
+        );
+    }
+
+    #[test]
+    fn test_entry_kind_round_trip() {
+        for kind in [
+            LedgerEntryKind::Sale,
+            LedgerEntryKind::SaleReversal,
+            LedgerEntryKind::Refund,
+            LedgerEntryKind::Payout,
+            LedgerEntryKind::PayoutSettlement,
+        ] {
+            assert_eq!(LedgerEntryKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(LedgerEntryKind::parse("transfer"), None);
+    }
+}
@@ -0,0 +1,8 @@
+pub mod models;
+pub mod repositories;
+pub mod routes;
+pub mod services;
+pub mod storage;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,206 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Formatos de imagem aceitos no upload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaFormat {
+    Jpeg,
+    Png,
+    Gif,
+    Webp,
+}
+
+impl MediaFormat {
+    pub const ALLOWED_CONTENT_TYPES: [&'static str; 4] =
+        ["image/jpeg", "image/png", "image/gif", "image/webp"];
+
+    /// Formato real pelo conteúdo (assinatura do arquivo), não pelo que o cliente declarou
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        match image::guess_format(data).ok()? {
+            image::ImageFormat::Jpeg => Some(MediaFormat::Jpeg),
+            image::ImageFormat::Png => Some(MediaFormat::Png),
+            image::ImageFormat::Gif => Some(MediaFormat::Gif),
+            image::ImageFormat::WebP => Some(MediaFormat::Webp),
+            _ => None,
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "jpg" | "jpeg" => Some(MediaFormat::Jpeg),
+            "png" => Some(MediaFormat::Png),
+            "gif" => Some(MediaFormat::Gif),
+            "webp" => Some(MediaFormat::Webp),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            MediaFormat::Jpeg => "image/jpeg",
+            MediaFormat::Png => "image/png",
+            MediaFormat::Gif => "image/gif",
+            MediaFormat::Webp => "image/webp",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            MediaFormat::Jpeg => "jpg",
+            MediaFormat::Png => "png",
+            MediaFormat::Gif => "gif",
+            MediaFormat::Webp => "webp",
+        }
+    }
+
+    pub fn image_format(&self) -> image::ImageFormat {
+        match self {
+            MediaFormat::Jpeg => image::ImageFormat::Jpeg,
+            MediaFormat::Png => image::ImageFormat::Png,
+            MediaFormat::Gif => image::ImageFormat::Gif,
+            MediaFormat::Webp => image::ImageFormat::WebP,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaVisibility {
+    /// URL permanente (catálogo, avatar)
+    Public,
+    /// Só acessível por URL assinada que expira
+    Private,
+}
+
+impl MediaVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MediaVisibility::Public => "public",
+            MediaVisibility::Private => "private",
+        }
+    }
+}
+
+impl FromStr for MediaVisibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "public" => Ok(MediaVisibility::Public),
+            "private" => Ok(MediaVisibility::Private),
+            _ => Err(format!("Visibilidade inválida: {}", s)),
+        }
+    }
+}
+
+/// Chave no storage: "<visibilidade>/<tenant>/<id>[_thumb].<ext>"
+pub fn storage_key(
+    visibility: MediaVisibility,
+    tenant_id: Uuid,
+    media_id: Uuid,
+    format: MediaFormat,
+    thumbnail: bool,
+) -> String {
+    format!(
+        "{}/{}/{}{}.{}",
+        visibility.as_str(),
+        tenant_id,
+        media_id,
+        if thumbnail { "_thumb" } else { "" },
+        format.extension()
+    )
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MediaAsset {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub uploaded_by: Option<Uuid>,
+    pub backend: String,
+    pub storage_key: String,
+    pub thumbnail_key: Option<String>,
+    pub visibility: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub width: i32,
+    pub height: i32,
+    pub original_filename: Option<String>,
+    pub dt_created: DateTime<Utc>,
+    pub dt_deleted: Option<DateTime<Utc>>,
+}
+
+impl MediaAsset {
+    pub fn is_private(&self) -> bool {
+        self.visibility == MediaVisibility::Private.as_str()
+    }
+}
+
+/// Mídia como exposta na API: URLs prontas (assinadas quando o arquivo é privado)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MediaResponse {
+    pub id: Uuid,
+    pub visibility: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub width: i32,
+    pub height: i32,
+    pub original_filename: Option<String>,
+    pub url: String,
+    pub thumbnail_url: Option<String>,
+    /// Presente apenas para arquivos privados
+    pub url_expires_at: Option<DateTime<Utc>>,
+    pub dt_created: DateTime<Utc>,
+}
+
+/// Arquivo recebido via multipart, ainda não validado
+#[derive(Debug, Clone, Default)]
+pub struct MediaUpload {
+    pub data: Vec<u8>,
+    pub filename: Option<String>,
+    pub declared_content_type: Option<String>,
+    pub visibility: Option<String>,
+    pub alt_text: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductImage {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub media_id: Uuid,
+    pub position: i32,
+    pub alt_text: Option<String>,
+    pub dt_created: DateTime<Utc>,
+    pub dt_updated: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductImageResponse {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub position: i32,
+    pub alt_text: Option<String>,
+    pub media: MediaResponse,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateProductImageRequest {
+    #[validate(length(max = 250, message = "O texto alternativo deve ter até 250 caracteres"))]
+    pub alt_text: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReorderProductImagesRequest {
+    /// Todas as imagens do produto, na nova ordem
+    #[validate(length(min = 1, max = 100, message = "Informe entre 1 e 100 imagens"))]
+    pub image_ids: Vec<Uuid>,
+}
+
+/// Query string das URLs assinadas do backend local
+#[derive(Debug, Deserialize)]
+pub struct SignedFileParams {
+    pub expires: Option<i64>,
+    pub signature: Option<String>,
+}
@@ -0,0 +1,282 @@
+use crate::app_core::app_state::AppState;
+use crate::apps::media::models::{MediaAsset, ProductImage};
+use chrono::{DateTime, Utc};
+use tracing::instrument;
+use uuid::Uuid;
+
+pub struct MediaRepository<'a> {
+    app_state: &'a AppState,
+}
+
+impl<'a> MediaRepository<'a> {
+    pub fn new(app_state: &'a AppState) -> Self {
+        Self { app_state }
+    }
+
+    #[instrument(name = "MediaRepository::create", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create(&self, asset: &MediaAsset) -> Result<MediaAsset, sqlx::Error> {
+        sqlx::query_as!(
+            MediaAsset,
+            r#"
+            INSERT INTO media_assets (
+                id, tenant_id, uploaded_by, backend, storage_key, thumbnail_key, visibility,
+                content_type, size_bytes, width, height, original_filename, dt_created
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, NOW())
+            RETURNING
+                id, tenant_id, uploaded_by, backend, storage_key, thumbnail_key, visibility,
+                content_type, size_bytes, width, height, original_filename,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            "#,
+            asset.id,
+            asset.tenant_id,
+            asset.uploaded_by,
+            asset.backend,
+            asset.storage_key,
+            asset.thumbnail_key,
+            asset.visibility,
+            asset.content_type,
+            asset.size_bytes,
+            asset.width,
+            asset.height,
+            asset.original_filename
+        )
+        .fetch_one(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "MediaRepository::find_by_id", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_id(
+        &self,
+        id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<Option<MediaAsset>, sqlx::Error> {
+        sqlx::query_as!(
+            MediaAsset,
+            r#"
+            SELECT
+                id, tenant_id, uploaded_by, backend, storage_key, thumbnail_key, visibility,
+                content_type, size_bytes, width, height, original_filename,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            FROM media_assets
+            WHERE id = $1 AND tenant_id = $2 AND dt_deleted IS NULL
+            "#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "MediaRepository::find_by_ids", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<MediaAsset>, sqlx::Error> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        sqlx::query_as!(
+            MediaAsset,
+            r#"
+            SELECT
+                id, tenant_id, uploaded_by, backend, storage_key, thumbnail_key, visibility,
+                content_type, size_bytes, width, height, original_filename,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            FROM media_assets
+            WHERE id = ANY($1) AND dt_deleted IS NULL
+            "#,
+            ids
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    /// Remove a mídia e tira das galerias em que aparecia
+    #[instrument(name = "MediaRepository::delete", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete(&self, id: Uuid, tenant_id: Uuid) -> Result<bool, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+
+        let product_ids = sqlx::query_scalar!(
+            "DELETE FROM product_images WHERE media_id = $1 RETURNING product_id",
+            id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for product_id in product_ids {
+            Self::compact_positions(&mut tx, product_id).await?;
+        }
+
+        let result = sqlx::query!(
+            "UPDATE media_assets SET dt_deleted = NOW() WHERE id = $1 AND tenant_id = $2 AND dt_deleted IS NULL",
+            id,
+            tenant_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ===== GALERIA DE PRODUTOS =====
+
+    #[instrument(name = "MediaRepository::list_product_images", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn list_product_images(
+        &self,
+        product_id: Uuid,
+    ) -> Result<Vec<ProductImage>, sqlx::Error> {
+        sqlx::query_as!(
+            ProductImage,
+            r#"
+            SELECT
+                id, product_id, media_id, position, alt_text,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            FROM product_images
+            WHERE product_id = $1
+            ORDER BY position, dt_created
+            "#,
+            product_id
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    /// Adiciona a imagem no fim da galeria
+    #[instrument(name = "MediaRepository::add_product_image", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn add_product_image(
+        &self,
+        product_id: Uuid,
+        media_id: Uuid,
+        alt_text: Option<String>,
+    ) -> Result<ProductImage, sqlx::Error> {
+        sqlx::query_as!(
+            ProductImage,
+            r#"
+            INSERT INTO product_images (id, product_id, media_id, position, alt_text, dt_created, dt_updated)
+            VALUES (
+                $1, $2, $3,
+                (SELECT COALESCE(MAX(position) + 1, 0) FROM product_images WHERE product_id = $2),
+                $4, NOW(), NOW()
+            )
+            RETURNING
+                id, product_id, media_id, position, alt_text,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            "#,
+            Uuid::new_v4(),
+            product_id,
+            media_id,
+            alt_text
+        )
+        .fetch_one(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "MediaRepository::update_product_image", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn update_product_image(
+        &self,
+        id: Uuid,
+        product_id: Uuid,
+        alt_text: Option<String>,
+    ) -> Result<Option<ProductImage>, sqlx::Error> {
+        sqlx::query_as!(
+            ProductImage,
+            r#"
+            UPDATE product_images SET alt_text = $3, dt_updated = NOW()
+            WHERE id = $1 AND product_id = $2
+            RETURNING
+                id, product_id, media_id, position, alt_text,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            "#,
+            id,
+            product_id,
+            alt_text
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    /// Grava a nova ordem (`image_ids` já validado como a galeria completa)
+    #[instrument(name = "MediaRepository::reorder_product_images", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn reorder_product_images(
+        &self,
+        product_id: Uuid,
+        image_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE product_images pi
+            SET position = ordered.position - 1, dt_updated = NOW()
+            FROM UNNEST($2::uuid[]) WITH ORDINALITY AS ordered(id, position)
+            WHERE pi.id = ordered.id AND pi.product_id = $1
+            "#,
+            product_id,
+            image_ids
+        )
+        .execute(&self.app_state.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove a imagem da galeria; devolve a mídia para ser apagada do storage
+    #[instrument(name = "MediaRepository::delete_product_image", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete_product_image(
+        &self,
+        id: Uuid,
+        product_id: Uuid,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+
+        let media_id = sqlx::query_scalar!(
+            "DELETE FROM product_images WHERE id = $1 AND product_id = $2 RETURNING media_id",
+            id,
+            product_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(media_id) = media_id {
+            Self::compact_positions(&mut tx, product_id).await?;
+            sqlx::query!(
+                "UPDATE media_assets SET dt_deleted = NOW() WHERE id = $1 AND dt_deleted IS NULL",
+                media_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(media_id)
+    }
+
+    /// Renumera as posições (0..n) mantendo a ordem atual
+    #[instrument(name = "MediaRepository::compact_positions", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    async fn compact_positions(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        product_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE product_images pi
+            SET position = ordered.position
+            FROM (
+                SELECT id, (ROW_NUMBER() OVER (ORDER BY position, dt_created) - 1)::int AS position
+                FROM product_images
+                WHERE product_id = $1
+            ) ordered
+            WHERE pi.id = ordered.id AND pi.position <> ordered.position
+            "#,
+            product_id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
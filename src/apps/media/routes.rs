@@ -0,0 +1,273 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_extensions::RequestUserExt;
+use crate::app_core::app_state::AppState;
+use crate::app_core::init_settings::get_settings;
+use crate::apps::audit::models::AuditContext;
+use crate::apps::media::models::{
+    MediaUpload, MediaVisibility, ReorderProductImagesRequest, SignedFileParams,
+    UpdateProductImageRequest,
+};
+use crate::apps::media::services::MediaService;
+use actix_multipart::{Field, Multipart};
+use actix_web::http::header;
+use actix_web::web::Json;
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+use futures_util::StreamExt;
+use uuid::Uuid;
+
+/// Limite dos campos de texto do formulário (visibility, alt_text)
+const MAX_TEXT_FIELD_BYTES: usize = 1024;
+
+async fn read_field(field: &mut Field, limit: usize) -> Result<Vec<u8>, AppError> {
+    let mut data = Vec::new();
+    while let Some(chunk) = field.next().await {
+        let chunk =
+            chunk.map_err(|e| AppError::bad_request(format!("Erro ao ler o upload: {}", e)))?;
+        if data.len() + chunk.len() > limit {
+            return Err(AppError::payload_too_large(format!(
+                "O campo '{}' excede o limite de {} bytes",
+                field.name().unwrap_or_default(),
+                limit
+            )));
+        }
+        data.extend_from_slice(&chunk);
+    }
+    Ok(data)
+}
+
+/// Lê o multipart: arquivo no campo "file", mais "visibility" e "alt_text" opcionais
+async fn read_upload(mut payload: Multipart) -> Result<MediaUpload, AppError> {
+    let max_bytes = get_settings().media.max_upload_bytes;
+    let mut upload = MediaUpload::default();
+    let mut has_file = false;
+
+    while let Some(field) = payload.next().await {
+        let mut field =
+            field.map_err(|e| AppError::bad_request(format!("Multipart inválido: {}", e)))?;
+
+        match field.name().unwrap_or_default() {
+            "file" => {
+                has_file = true;
+                upload.filename = field
+                    .content_disposition()
+                    .and_then(|cd| cd.get_filename())
+                    .map(|name| name.chars().take(255).collect());
+                upload.declared_content_type = field
+                    .content_type()
+                    .map(|mime| mime.essence_str().to_string());
+                upload.data = read_field(&mut field, max_bytes).await?;
+            }
+            name @ ("visibility" | "alt_text") => {
+                let name = name.to_string();
+                let value = String::from_utf8(read_field(&mut field, MAX_TEXT_FIELD_BYTES).await?)
+                    .map_err(|_| AppError::bad_request(format!("Campo '{}' inválido", name)))?;
+                match name.as_str() {
+                    "visibility" => upload.visibility = Some(value),
+                    _ => upload.alt_text = Some(value),
+                }
+            }
+            // Campos desconhecidos são descartados
+            _ => {
+                read_field(&mut field, max_bytes).await?;
+            }
+        }
+    }
+
+    if !has_file {
+        return Err(AppError::bad_request("Envie o arquivo no campo 'file'"));
+    }
+    Ok(upload)
+}
+
+/// POST /media/ - upload de imagem (multipart: file, visibility=public|private)
+pub async fn upload_media(
+    app_state: web::Data<AppState>,
+    payload: Multipart,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let audit = AuditContext::from_request(&req);
+    let upload = read_upload(payload).await?;
+
+    let visibility = match upload.visibility.as_deref() {
+        Some(visibility) => visibility
+            .parse::<MediaVisibility>()
+            .map_err(AppError::bad_request)?,
+        None => MediaVisibility::Public,
+    };
+
+    let asset = MediaService::upload(
+        &app_state,
+        tenant_id,
+        Some(user_id),
+        upload,
+        visibility,
+        &audit,
+    )
+    .await?;
+    let result = MediaService::to_response(&asset).await?;
+
+    Ok(HttpResponse::Created().json(serde_json::json!(result)))
+}
+
+pub async fn get_media(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let result = MediaService::get_media(&app_state, id, tenant_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn delete_media(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let audit = AuditContext::from_request(&req);
+    MediaService::delete_media(&app_state, id, tenant_id, &audit).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// GET /media/files/{key} - serve os arquivos do storage (rota pública; privados exigem assinatura)
+pub async fn serve_media_file(
+    path: web::Path<String>,
+    query: web::Query<SignedFileParams>,
+) -> Result<impl Responder, AppError> {
+    let key = path.into_inner();
+    let (data, content_type) = MediaService::read_file(&key, &query).await?;
+
+    let cache_control = if key.starts_with("private/") {
+        "private, no-store"
+    } else {
+        "public, max-age=86400"
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header((header::CACHE_CONTROL, cache_control))
+        .body(data))
+}
+
+/// POST /users/avatar/ - envia a foto de perfil (multipart: file)
+pub async fn upload_avatar(
+    app_state: web::Data<AppState>,
+    payload: Multipart,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = req.user_id()?;
+    let tenant_id = req.tenant_id()?;
+
+    // Mesmo token do header Authorization, para devolver o perfil como no PATCH /users/profile/
+    let auth_header = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .ok_or_else(|| AppError::unauthorized("Token não fornecido"))?;
+    let token = auth_header
+        .strip_prefix("Bearer ")
+        .or_else(|| auth_header.strip_prefix("Token "))
+        .unwrap_or(auth_header)
+        .to_string();
+
+    let audit = AuditContext::from_request(&req);
+    let upload = read_upload(payload).await?;
+    let result =
+        MediaService::upload_avatar(&app_state, user_id, tenant_id, token, upload, &audit).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+// ===== GALERIA DE PRODUTOS =====
+
+pub async fn list_product_images(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let product_id = path.into_inner();
+    let result = MediaService::list_product_images(&app_state, product_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /products/{id}/images/ - multipart: file, alt_text
+pub async fn add_product_image(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Multipart,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let product_id = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let audit = AuditContext::from_request(&req);
+    let upload = read_upload(payload).await?;
+    let result =
+        MediaService::add_product_image(&app_state, product_id, tenant_id, user_id, upload, &audit)
+            .await?;
+
+    Ok(HttpResponse::Created().json(serde_json::json!(result)))
+}
+
+pub async fn reorder_product_images(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Json<ReorderProductImagesRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let product_id = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let audit = AuditContext::from_request(&req);
+    let result = MediaService::reorder_product_images(
+        &app_state,
+        product_id,
+        tenant_id,
+        payload.into_inner(),
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn update_product_image(
+    app_state: web::Data<AppState>,
+    path: web::Path<(Uuid, Uuid)>,
+    payload: Json<UpdateProductImageRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let (product_id, image_id) = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let audit = AuditContext::from_request(&req);
+    let result = MediaService::update_product_image(
+        &app_state,
+        product_id,
+        image_id,
+        tenant_id,
+        payload.into_inner(),
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn delete_product_image(
+    app_state: web::Data<AppState>,
+    path: web::Path<(Uuid, Uuid)>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let (product_id, image_id) = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let audit = AuditContext::from_request(&req);
+    MediaService::delete_product_image(&app_state, product_id, image_id, tenant_id, &audit).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,652 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::app_core::init_settings::get_settings;
+use crate::apps::audit::models::{AuditAction, AuditContext, AuditEntity};
+use crate::apps::audit::services::AuditService;
+use crate::apps::media::models::{
+    MediaAsset, MediaFormat, MediaResponse, MediaUpload, MediaVisibility, ProductImage,
+    ProductImageResponse, ReorderProductImagesRequest, SignedFileParams, UpdateProductImageRequest,
+    storage_key,
+};
+use crate::apps::media::repositories::MediaRepository;
+use crate::apps::media::storage::{StorageError, is_valid_key, storage, verify_signature};
+use crate::apps::product::repositories::ProductRepository;
+use crate::apps::user::models::{UpdateProfileRequest, UserResponse};
+use crate::apps::user::services::UserService;
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+use std::io::Cursor;
+use std::time::Duration;
+use tracing::warn;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Maior largura/altura aceita (evita imagens gigantes que estouram a memória ao decodificar)
+const MAX_IMAGE_DIMENSION: u32 = 10_000;
+
+const MAX_ALT_TEXT_LENGTH: usize = 250;
+
+/// Imagem validada, com as dimensões originais e a miniatura já codificada
+#[derive(Debug)]
+pub struct ProcessedImage {
+    pub format: MediaFormat,
+    pub width: u32,
+    pub height: u32,
+    pub thumbnail: Vec<u8>,
+}
+
+/// Valida tipo (pelo conteúdo) e tamanho e gera a miniatura no mesmo formato
+pub fn process_image(
+    data: &[u8],
+    declared_content_type: Option<&str>,
+    max_bytes: usize,
+    thumbnail_size: u32,
+) -> Result<ProcessedImage, AppError> {
+    if data.is_empty() {
+        return Err(AppError::bad_request("Arquivo vazio"));
+    }
+    if data.len() > max_bytes {
+        return Err(AppError::payload_too_large(format!(
+            "O arquivo excede o limite de {} bytes",
+            max_bytes
+        )));
+    }
+
+    // "application/octet-stream" é o que muitos clientes mandam quando não sabem o tipo
+    let declared = declared_content_type.filter(|ct| *ct != "application/octet-stream");
+    if let Some(declared) = declared
+        && !MediaFormat::ALLOWED_CONTENT_TYPES.contains(&declared)
+    {
+        return Err(AppError::bad_request(format!(
+            "Tipo de arquivo não permitido: {}. Aceitos: {}",
+            declared,
+            MediaFormat::ALLOWED_CONTENT_TYPES.join(", ")
+        )));
+    }
+
+    let format = MediaFormat::detect(data)
+        .ok_or_else(|| AppError::bad_request("O arquivo não é uma imagem suportada"))?;
+    if let Some(declared) = declared
+        && declared != format.content_type()
+    {
+        return Err(AppError::bad_request(format!(
+            "O conteúdo do arquivo ({}) não corresponde ao tipo informado ({})",
+            format.content_type(),
+            declared
+        )));
+    }
+
+    let mut limits = image::Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+
+    let mut reader = image::ImageReader::with_format(Cursor::new(data), format.image_format());
+    reader.limits(limits);
+    let image = reader
+        .decode()
+        .map_err(|e| AppError::bad_request(format!("Imagem inválida: {}", e)))?;
+
+    // Nunca amplia: imagens menores que a miniatura são reaproveitadas como estão
+    let thumbnail = if image.width() > thumbnail_size || image.height() > thumbnail_size {
+        image.thumbnail(thumbnail_size, thumbnail_size)
+    } else {
+        image.clone()
+    };
+
+    let mut encoded = Vec::new();
+    thumbnail
+        .write_to(&mut Cursor::new(&mut encoded), format.image_format())
+        .map_err(|e| AppError::internal(format!("Erro ao gerar miniatura: {}", e)))?;
+
+    Ok(ProcessedImage {
+        format,
+        width: image.width(),
+        height: image.height(),
+        thumbnail: encoded,
+    })
+}
+
+fn storage_error(e: StorageError) -> AppError {
+    match e {
+        StorageError::NotFound | StorageError::InvalidKey => {
+            AppError::not_found("Arquivo não encontrado")
+        }
+        StorageError::Backend(_) => AppError::internal(e.to_string()),
+    }
+}
+
+fn validate_alt_text(alt_text: &Option<String>) -> Result<(), AppError> {
+    match alt_text {
+        Some(alt) if alt.chars().count() > MAX_ALT_TEXT_LENGTH => {
+            Err(AppError::bad_request(format!(
+                "O texto alternativo deve ter até {} caracteres",
+                MAX_ALT_TEXT_LENGTH
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Estado da galeria para a trilha de auditoria
+fn gallery_snapshot(images: &[ProductImage]) -> Option<serde_json::Value> {
+    Some(serde_json::json!({
+        "images": images
+            .iter()
+            .map(|i| serde_json::json!({
+                "id": i.id,
+                "media_id": i.media_id,
+                "position": i.position,
+                "alt_text": i.alt_text,
+            }))
+            .collect::<Vec<_>>()
+    }))
+}
+
+pub struct MediaService;
+
+impl MediaService {
+    /// Valida, gera a miniatura e grava original + miniatura no storage
+    pub async fn upload(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Option<Uuid>,
+        upload: MediaUpload,
+        visibility: MediaVisibility,
+        audit: &AuditContext,
+    ) -> Result<MediaAsset, AppError> {
+        let settings = &get_settings().media;
+        let (max_bytes, thumbnail_size) = (settings.max_upload_bytes, settings.thumbnail_size);
+
+        let MediaUpload {
+            data,
+            filename,
+            declared_content_type,
+            ..
+        } = upload;
+
+        // Decodificar/redimensionar é CPU pesada: fora das threads do executor
+        let (data, processed) = tokio::task::spawn_blocking(move || {
+            let processed = process_image(
+                &data,
+                declared_content_type.as_deref(),
+                max_bytes,
+                thumbnail_size,
+            );
+            (data, processed)
+        })
+        .await
+        .map_err(|e| AppError::internal(format!("Erro ao processar imagem: {}", e)))?;
+        let processed = processed?;
+
+        let id = Uuid::new_v4();
+        let key = storage_key(visibility, tenant_id, id, processed.format, false);
+        let thumbnail_key = storage_key(visibility, tenant_id, id, processed.format, true);
+        let content_type = processed.format.content_type();
+        let size_bytes = data.len() as i64;
+
+        let backend = storage().await;
+        backend
+            .put(&key, data, content_type)
+            .await
+            .map_err(storage_error)?;
+        backend
+            .put(&thumbnail_key, processed.thumbnail, content_type)
+            .await
+            .map_err(storage_error)?;
+
+        let asset = MediaAsset {
+            id,
+            tenant_id,
+            uploaded_by: user_id,
+            backend: backend.name().to_string(),
+            storage_key: key,
+            thumbnail_key: Some(thumbnail_key),
+            visibility: visibility.as_str().to_string(),
+            content_type: content_type.to_string(),
+            size_bytes,
+            width: processed.width as i32,
+            height: processed.height as i32,
+            original_filename: filename,
+            dt_created: Utc::now(),
+            dt_deleted: None,
+        };
+
+        let asset = match MediaRepository::new(app_state).create(&asset).await {
+            Ok(asset) => asset,
+            Err(e) => {
+                // Sem registro no banco os arquivos ficariam órfãos
+                Self::remove_files(&asset).await;
+                return Err(AppError::database_error(e.to_string()));
+            }
+        };
+
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Create,
+            AuditEntity::Media,
+            Some(asset.id),
+            None,
+            AuditService::snapshot(&asset),
+        )
+        .await;
+
+        Ok(asset)
+    }
+
+    /// URLs prontas para o cliente: permanentes se pública, assinadas se privada
+    pub async fn to_response(asset: &MediaAsset) -> Result<MediaResponse, AppError> {
+        let backend = storage().await;
+
+        let (url, thumbnail_url, url_expires_at) = if asset.is_private() {
+            let ttl = Duration::from_secs(get_settings().media.url_ttl_secs);
+            let url = backend
+                .signed_url(&asset.storage_key, ttl)
+                .await
+                .map_err(storage_error)?;
+            let thumbnail_url = match &asset.thumbnail_key {
+                Some(key) => Some(backend.signed_url(key, ttl).await.map_err(storage_error)?),
+                None => None,
+            };
+            let expires_at = Utc::now() + chrono::Duration::seconds(ttl.as_secs() as i64);
+            (url, thumbnail_url, Some(expires_at))
+        } else {
+            (
+                backend.public_url(&asset.storage_key),
+                asset
+                    .thumbnail_key
+                    .as_deref()
+                    .map(|k| backend.public_url(k)),
+                None,
+            )
+        };
+
+        Ok(MediaResponse {
+            id: asset.id,
+            visibility: asset.visibility.clone(),
+            content_type: asset.content_type.clone(),
+            size_bytes: asset.size_bytes,
+            width: asset.width,
+            height: asset.height,
+            original_filename: asset.original_filename.clone(),
+            url,
+            thumbnail_url,
+            url_expires_at,
+            dt_created: asset.dt_created,
+        })
+    }
+
+    pub async fn get_media(
+        app_state: &AppState,
+        id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<MediaResponse, AppError> {
+        let asset = MediaRepository::new(app_state)
+            .find_by_id(id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Mídia não encontrada"))?;
+
+        Self::to_response(&asset).await
+    }
+
+    pub async fn delete_media(
+        app_state: &AppState,
+        id: Uuid,
+        tenant_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<(), AppError> {
+        let repository = MediaRepository::new(app_state);
+        let asset = repository
+            .find_by_id(id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Mídia não encontrada"))?;
+
+        repository
+            .delete(id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        Self::remove_files(&asset).await;
+
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Delete,
+            AuditEntity::Media,
+            Some(id),
+            AuditService::snapshot(&asset),
+            None,
+        )
+        .await;
+
+        Ok(())
+    }
+
+    /// Conteúdo de um arquivo para a rota /media/files/ (privados exigem URL assinada válida)
+    pub async fn read_file(
+        key: &str,
+        params: &SignedFileParams,
+    ) -> Result<(Vec<u8>, &'static str), AppError> {
+        if !is_valid_key(key) {
+            return Err(AppError::not_found("Arquivo não encontrado"));
+        }
+
+        if key.starts_with("private/") {
+            let valid = match (params.expires, params.signature.as_deref()) {
+                (Some(expires), Some(signature)) => verify_signature(
+                    &get_settings().media.signing_secret,
+                    key,
+                    expires,
+                    signature,
+                    Utc::now().timestamp(),
+                ),
+                _ => false,
+            };
+            if !valid {
+                return Err(AppError::forbidden("URL expirada ou assinatura inválida"));
+            }
+        }
+
+        let content_type = key
+            .rsplit_once('.')
+            .and_then(|(_, extension)| MediaFormat::from_extension(extension))
+            .map(|format| format.content_type())
+            .unwrap_or("application/octet-stream");
+
+        let data = storage().await.get(key).await.map_err(storage_error)?;
+        Ok((data, content_type))
+    }
+
+    /// Remoção dos arquivos é best-effort: falha só gera log (o registro já foi apagado)
+    async fn remove_files(asset: &MediaAsset) {
+        let backend = storage().await;
+        for key in std::iter::once(&asset.storage_key).chain(asset.thumbnail_key.as_ref()) {
+            if let Err(e) = backend.delete(key).await {
+                warn!(media_id = %asset.id, key = %key, error = %e, "Erro ao remover arquivo do storage");
+            }
+        }
+    }
+
+    // ===== AVATAR =====
+
+    /// Envia a imagem (pública) e grava a URL em `profiles.avatar`
+    pub async fn upload_avatar(
+        app_state: &AppState,
+        user_id: Uuid,
+        tenant_id: Uuid,
+        token: String,
+        upload: MediaUpload,
+        audit: &AuditContext,
+    ) -> Result<UserResponse, AppError> {
+        let asset = Self::upload(
+            app_state,
+            tenant_id,
+            Some(user_id),
+            upload,
+            MediaVisibility::Public,
+            audit,
+        )
+        .await?;
+        let url = storage().await.public_url(&asset.storage_key);
+
+        let request = UpdateProfileRequest {
+            bio: None,
+            phone: None,
+            birth_date: None,
+            profession: None,
+            document: None,
+            avatar: Some(url),
+        };
+        UserService::update_profile(user_id, request, token, app_state, audit).await
+    }
+
+    // ===== GALERIA DE PRODUTOS =====
+
+    async fn ensure_product_owner(
+        app_state: &AppState,
+        product_id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<(), AppError> {
+        let product = ProductRepository::new(app_state)
+            .find_by_id(product_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        match product {
+            Some(product) if product.tenant_id == tenant_id => Ok(()),
+            _ => Err(AppError::not_found("Produto não encontrado")),
+        }
+    }
+
+    async fn load_gallery(
+        app_state: &AppState,
+        product_id: Uuid,
+    ) -> Result<Vec<ProductImage>, AppError> {
+        MediaRepository::new(app_state)
+            .list_product_images(product_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    async fn gallery_response(
+        app_state: &AppState,
+        images: Vec<ProductImage>,
+    ) -> Result<Vec<ProductImageResponse>, AppError> {
+        let media_ids: Vec<Uuid> = images.iter().map(|i| i.media_id).collect();
+        let media: HashMap<Uuid, MediaAsset> = MediaRepository::new(app_state)
+            .find_by_ids(&media_ids)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .into_iter()
+            .map(|m| (m.id, m))
+            .collect();
+
+        let mut response = Vec::with_capacity(images.len());
+        for image in images {
+            let Some(asset) = media.get(&image.media_id) else {
+                continue;
+            };
+            response.push(ProductImageResponse {
+                id: image.id,
+                product_id: image.product_id,
+                position: image.position,
+                alt_text: image.alt_text,
+                media: Self::to_response(asset).await?,
+            });
+        }
+
+        Ok(response)
+    }
+
+    /// Galeria ordenada do produto
+    pub async fn list_product_images(
+        app_state: &AppState,
+        product_id: Uuid,
+    ) -> Result<Vec<ProductImageResponse>, AppError> {
+        let product = ProductRepository::new(app_state)
+            .find_by_id(product_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        if product.is_none() {
+            return Err(AppError::not_found("Produto não encontrado"));
+        }
+
+        let images = Self::load_gallery(app_state, product_id).await?;
+        Self::gallery_response(app_state, images).await
+    }
+
+    /// Envia uma imagem (sempre pública) e a coloca no fim da galeria
+    pub async fn add_product_image(
+        app_state: &AppState,
+        product_id: Uuid,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        mut upload: MediaUpload,
+        audit: &AuditContext,
+    ) -> Result<ProductImageResponse, AppError> {
+        let alt_text = upload.alt_text.take().filter(|alt| !alt.trim().is_empty());
+        validate_alt_text(&alt_text)?;
+        Self::ensure_product_owner(app_state, product_id, tenant_id).await?;
+
+        let before = Self::load_gallery(app_state, product_id).await?;
+        let asset = Self::upload(
+            app_state,
+            tenant_id,
+            Some(user_id),
+            upload,
+            MediaVisibility::Public,
+            audit,
+        )
+        .await?;
+
+        let repository = MediaRepository::new(app_state);
+        let image = match repository
+            .add_product_image(product_id, asset.id, alt_text)
+            .await
+        {
+            Ok(image) => image,
+            Err(e) => {
+                let _ = repository.delete(asset.id, tenant_id).await;
+                Self::remove_files(&asset).await;
+                return Err(AppError::database_error(e.to_string()));
+            }
+        };
+
+        let after = Self::load_gallery(app_state, product_id).await?;
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Update,
+            AuditEntity::Product,
+            Some(product_id),
+            gallery_snapshot(&before),
+            gallery_snapshot(&after),
+        )
+        .await;
+
+        Ok(ProductImageResponse {
+            id: image.id,
+            product_id: image.product_id,
+            position: image.position,
+            alt_text: image.alt_text,
+            media: Self::to_response(&asset).await?,
+        })
+    }
+
+    pub async fn update_product_image(
+        app_state: &AppState,
+        product_id: Uuid,
+        image_id: Uuid,
+        tenant_id: Uuid,
+        request: UpdateProductImageRequest,
+        audit: &AuditContext,
+    ) -> Result<Vec<ProductImageResponse>, AppError> {
+        request.validate()?;
+        Self::ensure_product_owner(app_state, product_id, tenant_id).await?;
+
+        let before = Self::load_gallery(app_state, product_id).await?;
+        let alt_text = request.alt_text.filter(|alt| !alt.trim().is_empty());
+        MediaRepository::new(app_state)
+            .update_product_image(image_id, product_id, alt_text)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Imagem não encontrada"))?;
+
+        let after = Self::load_gallery(app_state, product_id).await?;
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Update,
+            AuditEntity::Product,
+            Some(product_id),
+            gallery_snapshot(&before),
+            gallery_snapshot(&after),
+        )
+        .await;
+
+        Self::gallery_response(app_state, after).await
+    }
+
+    pub async fn reorder_product_images(
+        app_state: &AppState,
+        product_id: Uuid,
+        tenant_id: Uuid,
+        request: ReorderProductImagesRequest,
+        audit: &AuditContext,
+    ) -> Result<Vec<ProductImageResponse>, AppError> {
+        request.validate()?;
+        Self::ensure_product_owner(app_state, product_id, tenant_id).await?;
+
+        let before = Self::load_gallery(app_state, product_id).await?;
+        let current: HashSet<Uuid> = before.iter().map(|i| i.id).collect();
+        let requested: HashSet<Uuid> = request.image_ids.iter().copied().collect();
+        if requested.len() != request.image_ids.len() || requested != current {
+            return Err(AppError::bad_request(
+                "A nova ordem deve conter todas as imagens do produto, sem repetição",
+            ));
+        }
+
+        MediaRepository::new(app_state)
+            .reorder_product_images(product_id, &request.image_ids)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        let after = Self::load_gallery(app_state, product_id).await?;
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Update,
+            AuditEntity::Product,
+            Some(product_id),
+            gallery_snapshot(&before),
+            gallery_snapshot(&after),
+        )
+        .await;
+
+        Self::gallery_response(app_state, after).await
+    }
+
+    /// Tira a imagem da galeria e apaga o arquivo
+    pub async fn delete_product_image(
+        app_state: &AppState,
+        product_id: Uuid,
+        image_id: Uuid,
+        tenant_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<(), AppError> {
+        Self::ensure_product_owner(app_state, product_id, tenant_id).await?;
+
+        let before = Self::load_gallery(app_state, product_id).await?;
+        let repository = MediaRepository::new(app_state);
+        let asset = match before.iter().find(|i| i.id == image_id) {
+            Some(image) => repository
+                .find_by_id(image.media_id, tenant_id)
+                .await
+                .map_err(|e| AppError::database_error(e.to_string()))?,
+            None => return Err(AppError::not_found("Imagem não encontrada")),
+        };
+
+        repository
+            .delete_product_image(image_id, product_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        if let Some(asset) = asset {
+            Self::remove_files(&asset).await;
+        }
+
+        let after = Self::load_gallery(app_state, product_id).await?;
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Update,
+            AuditEntity::Product,
+            Some(product_id),
+            gallery_snapshot(&before),
+            gallery_snapshot(&after),
+        )
+        .await;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,288 @@
+use crate::app_core::init_settings::get_settings;
+use crate::app_core::settings::{MediaSettings, S3Settings, StorageBackendKind};
+use async_trait::async_trait;
+use aws_sdk_s3::presigning::PresigningConfig;
+use aws_sdk_s3::primitives::ByteStream;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+#[derive(Debug)]
+pub enum StorageError {
+    NotFound,
+    InvalidKey,
+    Backend(String),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::NotFound => write!(f, "Arquivo não encontrado"),
+            StorageError::InvalidKey => write!(f, "Chave de arquivo inválida"),
+            StorageError::Backend(e) => write!(f, "Erro no storage: {}", e),
+        }
+    }
+}
+
+/// Onde os arquivos de mídia ficam guardados. As chaves são caminhos relativos
+/// ("public/<tenant>/<id>.jpg"); o prefixo define se o arquivo é público ou privado.
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> Result<(), StorageError>;
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError>;
+
+    /// Remover um arquivo inexistente não é erro
+    async fn delete(&self, key: &str) -> Result<(), StorageError>;
+
+    /// URL permanente, para arquivos públicos
+    fn public_url(&self, key: &str) -> String;
+
+    /// URL que expira em `ttl`, para arquivos privados
+    async fn signed_url(&self, key: &str, ttl: Duration) -> Result<String, StorageError>;
+}
+
+/// Chaves aceitas: segmentos com letras, números, '.', '_' e '-' (sem "..")
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+        })
+}
+
+/// Assinatura HMAC-SHA256 de `key` válida até `expires` (timestamp unix)
+pub fn sign_key(secret: &str, key: &str, expires: i64) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC aceita chave de qualquer tamanho");
+    mac.update(format!("{}:{}", key, expires).as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Confere a assinatura (em tempo constante) e se ainda não expirou
+pub fn verify_signature(secret: &str, key: &str, expires: i64, signature: &str, now: i64) -> bool {
+    if expires < now {
+        return false;
+    }
+
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC aceita chave de qualquer tamanho");
+    mac.update(format!("{}:{}", key, expires).as_bytes());
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// Arquivos no disco, servidos pela rota /media/files/ (privados exigem assinatura)
+pub struct LocalStorage {
+    root: PathBuf,
+    base_url: String,
+    secret: String,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>, base_url: &str, secret: &str) -> Self {
+        Self {
+            root: root.into(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            secret: secret.to_string(),
+        }
+    }
+
+    fn path_for(&self, key: &str) -> Result<PathBuf, StorageError> {
+        if !is_valid_key(key) {
+            return Err(StorageError::InvalidKey);
+        }
+        Ok(self.root.join(Path::new(key)))
+    }
+}
+
+#[async_trait]
+impl StorageBackend for LocalStorage {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn put(&self, key: &str, data: Vec<u8>, _content_type: &str) -> Result<(), StorageError> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| StorageError::Backend(e.to_string()))?;
+        }
+
+        // Escreve em arquivo temporário e renomeia: leitores nunca veem arquivo pela metade
+        let tmp = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4()));
+        tokio::fs::write(&tmp, data)
+            .await
+            .map_err(|e| StorageError::Backend(e.to_string()))?;
+        tokio::fs::rename(&tmp, &path)
+            .await
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        let path = self.path_for(key)?;
+        tokio::fs::read(path).await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => StorageError::NotFound,
+            _ => StorageError::Backend(e.to_string()),
+        })
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(StorageError::Backend(e.to_string())),
+        }
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.base_url, key)
+    }
+
+    async fn signed_url(&self, key: &str, ttl: Duration) -> Result<String, StorageError> {
+        let expires = chrono::Utc::now().timestamp() + ttl.as_secs() as i64;
+        Ok(format!(
+            "{}/{}?expires={}&signature={}",
+            self.base_url,
+            key,
+            expires,
+            sign_key(&self.secret, key, expires)
+        ))
+    }
+}
+
+/// Bucket S3 (ou compatível); arquivos privados usam URLs pré-assinadas do próprio S3
+pub struct S3Storage {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+    base_url: String,
+}
+
+impl S3Storage {
+    pub async fn new(settings: &S3Settings, base_url: &str) -> Self {
+        let shared = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new(settings.region.clone()))
+            .load()
+            .await;
+
+        let mut config =
+            aws_sdk_s3::config::Builder::from(&shared).force_path_style(settings.force_path_style);
+        if let Some(endpoint) = &settings.endpoint {
+            config = config.endpoint_url(endpoint);
+        }
+
+        Self {
+            client: aws_sdk_s3::Client::from_conf(config.build()),
+            bucket: settings.bucket.clone(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl StorageBackend for S3Storage {
+    fn name(&self) -> &'static str {
+        "s3"
+    }
+
+    async fn put(&self, key: &str, data: Vec<u8>, content_type: &str) -> Result<(), StorageError> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(data))
+            .send()
+            .await
+            .map_err(|e| StorageError::Backend(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        let object = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| match e.as_service_error() {
+                Some(err) if err.is_no_such_key() => StorageError::NotFound,
+                _ => StorageError::Backend(e.to_string()),
+            })?;
+
+        let data = object
+            .body
+            .collect()
+            .await
+            .map_err(|e| StorageError::Backend(e.to_string()))?;
+        Ok(data.into_bytes().to_vec())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| StorageError::Backend(e.to_string()))?;
+        Ok(())
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.base_url, key)
+    }
+
+    async fn signed_url(&self, key: &str, ttl: Duration) -> Result<String, StorageError> {
+        let presigning =
+            PresigningConfig::expires_in(ttl).map_err(|e| StorageError::Backend(e.to_string()))?;
+
+        let request = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .presigned(presigning)
+            .await
+            .map_err(|e| StorageError::Backend(e.to_string()))?;
+        Ok(request.uri().to_string())
+    }
+}
+
+pub async fn build_storage(settings: &MediaSettings) -> Box<dyn StorageBackend> {
+    match (settings.backend, &settings.s3) {
+        (StorageBackendKind::S3, Some(s3)) => {
+            Box::new(S3Storage::new(s3, &settings.public_base_url).await)
+        }
+        _ => Box::new(LocalStorage::new(
+            &settings.local_root,
+            &settings.public_base_url,
+            &settings.signing_secret,
+        )),
+    }
+}
+
+static STORAGE: OnceCell<Box<dyn StorageBackend>> = OnceCell::const_new();
+
+/// Backend configurado em MEDIA_STORAGE_BACKEND (criado no primeiro uso)
+pub async fn storage() -> &'static dyn StorageBackend {
+    STORAGE
+        .get_or_init(|| build_storage(&get_settings().media))
+        .await
+        .as_ref()
+}
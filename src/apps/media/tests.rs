@@ -0,0 +1,177 @@
+#[cfg(test)]
+mod tests {
+    use crate::app_core::app_error::AppError;
+    use crate::apps::media::models::{MediaFormat, MediaVisibility, storage_key};
+    use crate::apps::media::services::process_image;
+    use crate::apps::media::storage::{
+        LocalStorage, StorageBackend, StorageError, is_valid_key, sign_key, verify_signature,
+    };
+    use std::io::Cursor;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    const SECRET: &str = "segredo-de-teste-com-mais-de-32-caracteres";
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = image::RgbImage::from_pixel(width, height, image::Rgb([200, 30, 30]));
+        let mut data = Vec::new();
+        image::DynamicImage::ImageRgb8(image)
+            .write_to(&mut Cursor::new(&mut data), image::ImageFormat::Png)
+            .unwrap();
+        data
+    }
+
+    // ===== VALIDAÇÃO E MINIATURAS =====
+
+    #[test]
+    fn test_format_is_detected_from_content() {
+        assert_eq!(MediaFormat::detect(&png(4, 4)), Some(MediaFormat::Png));
+        assert_eq!(MediaFormat::detect(b"%PDF-1.7 nada de imagem"), None);
+        assert_eq!(MediaFormat::from_extension("JPG"), Some(MediaFormat::Jpeg));
+        assert_eq!(MediaFormat::from_extension("svg"), None);
+    }
+
+    #[test]
+    fn test_process_image_generates_bounded_thumbnail() {
+        let processed = process_image(&png(800, 400), Some("image/png"), 1_000_000, 320).unwrap();
+
+        assert_eq!(processed.format, MediaFormat::Png);
+        assert_eq!((processed.width, processed.height), (800, 400));
+
+        let thumbnail = image::load_from_memory(&processed.thumbnail).unwrap();
+        assert_eq!((thumbnail.width(), thumbnail.height()), (320, 160));
+    }
+
+    #[test]
+    fn test_process_image_never_upscales() {
+        let processed = process_image(&png(100, 50), None, 1_000_000, 320).unwrap();
+        let thumbnail = image::load_from_memory(&processed.thumbnail).unwrap();
+        assert_eq!((thumbnail.width(), thumbnail.height()), (100, 50));
+    }
+
+    #[test]
+    fn test_process_image_rejects_invalid_uploads() {
+        let data = png(10, 10);
+
+        // Tipo fora da lista
+        assert!(matches!(
+            process_image(&data, Some("image/svg+xml"), 1_000_000, 320),
+            Err(AppError::BadRequest(_))
+        ));
+        // Conteúdo não bate com o tipo declarado
+        assert!(matches!(
+            process_image(&data, Some("image/jpeg"), 1_000_000, 320),
+            Err(AppError::BadRequest(_))
+        ));
+        // Não é imagem
+        assert!(matches!(
+            process_image(b"texto qualquer", None, 1_000_000, 320),
+            Err(AppError::BadRequest(_))
+        ));
+        // Acima do limite
+        assert!(matches!(
+            process_image(&data, Some("image/png"), 10, 320),
+            Err(AppError::PayloadTooLarge(_))
+        ));
+        // Vazio
+        assert!(matches!(
+            process_image(&[], None, 1_000_000, 320),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+
+    // ===== CHAVES E URLS ASSINADAS =====
+
+    #[test]
+    fn test_storage_keys() {
+        let tenant_id = Uuid::new_v4();
+        let media_id = Uuid::new_v4();
+
+        let key = storage_key(
+            MediaVisibility::Private,
+            tenant_id,
+            media_id,
+            MediaFormat::Jpeg,
+            true,
+        );
+        assert_eq!(key, format!("private/{}/{}_thumb.jpg", tenant_id, media_id));
+        assert!(is_valid_key(&key));
+
+        assert!(!is_valid_key(""));
+        assert!(!is_valid_key("../etc/passwd"));
+        assert!(!is_valid_key("public//a.png"));
+        assert!(!is_valid_key("public/a b.png"));
+        assert!(!is_valid_key("/public/a.png"));
+    }
+
+    #[test]
+    fn test_signed_url_signature() {
+        let key = "private/t/a.png";
+        let now = 1_700_000_000;
+        let signature = sign_key(SECRET, key, now + 60);
+
+        assert!(verify_signature(SECRET, key, now + 60, &signature, now));
+        // Expirada
+        assert!(!verify_signature(
+            SECRET,
+            key,
+            now + 60,
+            &signature,
+            now + 61
+        ));
+        // Outra chave, outro prazo ou outro segredo
+        assert!(!verify_signature(
+            SECRET,
+            "private/t/b.png",
+            now + 60,
+            &signature,
+            now
+        ));
+        assert!(!verify_signature(SECRET, key, now + 120, &signature, now));
+        assert!(!verify_signature(
+            "outro-segredo-com-mais-de-32-caracteres!!",
+            key,
+            now + 60,
+            &signature,
+            now
+        ));
+        assert!(!verify_signature(SECRET, key, now + 60, "nao-e-hex", now));
+    }
+
+    #[actix_web::test]
+    async fn test_local_storage_roundtrip() {
+        let root = std::env::temp_dir().join(format!("media_test_{}", Uuid::new_v4()));
+        let storage = LocalStorage::new(&root, "http://localhost/api/v1/media/files/", SECRET);
+
+        storage
+            .put("public/t/a.png", b"conteudo".to_vec(), "image/png")
+            .await
+            .unwrap();
+        assert_eq!(storage.get("public/t/a.png").await.unwrap(), b"conteudo");
+        assert_eq!(
+            storage.public_url("public/t/a.png"),
+            "http://localhost/api/v1/media/files/public/t/a.png"
+        );
+
+        let url = storage
+            .signed_url("private/t/a.png", Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(url.starts_with("http://localhost/api/v1/media/files/private/t/a.png?expires="));
+        assert!(url.contains("&signature="));
+
+        storage.delete("public/t/a.png").await.unwrap();
+        // Remover de novo não é erro
+        storage.delete("public/t/a.png").await.unwrap();
+        assert!(matches!(
+            storage.get("public/t/a.png").await,
+            Err(StorageError::NotFound)
+        ));
+        assert!(matches!(
+            storage.get("../fora.png").await,
+            Err(StorageError::InvalidKey)
+        ));
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
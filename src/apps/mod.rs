@@ -4,3 +4,16 @@ pub mod user;
 pub mod tenant;
 pub mod product;
 pub mod cart;
+pub mod audit;
+pub mod category;
+pub mod media;
+pub mod product_bulk;
+pub mod product_price;
+pub mod exchange_rate;
+pub mod wishlist;
+pub mod order;
+pub mod payment;
+pub mod order_return;
+pub mod commission;
+pub mod ledger;
+pub mod payout;
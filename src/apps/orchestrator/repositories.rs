@@ -4,6 +4,7 @@ use crate::apps::orchestrator::models::{
 };
 use chrono::{DateTime, NaiveDateTime, Utc};
 use sqlx::Row;
+use tracing::instrument;
 use uuid::Uuid;
 
 pub struct OrchestratorRepository<'a> {
@@ -15,6 +16,7 @@ impl<'a> OrchestratorRepository<'a> {
         Self { app_state }
     }
 
+    #[instrument(name = "OrchestratorRepository::find_all", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_all(&self) -> Result<Vec<Orchestrator>, sqlx::Error> {
         let rows = sqlx::query!(
             "SELECT id, app_name, app_url, app_token, dt_created, dt_updated, dt_deleted FROM orchestrators WHERE dt_deleted IS NULL"
@@ -40,6 +42,7 @@ impl<'a> OrchestratorRepository<'a> {
         Ok(orchestrators)
     }
 
+    #[instrument(name = "OrchestratorRepository::find_by_id", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Orchestrator>, sqlx::Error> {
         let row = sqlx::query!(
             "SELECT id, app_name, app_url, app_token, dt_created, dt_updated, dt_deleted FROM orchestrators WHERE id = $1 AND dt_deleted IS NULL",
@@ -61,6 +64,7 @@ impl<'a> OrchestratorRepository<'a> {
         }))
     }
 
+    #[instrument(name = "OrchestratorRepository::find_by_app_token", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_app_token(
         &self,
         app_token: Uuid,
@@ -85,6 +89,7 @@ impl<'a> OrchestratorRepository<'a> {
         }))
     }
 
+    #[instrument(name = "OrchestratorRepository::create", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn create(
         &self,
         request: CreateOrchestratorRequest,
@@ -118,6 +123,7 @@ impl<'a> OrchestratorRepository<'a> {
         })
     }
 
+    #[instrument(name = "OrchestratorRepository::update", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn update(
         &self,
         id: Uuid,
@@ -169,6 +175,7 @@ impl<'a> OrchestratorRepository<'a> {
         }))
     }
 
+    #[instrument(name = "OrchestratorRepository::delete", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
         let now = Utc::now();
 
@@ -1,6 +1,7 @@
 use crate::app_core::app_error::AppError;
 use crate::app_core::app_extensions::RequestUserExt;
 use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::AuditContext;
 use crate::apps::orchestrator::models::{CreateOrchestratorRequest, SyncAllUsersRequest};
 use crate::apps::orchestrator::services::OrchestratorService;
 use actix_web::{HttpRequest, HttpResponse, Responder, web};
@@ -72,12 +73,11 @@ pub async fn create_orchestrator(
     }
 
     // Validar o payload
-    payload
-        .validate()
-        .map_err(|e| AppError::bad_request(format!("Dados inválidos: {}", e)))?;
+    payload.validate()?;
 
+    let audit = AuditContext::from_request(&req);
     let orchestrator =
-        OrchestratorService::create_orchestrator(&app_state, payload.into_inner()).await?;
+        OrchestratorService::create_orchestrator(&app_state, payload.into_inner(), &audit).await?;
 
     Ok(HttpResponse::Created().json(serde_json::json!({
         "message": "App cadastrado com sucesso",
@@ -119,11 +119,10 @@ pub async fn sync_all_users_with_app(
     }
 
     // Validar o payload
-    payload
-        .validate()
-        .map_err(|e| AppError::bad_request(format!("Dados inválidos: {}", e)))?;
+    payload.validate()?;
 
-    OrchestratorService::sync_all_users_with_app(&app_state, payload.into_inner()).await?;
+    let audit = AuditContext::from_request(&req);
+    OrchestratorService::sync_all_users_with_app(&app_state, payload.into_inner(), &audit).await?;
 
     Ok(HttpResponse::Ok().json(serde_json::json!({
         "message": "Sincronização de usuários iniciada com sucesso"
@@ -149,7 +148,8 @@ pub async fn delete_orchestrator(
     let id = Uuid::parse_str(&id_str)
         .map_err(|_| AppError::bad_request("ID inválido".to_string()))?;
 
-    let deleted = OrchestratorService::delete_orchestrator(&app_state, id).await?;
+    let audit = AuditContext::from_request(&req);
+    let deleted = OrchestratorService::delete_orchestrator(&app_state, id, &audit).await?;
 
     if deleted {
         Ok(HttpResponse::Ok().json(serde_json::json!({
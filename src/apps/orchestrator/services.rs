@@ -1,5 +1,7 @@
 use crate::app_core::app_error::AppError;
 use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::{AuditAction, AuditContext, AuditEntity};
+use crate::apps::audit::services::AuditService;
 use crate::apps::orchestrator::models::{
     AppAuthorizationResponse, CreateOrchestratorRequest, OrchestratorResponse, SyncAllUsersRequest,
     UpdateOrchestratorRequest,
@@ -9,7 +11,7 @@ use crate::apps::sync_app::producer::SyncProducer;
 use crate::apps::tenant::repositories::TenantRepository;
 use crate::apps::user::models::UserWithProfile;
 use crate::apps::user::repositories::{ProfileRepository, UserRepository};
-use crate::utils::pagination::PaginatedResponse;
+use crate::utils::pagination::{PageRequest, PaginatedResponse};
 use tracing::{error, info};
 use uuid::Uuid;
 
@@ -53,6 +55,7 @@ impl OrchestratorService {
     pub async fn create_orchestrator(
         app_state: &AppState,
         request: CreateOrchestratorRequest,
+        audit: &AuditContext,
     ) -> Result<OrchestratorResponse, AppError> {
         let repository = OrchestratorRepository::new(app_state);
         let orchestrator = repository
@@ -60,6 +63,17 @@ impl OrchestratorService {
             .await
             .map_err(|e| AppError::database_error(e.to_string()))?;
 
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Create,
+            AuditEntity::Orchestrator,
+            Some(orchestrator.id),
+            None,
+            AuditService::snapshot(&orchestrator),
+        )
+        .await;
+
         Ok(orchestrator.into())
     }
 
@@ -67,22 +81,65 @@ impl OrchestratorService {
         app_state: &AppState,
         id: Uuid,
         request: UpdateOrchestratorRequest,
+        audit: &AuditContext,
     ) -> Result<Option<OrchestratorResponse>, AppError> {
         let repository = OrchestratorRepository::new(app_state);
+        let before = repository
+            .find_by_id(id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
         let orchestrator = repository
             .update(id, request)
             .await
             .map_err(|e| AppError::database_error(e.to_string()))?;
 
+        if let Some(orchestrator) = &orchestrator {
+            AuditService::record(
+                app_state,
+                audit,
+                AuditAction::Update,
+                AuditEntity::Orchestrator,
+                Some(orchestrator.id),
+                before.as_ref().and_then(AuditService::snapshot),
+                AuditService::snapshot(orchestrator),
+            )
+            .await;
+        }
+
         Ok(orchestrator.map(|o| o.into()))
     }
 
-    pub async fn delete_orchestrator(app_state: &AppState, id: Uuid) -> Result<bool, AppError> {
+    pub async fn delete_orchestrator(
+        app_state: &AppState,
+        id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<bool, AppError> {
         let repository = OrchestratorRepository::new(app_state);
-        repository
+        let before = repository
+            .find_by_id(id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        let deleted = repository
             .delete(id)
             .await
-            .map_err(|e| AppError::database_error(e.to_string()))
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        if deleted {
+            AuditService::record(
+                app_state,
+                audit,
+                AuditAction::Delete,
+                AuditEntity::Orchestrator,
+                Some(id),
+                before.as_ref().and_then(AuditService::snapshot),
+                None,
+            )
+            .await;
+        }
+
+        Ok(deleted)
     }
 
     pub async fn authorize_app(
@@ -110,6 +167,7 @@ impl OrchestratorService {
     pub async fn sync_all_users_with_app(
         app_state: &AppState,
         request: SyncAllUsersRequest,
+        audit: &AuditContext,
     ) -> Result<(), AppError> {
         info!(
             "Iniciando sync de todos os usuários com o app: {}",
@@ -121,7 +179,10 @@ impl OrchestratorService {
         let profile_repo = ProfileRepository::new(app_state);
 
         let users = user_repo
-            .find_all_paginated(1000, 0) // Buscar até 1000 usuários
+            .find_all_paginated(&PageRequest::Offset {
+                limit: 1000,
+                offset: 0,
+            }) // Buscar até 1000 usuários
             .await
             .map_err(|e| AppError::database_error(e.to_string()))?;
 
@@ -174,6 +235,21 @@ impl OrchestratorService {
             success_count, error_count
         );
 
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Sync,
+            AuditEntity::Orchestrator,
+            None,
+            None,
+            Some(serde_json::json!({
+                "app_name": request.app_name,
+                "success_count": success_count,
+                "error_count": error_count,
+            })),
+        )
+        .await;
+
         if error_count > 0 {
             return Err(AppError::internal(format!(
                 "Sync concluído com {} erros de {} usuários",
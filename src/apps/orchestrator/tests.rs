@@ -1,5 +1,5 @@
 #[cfg(test)]
-#[allow(unused_imports)]
+#[allow(unused_imports, clippy::assertions_on_constants)]
 mod tests {
     use super::*;
     use crate::apps::orchestrator::models::Orchestrator;
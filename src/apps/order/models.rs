@@ -0,0 +1,206 @@
+use crate::utils::money::Money;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+use uuid::Uuid;
+
+/// Estado do pedido; na API sai em minúsculas (`pending_payment`, `paid`, ...)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "order_status", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "lowercase")]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub enum OrderStatus {
+    PENDING_PAYMENT,
+    PAID,
+    FULFILLING,
+    SHIPPED,
+    DELIVERED,
+    CANCELLED,
+    REFUNDED,
+}
+
+impl OrderStatus {
+    /// Único lugar que decide as transições do ciclo de vida
+    pub fn can_transition_to(self, next: OrderStatus) -> bool {
+        use OrderStatus::*;
+
+        matches!(
+            (self, next),
+            (PENDING_PAYMENT, PAID)
+                | (PENDING_PAYMENT, CANCELLED)
+                | (PAID, FULFILLING)
+                | (PAID, SHIPPED)
+                | (PAID, CANCELLED)
+                | (PAID, REFUNDED)
+                | (FULFILLING, SHIPPED)
+                | (FULFILLING, CANCELLED)
+                | (SHIPPED, DELIVERED)
+                | (DELIVERED, REFUNDED)
+        )
+    }
+
+    /// Ainda não saiu para entrega (o comprador pode cancelar)
+    pub fn is_before_shipment(self) -> bool {
+        matches!(
+            self,
+            OrderStatus::PENDING_PAYMENT | OrderStatus::PAID | OrderStatus::FULFILLING
+        )
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderStatus::PENDING_PAYMENT => "pending_payment",
+            OrderStatus::PAID => "paid",
+            OrderStatus::FULFILLING => "fulfilling",
+            OrderStatus::SHIPPED => "shipped",
+            OrderStatus::DELIVERED => "delivered",
+            OrderStatus::CANCELLED => "cancelled",
+            OrderStatus::REFUNDED => "refunded",
+        }
+    }
+}
+
+/// Quem fez a transição
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderActorRole {
+    Buyer,
+    Seller,
+    System,
+}
+
+impl OrderActorRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderActorRole::Buyer => "buyer",
+            OrderActorRole::Seller => "seller",
+            OrderActorRole::System => "system",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "buyer" => OrderActorRole::Buyer,
+            "seller" => OrderActorRole::Seller,
+            _ => OrderActorRole::System,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Order {
+    pub id: Uuid,
+    pub cart_id: Uuid,
+    pub buyer_tenant_id: Uuid,
+    pub buyer_user_id: Uuid,
+    /// Loja dona dos produtos do pedido
+    pub seller_tenant_id: Uuid,
+    pub status: OrderStatus,
+    pub currency: String,
+    pub subtotal: Money,
+    pub discount_total: Money,
+    pub tax_total: Money,
+    pub shipping_total: Money,
+    pub grand_total: Money,
+    /// Soma das notas de crédito (devoluções reembolsadas)
+    pub refunded_total: Money,
+    pub tracking_code: Option<String>,
+    pub carrier: Option<String>,
+    pub dt_created: DateTime<Utc>,
+    pub dt_updated: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderItem {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    pub product_id: Uuid,
+    pub variant_id: Option<Uuid>,
+    pub attributes_snapshot: serde_json::Value,
+    pub product_name: String,
+    pub unit_price: Money,
+    pub quantity: i32,
+    pub line_total: Money,
+    pub dt_created: DateTime<Utc>,
+}
+
+/// Registro do histórico de status; `from_status` vazio na criação do pedido
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderStatusChange {
+    pub id: Uuid,
+    pub from_status: Option<OrderStatus>,
+    pub to_status: OrderStatus,
+    pub actor_id: Option<Uuid>,
+    pub actor_role: OrderActorRole,
+    pub note: Option<String>,
+    pub dt_created: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct OrderWithItems {
+    #[serde(flatten)]
+    pub order: Order,
+    pub items: Vec<OrderItem>,
+    pub history: Vec<OrderStatusChange>,
+}
+
+/// Linha do pedido montada a partir da linha do carrinho
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewOrderItem {
+    pub product_id: Uuid,
+    pub variant_id: Option<Uuid>,
+    pub attributes_snapshot: serde_json::Value,
+    pub product_name: String,
+    pub unit_price: Money,
+    pub quantity: i32,
+    /// Comissão da plataforma vigente no checkout, em pontos-base
+    pub commission_bps: i32,
+    pub commission_amount: Money,
+}
+
+/// Pedido a gravar no checkout (um por loja vendedora)
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewOrder {
+    pub seller_tenant_id: Uuid,
+    pub subtotal: Money,
+    pub items: Vec<NewOrderItem>,
+}
+
+/// Mudança de status a gravar, com quem a fez
+#[derive(Debug, Clone)]
+pub struct OrderTransition {
+    pub from: OrderStatus,
+    pub to: OrderStatus,
+    pub actor_id: Option<Uuid>,
+    pub actor_role: OrderActorRole,
+    pub note: Option<String>,
+    pub tracking_code: Option<String>,
+    pub carrier: Option<String>,
+}
+
+/// GET /orders/ e GET /orders/sales/ - `?status=paid` filtra
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct OrderListParams {
+    pub status: Option<OrderStatus>,
+}
+
+/// POST /orders/sales/{id}/status/ - `shipped` usa o /ship/ (rastreio obrigatório)
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateOrderStatusRequest {
+    pub status: OrderStatus,
+    pub note: Option<String>,
+}
+
+/// POST /orders/sales/{id}/ship/
+#[derive(Debug, Deserialize, Clone)]
+pub struct ShipOrderRequest {
+    pub tracking_code: String,
+    pub carrier: Option<String>,
+    pub note: Option<String>,
+}
+
+/// POST /orders/{id}/cancel/ - corpo opcional
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct CancelOrderRequest {
+    pub reason: Option<String>,
+}
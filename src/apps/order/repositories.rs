@@ -0,0 +1,588 @@
+use crate::app_core::app_state::AppState;
+use crate::apps::ledger::repositories::LedgerRepository;
+use crate::apps::order::models::{
+    NewOrder, Order, OrderActorRole, OrderItem, OrderStatus, OrderStatusChange, OrderTransition,
+};
+use crate::utils::money::Money;
+use crate::utils::pagination::{CursorPage, Page, PageRequest, PaginatedResponse, push_keyset};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{Postgres, QueryBuilder, Row, Transaction, postgres::PgRow};
+use std::collections::BTreeMap;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// De quem são os pedidos consultados
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderScope {
+    /// Pedidos feitos pelo usuário
+    Buyer { tenant_id: Uuid, user_id: Uuid },
+    /// Vendas da loja
+    Seller { tenant_id: Uuid },
+}
+
+/// Resultado do checkout; nada é gravado fora de `Created`
+#[derive(Debug)]
+pub enum CheckoutOutcome {
+    Created(Vec<Order>),
+    /// Estoque do produto acabou entre a leitura do carrinho e a reserva
+    OutOfStock(Uuid),
+    /// Carrinho deixou de estar ativo (checkout concorrente)
+    CartClosed,
+    /// Linhas do carrinho mudaram depois da leitura em que os pedidos foram planejados
+    CartChanged,
+}
+
+pub struct OrderRepository<'a> {
+    app_state: &'a AppState,
+}
+
+impl<'a> OrderRepository<'a> {
+    pub fn new(app_state: &'a AppState) -> Self {
+        Self { app_state }
+    }
+
+    #[instrument(name = "OrderRepository::find_all", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_all(
+        &self,
+        scope: OrderScope,
+        status: Option<OrderStatus>,
+        page: &PageRequest,
+    ) -> Result<Page<Order>, sqlx::Error> {
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
+            r#"
+            SELECT
+                id,
+                cart_id,
+                buyer_tenant_id,
+                buyer_user_id,
+                seller_tenant_id,
+                status,
+                currency,
+                ROW(subtotal, currency)::money_value AS subtotal,
+                ROW(discount_total, currency)::money_value AS discount_total,
+                ROW(tax_total, currency)::money_value AS tax_total,
+                ROW(shipping_total, currency)::money_value AS shipping_total,
+                ROW(grand_total, currency)::money_value AS grand_total,
+                ROW(refunded_total, currency)::money_value AS refunded_total,
+                tracking_code,
+                carrier,
+                dt_created,
+                dt_updated"#,
+        );
+
+        if let PageRequest::Offset { .. } = page {
+            qb.push(", COUNT(*) OVER() AS total_count");
+        }
+        qb.push(" FROM orders WHERE ");
+        push_scope(&mut qb, scope);
+        if let Some(status) = status {
+            qb.push(" AND status = ").push_bind(status);
+        }
+
+        match page {
+            PageRequest::Offset { limit, offset } => {
+                qb.push(" ORDER BY dt_created DESC, id DESC LIMIT ")
+                    .push_bind(*limit)
+                    .push(" OFFSET ")
+                    .push_bind(*offset);
+            }
+            PageRequest::Cursor { limit, cursor, .. } => {
+                push_keyset(&mut qb, "dt_created", "id", cursor.as_ref(), *limit);
+            }
+        }
+
+        let rows: Vec<PgRow> = qb.build().fetch_all(&self.app_state.db).await?;
+
+        let total = match page {
+            PageRequest::Offset { .. } => rows
+                .first()
+                .map(|r| r.get::<i64, _>("total_count"))
+                .unwrap_or(0),
+            _ => 0,
+        };
+        let orders: Vec<Order> = rows.into_iter().map(order_from_row).collect();
+
+        Ok(match page {
+            PageRequest::Offset { limit, offset } => Page::Offset(PaginatedResponse {
+                count: total,
+                results: orders,
+                limit: *limit,
+                offset: *offset,
+            }),
+            PageRequest::Cursor {
+                limit,
+                cursor,
+                include_count,
+            } => {
+                let count = match include_count {
+                    true => {
+                        let mut qb: QueryBuilder<Postgres> =
+                            QueryBuilder::new("SELECT COUNT(*) FROM orders WHERE ");
+                        push_scope(&mut qb, scope);
+                        if let Some(status) = status {
+                            qb.push(" AND status = ").push_bind(status);
+                        }
+                        Some(
+                            qb.build_query_scalar::<i64>()
+                                .fetch_one(&self.app_state.db)
+                                .await?,
+                        )
+                    }
+                    false => None,
+                };
+                Page::Cursor(CursorPage::from_rows(
+                    orders,
+                    *limit,
+                    cursor.as_ref(),
+                    |o| (o.dt_created, o.id),
+                    count,
+                ))
+            }
+        })
+    }
+
+    #[instrument(name = "OrderRepository::find_by_id", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_id(
+        &self,
+        id: Uuid,
+        scope: OrderScope,
+    ) -> Result<Option<Order>, sqlx::Error> {
+        let (buyer_tenant_id, buyer_user_id, seller_tenant_id) = match scope {
+            OrderScope::Buyer { tenant_id, user_id } => (Some(tenant_id), Some(user_id), None),
+            OrderScope::Seller { tenant_id } => (None, None, Some(tenant_id)),
+        };
+
+        sqlx::query_as!(
+            Order,
+            r#"
+            SELECT
+                id,
+                cart_id,
+                buyer_tenant_id,
+                buyer_user_id,
+                seller_tenant_id,
+                status as "status: OrderStatus",
+                currency,
+                ROW(subtotal, currency)::money_value as "subtotal!: Money",
+                ROW(discount_total, currency)::money_value as "discount_total!: Money",
+                ROW(tax_total, currency)::money_value as "tax_total!: Money",
+                ROW(shipping_total, currency)::money_value as "shipping_total!: Money",
+                ROW(grand_total, currency)::money_value as "grand_total!: Money",
+                ROW(refunded_total, currency)::money_value as "refunded_total!: Money",
+                tracking_code,
+                carrier,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            FROM orders
+            WHERE id = $1
+                AND (
+                    (buyer_tenant_id = $2 AND buyer_user_id = $3)
+                    OR seller_tenant_id = $4
+                )
+            "#,
+            id,
+            buyer_tenant_id,
+            buyer_user_id,
+            seller_tenant_id
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "OrderRepository::list_items", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn list_items(&self, order_id: Uuid) -> Result<Vec<OrderItem>, sqlx::Error> {
+        sqlx::query_as!(
+            OrderItem,
+            r#"
+            SELECT
+                oi.id,
+                oi.order_id,
+                oi.product_id,
+                oi.variant_id,
+                oi.attributes_snapshot,
+                oi.product_name,
+                ROW(oi.unit_price, o.currency)::money_value as "unit_price!: Money",
+                oi.quantity,
+                ROW(oi.line_total, o.currency)::money_value as "line_total!: Money",
+                (oi.dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>"
+            FROM order_items oi
+            JOIN orders o ON o.id = oi.order_id
+            WHERE oi.order_id = $1
+            ORDER BY oi.dt_created, oi.id
+            "#,
+            order_id
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "OrderRepository::list_history", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn list_history(
+        &self,
+        order_id: Uuid,
+    ) -> Result<Vec<OrderStatusChange>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                id,
+                from_status as "from_status: OrderStatus",
+                to_status as "to_status: OrderStatus",
+                actor_id,
+                actor_role,
+                note,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>"
+            FROM order_status_history
+            WHERE order_id = $1
+            ORDER BY dt_created, id
+            "#,
+            order_id
+        )
+        .fetch_all(&self.app_state.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| OrderStatusChange {
+                id: row.id,
+                from_status: row.from_status,
+                to_status: row.to_status,
+                actor_id: row.actor_id,
+                actor_role: OrderActorRole::parse(&row.actor_role),
+                note: row.note,
+                dt_created: row.dt_created,
+            })
+            .collect())
+    }
+
+    /// Fecha o carrinho, reserva o estoque e grava os pedidos numa única transação. Os pedidos
+    /// foram planejados sobre as linhas lidas na versão `cart_version` do carrinho.
+    #[instrument(name = "OrderRepository::create_from_cart", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create_from_cart(
+        &self,
+        cart_id: Uuid,
+        cart_version: i32,
+        buyer_tenant_id: Uuid,
+        buyer_user_id: Uuid,
+        currency: &str,
+        orders: &[NewOrder],
+    ) -> Result<CheckoutOutcome, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+        let now = Utc::now().naive_utc();
+
+        let version = sqlx::query_scalar!(
+            r#"
+            UPDATE carts
+            SET status = 'CONVERTED_TO_ORDER', dt_updated = $1
+            WHERE id = $2 AND status = 'ACTIVE' AND dt_deleted IS NULL
+            RETURNING version
+            "#,
+            now,
+            cart_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        match version {
+            None => return Ok(CheckoutOutcome::CartClosed),
+            Some(version) if version != cart_version => return Ok(CheckoutOutcome::CartChanged),
+            Some(_) => {}
+        }
+
+        // Reserva em ordem de produto: checkouts com os mesmos itens em ordens diferentes
+        // travam as linhas na mesma sequência em vez de entrar em deadlock
+        let mut reservations: BTreeMap<Uuid, i32> = BTreeMap::new();
+        for item in orders.iter().flat_map(|order| &order.items) {
+            let quantity = reservations.entry(item.product_id).or_default();
+            *quantity = quantity.saturating_add(item.quantity);
+        }
+        for (product_id, quantity) in reservations {
+            let reserved = sqlx::query!(
+                r#"
+                UPDATE products
+                SET stock_quantity = stock_quantity - $1, dt_updated = $2
+                WHERE id = $3 AND stock_quantity >= $1 AND is_active AND dt_deleted IS NULL
+                "#,
+                quantity,
+                now,
+                product_id
+            )
+            .execute(&mut *tx)
+            .await?;
+            if reserved.rows_affected() == 0 {
+                return Ok(CheckoutOutcome::OutOfStock(product_id));
+            }
+        }
+
+        let mut created = Vec::with_capacity(orders.len());
+        for new_order in orders {
+            let order = sqlx::query_as!(
+                Order,
+                r#"
+                INSERT INTO orders (
+                    id,
+                    cart_id,
+                    buyer_tenant_id,
+                    buyer_user_id,
+                    seller_tenant_id,
+                    status,
+                    currency,
+                    subtotal,
+                    dt_created,
+                    dt_updated
+                )
+                VALUES ($1, $2, $3, $4, $5, 'PENDING_PAYMENT', $6, $7, $8, $8)
+                RETURNING
+                    id,
+                    cart_id,
+                    buyer_tenant_id,
+                    buyer_user_id,
+                    seller_tenant_id,
+                    status as "status: OrderStatus",
+                    currency,
+                    ROW(subtotal, currency)::money_value as "subtotal!: Money",
+                    ROW(discount_total, currency)::money_value as "discount_total!: Money",
+                    ROW(tax_total, currency)::money_value as "tax_total!: Money",
+                    ROW(shipping_total, currency)::money_value as "shipping_total!: Money",
+                    ROW(grand_total, currency)::money_value as "grand_total!: Money",
+                    ROW(refunded_total, currency)::money_value as "refunded_total!: Money",
+                    tracking_code,
+                    carrier,
+                    (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                    (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+                "#,
+                Uuid::new_v4(),
+                cart_id,
+                buyer_tenant_id,
+                buyer_user_id,
+                new_order.seller_tenant_id,
+                currency,
+                new_order.subtotal.amount_minor,
+                now
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            for item in &new_order.items {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO order_items (
+                        id,
+                        order_id,
+                        product_id,
+                        variant_id,
+                        attributes_snapshot,
+                        product_name,
+                        unit_price,
+                        quantity,
+                        commission_bps,
+                        commission_amount,
+                        dt_created
+                    )
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                    "#,
+                    Uuid::new_v4(),
+                    order.id,
+                    item.product_id,
+                    item.variant_id,
+                    item.attributes_snapshot,
+                    item.product_name,
+                    item.unit_price.amount_minor,
+                    item.quantity,
+                    item.commission_bps,
+                    item.commission_amount.amount_minor,
+                    now
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            Self::insert_history(
+                &mut tx,
+                order.id,
+                None,
+                OrderStatus::PENDING_PAYMENT,
+                Some(buyer_user_id),
+                OrderActorRole::Buyer,
+                None,
+            )
+            .await?;
+            created.push(order);
+        }
+
+        tx.commit().await?;
+        Ok(CheckoutOutcome::Created(created))
+    }
+
+    /// Aplica a transição se o pedido ainda estiver em `from` (senão devolve None, sem gravar).
+    /// Cancelamento devolve ao estoque as quantidades do pedido, na mesma transação.
+    #[instrument(name = "OrderRepository::transition", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn transition(
+        &self,
+        order_id: Uuid,
+        transition: &OrderTransition,
+    ) -> Result<Option<Order>, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+        let now = Utc::now().naive_utc();
+
+        let order = sqlx::query_as!(
+            Order,
+            r#"
+            UPDATE orders
+            SET
+                status = $1,
+                tracking_code = COALESCE($2, tracking_code),
+                carrier = COALESCE($3, carrier),
+                dt_updated = $4
+            WHERE id = $5 AND status = $6
+            RETURNING
+                id,
+                cart_id,
+                buyer_tenant_id,
+                buyer_user_id,
+                seller_tenant_id,
+                status as "status: OrderStatus",
+                currency,
+                ROW(subtotal, currency)::money_value as "subtotal!: Money",
+                ROW(discount_total, currency)::money_value as "discount_total!: Money",
+                ROW(tax_total, currency)::money_value as "tax_total!: Money",
+                ROW(shipping_total, currency)::money_value as "shipping_total!: Money",
+                ROW(grand_total, currency)::money_value as "grand_total!: Money",
+                ROW(refunded_total, currency)::money_value as "refunded_total!: Money",
+                tracking_code,
+                carrier,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            "#,
+            transition.to as _,
+            transition.tracking_code,
+            transition.carrier,
+            now,
+            order_id,
+            transition.from as _
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(order) = order else {
+            return Ok(None);
+        };
+
+        if transition.to == OrderStatus::CANCELLED {
+            sqlx::query!(
+                r#"
+                UPDATE products p
+                SET stock_quantity = p.stock_quantity + reserved.quantity, dt_updated = $2
+                FROM (
+                    SELECT product_id, SUM(quantity)::int AS quantity
+                    FROM order_items
+                    WHERE order_id = $1
+                    GROUP BY product_id
+                ) reserved
+                WHERE p.id = reserved.product_id
+                "#,
+                order_id,
+                now
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // Razão do marketplace: venda quando o pagamento entra, estorno se o dinheiro volta
+        // antes do envio
+        match (transition.from, transition.to) {
+            (_, OrderStatus::PAID) => LedgerRepository::record_sale(&mut tx, &order).await?,
+            (
+                OrderStatus::PAID | OrderStatus::FULFILLING,
+                OrderStatus::CANCELLED | OrderStatus::REFUNDED,
+            ) => LedgerRepository::record_sale_reversal(&mut tx, &order).await?,
+            _ => {}
+        }
+
+        Self::insert_history(
+            &mut tx,
+            order_id,
+            Some(transition.from),
+            transition.to,
+            transition.actor_id,
+            transition.actor_role,
+            transition.note.as_deref(),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(order))
+    }
+
+    /// Grava no histórico dentro da transação de quem mudou o status
+    #[instrument(name = "OrderRepository::insert_history", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn insert_history(
+        tx: &mut Transaction<'_, Postgres>,
+        order_id: Uuid,
+        from: Option<OrderStatus>,
+        to: OrderStatus,
+        actor_id: Option<Uuid>,
+        actor_role: OrderActorRole,
+        note: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO order_status_history (
+                id, order_id, from_status, to_status, actor_id, actor_role, note, dt_created
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+            Uuid::new_v4(),
+            order_id,
+            from as _,
+            to as _,
+            actor_id,
+            actor_role.as_str(),
+            note,
+            Utc::now().naive_utc()
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
+
+fn push_scope(qb: &mut QueryBuilder<'_, Postgres>, scope: OrderScope) {
+    match scope {
+        OrderScope::Buyer { tenant_id, user_id } => {
+            qb.push("buyer_tenant_id = ")
+                .push_bind(tenant_id)
+                .push(" AND buyer_user_id = ")
+                .push_bind(user_id);
+        }
+        OrderScope::Seller { tenant_id } => {
+            qb.push("seller_tenant_id = ").push_bind(tenant_id);
+        }
+    }
+}
+
+fn order_from_row(row: PgRow) -> Order {
+    Order {
+        id: row.get("id"),
+        cart_id: row.get("cart_id"),
+        buyer_tenant_id: row.get("buyer_tenant_id"),
+        buyer_user_id: row.get("buyer_user_id"),
+        seller_tenant_id: row.get("seller_tenant_id"),
+        status: row.get("status"),
+        currency: row.get("currency"),
+        subtotal: row.get("subtotal"),
+        discount_total: row.get("discount_total"),
+        tax_total: row.get("tax_total"),
+        shipping_total: row.get("shipping_total"),
+        grand_total: row.get("grand_total"),
+        refunded_total: row.get("refunded_total"),
+        tracking_code: row.get("tracking_code"),
+        carrier: row.get("carrier"),
+        dt_created: DateTime::from_naive_utc_and_offset(
+            row.get::<NaiveDateTime, _>("dt_created"),
+            Utc,
+        ),
+        dt_updated: DateTime::from_naive_utc_and_offset(
+            row.get::<NaiveDateTime, _>("dt_updated"),
+            Utc,
+        ),
+    }
+}
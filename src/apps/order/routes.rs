@@ -0,0 +1,154 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_extensions::RequestUserExt;
+use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::AuditContext;
+use crate::apps::order::models::{
+    CancelOrderRequest, OrderListParams, ShipOrderRequest, UpdateOrderStatusRequest,
+};
+use crate::apps::order::services::OrderService;
+use crate::utils::pagination::PaginationParams;
+use actix_web::web::Json;
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+use uuid::Uuid;
+
+/// POST /orders/checkout/ - um pedido por loja vendedora do carrinho ativo
+pub async fn checkout(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let audit = AuditContext::from_request(&req);
+
+    let result = OrderService::checkout(&app_state, tenant_id, user_id, &audit).await?;
+
+    Ok(HttpResponse::Created().json(serde_json::json!(result)))
+}
+
+/// GET /orders/ - pedidos do usuário; `?status=` filtra
+pub async fn list_orders(
+    app_state: web::Data<AppState>,
+    query: web::Query<PaginationParams>,
+    filter: web::Query<OrderListParams>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let page = query.page_request()?;
+
+    let result =
+        OrderService::list_orders(&app_state, tenant_id, user_id, filter.into_inner(), &page)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn get_order(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let id = path.into_inner();
+
+    let result = OrderService::get_order(&app_state, tenant_id, user_id, id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /orders/{id}/cancel/ - corpo opcional `{"reason": "..."}`
+pub async fn cancel_order(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Option<Json<CancelOrderRequest>>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let id = path.into_inner();
+    let dto = payload.map(Json::into_inner).unwrap_or_default();
+
+    let result = OrderService::cancel_order(&app_state, tenant_id, user_id, id, dto).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// GET /orders/sales/ - vendas da loja (dono ou super_admin)
+pub async fn list_sales(
+    app_state: web::Data<AppState>,
+    query: web::Query<PaginationParams>,
+    filter: web::Query<OrderListParams>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let access_level = req.access_level()?;
+    let page = query.page_request()?;
+
+    let result = OrderService::list_sales(
+        &app_state,
+        tenant_id,
+        user_id,
+        &access_level,
+        filter.into_inner(),
+        &page,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn get_sale(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let access_level = req.access_level()?;
+    let id = path.into_inner();
+
+    let result = OrderService::get_sale(&app_state, tenant_id, user_id, &access_level, id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /orders/sales/{id}/status/ - `{"status": "paid", "note": "..."}`
+pub async fn update_sale_status(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Json<UpdateOrderStatusRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let access_level = req.access_level()?;
+    let id = path.into_inner();
+    let dto = payload.into_inner();
+
+    let result =
+        OrderService::update_sale_status(&app_state, tenant_id, user_id, &access_level, id, dto)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /orders/sales/{id}/ship/ - `{"tracking_code": "BR123", "carrier": "Correios"}`
+pub async fn ship_sale(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Json<ShipOrderRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let access_level = req.access_level()?;
+    let id = path.into_inner();
+    let dto = payload.into_inner();
+
+    let result =
+        OrderService::ship_sale(&app_state, tenant_id, user_id, &access_level, id, dto).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
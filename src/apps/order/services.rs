@@ -0,0 +1,397 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::app_core::telemetry::record_order_operation;
+use crate::apps::audit::models::{AuditAction, AuditContext};
+use crate::apps::cart::models::{CartAuditSnapshot, CartItemWithProduct, CartOwner, CartStatus};
+use crate::apps::cart::services::{CartService, cart_changed};
+use crate::apps::commission::services::{CommissionService, commission_amount};
+use crate::apps::order::models::{
+    CancelOrderRequest, NewOrder, NewOrderItem, Order, OrderActorRole, OrderListParams,
+    OrderStatus, OrderTransition, OrderWithItems, ShipOrderRequest, UpdateOrderStatusRequest,
+};
+use crate::apps::order::repositories::{CheckoutOutcome, OrderRepository, OrderScope};
+use crate::apps::product::repositories::ProductRepository;
+use crate::apps::tenant::services::TenantService;
+use crate::utils::money::{Money, MoneyError};
+use crate::utils::pagination::{Page, PageRequest};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Tamanho máximo do código de rastreio e da transportadora
+const MAX_TRACKING_LENGTH: usize = 100;
+
+pub struct OrderService;
+
+impl OrderService {
+    /// POST /orders/checkout/ - fecha o carrinho ativo e gera um pedido por loja vendedora,
+    /// reservando o estoque. Carrinho com avisos de revalidação é recusado.
+    pub async fn checkout(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<Vec<Order>, AppError> {
+        let cart = CartService::get_cart(app_state, CartOwner::User { tenant_id, user_id }).await?;
+        if cart.items.is_empty() {
+            return Err(AppError::bad_request("Carrinho vazio"));
+        }
+        if !cart.warnings.is_empty() {
+            return Err(AppError::Conflict(Some(
+                "Carrinho com divergências; revalide antes de finalizar o pedido".into(),
+            )));
+        }
+
+        let product_ids: Vec<Uuid> = cart.items.iter().map(|item| item.product_id).collect();
+        let sellers: HashMap<Uuid, Uuid> = ProductRepository::new(app_state)
+            .find_by_ids_any_status(&product_ids)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .into_iter()
+            .map(|product| (product.id, product.tenant_id))
+            .collect();
+        let commissions = CommissionService::resolve_rates(app_state, &product_ids).await?;
+        let orders = plan_orders(&cart.items, &sellers, &commissions, &cart.currency)?;
+
+        let outcome = OrderRepository::new(app_state)
+            .create_from_cart(
+                cart.id,
+                cart.version,
+                tenant_id,
+                user_id,
+                &cart.currency,
+                &orders,
+            )
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        match outcome {
+            CheckoutOutcome::Created(orders) => {
+                let before = CartAuditSnapshot::from_cart_with_items(&cart);
+                let after = CartAuditSnapshot {
+                    status: CartStatus::CONVERTED_TO_ORDER,
+                    order_ids: orders.iter().map(|order| order.id).collect(),
+                    ..before.clone()
+                };
+                CartService::record_audit(
+                    app_state,
+                    audit,
+                    AuditAction::Checkout,
+                    cart.id,
+                    Some(&before),
+                    Some(&after),
+                )
+                .await;
+
+                record_order_operation("checkout");
+                Ok(orders)
+            }
+            CheckoutOutcome::OutOfStock(product_id) => Err(AppError::bad_request(format!(
+                "Estoque insuficiente para o produto {}",
+                product_id
+            ))),
+            CheckoutOutcome::CartClosed => Err(AppError::Conflict(Some(
+                "Carrinho já foi finalizado".into(),
+            ))),
+            CheckoutOutcome::CartChanged => Err(cart_changed()),
+        }
+    }
+
+    /// GET /orders/ - pedidos do usuário
+    pub async fn list_orders(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        params: OrderListParams,
+        page: &PageRequest,
+    ) -> Result<Page<Order>, AppError> {
+        OrderRepository::new(app_state)
+            .find_all(
+                OrderScope::Buyer { tenant_id, user_id },
+                params.status,
+                page,
+            )
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    /// GET /orders/sales/ - vendas da loja; só para quem administra o tenant
+    pub async fn list_sales(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+        params: OrderListParams,
+        page: &PageRequest,
+    ) -> Result<Page<Order>, AppError> {
+        Self::ensure_seller(app_state, tenant_id, user_id, access_level).await?;
+
+        OrderRepository::new(app_state)
+            .find_all(OrderScope::Seller { tenant_id }, params.status, page)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    pub async fn get_order(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<OrderWithItems, AppError> {
+        let order =
+            Self::find_order(app_state, id, OrderScope::Buyer { tenant_id, user_id }).await?;
+        Self::with_items(app_state, order).await
+    }
+
+    pub async fn get_sale(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+        id: Uuid,
+    ) -> Result<OrderWithItems, AppError> {
+        Self::ensure_seller(app_state, tenant_id, user_id, access_level).await?;
+
+        let order = Self::find_order(app_state, id, OrderScope::Seller { tenant_id }).await?;
+        Self::with_items(app_state, order).await
+    }
+
+    /// POST /orders/{id}/cancel/ - só antes do envio; o estoque volta para os produtos
+    pub async fn cancel_order(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        id: Uuid,
+        request: CancelOrderRequest,
+    ) -> Result<OrderWithItems, AppError> {
+        let order =
+            Self::find_order(app_state, id, OrderScope::Buyer { tenant_id, user_id }).await?;
+        if !order.status.is_before_shipment() {
+            return Err(AppError::Conflict(Some(format!(
+                "Pedido {} não pode mais ser cancelado",
+                order.status.as_str()
+            ))));
+        }
+
+        let transition = OrderTransition {
+            from: order.status,
+            to: OrderStatus::CANCELLED,
+            actor_id: Some(user_id),
+            actor_role: OrderActorRole::Buyer,
+            note: request.reason,
+            tracking_code: None,
+            carrier: None,
+        };
+        let order = Self::apply_transition(app_state, order, transition).await?;
+
+        record_order_operation("cancel");
+        Self::with_items(app_state, order).await
+    }
+
+    /// POST /orders/sales/{id}/status/ - pagamento confirmado, separação, entrega ou
+    /// cancelamento pela loja. Envio e reembolso têm fluxo próprio.
+    pub async fn update_sale_status(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+        id: Uuid,
+        request: UpdateOrderStatusRequest,
+    ) -> Result<OrderWithItems, AppError> {
+        match request.status {
+            OrderStatus::SHIPPED => {
+                return Err(AppError::bad_request(
+                    "Use /ship/ para informar o código de rastreio",
+                ));
+            }
+            OrderStatus::REFUNDED | OrderStatus::PENDING_PAYMENT => {
+                return Err(AppError::bad_request(format!(
+                    "Status {} não pode ser definido manualmente",
+                    request.status.as_str()
+                )));
+            }
+            _ => {}
+        }
+        Self::ensure_seller(app_state, tenant_id, user_id, access_level).await?;
+
+        let order = Self::find_order(app_state, id, OrderScope::Seller { tenant_id }).await?;
+        let transition = OrderTransition {
+            from: order.status,
+            to: request.status,
+            actor_id: Some(user_id),
+            actor_role: OrderActorRole::Seller,
+            note: request.note,
+            tracking_code: None,
+            carrier: None,
+        };
+        let order = Self::apply_transition(app_state, order, transition).await?;
+
+        record_order_operation(request.status.as_str());
+        Self::with_items(app_state, order).await
+    }
+
+    /// POST /orders/sales/{id}/ship/ - envio com código de rastreio
+    pub async fn ship_sale(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+        id: Uuid,
+        request: ShipOrderRequest,
+    ) -> Result<OrderWithItems, AppError> {
+        let tracking_code = validate_tracking(Some(&request.tracking_code), "Código de rastreio")?
+            .ok_or_else(|| AppError::bad_request("Código de rastreio é obrigatório"))?;
+        let carrier = validate_tracking(request.carrier.as_deref(), "Transportadora")?;
+        Self::ensure_seller(app_state, tenant_id, user_id, access_level).await?;
+
+        let order = Self::find_order(app_state, id, OrderScope::Seller { tenant_id }).await?;
+        let transition = OrderTransition {
+            from: order.status,
+            to: OrderStatus::SHIPPED,
+            actor_id: Some(user_id),
+            actor_role: OrderActorRole::Seller,
+            note: request.note,
+            tracking_code: Some(tracking_code),
+            carrier,
+        };
+        let order = Self::apply_transition(app_state, order, transition).await?;
+
+        record_order_operation("shipped");
+        Self::with_items(app_state, order).await
+    }
+
+    /// Confere a transição e grava; pedido alterado no meio do caminho vira 409
+    async fn apply_transition(
+        app_state: &AppState,
+        order: Order,
+        transition: OrderTransition,
+    ) -> Result<Order, AppError> {
+        ensure_transition(order.status, transition.to)?;
+
+        OrderRepository::new(app_state)
+            .transition(order.id, &transition)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::Conflict(Some("Pedido foi alterado; tente novamente".into())))
+    }
+
+    async fn find_order(
+        app_state: &AppState,
+        id: Uuid,
+        scope: OrderScope,
+    ) -> Result<Order, AppError> {
+        OrderRepository::new(app_state)
+            .find_by_id(id, scope)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Pedido não encontrado"))
+    }
+
+    async fn with_items(app_state: &AppState, order: Order) -> Result<OrderWithItems, AppError> {
+        let repository = OrderRepository::new(app_state);
+        let items = repository
+            .list_items(order.id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        let history = repository
+            .list_history(order.id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        Ok(OrderWithItems {
+            order,
+            items,
+            history,
+        })
+    }
+
+    async fn ensure_seller(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+    ) -> Result<(), AppError> {
+        TenantService::ensure_admin(
+            app_state,
+            tenant_id,
+            user_id,
+            access_level,
+            "Acesso negado. Apenas o dono da loja pode gerenciar as vendas.",
+        )
+        .await
+    }
+}
+
+/// 409 quando o ciclo de vida não permite ir de `from` para `to`
+pub fn ensure_transition(from: OrderStatus, to: OrderStatus) -> Result<(), AppError> {
+    match from.can_transition_to(to) {
+        true => Ok(()),
+        false => Err(AppError::Conflict(Some(format!(
+            "Transição de pedido inválida: {} -> {}",
+            from.as_str(),
+            to.as_str()
+        )))),
+    }
+}
+
+/// Agrupa as linhas do carrinho por loja vendedora (`sellers`: produto -> tenant), fixa a
+/// comissão de cada linha (`commissions`: produto -> pontos-base) e calcula o subtotal de cada
+/// pedido na moeda do carrinho
+pub fn plan_orders(
+    items: &[CartItemWithProduct],
+    sellers: &HashMap<Uuid, Uuid>,
+    commissions: &HashMap<Uuid, i32>,
+    currency: &str,
+) -> Result<Vec<NewOrder>, AppError> {
+    let mut grouped: BTreeMap<Uuid, Vec<NewOrderItem>> = BTreeMap::new();
+    for item in items {
+        let seller_tenant_id = sellers
+            .get(&item.product_id)
+            .ok_or_else(|| AppError::not_found("Produto não encontrado"))?;
+        let commission_bps = commissions.get(&item.product_id).copied().unwrap_or(0);
+        let line_total = item.unit_price.checked_mul(item.quantity as i64)?;
+        grouped
+            .entry(*seller_tenant_id)
+            .or_default()
+            .push(NewOrderItem {
+                product_id: item.product_id,
+                variant_id: item.variant_id.filter(|id| !id.is_nil()),
+                attributes_snapshot: item.attributes_snapshot.clone(),
+                product_name: item.product_name.clone(),
+                unit_price: item.unit_price.clone(),
+                quantity: item.quantity,
+                commission_bps,
+                commission_amount: Money::new(
+                    commission_amount(line_total.amount_minor, commission_bps),
+                    currency,
+                ),
+            });
+    }
+
+    grouped
+        .into_iter()
+        .map(|(seller_tenant_id, items)| {
+            let line_totals = items
+                .iter()
+                .map(|item| item.unit_price.checked_mul(item.quantity as i64))
+                .collect::<Result<Vec<_>, MoneyError>>()?;
+            Ok(NewOrder {
+                seller_tenant_id,
+                subtotal: Money::checked_sum(&line_totals, currency)?,
+                items,
+            })
+        })
+        .collect()
+}
+
+fn validate_tracking(value: Option<&str>, field: &str) -> Result<Option<String>, AppError> {
+    let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+        return Ok(None);
+    };
+    if value.chars().count() > MAX_TRACKING_LENGTH {
+        return Err(AppError::bad_request(format!(
+            "{} deve ter no máximo {} caracteres",
+            field, MAX_TRACKING_LENGTH
+        )));
+    }
+    Ok(Some(value.to_string()))
+}
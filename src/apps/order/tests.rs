@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod tests {
+    use crate::apps::cart::models::CartItemWithProduct;
+    use crate::apps::order::models::OrderStatus;
+    use crate::apps::order::services::{ensure_transition, plan_orders};
+    use crate::utils::money::Money;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    const ALL: [OrderStatus; 7] = [
+        OrderStatus::PENDING_PAYMENT,
+        OrderStatus::PAID,
+        OrderStatus::FULFILLING,
+        OrderStatus::SHIPPED,
+        OrderStatus::DELIVERED,
+        OrderStatus::CANCELLED,
+        OrderStatus::REFUNDED,
+    ];
+
+    fn item(product_id: Uuid, unit_price: i64, quantity: i32) -> CartItemWithProduct {
+        CartItemWithProduct {
+            id: Uuid::new_v4(),
+            cart_id: Uuid::nil(),
+            product_id,
+            variant_id: Some(Uuid::nil()),
+            unit_price: Money::new(unit_price, "BRL"),
+            quantity,
+            line_discount_total: Money::zero("BRL"),
+            line_tax_total: Money::zero("BRL"),
+            line_total: Money::new(unit_price * quantity as i64, "BRL"),
+            attributes_snapshot: serde_json::json!({}),
+            attributes_hash: String::new(),
+            dt_created: Utc::now(),
+            dt_updated: Utc::now(),
+            dt_deleted: None,
+            product_name: "Produto".to_string(),
+            product_slug: "produto".to_string(),
+            product_short_description: None,
+            product_description: None,
+            product_price: Money::zero("BRL"),
+            product_stock_quantity: 10,
+            product_is_active: true,
+        }
+    }
+
+    #[test]
+    fn test_order_happy_path_transitions() {
+        let path = [
+            OrderStatus::PENDING_PAYMENT,
+            OrderStatus::PAID,
+            OrderStatus::FULFILLING,
+            OrderStatus::SHIPPED,
+            OrderStatus::DELIVERED,
+            OrderStatus::REFUNDED,
+        ];
+        for pair in path.windows(2) {
+            assert!(pair[0].can_transition_to(pair[1]), "{:?}", pair);
+        }
+        assert!(OrderStatus::PAID.can_transition_to(OrderStatus::SHIPPED));
+    }
+
+    #[test]
+    fn test_order_cancel_only_before_shipment() {
+        for status in ALL {
+            assert_eq!(
+                status.can_transition_to(OrderStatus::CANCELLED),
+                status.is_before_shipment(),
+                "{:?}",
+                status
+            );
+        }
+    }
+
+    #[test]
+    fn test_order_terminal_and_backward_transitions_rejected() {
+        for next in ALL {
+            assert!(!OrderStatus::CANCELLED.can_transition_to(next));
+            assert!(!OrderStatus::REFUNDED.can_transition_to(next));
+            assert!(!next.can_transition_to(next));
+        }
+        assert!(!OrderStatus::SHIPPED.can_transition_to(OrderStatus::PAID));
+        assert!(!OrderStatus::PENDING_PAYMENT.can_transition_to(OrderStatus::SHIPPED));
+        assert!(!OrderStatus::PENDING_PAYMENT.can_transition_to(OrderStatus::REFUNDED));
+
+        let err = ensure_transition(OrderStatus::PAID, OrderStatus::DELIVERED).unwrap_err();
+        assert!(
+            err.message().contains("paid -> delivered"),
+            "{}",
+            err.message()
+        );
+    }
+
+    #[test]
+    fn test_order_status_serializes_lowercase() {
+        assert_eq!(
+            serde_json::to_value(OrderStatus::PENDING_PAYMENT).unwrap(),
+            "pending_payment"
+        );
+        let status: OrderStatus = serde_json::from_value(serde_json::json!("shipped")).unwrap();
+        assert_eq!(status, OrderStatus::SHIPPED);
+        for status in ALL {
+            assert_eq!(serde_json::to_value(status).unwrap(), status.as_str());
+        }
+    }
+
+    #[test]
+    fn test_plan_orders_splits_by_seller() {
+        let (seller_a, seller_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let (pen, book, mug) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let sellers = HashMap::from([(pen, seller_a), (book, seller_b), (mug, seller_a)]);
+        let commissions = HashMap::from([(pen, 1000), (book, 1250)]);
+
+        let orders = plan_orders(
+            &[item(pen, 250, 2), item(book, 4000, 1), item(mug, 1500, 3)],
+            &sellers,
+            &commissions,
+            "BRL",
+        )
+        .unwrap();
+
+        assert_eq!(orders.len(), 2);
+        let order_a = orders
+            .iter()
+            .find(|o| o.seller_tenant_id == seller_a)
+            .unwrap();
+        assert_eq!(order_a.subtotal, Money::new(5000, "BRL"));
+        assert_eq!(order_a.items.len(), 2);
+        // Variante vazia do carrinho não vira variante no pedido
+        assert!(order_a.items.iter().all(|i| i.variant_id.is_none()));
+
+        let order_b = orders
+            .iter()
+            .find(|o| o.seller_tenant_id == seller_b)
+            .unwrap();
+        assert_eq!(order_b.subtotal, Money::new(4000, "BRL"));
+        assert_eq!(order_b.items[0].commission_bps, 1250);
+        assert_eq!(order_b.items[0].commission_amount, Money::new(500, "BRL"));
+
+        // Produto sem taxa resolvida fica sem comissão
+        let mug_line = order_a.items.iter().find(|i| i.product_id == mug).unwrap();
+        assert_eq!(mug_line.commission_amount, Money::new(0, "BRL"));
+    }
+
+    #[test]
+    fn test_plan_orders_unknown_product() {
+        let err = plan_orders(
+            &[item(Uuid::new_v4(), 100, 1)],
+            &HashMap::new(),
+            &HashMap::new(),
+            "BRL",
+        );
+        assert!(err.is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::apps::order::models::Order;
+use crate::apps::payment::models::PaymentRefund;
+use crate::utils::money::Money;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+use uuid::Uuid;
+
+/// Estado da devolução; na API sai em minúsculas (`requested`, `approved`, ...)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "return_status", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "lowercase")]
+#[allow(clippy::upper_case_acronyms)]
+pub enum ReturnStatus {
+    REQUESTED,
+    APPROVED,
+    REJECTED,
+    RECEIVED,
+}
+
+impl ReturnStatus {
+    /// Solicitada -> aprovada (reembolso) ou recusada; aprovada -> recebida pela loja
+    pub fn can_transition_to(self, next: ReturnStatus) -> bool {
+        use ReturnStatus::*;
+
+        matches!(
+            (self, next),
+            (REQUESTED, APPROVED) | (REQUESTED, REJECTED) | (APPROVED, RECEIVED)
+        )
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReturnStatus::REQUESTED => "requested",
+            ReturnStatus::APPROVED => "approved",
+            ReturnStatus::REJECTED => "rejected",
+            ReturnStatus::RECEIVED => "received",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "return_reason", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "lowercase")]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub enum ReturnReason {
+    DAMAGED,
+    WRONG_ITEM,
+    NOT_AS_DESCRIBED,
+    NO_LONGER_NEEDED,
+    OTHER,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReturnRequest {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    pub order_item_id: Uuid,
+    pub buyer_user_id: Uuid,
+    pub quantity: i32,
+    pub reason: ReturnReason,
+    pub description: Option<String>,
+    pub status: ReturnStatus,
+    pub decision_note: Option<String>,
+    pub decided_by: Option<Uuid>,
+    pub dt_decided: Option<DateTime<Utc>>,
+    pub received_by: Option<Uuid>,
+    pub dt_received: Option<DateTime<Utc>>,
+    pub dt_created: DateTime<Utc>,
+    pub dt_updated: DateTime<Utc>,
+}
+
+/// Nota de crédito da devolução aprovada, abatida do total do pedido
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreditNote {
+    pub id: Uuid,
+    /// Numeração sequencial da plataforma
+    pub number: i64,
+    pub order_id: Uuid,
+    pub return_request_id: Uuid,
+    pub refund_id: Uuid,
+    pub amount: Money,
+    pub dt_created: DateTime<Utc>,
+}
+
+/// Resposta da aprovação: devolução, reembolso, nota de crédito e pedido com o novo total
+#[derive(Debug, Serialize, Clone)]
+pub struct ApprovedReturn {
+    #[serde(rename = "return")]
+    pub return_request: ReturnRequest,
+    pub refund: PaymentRefund,
+    pub credit_note: CreditNote,
+    pub order: Order,
+}
+
+/// POST /orders/{id}/returns/
+#[derive(Debug, Deserialize, Clone)]
+pub struct CreateReturnRequest {
+    pub order_item_id: Uuid,
+    pub quantity: i32,
+    pub reason: ReturnReason,
+    pub description: Option<String>,
+}
+
+/// POST /orders/sales/returns/{id}/approve|reject|receive/ - recusa exige `note`
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ReturnDecisionRequest {
+    pub note: Option<String>,
+}
+
+/// GET /orders/sales/returns/ - `?status=requested` filtra
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ReturnListParams {
+    pub status: Option<ReturnStatus>,
+}
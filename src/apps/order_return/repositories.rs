@@ -0,0 +1,645 @@
+use crate::app_core::app_state::AppState;
+use crate::apps::ledger::repositories::LedgerRepository;
+use crate::apps::order::models::{Order, OrderActorRole, OrderStatus};
+use crate::apps::order::repositories::OrderRepository;
+use crate::apps::order_return::models::{
+    ApprovedReturn, CreateReturnRequest, CreditNote, ReturnReason, ReturnRequest, ReturnStatus,
+};
+use crate::apps::payment::models::{PaymentRefund, PendingRefund};
+use crate::apps::payment::repositories::PaymentRepository;
+use crate::utils::money::Money;
+use crate::utils::pagination::{CursorPage, Page, PageRequest, PaginatedResponse, push_keyset};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{PgExecutor, Postgres, QueryBuilder, Row, postgres::PgRow};
+use tracing::instrument;
+use uuid::Uuid;
+
+/// Resultado da solicitação; nada é gravado fora de `Created`
+#[derive(Debug)]
+pub enum CreateReturnOutcome {
+    Created(ReturnRequest),
+    /// Linha não pertence ao pedido
+    ItemNotFound,
+    /// Quantidade acima do que ainda pode ser devolvido na linha
+    ExceedsQuantity {
+        available: i32,
+    },
+}
+
+/// Reembolso já confirmado pelo provedor, a registrar junto com a nota de crédito
+pub struct RefundRecord {
+    pub product_id: Uuid,
+    pub refund: PaymentRefund,
+    pub actor_id: Uuid,
+}
+
+pub struct ReturnRepository<'a> {
+    app_state: &'a AppState,
+}
+
+impl<'a> ReturnRepository<'a> {
+    pub fn new(app_state: &'a AppState) -> Self {
+        Self { app_state }
+    }
+
+    /// Devoluções de um pedido, da mais antiga para a mais nova
+    #[instrument(name = "ReturnRepository::find_by_order", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_order(&self, order_id: Uuid) -> Result<Vec<ReturnRequest>, sqlx::Error> {
+        sqlx::query_as!(
+            ReturnRequest,
+            r#"
+            SELECT
+                id,
+                order_id,
+                order_item_id,
+                buyer_user_id,
+                quantity,
+                reason as "reason: ReturnReason",
+                description,
+                status as "status: ReturnStatus",
+                decision_note,
+                decided_by,
+                (dt_decided AT TIME ZONE 'UTC') as "dt_decided: DateTime<Utc>",
+                received_by,
+                (dt_received AT TIME ZONE 'UTC') as "dt_received: DateTime<Utc>",
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            FROM return_requests
+            WHERE order_id = $1
+            ORDER BY dt_created, id
+            "#,
+            order_id
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    /// Devolução de um pedido vendido pela loja
+    #[instrument(name = "ReturnRepository::find_by_id", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_id(
+        &self,
+        id: Uuid,
+        seller_tenant_id: Uuid,
+    ) -> Result<Option<ReturnRequest>, sqlx::Error> {
+        sqlx::query_as!(
+            ReturnRequest,
+            r#"
+            SELECT
+                r.id,
+                r.order_id,
+                r.order_item_id,
+                r.buyer_user_id,
+                r.quantity,
+                r.reason as "reason: ReturnReason",
+                r.description,
+                r.status as "status: ReturnStatus",
+                r.decision_note,
+                r.decided_by,
+                (r.dt_decided AT TIME ZONE 'UTC') as "dt_decided: DateTime<Utc>",
+                r.received_by,
+                (r.dt_received AT TIME ZONE 'UTC') as "dt_received: DateTime<Utc>",
+                (r.dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (r.dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            FROM return_requests r
+            JOIN orders o ON o.id = r.order_id
+            WHERE r.id = $1 AND o.seller_tenant_id = $2
+            "#,
+            id,
+            seller_tenant_id
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    /// Fila de devoluções da loja, mais novas primeiro
+    #[instrument(name = "ReturnRepository::find_all_for_seller", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_all_for_seller(
+        &self,
+        seller_tenant_id: Uuid,
+        status: Option<ReturnStatus>,
+        page: &PageRequest,
+    ) -> Result<Page<ReturnRequest>, sqlx::Error> {
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
+            r#"
+            SELECT
+                r.id,
+                r.order_id,
+                r.order_item_id,
+                r.buyer_user_id,
+                r.quantity,
+                r.reason,
+                r.description,
+                r.status,
+                r.decision_note,
+                r.decided_by,
+                r.dt_decided,
+                r.received_by,
+                r.dt_received,
+                r.dt_created,
+                r.dt_updated"#,
+        );
+
+        if let PageRequest::Offset { .. } = page {
+            qb.push(", COUNT(*) OVER() AS total_count");
+        }
+        qb.push(" FROM return_requests r JOIN orders o ON o.id = r.order_id WHERE ");
+        push_filters(&mut qb, seller_tenant_id, status);
+
+        match page {
+            PageRequest::Offset { limit, offset } => {
+                qb.push(" ORDER BY r.dt_created DESC, r.id DESC LIMIT ")
+                    .push_bind(*limit)
+                    .push(" OFFSET ")
+                    .push_bind(*offset);
+            }
+            PageRequest::Cursor { limit, cursor, .. } => {
+                push_keyset(&mut qb, "r.dt_created", "r.id", cursor.as_ref(), *limit);
+            }
+        }
+
+        let rows: Vec<PgRow> = qb.build().fetch_all(&self.app_state.db).await?;
+
+        let total = match page {
+            PageRequest::Offset { .. } => rows
+                .first()
+                .map(|r| r.get::<i64, _>("total_count"))
+                .unwrap_or(0),
+            _ => 0,
+        };
+        let returns: Vec<ReturnRequest> = rows.into_iter().map(return_from_row).collect();
+
+        Ok(match page {
+            PageRequest::Offset { limit, offset } => Page::Offset(PaginatedResponse {
+                count: total,
+                results: returns,
+                limit: *limit,
+                offset: *offset,
+            }),
+            PageRequest::Cursor {
+                limit,
+                cursor,
+                include_count,
+            } => {
+                let count = match include_count {
+                    true => {
+                        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
+                            "SELECT COUNT(*) FROM return_requests r \
+                             JOIN orders o ON o.id = r.order_id WHERE ",
+                        );
+                        push_filters(&mut qb, seller_tenant_id, status);
+                        Some(
+                            qb.build_query_scalar::<i64>()
+                                .fetch_one(&self.app_state.db)
+                                .await?,
+                        )
+                    }
+                    false => None,
+                };
+                Page::Cursor(CursorPage::from_rows(
+                    returns,
+                    *limit,
+                    cursor.as_ref(),
+                    |r| (r.dt_created, r.id),
+                    count,
+                ))
+            }
+        })
+    }
+
+    #[instrument(name = "ReturnRepository::list_credit_notes", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn list_credit_notes(&self, order_id: Uuid) -> Result<Vec<CreditNote>, sqlx::Error> {
+        sqlx::query_as!(
+            CreditNote,
+            r#"
+            SELECT
+                id,
+                number,
+                order_id,
+                return_request_id,
+                refund_id,
+                ROW(amount, currency)::money_value as "amount!: Money",
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>"
+            FROM credit_notes
+            WHERE order_id = $1
+            ORDER BY number
+            "#,
+            order_id
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    /// Grava a solicitação com a linha do pedido travada, para que duas solicitações
+    /// simultâneas não passem juntas da quantidade comprada
+    #[instrument(name = "ReturnRepository::create", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create(
+        &self,
+        order_id: Uuid,
+        buyer_user_id: Uuid,
+        request: &CreateReturnRequest,
+    ) -> Result<CreateReturnOutcome, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+
+        let purchased = sqlx::query_scalar!(
+            r#"
+            SELECT quantity FROM order_items
+            WHERE id = $1 AND order_id = $2
+            FOR UPDATE
+            "#,
+            request.order_item_id,
+            order_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(purchased) = purchased else {
+            return Ok(CreateReturnOutcome::ItemNotFound);
+        };
+
+        let reserved = sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(SUM(quantity), 0)::int as "reserved!"
+            FROM return_requests
+            WHERE order_item_id = $1 AND status <> 'REJECTED'
+            "#,
+            request.order_item_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let available = purchased - reserved;
+        if request.quantity > available {
+            return Ok(CreateReturnOutcome::ExceedsQuantity {
+                available: available.max(0),
+            });
+        }
+
+        let now = Utc::now().naive_utc();
+        let created = sqlx::query_as!(
+            ReturnRequest,
+            r#"
+            INSERT INTO return_requests (
+                id,
+                order_id,
+                order_item_id,
+                buyer_user_id,
+                quantity,
+                reason,
+                description,
+                status,
+                dt_created,
+                dt_updated
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, 'REQUESTED', $8, $8)
+            RETURNING
+                id,
+                order_id,
+                order_item_id,
+                buyer_user_id,
+                quantity,
+                reason as "reason: ReturnReason",
+                description,
+                status as "status: ReturnStatus",
+                decision_note,
+                decided_by,
+                (dt_decided AT TIME ZONE 'UTC') as "dt_decided: DateTime<Utc>",
+                received_by,
+                (dt_received AT TIME ZONE 'UTC') as "dt_received: DateTime<Utc>",
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            "#,
+            Uuid::new_v4(),
+            order_id,
+            request.order_item_id,
+            buyer_user_id,
+            request.quantity,
+            request.reason as _,
+            request.description,
+            now
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(CreateReturnOutcome::Created(created))
+    }
+
+    /// Grava a decisão se a devolução ainda estiver solicitada (senão devolve None); a
+    /// aprovação passa por `approve`, que grava também o reembolso
+    #[instrument(name = "ReturnRepository::decide", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn decide(
+        &self,
+        id: Uuid,
+        status: ReturnStatus,
+        actor_id: Uuid,
+        note: Option<&str>,
+    ) -> Result<Option<ReturnRequest>, sqlx::Error> {
+        Self::update_decision(&self.app_state.db, id, status, actor_id, note).await
+    }
+
+    /// Aprova se a devolução ainda estiver solicitada (senão devolve None) e grava, na mesma
+    /// transação, o reembolso ainda sem recibo. Isso reserva a devolução antes de chamar o
+    /// provedor de pagamento e deixa rastro do reembolso se algo falhar depois da chamada.
+    #[instrument(name = "ReturnRepository::approve", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn approve(
+        &self,
+        id: Uuid,
+        actor_id: Uuid,
+        note: Option<&str>,
+        provider: &str,
+        amount: &Money,
+    ) -> Result<Option<(ReturnRequest, PendingRefund)>, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+
+        let approved =
+            Self::update_decision(&mut *tx, id, ReturnStatus::APPROVED, actor_id, note).await?;
+        let Some(approved) = approved else {
+            return Ok(None);
+        };
+        let refund = PaymentRepository::insert_pending_refund(
+            &mut tx,
+            approved.id,
+            approved.order_id,
+            provider,
+            amount,
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some((approved, refund)))
+    }
+
+    async fn update_decision(
+        executor: impl PgExecutor<'_>,
+        id: Uuid,
+        status: ReturnStatus,
+        actor_id: Uuid,
+        note: Option<&str>,
+    ) -> Result<Option<ReturnRequest>, sqlx::Error> {
+        sqlx::query_as!(
+            ReturnRequest,
+            r#"
+            UPDATE return_requests
+            SET status = $1, decision_note = $2, decided_by = $3, dt_decided = $4, dt_updated = $4
+            WHERE id = $5 AND status = 'REQUESTED'
+            RETURNING
+                id,
+                order_id,
+                order_item_id,
+                buyer_user_id,
+                quantity,
+                reason as "reason: ReturnReason",
+                description,
+                status as "status: ReturnStatus",
+                decision_note,
+                decided_by,
+                (dt_decided AT TIME ZONE 'UTC') as "dt_decided: DateTime<Utc>",
+                received_by,
+                (dt_received AT TIME ZONE 'UTC') as "dt_received: DateTime<Utc>",
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            "#,
+            status as _,
+            note,
+            actor_id,
+            Utc::now().naive_utc(),
+            id
+        )
+        .fetch_optional(executor)
+        .await
+    }
+
+    /// Desfaz a aprovação e apaga o reembolso sem recibo quando o provedor o recusa de forma
+    /// definitiva
+    #[instrument(name = "ReturnRepository::release_approval", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn release_approval(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+
+        PaymentRepository::delete_pending_refund(&mut tx, id).await?;
+        sqlx::query!(
+            r#"
+            UPDATE return_requests
+            SET status = 'REQUESTED', decision_note = NULL, decided_by = NULL,
+                dt_decided = NULL, dt_updated = $1
+            WHERE id = $2 AND status = 'APPROVED'
+            "#,
+            Utc::now().naive_utc(),
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Confirma a chegada do produto devolvido (só depois da aprovação com o reembolso
+    /// registrado em nota de crédito)
+    #[instrument(name = "ReturnRepository::mark_received", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn mark_received(
+        &self,
+        id: Uuid,
+        actor_id: Uuid,
+    ) -> Result<Option<ReturnRequest>, sqlx::Error> {
+        sqlx::query_as!(
+            ReturnRequest,
+            r#"
+            UPDATE return_requests
+            SET status = 'RECEIVED', received_by = $1, dt_received = $2, dt_updated = $2
+            WHERE id = $3 AND status = 'APPROVED'
+              AND EXISTS (SELECT 1 FROM credit_notes c WHERE c.return_request_id = $3)
+            RETURNING
+                id,
+                order_id,
+                order_item_id,
+                buyer_user_id,
+                quantity,
+                reason as "reason: ReturnReason",
+                description,
+                status as "status: ReturnStatus",
+                decision_note,
+                decided_by,
+                (dt_decided AT TIME ZONE 'UTC') as "dt_decided: DateTime<Utc>",
+                received_by,
+                (dt_received AT TIME ZONE 'UTC') as "dt_received: DateTime<Utc>",
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            "#,
+            actor_id,
+            Utc::now().naive_utc(),
+            id
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    /// Registra o reembolso confirmado numa única transação: nota de crédito, total devolvido
+    /// do pedido e volta ao estoque. Quando o total devolvido chega ao total do pedido
+    /// entregue, ele passa a REFUNDED. Pode ser repetido depois de uma falha; devolve None se
+    /// outra tentativa já registrou a devolução.
+    #[instrument(name = "ReturnRepository::record_refund", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn record_refund(
+        &self,
+        return_request: ReturnRequest,
+        record: RefundRecord,
+    ) -> Result<Option<ApprovedReturn>, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+        let now = Utc::now().naive_utc();
+        let refund = record.refund;
+        let amount = &refund.amount;
+
+        let credit_note = sqlx::query_as!(
+            CreditNote,
+            r#"
+            INSERT INTO credit_notes (
+                id, order_id, return_request_id, refund_id, amount, currency, dt_created
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (return_request_id) DO NOTHING
+            RETURNING
+                id,
+                number,
+                order_id,
+                return_request_id,
+                refund_id,
+                ROW(amount, currency)::money_value as "amount!: Money",
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>"
+            "#,
+            Uuid::new_v4(),
+            return_request.order_id,
+            return_request.id,
+            refund.id,
+            amount.amount_minor,
+            amount.currency,
+            now
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(credit_note) = credit_note else {
+            return Ok(None);
+        };
+
+        let order = sqlx::query_as!(
+            Order,
+            r#"
+            UPDATE orders
+            SET refunded_total = refunded_total + $1, dt_updated = $2
+            WHERE id = $3
+            RETURNING
+                id,
+                cart_id,
+                buyer_tenant_id,
+                buyer_user_id,
+                seller_tenant_id,
+                status as "status: OrderStatus",
+                currency,
+                ROW(subtotal, currency)::money_value as "subtotal!: Money",
+                ROW(discount_total, currency)::money_value as "discount_total!: Money",
+                ROW(tax_total, currency)::money_value as "tax_total!: Money",
+                ROW(shipping_total, currency)::money_value as "shipping_total!: Money",
+                ROW(grand_total, currency)::money_value as "grand_total!: Money",
+                ROW(refunded_total, currency)::money_value as "refunded_total!: Money",
+                tracking_code,
+                carrier,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            "#,
+            amount.amount_minor,
+            now,
+            return_request.order_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        LedgerRepository::record_refund(
+            &mut tx,
+            &order,
+            return_request.id,
+            return_request.order_item_id,
+            return_request.quantity,
+            credit_note.id,
+            amount,
+        )
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE products
+            SET stock_quantity = stock_quantity + $1, dt_updated = $2
+            WHERE id = $3
+            "#,
+            return_request.quantity,
+            now,
+            record.product_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let fully_refunded = order.refunded_total.amount_minor >= order.grand_total.amount_minor;
+        let order = match fully_refunded && order.status.can_transition_to(OrderStatus::REFUNDED) {
+            true => {
+                sqlx::query!(
+                    "UPDATE orders SET status = 'REFUNDED' WHERE id = $1",
+                    order.id
+                )
+                .execute(&mut *tx)
+                .await?;
+                OrderRepository::insert_history(
+                    &mut tx,
+                    order.id,
+                    Some(order.status),
+                    OrderStatus::REFUNDED,
+                    Some(record.actor_id),
+                    OrderActorRole::Seller,
+                    Some("Reembolso total por devolução"),
+                )
+                .await?;
+                Order {
+                    status: OrderStatus::REFUNDED,
+                    ..order
+                }
+            }
+            false => order,
+        };
+
+        tx.commit().await?;
+        Ok(Some(ApprovedReturn {
+            return_request,
+            refund,
+            credit_note,
+            order,
+        }))
+    }
+}
+
+fn push_filters(
+    qb: &mut QueryBuilder<'_, Postgres>,
+    seller_tenant_id: Uuid,
+    status: Option<ReturnStatus>,
+) {
+    qb.push("o.seller_tenant_id = ").push_bind(seller_tenant_id);
+    if let Some(status) = status {
+        qb.push(" AND r.status = ").push_bind(status);
+    }
+}
+
+fn return_from_row(row: PgRow) -> ReturnRequest {
+    let utc = |value: NaiveDateTime| DateTime::from_naive_utc_and_offset(value, Utc);
+
+    ReturnRequest {
+        id: row.get("id"),
+        order_id: row.get("order_id"),
+        order_item_id: row.get("order_item_id"),
+        buyer_user_id: row.get("buyer_user_id"),
+        quantity: row.get("quantity"),
+        reason: row.get("reason"),
+        description: row.get("description"),
+        status: row.get("status"),
+        decision_note: row.get("decision_note"),
+        decided_by: row.get("decided_by"),
+        dt_decided: row.get::<Option<NaiveDateTime>, _>("dt_decided").map(utc),
+        received_by: row.get("received_by"),
+        dt_received: row.get::<Option<NaiveDateTime>, _>("dt_received").map(utc),
+        dt_created: utc(row.get("dt_created")),
+        dt_updated: utc(row.get("dt_updated")),
+    }
+}
@@ -0,0 +1,163 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_extensions::RequestUserExt;
+use crate::app_core::app_state::AppState;
+use crate::apps::order_return::models::{
+    CreateReturnRequest, ReturnDecisionRequest, ReturnListParams,
+};
+use crate::apps::order_return::services::ReturnService;
+use crate::utils::pagination::PaginationParams;
+use actix_web::web::Json;
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+use uuid::Uuid;
+
+/// POST /orders/{id}/returns/ - `{"order_item_id": "...", "quantity": 1, "reason": "damaged"}`
+pub async fn create_return(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Json<CreateReturnRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let order_id = path.into_inner();
+    let dto = payload.into_inner();
+
+    let result =
+        ReturnService::request_return(&app_state, tenant_id, user_id, order_id, dto).await?;
+
+    Ok(HttpResponse::Created().json(serde_json::json!(result)))
+}
+
+pub async fn list_order_returns(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let order_id = path.into_inner();
+
+    let result =
+        ReturnService::list_order_returns(&app_state, tenant_id, user_id, order_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn list_order_credit_notes(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let order_id = path.into_inner();
+
+    let result =
+        ReturnService::list_order_credit_notes(&app_state, tenant_id, user_id, order_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// GET /orders/sales/returns/ - devoluções da loja; `?status=requested` filtra
+pub async fn list_seller_returns(
+    app_state: web::Data<AppState>,
+    query: web::Query<PaginationParams>,
+    filter: web::Query<ReturnListParams>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let access_level = req.access_level()?;
+    let page = query.page_request()?;
+
+    let result = ReturnService::list_seller_returns(
+        &app_state,
+        tenant_id,
+        user_id,
+        &access_level,
+        filter.into_inner(),
+        &page,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn list_sale_credit_notes(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let access_level = req.access_level()?;
+    let order_id = path.into_inner();
+
+    let result = ReturnService::list_sale_credit_notes(
+        &app_state,
+        tenant_id,
+        user_id,
+        &access_level,
+        order_id,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /orders/sales/returns/{id}/approve/ - corpo opcional `{"note": "..."}`
+pub async fn approve_return(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Option<Json<ReturnDecisionRequest>>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let access_level = req.access_level()?;
+    let id = path.into_inner();
+    let dto = payload.map(Json::into_inner).unwrap_or_default();
+
+    let result =
+        ReturnService::approve_return(&app_state, tenant_id, user_id, &access_level, id, dto)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /orders/sales/returns/{id}/reject/ - `{"note": "motivo"}`
+pub async fn reject_return(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Json<ReturnDecisionRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let access_level = req.access_level()?;
+    let id = path.into_inner();
+    let dto = payload.into_inner();
+
+    let result =
+        ReturnService::reject_return(&app_state, tenant_id, user_id, &access_level, id, dto)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /orders/sales/returns/{id}/receive/
+pub async fn receive_return(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let access_level = req.access_level()?;
+    let id = path.into_inner();
+
+    let result =
+        ReturnService::receive_return(&app_state, tenant_id, user_id, &access_level, id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
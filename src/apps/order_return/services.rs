@@ -0,0 +1,436 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::app_core::telemetry::record_order_operation;
+use crate::apps::order::models::{Order, OrderItem, OrderStatus};
+use crate::apps::order::repositories::{OrderRepository, OrderScope};
+use crate::apps::order_return::models::{
+    ApprovedReturn, CreateReturnRequest, CreditNote, ReturnDecisionRequest, ReturnListParams,
+    ReturnRequest, ReturnStatus,
+};
+use crate::apps::order_return::repositories::{
+    CreateReturnOutcome, RefundRecord, ReturnRepository,
+};
+use crate::apps::payment::models::{RefundReceipt, RefundRequest};
+use crate::apps::payment::provider::{PaymentProvider, RefundError, configured_provider};
+use crate::apps::payment::repositories::PaymentRepository;
+use crate::apps::tenant::services::TenantService;
+use crate::utils::money::Money;
+use crate::utils::pagination::{Page, PageRequest};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+/// Tamanho máximo da descrição do comprador e da observação da loja
+const MAX_NOTE_LENGTH: usize = 1000;
+
+pub struct ReturnService;
+
+impl ReturnService {
+    /// POST /orders/{id}/returns/ - devolução de parte ou de toda uma linha do pedido entregue
+    pub async fn request_return(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        order_id: Uuid,
+        mut request: CreateReturnRequest,
+    ) -> Result<ReturnRequest, AppError> {
+        if request.quantity <= 0 {
+            return Err(AppError::bad_request("Quantidade deve ser maior que zero"));
+        }
+        request.description = validate_note(request.description.as_deref(), "Descrição")?;
+
+        let order = Self::find_order(
+            app_state,
+            order_id,
+            OrderScope::Buyer { tenant_id, user_id },
+        )
+        .await?;
+        ensure_returnable(order.status)?;
+
+        let outcome = ReturnRepository::new(app_state)
+            .create(order.id, user_id, &request)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        match outcome {
+            CreateReturnOutcome::Created(return_request) => {
+                record_order_operation("return_requested");
+                Ok(return_request)
+            }
+            CreateReturnOutcome::ItemNotFound => {
+                Err(AppError::not_found("Item do pedido não encontrado"))
+            }
+            CreateReturnOutcome::ExceedsQuantity { available } => {
+                Err(AppError::bad_request(format!(
+                    "Quantidade acima do disponível para devolução ({})",
+                    available
+                )))
+            }
+        }
+    }
+
+    /// GET /orders/{id}/returns/
+    pub async fn list_order_returns(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        order_id: Uuid,
+    ) -> Result<Vec<ReturnRequest>, AppError> {
+        let order = Self::find_order(
+            app_state,
+            order_id,
+            OrderScope::Buyer { tenant_id, user_id },
+        )
+        .await?;
+
+        ReturnRepository::new(app_state)
+            .find_by_order(order.id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    /// GET /orders/{id}/credit-notes/
+    pub async fn list_order_credit_notes(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        order_id: Uuid,
+    ) -> Result<Vec<CreditNote>, AppError> {
+        let order = Self::find_order(
+            app_state,
+            order_id,
+            OrderScope::Buyer { tenant_id, user_id },
+        )
+        .await?;
+
+        Self::credit_notes(app_state, order.id).await
+    }
+
+    /// GET /orders/sales/returns/ - fila de devoluções da loja
+    pub async fn list_seller_returns(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+        params: ReturnListParams,
+        page: &PageRequest,
+    ) -> Result<Page<ReturnRequest>, AppError> {
+        Self::ensure_seller(app_state, tenant_id, user_id, access_level).await?;
+
+        ReturnRepository::new(app_state)
+            .find_all_for_seller(tenant_id, params.status, page)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    /// GET /orders/sales/{id}/credit-notes/
+    pub async fn list_sale_credit_notes(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+        order_id: Uuid,
+    ) -> Result<Vec<CreditNote>, AppError> {
+        Self::ensure_seller(app_state, tenant_id, user_id, access_level).await?;
+
+        let order = Self::find_order(app_state, order_id, OrderScope::Seller { tenant_id }).await?;
+        Self::credit_notes(app_state, order.id).await
+    }
+
+    /// POST /orders/sales/returns/{id}/approve/ - reembolsa a linha devolvida pelo provedor de
+    /// pagamento, emite a nota de crédito e devolve a quantidade ao estoque. Repetir a chamada
+    /// numa devolução aprovada cujo reembolso não chegou à nota de crédito conclui o registro.
+    pub async fn approve_return(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+        id: Uuid,
+        request: ReturnDecisionRequest,
+    ) -> Result<ApprovedReturn, AppError> {
+        let provider = configured_provider();
+        Self::approve_return_with(
+            app_state,
+            provider.as_ref(),
+            tenant_id,
+            user_id,
+            access_level,
+            id,
+            request,
+        )
+        .await
+    }
+
+    /// `approve_return` com o provedor de pagamento dado (testes usam provedores próprios)
+    pub async fn approve_return_with(
+        app_state: &AppState,
+        provider: &dyn PaymentProvider,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+        id: Uuid,
+        request: ReturnDecisionRequest,
+    ) -> Result<ApprovedReturn, AppError> {
+        let note = validate_note(request.note.as_deref(), "Observação")?;
+        Self::ensure_seller(app_state, tenant_id, user_id, access_level).await?;
+
+        let return_request = Self::find_return(app_state, id, tenant_id).await?;
+        let unsettled = match return_request.status {
+            ReturnStatus::APPROVED => PaymentRepository::find_unsettled_refund(&app_state.db, id)
+                .await
+                .map_err(|e| AppError::database_error(e.to_string()))?,
+            _ => None,
+        };
+        if unsettled.is_none() {
+            ensure_return_transition(return_request.status, ReturnStatus::APPROVED)?;
+        }
+
+        let order = Self::find_order(
+            app_state,
+            return_request.order_id,
+            OrderScope::Seller { tenant_id },
+        )
+        .await?;
+        ensure_returnable(order.status)?;
+
+        let item = OrderRepository::new(app_state)
+            .list_items(order.id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .into_iter()
+            .find(|item| item.id == return_request.order_item_id)
+            .ok_or_else(|| AppError::not_found("Item do pedido não encontrado"))?;
+
+        let repository = ReturnRepository::new(app_state);
+        let (approved, pending) = match unsettled {
+            Some(pending) => (return_request, pending),
+            None => {
+                let amount = refund_amount(&item, return_request.quantity)?;
+                // Aprovar primeiro garante um único reembolso mesmo com aprovações simultâneas
+                repository
+                    .approve(id, user_id, note.as_deref(), provider.name(), &amount)
+                    .await
+                    .map_err(|e| AppError::database_error(e.to_string()))?
+                    .ok_or_else(Self::changed)?
+            }
+        };
+
+        let receipt = match pending.provider_reference {
+            Some(provider_reference) => RefundReceipt { provider_reference },
+            None => {
+                let refund_request = RefundRequest {
+                    refund_id: pending.id,
+                    order_id: pending.order_id,
+                    amount: pending.amount.clone(),
+                    reason: note,
+                };
+                match provider.refund(&refund_request).await {
+                    Ok(receipt) => receipt,
+                    Err(RefundError::Declined(err)) => {
+                        repository
+                            .release_approval(id)
+                            .await
+                            .map_err(|e| AppError::database_error(e.to_string()))?;
+                        return Err(err);
+                    }
+                    // O reembolso pode ter sido feito: a devolução segue aprovada com o
+                    // reembolso pendente, e a nova tentativa repete o mesmo `refund_id`
+                    Err(RefundError::Unavailable(err)) => {
+                        warn!(
+                            "Reembolso {} da devolução {} sem resposta do provedor; aprove de novo para concluir: {}",
+                            pending.id, id, err
+                        );
+                        return Err(err);
+                    }
+                }
+            }
+        };
+
+        let not_recorded = |e: sqlx::Error| {
+            error!(
+                "Reembolso {} da devolução {} não foi registrado; aprove de novo para concluir: {}",
+                receipt.provider_reference, id, e
+            );
+            AppError::database_error(e.to_string())
+        };
+        let refund = PaymentRepository::confirm_refund(&app_state.db, pending.id, &receipt)
+            .await
+            .map_err(not_recorded)?;
+        let record = RefundRecord {
+            product_id: item.product_id,
+            refund,
+            actor_id: user_id,
+        };
+        let approved = repository
+            .record_refund(approved, record)
+            .await
+            .map_err(not_recorded)?
+            .ok_or_else(Self::changed)?;
+
+        record_order_operation("return_approved");
+        Ok(approved)
+    }
+
+    /// POST /orders/sales/returns/{id}/reject/ - `note` com o motivo é obrigatória
+    pub async fn reject_return(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+        id: Uuid,
+        request: ReturnDecisionRequest,
+    ) -> Result<ReturnRequest, AppError> {
+        let note = validate_note(request.note.as_deref(), "Observação")?
+            .ok_or_else(|| AppError::bad_request("Informe o motivo da recusa em note"))?;
+        Self::ensure_seller(app_state, tenant_id, user_id, access_level).await?;
+
+        let return_request = Self::find_return(app_state, id, tenant_id).await?;
+        ensure_return_transition(return_request.status, ReturnStatus::REJECTED)?;
+
+        let rejected = ReturnRepository::new(app_state)
+            .decide(id, ReturnStatus::REJECTED, user_id, Some(&note))
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(Self::changed)?;
+
+        record_order_operation("return_rejected");
+        Ok(rejected)
+    }
+
+    /// POST /orders/sales/returns/{id}/receive/ - produto devolvido chegou à loja; recusado
+    /// enquanto o reembolso da aprovação não virou nota de crédito
+    pub async fn receive_return(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+        id: Uuid,
+    ) -> Result<ReturnRequest, AppError> {
+        Self::ensure_seller(app_state, tenant_id, user_id, access_level).await?;
+
+        let return_request = Self::find_return(app_state, id, tenant_id).await?;
+        ensure_return_transition(return_request.status, ReturnStatus::RECEIVED)?;
+        if PaymentRepository::find_unsettled_refund(&app_state.db, id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .is_some()
+        {
+            return Err(Self::refund_pending());
+        }
+
+        let received = ReturnRepository::new(app_state)
+            .mark_received(id, user_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(Self::changed)?;
+
+        record_order_operation("return_received");
+        Ok(received)
+    }
+
+    async fn find_order(
+        app_state: &AppState,
+        id: Uuid,
+        scope: OrderScope,
+    ) -> Result<Order, AppError> {
+        OrderRepository::new(app_state)
+            .find_by_id(id, scope)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Pedido não encontrado"))
+    }
+
+    async fn find_return(
+        app_state: &AppState,
+        id: Uuid,
+        seller_tenant_id: Uuid,
+    ) -> Result<ReturnRequest, AppError> {
+        ReturnRepository::new(app_state)
+            .find_by_id(id, seller_tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Devolução não encontrada"))
+    }
+
+    async fn credit_notes(
+        app_state: &AppState,
+        order_id: Uuid,
+    ) -> Result<Vec<CreditNote>, AppError> {
+        ReturnRepository::new(app_state)
+            .list_credit_notes(order_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    async fn ensure_seller(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+    ) -> Result<(), AppError> {
+        TenantService::ensure_admin(
+            app_state,
+            tenant_id,
+            user_id,
+            access_level,
+            "Acesso negado. Apenas o dono da loja pode gerenciar as devoluções.",
+        )
+        .await
+    }
+
+    fn refund_pending() -> AppError {
+        AppError::Conflict(Some(
+            "Reembolso da devolução ainda pendente; aprove de novo para concluir".into(),
+        ))
+    }
+
+    fn changed() -> AppError {
+        AppError::Conflict(Some("Devolução foi alterada; tente novamente".into()))
+    }
+}
+
+/// Devolução só vale para pedido entregue (e ainda não reembolsado por inteiro)
+pub fn ensure_returnable(status: OrderStatus) -> Result<(), AppError> {
+    match status {
+        OrderStatus::DELIVERED => Ok(()),
+        _ => Err(AppError::Conflict(Some(format!(
+            "Pedido {} não aceita devolução",
+            status.as_str()
+        )))),
+    }
+}
+
+/// 409 quando a devolução não pode ir de `from` para `to`
+pub fn ensure_return_transition(from: ReturnStatus, to: ReturnStatus) -> Result<(), AppError> {
+    match from.can_transition_to(to) {
+        true => Ok(()),
+        false => Err(AppError::Conflict(Some(format!(
+            "Transição de devolução inválida: {} -> {}",
+            from.as_str(),
+            to.as_str()
+        )))),
+    }
+}
+
+/// Valor reembolsado: preço pago na linha vezes a quantidade devolvida
+pub fn refund_amount(item: &OrderItem, quantity: i32) -> Result<Money, AppError> {
+    if quantity <= 0 || quantity > item.quantity {
+        return Err(AppError::bad_request(format!(
+            "Quantidade deve estar entre 1 e {}",
+            item.quantity
+        )));
+    }
+    Ok(item.unit_price.checked_mul(quantity as i64)?)
+}
+
+fn validate_note(value: Option<&str>, field: &str) -> Result<Option<String>, AppError> {
+    let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+        return Ok(None);
+    };
+    if value.chars().count() > MAX_NOTE_LENGTH {
+        return Err(AppError::bad_request(format!(
+            "{} deve ter no máximo {} caracteres",
+            field, MAX_NOTE_LENGTH
+        )));
+    }
+    Ok(Some(value.to_string()))
+}
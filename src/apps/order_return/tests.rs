@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use crate::apps::order::models::{OrderItem, OrderStatus};
+    use crate::apps::order_return::models::{ReturnReason, ReturnStatus};
+    use crate::apps::order_return::services::{
+        ensure_return_transition, ensure_returnable, refund_amount,
+    };
+    use crate::utils::money::Money;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    const ALL: [ReturnStatus; 4] = [
+        ReturnStatus::REQUESTED,
+        ReturnStatus::APPROVED,
+        ReturnStatus::REJECTED,
+        ReturnStatus::RECEIVED,
+    ];
+
+    fn item(unit_price: i64, quantity: i32) -> OrderItem {
+        OrderItem {
+            id: Uuid::new_v4(),
+            order_id: Uuid::new_v4(),
+            product_id: Uuid::new_v4(),
+            variant_id: None,
+            attributes_snapshot: serde_json::json!({}),
+            product_name: "Produto".to_string(),
+            unit_price: Money::new(unit_price, "BRL"),
+            quantity,
+            line_total: Money::new(unit_price * quantity as i64, "BRL"),
+            dt_created: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_return_transitions() {
+        assert!(ReturnStatus::REQUESTED.can_transition_to(ReturnStatus::APPROVED));
+        assert!(ReturnStatus::REQUESTED.can_transition_to(ReturnStatus::REJECTED));
+        assert!(ReturnStatus::APPROVED.can_transition_to(ReturnStatus::RECEIVED));
+
+        for next in ALL {
+            assert!(!ReturnStatus::REJECTED.can_transition_to(next));
+            assert!(!ReturnStatus::RECEIVED.can_transition_to(next));
+        }
+        assert!(!ReturnStatus::REQUESTED.can_transition_to(ReturnStatus::RECEIVED));
+        assert!(!ReturnStatus::APPROVED.can_transition_to(ReturnStatus::REJECTED));
+
+        let err =
+            ensure_return_transition(ReturnStatus::REJECTED, ReturnStatus::APPROVED).unwrap_err();
+        assert!(
+            err.message().contains("rejected -> approved"),
+            "{}",
+            err.message()
+        );
+    }
+
+    #[test]
+    fn test_only_delivered_orders_are_returnable() {
+        for status in [
+            OrderStatus::PENDING_PAYMENT,
+            OrderStatus::PAID,
+            OrderStatus::FULFILLING,
+            OrderStatus::SHIPPED,
+            OrderStatus::CANCELLED,
+            OrderStatus::REFUNDED,
+        ] {
+            assert!(ensure_returnable(status).is_err(), "{:?}", status);
+        }
+        assert!(ensure_returnable(OrderStatus::DELIVERED).is_ok());
+    }
+
+    #[test]
+    fn test_refund_amount_per_quantity() {
+        let line = item(1250, 3);
+
+        assert_eq!(refund_amount(&line, 1).unwrap(), Money::new(1250, "BRL"));
+        assert_eq!(refund_amount(&line, 3).unwrap(), line.line_total);
+        assert!(refund_amount(&line, 0).is_err());
+        assert!(refund_amount(&line, 4).is_err());
+    }
+
+    #[test]
+    fn test_return_enums_serialize_lowercase() {
+        assert_eq!(
+            serde_json::to_value(ReturnReason::NOT_AS_DESCRIBED).unwrap(),
+            "not_as_described"
+        );
+        let reason: ReturnReason = serde_json::from_value(serde_json::json!("damaged")).unwrap();
+        assert_eq!(reason, ReturnReason::DAMAGED);
+        for status in ALL {
+            assert_eq!(serde_json::to_value(status).unwrap(), status.as_str());
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod models;
+pub mod provider;
+pub mod repositories;
+
+#[cfg(test)]
+mod tests;
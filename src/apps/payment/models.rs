@@ -0,0 +1,42 @@
+use crate::utils::money::Money;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Reembolso registrado em `payment_refunds`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PaymentRefund {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    /// Nome do provedor que fez o reembolso (`manual`, ...)
+    pub provider: String,
+    pub provider_reference: String,
+    pub amount: Money,
+    pub dt_created: DateTime<Utc>,
+}
+
+/// Reembolso gravado antes da chamada ao provedor; `provider_reference` fica vazio até a
+/// confirmação
+#[derive(Debug, Clone)]
+pub struct PendingRefund {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    pub provider_reference: Option<String>,
+    pub amount: Money,
+}
+
+/// Pedido de reembolso enviado ao provedor
+#[derive(Debug, Clone)]
+pub struct RefundRequest {
+    /// Chave de idempotência: repetir a chamada com o mesmo id não reembolsa duas vezes
+    pub refund_id: Uuid,
+    pub order_id: Uuid,
+    pub amount: Money,
+    pub reason: Option<String>,
+}
+
+/// Confirmação devolvida pelo provedor
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefundReceipt {
+    pub provider_reference: String,
+}
@@ -0,0 +1,68 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::init_settings::get_settings;
+use crate::app_core::settings::PaymentProviderKind;
+use crate::apps::payment::models::{RefundReceipt, RefundRequest};
+use async_trait::async_trait;
+use tracing::info;
+
+/// Falha do provedor ao reembolsar
+#[derive(Debug)]
+pub enum RefundError {
+    /// Recusa definitiva: nada foi reembolsado e a aprovação pode ser desfeita
+    Declined(AppError),
+    /// Resultado desconhecido (timeout, falha de rede): o reembolso pode ter sido feito, e a
+    /// nova tentativa precisa repetir o mesmo `refund_id`. O provedor manual nunca fica sem
+    /// resposta; gateways usam esta variante.
+    #[allow(dead_code)]
+    Unavailable(AppError),
+}
+
+/// Camada de pagamento usada pelos reembolsos de devoluções.
+/// Gateways (cartão, Pix) entram implementando este trait.
+#[async_trait]
+pub trait PaymentProvider: Send + Sync {
+    /// Gravado em `payment_refunds.provider`
+    fn name(&self) -> &'static str;
+
+    /// Deve ser idempotente por `request.refund_id`: uma nova tentativa após falha ao gravar
+    /// devolve o mesmo recibo
+    async fn refund(&self, request: &RefundRequest) -> Result<RefundReceipt, RefundError>;
+}
+
+/// A loja devolve o dinheiro por fora (transferência, estorno na maquininha);
+/// o sistema só registra o reembolso com uma referência própria
+pub struct ManualPaymentProvider;
+
+#[async_trait]
+impl PaymentProvider for ManualPaymentProvider {
+    fn name(&self) -> &'static str {
+        "manual"
+    }
+
+    async fn refund(&self, request: &RefundRequest) -> Result<RefundReceipt, RefundError> {
+        if request.amount.amount_minor <= 0 {
+            return Err(RefundError::Declined(AppError::bad_request(
+                "Valor do reembolso deve ser maior que zero",
+            )));
+        }
+
+        let provider_reference = format!("manual-{}", request.refund_id);
+        info!(
+            "Reembolso manual {} do pedido {}: {} {} ({})",
+            provider_reference,
+            request.order_id,
+            request.amount.amount_minor,
+            request.amount.currency,
+            request.reason.as_deref().unwrap_or("sem motivo")
+        );
+
+        Ok(RefundReceipt { provider_reference })
+    }
+}
+
+/// Provedor definido em PAYMENT_PROVIDER
+pub fn configured_provider() -> Box<dyn PaymentProvider> {
+    match get_settings().payment.provider {
+        PaymentProviderKind::Manual => Box::new(ManualPaymentProvider),
+    }
+}
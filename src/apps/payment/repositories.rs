@@ -0,0 +1,116 @@
+use crate::apps::payment::models::{PaymentRefund, PendingRefund, RefundReceipt};
+use crate::utils::money::Money;
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, Postgres, Transaction};
+use tracing::instrument;
+use uuid::Uuid;
+
+pub struct PaymentRepository;
+
+impl PaymentRepository {
+    /// Grava o reembolso ainda sem recibo dentro da transação que aprova a devolução, antes da
+    /// chamada ao provedor
+    #[instrument(name = "PaymentRepository::insert_pending_refund", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn insert_pending_refund(
+        tx: &mut Transaction<'_, Postgres>,
+        return_request_id: Uuid,
+        order_id: Uuid,
+        provider: &str,
+        amount: &Money,
+    ) -> Result<PendingRefund, sqlx::Error> {
+        sqlx::query_as!(
+            PendingRefund,
+            r#"
+            INSERT INTO payment_refunds (
+                id, order_id, return_request_id, provider, amount, currency, dt_created
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING
+                id,
+                order_id,
+                provider_reference,
+                ROW(amount, currency)::money_value as "amount!: Money"
+            "#,
+            Uuid::new_v4(),
+            order_id,
+            return_request_id,
+            provider,
+            amount.amount_minor,
+            amount.currency,
+            Utc::now().naive_utc()
+        )
+        .fetch_one(&mut **tx)
+        .await
+    }
+
+    /// Reembolso da devolução que ainda não virou nota de crédito (com ou sem recibo)
+    #[instrument(name = "PaymentRepository::find_unsettled_refund", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_unsettled_refund(
+        executor: impl PgExecutor<'_>,
+        return_request_id: Uuid,
+    ) -> Result<Option<PendingRefund>, sqlx::Error> {
+        sqlx::query_as!(
+            PendingRefund,
+            r#"
+            SELECT
+                r.id,
+                r.order_id,
+                r.provider_reference,
+                ROW(r.amount, r.currency)::money_value as "amount!: Money"
+            FROM payment_refunds r
+            WHERE r.return_request_id = $1
+              AND NOT EXISTS (SELECT 1 FROM credit_notes c WHERE c.refund_id = r.id)
+            "#,
+            return_request_id
+        )
+        .fetch_optional(executor)
+        .await
+    }
+
+    /// Guarda o recibo do provedor assim que ele confirma o reembolso
+    #[instrument(name = "PaymentRepository::confirm_refund", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn confirm_refund(
+        executor: impl PgExecutor<'_>,
+        id: Uuid,
+        receipt: &RefundReceipt,
+    ) -> Result<PaymentRefund, sqlx::Error> {
+        sqlx::query_as!(
+            PaymentRefund,
+            r#"
+            UPDATE payment_refunds
+            SET provider_reference = $1
+            WHERE id = $2
+            RETURNING
+                id,
+                order_id,
+                provider,
+                provider_reference as "provider_reference!",
+                ROW(amount, currency)::money_value as "amount!: Money",
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>"
+            "#,
+            receipt.provider_reference,
+            id
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    /// Apaga o reembolso sem recibo quando o provedor o recusa
+    #[instrument(name = "PaymentRepository::delete_pending_refund", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete_pending_refund(
+        tx: &mut Transaction<'_, Postgres>,
+        return_request_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            DELETE FROM payment_refunds
+            WHERE return_request_id = $1 AND provider_reference IS NULL
+            "#,
+            return_request_id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
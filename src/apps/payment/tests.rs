@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use crate::apps::payment::models::RefundRequest;
+    use crate::apps::payment::provider::{ManualPaymentProvider, PaymentProvider, RefundError};
+    use crate::utils::money::Money;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_manual_provider_refund() {
+        let provider = ManualPaymentProvider;
+        let request = RefundRequest {
+            refund_id: Uuid::new_v4(),
+            order_id: Uuid::new_v4(),
+            amount: Money::new(1500, "BRL"),
+            reason: None,
+        };
+
+        let receipt = provider.refund(&request).await.unwrap();
+        assert!(receipt.provider_reference.starts_with("manual-"));
+        assert_eq!(
+            receipt,
+            provider.refund(&request).await.unwrap(),
+            "nova tentativa do mesmo reembolso devolve o mesmo recibo"
+        );
+        let other = RefundRequest {
+            refund_id: Uuid::new_v4(),
+            ..request
+        };
+        assert_ne!(
+            receipt,
+            provider.refund(&other).await.unwrap(),
+            "cada reembolso tem referência própria"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_manual_provider_rejects_zero_amount() {
+        let request = RefundRequest {
+            refund_id: Uuid::new_v4(),
+            order_id: Uuid::new_v4(),
+            amount: Money::zero("BRL"),
+            reason: None,
+        };
+
+        assert!(matches!(
+            ManualPaymentProvider.refund(&request).await,
+            Err(RefundError::Declined(_))
+        ));
+    }
+}
@@ -0,0 +1,64 @@
+use crate::utils::money::Money;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+use uuid::Uuid;
+
+/// Lote criado (aguardando o banco) ou conciliado com o extrato
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "payout_batch_status", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "lowercase")]
+#[allow(clippy::upper_case_acronyms)]
+pub enum PayoutBatchStatus {
+    PENDING,
+    RECONCILED,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PayoutBatch {
+    pub id: Uuid,
+    pub currency: String,
+    pub status: PayoutBatchStatus,
+    pub total: Money,
+    /// Identificação da transferência no extrato bancário
+    pub reference: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub reconciled_by: Option<Uuid>,
+    pub dt_created: DateTime<Utc>,
+    pub dt_reconciled: Option<DateTime<Utc>>,
+}
+
+/// Repasse de uma loja dentro do lote
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Payout {
+    pub id: Uuid,
+    pub batch_id: Uuid,
+    pub seller_tenant_id: Uuid,
+    pub amount: Money,
+    pub batch_status: PayoutBatchStatus,
+    pub dt_created: DateTime<Utc>,
+}
+
+/// Lote com os repasses e o total lançado no razão, para conferência
+#[derive(Debug, Serialize, Clone)]
+pub struct PayoutBatchWithPayouts {
+    #[serde(flatten)]
+    pub batch: PayoutBatch,
+    pub payouts: Vec<Payout>,
+    /// Soma levada para `payout_clearing` na criação do lote
+    pub ledger_total: Money,
+}
+
+/// POST /payouts/batches/ - `{"currency": "BRL"}`
+#[derive(Debug, Deserialize, Clone)]
+pub struct CreatePayoutBatchRequest {
+    pub currency: String,
+}
+
+/// POST /payouts/batches/{id}/reconcile/ - `{"reference": "...", "paid_total": 12345}`
+#[derive(Debug, Deserialize, Clone)]
+pub struct ReconcilePayoutBatchRequest {
+    pub reference: String,
+    /// Valor transferido segundo o extrato, na unidade mínima da moeda do lote
+    pub paid_total: i64,
+}
@@ -0,0 +1,322 @@
+use crate::app_core::app_state::AppState;
+use crate::apps::ledger::models::NewLedgerEntry;
+use crate::apps::ledger::repositories::LedgerRepository;
+use crate::apps::payout::models::{Payout, PayoutBatch, PayoutBatchStatus};
+use crate::utils::money::Money;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+use tracing::instrument;
+use uuid::Uuid;
+
+/// Resultado da conciliação; nada é gravado fora de `Reconciled`
+#[derive(Debug)]
+pub enum ReconcileOutcome {
+    Reconciled(PayoutBatch),
+    NotFound,
+    AlreadyReconciled,
+    /// Valor do extrato ou do razão não bate com o total do lote
+    Mismatch {
+        batch_total: i64,
+        ledger_total: i64,
+    },
+}
+
+pub struct PayoutRepository<'a> {
+    app_state: &'a AppState,
+}
+
+impl<'a> PayoutRepository<'a> {
+    pub fn new(app_state: &'a AppState) -> Self {
+        Self { app_state }
+    }
+
+    #[instrument(name = "PayoutRepository::find_all_batches", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_all_batches(&self) -> Result<Vec<PayoutBatch>, sqlx::Error> {
+        sqlx::query_as!(
+            PayoutBatch,
+            r#"
+            SELECT
+                id,
+                currency,
+                status as "status: PayoutBatchStatus",
+                ROW(total, currency)::money_value as "total!: Money",
+                reference,
+                created_by,
+                reconciled_by,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_reconciled AT TIME ZONE 'UTC') as "dt_reconciled: DateTime<Utc>"
+            FROM payout_batches
+            ORDER BY dt_created DESC, id DESC
+            "#
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "PayoutRepository::find_batch", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_batch(&self, id: Uuid) -> Result<Option<PayoutBatch>, sqlx::Error> {
+        sqlx::query_as!(
+            PayoutBatch,
+            r#"
+            SELECT
+                id,
+                currency,
+                status as "status: PayoutBatchStatus",
+                ROW(total, currency)::money_value as "total!: Money",
+                reference,
+                created_by,
+                reconciled_by,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_reconciled AT TIME ZONE 'UTC') as "dt_reconciled: DateTime<Utc>"
+            FROM payout_batches
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "PayoutRepository::list_payouts", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn list_payouts(&self, batch_id: Uuid) -> Result<Vec<Payout>, sqlx::Error> {
+        sqlx::query_as!(
+            Payout,
+            r#"
+            SELECT
+                p.id,
+                p.batch_id,
+                p.seller_tenant_id,
+                ROW(p.amount, p.currency)::money_value as "amount!: Money",
+                b.status as "batch_status: PayoutBatchStatus",
+                (p.dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>"
+            FROM payouts p
+            JOIN payout_batches b ON b.id = p.batch_id
+            WHERE p.batch_id = $1
+            ORDER BY p.amount DESC, p.id
+            "#,
+            batch_id
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    /// Repasses recebidos (ou a receber) pela loja, mais novos primeiro
+    #[instrument(name = "PayoutRepository::find_seller_payouts", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_seller_payouts(&self, tenant_id: Uuid) -> Result<Vec<Payout>, sqlx::Error> {
+        sqlx::query_as!(
+            Payout,
+            r#"
+            SELECT
+                p.id,
+                p.batch_id,
+                p.seller_tenant_id,
+                ROW(p.amount, p.currency)::money_value as "amount!: Money",
+                b.status as "batch_status: PayoutBatchStatus",
+                (p.dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>"
+            FROM payouts p
+            JOIN payout_batches b ON b.id = p.batch_id
+            WHERE p.seller_tenant_id = $1
+            ORDER BY p.dt_created DESC, p.id DESC
+            "#,
+            tenant_id
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    /// Soma levada para `payout_clearing` quando o lote foi criado
+    #[instrument(name = "PayoutRepository::ledger_total", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn ledger_total(&self, batch_id: Uuid) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(-SUM(p.amount), 0)::bigint as "total!"
+            FROM ledger_postings p
+            JOIN ledger_entries e ON e.id = p.entry_id
+            WHERE e.payout_batch_id = $1 AND e.kind = 'payout' AND p.account = 'payout_clearing'
+            "#,
+            batch_id
+        )
+        .fetch_one(&self.app_state.db)
+        .await
+    }
+
+    /// Cria um lote com o saldo positivo de cada loja na moeda e lança no razão.
+    /// `None` quando nenhuma loja tem saldo. Lotes são criados um de cada vez
+    /// (advisory lock) para que o mesmo saldo não entre em dois lotes.
+    #[instrument(name = "PayoutRepository::create_batch", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create_batch(
+        &self,
+        currency: &str,
+        created_by: Uuid,
+    ) -> Result<Option<PayoutBatch>, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+        sqlx::query!("SELECT pg_advisory_xact_lock(hashtext('payout_batches'))")
+            .execute(&mut *tx)
+            .await?;
+
+        let balances = sqlx::query!(
+            r#"
+            SELECT tenant_id as "tenant_id!", -SUM(amount)::bigint as "available!"
+            FROM ledger_postings
+            WHERE account = 'seller_payable' AND currency = $1
+            GROUP BY tenant_id
+            HAVING -SUM(amount) > 0
+            ORDER BY tenant_id
+            "#,
+            currency
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        if balances.is_empty() {
+            return Ok(None);
+        }
+
+        let lines: Vec<(Uuid, i64)> = balances
+            .into_iter()
+            .map(|row| (row.tenant_id, row.available))
+            .collect();
+        let total: i64 = lines.iter().map(|(_, amount)| amount).sum();
+        let now = Utc::now().naive_utc();
+        let batch_id = Uuid::new_v4();
+
+        let batch = sqlx::query_as!(
+            PayoutBatch,
+            r#"
+            INSERT INTO payout_batches (id, currency, status, total, created_by, dt_created)
+            VALUES ($1, $2, 'PENDING', $3, $4, $5)
+            RETURNING
+                id,
+                currency,
+                status as "status: PayoutBatchStatus",
+                ROW(total, currency)::money_value as "total!: Money",
+                reference,
+                created_by,
+                reconciled_by,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_reconciled AT TIME ZONE 'UTC') as "dt_reconciled: DateTime<Utc>"
+            "#,
+            batch_id,
+            currency,
+            total,
+            created_by,
+            now
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for (tenant_id, amount) in &lines {
+            sqlx::query!(
+                r#"
+                INSERT INTO payouts (id, batch_id, seller_tenant_id, amount, currency, dt_created)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+                Uuid::new_v4(),
+                batch_id,
+                tenant_id,
+                amount,
+                currency,
+                now
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        LedgerRepository::post_entry(&mut tx, &NewLedgerEntry::payout(batch_id, currency, &lines))
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some(batch))
+    }
+
+    /// Confere o valor do extrato com o lote e com o razão; batendo, lança a saída do caixa
+    /// e marca o lote como conciliado
+    #[instrument(name = "PayoutRepository::reconcile", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn reconcile(
+        &self,
+        id: Uuid,
+        reference: &str,
+        paid_total: i64,
+        actor_id: Uuid,
+    ) -> Result<ReconcileOutcome, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+
+        let batch = sqlx::query!(
+            r#"
+            SELECT currency, status as "status: PayoutBatchStatus", total
+            FROM payout_batches
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(batch) = batch else {
+            return Ok(ReconcileOutcome::NotFound);
+        };
+        if batch.status == PayoutBatchStatus::RECONCILED {
+            return Ok(ReconcileOutcome::AlreadyReconciled);
+        }
+
+        let ledger_total = Self::batch_ledger_total(&mut tx, id).await?;
+        if paid_total != batch.total || ledger_total != batch.total {
+            return Ok(ReconcileOutcome::Mismatch {
+                batch_total: batch.total,
+                ledger_total,
+            });
+        }
+
+        LedgerRepository::post_entry(
+            &mut tx,
+            &NewLedgerEntry::payout_settlement(id, &batch.currency, batch.total),
+        )
+        .await?;
+
+        let now = Utc::now().naive_utc();
+        let reconciled = sqlx::query_as!(
+            PayoutBatch,
+            r#"
+            UPDATE payout_batches
+            SET status = 'RECONCILED', reference = $1, reconciled_by = $2, dt_reconciled = $3
+            WHERE id = $4
+            RETURNING
+                id,
+                currency,
+                status as "status: PayoutBatchStatus",
+                ROW(total, currency)::money_value as "total!: Money",
+                reference,
+                created_by,
+                reconciled_by,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_reconciled AT TIME ZONE 'UTC') as "dt_reconciled: DateTime<Utc>"
+            "#,
+            reference,
+            actor_id,
+            now,
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(ReconcileOutcome::Reconciled(reconciled))
+    }
+
+    #[instrument(name = "PayoutRepository::batch_ledger_total", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    async fn batch_ledger_total(
+        tx: &mut Transaction<'_, Postgres>,
+        batch_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(-SUM(p.amount), 0)::bigint as "total!"
+            FROM ledger_postings p
+            JOIN ledger_entries e ON e.id = p.entry_id
+            WHERE e.payout_batch_id = $1 AND e.kind = 'payout' AND p.account = 'payout_clearing'
+            "#,
+            batch_id
+        )
+        .fetch_one(&mut **tx)
+        .await
+    }
+}
@@ -0,0 +1,90 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_extensions::RequestUserExt;
+use crate::app_core::app_state::AppState;
+use crate::apps::payout::models::{CreatePayoutBatchRequest, ReconcilePayoutBatchRequest};
+use crate::apps::payout::services::PayoutService;
+use actix_web::web::Json;
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+use uuid::Uuid;
+
+fn ensure_super_admin(req: &HttpRequest) -> Result<(), AppError> {
+    if req.access_level()? != "super_admin" {
+        return Err(AppError::forbidden(
+            "Acesso negado. Apenas super_admin pode gerenciar os repasses.".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// GET /payouts/ - repasses da loja do usuário
+pub async fn list_seller_payouts(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let access_level = req.access_level()?;
+
+    let result =
+        PayoutService::list_seller_payouts(&app_state, tenant_id, user_id, &access_level).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// GET /payouts/batches/
+pub async fn list_payout_batches(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_super_admin(&req)?;
+    let result = PayoutService::list_batches(&app_state).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /payouts/batches/ - `{"currency": "BRL"}`
+pub async fn create_payout_batch(
+    app_state: web::Data<AppState>,
+    payload: Json<CreatePayoutBatchRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_super_admin(&req)?;
+    let user_id = req.user_id()?;
+
+    let result = PayoutService::create_batch(&app_state, user_id, payload.into_inner()).await?;
+
+    Ok(HttpResponse::Created().json(serde_json::json!(result)))
+}
+
+/// GET /payouts/batches/{id}/
+pub async fn get_payout_batch(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_super_admin(&req)?;
+    let result = PayoutService::get_batch(&app_state, path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /payouts/batches/{id}/reconcile/ - `{"reference": "...", "paid_total": 12345}`
+pub async fn reconcile_payout_batch(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Json<ReconcilePayoutBatchRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    ensure_super_admin(&req)?;
+    let user_id = req.user_id()?;
+
+    let result = PayoutService::reconcile_batch(
+        &app_state,
+        user_id,
+        path.into_inner(),
+        payload.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
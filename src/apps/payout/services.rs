@@ -0,0 +1,165 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::app_core::telemetry::record_order_operation;
+use crate::apps::payout::models::{
+    CreatePayoutBatchRequest, Payout, PayoutBatch, PayoutBatchWithPayouts,
+    ReconcilePayoutBatchRequest,
+};
+use crate::apps::payout::repositories::{PayoutRepository, ReconcileOutcome};
+use crate::apps::tenant::services::TenantService;
+use crate::utils::currency::normalize_currency;
+use crate::utils::money::Money;
+use uuid::Uuid;
+
+/// Tamanho máximo da referência do extrato bancário
+const MAX_REFERENCE_LENGTH: usize = 200;
+
+pub struct PayoutService;
+
+impl PayoutService {
+    /// GET /payouts/batches/
+    pub async fn list_batches(app_state: &AppState) -> Result<Vec<PayoutBatch>, AppError> {
+        PayoutRepository::new(app_state)
+            .find_all_batches()
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    /// GET /payouts/batches/{id}/
+    pub async fn get_batch(
+        app_state: &AppState,
+        id: Uuid,
+    ) -> Result<PayoutBatchWithPayouts, AppError> {
+        let repository = PayoutRepository::new(app_state);
+        let batch = repository
+            .find_batch(id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Lote de repasse não encontrado"))?;
+        Self::with_payouts(&repository, batch).await
+    }
+
+    /// POST /payouts/batches/ - um repasse para cada loja com saldo a receber na moeda
+    pub async fn create_batch(
+        app_state: &AppState,
+        user_id: Uuid,
+        request: CreatePayoutBatchRequest,
+    ) -> Result<PayoutBatchWithPayouts, AppError> {
+        let currency = normalize_currency(&request.currency)?;
+
+        let repository = PayoutRepository::new(app_state);
+        let batch = repository
+            .create_batch(&currency, user_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| {
+                AppError::bad_request(format!("Nenhuma loja com saldo a receber em {}", currency))
+            })?;
+
+        record_order_operation("payout_batch_created");
+        Self::with_payouts(&repository, batch).await
+    }
+
+    /// POST /payouts/batches/{id}/reconcile/ - `paid_total` precisa bater com o lote e com o razão
+    pub async fn reconcile_batch(
+        app_state: &AppState,
+        user_id: Uuid,
+        id: Uuid,
+        request: ReconcilePayoutBatchRequest,
+    ) -> Result<PayoutBatchWithPayouts, AppError> {
+        let reference = request.reference.trim();
+        if reference.is_empty() {
+            return Err(AppError::bad_request("Informe a referência do extrato"));
+        }
+        if reference.chars().count() > MAX_REFERENCE_LENGTH {
+            return Err(AppError::bad_request(format!(
+                "Referência deve ter no máximo {} caracteres",
+                MAX_REFERENCE_LENGTH
+            )));
+        }
+
+        let repository = PayoutRepository::new(app_state);
+        let outcome = repository
+            .reconcile(id, reference, request.paid_total, user_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        match outcome {
+            ReconcileOutcome::Reconciled(batch) => {
+                record_order_operation("payout_batch_reconciled");
+                Self::with_payouts(&repository, batch).await
+            }
+            ReconcileOutcome::NotFound => {
+                Err(AppError::not_found("Lote de repasse não encontrado"))
+            }
+            ReconcileOutcome::AlreadyReconciled => Err(AppError::Conflict(Some(
+                "Lote de repasse já conciliado".into(),
+            ))),
+            ReconcileOutcome::Mismatch {
+                batch_total,
+                ledger_total,
+            } => Err(reconcile_mismatch(
+                request.paid_total,
+                batch_total,
+                ledger_total,
+            )),
+        }
+    }
+
+    /// GET /payouts/ - repasses da loja
+    pub async fn list_seller_payouts(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+    ) -> Result<Vec<Payout>, AppError> {
+        TenantService::ensure_admin(
+            app_state,
+            tenant_id,
+            user_id,
+            access_level,
+            "Acesso negado. Apenas o dono da loja pode ver os repasses.",
+        )
+        .await?;
+
+        PayoutRepository::new(app_state)
+            .find_seller_payouts(tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    async fn with_payouts(
+        repository: &PayoutRepository<'_>,
+        batch: PayoutBatch,
+    ) -> Result<PayoutBatchWithPayouts, AppError> {
+        let payouts = repository
+            .list_payouts(batch.id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        let ledger_total = repository
+            .ledger_total(batch.id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        Ok(PayoutBatchWithPayouts {
+            ledger_total: Money::new(ledger_total, &batch.currency),
+            batch,
+            payouts,
+        })
+    }
+}
+
+/// 409 explicando qual dos valores não bate com o total do lote
+pub fn reconcile_mismatch(paid_total: i64, batch_total: i64, ledger_total: i64) -> AppError {
+    let message = match ledger_total != batch_total {
+        true => format!(
+            "Razão não confere com o lote: lançado {}, lote {}",
+            ledger_total, batch_total
+        ),
+        false => format!(
+            "Valor pago {} diferente do total do lote {}",
+            paid_total, batch_total
+        ),
+    };
+    AppError::Conflict(Some(message))
+}
@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use crate::apps::payout::models::PayoutBatchStatus;
+    use crate::apps::payout::services::reconcile_mismatch;
+
+    #[test]
+    fn test_batch_status_serializes_lowercase() {
+        assert_eq!(
+            serde_json::to_value(PayoutBatchStatus::RECONCILED).unwrap(),
+            serde_json::json!("reconciled")
+        );
+        let status: PayoutBatchStatus = serde_json::from_str("\"pending\"").unwrap();
+        assert_eq!(status, PayoutBatchStatus::PENDING);
+    }
+
+    #[test]
+    fn test_reconcile_mismatch_names_the_divergent_value() {
+        let paid = reconcile_mismatch(900, 1000, 1000);
+        assert!(
+            paid.message().contains("Valor pago 900"),
+            "{}",
+            paid.message()
+        );
+
+        let ledger = reconcile_mismatch(1000, 1000, 800);
+        assert!(ledger.message().contains("Razão"), "{}", ledger.message());
+    }
+}
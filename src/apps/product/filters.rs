@@ -0,0 +1,238 @@
+use crate::app_core::app_error::AppError;
+use actix_web::web;
+use bigdecimal::BigDecimal;
+use serde_json::{Value, json};
+use std::str::FromStr;
+
+/// Prefixo dos filtros por atributo na query string (`attr.cor=azul`, `attr.peso.gte=2`)
+const ATTRIBUTE_PREFIX: &str = "attr.";
+
+/// Limites para manter a consulta previsível
+const MAX_SORT_KEYS: usize = 4;
+const MAX_ATTRIBUTE_FILTERS: usize = 10;
+const MAX_ATTRIBUTE_KEY_LENGTH: usize = 64;
+
+/// Preço efetivo na listagem: o agendamento vigente (`sp`, ver `push_from`) ou o preço base
+pub const EFFECTIVE_PRICE: &str = "COALESCE(sp.price, p.price)";
+
+/// Campos aceitos em `sort`; cada um corresponde a uma coluna fixa
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    Price,
+    Name,
+    Created,
+    Updated,
+    Stock,
+}
+
+impl SortField {
+    pub fn column(&self) -> &'static str {
+        match self {
+            SortField::Price => EFFECTIVE_PRICE,
+            SortField::Name => "p.name",
+            SortField::Created => "p.dt_created",
+            SortField::Updated => "p.dt_updated",
+            SortField::Stock => "p.stock_quantity",
+        }
+    }
+}
+
+impl FromStr for SortField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "price" => Ok(SortField::Price),
+            "name" => Ok(SortField::Name),
+            "created" => Ok(SortField::Created),
+            "updated" => Ok(SortField::Updated),
+            "stock" => Ok(SortField::Stock),
+            _ => Err(format!(
+                "Ordenação inválida: {} (use price, name, created, updated ou stock)",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: SortField,
+    pub descending: bool,
+}
+
+/// Ordem padrão das listagens: mais recentes primeiro
+pub const DEFAULT_SORT: [SortKey; 1] = [SortKey {
+    field: SortField::Created,
+    descending: true,
+}];
+
+/// `sort=-price,name`: campos separados por vírgula, `-` para decrescente
+pub fn parse_sort(value: &str) -> Result<Vec<SortKey>, AppError> {
+    let mut keys: Vec<SortKey> = Vec::new();
+
+    for part in value.split(',').map(str::trim) {
+        let (descending, name) = match part.strip_prefix('-') {
+            Some(name) => (true, name),
+            None => (false, part),
+        };
+        let field: SortField = name.parse().map_err(AppError::bad_request)?;
+
+        if keys.iter().any(|k| k.field == field) {
+            return Err(AppError::bad_request(format!(
+                "Campo repetido na ordenação: {}",
+                name
+            )));
+        }
+        keys.push(SortKey { field, descending });
+    }
+
+    if keys.len() > MAX_SORT_KEYS {
+        return Err(AppError::bad_request(format!(
+            "Use no máximo {} campos na ordenação",
+            MAX_SORT_KEYS
+        )));
+    }
+    Ok(keys)
+}
+
+/// Comparação numérica sobre um atributo
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeOp {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl RangeOp {
+    pub fn operator(&self) -> &'static str {
+        match self {
+            RangeOp::Gt => ">",
+            RangeOp::Gte => ">=",
+            RangeOp::Lt => "<",
+            RangeOp::Lte => "<=",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeCondition {
+    /// Qualquer um dos valores, já em todas as formas JSON aceitas (texto, número, booleano)
+    Equals(Vec<Value>),
+    Range(RangeOp, BigDecimal),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeFilter {
+    pub key: String,
+    pub condition: AttributeCondition,
+}
+
+impl AttributeFilter {
+    /// Documento para `attributes @> ...` (usa o índice GIN)
+    pub fn containment(&self, value: &Value) -> Value {
+        json!({ self.key.clone(): value })
+    }
+}
+
+fn valid_attribute_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_ATTRIBUTE_KEY_LENGTH
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Formas JSON equivalentes ao valor da query string: "42" casa com "42" e com 42
+fn candidate_values(raw: &str) -> Vec<Value> {
+    let mut values = vec![Value::String(raw.to_string())];
+
+    if let Ok(number) = serde_json::from_str::<serde_json::Number>(raw) {
+        values.push(Value::Number(number));
+    }
+    match raw {
+        "true" => values.push(Value::Bool(true)),
+        "false" => values.push(Value::Bool(false)),
+        _ => {}
+    }
+    values
+}
+
+/// Filtros `attr.*` da query string. Repetir a chave aceita qualquer um dos valores
+/// (`attr.cor=azul&attr.cor=verde`); faixas usam `.gt`, `.gte`, `.lt` e `.lte`.
+pub fn parse_attribute_filters(
+    pairs: &[(String, String)],
+) -> Result<Vec<AttributeFilter>, AppError> {
+    let mut filters: Vec<AttributeFilter> = Vec::new();
+
+    for (name, raw) in pairs {
+        let Some(path) = name.strip_prefix(ATTRIBUTE_PREFIX) else {
+            continue;
+        };
+
+        let (key, op) = match path.rsplit_once('.') {
+            Some((key, "gt")) => (key, Some(RangeOp::Gt)),
+            Some((key, "gte")) => (key, Some(RangeOp::Gte)),
+            Some((key, "lt")) => (key, Some(RangeOp::Lt)),
+            Some((key, "lte")) => (key, Some(RangeOp::Lte)),
+            Some(_) => {
+                return Err(AppError::bad_request(format!(
+                    "Filtro de atributo inválido: {} (use attr.<chave>, attr.<chave>.gte, .gt, .lte ou .lt)",
+                    name
+                )));
+            }
+            None => (path, None),
+        };
+
+        if !valid_attribute_key(key) {
+            return Err(AppError::bad_request(format!(
+                "Chave de atributo inválida: {} (letras, números, '_' ou '-', até {} caracteres)",
+                key, MAX_ATTRIBUTE_KEY_LENGTH
+            )));
+        }
+
+        match op {
+            Some(op) => {
+                let value = BigDecimal::from_str(raw.trim()).map_err(|_| {
+                    AppError::bad_request(format!("Valor numérico inválido em {}: {}", name, raw))
+                })?;
+                filters.push(AttributeFilter {
+                    key: key.to_string(),
+                    condition: AttributeCondition::Range(op, value),
+                });
+            }
+            None => {
+                let values = candidate_values(raw);
+                let existing = filters
+                    .iter_mut()
+                    .find(|f| f.key == key && matches!(f.condition, AttributeCondition::Equals(_)));
+                match existing {
+                    Some(AttributeFilter {
+                        condition: AttributeCondition::Equals(options),
+                        ..
+                    }) => options.extend(values),
+                    _ => filters.push(AttributeFilter {
+                        key: key.to_string(),
+                        condition: AttributeCondition::Equals(values),
+                    }),
+                }
+            }
+        }
+    }
+
+    if filters.len() > MAX_ATTRIBUTE_FILTERS {
+        return Err(AppError::bad_request(format!(
+            "Use no máximo {} filtros de atributo",
+            MAX_ATTRIBUTE_FILTERS
+        )));
+    }
+    Ok(filters)
+}
+
+/// Lê os filtros `attr.*` direto da query string (chaves dinâmicas, fora de `ProductListParams`)
+pub fn attribute_filters_from_query(query: &str) -> Result<Vec<AttributeFilter>, AppError> {
+    let pairs = web::Query::<Vec<(String, String)>>::from_query(query)
+        .map_err(|_| AppError::bad_request("Query string inválida"))?;
+    parse_attribute_filters(&pairs)
+}
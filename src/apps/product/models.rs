@@ -1,20 +1,23 @@
-use bigdecimal::BigDecimal;
+use crate::apps::category::models::ProductCategory;
+use crate::apps::product::filters::AttributeFilter;
+use crate::apps::product_price::models::EffectivePrice;
+use crate::utils::money::Money;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use serde_with::{DisplayFromStr, serde_as};
 use uuid::Uuid;
 
-#[serde_as]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Product {
     pub id: Uuid,
     pub tenant_id: Uuid,
     pub name: String,
     pub slug: String,
+    /// Código do lojista, único por tenant (chave da importação em lote)
+    pub sku: Option<String>,
     pub short_description: Option<String>,
     pub description: Option<String>,
-    #[serde_as(as = "DisplayFromStr")]
-    pub price: BigDecimal,
+    /// Preço base, na moeda base da loja
+    pub price: Money,
     pub stock_quantity: i32,
     pub attributes: Option<serde_json::Value>,
     pub is_active: bool,
@@ -23,9 +26,28 @@ pub struct Product {
     pub dt_deleted: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Produto com as categorias atribuídas (e breadcrumbs de cada uma)
+#[derive(Debug, Serialize, Clone)]
+pub struct ProductWithCategories {
+    #[serde(flatten)]
+    pub product: Product,
+    pub categories: Vec<ProductCategory>,
+    /// Preço cobrado agora (`price` é o preço base)
+    #[serde(flatten)]
+    pub pricing: EffectivePrice,
+}
+
+/// Resultado da busca por slug: o produto ou o slug atual de um produto renomeado
+#[derive(Debug, Clone)]
+pub enum ProductSlugLookup {
+    Found(Box<ProductWithCategories>),
+    Moved(String),
+}
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct CreateProductRequest {
     pub name: String,
+    pub sku: Option<String>,
     pub short_description: Option<String>,
     pub description: Option<String>,
     pub price: i64,
@@ -37,6 +59,7 @@ pub struct CreateProductRequest {
 #[derive(Debug, Deserialize)]
 pub struct UpdateProductRequest {
     pub name: Option<String>,
+    pub sku: Option<String>,
     pub short_description: Option<String>,
     pub description: Option<String>,
     pub price: Option<i64>,
@@ -45,6 +68,13 @@ pub struct UpdateProductRequest {
     pub is_active: Option<bool>,
 }
 
+/// Quem consulta o catálogo: o lojista vê tudo do próprio tenant; a vitrine só os ativos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogView {
+    Admin,
+    Storefront,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ProductListParams {
     pub name: Option<String>,
@@ -53,4 +83,25 @@ pub struct ProductListParams {
     pub limit: Option<i64>,
     pub offset: Option<i64>,
     pub is_active: Option<bool>,
+    /// Filtra pela categoria e todas as suas descendentes
+    pub category_id: Option<Uuid>,
+    /// Inclui os produtos excluídos (apenas na administração do catálogo)
+    pub include_deleted: Option<bool>,
+    /// Paginação por cursor: vazio na primeira página, depois `next`/`prev` da resposta
+    pub cursor: Option<String>,
+    /// Total na paginação por cursor (consulta extra)
+    pub include_count: Option<bool>,
+    /// Ordenação: `-price,name` (price, name, created, updated, stock)
+    pub sort: Option<String>,
+    /// `true` só com estoque; `false` só sem estoque
+    pub in_stock: Option<bool>,
+    /// Alterados a partir deste instante (RFC 3339)
+    pub updated_since: Option<DateTime<Utc>>,
+    /// Filtros `attr.*`, lidos da query string pela rota
+    #[serde(skip)]
+    pub attribute_filters: Vec<AttributeFilter>,
+    /// Moeda base da loja, preenchida pelo serviço: `min_price`/`max_price` estão na unidade
+    /// mínima dela
+    #[serde(skip)]
+    pub currency: String,
 }
@@ -1,15 +1,151 @@
 use crate::app_core::app_state::AppState;
+use crate::apps::product::filters::{AttributeCondition, EFFECTIVE_PRICE, SortKey};
 use crate::apps::product::models::{
-    CreateProductRequest, Product, ProductListParams, UpdateProductRequest,
+    CatalogView, CreateProductRequest, Product, ProductListParams, UpdateProductRequest,
 };
-use crate::utils::pagination::PaginatedResponse;
+use crate::apps::product_price::models::PriceKind;
+use crate::apps::product_price::repositories::ProductPriceRepository;
+use crate::utils::currency::Rounding;
+use crate::utils::money::Money;
+use crate::utils::pagination::{CursorPage, Page, PageRequest, PaginatedResponse, push_keyset};
 use bigdecimal::BigDecimal;
 use chrono::{DateTime, NaiveDateTime, Utc};
 use sqlx::{Postgres, QueryBuilder, Row, postgres::PgRow};
+use tracing::instrument;
 use uuid::Uuid;
 
-fn cents_to_bigdecimal(cents: i64) -> BigDecimal {
-    BigDecimal::from(cents) / BigDecimal::from(100)
+/// `products.price` é o decimal na moeda base da loja; fora do repositório o preço é `Money`.
+/// Gravado sempre a partir da unidade mínima, então não há arredondamento de fato.
+fn base_price(price: &BigDecimal, currency: &str) -> Result<Money, sqlx::Error> {
+    Money::from_decimal(price, currency, Rounding::HalfEven)
+        .map_err(|e| sqlx::Error::Decode(e.to_string().into()))
+}
+
+/// FROM da listagem com a loja (`t`, moeda base) e o agendamento de preço vigente de cada
+/// produto (`sp`), para que ordenação e faixa de preço usem o preço efetivo
+fn push_from(qb: &mut QueryBuilder<'_, Postgres>) {
+    qb.push(
+        " FROM products p
+        JOIN tenants t ON t.id = p.tenant_id
+        LEFT JOIN LATERAL (
+            SELECT pp.price
+            FROM product_prices pp
+            WHERE pp.product_id = p.id
+              AND pp.kind = ",
+    )
+    .push_bind(PriceKind::Scheduled.as_str())
+    .push(" AND pp.dt_deleted IS NULL AND pp.starts_at <= ");
+    let now = Utc::now().naive_utc();
+    qb.push_bind(now)
+        .push(" AND (pp.ends_at IS NULL OR pp.ends_at > ")
+        .push_bind(now)
+        .push(
+            ")
+            ORDER BY pp.starts_at DESC
+            LIMIT 1
+        ) sp ON true
+        WHERE ",
+        );
+}
+
+/// WHERE da listagem (depois de "WHERE "), compartilhado entre a página e a contagem
+fn push_filters(
+    qb: &mut QueryBuilder<'_, Postgres>,
+    tenant_id: Uuid,
+    params: &ProductListParams,
+    view: CatalogView,
+) {
+    // WHERE obrigatório: tenant; a vitrine só enxerga produtos ativos e não excluídos
+    qb.push("p.tenant_id = ").push_bind(tenant_id);
+
+    let include_deleted = view == CatalogView::Admin && params.include_deleted == Some(true);
+    if !include_deleted {
+        qb.push(" AND p.dt_deleted IS NULL");
+    }
+
+    let is_active = match view {
+        CatalogView::Admin => params.is_active,
+        CatalogView::Storefront => Some(true),
+    };
+
+    // Filtros opcionais
+    if let Some(name) = params.name.as_ref().filter(|s| !s.trim().is_empty()) {
+        qb.push(" AND p.name ILIKE ")
+            .push_bind(format!("%{}%", name));
+    }
+
+    if let Some(min_cents) = params.min_price {
+        let bd = Money::new(min_cents, params.currency.as_str()).to_decimal();
+        qb.push(format!(" AND {} >= ", EFFECTIVE_PRICE))
+            .push_bind(bd);
+    }
+
+    if let Some(max_cents) = params.max_price {
+        let bd = Money::new(max_cents, params.currency.as_str()).to_decimal();
+        qb.push(format!(" AND {} <= ", EFFECTIVE_PRICE))
+            .push_bind(bd);
+    }
+
+    if let Some(is_active) = is_active {
+        qb.push(" AND p.is_active = ").push_bind(is_active);
+    }
+
+    // Categoria inclui as descendentes (prefixo do materialized path)
+    if let Some(category_id) = params.category_id {
+        qb.push(
+            " AND EXISTS (
+            SELECT 1
+            FROM product_categories pc
+            JOIN categories c ON c.id = pc.category_id AND c.dt_deleted IS NULL
+            JOIN categories root ON root.id = ",
+        )
+        .push_bind(category_id)
+        .push(
+            " AND root.dt_deleted IS NULL
+            WHERE pc.product_id = p.id AND c.path LIKE root.path || '%'
+        )",
+        );
+    }
+
+    match params.in_stock {
+        Some(true) => {
+            qb.push(" AND p.stock_quantity > 0");
+        }
+        Some(false) => {
+            qb.push(" AND p.stock_quantity <= 0");
+        }
+        None => {}
+    }
+
+    if let Some(updated_since) = params.updated_since {
+        qb.push(" AND p.dt_updated >= ")
+            .push_bind(updated_since.naive_utc());
+    }
+
+    // Atributos: chave e valores sempre como parâmetros; igualdade usa o índice GIN
+    for filter in &params.attribute_filters {
+        match &filter.condition {
+            AttributeCondition::Equals(values) => {
+                qb.push(" AND (");
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        qb.push(" OR ");
+                    }
+                    qb.push("p.attributes @> ")
+                        .push_bind(filter.containment(value));
+                }
+                qb.push(")");
+            }
+            AttributeCondition::Range(op, value) => {
+                qb.push(" AND (CASE WHEN jsonb_typeof(p.attributes -> ")
+                    .push_bind(filter.key.clone())
+                    .push(") = 'number' THEN (p.attributes ->> ")
+                    .push_bind(filter.key.clone())
+                    .push(format!(")::numeric END) {} ", op.operator()))
+                    .push_bind(value.clone());
+            }
+        }
+    }
 }
 
 pub struct ProductRepository<'a> {
@@ -21,19 +157,22 @@ impl<'a> ProductRepository<'a> {
         Self { app_state }
     }
 
+    #[instrument(name = "ProductRepository::find_all", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_all(
         &self,
+        tenant_id: Uuid,
         params: ProductListParams,
-    ) -> Result<PaginatedResponse<Product>, sqlx::Error> {
-        let limit = params.limit.unwrap_or(20).clamp(1, 100); // limites saudáveis
-        let offset = params.offset.unwrap_or(0).max(0);
-
+        view: CatalogView,
+        sort: &[SortKey],
+        page: &PageRequest,
+    ) -> Result<Page<Product>, sqlx::Error> {
         let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
             "SELECT
             p.id,
             p.tenant_id,
             p.name,
             p.slug,
+            p.sku,
             p.short_description,
             p.description,
             p.price,
@@ -43,85 +182,100 @@ impl<'a> ProductRepository<'a> {
             p.dt_created,
             p.dt_updated,
             p.dt_deleted,
-            COUNT(*) OVER() AS total_count
-         FROM products p
-         WHERE ",
+            t.base_currency",
         );
 
-        // WHERE obrigatório: tenant, não deletado
-        {
-            let mut cond = qb.separated(" AND ");
-            // cond.push("p.tenant_id = ").push_bind(tenant_id);
-            cond.push("p.dt_deleted IS NULL");
-        }
-
-        // Filtros opcionais
-        if let Some(name) = params.name.filter(|s| !s.trim().is_empty()) {
-            qb.push(" AND p.name ILIKE ")
-                .push_bind(format!("%{}%", name));
+        // Total na própria consulta só na paginação por offset; por cursor é opcional
+        if let PageRequest::Offset { .. } = page {
+            qb.push(", COUNT(*) OVER() AS total_count");
         }
-
-        if let Some(min_cents) = params.min_price {
-            let bd = cents_to_bigdecimal(min_cents);
-            qb.push(" AND p.price >= ").push_bind(bd);
-        }
-
-        if let Some(max_cents) = params.max_price {
-            let bd = cents_to_bigdecimal(max_cents);
-            qb.push(" AND p.price <= ").push_bind(bd);
-        }
-
-        if let Some(is_active) = params.is_active {
-            qb.push(" AND p.is_active = ").push_bind(is_active);
-        }
-
-        // Ordenação padrão (ajuste se preferir)
-        qb.push(" ORDER BY p.dt_created DESC, p.id DESC");
-
-        // Paginação
-        qb.push(" LIMIT ").push_bind(limit);
-        qb.push(" OFFSET ").push_bind(offset);
-
-        let rows: Vec<PgRow> = qb.build().fetch_all(&self.app_state.db).await?;
+        push_from(&mut qb);
+        push_filters(&mut qb, tenant_id, &params, view);
+
+        let rows: Vec<PgRow> = match page {
+            PageRequest::Offset { limit, offset } => {
+                // Colunas vêm da lista fixa de SortField; o id desempata
+                qb.push(" ORDER BY ");
+                for key in sort {
+                    let order = if key.descending { "DESC" } else { "ASC" };
+                    qb.push(format!("{} {}, ", key.field.column(), order));
+                }
+                qb.push("p.id DESC");
+
+                // Paginação
+                qb.push(" LIMIT ").push_bind(*limit);
+                qb.push(" OFFSET ").push_bind(*offset);
+                qb.build().fetch_all(&self.app_state.db).await?
+            }
+            PageRequest::Cursor { limit, cursor, .. } => {
+                push_keyset(&mut qb, "p.dt_created", "p.id", cursor.as_ref(), *limit);
+                qb.build().fetch_all(&self.app_state.db).await?
+            }
+        };
 
         // Extrai total de forma segura
-        let total = rows
-            .get(0)
-            .map(|r| r.get::<i64, _>("total_count"))
-            .unwrap_or(0);
+        let total = match page {
+            PageRequest::Offset { .. } => rows
+                .first()
+                .map(|r| r.get::<i64, _>("total_count"))
+                .unwrap_or(0),
+            _ => 0,
+        };
 
-        let items = rows
+        let items: Vec<Product> = rows
             .into_iter()
-            .map(|row| Product {
-                id: row.get("id"),
-                tenant_id: row.get("tenant_id"),
-                name: row.get("name"),
-                slug: row.get("slug"),
-                short_description: row.get("short_description"),
-                description: row.get("description"),
-                price: row.get("price"),
-                stock_quantity: row.get("stock_quantity"),
-                attributes: row.get("attributes"),
-                is_active: row.get("is_active"),
-                dt_created: DateTime::from_naive_utc_and_offset(
-                    row.get::<NaiveDateTime, _>("dt_created"),
-                    Utc,
-                ),
-                dt_updated: DateTime::from_naive_utc_and_offset(
-                    row.get::<NaiveDateTime, _>("dt_updated"),
-                    Utc,
-                ),
-                dt_deleted: row
-                    .get::<Option<NaiveDateTime>, _>("dt_deleted")
-                    .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            .map(|row| {
+                Ok(Product {
+                    id: row.get("id"),
+                    tenant_id: row.get("tenant_id"),
+                    name: row.get("name"),
+                    slug: row.get("slug"),
+                    sku: row.get("sku"),
+                    short_description: row.get("short_description"),
+                    description: row.get("description"),
+                    price: base_price(&row.get("price"), row.get("base_currency"))?,
+                    stock_quantity: row.get("stock_quantity"),
+                    attributes: row.get("attributes"),
+                    is_active: row.get("is_active"),
+                    dt_created: DateTime::from_naive_utc_and_offset(
+                        row.get::<NaiveDateTime, _>("dt_created"),
+                        Utc,
+                    ),
+                    dt_updated: DateTime::from_naive_utc_and_offset(
+                        row.get::<NaiveDateTime, _>("dt_updated"),
+                        Utc,
+                    ),
+                    dt_deleted: row
+                        .get::<Option<NaiveDateTime>, _>("dt_deleted")
+                        .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+                })
             })
-            .collect();
-
-        Ok(PaginatedResponse {
-            count: total,
-            results: items,
-            limit,
-            offset,
+            .collect::<Result<_, sqlx::Error>>()?;
+
+        Ok(match page {
+            PageRequest::Offset { limit, offset } => Page::Offset(PaginatedResponse {
+                count: total,
+                results: items,
+                limit: *limit,
+                offset: *offset,
+            }),
+            PageRequest::Cursor {
+                limit,
+                cursor,
+                include_count,
+            } => {
+                let count = match include_count {
+                    true => Some(self.count_all(tenant_id, &params, view).await?),
+                    false => None,
+                };
+                Page::Cursor(CursorPage::from_rows(
+                    items,
+                    *limit,
+                    cursor.as_ref(),
+                    |p| (p.dt_created, p.id),
+                    count,
+                ))
+            }
         })
 
         // let rows =
@@ -153,34 +307,118 @@ impl<'a> ProductRepository<'a> {
         // Ok(products)
     }
 
+    /// Total da listagem com os mesmos filtros (paginação por cursor com `include_count`)
+    #[instrument(name = "ProductRepository::count_all", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    async fn count_all(
+        &self,
+        tenant_id: Uuid,
+        params: &ProductListParams,
+        view: CatalogView,
+    ) -> Result<i64, sqlx::Error> {
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new("SELECT COUNT(*)");
+        push_from(&mut qb);
+        push_filters(&mut qb, tenant_id, params, view);
+
+        qb.build_query_scalar().fetch_one(&self.app_state.db).await
+    }
+
+    #[instrument(name = "ProductRepository::find_by_id", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Product>, sqlx::Error> {
         let row = sqlx::query!(
-            "SELECT * FROM products WHERE id = $1 AND dt_deleted IS NULL AND is_active = true",
+            r#"
+            SELECT p.*, t.base_currency
+            FROM products p
+            JOIN tenants t ON t.id = p.tenant_id
+            WHERE p.id = $1 AND p.dt_deleted IS NULL AND p.is_active = true
+            "#,
             id
         )
         .fetch_optional(&self.app_state.db)
         .await?;
 
-        Ok(row.map(|row| Product {
-            id: row.id,
-            tenant_id: row.tenant_id,
-            name: row.name,
-            slug: row.slug,
-            short_description: row.short_description,
-            description: row.description,
-            price: row.price,
-            stock_quantity: row.stock_quantity,
-            attributes: row.attributes,
-            is_active: row.is_active,
-            dt_created: DateTime::from_naive_utc_and_offset(row.dt_created, Utc),
-            dt_updated: DateTime::from_naive_utc_and_offset(row.dt_updated, Utc),
-            dt_deleted: row
-                .dt_deleted
-                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
-        }))
+        row.map(|row| {
+            Ok(Product {
+                id: row.id,
+                tenant_id: row.tenant_id,
+                name: row.name,
+                slug: row.slug,
+                sku: row.sku,
+                short_description: row.short_description,
+                description: row.description,
+                price: base_price(&row.price, &row.base_currency)?,
+                stock_quantity: row.stock_quantity,
+                attributes: row.attributes,
+                is_active: row.is_active,
+                dt_created: DateTime::from_naive_utc_and_offset(row.dt_created, Utc),
+                dt_updated: DateTime::from_naive_utc_and_offset(row.dt_updated, Utc),
+                dt_deleted: row
+                    .dt_deleted
+                    .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            })
+        })
+        .transpose()
     }
 
+    /// Produto do tenant em qualquer estado (inativo ou excluído), para a administração do catálogo
+    #[instrument(name = "ProductRepository::find_in_tenant", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_in_tenant(
+        &self,
+        id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<Option<Product>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"
+            SELECT p.*, t.base_currency
+            FROM products p
+            JOIN tenants t ON t.id = p.tenant_id
+            WHERE p.id = $1 AND p.tenant_id = $2
+            "#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(&self.app_state.db)
+        .await?;
+
+        row.map(|row| {
+            Ok(Product {
+                id: row.id,
+                tenant_id: row.tenant_id,
+                name: row.name,
+                slug: row.slug,
+                sku: row.sku,
+                short_description: row.short_description,
+                description: row.description,
+                price: base_price(&row.price, &row.base_currency)?,
+                stock_quantity: row.stock_quantity,
+                attributes: row.attributes,
+                is_active: row.is_active,
+                dt_created: DateTime::from_naive_utc_and_offset(row.dt_created, Utc),
+                dt_updated: DateTime::from_naive_utc_and_offset(row.dt_updated, Utc),
+                dt_deleted: row
+                    .dt_deleted
+                    .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            })
+        })
+        .transpose()
+    }
+
+    #[instrument(name = "ProductRepository::find_by_ids", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Product>, sqlx::Error> {
+        self.fetch_by_ids(ids, true).await
+    }
+
+    /// Como `find_by_ids`, mas inclui produtos inativos e removidos (revalidação do carrinho)
+    #[instrument(name = "ProductRepository::find_by_ids_any_status", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_ids_any_status(&self, ids: &[Uuid]) -> Result<Vec<Product>, sqlx::Error> {
+        self.fetch_by_ids(ids, false).await
+    }
+
+    #[instrument(name = "ProductRepository::fetch_by_ids", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    async fn fetch_by_ids(
+        &self,
+        ids: &[Uuid],
+        only_available: bool,
+    ) -> Result<Vec<Product>, sqlx::Error> {
         if ids.is_empty() {
             return Ok(vec![]);
         }
@@ -191,8 +429,13 @@ impl<'a> ProductRepository<'a> {
             .collect();
         
         let query = format!(
-            "SELECT * FROM products WHERE id IN ({}) AND dt_deleted IS NULL AND is_active = true",
-            placeholders.join(",")
+            "SELECT p.*, t.base_currency FROM products p JOIN tenants t ON t.id = p.tenant_id WHERE p.id IN ({}){}",
+            placeholders.join(","),
+            if only_available {
+                " AND p.dt_deleted IS NULL AND p.is_active = true"
+            } else {
+                ""
+            }
         );
 
         let mut query_builder = sqlx::query(&query);
@@ -204,55 +447,262 @@ impl<'a> ProductRepository<'a> {
 
         let rows = query_builder.fetch_all(&self.app_state.db).await?;
 
-        let products = rows
-            .into_iter()
-            .map(|row| Product {
-                id: row.get("id"),
-                tenant_id: row.get("tenant_id"),
-                name: row.get("name"),
-                slug: row.get("slug"),
-                short_description: row.get("short_description"),
-                description: row.get("description"),
-                price: row.get("price"),
-                stock_quantity: row.get("stock_quantity"),
-                attributes: row.get("attributes"),
-                is_active: row.get("is_active"),
-                dt_created: DateTime::from_naive_utc_and_offset(
-                    row.get::<NaiveDateTime, _>("dt_created"),
-                    Utc,
-                ),
-                dt_updated: DateTime::from_naive_utc_and_offset(
-                    row.get::<NaiveDateTime, _>("dt_updated"),
-                    Utc,
-                ),
+        rows.into_iter()
+            .map(|row| {
+                Ok(Product {
+                    id: row.get("id"),
+                    tenant_id: row.get("tenant_id"),
+                    name: row.get("name"),
+                    slug: row.get("slug"),
+                    sku: row.get("sku"),
+                    short_description: row.get("short_description"),
+                    description: row.get("description"),
+                    price: base_price(&row.get("price"), row.get("base_currency"))?,
+                    stock_quantity: row.get("stock_quantity"),
+                    attributes: row.get("attributes"),
+                    is_active: row.get("is_active"),
+                    dt_created: DateTime::from_naive_utc_and_offset(
+                        row.get::<NaiveDateTime, _>("dt_created"),
+                        Utc,
+                    ),
+                    dt_updated: DateTime::from_naive_utc_and_offset(
+                        row.get::<NaiveDateTime, _>("dt_updated"),
+                        Utc,
+                    ),
+                    dt_deleted: row
+                        .get::<Option<NaiveDateTime>, _>("dt_deleted")
+                        .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+                })
+            })
+            .collect()
+    }
+
+    /// Busca o produto ativo pelo slug atual dentro do tenant (vitrine)
+    #[instrument(name = "ProductRepository::find_by_slug", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_slug(
+        &self,
+        tenant_id: Uuid,
+        slug: &str,
+    ) -> Result<Option<Product>, sqlx::Error> {
+        self.fetch_by_slug(tenant_id, slug, true).await
+    }
+
+    /// Como `find_by_slug`, mas inclui produtos inativos (rota administrativa)
+    #[instrument(name = "ProductRepository::find_by_slug_any_status", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_slug_any_status(
+        &self,
+        tenant_id: Uuid,
+        slug: &str,
+    ) -> Result<Option<Product>, sqlx::Error> {
+        self.fetch_by_slug(tenant_id, slug, false).await
+    }
+
+    #[instrument(name = "ProductRepository::fetch_by_slug", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    async fn fetch_by_slug(
+        &self,
+        tenant_id: Uuid,
+        slug: &str,
+        only_active: bool,
+    ) -> Result<Option<Product>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"
+            SELECT p.*, t.base_currency
+            FROM products p
+            JOIN tenants t ON t.id = p.tenant_id
+            WHERE p.tenant_id = $1 AND p.slug = $2 AND p.dt_deleted IS NULL
+              AND ($3 = false OR p.is_active = true)
+            "#,
+            tenant_id,
+            slug,
+            only_active
+        )
+        .fetch_optional(&self.app_state.db)
+        .await?;
+
+        row.map(|row| {
+            Ok(Product {
+                id: row.id,
+                tenant_id: row.tenant_id,
+                name: row.name,
+                slug: row.slug,
+                sku: row.sku,
+                short_description: row.short_description,
+                description: row.description,
+                price: base_price(&row.price, &row.base_currency)?,
+                stock_quantity: row.stock_quantity,
+                attributes: row.attributes,
+                is_active: row.is_active,
+                dt_created: DateTime::from_naive_utc_and_offset(row.dt_created, Utc),
+                dt_updated: DateTime::from_naive_utc_and_offset(row.dt_updated, Utc),
                 dt_deleted: row
-                    .get::<Option<NaiveDateTime>, _>("dt_deleted")
+                    .dt_deleted
                     .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
             })
-            .collect();
+        })
+        .transpose()
+    }
 
-        Ok(products)
+    /// Produto do tenant para o upsert da importação: pelo SKU e, sem SKU correspondente, pelo slug.
+    /// Inclui inativos, que também são atualizados pela planilha.
+    #[instrument(name = "ProductRepository::find_import_match", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_import_match(
+        &self,
+        tenant_id: Uuid,
+        sku: Option<&str>,
+        slug: Option<&str>,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT id FROM products
+            WHERE tenant_id = $1 AND dt_deleted IS NULL
+              AND (sku = $2 OR slug = $3)
+            ORDER BY (sku IS NOT DISTINCT FROM $2) DESC
+            LIMIT 1
+            "#,
+            tenant_id,
+            sku,
+            slug
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
     }
 
+    /// Lote da exportação em ordem estável (dt_created, id), a partir do último item do lote anterior
+    #[instrument(name = "ProductRepository::find_for_export", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_for_export(
+        &self,
+        tenant_id: Uuid,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> Result<Vec<Product>, sqlx::Error> {
+        let (after_created, after_id) = after
+            .map(|(dt_created, id)| (Some(dt_created.naive_utc()), Some(id)))
+            .unwrap_or((None, None));
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT p.*, t.base_currency
+            FROM products p
+            JOIN tenants t ON t.id = p.tenant_id
+            WHERE p.tenant_id = $1 AND p.dt_deleted IS NULL
+              AND ($2::timestamp IS NULL OR (p.dt_created, p.id) > ($2, $3::uuid))
+            ORDER BY p.dt_created, p.id
+            LIMIT $4
+            "#,
+            tenant_id,
+            after_created,
+            after_id,
+            limit
+        )
+        .fetch_all(&self.app_state.db)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(Product {
+                    id: row.id,
+                    tenant_id: row.tenant_id,
+                    name: row.name,
+                    slug: row.slug,
+                    sku: row.sku,
+                    short_description: row.short_description,
+                    description: row.description,
+                    price: base_price(&row.price, &row.base_currency)?,
+                    stock_quantity: row.stock_quantity,
+                    attributes: row.attributes,
+                    is_active: row.is_active,
+                    dt_created: DateTime::from_naive_utc_and_offset(row.dt_created, Utc),
+                    dt_updated: DateTime::from_naive_utc_and_offset(row.dt_updated, Utc),
+                    dt_deleted: row
+                        .dt_deleted
+                        .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+                })
+            })
+            .collect()
+    }
+
+    /// Slug atual do produto que já usou `slug` (histórico de renomeações)
+    #[instrument(name = "ProductRepository::find_slug_redirect", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_slug_redirect(
+        &self,
+        tenant_id: Uuid,
+        slug: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT p.slug
+            FROM product_slug_history h
+            JOIN products p ON p.id = h.product_id
+            WHERE h.tenant_id = $1 AND h.slug = $2
+              AND p.dt_deleted IS NULL AND p.is_active = true
+            "#,
+            tenant_id,
+            slug
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    /// Slugs do tenant que colidem com `base` ("base" e "base-N"), ativos ou no histórico.
+    /// Os do próprio produto (`except`) ficam de fora: ele pode recuperar um slug antigo.
+    #[instrument(name = "ProductRepository::find_taken_slugs", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_taken_slugs(
+        &self,
+        tenant_id: Uuid,
+        base: &str,
+        except: Option<Uuid>,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let pattern = format!("{}-%", base);
+
+        let slugs = sqlx::query_scalar!(
+            r#"
+            SELECT slug AS "slug!" FROM products
+            WHERE tenant_id = $1 AND dt_deleted IS NULL
+              AND (slug = $2 OR slug LIKE $3)
+              AND ($4::uuid IS NULL OR id <> $4)
+            UNION
+            SELECT slug AS "slug!" FROM product_slug_history
+            WHERE tenant_id = $1
+              AND (slug = $2 OR slug LIKE $3)
+              AND ($4::uuid IS NULL OR product_id <> $4)
+            "#,
+            tenant_id,
+            base,
+            pattern,
+            except
+        )
+        .fetch_all(&self.app_state.db)
+        .await?;
+
+        Ok(slugs)
+    }
+
+    #[instrument(name = "ProductRepository::create", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn create(
         &self,
         _request: CreateProductRequest,
         tenant_id: Uuid,
+        currency: &str,
+        slug: &str,
+        changed_by: Option<Uuid>,
     ) -> Result<Product, sqlx::Error> {
         let id = Uuid::new_v4();
         let now = Utc::now();
-        let price_bd = cents_to_bigdecimal(_request.price);
+        let price = Money::new(_request.price, currency);
+        let price_bd = price.to_decimal();
+        let mut tx = self.app_state.db.begin().await?;
 
         let row = sqlx::query!(
             r#"
-            INSERT INTO products (id,tenant_id,name,slug,short_description,description,price,stock_quantity,attributes,is_active,dt_created,dt_updated)
-            VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12)
-            RETURNING id, tenant_id, name, slug, short_description, description, price, stock_quantity, attributes, is_active, dt_created, dt_updated, dt_deleted
+            INSERT INTO products (id,tenant_id,name,slug,sku,short_description,description,price,stock_quantity,attributes,is_active,dt_created,dt_updated)
+            VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13)
+            RETURNING id, tenant_id, name, slug, sku, short_description, description, price, stock_quantity, attributes, is_active, dt_created, dt_updated, dt_deleted
             "#,
             id,
             tenant_id,
             _request.name,
-            _request.name,
+            slug,
+            _request.sku,
             _request.short_description,
             _request.description,
             price_bd,
@@ -262,17 +712,25 @@ impl<'a> ProductRepository<'a> {
             now.naive_utc(),
             now.naive_utc()
         )
-        .fetch_one(&self.app_state.db)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // Preço inicial abre o histórico
+        ProductPriceRepository::record_base_price(
+            &mut tx, tenant_id, row.id, &row.price, changed_by, now,
+        )
         .await?;
+        tx.commit().await?;
 
         Ok(Product {
             id: row.id,
             tenant_id: row.tenant_id,
             name: row.name,
             slug: row.slug,
+            sku: row.sku,
             short_description: row.short_description,
             description: row.description,
-            price: row.price,
+            price,
             stock_quantity: row.stock_quantity,
             attributes: row.attributes,
             is_active: row.is_active,
@@ -284,13 +742,47 @@ impl<'a> ProductRepository<'a> {
         })
     }
 
+    #[instrument(name = "ProductRepository::update", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn update(
         &self,
         id: Uuid,
         tenant_id: Uuid,
+        currency: &str,
         request: UpdateProductRequest,
+        slug: Option<&str>,
+        changed_by: Option<Uuid>,
     ) -> Result<Option<Product>, sqlx::Error> {
-        let now = Utc::now().naive_utc();
+        let changed_at = Utc::now();
+        let now = changed_at.naive_utc();
+        let mut tx = self.app_state.db.begin().await?;
+
+        // Preço anterior, para registrar no histórico só quando ele muda
+        let previous_price = match request.price {
+            Some(_) => {
+                sqlx::query_scalar!(
+                    "SELECT price FROM products WHERE id = $1 AND tenant_id = $2 AND dt_deleted IS NULL FOR UPDATE",
+                    id,
+                    tenant_id
+                )
+                .fetch_optional(&mut *tx)
+                .await?
+            }
+            None => None,
+        };
+
+        // Slug anterior vai para o histórico quando o produto é renomeado
+        let previous_slug = match slug {
+            Some(_) => {
+                sqlx::query_scalar!(
+                    "SELECT slug FROM products WHERE id = $1 AND tenant_id = $2 AND dt_deleted IS NULL FOR UPDATE",
+                    id,
+                    tenant_id
+                )
+                .fetch_optional(&mut *tx)
+                .await?
+            }
+            None => None,
+        };
 
         let mut qb: QueryBuilder<Postgres> = QueryBuilder::new("UPDATE products SET ");
         let mut first = true;
@@ -313,6 +805,14 @@ impl<'a> ProductRepository<'a> {
             push_set(&mut qb, "name = ");
             qb.push_bind(name);
         }
+        if let Some(slug) = slug {
+            push_set(&mut qb, "slug = ");
+            qb.push_bind(slug);
+        }
+        if let Some(sku) = request.sku {
+            push_set(&mut qb, "sku = ");
+            qb.push_bind(sku);
+        }
         if let Some(short_description) = request.short_description {
             push_set(&mut qb, "short_description = ");
             qb.push_bind(short_description);
@@ -322,7 +822,7 @@ impl<'a> ProductRepository<'a> {
             qb.push_bind(description);
         }
         if let Some(price_cents) = request.price {
-            let bd = cents_to_bigdecimal(price_cents);
+            let bd = Money::new(price_cents, currency).to_decimal();
             push_set(&mut qb, "price = ");
             qb.push_bind(bd);
         }
@@ -344,32 +844,78 @@ impl<'a> ProductRepository<'a> {
         qb.push(" AND dt_deleted IS NULL");
         qb.push(
             " RETURNING
-            id, tenant_id, name, slug, short_description, description,
+            id, tenant_id, name, slug, sku, short_description, description,
             price, stock_quantity, attributes, is_active,
             dt_created, dt_updated, dt_deleted",
         );
 
-        let row_opt = qb.build().fetch_optional(&self.app_state.db).await?;
-
-        Ok(row_opt.map(|row| Product {
-            id: row.get("id"),
-            tenant_id: row.get("tenant_id"),
-            name: row.get("name"),
-            slug: row.get("slug"),
-            short_description: row.get("short_description"),
-            description: row.get("description"),
-            price: row.get("price"),
-            stock_quantity: row.get("stock_quantity"),
-            attributes: row.get("attributes"),
-            is_active: row.get("is_active"),
-            dt_created: DateTime::from_naive_utc_and_offset(row.get("dt_created"), Utc),
-            dt_updated: DateTime::from_naive_utc_and_offset(row.get("dt_updated"), Utc),
-            dt_deleted: row
-                .get::<Option<NaiveDateTime>, _>("dt_deleted")
-                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
-        }))
+        let row_opt = qb.build().fetch_optional(&mut *tx).await?;
+
+        if let (Some(_), Some(previous), Some(current)) = (&row_opt, previous_slug, slug)
+            && previous != current
+        {
+            sqlx::query!(
+                r#"
+                INSERT INTO product_slug_history (id, tenant_id, product_id, slug, dt_created)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (tenant_id, slug) DO UPDATE SET product_id = EXCLUDED.product_id
+                "#,
+                Uuid::new_v4(),
+                tenant_id,
+                id,
+                previous,
+                now
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            // O slug voltou a ser o atual: não é mais um redirect
+            sqlx::query!(
+                "DELETE FROM product_slug_history WHERE tenant_id = $1 AND slug = $2",
+                tenant_id,
+                current
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        if let (Some(row), Some(previous)) = (&row_opt, previous_price) {
+            let price: BigDecimal = row.get("price");
+            if price != previous {
+                ProductPriceRepository::record_base_price(
+                    &mut tx, tenant_id, id, &price, changed_by, changed_at,
+                )
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        row_opt
+            .map(|row| {
+                Ok(Product {
+                    id: row.get("id"),
+                    tenant_id: row.get("tenant_id"),
+                    name: row.get("name"),
+                    slug: row.get("slug"),
+                    sku: row.get("sku"),
+                    short_description: row.get("short_description"),
+                    description: row.get("description"),
+                    price: base_price(&row.get("price"), currency)?,
+                    stock_quantity: row.get("stock_quantity"),
+                    attributes: row.get("attributes"),
+                    is_active: row.get("is_active"),
+                    dt_created: DateTime::from_naive_utc_and_offset(row.get("dt_created"), Utc),
+                    dt_updated: DateTime::from_naive_utc_and_offset(row.get("dt_updated"), Utc),
+                    dt_deleted: row
+                        .get::<Option<NaiveDateTime>, _>("dt_deleted")
+                        .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+                })
+            })
+            .transpose()
     }
 
+    #[instrument(name = "ProductRepository::delete", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn delete(&self, id: Uuid, tenant_id: Uuid) -> Result<bool, sqlx::Error> {
         let exists = sqlx::query!(
             "SELECT * FROM products WHERE id = $1 AND tenant_id = $2 AND dt_deleted IS NULL AND is_active = true",
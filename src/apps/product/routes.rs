@@ -1,7 +1,12 @@
 use crate::app_core::app_extensions::RequestUserExt;
 use crate::app_core::app_state::AppState;
-use crate::apps::product::models::{CreateProductRequest, ProductListParams, UpdateProductRequest};
+use crate::apps::audit::models::AuditContext;
+use crate::apps::product::filters::attribute_filters_from_query;
+use crate::apps::product::models::{
+    CreateProductRequest, ProductListParams, ProductSlugLookup, UpdateProductRequest,
+};
 use crate::{app_core::app_error::AppError, apps::product::services::ProductService};
+use actix_web::http::header;
 use actix_web::web::Json;
 use actix_web::{HttpRequest, HttpResponse, Responder, web};
 
@@ -10,9 +15,12 @@ use uuid::Uuid;
 pub async fn list_products(
     app_state: web::Data<AppState>,
     query: web::Query<ProductListParams>,
+    req: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    let params = query.into_inner();
-    let result = ProductService::list_products(&app_state, params).await?;
+    let tenant_id = req.tenant_id()?;
+    let mut params = query.into_inner();
+    params.attribute_filters = attribute_filters_from_query(req.query_string())?;
+    let result = ProductService::list_products(&app_state, tenant_id, params).await?;
 
     Ok(HttpResponse::Ok().json(serde_json::json!(result)))
 }
@@ -20,21 +28,45 @@ pub async fn list_products(
 pub async fn get_product(
     app_state: web::Data<AppState>,
     path: web::Path<Uuid>,
+    req: HttpRequest,
 ) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
-    let result = ProductService::get_product(&app_state, id).await?;
+    let tenant_id = req.tenant_id()?;
+    let result = ProductService::get_product(&app_state, id, tenant_id).await?;
 
     Ok(HttpResponse::Ok().json(serde_json::json!(result)))
 }
 
+/// GET /products/by-slug/{slug}/ - slug antigo responde 301 para o slug atual
+pub async fn get_product_by_slug(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let slug = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let result = ProductService::get_product_by_slug(&app_state, tenant_id, &slug).await?;
+
+    Ok(match result {
+        ProductSlugLookup::Found(product) => HttpResponse::Ok().json(serde_json::json!(product)),
+        ProductSlugLookup::Moved(current) => HttpResponse::MovedPermanently()
+            .insert_header((
+                header::LOCATION,
+                format!("/api/v1/products/by-slug/{}/", current),
+            ))
+            .json(serde_json::json!({ "slug": current })),
+    })
+}
+
 pub async fn create_product(
     app_state: web::Data<AppState>,
     payload: Json<CreateProductRequest>,
     req: HttpRequest,
 ) -> Result<impl Responder, AppError> {
     let tenant_id = req.tenant_id()?;
+    let audit = AuditContext::from_request(&req);
     let dto = payload.into_inner();
-    let result = ProductService::create_product(&app_state, dto, tenant_id).await?;
+    let result = ProductService::create_product(&app_state, dto, tenant_id, &audit).await?;
 
     Ok(HttpResponse::Created().json(serde_json::json!(result)))
 }
@@ -47,8 +79,9 @@ pub async fn update_product(
 ) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
     let tenant_id = req.tenant_id()?;
+    let audit = AuditContext::from_request(&req);
     let dto = payload.into_inner();
-    let result = ProductService::update_product(&app_state, id, tenant_id, dto).await?;
+    let result = ProductService::update_product(&app_state, id, tenant_id, dto, &audit).await?;
 
     Ok(HttpResponse::Ok().json(serde_json::json!(result)))
 }
@@ -60,9 +93,42 @@ pub async fn delete_product(
 ) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
     let tenant_id = req.tenant_id()?;
-    ProductService::delete_product(&app_state, id, tenant_id)
-        .await
-        .map_err(AppError::from)?;
+    let audit = AuditContext::from_request(&req);
+    ProductService::delete_product(&app_state, id, tenant_id, &audit).await?;
 
     Ok(HttpResponse::NoContent().finish())
 }
+
+/// GET /store/{tenant_slug}/products/ - vitrine pública, só produtos ativos
+pub async fn list_store_products(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<ProductListParams>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_slug = path.into_inner();
+    let mut params = query.into_inner();
+    params.attribute_filters = attribute_filters_from_query(req.query_string())?;
+    let result = ProductService::list_store_products(&app_state, &tenant_slug, params).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// GET /store/{tenant_slug}/products/{slug}/ - slug antigo responde 301 para o slug atual
+pub async fn get_store_product(
+    app_state: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+) -> Result<impl Responder, AppError> {
+    let (tenant_slug, slug) = path.into_inner();
+    let result = ProductService::get_store_product_by_slug(&app_state, &tenant_slug, &slug).await?;
+
+    Ok(match result {
+        ProductSlugLookup::Found(product) => HttpResponse::Ok().json(serde_json::json!(product)),
+        ProductSlugLookup::Moved(current) => HttpResponse::MovedPermanently()
+            .insert_header((
+                header::LOCATION,
+                format!("/api/v1/store/{}/products/{}/", tenant_slug, current),
+            ))
+            .json(serde_json::json!({ "slug": current })),
+    })
+}
@@ -1,67 +1,358 @@
 use crate::app_core::app_error::AppError;
 use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::{AuditAction, AuditContext, AuditEntity};
+use crate::apps::audit::services::AuditService;
+use crate::apps::category::services::CategoryService;
+use crate::apps::product::filters::{DEFAULT_SORT, parse_sort};
 use crate::apps::product::models::{
-    CreateProductRequest, Product, ProductListParams, UpdateProductRequest,
+    CatalogView, CreateProductRequest, Product, ProductListParams, ProductSlugLookup,
+    ProductWithCategories, UpdateProductRequest,
 };
 use crate::apps::product::repositories::ProductRepository;
-use crate::utils::pagination::PaginatedResponse;
+use crate::apps::product_price::services::ProductPriceService;
+use crate::apps::tenant::repositories::TenantRepository;
+use crate::apps::tenant::services::TenantService;
+use crate::utils::formatter::{slugify, unique_slug};
+use crate::utils::pagination::{Page, PageRequest};
 use uuid::Uuid;
 
+/// Slug usado quando o nome não tem nenhum caractere aproveitável
+const FALLBACK_SLUG: &str = "produto";
+
+/// Tentativas de gerar um slug livre quando outra requisição leva o mesmo antes
+const SLUG_ATTEMPTS: usize = 3;
+
+/// Índice único do SKU por tenant; as demais violações de unicidade são de slug
+const SKU_CONSTRAINT: &str = "uq_products_tenant_sku";
+
+/// Tamanho máximo do SKU
+pub const SKU_MAX_LENGTH: usize = 64;
+
+fn is_unique_violation(e: &sqlx::Error) -> bool {
+    e.as_database_error()
+        .and_then(|db| db.code())
+        .is_some_and(|code| code == "23505")
+}
+
+fn is_sku_violation(e: &sqlx::Error) -> bool {
+    e.as_database_error()
+        .and_then(|db| db.constraint())
+        .is_some_and(|constraint| constraint == SKU_CONSTRAINT)
+}
+
+/// SKU sem espaços nas pontas; vazio equivale a não informado
+pub fn normalize_sku(sku: Option<String>) -> Result<Option<String>, AppError> {
+    let Some(sku) = sku.map(|s| s.trim().to_string()).filter(|s| !s.is_empty()) else {
+        return Ok(None);
+    };
+
+    if sku.chars().count() > SKU_MAX_LENGTH || sku.chars().any(char::is_whitespace) {
+        return Err(AppError::bad_request(format!(
+            "SKU inválido: use até {} caracteres, sem espaços",
+            SKU_MAX_LENGTH
+        )));
+    }
+    Ok(Some(sku))
+}
+
 pub struct ProductService;
 
 impl ProductService {
+    /// Catálogo do próprio tenant, inclusive inativos (e excluídos com `include_deleted`)
     pub async fn list_products(
         app_state: &AppState,
+        tenant_id: Uuid,
+        params: ProductListParams,
+    ) -> Result<Page<ProductWithCategories>, AppError> {
+        Self::list_catalog(app_state, tenant_id, params, CatalogView::Admin).await
+    }
+
+    /// Vitrine pública: só produtos ativos da loja
+    pub async fn list_store_products(
+        app_state: &AppState,
+        tenant_slug: &str,
         params: ProductListParams,
-    ) -> Result<PaginatedResponse<Product>, AppError> {
+    ) -> Result<Page<ProductWithCategories>, AppError> {
+        let tenant_id = Self::store_tenant_id(app_state, tenant_slug).await?;
+        Self::list_catalog(app_state, tenant_id, params, CatalogView::Storefront).await
+    }
+
+    async fn list_catalog(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        mut params: ProductListParams,
+        view: CatalogView,
+    ) -> Result<Page<ProductWithCategories>, AppError> {
+        params.currency = TenantService::base_currency(app_state, tenant_id).await?;
+        let page = PageRequest::new(
+            params.limit.unwrap_or(20),
+            params.offset,
+            params.cursor.as_deref(),
+            params.include_count,
+        )?;
+
+        let sort = match params.sort.as_deref().map(str::trim) {
+            Some(sort) if !sort.is_empty() => parse_sort(sort)?,
+            _ => DEFAULT_SORT.to_vec(),
+        };
+        // O cursor guarda a posição em (dt_created, id); outra ordenação só com offset
+        if matches!(page, PageRequest::Cursor { .. }) && sort != DEFAULT_SORT {
+            return Err(AppError::bad_request(
+                "Paginação por cursor aceita apenas a ordenação padrão (-created)",
+            ));
+        }
+
         let repository = ProductRepository::new(app_state);
-        let products_paginated = repository
-            .find_all(params)
+        let products = repository
+            .find_all(tenant_id, params, view, &sort, &page)
             .await
             .map_err(|e| AppError::database_error(e.to_string()))?;
 
-        Ok(products_paginated)
+        let ids: Vec<Uuid> = products.results().iter().map(|p| p.id).collect();
+        let mut categories = CategoryService::categories_for_products(app_state, &ids).await?;
+        let prices = ProductPriceService::effective_prices(app_state, products.results()).await?;
+
+        Ok(products.map_results(|products| {
+            products
+                .into_iter()
+                .zip(prices)
+                .map(|(product, pricing)| ProductWithCategories {
+                    categories: categories.remove(&product.id).unwrap_or_default(),
+                    pricing,
+                    product,
+                })
+                .collect()
+        }))
     }
 
-    pub async fn get_product(app_state: &AppState, id: Uuid) -> Result<Product, AppError> {
+    /// Produto do tenant em qualquer estado (inativo ou excluído)
+    pub async fn get_product(
+        app_state: &AppState,
+        id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<ProductWithCategories, AppError> {
         let repository = ProductRepository::new(app_state);
         let product = repository
-            .find_by_id(id)
+            .find_in_tenant(id, tenant_id)
             .await
             .map_err(|e| AppError::database_error(e.to_string()))?;
 
         match product {
-            Some(product) => Ok(product),
+            Some(product) => Ok(Self::with_categories(app_state, vec![product])
+                .await?
+                .remove(0)),
+            None => Err(AppError::not_found("Produto não encontrado")),
+        }
+    }
+
+    /// Busca pelo slug no tenant, inclusive inativos; slugs antigos apontam para o slug atual
+    pub async fn get_product_by_slug(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        slug: &str,
+    ) -> Result<ProductSlugLookup, AppError> {
+        Self::lookup_by_slug(app_state, tenant_id, slug, false).await
+    }
+
+    /// Produto ativo da vitrine pelo slug; slugs antigos apontam para o slug atual
+    pub async fn get_store_product_by_slug(
+        app_state: &AppState,
+        tenant_slug: &str,
+        slug: &str,
+    ) -> Result<ProductSlugLookup, AppError> {
+        let tenant_id = Self::store_tenant_id(app_state, tenant_slug).await?;
+        Self::lookup_by_slug(app_state, tenant_id, slug, true).await
+    }
+
+    async fn lookup_by_slug(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        slug: &str,
+        only_active: bool,
+    ) -> Result<ProductSlugLookup, AppError> {
+        let repository = ProductRepository::new(app_state);
+        let product = match only_active {
+            true => repository.find_by_slug(tenant_id, slug).await,
+            false => repository.find_by_slug_any_status(tenant_id, slug).await,
+        }
+        .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        if let Some(product) = product {
+            let product = Self::with_categories(app_state, vec![product])
+                .await?
+                .remove(0);
+            return Ok(ProductSlugLookup::Found(Box::new(product)));
+        }
+
+        let current = repository
+            .find_slug_redirect(tenant_id, slug)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        match current {
+            Some(current) => Ok(ProductSlugLookup::Moved(current)),
             None => Err(AppError::not_found("Produto não encontrado")),
         }
     }
 
+    /// Tenant da loja pelo slug público
+    async fn store_tenant_id(app_state: &AppState, tenant_slug: &str) -> Result<Uuid, AppError> {
+        TenantRepository::new(app_state)
+            .find_by_slug(tenant_slug)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .map(|tenant| tenant.id)
+            .ok_or_else(|| AppError::not_found("Loja não encontrada"))
+    }
+
+    /// Gera o slug a partir do nome, com sufixo numérico se já estiver em uso no tenant
+    async fn available_slug(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        name: &str,
+        product_id: Option<Uuid>,
+    ) -> Result<String, AppError> {
+        let base = match slugify(name) {
+            slug if slug.is_empty() => FALLBACK_SLUG.to_string(),
+            slug => slug,
+        };
+
+        let taken = ProductRepository::new(app_state)
+            .find_taken_slugs(tenant_id, &base, product_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        Ok(unique_slug(&base, &taken))
+    }
+
+    /// Anexa as categorias (com breadcrumbs) a cada produto
+    async fn with_categories(
+        app_state: &AppState,
+        products: Vec<Product>,
+    ) -> Result<Vec<ProductWithCategories>, AppError> {
+        let ids: Vec<Uuid> = products.iter().map(|p| p.id).collect();
+        let mut categories = CategoryService::categories_for_products(app_state, &ids).await?;
+        let prices = ProductPriceService::effective_prices(app_state, &products).await?;
+
+        Ok(products
+            .into_iter()
+            .zip(prices)
+            .map(|(product, pricing)| ProductWithCategories {
+                categories: categories.remove(&product.id).unwrap_or_default(),
+                pricing,
+                product,
+            })
+            .collect())
+    }
+
     pub async fn create_product(
         app_state: &AppState,
-        request: CreateProductRequest,
+        mut request: CreateProductRequest,
         tenant_id: Uuid,
+        audit: &AuditContext,
     ) -> Result<Product, AppError> {
+        request.sku = normalize_sku(request.sku)?;
+        let currency = TenantService::base_currency(app_state, tenant_id).await?;
         let repository = ProductRepository::new(app_state);
-        repository
-            .create(request, tenant_id)
-            .await
-            .map_err(|e| AppError::database_error(e.to_string()))
+
+        // O índice único decide colisões concorrentes: recalcula o sufixo e tenta de novo
+        let mut attempt = 1;
+        let product = loop {
+            let slug = Self::available_slug(app_state, tenant_id, &request.name, None).await?;
+            match repository
+                .create(request.clone(), tenant_id, &currency, &slug, audit.actor_id)
+                .await
+            {
+                Ok(product) => break product,
+                Err(e) if is_sku_violation(&e) => {
+                    return Err(AppError::Conflict(Some(
+                        "Já existe um produto com este SKU".into(),
+                    )));
+                }
+                Err(e) if is_unique_violation(&e) && attempt < SLUG_ATTEMPTS => attempt += 1,
+                Err(e) if is_unique_violation(&e) => {
+                    return Err(AppError::Conflict(Some(
+                        "Já existe um produto com este slug".into(),
+                    )));
+                }
+                Err(e) => return Err(AppError::database_error(e.to_string())),
+            }
+        };
+
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Create,
+            AuditEntity::Product,
+            Some(product.id),
+            None,
+            AuditService::snapshot(&product),
+        )
+        .await;
+
+        Ok(product)
     }
 
     pub async fn update_product(
         app_state: &AppState,
         id: Uuid,
         tenant_id: Uuid,
-        request: UpdateProductRequest,
+        mut request: UpdateProductRequest,
+        audit: &AuditContext,
     ) -> Result<Product, AppError> {
+        request.sku = normalize_sku(request.sku)?;
+        let currency = TenantService::base_currency(app_state, tenant_id).await?;
         let repository = ProductRepository::new(app_state);
+        let before = repository
+            .find_in_tenant(id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .filter(|product| product.dt_deleted.is_none());
+
+        // Renomear regenera o slug; o anterior fica no histórico para redirect
+        let slug = match (&before, &request.name) {
+            (Some(before), Some(name)) if *name != before.name => {
+                Some(Self::available_slug(app_state, tenant_id, name, Some(id)).await?)
+            }
+            _ => None,
+        }
+        .filter(|slug| before.as_ref().is_some_and(|b| b.slug != *slug));
+
         let product = repository
-            .update(id, tenant_id, request)
+            .update(
+                id,
+                tenant_id,
+                &currency,
+                request,
+                slug.as_deref(),
+                audit.actor_id,
+            )
             .await
-            .map_err(|e| AppError::database_error(e.to_string()))?;
+            .map_err(|e| {
+                if is_sku_violation(&e) {
+                    AppError::Conflict(Some("Já existe um produto com este SKU".into()))
+                } else if is_unique_violation(&e) {
+                    AppError::Conflict(Some("Já existe um produto com este slug".into()))
+                } else {
+                    AppError::database_error(e.to_string())
+                }
+            })?;
 
         match product {
-            Some(product) => Ok(product),
+            Some(product) => {
+                AuditService::record(
+                    app_state,
+                    audit,
+                    AuditAction::Update,
+                    AuditEntity::Product,
+                    Some(product.id),
+                    before.as_ref().and_then(AuditService::snapshot),
+                    AuditService::snapshot(&product),
+                )
+                .await;
+
+                Ok(product)
+            }
             None => Err(AppError::not_found("Produto não encontrado")),
         }
     }
@@ -70,15 +361,34 @@ impl ProductService {
         app_state: &AppState,
         id: Uuid,
         tenant_id: Uuid,
+        audit: &AuditContext,
     ) -> Result<bool, AppError> {
         let repository = ProductRepository::new(app_state);
+        let before = repository
+            .find_in_tenant(id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
         let deleted = repository
             .delete(id, tenant_id)
             .await
             .map_err(|e| AppError::database_error(e.to_string()))?;
 
         match deleted {
-            true => Ok(deleted),
+            true => {
+                AuditService::record(
+                    app_state,
+                    audit,
+                    AuditAction::Delete,
+                    AuditEntity::Product,
+                    Some(id),
+                    before.as_ref().and_then(AuditService::snapshot),
+                    None,
+                )
+                .await;
+
+                Ok(deleted)
+            }
             false => Err(AppError::not_found("Produto não encontrado")),
         }
     }
@@ -1,12 +1,18 @@
 #[cfg(test)]
 mod tests {
     use crate::app_core::app_error::AppError;
+    use crate::apps::product::filters::{
+        AttributeCondition, RangeOp, SortField, SortKey, parse_attribute_filters, parse_sort,
+    };
     use crate::apps::product::models::{
         CreateProductRequest, Product, ProductListParams, UpdateProductRequest,
     };
+    use crate::utils::formatter::{slugify, unique_slug};
+    use crate::utils::money::Money;
     use bigdecimal::BigDecimal;
     use chrono::Utc;
     use serde_json::json;
+    use std::str::FromStr;
     use uuid::Uuid;
 
     // ===== TESTES UNITÁRIOS DE MODELS =====
@@ -19,9 +25,10 @@ mod tests {
             tenant_id: Uuid::new_v4(),
             name: "Test Product".to_string(),
             slug: "test-product".to_string(),
+            sku: None,
             short_description: Some("A test product".to_string()),
             description: Some("This is a detailed description".to_string()),
-            price: BigDecimal::from(9999), // 99.99
+            price: Money::new(9999, "BRL"), // 99.99
             stock_quantity: 100,
             attributes: Some(json!({"color": "red", "size": "M"})),
             is_active: true,
@@ -34,13 +41,14 @@ mod tests {
         assert_eq!(product.slug, "test-product");
         assert_eq!(product.stock_quantity, 100);
         assert!(product.is_active);
-        assert_eq!(product.price, BigDecimal::from(9999));
+        assert_eq!(product.price, Money::new(9999, "BRL"));
     }
 
     #[test]
     fn test_create_product_request_validation() {
         let valid_request = CreateProductRequest {
             name: "Valid Product".to_string(),
+            sku: None,
             short_description: Some("Short desc".to_string()),
             description: Some("Long description".to_string()),
             price: 9999, // 99.99
@@ -60,6 +68,7 @@ mod tests {
     fn test_update_product_request_optional_fields() {
         let update_request = UpdateProductRequest {
             name: Some("Updated Name".to_string()),
+            sku: None,
             short_description: None, // Campo opcional não preenchido
             description: Some("Updated description".to_string()),
             price: Some(14999),   // 149.99
@@ -83,6 +92,15 @@ mod tests {
             limit: Some(20),
             offset: Some(0),
             is_active: Some(true),
+            category_id: None,
+            include_deleted: None,
+            cursor: None,
+            include_count: None,
+            sort: None,
+            in_stock: None,
+            updated_since: None,
+            attribute_filters: Vec::new(),
+            currency: "BRL".to_string(),
         };
 
         assert_eq!(params.name, Some("test".to_string()));
@@ -100,6 +118,7 @@ mod tests {
         // Teste de validação de nome vazio
         let invalid_request = CreateProductRequest {
             name: "".to_string(), // Nome vazio
+            sku: None,
             short_description: Some("A test product".to_string()),
             description: Some("This is a detailed description".to_string()),
             price: 9999,
@@ -118,6 +137,7 @@ mod tests {
         // Teste de preço negativo
         let negative_price_request = CreateProductRequest {
             name: "Test Product".to_string(),
+            sku: None,
             short_description: Some("A test product".to_string()),
             description: Some("This is a detailed description".to_string()),
             price: -1000, // Preço negativo
@@ -135,6 +155,7 @@ mod tests {
         // Teste de estoque negativo
         let negative_stock_request = CreateProductRequest {
             name: "Test Product".to_string(),
+            sku: None,
             short_description: Some("A test product".to_string()),
             description: Some("This is a detailed description".to_string()),
             price: 9999,
@@ -155,6 +176,7 @@ mod tests {
         // Teste de request válido
         let valid_request = CreateProductRequest {
             name: "Valid Product".to_string(),
+            sku: None,
             short_description: Some("A valid product".to_string()),
             description: Some("This is a valid description".to_string()),
             price: 9999,
@@ -190,6 +212,7 @@ mod tests {
         for name in valid_names {
             let request = CreateProductRequest {
                 name: name.to_string(),
+                sku: None,
                 short_description: Some("Description".to_string()),
                 description: Some("Long description".to_string()),
                 price: 9999,
@@ -203,6 +226,7 @@ mod tests {
         for name in invalid_names {
             let request = CreateProductRequest {
                 name: name.to_string(),
+                sku: None,
                 short_description: Some("Description".to_string()),
                 description: Some("Long description".to_string()),
                 price: 9999,
@@ -227,6 +251,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_product_slug_transliterates_portuguese() {
+        assert_eq!(slugify("Pão de Açúcar"), "pao-de-acucar");
+        assert_eq!(
+            slugify("CAMISETA Básica — Edição Nº 2"),
+            "camiseta-basica-edicao-no-2"
+        );
+        assert_eq!(slugify("  Óculos  à  Vênus!! "), "oculos-a-venus");
+        assert_eq!(slugify("Coração & Maçã"), "coracao-maca");
+        assert_eq!(slugify("???"), "");
+    }
+
+    #[test]
+    fn test_product_slug_collision_suffix() {
+        let taken = |slugs: &[&str]| slugs.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(unique_slug("camiseta", &taken(&[])), "camiseta");
+        assert_eq!(unique_slug("camiseta", &taken(&["camiseta-2"])), "camiseta");
+        assert_eq!(unique_slug("camiseta", &taken(&["camiseta"])), "camiseta-2");
+        assert_eq!(
+            unique_slug(
+                "camiseta",
+                &taken(&["camiseta", "camiseta-2", "camiseta-4"])
+            ),
+            "camiseta-3"
+        );
+    }
+
     #[test]
     fn test_product_price_validation() {
         let valid_prices = vec![0, 100, 9999, 100000, 999999];
@@ -236,6 +288,7 @@ mod tests {
         for price in valid_prices {
             let request = CreateProductRequest {
                 name: "Test Product".to_string(),
+                sku: None,
                 short_description: Some("Description".to_string()),
                 description: Some("Long description".to_string()),
                 price,
@@ -249,6 +302,7 @@ mod tests {
         for price in invalid_prices {
             let request = CreateProductRequest {
                 name: "Test Product".to_string(),
+                sku: None,
                 short_description: Some("Description".to_string()),
                 description: Some("Long description".to_string()),
                 price,
@@ -269,6 +323,7 @@ mod tests {
         for stock in valid_stocks {
             let request = CreateProductRequest {
                 name: "Test Product".to_string(),
+                sku: None,
                 short_description: Some("Description".to_string()),
                 description: Some("Long description".to_string()),
                 price: 9999,
@@ -286,6 +341,7 @@ mod tests {
         for stock in invalid_stocks {
             let request = CreateProductRequest {
                 name: "Test Product".to_string(),
+                sku: None,
                 short_description: Some("Description".to_string()),
                 description: Some("Long description".to_string()),
                 price: 9999,
@@ -301,6 +357,80 @@ mod tests {
         }
     }
 
+    // ===== TESTES DE ORDENAÇÃO E FILTROS =====
+
+    #[test]
+    fn test_parse_sort_whitelist() {
+        let keys = parse_sort("-price, name").unwrap();
+        assert_eq!(
+            keys,
+            vec![
+                SortKey {
+                    field: SortField::Price,
+                    descending: true
+                },
+                SortKey {
+                    field: SortField::Name,
+                    descending: false
+                },
+            ]
+        );
+        assert_eq!(keys[0].field.column(), "COALESCE(sp.price, p.price)");
+
+        for invalid in [
+            "",
+            "preco",
+            "price;DROP TABLE products",
+            "price,-price",
+            "price,name,created,updated,stock",
+        ] {
+            let error = parse_sort(invalid).unwrap_err();
+            assert_eq!(error.code(), "BAD_REQUEST", "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_parse_attribute_filters() {
+        let pairs: Vec<(String, String)> = [
+            ("name", "camiseta"),
+            ("attr.cor", "azul"),
+            ("attr.tamanho", "42"),
+            ("attr.cor", "verde"),
+            ("attr.peso.gte", "1.5"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let filters = parse_attribute_filters(&pairs).unwrap();
+        assert_eq!(filters.len(), 3);
+        assert_eq!(
+            filters[0].condition,
+            AttributeCondition::Equals(vec![json!("azul"), json!("verde")])
+        );
+        // Número na query casa tanto com texto quanto com número no JSON
+        assert_eq!(
+            filters[1].condition,
+            AttributeCondition::Equals(vec![json!("42"), json!(42)])
+        );
+        assert_eq!(filters[1].containment(&json!(42)), json!({ "tamanho": 42 }));
+        assert_eq!(
+            filters[2].condition,
+            AttributeCondition::Range(RangeOp::Gte, BigDecimal::from_str("1.5").unwrap())
+        );
+
+        for (key, value) in [
+            ("attr.cor'--", "azul"),
+            ("attr.", "azul"),
+            ("attr.peso.entre", "1"),
+            ("attr.peso.lt", "leve"),
+        ] {
+            let error =
+                parse_attribute_filters(&[(key.to_string(), value.to_string())]).unwrap_err();
+            assert_eq!(error.code(), "BAD_REQUEST", "{}", key);
+        }
+    }
+
     // ===== TESTES DE INTEGRAÇÃO SIMULADOS =====
 
     #[tokio::test]
@@ -335,6 +465,7 @@ mod tests {
     pub fn _create_valid_product_request() -> CreateProductRequest {
         CreateProductRequest {
             name: format!("Test Product {}", Uuid::new_v4()),
+            sku: None,
             short_description: Some("A test product for testing".to_string()),
             description: Some("This is a detailed description for testing purposes".to_string()),
             price: 9999, // 99.99
@@ -352,6 +483,7 @@ mod tests {
     pub fn _create_valid_update_request() -> UpdateProductRequest {
         UpdateProductRequest {
             name: Some("Updated Product Name".to_string()),
+            sku: None,
             short_description: Some("Updated short description".to_string()),
             description: Some("Updated long description".to_string()),
             price: Some(14999), // 149.99
@@ -373,6 +505,15 @@ mod tests {
             limit: Some(20),
             offset: Some(0),
             is_active: Some(true),
+            category_id: None,
+            include_deleted: None,
+            cursor: None,
+            include_count: None,
+            sort: None,
+            in_stock: None,
+            updated_since: None,
+            attribute_filters: Vec::new(),
+            currency: "BRL".to_string(),
         }
     }
 
@@ -384,9 +525,10 @@ mod tests {
             tenant_id: Uuid::new_v4(),
             name: "Test Product".to_string(),
             slug: "test-product".to_string(),
+            sku: None,
             short_description: Some("A test product".to_string()),
             description: Some("This is a detailed description".to_string()),
-            price: BigDecimal::from(9999),
+            price: Money::new(9999, "BRL"),
             stock_quantity: 100,
             attributes: Some(json!({"color": "red", "size": "M"})),
             is_active: true,
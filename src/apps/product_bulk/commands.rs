@@ -0,0 +1,80 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::AuditContext;
+use crate::apps::product_bulk::models::{ProductImportJob, TransferFormat};
+use crate::apps::product_bulk::services::ProductBulkService;
+use futures_util::StreamExt;
+use std::path::Path;
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+/// Identifica as ações feitas pela linha de comando na trilha de auditoria
+const CLI_USER_AGENT: &str = "cli";
+
+/// Formato explícito ou pela extensão do arquivo (.csv, .jsonl, .ndjson)
+fn resolve_format(format: Option<&str>, path: &Path) -> Result<TransferFormat, AppError> {
+    match format {
+        Some(format) => format.parse().map_err(AppError::bad_request),
+        None => path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| ext.parse().ok())
+            .ok_or_else(|| {
+                AppError::bad_request("Não foi possível deduzir o formato; use --format csv|jsonl")
+            }),
+    }
+}
+
+fn cli_audit(tenant_id: Uuid) -> AuditContext {
+    AuditContext {
+        tenant_id: Some(tenant_id),
+        user_agent: Some(CLI_USER_AGENT.to_string()),
+        ..Default::default()
+    }
+}
+
+/// Importa o arquivo na hora, qualquer que seja o tamanho (progresso vai para o log)
+pub async fn import_products(
+    app_state: &AppState,
+    tenant_id: Uuid,
+    file: &Path,
+    format: Option<&str>,
+) -> Result<ProductImportJob, AppError> {
+    let format = resolve_format(format, file)?;
+    let data = tokio::fs::read(file)
+        .await
+        .map_err(|e| AppError::bad_request(format!("Erro ao ler {}: {}", file.display(), e)))?;
+
+    let (job, rows) =
+        ProductBulkService::prepare_import(app_state, tenant_id, None, format, data).await?;
+    ProductBulkService::run_import(app_state, job, rows, &cli_audit(tenant_id), None).await
+}
+
+/// Grava o catálogo do tenant em `output`; devolve o número de bytes escritos
+pub async fn export_products(
+    app_state: &AppState,
+    tenant_id: Uuid,
+    output: &Path,
+    format: Option<&str>,
+) -> Result<u64, AppError> {
+    let format = resolve_format(format, output)?;
+    let write_error = |e: std::io::Error| {
+        AppError::internal(format!("Erro ao gravar {}: {}", output.display(), e))
+    };
+
+    let mut file = tokio::fs::File::create(output).await.map_err(write_error)?;
+    let state = AppState {
+        db: app_state.db.clone(),
+    };
+    let mut stream = Box::pin(ProductBulkService::export_stream(state, tenant_id, format));
+
+    let mut written = 0;
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        file.write_all(&chunk).await.map_err(write_error)?;
+        written += chunk.len() as u64;
+    }
+    file.flush().await.map_err(write_error)?;
+
+    Ok(written)
+}
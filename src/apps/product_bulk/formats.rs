@@ -0,0 +1,308 @@
+use crate::app_core::app_error::AppError;
+use crate::apps::product::models::Product;
+use crate::apps::product::services::normalize_sku;
+use crate::apps::product_bulk::models::{
+    COLUMNS, FieldError, ImportRow, RawRow, RowError, TransferFormat,
+};
+use crate::utils::csv;
+use serde_json::{Map, Value, json};
+
+/// Maior preço aceito pela coluna NUMERIC(12,2), em centavos
+const MAX_PRICE_CENTS: i64 = 999_999_999_999;
+
+const NAME_MAX_LENGTH: usize = 255;
+
+// ===== LEITURA =====
+
+/// Separa o arquivo em linhas. Erros de estrutura (CSV malformado, cabeçalho inválido)
+/// rejeitam o arquivo inteiro; problemas de uma linha ficam só nela.
+pub fn parse_rows(format: TransferFormat, data: &str) -> Result<Vec<RawRow>, AppError> {
+    match format {
+        TransferFormat::Csv => parse_csv(data),
+        TransferFormat::Jsonl => Ok(parse_jsonl(data)),
+    }
+}
+
+fn parse_csv(data: &str) -> Result<Vec<RawRow>, AppError> {
+    let mut records = csv::parse(data)
+        .map_err(|e| AppError::bad_request(format!("CSV inválido ({})", e)))?
+        .into_iter();
+
+    let Some(header) = records.next() else {
+        return Ok(vec![]);
+    };
+    let columns: Vec<String> = header
+        .fields
+        .iter()
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+
+    if !columns
+        .iter()
+        .any(|column| matches!(column.as_str(), "sku" | "slug" | "name"))
+    {
+        return Err(AppError::bad_request(
+            "O cabeçalho do CSV deve ter ao menos uma das colunas sku, slug ou name",
+        ));
+    }
+    if let Some(duplicated) = columns
+        .iter()
+        .enumerate()
+        .find(|(i, column)| columns[..*i].contains(column))
+        .map(|(_, column)| column)
+    {
+        return Err(AppError::bad_request(format!(
+            "Coluna repetida no cabeçalho: {}",
+            duplicated
+        )));
+    }
+
+    Ok(records
+        .map(|record| {
+            let values = if record.fields.len() != columns.len() {
+                Err(FieldError::row(format!(
+                    "Esperadas {} colunas, encontradas {}",
+                    columns.len(),
+                    record.fields.len()
+                )))
+            } else {
+                // Célula vazia equivale a coluna ausente: não altera o produto
+                Ok(columns
+                    .iter()
+                    .zip(record.fields)
+                    .filter(|(_, value)| !value.trim().is_empty())
+                    .map(|(column, value)| (column.clone(), Value::String(value)))
+                    .collect())
+            };
+            RawRow {
+                line: record.line,
+                values,
+            }
+        })
+        .collect())
+}
+
+fn parse_jsonl(data: &str) -> Vec<RawRow> {
+    let data = data.strip_prefix('\u{feff}').unwrap_or(data);
+
+    data.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let values = match serde_json::from_str::<Value>(line) {
+                Ok(Value::Object(values)) => Ok(values
+                    .into_iter()
+                    .filter(|(_, value)| !value.is_null())
+                    .collect()),
+                Ok(_) => Err(FieldError::row("Cada linha deve ser um objeto JSON")),
+                Err(e) => Err(FieldError::row(format!("JSON inválido: {}", e))),
+            };
+            RawRow {
+                line: index + 1,
+                values,
+            }
+        })
+        .collect()
+}
+
+// ===== VALIDAÇÃO =====
+
+fn text(value: &Value) -> Result<Option<String>, String> {
+    match value {
+        Value::String(s) => Ok(Some(s.trim().to_string()).filter(|s| !s.is_empty())),
+        Value::Number(n) => Ok(Some(n.to_string())),
+        Value::Bool(b) => Ok(Some(b.to_string())),
+        Value::Null => Ok(None),
+        _ => Err("Deve ser um texto".to_string()),
+    }
+}
+
+/// Valor decimal ("49.90", "49,90", 49.9) convertido para centavos
+pub fn parse_price(value: &str) -> Result<i64, String> {
+    let value = value.trim();
+    if value.starts_with('-') {
+        return Err("O preço não pode ser negativo".to_string());
+    }
+
+    let invalid = || format!("Preço inválido: {}", value);
+    let (units, fraction) = match value.split_once(['.', ',']) {
+        Some((units, fraction)) => (units, fraction),
+        None => (value, ""),
+    };
+    if units.is_empty()
+        || fraction.len() > 2
+        || !units.chars().all(|c| c.is_ascii_digit())
+        || !fraction.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    let units: i64 = units.parse().map_err(|_| invalid())?;
+    let fraction: i64 = format!("{:0<2}", fraction).parse().map_err(|_| invalid())?;
+    units
+        .checked_mul(100)
+        .and_then(|cents| cents.checked_add(fraction))
+        .filter(|cents| *cents <= MAX_PRICE_CENTS)
+        .ok_or_else(|| "Preço acima do máximo permitido".to_string())
+}
+
+fn parse_stock(value: &str) -> Result<i32, String> {
+    let stock: i32 = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("Estoque deve ser um número inteiro: {}", value))?;
+    if stock < 0 {
+        return Err("O estoque não pode ser negativo".to_string());
+    }
+    Ok(stock)
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "sim" | "s" | "yes" => Ok(true),
+        "false" | "0" | "não" | "nao" | "n" | "no" => Ok(false),
+        _ => Err(format!("Valor inválido: {} (use true ou false)", value)),
+    }
+}
+
+/// Objeto JSON; no CSV chega como texto
+fn parse_attributes(value: &Value) -> Result<Value, String> {
+    let parsed = match value {
+        Value::String(s) => serde_json::from_str(s)
+            .map_err(|_| "Atributos devem ser um objeto JSON válido".to_string())?,
+        other => other.clone(),
+    };
+    match parsed {
+        Value::Object(_) => Ok(parsed),
+        _ => Err("Atributos devem ser um objeto JSON".to_string()),
+    }
+}
+
+/// Valida todos os campos da linha de uma vez, para o relatório listar todos os problemas
+pub fn validate_row(raw: RawRow) -> Result<ImportRow, RowError> {
+    let line = raw.line;
+    let values: Map<String, Value> = raw.values.map_err(|error| RowError {
+        row: line,
+        key: None,
+        errors: vec![error],
+    })?;
+
+    let mut errors = Vec::new();
+    let mut row = ImportRow {
+        line,
+        ..Default::default()
+    };
+
+    let field_text = |field: &str, errors: &mut Vec<FieldError>| -> Option<String> {
+        let value = values.get(field)?;
+        text(value)
+            .map_err(|message| errors.push(FieldError::new(field, message)))
+            .ok()
+            .flatten()
+    };
+
+    let raw_sku = field_text("sku", &mut errors);
+    row.sku = normalize_sku(raw_sku.clone())
+        .map_err(|e| errors.push(FieldError::new("sku", e.message())))
+        .ok()
+        .flatten();
+    row.slug = field_text("slug", &mut errors).map(|slug| slug.to_lowercase());
+    row.name = field_text("name", &mut errors);
+    row.short_description = field_text("short_description", &mut errors);
+    row.description = field_text("description", &mut errors);
+
+    if let Some(name) = &row.name
+        && name.chars().count() > NAME_MAX_LENGTH
+    {
+        errors.push(FieldError::new(
+            "name",
+            format!("O nome deve ter até {} caracteres", NAME_MAX_LENGTH),
+        ));
+    }
+    if let Some(price) = field_text("price", &mut errors) {
+        row.price = parse_price(&price)
+            .map_err(|message| errors.push(FieldError::new("price", message)))
+            .ok();
+    }
+    if let Some(stock) = field_text("stock_quantity", &mut errors) {
+        row.stock_quantity = parse_stock(&stock)
+            .map_err(|message| errors.push(FieldError::new("stock_quantity", message)))
+            .ok();
+    }
+    if let Some(is_active) = field_text("is_active", &mut errors) {
+        row.is_active = parse_bool(&is_active)
+            .map_err(|message| errors.push(FieldError::new("is_active", message)))
+            .ok();
+    }
+    if let Some(attributes) = values.get("attributes") {
+        row.attributes = parse_attributes(attributes)
+            .map_err(|message| errors.push(FieldError::new("attributes", message)))
+            .ok();
+    }
+
+    if raw_sku.is_none() && row.slug.is_none() && row.name.is_none() && errors.is_empty() {
+        errors.push(FieldError::row("Informe ao menos sku, slug ou name"));
+    }
+
+    if errors.is_empty() {
+        Ok(row)
+    } else {
+        Err(RowError {
+            row: line,
+            key: raw_sku.or(row.slug),
+            errors,
+        })
+    }
+}
+
+// ===== ESCRITA =====
+
+/// Preço sempre com duas casas ("39.00"), independente da escala vinda do banco
+fn price_text(product: &Product) -> String {
+    product.price.to_decimal().with_scale(2).to_string()
+}
+
+pub fn csv_header() -> String {
+    csv::write_record(COLUMNS)
+}
+
+/// Linha no mesmo formato aceito pela importação (preço decimal, atributos em JSON)
+pub fn to_csv_line(product: &Product) -> String {
+    let price = price_text(product);
+    let stock_quantity = product.stock_quantity.to_string();
+    let is_active = product.is_active.to_string();
+    let attributes = product
+        .attributes
+        .as_ref()
+        .map(Value::to_string)
+        .unwrap_or_default();
+
+    csv::write_record([
+        product.sku.as_deref().unwrap_or_default(),
+        product.slug.as_str(),
+        product.name.as_str(),
+        product.short_description.as_deref().unwrap_or_default(),
+        product.description.as_deref().unwrap_or_default(),
+        price.as_str(),
+        stock_quantity.as_str(),
+        is_active.as_str(),
+        attributes.as_str(),
+    ])
+}
+
+pub fn to_jsonl_line(product: &Product) -> String {
+    let mut line = json!({
+        "sku": product.sku,
+        "slug": product.slug,
+        "name": product.name,
+        "short_description": product.short_description,
+        "description": product.description,
+        "price": price_text(product),
+        "stock_quantity": product.stock_quantity,
+        "is_active": product.is_active,
+        "attributes": product.attributes,
+    })
+    .to_string();
+    line.push('\n');
+    line
+}
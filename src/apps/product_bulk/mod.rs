@@ -0,0 +1,9 @@
+pub mod commands;
+pub mod formats;
+pub mod models;
+pub mod repositories;
+pub mod routes;
+pub mod services;
+
+#[cfg(test)]
+mod tests;
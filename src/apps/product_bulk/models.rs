@@ -0,0 +1,213 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Colunas do CSV (e chaves do JSONL), na ordem usada pela exportação
+pub const COLUMNS: [&str; 9] = [
+    "sku",
+    "slug",
+    "name",
+    "short_description",
+    "description",
+    "price",
+    "stock_quantity",
+    "is_active",
+    "attributes",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferFormat {
+    Csv,
+    /// JSON Lines: um objeto por linha
+    Jsonl,
+}
+
+impl TransferFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferFormat::Csv => "csv",
+            TransferFormat::Jsonl => "jsonl",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            TransferFormat::Csv => "text/csv; charset=utf-8",
+            TransferFormat::Jsonl => "application/x-ndjson",
+        }
+    }
+
+    /// Formato pelo Content-Type do upload, quando `?format=` não é informado
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type
+            .split(';')
+            .next()?
+            .trim()
+            .to_lowercase()
+            .as_str()
+        {
+            "text/csv" | "application/csv" => Some(TransferFormat::Csv),
+            "application/x-ndjson" | "application/jsonl" | "application/x-jsonlines" => {
+                Some(TransferFormat::Jsonl)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for TransferFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(TransferFormat::Csv),
+            "jsonl" | "ndjson" => Ok(TransferFormat::Jsonl),
+            _ => Err(format!("Formato inválido: {} (use csv ou jsonl)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportJobStatus {
+    Pending,
+    Running,
+    /// Parado pelo prazo de desligamento; retomado pelo próximo servidor
+    Interrupted,
+    Completed,
+    Failed,
+}
+
+impl ImportJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportJobStatus::Pending => "pending",
+            ImportJobStatus::Running => "running",
+            ImportJobStatus::Interrupted => "interrupted",
+            ImportJobStatus::Completed => "completed",
+            ImportJobStatus::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FieldError {
+    /// Ausente quando o erro é da linha inteira (ex: JSON inválido, conflito no banco)
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: Some(field.to_string()),
+            message: message.into(),
+        }
+    }
+
+    pub fn row(message: impl Into<String>) -> Self {
+        Self {
+            field: None,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RowError {
+    /// Linha do arquivo (o cabeçalho do CSV é a linha 1)
+    pub row: usize,
+    /// SKU ou slug da linha, para achar o produto na planilha
+    pub key: Option<String>,
+    pub errors: Vec<FieldError>,
+}
+
+/// Linha lida do arquivo, antes da validação dos campos
+#[derive(Debug, Clone)]
+pub struct RawRow {
+    pub line: usize,
+    pub values: Result<serde_json::Map<String, Value>, FieldError>,
+}
+
+/// Linha validada; campos ausentes não alteram o produto existente
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportRow {
+    pub line: usize,
+    pub sku: Option<String>,
+    pub slug: Option<String>,
+    pub name: Option<String>,
+    pub short_description: Option<String>,
+    pub description: Option<String>,
+    /// Em centavos, como no CreateProductRequest
+    pub price: Option<i64>,
+    pub stock_quantity: Option<i32>,
+    pub is_active: Option<bool>,
+    pub attributes: Option<Value>,
+}
+
+impl ImportRow {
+    pub fn key(&self) -> Option<String> {
+        self.sku.clone().or_else(|| self.slug.clone())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportOutcome {
+    Created,
+    Updated,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductImportJob {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub created_by: Option<Uuid>,
+    pub format: String,
+    pub status: String,
+    pub total_rows: i32,
+    pub processed_rows: i32,
+    pub created_count: i32,
+    pub updated_count: i32,
+    pub failed_count: i32,
+    /// Lista de RowError (limitada; `failed_count` tem o total)
+    pub row_errors: Value,
+    pub error_message: Option<String>,
+    pub dt_created: DateTime<Utc>,
+    pub dt_started: Option<DateTime<Utc>>,
+    pub dt_finished: Option<DateTime<Utc>>,
+}
+
+/// Contadores e erros acumulados enquanto a importação roda
+#[derive(Debug, Clone, Default)]
+pub struct ImportProgress {
+    pub processed_rows: i32,
+    pub created_count: i32,
+    pub updated_count: i32,
+    pub failed_count: i32,
+    pub row_errors: Vec<RowError>,
+}
+
+impl ImportProgress {
+    /// Progresso já gravado no job, para retomar de onde parou
+    pub fn from_job(job: &ProductImportJob) -> Self {
+        Self {
+            processed_rows: job.processed_rows,
+            created_count: job.created_count,
+            updated_count: job.updated_count,
+            failed_count: job.failed_count,
+            row_errors: serde_json::from_value(job.row_errors.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportParams {
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportParams {
+    pub format: Option<String>,
+}
@@ -0,0 +1,225 @@
+use crate::app_core::app_state::AppState;
+use crate::apps::product_bulk::models::{ImportJobStatus, ImportProgress, ProductImportJob};
+use chrono::{DateTime, Utc};
+use tracing::instrument;
+use uuid::Uuid;
+
+pub struct ProductImportRepository<'a> {
+    app_state: &'a AppState,
+}
+
+impl<'a> ProductImportRepository<'a> {
+    pub fn new(app_state: &'a AppState) -> Self {
+        Self { app_state }
+    }
+
+    #[instrument(name = "ProductImportRepository::create", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create(
+        &self,
+        tenant_id: Uuid,
+        created_by: Option<Uuid>,
+        format: &str,
+        total_rows: i32,
+        source: &str,
+    ) -> Result<ProductImportJob, sqlx::Error> {
+        sqlx::query_as!(
+            ProductImportJob,
+            r#"
+            INSERT INTO product_import_jobs (id, tenant_id, created_by, format, status, total_rows, source, dt_created)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+            RETURNING
+                id, tenant_id, created_by, format, status, total_rows, processed_rows,
+                created_count, updated_count, failed_count, row_errors, error_message,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_started AT TIME ZONE 'UTC') as "dt_started?: DateTime<Utc>",
+                (dt_finished AT TIME ZONE 'UTC') as "dt_finished?: DateTime<Utc>"
+            "#,
+            Uuid::new_v4(),
+            tenant_id,
+            created_by,
+            format,
+            ImportJobStatus::Pending.as_str(),
+            total_rows,
+            source
+        )
+        .fetch_one(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "ProductImportRepository::find_by_id", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_id(
+        &self,
+        id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<Option<ProductImportJob>, sqlx::Error> {
+        sqlx::query_as!(
+            ProductImportJob,
+            r#"
+            SELECT
+                id, tenant_id, created_by, format, status, total_rows, processed_rows,
+                created_count, updated_count, failed_count, row_errors, error_message,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_started AT TIME ZONE 'UTC') as "dt_started?: DateTime<Utc>",
+                (dt_finished AT TIME ZONE 'UTC') as "dt_finished?: DateTime<Utc>"
+            FROM product_import_jobs
+            WHERE id = $1 AND tenant_id = $2
+            "#,
+            id,
+            tenant_id
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "ProductImportRepository::mark_running", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn mark_running(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE product_import_jobs SET status = $2, dt_started = COALESCE(dt_started, NOW()) WHERE id = $1",
+            id,
+            ImportJobStatus::Running.as_str()
+        )
+        .execute(&self.app_state.db)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "ProductImportRepository::update_progress", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn update_progress(
+        &self,
+        id: Uuid,
+        progress: &ImportProgress,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE product_import_jobs
+            SET processed_rows = $2, created_count = $3, updated_count = $4,
+                failed_count = $5, row_errors = $6
+            WHERE id = $1
+            "#,
+            id,
+            progress.processed_rows,
+            progress.created_count,
+            progress.updated_count,
+            progress.failed_count,
+            serde_json::json!(progress.row_errors)
+        )
+        .execute(&self.app_state.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Grava o resultado final (concluído ou com falha) com o progresso até ali e descarta o arquivo
+    #[instrument(name = "ProductImportRepository::finish", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn finish(
+        &self,
+        id: Uuid,
+        status: ImportJobStatus,
+        progress: &ImportProgress,
+        error_message: Option<&str>,
+    ) -> Result<ProductImportJob, sqlx::Error> {
+        sqlx::query_as!(
+            ProductImportJob,
+            r#"
+            UPDATE product_import_jobs
+            SET status = $2, processed_rows = $3, created_count = $4, updated_count = $5,
+                failed_count = $6, row_errors = $7, error_message = $8, source = NULL,
+                dt_finished = NOW()
+            WHERE id = $1
+            RETURNING
+                id, tenant_id, created_by, format, status, total_rows, processed_rows,
+                created_count, updated_count, failed_count, row_errors, error_message,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_started AT TIME ZONE 'UTC') as "dt_started?: DateTime<Utc>",
+                (dt_finished AT TIME ZONE 'UTC') as "dt_finished?: DateTime<Utc>"
+            "#,
+            id,
+            status.as_str(),
+            progress.processed_rows,
+            progress.created_count,
+            progress.updated_count,
+            progress.failed_count,
+            serde_json::json!(progress.row_errors),
+            error_message
+        )
+        .fetch_one(&self.app_state.db)
+        .await
+    }
+
+    /// Guarda o progresso de um job parado no desligamento; o arquivo fica para a retomada
+    #[instrument(name = "ProductImportRepository::interrupt", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn interrupt(
+        &self,
+        id: Uuid,
+        progress: &ImportProgress,
+    ) -> Result<ProductImportJob, sqlx::Error> {
+        sqlx::query_as!(
+            ProductImportJob,
+            r#"
+            UPDATE product_import_jobs
+            SET status = $2, processed_rows = $3, created_count = $4, updated_count = $5,
+                failed_count = $6, row_errors = $7
+            WHERE id = $1
+            RETURNING
+                id, tenant_id, created_by, format, status, total_rows, processed_rows,
+                created_count, updated_count, failed_count, row_errors, error_message,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_started AT TIME ZONE 'UTC') as "dt_started?: DateTime<Utc>",
+                (dt_finished AT TIME ZONE 'UTC') as "dt_finished?: DateTime<Utc>"
+            "#,
+            id,
+            ImportJobStatus::Interrupted.as_str(),
+            progress.processed_rows,
+            progress.created_count,
+            progress.updated_count,
+            progress.failed_count,
+            serde_json::json!(progress.row_errors)
+        )
+        .fetch_one(&self.app_state.db)
+        .await
+    }
+
+    /// Jobs de todos os tenants parados no desligamento, do mais antigo ao mais novo
+    #[instrument(name = "ProductImportRepository::find_interrupted", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_interrupted(&self) -> Result<Vec<ProductImportJob>, sqlx::Error> {
+        sqlx::query_as!(
+            ProductImportJob,
+            r#"
+            SELECT
+                id, tenant_id, created_by, format, status, total_rows, processed_rows,
+                created_count, updated_count, failed_count, row_errors, error_message,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_started AT TIME ZONE 'UTC') as "dt_started?: DateTime<Utc>",
+                (dt_finished AT TIME ZONE 'UTC') as "dt_finished?: DateTime<Utc>"
+            FROM product_import_jobs
+            WHERE status = $1
+            ORDER BY dt_created
+            "#,
+            ImportJobStatus::Interrupted.as_str()
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    /// Passa o job interrompido para `running` e devolve o arquivo. `None` quando outra
+    /// instância já o retomou.
+    #[instrument(name = "ProductImportRepository::claim_interrupted", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn claim_interrupted(&self, id: Uuid) -> Result<Option<String>, sqlx::Error> {
+        let source = sqlx::query_scalar!(
+            r#"
+            UPDATE product_import_jobs
+            SET status = $2
+            WHERE id = $1 AND status = $3 AND source IS NOT NULL
+            RETURNING source as "source!"
+            "#,
+            id,
+            ImportJobStatus::Running.as_str(),
+            ImportJobStatus::Interrupted.as_str()
+        )
+        .fetch_optional(&self.app_state.db)
+        .await?;
+
+        Ok(source)
+    }
+}
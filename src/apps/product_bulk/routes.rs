@@ -0,0 +1,130 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_extensions::RequestUserExt;
+use crate::app_core::app_state::AppState;
+use crate::app_core::init_settings::get_settings;
+use crate::apps::audit::models::AuditContext;
+use crate::apps::product_bulk::models::{
+    ExportParams, ImportJobStatus, ImportParams, TransferFormat,
+};
+use crate::apps::product_bulk::services::ProductBulkService;
+use actix_web::http::header;
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+use futures_util::StreamExt;
+use uuid::Uuid;
+
+/// Formato pelo `?format=`; sem ele, pelo Content-Type do corpo
+fn import_format(params: &ImportParams, req: &HttpRequest) -> Result<TransferFormat, AppError> {
+    if let Some(format) = &params.format {
+        return format.parse().map_err(AppError::bad_request);
+    }
+
+    req.headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|h| h.to_str().ok())
+        .and_then(TransferFormat::from_content_type)
+        .ok_or_else(|| {
+            AppError::bad_request(
+                "Informe o formato em ?format=csv|jsonl ou no Content-Type (text/csv, application/x-ndjson)",
+            )
+        })
+}
+
+async fn read_body(mut payload: web::Payload, limit: usize) -> Result<Vec<u8>, AppError> {
+    let mut data = Vec::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk =
+            chunk.map_err(|e| AppError::bad_request(format!("Erro ao ler o arquivo: {}", e)))?;
+        if data.len() + chunk.len() > limit {
+            return Err(AppError::payload_too_large(format!(
+                "O arquivo excede o limite de {} bytes",
+                limit
+            )));
+        }
+        data.extend_from_slice(&chunk);
+    }
+    Ok(data)
+}
+
+/// POST /products/import/?format=csv|jsonl - corpo é o arquivo.
+/// 200 com o relatório quando importado na hora; 202 quando vira job em background.
+pub async fn import_products(
+    app_state: web::Data<AppState>,
+    query: web::Query<ImportParams>,
+    payload: web::Payload,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let audit = AuditContext::from_request(&req);
+    let format = import_format(&query, &req)?;
+    let data = read_body(payload, get_settings().product_import.max_bytes).await?;
+
+    let job = ProductBulkService::start_import(
+        &app_state,
+        tenant_id,
+        Some(user_id),
+        format,
+        data,
+        &audit,
+    )
+    .await?;
+
+    let finished = job.status == ImportJobStatus::Completed.as_str()
+        || job.status == ImportJobStatus::Failed.as_str();
+    Ok(if finished {
+        HttpResponse::Ok().json(serde_json::json!(job))
+    } else {
+        HttpResponse::Accepted()
+            .insert_header((
+                header::LOCATION,
+                format!("/api/v1/products/import/{}/", job.id),
+            ))
+            .json(serde_json::json!(job))
+    })
+}
+
+/// GET /products/import/{id}/ - progresso e erros por linha
+pub async fn get_import_job(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let result = ProductBulkService::get_job(&app_state, id, tenant_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// GET /products/export/?format=csv|jsonl - catálogo do tenant em streaming
+pub async fn export_products(
+    app_state: web::Data<AppState>,
+    query: web::Query<ExportParams>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let format = match &query.format {
+        Some(format) => format
+            .parse::<TransferFormat>()
+            .map_err(AppError::bad_request)?,
+        None => TransferFormat::Csv,
+    };
+
+    let state = AppState {
+        db: app_state.db.clone(),
+    };
+    let stream = ProductBulkService::export_stream(state, tenant_id, format)
+        .map(|chunk| chunk.map_err(actix_web::Error::from));
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"products-{}.{}\"",
+                chrono::Utc::now().format("%Y%m%d"),
+                format.as_str()
+            ),
+        ))
+        .streaming(stream))
+}
@@ -0,0 +1,390 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::app_core::init_settings::get_settings;
+use crate::app_core::workers::{ShutdownSignal, WorkerSupervisor, supervisor};
+use crate::apps::audit::models::AuditContext;
+use crate::apps::product::models::{CreateProductRequest, UpdateProductRequest};
+use crate::apps::product::repositories::ProductRepository;
+use crate::apps::product::services::ProductService;
+use crate::apps::product_bulk::formats::{
+    csv_header, parse_rows, to_csv_line, to_jsonl_line, validate_row,
+};
+use crate::apps::product_bulk::models::{
+    FieldError, ImportJobStatus, ImportOutcome, ImportProgress, ProductImportJob, RawRow, RowError,
+    TransferFormat,
+};
+use crate::apps::product_bulk::repositories::ProductImportRepository;
+use actix_web::web::{self, Bytes};
+use chrono::{DateTime, Utc};
+use futures_util::Stream;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Linhas processadas entre uma gravação de progresso e outra
+const PROGRESS_EVERY: usize = 50;
+
+/// Erros de linha guardados no job; `failed_count` mantém o total
+const MAX_STORED_ERRORS: usize = 1000;
+
+/// Folga antes do prazo de desligamento para gravar onde a importação parou
+const SHUTDOWN_SAVE_MARGIN: Duration = Duration::from_secs(1);
+
+/// Produtos por consulta na exportação
+const EXPORT_BATCH_SIZE: i64 = 500;
+
+struct ExportCursor {
+    app_state: AppState,
+    after: Option<(DateTime<Utc>, Uuid)>,
+    header_pending: bool,
+    done: bool,
+}
+
+pub struct ProductBulkService;
+
+impl ProductBulkService {
+    /// Lê o arquivo e cria o job com o total de linhas, sem processar nada ainda
+    pub async fn prepare_import(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        created_by: Option<Uuid>,
+        format: TransferFormat,
+        data: Vec<u8>,
+    ) -> Result<(ProductImportJob, Vec<RawRow>), AppError> {
+        let settings = &get_settings().product_import;
+
+        let text = String::from_utf8(data)
+            .map_err(|_| AppError::bad_request("O arquivo deve estar em UTF-8"))?;
+        let rows = parse_rows(format, &text)?;
+
+        if rows.is_empty() {
+            return Err(AppError::bad_request(
+                "O arquivo não tem linhas para importar",
+            ));
+        }
+        if rows.len() > settings.max_rows {
+            return Err(AppError::bad_request(format!(
+                "O arquivo excede o limite de {} linhas",
+                settings.max_rows
+            )));
+        }
+
+        let job = ProductImportRepository::new(app_state)
+            .create(
+                tenant_id,
+                created_by,
+                format.as_str(),
+                rows.len() as i32,
+                &text,
+            )
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        Ok((job, rows))
+    }
+
+    /// Arquivos pequenos são importados na hora (job já concluído); os maiores rodam em
+    /// background e o progresso fica em GET /products/import/{id}/
+    pub async fn start_import(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        created_by: Option<Uuid>,
+        format: TransferFormat,
+        data: Vec<u8>,
+        audit: &AuditContext,
+    ) -> Result<ProductImportJob, AppError> {
+        let (job, rows) =
+            Self::prepare_import(app_state, tenant_id, created_by, format, data).await?;
+
+        if rows.len() <= get_settings().product_import.sync_max_rows {
+            return Self::run_import(app_state, job, rows, audit, None).await;
+        }
+
+        let state = AppState {
+            db: app_state.db.clone(),
+        };
+        let audit = audit.clone();
+        let job_id = job.id;
+        supervisor().spawn_task(
+            &format!("product-import-{}", job_id),
+            move |signal| async move {
+                if let Err(e) = Self::run_import(&state, job, rows, &audit, Some(signal)).await {
+                    error!(job_id = %job_id, "Erro na importação de produtos: {}", e.message());
+                }
+            },
+        );
+
+        Self::get_job(app_state, job_id, tenant_id).await
+    }
+
+    /// Processa as linhas em ordem, a partir de `processed_rows` do job; uma linha com erro
+    /// não interrompe as demais. No desligamento segue até perto do prazo e então deixa o job
+    /// `interrupted` para o próximo servidor retomar.
+    pub async fn run_import(
+        app_state: &AppState,
+        job: ProductImportJob,
+        rows: Vec<RawRow>,
+        audit: &AuditContext,
+        signal: Option<ShutdownSignal>,
+    ) -> Result<ProductImportJob, AppError> {
+        let repository = ProductImportRepository::new(app_state);
+        repository
+            .mark_running(job.id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        let total = rows.len();
+        let mut progress = ImportProgress::from_job(&job);
+
+        for raw in rows.into_iter().skip(progress.processed_rows as usize) {
+            let deadline = signal.as_ref().and_then(|s| s.deadline());
+            if deadline.is_some_and(|deadline| Instant::now() + SHUTDOWN_SAVE_MARGIN >= deadline) {
+                warn!(
+                    job_id = %job.id,
+                    processed = progress.processed_rows,
+                    total,
+                    "Importação de produtos interrompida pelo prazo de desligamento"
+                );
+                return repository
+                    .interrupt(job.id, &progress)
+                    .await
+                    .map_err(|e| AppError::database_error(e.to_string()));
+            }
+
+            match Self::import_row(app_state, job.tenant_id, raw, audit).await {
+                Ok(ImportOutcome::Created) => progress.created_count += 1,
+                Ok(ImportOutcome::Updated) => progress.updated_count += 1,
+                Err(row_error) => {
+                    progress.failed_count += 1;
+                    if progress.row_errors.len() < MAX_STORED_ERRORS {
+                        progress.row_errors.push(row_error);
+                    }
+                }
+            }
+            progress.processed_rows += 1;
+
+            if (progress.processed_rows as usize).is_multiple_of(PROGRESS_EVERY) {
+                info!(
+                    job_id = %job.id,
+                    processed = progress.processed_rows,
+                    total,
+                    "Progresso da importação de produtos"
+                );
+                repository
+                    .update_progress(job.id, &progress)
+                    .await
+                    .map_err(|e| AppError::database_error(e.to_string()))?;
+            }
+        }
+
+        info!(
+            job_id = %job.id,
+            created = progress.created_count,
+            updated = progress.updated_count,
+            failed = progress.failed_count,
+            "Importação de produtos concluída"
+        );
+        repository
+            .finish(job.id, ImportJobStatus::Completed, &progress, None)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    /// Upsert de uma linha: atualiza o produto com o mesmo SKU (ou slug), senão cria
+    async fn import_row(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        raw: RawRow,
+        audit: &AuditContext,
+    ) -> Result<ImportOutcome, RowError> {
+        let row = validate_row(raw)?;
+        let row_error = |errors: Vec<FieldError>| RowError {
+            row: row.line,
+            key: row.key(),
+            errors,
+        };
+
+        let existing = ProductRepository::new(app_state)
+            .find_import_match(tenant_id, row.sku.as_deref(), row.slug.as_deref())
+            .await
+            .map_err(|e| row_error(vec![FieldError::row(e.to_string())]))?;
+
+        match existing {
+            Some(id) => {
+                let request = UpdateProductRequest {
+                    name: row.name.clone(),
+                    sku: row.sku.clone(),
+                    short_description: row.short_description.clone(),
+                    description: row.description.clone(),
+                    price: row.price,
+                    stock_quantity: row.stock_quantity,
+                    attributes: row.attributes.clone(),
+                    is_active: row.is_active,
+                };
+                ProductService::update_product(app_state, id, tenant_id, request, audit)
+                    .await
+                    .map_err(|e| row_error(vec![FieldError::row(e.message())]))?;
+
+                Ok(ImportOutcome::Updated)
+            }
+            None => {
+                let (Some(name), Some(price)) = (row.name.clone(), row.price) else {
+                    let errors = [("name", row.name.is_none()), ("price", row.price.is_none())]
+                        .into_iter()
+                        .filter(|(_, missing)| *missing)
+                        .map(|(field, _)| {
+                            FieldError::new(field, "Obrigatório para criar um produto")
+                        })
+                        .collect();
+                    return Err(row_error(errors));
+                };
+
+                let request = CreateProductRequest {
+                    name,
+                    sku: row.sku.clone(),
+                    short_description: row.short_description.clone(),
+                    description: row.description.clone(),
+                    price,
+                    stock_quantity: row.stock_quantity.unwrap_or(0),
+                    attributes: row.attributes.clone(),
+                    is_active: row.is_active.unwrap_or(true),
+                };
+                ProductService::create_product(app_state, request, tenant_id, audit)
+                    .await
+                    .map_err(|e| row_error(vec![FieldError::row(e.message())]))?;
+
+                Ok(ImportOutcome::Created)
+            }
+        }
+    }
+
+    pub async fn get_job(
+        app_state: &AppState,
+        id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<ProductImportJob, AppError> {
+        ProductImportRepository::new(app_state)
+            .find_by_id(id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Importação não encontrada"))
+    }
+
+    /// Catálogo do tenant (ativos e inativos) em lotes, sem carregar tudo em memória
+    pub fn export_stream(
+        app_state: AppState,
+        tenant_id: Uuid,
+        format: TransferFormat,
+    ) -> impl Stream<Item = Result<Bytes, AppError>> + 'static {
+        let cursor = ExportCursor {
+            app_state,
+            after: None,
+            header_pending: format == TransferFormat::Csv,
+            done: false,
+        };
+
+        futures_util::stream::try_unfold(cursor, move |mut cursor| async move {
+            if cursor.done {
+                return Ok(None);
+            }
+
+            let products = ProductRepository::new(&cursor.app_state)
+                .find_for_export(tenant_id, cursor.after, EXPORT_BATCH_SIZE)
+                .await
+                .map_err(|e| AppError::database_error(e.to_string()))?;
+
+            cursor.done = (products.len() as i64) < EXPORT_BATCH_SIZE;
+            cursor.after = products.last().map(|p| (p.dt_created, p.id));
+
+            let mut chunk = String::new();
+            if cursor.header_pending {
+                chunk.push_str(&csv_header());
+                cursor.header_pending = false;
+            }
+            for product in &products {
+                chunk.push_str(&match format {
+                    TransferFormat::Csv => to_csv_line(product),
+                    TransferFormat::Jsonl => to_jsonl_line(product),
+                });
+            }
+
+            Ok(Some((Bytes::from(chunk), cursor)))
+        })
+    }
+}
+
+/// Retoma em background as importações paradas no último desligamento
+pub fn spawn_interrupted_imports(supervisor: &WorkerSupervisor, app_state: web::Data<AppState>) {
+    supervisor.spawn_task("product-import-resume", move |signal| async move {
+        let repository = ProductImportRepository::new(&app_state);
+        let jobs = match repository.find_interrupted().await {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                error!("Erro ao buscar importações interrompidas: {}", e);
+                return;
+            }
+        };
+
+        for job in jobs {
+            if signal.is_shutdown() {
+                break;
+            }
+            if let Err(e) = resume_import(&app_state, &job, signal.clone()).await {
+                error!(job_id = %job.id, "Erro ao retomar importação de produtos: {}", e.message());
+            }
+        }
+    });
+}
+
+async fn resume_import(
+    app_state: &AppState,
+    job: &ProductImportJob,
+    signal: ShutdownSignal,
+) -> Result<(), AppError> {
+    let repository = ProductImportRepository::new(app_state);
+    let Some(source) = repository
+        .claim_interrupted(job.id)
+        .await
+        .map_err(|e| AppError::database_error(e.to_string()))?
+    else {
+        return Ok(());
+    };
+    // Relido depois de reservar: o progresso pode ter mudado desde a listagem
+    let job = ProductBulkService::get_job(app_state, job.id, job.tenant_id).await?;
+    info!(
+        job_id = %job.id,
+        processed = job.processed_rows,
+        total = job.total_rows,
+        "Retomando importação de produtos"
+    );
+
+    let rows = match job
+        .format
+        .parse::<TransferFormat>()
+        .map_err(AppError::bad_request)
+        .and_then(|format| parse_rows(format, &source))
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            repository
+                .finish(
+                    job.id,
+                    ImportJobStatus::Failed,
+                    &ImportProgress::from_job(&job),
+                    Some(&e.message()),
+                )
+                .await
+                .map_err(|e| AppError::database_error(e.to_string()))?;
+            return Err(e);
+        }
+    };
+
+    let audit = AuditContext {
+        actor_id: job.created_by,
+        tenant_id: Some(job.tenant_id),
+        ..Default::default()
+    };
+    ProductBulkService::run_import(app_state, job, rows, &audit, Some(signal)).await?;
+    Ok(())
+}
@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod tests {
+    use crate::apps::product::models::Product;
+    use crate::apps::product_bulk::formats::{
+        csv_header, parse_price, parse_rows, to_csv_line, to_jsonl_line, validate_row,
+    };
+    use crate::apps::product_bulk::models::{ImportRow, TransferFormat};
+    use crate::utils::csv;
+    use crate::utils::money::Money;
+    use chrono::Utc;
+    use serde_json::json;
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    fn product() -> Product {
+        let now = Utc::now();
+        Product {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            name: "Camiseta \"Básica\", algodão".to_string(),
+            slug: "camiseta-basica-algodao".to_string(),
+            sku: Some("CAM-001".to_string()),
+            short_description: None,
+            description: Some("Linha 1\nLinha 2".to_string()),
+            price: Money::new(4990, "BRL"),
+            stock_quantity: 12,
+            attributes: Some(json!({"cor": "azul"})),
+            is_active: false,
+            dt_created: now,
+            dt_updated: now,
+            dt_deleted: None,
+        }
+    }
+
+    // ===== CSV =====
+
+    #[test]
+    fn test_csv_parse_quotes_and_line_breaks() {
+        let records = csv::parse("\u{feff}a,b\r\n\"x, \"\"y\"\"\",\"1\n2\"\n\nz,\n").unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1].fields, vec!["x, \"y\"", "1\n2"]);
+        assert_eq!(records[1].line, 2);
+        // A linha em branco é ignorada, mas conta na numeração
+        assert_eq!(records[2].fields, vec!["z", ""]);
+        assert_eq!(records[2].line, 5);
+    }
+
+    #[test]
+    fn test_csv_parse_semicolon_and_errors() {
+        let records = csv::parse("sku;price\nA;49,90").unwrap();
+        assert_eq!(records[1].fields, vec!["A", "49,90"]);
+
+        assert_eq!(csv::parse("a,\"b\nc").unwrap_err().line, 1);
+        assert_eq!(csv::parse("a,b\n\"x\"y,z").unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn test_csv_write_roundtrip() {
+        let product = product();
+        let parsed = csv::parse(&(csv_header() + &to_csv_line(&product))).unwrap();
+
+        assert_eq!(parsed[1].fields[0], "CAM-001");
+        assert_eq!(parsed[1].fields[2], product.name);
+        assert_eq!(parsed[1].fields[4], "Linha 1\nLinha 2");
+        assert_eq!(parsed[1].fields[5], "49.90");
+        assert_eq!(parsed[1].fields[8], "{\"cor\":\"azul\"}");
+    }
+
+    // ===== VALIDAÇÃO DE LINHAS =====
+
+    #[test]
+    fn test_price_parsing() {
+        assert_eq!(parse_price("49.90"), Ok(4990));
+        assert_eq!(parse_price("49,9"), Ok(4990));
+        assert_eq!(parse_price("7"), Ok(700));
+        assert!(parse_price("-1").is_err());
+        assert!(parse_price("1.234,56").is_err());
+        assert!(parse_price("4.999").is_err());
+        assert!(parse_price("abc").is_err());
+        assert!(parse_price("99999999999").is_err());
+    }
+
+    #[test]
+    fn test_exported_rows_are_importable() {
+        let product = product();
+        let expected = ImportRow {
+            line: 2,
+            sku: Some("CAM-001".to_string()),
+            slug: Some("camiseta-basica-algodao".to_string()),
+            name: Some(product.name.clone()),
+            short_description: None,
+            description: Some("Linha 1\nLinha 2".to_string()),
+            price: Some(4990),
+            stock_quantity: Some(12),
+            is_active: Some(false),
+            attributes: Some(json!({"cor": "azul"})),
+        };
+
+        let csv_data = csv_header() + &to_csv_line(&product);
+        let mut rows = parse_rows(TransferFormat::Csv, &csv_data).unwrap();
+        assert_eq!(validate_row(rows.remove(0)).unwrap(), expected);
+
+        let mut rows = parse_rows(TransferFormat::Jsonl, &to_jsonl_line(&product)).unwrap();
+        let row = validate_row(rows.remove(0)).unwrap();
+        assert_eq!(
+            row,
+            ImportRow {
+                line: 1,
+                ..expected
+            }
+        );
+    }
+
+    #[test]
+    fn test_row_errors_list_every_field() {
+        let data = "sku,name,price,stock_quantity,is_active,attributes\n\
+                    SKU 1,Produto,dez,-2,talvez,[1]\n";
+        let mut rows = parse_rows(TransferFormat::Csv, data).unwrap();
+        let error = validate_row(rows.remove(0)).unwrap_err();
+
+        assert_eq!(error.row, 2);
+        assert_eq!(error.key.as_deref(), Some("SKU 1"));
+        let fields: Vec<_> = error
+            .errors
+            .iter()
+            .map(|e| e.field.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            fields,
+            vec!["sku", "price", "stock_quantity", "is_active", "attributes"]
+        );
+    }
+
+    #[test]
+    fn test_structural_problems() {
+        // Cabeçalho sem coluna de identificação
+        assert!(parse_rows(TransferFormat::Csv, "price,stock_quantity\n1,2").is_err());
+        // Coluna repetida
+        assert!(parse_rows(TransferFormat::Csv, "sku,SKU\nA,B").is_err());
+
+        // Quantidade de colunas e JSON inválido afetam só a linha
+        let mut rows = parse_rows(TransferFormat::Csv, "sku,name\nA\n").unwrap();
+        assert!(validate_row(rows.remove(0)).is_err());
+
+        let rows = parse_rows(TransferFormat::Jsonl, "{\"sku\": \"A\"}\n{quebrado\n[1]\n").unwrap();
+        assert_eq!(rows.len(), 3);
+        let results: Vec<_> = rows.into_iter().map(validate_row).collect();
+        assert!(results[0].is_ok());
+        assert_eq!(results[1].as_ref().unwrap_err().row, 2);
+        assert!(results[2].is_err());
+    }
+
+    #[test]
+    fn test_transfer_format() {
+        assert_eq!(
+            TransferFormat::from_str("NDJSON"),
+            Ok(TransferFormat::Jsonl)
+        );
+        assert!(TransferFormat::from_str("xlsx").is_err());
+        assert_eq!(
+            TransferFormat::from_content_type("text/csv; charset=utf-8"),
+            Some(TransferFormat::Csv)
+        );
+        assert_eq!(TransferFormat::from_content_type("application/json"), None);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::utils::currency::Rounding;
+use crate::utils::money::{Money, MoneyError};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+use uuid::Uuid;
+
+/// Origem do registro no histórico de preços
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceKind {
+    /// Alteração do preço base do produto (cadastro, edição, importação)
+    Base,
+    /// Preço com início (e opcionalmente fim) agendados, ex: Black Friday
+    Scheduled,
+}
+
+impl PriceKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriceKind::Base => "base",
+            PriceKind::Scheduled => "scheduled",
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductPrice {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub product_id: Uuid,
+    pub kind: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub price: BigDecimal,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub compare_at_price: Option<BigDecimal>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    /// Usuário que fez a alteração (nulo em registros migrados)
+    pub created_by: Option<Uuid>,
+    pub dt_created: DateTime<Utc>,
+    /// Agendamento cancelado
+    pub dt_deleted: Option<DateTime<Utc>>,
+}
+
+/// POST /products/{id}/prices/ - valores em centavos, como no cadastro do produto
+#[derive(Debug, Deserialize, Clone)]
+pub struct SchedulePriceRequest {
+    pub price: i64,
+    pub compare_at_price: Option<i64>,
+    pub starts_at: DateTime<Utc>,
+    /// Sem fim: vale até ser cancelado
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+/// Preço cobrado agora e, durante uma promoção, o preço "de" para exibir riscado,
+/// na moeda base da loja
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EffectivePrice {
+    #[serde(rename = "effective_price")]
+    pub price: Money,
+    pub compare_at_price: Option<Money>,
+    /// Fim da promoção vigente, quando houver
+    pub sale_ends_at: Option<DateTime<Utc>>,
+}
+
+impl EffectivePrice {
+    /// Preço base, a menos que um agendamento esteja vigente (mesma moeda do preço base)
+    pub fn resolve(base_price: &Money, active: Option<&ProductPrice>) -> Result<Self, MoneyError> {
+        let money = |amount: &BigDecimal| {
+            Money::from_decimal(amount, &base_price.currency, Rounding::HalfEven)
+        };
+        match active {
+            Some(scheduled) => {
+                let price = money(&scheduled.price)?;
+                let compare_at = match &scheduled.compare_at_price {
+                    Some(compare_at) => money(compare_at)?,
+                    None => base_price.clone(),
+                };
+                Ok(Self {
+                    compare_at_price: (compare_at.amount_minor > price.amount_minor)
+                        .then_some(compare_at),
+                    price,
+                    sale_ends_at: scheduled.ends_at,
+                })
+            }
+            None => Ok(Self {
+                price: base_price.clone(),
+                compare_at_price: None,
+                sale_ends_at: None,
+            }),
+        }
+    }
+}
+
+/// Preço fixo do produto em outra moeda; quando existe, dispensa a conversão pela cotação
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductCurrencyPrice {
+    pub product_id: Uuid,
+    pub currency: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub price: BigDecimal,
+    pub dt_updated: DateTime<Utc>,
+}
+
+/// Item de PUT /products/{id}/currency-prices/ - preço na unidade mínima da moeda (centavos em USD)
+#[derive(Debug, Deserialize, Clone)]
+pub struct CurrencyPriceInput {
+    pub currency: String,
+    pub price: i64,
+}
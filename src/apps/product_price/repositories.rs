@@ -0,0 +1,293 @@
+use crate::app_core::app_state::AppState;
+use crate::apps::product_price::models::{PriceKind, ProductCurrencyPrice, ProductPrice};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use tracing::instrument;
+use uuid::Uuid;
+
+pub struct ProductPriceRepository<'a> {
+    app_state: &'a AppState,
+}
+
+impl<'a> ProductPriceRepository<'a> {
+    pub fn new(app_state: &'a AppState) -> Self {
+        Self { app_state }
+    }
+
+    /// Registra uma alteração do preço base dentro da transação que alterou o produto
+    #[instrument(name = "ProductPriceRepository::record_base_price", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn record_base_price(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        tenant_id: Uuid,
+        product_id: Uuid,
+        price: &BigDecimal,
+        created_by: Option<Uuid>,
+        at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO product_prices (id, tenant_id, product_id, kind, price, starts_at, created_by, dt_created)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $6)
+            "#,
+            Uuid::new_v4(),
+            tenant_id,
+            product_id,
+            PriceKind::Base.as_str(),
+            price,
+            at.naive_utc(),
+            created_by
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "ProductPriceRepository::create_scheduled", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create_scheduled(
+        &self,
+        price: &ProductPrice,
+    ) -> Result<ProductPrice, sqlx::Error> {
+        sqlx::query_as!(
+            ProductPrice,
+            r#"
+            INSERT INTO product_prices (
+                id, tenant_id, product_id, kind, price, compare_at_price,
+                starts_at, ends_at, created_by, dt_created
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING
+                id, tenant_id, product_id, kind, price, compare_at_price,
+                (starts_at AT TIME ZONE 'UTC') as "starts_at!: DateTime<Utc>",
+                (ends_at AT TIME ZONE 'UTC') as "ends_at?: DateTime<Utc>",
+                created_by,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            "#,
+            price.id,
+            price.tenant_id,
+            price.product_id,
+            PriceKind::Scheduled.as_str(),
+            price.price,
+            price.compare_at_price,
+            price.starts_at.naive_utc(),
+            price.ends_at.map(|dt| dt.naive_utc()),
+            price.created_by,
+            price.dt_created.naive_utc()
+        )
+        .fetch_one(&self.app_state.db)
+        .await
+    }
+
+    /// Histórico completo do produto (inclusive agendamentos cancelados), mais recentes primeiro
+    #[instrument(name = "ProductPriceRepository::find_by_product", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_product(
+        &self,
+        product_id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<Vec<ProductPrice>, sqlx::Error> {
+        sqlx::query_as!(
+            ProductPrice,
+            r#"
+            SELECT
+                id, tenant_id, product_id, kind, price, compare_at_price,
+                (starts_at AT TIME ZONE 'UTC') as "starts_at!: DateTime<Utc>",
+                (ends_at AT TIME ZONE 'UTC') as "ends_at?: DateTime<Utc>",
+                created_by,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            FROM product_prices
+            WHERE product_id = $1 AND tenant_id = $2
+            ORDER BY dt_created DESC, id DESC
+            "#,
+            product_id,
+            tenant_id
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    /// Agendamentos vigentes em `at` (no máximo um por produto, já que não se sobrepõem)
+    #[instrument(name = "ProductPriceRepository::find_active", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_active(
+        &self,
+        product_ids: &[Uuid],
+        at: DateTime<Utc>,
+    ) -> Result<Vec<ProductPrice>, sqlx::Error> {
+        if product_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        sqlx::query_as!(
+            ProductPrice,
+            r#"
+            SELECT DISTINCT ON (product_id)
+                id, tenant_id, product_id, kind, price, compare_at_price,
+                (starts_at AT TIME ZONE 'UTC') as "starts_at!: DateTime<Utc>",
+                (ends_at AT TIME ZONE 'UTC') as "ends_at?: DateTime<Utc>",
+                created_by,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            FROM product_prices
+            WHERE product_id = ANY($1)
+              AND kind = $2
+              AND dt_deleted IS NULL
+              AND starts_at <= $3
+              AND (ends_at IS NULL OR ends_at > $3)
+            ORDER BY product_id, starts_at DESC
+            "#,
+            product_ids,
+            PriceKind::Scheduled.as_str(),
+            at.naive_utc()
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    /// Já existe agendamento ativo do produto que cruza a janela informada?
+    #[instrument(name = "ProductPriceRepository::has_overlap", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn has_overlap(
+        &self,
+        product_id: Uuid,
+        starts_at: DateTime<Utc>,
+        ends_at: Option<DateTime<Utc>>,
+    ) -> Result<bool, sqlx::Error> {
+        let overlap = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM product_prices
+                WHERE product_id = $1
+                  AND kind = $2
+                  AND dt_deleted IS NULL
+                  AND starts_at < COALESCE($4, 'infinity'::timestamp)
+                  AND COALESCE(ends_at, 'infinity'::timestamp) > $3
+            ) as "exists!"
+            "#,
+            product_id,
+            PriceKind::Scheduled.as_str(),
+            starts_at.naive_utc(),
+            ends_at.map(|dt| dt.naive_utc())
+        )
+        .fetch_one(&self.app_state.db)
+        .await?;
+
+        Ok(overlap)
+    }
+
+    /// Cancela um agendamento que ainda não terminou
+    #[instrument(name = "ProductPriceRepository::cancel", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn cancel(
+        &self,
+        id: Uuid,
+        product_id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<Option<ProductPrice>, sqlx::Error> {
+        sqlx::query_as!(
+            ProductPrice,
+            r#"
+            UPDATE product_prices SET dt_deleted = $5
+            WHERE id = $1 AND product_id = $2 AND tenant_id = $3
+              AND kind = $4
+              AND dt_deleted IS NULL
+              AND (ends_at IS NULL OR ends_at > $5)
+            RETURNING
+                id, tenant_id, product_id, kind, price, compare_at_price,
+                (starts_at AT TIME ZONE 'UTC') as "starts_at!: DateTime<Utc>",
+                (ends_at AT TIME ZONE 'UTC') as "ends_at?: DateTime<Utc>",
+                created_by,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_deleted AT TIME ZONE 'UTC') as "dt_deleted?: DateTime<Utc>"
+            "#,
+            id,
+            product_id,
+            tenant_id,
+            PriceKind::Scheduled.as_str(),
+            Utc::now().naive_utc()
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "ProductPriceRepository::find_currency_prices", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_currency_prices(
+        &self,
+        product_id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<Vec<ProductCurrencyPrice>, sqlx::Error> {
+        sqlx::query_as!(
+            ProductCurrencyPrice,
+            r#"
+            SELECT
+                product_id, currency, price,
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            FROM product_currency_prices
+            WHERE product_id = $1 AND tenant_id = $2
+            ORDER BY currency
+            "#,
+            product_id,
+            tenant_id
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "ProductPriceRepository::find_currency_price", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_currency_price(
+        &self,
+        product_id: Uuid,
+        currency: &str,
+    ) -> Result<Option<ProductCurrencyPrice>, sqlx::Error> {
+        sqlx::query_as!(
+            ProductCurrencyPrice,
+            r#"
+            SELECT
+                product_id, currency, price,
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            FROM product_currency_prices
+            WHERE product_id = $1 AND currency = $2
+            "#,
+            product_id,
+            currency
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    /// Substitui a tabela de preços por moeda do produto (moedas fora da lista são removidas)
+    #[instrument(name = "ProductPriceRepository::replace_currency_prices", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn replace_currency_prices(
+        &self,
+        product_id: Uuid,
+        tenant_id: Uuid,
+        prices: &[(String, BigDecimal)],
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now().naive_utc();
+        let mut tx = self.app_state.db.begin().await?;
+
+        sqlx::query!(
+            "DELETE FROM product_currency_prices WHERE product_id = $1 AND tenant_id = $2",
+            product_id,
+            tenant_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for (currency, price) in prices {
+            sqlx::query!(
+                r#"
+                INSERT INTO product_currency_prices (product_id, tenant_id, currency, price, dt_created, dt_updated)
+                VALUES ($1, $2, $3, $4, $5, $5)
+                "#,
+                product_id,
+                tenant_id,
+                currency,
+                price,
+                now
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await
+    }
+}
@@ -0,0 +1,94 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_extensions::RequestUserExt;
+use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::AuditContext;
+use crate::apps::product_price::models::{CurrencyPriceInput, SchedulePriceRequest};
+use crate::apps::product_price::services::ProductPriceService;
+use actix_web::web::Json;
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+use uuid::Uuid;
+
+/// GET /products/{id}/prices/ - histórico de preços, mais recentes primeiro
+pub async fn list_product_prices(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let product_id = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let result = ProductPriceService::list_prices(&app_state, product_id, tenant_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /products/{id}/prices/ - agenda um preço (ex: promoção com início e fim)
+pub async fn schedule_product_price(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Json<SchedulePriceRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let product_id = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let audit = AuditContext::from_request(&req);
+    let result = ProductPriceService::schedule_price(
+        &app_state,
+        product_id,
+        tenant_id,
+        payload.into_inner(),
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(serde_json::json!(result)))
+}
+
+/// DELETE /products/{id}/prices/{price_id}/ - cancela um agendamento não encerrado
+pub async fn cancel_product_price(
+    app_state: web::Data<AppState>,
+    path: web::Path<(Uuid, Uuid)>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let (product_id, price_id) = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let audit = AuditContext::from_request(&req);
+    ProductPriceService::cancel_price(&app_state, product_id, price_id, tenant_id, &audit).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// GET /products/{id}/currency-prices/ - preços fixos do produto em outras moedas
+pub async fn list_product_currency_prices(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let product_id = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let result =
+        ProductPriceService::list_currency_prices(&app_state, product_id, tenant_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// PUT /products/{id}/currency-prices/ - substitui a tabela (`[]` remove todos)
+pub async fn set_product_currency_prices(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Json<Vec<CurrencyPriceInput>>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let product_id = path.into_inner();
+    let tenant_id = req.tenant_id()?;
+    let audit = AuditContext::from_request(&req);
+    let result = ProductPriceService::set_currency_prices(
+        &app_state,
+        product_id,
+        tenant_id,
+        payload.into_inner(),
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
@@ -0,0 +1,329 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::{AuditAction, AuditContext, AuditEntity};
+use crate::apps::audit::services::AuditService;
+use crate::apps::exchange_rate::services::ExchangeRateService;
+use crate::apps::product::models::Product;
+use crate::apps::product::repositories::ProductRepository;
+use crate::apps::product_price::models::{
+    CurrencyPriceInput, EffectivePrice, PriceKind, ProductCurrencyPrice, ProductPrice,
+    SchedulePriceRequest,
+};
+use crate::apps::product_price::repositories::ProductPriceRepository;
+use crate::apps::tenant::services::TenantService;
+use crate::utils::currency::{CONVERSION_ROUNDING, normalize_currency};
+use crate::utils::money::Money;
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Janela sobreposta que passou pela checagem prévia (`ex_product_prices_scheduled_overlap`)
+fn map_schedule_error(e: sqlx::Error) -> AppError {
+    match e.as_database_error().and_then(|db| db.code()).as_deref() {
+        Some("23P01") => overlap_conflict(),
+        _ => AppError::database_error(e.to_string()),
+    }
+}
+
+fn overlap_conflict() -> AppError {
+    AppError::Conflict(Some("Já existe um preço agendado neste período".into()))
+}
+
+/// Regras do agendamento: valores não negativos, preço "de" acima do preço e janela futura
+pub fn validate_schedule(
+    request: &SchedulePriceRequest,
+    now: DateTime<Utc>,
+) -> Result<(), AppError> {
+    if request.price < 0 {
+        return Err(AppError::bad_request("Preço não pode ser negativo"));
+    }
+
+    if let Some(compare_at) = request.compare_at_price
+        && compare_at <= request.price
+    {
+        return Err(AppError::bad_request(
+            "compare_at_price deve ser maior que o preço agendado",
+        ));
+    }
+
+    if let Some(ends_at) = request.ends_at {
+        if ends_at <= request.starts_at {
+            return Err(AppError::bad_request(
+                "ends_at deve ser posterior a starts_at",
+            ));
+        }
+        if ends_at <= now {
+            return Err(AppError::bad_request("O agendamento já terminou"));
+        }
+    }
+    Ok(())
+}
+
+/// Tabela de preços por moeda: códigos válidos, sem repetição, fora da moeda base
+/// (que já é o preço do produto) e valores não negativos
+pub fn validate_currency_prices(
+    inputs: &[CurrencyPriceInput],
+    base_currency: &str,
+) -> Result<Vec<(String, BigDecimal)>, AppError> {
+    let mut prices: Vec<(String, BigDecimal)> = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let currency = normalize_currency(&input.currency)?;
+        if currency == base_currency {
+            return Err(AppError::bad_request(format!(
+                "{} é a moeda base da loja; altere o preço do produto",
+                currency
+            )));
+        }
+        if prices.iter().any(|(existing, _)| *existing == currency) {
+            return Err(AppError::bad_request(format!(
+                "Moeda repetida: {}",
+                currency
+            )));
+        }
+        if input.price < 0 {
+            return Err(AppError::bad_request("Preço não pode ser negativo"));
+        }
+        let price = Money::new(input.price, currency.as_str()).to_decimal();
+        prices.push((currency, price));
+    }
+    Ok(prices)
+}
+
+pub struct ProductPriceService;
+
+impl ProductPriceService {
+    /// Preço efetivo de cada produto agora (base ou agendamento vigente), na ordem recebida
+    pub async fn effective_prices(
+        app_state: &AppState,
+        products: &[Product],
+    ) -> Result<Vec<EffectivePrice>, AppError> {
+        let ids: Vec<Uuid> = products.iter().map(|p| p.id).collect();
+        let mut active: HashMap<Uuid, ProductPrice> = ProductPriceRepository::new(app_state)
+            .find_active(&ids, Utc::now())
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .into_iter()
+            .map(|price| (price.product_id, price))
+            .collect();
+
+        products
+            .iter()
+            .map(|product| {
+                let scheduled = active.remove(&product.id);
+                Ok(EffectivePrice::resolve(&product.price, scheduled.as_ref())?)
+            })
+            .collect()
+    }
+
+    pub async fn effective_price(
+        app_state: &AppState,
+        product: &Product,
+    ) -> Result<EffectivePrice, AppError> {
+        Self::effective_prices(app_state, std::slice::from_ref(product))
+            .await?
+            .pop()
+            .ok_or_else(|| AppError::not_found("Produto não encontrado"))
+    }
+
+    pub async fn list_prices(
+        app_state: &AppState,
+        product_id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<Vec<ProductPrice>, AppError> {
+        Self::ensure_product(app_state, product_id, tenant_id).await?;
+
+        ProductPriceRepository::new(app_state)
+            .find_by_product(product_id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    /// Agenda um preço (promoção); janelas do mesmo produto não podem se sobrepor
+    pub async fn schedule_price(
+        app_state: &AppState,
+        product_id: Uuid,
+        tenant_id: Uuid,
+        request: SchedulePriceRequest,
+        audit: &AuditContext,
+    ) -> Result<ProductPrice, AppError> {
+        let now = Utc::now();
+        validate_schedule(&request, now)?;
+        Self::ensure_product(app_state, product_id, tenant_id).await?;
+        // Valores chegam na unidade mínima da moeda base da loja
+        let currency = TenantService::base_currency(app_state, tenant_id).await?;
+        let to_decimal = |amount: i64| Money::new(amount, currency.as_str()).to_decimal();
+
+        let repository = ProductPriceRepository::new(app_state);
+        let overlap = repository
+            .has_overlap(product_id, request.starts_at, request.ends_at)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        if overlap {
+            return Err(overlap_conflict());
+        }
+
+        let price = repository
+            .create_scheduled(&ProductPrice {
+                id: Uuid::new_v4(),
+                tenant_id,
+                product_id,
+                kind: PriceKind::Scheduled.as_str().to_string(),
+                price: to_decimal(request.price),
+                compare_at_price: request.compare_at_price.map(to_decimal),
+                starts_at: request.starts_at,
+                ends_at: request.ends_at,
+                created_by: audit.actor_id,
+                dt_created: now,
+                dt_deleted: None,
+            })
+            .await
+            .map_err(map_schedule_error)?;
+
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Create,
+            AuditEntity::ProductPrice,
+            Some(price.id),
+            None,
+            AuditService::snapshot(&price),
+        )
+        .await;
+
+        Ok(price)
+    }
+
+    /// Cancela um agendamento ainda não encerrado (vigente ou futuro)
+    pub async fn cancel_price(
+        app_state: &AppState,
+        product_id: Uuid,
+        price_id: Uuid,
+        tenant_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<(), AppError> {
+        let canceled = ProductPriceRepository::new(app_state)
+            .cancel(price_id, product_id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        match canceled {
+            Some(price) => {
+                AuditService::record(
+                    app_state,
+                    audit,
+                    AuditAction::Delete,
+                    AuditEntity::ProductPrice,
+                    Some(price.id),
+                    AuditService::snapshot(&price),
+                    None,
+                )
+                .await;
+
+                Ok(())
+            }
+            None => Err(AppError::not_found("Preço agendado não encontrado")),
+        }
+    }
+
+    async fn ensure_product(
+        app_state: &AppState,
+        product_id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<(), AppError> {
+        let product = ProductRepository::new(app_state)
+            .find_in_tenant(product_id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .filter(|product| product.dt_deleted.is_none());
+
+        match product {
+            Some(_) => Ok(()),
+            None => Err(AppError::not_found("Produto não encontrado")),
+        }
+    }
+
+    pub async fn list_currency_prices(
+        app_state: &AppState,
+        product_id: Uuid,
+        tenant_id: Uuid,
+    ) -> Result<Vec<ProductCurrencyPrice>, AppError> {
+        Self::ensure_product(app_state, product_id, tenant_id).await?;
+
+        ProductPriceRepository::new(app_state)
+            .find_currency_prices(product_id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    /// Substitui a tabela de preços por moeda do produto
+    pub async fn set_currency_prices(
+        app_state: &AppState,
+        product_id: Uuid,
+        tenant_id: Uuid,
+        inputs: Vec<CurrencyPriceInput>,
+        audit: &AuditContext,
+    ) -> Result<Vec<ProductCurrencyPrice>, AppError> {
+        Self::ensure_product(app_state, product_id, tenant_id).await?;
+        let base_currency = TenantService::base_currency(app_state, tenant_id).await?;
+        let prices = validate_currency_prices(&inputs, &base_currency)?;
+
+        let repository = ProductPriceRepository::new(app_state);
+        let before = repository
+            .find_currency_prices(product_id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        repository
+            .replace_currency_prices(product_id, tenant_id, &prices)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        let after = repository
+            .find_currency_prices(product_id, tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Update,
+            AuditEntity::ProductPrice,
+            Some(product_id),
+            AuditService::snapshot(&before),
+            AuditService::snapshot(&after),
+        )
+        .await;
+
+        Ok(after)
+    }
+
+    /// Preço do produto em `currency`: preço efetivo na moeda base da loja, o preço fixo
+    /// da tabela por moeda quando existe, ou a conversão pela cotação vigente
+    pub async fn price_in_currency(
+        app_state: &AppState,
+        product: &Product,
+        currency: &str,
+    ) -> Result<Money, AppError> {
+        let effective = Self::effective_price(app_state, product).await?.price;
+        if currency == effective.currency {
+            return Ok(effective);
+        }
+
+        let listed = ProductPriceRepository::new(app_state)
+            .find_currency_price(product.id, currency)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        let amount = match listed {
+            Some(listed) => listed.price,
+            None => {
+                ExchangeRateService::convert(
+                    app_state,
+                    &effective.to_decimal(),
+                    &effective.currency,
+                    currency,
+                )
+                .await?
+            }
+        };
+        Ok(Money::from_decimal(&amount, currency, CONVERSION_ROUNDING)?)
+    }
+}
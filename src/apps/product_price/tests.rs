@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use crate::apps::product_price::models::{
+        CurrencyPriceInput, EffectivePrice, PriceKind, ProductPrice, SchedulePriceRequest,
+    };
+    use crate::apps::product_price::services::{validate_currency_prices, validate_schedule};
+    use crate::utils::money::Money;
+    use bigdecimal::BigDecimal;
+    use chrono::{Duration, Utc};
+    use std::str::FromStr;
+    use uuid::Uuid;
+
+    fn decimal(value: &str) -> BigDecimal {
+        BigDecimal::from_str(value).unwrap()
+    }
+
+    fn scheduled(price: &str, compare_at: Option<&str>) -> ProductPrice {
+        let now = Utc::now();
+        ProductPrice {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            product_id: Uuid::new_v4(),
+            kind: PriceKind::Scheduled.as_str().to_string(),
+            price: decimal(price),
+            compare_at_price: compare_at.map(decimal),
+            starts_at: now - Duration::hours(1),
+            ends_at: Some(now + Duration::hours(1)),
+            created_by: None,
+            dt_created: now,
+            dt_deleted: None,
+        }
+    }
+
+    // ===== PREÇO EFETIVO =====
+
+    #[test]
+    fn test_effective_price_without_schedule_is_base_price() {
+        let pricing = EffectivePrice::resolve(&Money::new(9990, "BRL"), None).unwrap();
+
+        assert_eq!(pricing.price, Money::new(9990, "BRL"));
+        assert!(pricing.compare_at_price.is_none());
+        assert!(pricing.sale_ends_at.is_none());
+    }
+
+    #[test]
+    fn test_effective_price_with_active_schedule() {
+        // Sem preço "de" explícito, compara com o preço base
+        let sale = scheduled("59.90", None);
+        let pricing = EffectivePrice::resolve(&Money::new(9990, "BRL"), Some(&sale)).unwrap();
+        assert_eq!(pricing.price, Money::new(5990, "BRL"));
+        assert_eq!(pricing.compare_at_price, Some(Money::new(9990, "BRL")));
+        assert_eq!(pricing.sale_ends_at, sale.ends_at);
+
+        let sale = scheduled("59.90", Some("129.90"));
+        let pricing = EffectivePrice::resolve(&Money::new(9990, "BRL"), Some(&sale)).unwrap();
+        assert_eq!(pricing.compare_at_price, Some(Money::new(12990, "BRL")));
+
+        // Agendamento acima do preço base não exibe preço riscado
+        let increase = scheduled("120.00", None);
+        let pricing = EffectivePrice::resolve(&Money::new(9990, "BRL"), Some(&increase)).unwrap();
+        assert_eq!(pricing.price, Money::new(12000, "BRL"));
+        assert!(pricing.compare_at_price.is_none());
+
+        let json = serde_json::to_value(&pricing).unwrap();
+        assert_eq!(json["effective_price"]["amount_minor"], 12000);
+        assert_eq!(json["effective_price"]["formatted"], "BRL 120.00");
+    }
+
+    // ===== VALIDAÇÃO DO AGENDAMENTO =====
+
+    #[test]
+    fn test_validate_schedule() {
+        let now = Utc::now();
+        let valid = SchedulePriceRequest {
+            price: 5990,
+            compare_at_price: Some(9990),
+            starts_at: now + Duration::days(1),
+            ends_at: Some(now + Duration::days(2)),
+        };
+        assert!(validate_schedule(&valid, now).is_ok());
+        assert!(
+            validate_schedule(
+                &SchedulePriceRequest {
+                    ends_at: None,
+                    ..valid.clone()
+                },
+                now
+            )
+            .is_ok()
+        );
+
+        let invalid = [
+            SchedulePriceRequest {
+                price: -1,
+                ..valid.clone()
+            },
+            SchedulePriceRequest {
+                compare_at_price: Some(5990),
+                ..valid.clone()
+            },
+            SchedulePriceRequest {
+                ends_at: Some(valid.starts_at),
+                ..valid.clone()
+            },
+            SchedulePriceRequest {
+                starts_at: now - Duration::days(2),
+                ends_at: Some(now - Duration::days(1)),
+                ..valid.clone()
+            },
+        ];
+        for request in invalid {
+            let error = validate_schedule(&request, now).unwrap_err();
+            assert_eq!(error.code(), "BAD_REQUEST", "{:?}", request);
+        }
+    }
+
+    #[test]
+    fn test_validate_currency_prices() {
+        let input = |currency: &str, price: i64| CurrencyPriceInput {
+            currency: currency.to_string(),
+            price,
+        };
+
+        let prices =
+            validate_currency_prices(&[input("usd", 1999), input("JPY", 3000)], "BRL").unwrap();
+        assert_eq!(prices[0], ("USD".to_string(), decimal("19.99")));
+        assert_eq!(prices[1], ("JPY".to_string(), decimal("3000")));
+
+        for invalid in [
+            vec![input("BRL", 1000)],
+            vec![input("USD", 1000), input("usd", 1100)],
+            vec![input("USD", -1)],
+            vec![input("DOLAR", 1000)],
+        ] {
+            let error = validate_currency_prices(&invalid, "BRL").unwrap_err();
+            assert_eq!(error.code(), "BAD_REQUEST", "{:?}", invalid);
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use crate::app_core::app_state::AppState;
+use crate::app_core::request_id::RequestIdExt;
+use crate::app_core::telemetry::{TraceContextPropagationExt, record_orchestrator_sync};
 use crate::apps::orchestrator::repositories::OrchestratorRepository;
 use crate::apps::user::models::UserWithProfile;
 use reqwest::Client;
 use serde_json;
-use tracing::{error, info, warn};
+use tracing::{Instrument, error, info, info_span, warn};
 
 pub struct SyncProducer;
 
@@ -95,13 +97,29 @@ impl SyncProducer {
                 orchestrator.app_name, url
             );
 
-            let response = client
-                .post(&url)
-                .header("Content-Type", "application/json")
-                .header("Authorization", format!("Token {}", orchestrator.app_token))
-                .json(&msg_data)
-                .send()
-                .await;
+            // Span de cliente: o traceparent enviado aponta para ele
+            let span = info_span!(
+                "orchestrator_sync",
+                otel.kind = "client",
+                app_name = %orchestrator.app_name,
+                http.url = %url
+            );
+            let response = async {
+                client
+                    .post(&url)
+                    .header("Content-Type", "application/json")
+                    .header("Authorization", format!("Token {}", orchestrator.app_token))
+                    .json(&msg_data)
+                    .with_request_id()
+                    .with_trace_context()
+                    .send()
+                    .await
+            }
+            .instrument(span)
+            .await;
+
+            let success = matches!(&response, Ok(resp) if resp.status().is_success());
+            record_orchestrator_sync(&orchestrator.app_name, success);
 
             match response {
                 Ok(resp) => {
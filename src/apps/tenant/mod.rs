@@ -1,5 +1,7 @@
 pub mod models;
 pub mod repositories;
+pub mod routes;
+pub mod services;
 
 #[cfg(test)]
 mod tests;
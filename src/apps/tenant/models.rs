@@ -8,12 +8,23 @@ pub struct Tenant {
     pub id: Uuid,
     pub user_id: Uuid,
     pub tenant_type: String,
+    /// Identificador público da loja nas rotas da vitrine
+    pub slug: String,
+    /// Moeda do preço dos produtos (ISO 4217) e padrão dos carrinhos da loja
+    pub base_currency: String,
     pub dt_created: DateTime<Utc>,
     pub dt_updated: DateTime<Utc>,
     #[serde(skip_serializing)]
     pub dt_deleted: Option<DateTime<Utc>>,
 }
 
+impl Tenant {
+    /// Slug inicial da loja, derivado do id ("loja-1a2b3c4d5e6f")
+    pub fn default_slug(id: Uuid) -> String {
+        format!("loja-{}", &id.simple().to_string()[..12])
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 pub struct CreateTenantRequest {
@@ -27,10 +38,11 @@ pub struct CreateTenantRequest {
 
 #[derive(Debug, Deserialize)]
 pub struct UpdateTenantRequest {
-    // Adicione os campos necessários aqui
+    pub base_currency: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
+#[allow(dead_code)]
 pub struct TenantResponse {
     pub id: Uuid,
     pub user_id: Uuid,
@@ -1,9 +1,20 @@
 use crate::app_core::app_state::AppState;
 use crate::apps::tenant::models::{Tenant, UpdateTenantRequest};
 use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 // use sqlx::Row;
+use tracing::instrument;
 use uuid::Uuid;
 
+/// Resultado da alteração da loja; nada é gravado fora de `Updated`
+#[derive(Debug)]
+pub enum UpdateTenantOutcome {
+    Updated(Tenant),
+    NotFound,
+    /// Troca de moeda base com produtos ou carrinhos abertos precificados na moeda atual
+    CurrencyInUse,
+}
+
 pub struct TenantRepository<'a> {
     app_state: &'a AppState,
 }
@@ -14,9 +25,10 @@ impl<'a> TenantRepository<'a> {
         Self { app_state }
     }
 
+    #[instrument(name = "TenantRepository::find_all", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_all(&self) -> Result<Vec<Tenant>, sqlx::Error> {
         let rows = sqlx::query!(
-            "SELECT id, user_id, tenant_type, dt_created, dt_updated, dt_deleted FROM tenants WHERE dt_deleted IS NULL"
+            "SELECT id, user_id, tenant_type, slug, base_currency, dt_created, dt_updated, dt_deleted FROM tenants WHERE dt_deleted IS NULL"
         )
         .fetch_all(&self.app_state.db)
         .await?;
@@ -27,6 +39,8 @@ impl<'a> TenantRepository<'a> {
                 id: row.id,
                 user_id: row.user_id,
                 tenant_type: row.tenant_type,
+                slug: row.slug,
+                base_currency: row.base_currency,
                 dt_created: DateTime::from_naive_utc_and_offset(row.dt_created, Utc),
                 dt_updated: DateTime::from_naive_utc_and_offset(row.dt_updated, Utc),
                 dt_deleted: row
@@ -38,9 +52,10 @@ impl<'a> TenantRepository<'a> {
         Ok(tenants)
     }
 
+    #[instrument(name = "TenantRepository::find_by_id", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Tenant>, sqlx::Error> {
         let row = sqlx::query!(
-            "SELECT id, user_id, tenant_type, dt_created, dt_updated, dt_deleted FROM tenants WHERE id = $1 AND dt_deleted IS NULL",
+            "SELECT id, user_id, tenant_type, slug, base_currency, dt_created, dt_updated, dt_deleted FROM tenants WHERE id = $1 AND dt_deleted IS NULL",
             id
         )
         .fetch_optional(&self.app_state.db)
@@ -50,6 +65,8 @@ impl<'a> TenantRepository<'a> {
             id: row.id,
             user_id: row.user_id,
             tenant_type: row.tenant_type,
+            slug: row.slug,
+            base_currency: row.base_currency,
             dt_created: DateTime::from_naive_utc_and_offset(row.dt_created, Utc),
             dt_updated: DateTime::from_naive_utc_and_offset(row.dt_updated, Utc),
             dt_deleted: row
@@ -58,6 +75,50 @@ impl<'a> TenantRepository<'a> {
         }))
     }
 
+    /// Loja pelo slug público (rotas da vitrine)
+    #[instrument(name = "TenantRepository::find_by_slug", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_slug(&self, slug: &str) -> Result<Option<Tenant>, sqlx::Error> {
+        let row = sqlx::query!(
+            "SELECT id, user_id, tenant_type, slug, base_currency, dt_created, dt_updated, dt_deleted FROM tenants WHERE slug = $1 AND dt_deleted IS NULL",
+            slug
+        )
+        .fetch_optional(&self.app_state.db)
+        .await?;
+
+        Ok(row.map(|row| Tenant {
+            id: row.id,
+            user_id: row.user_id,
+            tenant_type: row.tenant_type,
+            slug: row.slug,
+            base_currency: row.base_currency,
+            dt_created: DateTime::from_naive_utc_and_offset(row.dt_created, Utc),
+            dt_updated: DateTime::from_naive_utc_and_offset(row.dt_updated, Utc),
+            dt_deleted: row
+                .dt_deleted
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+        }))
+    }
+
+    /// Moeda base de cada loja (preços dos produtos estão nela)
+    #[instrument(name = "TenantRepository::find_base_currencies", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_base_currencies(
+        &self,
+        ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, String>, sqlx::Error> {
+        let rows = sqlx::query!(
+            "SELECT id, base_currency FROM tenants WHERE id = ANY($1)",
+            ids
+        )
+        .fetch_all(&self.app_state.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.id, row.base_currency))
+            .collect())
+    }
+
+    #[instrument(name = "TenantRepository::find_by_user_id", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_user_id(&self, user_id: Uuid) -> Result<Tenant, sqlx::Error> {
         if let Some(tenant_user) = sqlx::query!(
             "SELECT id, user_id, tenant_id, dt_created, dt_updated, dt_deleted FROM tenant_users WHERE user_id = $1 AND dt_deleted IS NULL",
@@ -67,7 +128,7 @@ impl<'a> TenantRepository<'a> {
         .await?
         {
             let row = sqlx::query!(
-                "SELECT id, user_id, tenant_type, dt_created, dt_updated, dt_deleted FROM tenants WHERE id = $1 AND dt_deleted IS NULL",
+                "SELECT id, user_id, tenant_type, slug, base_currency, dt_created, dt_updated, dt_deleted FROM tenants WHERE id = $1 AND dt_deleted IS NULL",
                 tenant_user.tenant_id
             )
             .fetch_one(&self.app_state.db)
@@ -75,8 +136,10 @@ impl<'a> TenantRepository<'a> {
 
             Ok(Tenant {
                 id: row.id,
-                user_id: user_id,
+                user_id,
                 tenant_type: row.tenant_type.to_string(),
+                slug: row.slug,
+                base_currency: row.base_currency,
                 dt_created: DateTime::from_naive_utc_and_offset(row.dt_created, Utc),
                 dt_updated: DateTime::from_naive_utc_and_offset(row.dt_updated, Utc),
                 dt_deleted: row
@@ -89,15 +152,18 @@ impl<'a> TenantRepository<'a> {
         }
     }
 
+    #[instrument(name = "TenantRepository::create", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn create(&self, user_id: Uuid, tenant_type: &str) -> Result<Tenant, sqlx::Error> {
         let id = Uuid::new_v4();
+        let slug = Tenant::default_slug(id);
         let now = Utc::now();
 
         let row = sqlx::query!(
-            "INSERT INTO tenants (id, user_id, tenant_type, dt_created, dt_updated) VALUES ($1, $2, $3, $4, $5) RETURNING id, dt_created, dt_updated, dt_deleted",
+            "INSERT INTO tenants (id, user_id, tenant_type, slug, dt_created, dt_updated) VALUES ($1, $2, $3, $4, $5, $6) RETURNING id, base_currency, dt_created, dt_updated, dt_deleted",
             id,
             user_id,
             tenant_type,
+            slug,
             now.naive_utc(),
             now.naive_utc()
         )
@@ -117,8 +183,10 @@ impl<'a> TenantRepository<'a> {
 
         Ok(Tenant {
             id: row.id,
-            user_id: user_id,
+            user_id,
             tenant_type: tenant_type.to_string(),
+            slug,
+            base_currency: row.base_currency,
             dt_created: DateTime::from_naive_utc_and_offset(row.dt_created, Utc),
             dt_updated: DateTime::from_naive_utc_and_offset(row.dt_updated, Utc),
             dt_deleted: row
@@ -127,6 +195,7 @@ impl<'a> TenantRepository<'a> {
         })
     }
 
+    #[instrument(name = "TenantRepository::create_tenant_user", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn create_tenant_user(
         &self,
         user_id: Uuid,
@@ -146,7 +215,7 @@ impl<'a> TenantRepository<'a> {
         .await?;
 
         let row = sqlx::query!(
-            "SELECT id, user_id, tenant_type, dt_created, dt_updated, dt_deleted FROM tenants WHERE id = $1 AND dt_deleted IS NULL",
+            "SELECT id, user_id, tenant_type, slug, base_currency, dt_created, dt_updated, dt_deleted FROM tenants WHERE id = $1 AND dt_deleted IS NULL",
             tenant_id
         )
         .fetch_one(&self.app_state.db)
@@ -156,6 +225,8 @@ impl<'a> TenantRepository<'a> {
             id: row.id,
             user_id: row.user_id,
             tenant_type: row.tenant_type,
+            slug: row.slug,
+            base_currency: row.base_currency,
             dt_created: DateTime::from_naive_utc_and_offset(row.dt_created, Utc),
             dt_updated: DateTime::from_naive_utc_and_offset(row.dt_updated, Utc),
             dt_deleted: row
@@ -164,25 +235,71 @@ impl<'a> TenantRepository<'a> {
         })
     }
 
+    /// Altera a loja. A moeda base só muda sem produtos e sem carrinhos abertos com itens dela:
+    /// os preços estão gravados na moeda atual. O `FOR UPDATE` na loja espera inserções de
+    /// produto em andamento (a FK trava a linha do tenant), então a checagem não tem janela.
+    #[instrument(name = "TenantRepository::update", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn update(
         &self,
         id: Uuid,
-        _request: UpdateTenantRequest,
-    ) -> Result<Option<Tenant>, sqlx::Error> {
+        request: UpdateTenantRequest,
+    ) -> Result<UpdateTenantOutcome, sqlx::Error> {
         let now = Utc::now();
+        let mut tx = self.app_state.db.begin().await?;
+
+        let current = sqlx::query_scalar!(
+            "SELECT base_currency FROM tenants WHERE id = $1 AND dt_deleted IS NULL FOR UPDATE",
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(current) = current else {
+            return Ok(UpdateTenantOutcome::NotFound);
+        };
+
+        if let Some(currency) = request.base_currency.as_deref()
+            && currency != current
+        {
+            let in_use = sqlx::query_scalar!(
+                r#"
+                SELECT EXISTS (
+                    SELECT 1 FROM products WHERE tenant_id = $1 AND dt_deleted IS NULL
+                ) OR EXISTS (
+                    SELECT 1
+                    FROM cart_items ci
+                    JOIN carts c ON c.id = ci.cart_id
+                    JOIN products p ON p.id = ci.product_id
+                    WHERE p.tenant_id = $1
+                      AND ci.dt_deleted IS NULL
+                      AND c.dt_deleted IS NULL
+                      AND c.status IN ('ACTIVE', 'CHECKOUT_IN_PROGRESS')
+                ) as "in_use!"
+                "#,
+                id
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            if in_use {
+                return Ok(UpdateTenantOutcome::CurrencyInUse);
+            }
+        }
 
         let row = sqlx::query!(
-            "UPDATE tenants SET dt_updated = $1 WHERE id = $2 AND dt_deleted IS NULL RETURNING id, user_id, tenant_type, dt_created, dt_updated, dt_deleted",
+            "UPDATE tenants SET base_currency = COALESCE($3, base_currency), dt_updated = $1 WHERE id = $2 RETURNING id, user_id, tenant_type, slug, base_currency, dt_created, dt_updated, dt_deleted",
             now.naive_utc(),
-            id
+            id,
+            request.base_currency
         )
-        .fetch_optional(&self.app_state.db)
+        .fetch_one(&mut *tx)
         .await?;
 
-        Ok(row.map(|row| Tenant {
+        tx.commit().await?;
+        Ok(UpdateTenantOutcome::Updated(Tenant {
             id: row.id,
             user_id: row.user_id,
             tenant_type: row.tenant_type,
+            slug: row.slug,
+            base_currency: row.base_currency,
             dt_created: DateTime::from_naive_utc_and_offset(row.dt_created, Utc),
             dt_updated: DateTime::from_naive_utc_and_offset(row.dt_updated, Utc),
             dt_deleted: row
@@ -191,6 +308,7 @@ impl<'a> TenantRepository<'a> {
         }))
     }
 
+    #[instrument(name = "TenantRepository::delete", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
         let now = Utc::now();
 
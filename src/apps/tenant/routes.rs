@@ -0,0 +1,35 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_extensions::RequestUserExt;
+use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::AuditContext;
+use crate::apps::tenant::models::UpdateTenantRequest;
+use crate::apps::tenant::services::TenantService;
+use actix_web::web::Json;
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+
+/// GET /tenant/ - loja do usuário autenticado
+pub async fn get_tenant(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let result = TenantService::get_tenant(&app_state, tenant_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// PATCH /tenant/ - configurações da loja (dono apenas)
+pub async fn update_tenant(
+    app_state: web::Data<AppState>,
+    payload: Json<UpdateTenantRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let audit = AuditContext::from_request(&req);
+    let result =
+        TenantService::update_tenant(&app_state, tenant_id, user_id, payload.into_inner(), &audit)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
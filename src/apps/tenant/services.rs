@@ -0,0 +1,100 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::{AuditAction, AuditContext, AuditEntity};
+use crate::apps::audit::services::AuditService;
+use crate::apps::tenant::models::{Tenant, UpdateTenantRequest};
+use crate::apps::tenant::repositories::{TenantRepository, UpdateTenantOutcome};
+use crate::utils::currency::normalize_currency;
+use uuid::Uuid;
+
+pub struct TenantService;
+
+impl TenantService {
+    pub async fn get_tenant(app_state: &AppState, tenant_id: Uuid) -> Result<Tenant, AppError> {
+        TenantRepository::new(app_state)
+            .find_by_id(tenant_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Loja não encontrada"))
+    }
+
+    /// Moeda base da loja: preços dos produtos e padrão dos carrinhos
+    pub async fn base_currency(app_state: &AppState, tenant_id: Uuid) -> Result<String, AppError> {
+        TenantRepository::new(app_state)
+            .find_base_currencies(&[tenant_id])
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .remove(&tenant_id)
+            .ok_or_else(|| AppError::not_found("Loja não encontrada"))
+    }
+
+    /// Administra a loja: o dono dela ou um super_admin
+    pub async fn ensure_admin(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        access_level: &str,
+        message: &str,
+    ) -> Result<(), AppError> {
+        if access_level == "super_admin" {
+            return Ok(());
+        }
+
+        let tenant = Self::get_tenant(app_state, tenant_id).await?;
+        match tenant.user_id == user_id {
+            true => Ok(()),
+            false => Err(AppError::forbidden(message)),
+        }
+    }
+
+    /// Só o dono da loja altera as configurações (hoje, a moeda base)
+    pub async fn update_tenant(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        mut request: UpdateTenantRequest,
+        audit: &AuditContext,
+    ) -> Result<Tenant, AppError> {
+        let before = Self::get_tenant(app_state, tenant_id).await?;
+        if before.user_id != user_id {
+            return Err(AppError::forbidden(
+                "Acesso negado. Apenas o dono da loja pode alterar as configurações.",
+            ));
+        }
+
+        request.base_currency = request
+            .base_currency
+            .as_deref()
+            .map(normalize_currency)
+            .transpose()?;
+
+        let outcome = TenantRepository::new(app_state)
+            .update(tenant_id, request)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        let tenant = match outcome {
+            UpdateTenantOutcome::Updated(tenant) => tenant,
+            UpdateTenantOutcome::NotFound => {
+                return Err(AppError::not_found("Loja não encontrada"));
+            }
+            UpdateTenantOutcome::CurrencyInUse => {
+                return Err(AppError::Conflict(Some(
+                    "Moeda base não pode ser alterada com produtos ou carrinhos abertos".into(),
+                )));
+            }
+        };
+
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Update,
+            AuditEntity::Tenant,
+            Some(tenant.id),
+            AuditService::snapshot(&before),
+            AuditService::snapshot(&tenant),
+        )
+        .await;
+
+        Ok(tenant)
+    }
+}
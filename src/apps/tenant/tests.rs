@@ -1,5 +1,5 @@
 #[cfg(test)]
-#[allow(unused_imports)]
+#[allow(unused_imports, clippy::assertions_on_constants)]
 mod tests {
     use super::*;
     use crate::apps::tenant::models::Tenant;
@@ -10,6 +10,12 @@ mod tests {
         assert!(true);
     }
 
+    #[test]
+    fn test_tenant_default_slug() {
+        let id = uuid::Uuid::parse_str("1a2b3c4d-5e6f-4a8b-9c0d-ef1234567890").unwrap();
+        assert_eq!(Tenant::default_slug(id), "loja-1a2b3c4d5e6f");
+    }
+
     #[test]
     fn test_tenant_validation() {
         // Adicione seus testes aqui
@@ -10,6 +10,12 @@ pub struct KeycloakConfig {
     pub admin_password: String,
 }
 
+impl Default for KeycloakConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl KeycloakConfig {
     pub fn new() -> Self {
         Self {
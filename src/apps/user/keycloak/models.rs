@@ -61,6 +61,7 @@ pub struct KeycloakUserInfo {
 }
 
 #[derive(Debug, Serialize)]
+#[allow(dead_code)]
 pub struct KeycloakLoginResponse {
     pub message: String,
     pub data: KeycloakAuthData,
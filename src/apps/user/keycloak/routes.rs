@@ -1,15 +1,30 @@
 use crate::app_core::app_error::AppError;
 use crate::app_core::app_state::AppState;
+use crate::apps::cart::guest::{expired_cart_cookie, merge_guest_cart_on_login};
 use crate::apps::user::keycloak::models::KeycloakLoginRequest;
 use crate::apps::user::keycloak::services::KeycloakService;
-use actix_web::{HttpResponse, Responder, web};
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
 
 /// Endpoint para login via Keycloak
 pub async fn login_keycloak(
     app_state: web::Data<AppState>,
     payload: web::Json<KeycloakLoginRequest>,
+    req: HttpRequest,
 ) -> Result<impl Responder, AppError> {
     let auth_data =
         KeycloakService::login_with_provider_token(payload.into_inner(), &app_state).await?;
-    Ok(HttpResponse::Ok().json(auth_data))
+
+    let mut builder = HttpResponse::Ok();
+    // Carrinho montado como visitante é juntado ao carrinho ativo da conta
+    if merge_guest_cart_on_login(
+        &app_state,
+        &req,
+        auth_data.user.tenant.id,
+        auth_data.user.id,
+    )
+    .await
+    {
+        builder.cookie(expired_cart_cookie());
+    }
+    Ok(builder.json(auth_data))
 }
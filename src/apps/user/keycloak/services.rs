@@ -1,5 +1,7 @@
 use crate::app_core::app_error::AppError;
 use crate::app_core::app_state::AppState;
+use crate::app_core::request_id::RequestIdExt;
+use crate::app_core::telemetry::TraceContextPropagationExt;
 use crate::apps::sync_app::producer::SyncProducer;
 use crate::apps::tenant::repositories::TenantRepository;
 use crate::apps::user::keycloak::{
@@ -12,7 +14,7 @@ use crate::apps::user::models::{Profile, User, UserWithProfile};
 use crate::apps::user::repositories::{ProfileRepository, UserRepository};
 use crate::utils::jwt::{calculate_remaining_expiration, generate_jwt};
 use reqwest::Client;
-use tracing::{error, info};
+use tracing::{Instrument, error, info, info_span};
 use uuid::Uuid;
 use validator::Validate;
 
@@ -27,9 +29,7 @@ impl KeycloakService {
         info!("Iniciando login via Keycloak");
 
         // Validar request
-        request
-            .validate()
-            .map_err(|e| AppError::bad_request(format!("Dados inválidos: {}", e)))?;
+        request.validate()?;
 
         let config = KeycloakConfig::new();
         let client = Client::new();
@@ -87,15 +87,22 @@ impl KeycloakService {
             ("client_secret", &config.client_secret),
         ];
 
-        let response = client
-            .post(&config.token_introspect_url())
-            .form(&params)
-            .send()
-            .await
-            .map_err(|e| {
-                error!("Erro ao validar token com Keycloak: {}", e);
-                AppError::internal("Erro ao validar token com Keycloak")
-            })?;
+        let span = info_span!("keycloak_introspect", otel.kind = "client");
+        let response = async {
+            client
+                .post(config.token_introspect_url())
+                .form(&params)
+                .with_request_id()
+                .with_trace_context()
+                .send()
+                .await
+        }
+        .instrument(span)
+        .await
+        .map_err(|e| {
+            error!("Erro ao validar token com Keycloak: {}", e);
+            AppError::internal("Erro ao validar token com Keycloak")
+        })?;
 
         if !response.status().is_success() {
             error!("Keycloak retornou erro: {}", response.status());
@@ -280,14 +287,16 @@ impl KeycloakService {
         }
 
         // Verificar roles do client
-        if let Some(resource_access) = &user_info.resource_access {
-            if let Some(client_access) = &resource_access.rust_template_client {
-                if client_access.roles.contains(&"super_admin".to_string()) {
-                    return "super_admin".to_string();
-                }
-                if client_access.roles.contains(&"admin".to_string()) {
-                    return "admin".to_string();
-                }
+        if let Some(client_access) = user_info
+            .resource_access
+            .as_ref()
+            .and_then(|resource_access| resource_access.rust_template_client.as_ref())
+        {
+            if client_access.roles.contains(&"super_admin".to_string()) {
+                return "super_admin".to_string();
+            }
+            if client_access.roles.contains(&"admin".to_string()) {
+                return "admin".to_string();
             }
         }
 
@@ -1,6 +1,9 @@
 use crate::app_core::app_state::AppState;
 use crate::apps::user::models::{Profile, UpdateUserRequest, User, UserToken};
-use chrono::{DateTime, Utc};
+use crate::utils::pagination::{PageRequest, push_keyset};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{Postgres, QueryBuilder, Row};
+use tracing::instrument;
 use uuid::Uuid;
 
 // ===== USER REPOSITORY =====
@@ -14,6 +17,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Buscar usuário por ID
+    #[instrument(name = "UserRepository::find_by_id", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_id(&self, id: Uuid) -> Result<User, sqlx::Error> {
         let row = sqlx::query!(
             r#"
@@ -44,6 +48,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Buscar usuário por email
+    #[instrument(name = "UserRepository::find_by_email", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_email(&self, email: &str) -> Result<Option<User>, sqlx::Error> {
         let row = sqlx::query!(
             r#"
@@ -74,6 +79,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Contar total de usuários
+    #[instrument(name = "UserRepository::count_all", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn count_all(&self) -> Result<i64, sqlx::Error> {
         let row: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users WHERE dt_deleted IS NULL")
             .fetch_one(&self.app_state.db)
@@ -81,41 +87,52 @@ impl<'a> UserRepository<'a> {
         Ok(row.0)
     }
 
-    /// Listar usuários paginados
-    pub async fn find_all_paginated(
-        &self,
-        limit: i64,
-        offset: i64,
-    ) -> Result<Vec<User>, sqlx::Error> {
-        let rows = sqlx::query!(
+    /// Listar usuários paginados (offset ou cursor)
+    #[instrument(name = "UserRepository::find_all_paginated", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_all_paginated(&self, page: &PageRequest) -> Result<Vec<User>, sqlx::Error> {
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
             r#"
             SELECT 
                 id, username, email, first_name, last_name, password,
                 dt_created, dt_updated, dt_deleted
             FROM users
             WHERE dt_deleted IS NULL
-            ORDER BY dt_created DESC
-            LIMIT $1 OFFSET $2
             "#,
-            limit,
-            offset
-        )
-        .fetch_all(&self.app_state.db)
-        .await?;
+        );
+
+        match page {
+            PageRequest::Offset { limit, offset } => {
+                qb.push(" ORDER BY dt_created DESC, id DESC LIMIT ")
+                    .push_bind(*limit)
+                    .push(" OFFSET ")
+                    .push_bind(*offset);
+            }
+            PageRequest::Cursor { limit, cursor, .. } => {
+                push_keyset(&mut qb, "dt_created", "id", cursor.as_ref(), *limit);
+            }
+        }
+
+        let rows = qb.build().fetch_all(&self.app_state.db).await?;
 
         let users = rows
             .into_iter()
             .map(|row| User {
-                id: row.id,
-                username: row.username,
-                email: row.email,
-                first_name: row.first_name,
-                last_name: row.last_name,
-                password: row.password,
-                dt_created: DateTime::from_naive_utc_and_offset(row.dt_created, Utc),
-                dt_updated: DateTime::from_naive_utc_and_offset(row.dt_updated, Utc),
+                id: row.get("id"),
+                username: row.get("username"),
+                email: row.get("email"),
+                first_name: row.get("first_name"),
+                last_name: row.get("last_name"),
+                password: row.get("password"),
+                dt_created: DateTime::from_naive_utc_and_offset(
+                    row.get::<NaiveDateTime, _>("dt_created"),
+                    Utc,
+                ),
+                dt_updated: DateTime::from_naive_utc_and_offset(
+                    row.get::<NaiveDateTime, _>("dt_updated"),
+                    Utc,
+                ),
                 dt_deleted: row
-                    .dt_deleted
+                    .get::<Option<NaiveDateTime>, _>("dt_deleted")
                     .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
             })
             .collect();
@@ -124,6 +141,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Criar usuário e perfil em transação
+    #[instrument(name = "UserRepository::create_user_with_profile", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn create_user_with_profile(
         &self,
         user: &User,
@@ -181,6 +199,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Atualizar campos do usuário
+    #[instrument(name = "UserRepository::update_user_fields", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn update_user_fields(
         &self,
         user_id: Uuid,
@@ -221,6 +240,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Soft delete do usuário
+    #[instrument(name = "UserRepository::soft_delete", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn soft_delete(&self, user_id: Uuid) -> Result<u64, sqlx::Error> {
         let result = sqlx::query!(
             r#"
@@ -238,6 +258,7 @@ impl<'a> UserRepository<'a> {
     }
 
     /// Atualizar senha do usuário
+    #[instrument(name = "UserRepository::update_password", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn update_password(
         &self,
         user_id: Uuid,
@@ -271,6 +292,7 @@ impl<'a> ProfileRepository<'a> {
     }
 
     /// Buscar perfil por user_id
+    #[instrument(name = "ProfileRepository::find_by_user_id", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<Profile>, sqlx::Error> {
         let row = sqlx::query!(
             r#"
@@ -303,6 +325,7 @@ impl<'a> ProfileRepository<'a> {
     }
 
     /// Atualizar perfil
+    #[instrument(name = "ProfileRepository::update", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn update(&self, user_id: Uuid, profile: &Profile) -> Result<(), sqlx::Error> {
         sqlx::query!(
             r#"
@@ -328,6 +351,7 @@ impl<'a> ProfileRepository<'a> {
     }
 
     /// Confirmar email
+    #[instrument(name = "ProfileRepository::confirm_email", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn confirm_email(&self, user_id: Uuid) -> Result<(), sqlx::Error> {
         sqlx::query!(
             r#"
@@ -356,6 +380,7 @@ impl<'a> TokenRepository<'a> {
     }
 
     /// Criar token para usuário
+    #[instrument(name = "TokenRepository::create_token", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn create_token(
         &self,
         user_id: Uuid,
@@ -384,6 +409,7 @@ impl<'a> TokenRepository<'a> {
     }
 
     /// Buscar token válido por user_id e token_type
+    #[instrument(name = "TokenRepository::_find_valid_token", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn _find_valid_token(
         &self,
         user_id: Uuid,
@@ -421,6 +447,7 @@ impl<'a> TokenRepository<'a> {
     }
 
     /// Buscar token válido por código
+    #[instrument(name = "TokenRepository::find_valid_token_by_code", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn find_valid_token_by_code(
         &self,
         code: &str,
@@ -458,6 +485,7 @@ impl<'a> TokenRepository<'a> {
     }
 
     /// Marcar token como consumido
+    #[instrument(name = "TokenRepository::mark_as_consumed", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
     pub async fn mark_as_consumed(&self, token_id: Uuid) -> Result<(), sqlx::Error> {
         sqlx::query!(
             r#"
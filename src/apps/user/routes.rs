@@ -1,6 +1,8 @@
 use crate::app_core::app_error::AppError;
 use crate::app_core::app_extensions::RequestUserExt;
 use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::AuditContext;
+use crate::apps::cart::guest::{expired_cart_cookie, merge_guest_cart_on_login};
 use crate::apps::user::models::{
     ChangePasswordRequest, ForgotPasswordRequest, LoginRequest, UpdateProfileRequest,
     UpdateUserRequest, UserRequest,
@@ -15,20 +17,37 @@ use actix_web::{HttpRequest, HttpResponse, Responder, web};
 pub async fn create_user(
     app_state: web::Data<AppState>,
     payload: web::Json<UserRequest>,
+    req: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    let response = UserService::create_user_with_profile(payload.into_inner(), &app_state).await?;
+    let audit = AuditContext::from_request(&req);
+    let response =
+        UserService::create_user_with_profile(payload.into_inner(), &app_state, &audit).await?;
 
-    Ok(HttpResponse::Created().json(response))
+    let mut builder = HttpResponse::Created();
+    // Carrinho montado como visitante passa para a conta nova
+    if merge_guest_cart_on_login(&app_state, &req, response.user.tenant.id, response.user.id).await
+    {
+        builder.cookie(expired_cart_cookie());
+    }
+    Ok(builder.json(response))
 }
 
 /// Login do usuário
 pub async fn login(
     app_state: web::Data<AppState>,
     payload: web::Json<LoginRequest>,
+    req: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    let response = UserService::login_user(payload.into_inner(), &app_state).await?;
-
-    Ok(HttpResponse::Ok().json(response))
+    let audit = AuditContext::from_request(&req);
+    let response = UserService::login_user(payload.into_inner(), &app_state, &audit).await?;
+
+    let mut builder = HttpResponse::Ok();
+    // Carrinho montado como visitante é juntado ao carrinho ativo da conta
+    if merge_guest_cart_on_login(&app_state, &req, response.user.tenant.id, response.user.id).await
+    {
+        builder.cookie(expired_cart_cookie());
+    }
+    Ok(builder.json(response))
 }
 
 /// Esqueci minha senha
@@ -47,8 +66,10 @@ pub async fn forgot_password(
 pub async fn change_password(
     app_state: web::Data<AppState>,
     payload: web::Json<ChangePasswordRequest>,
+    req: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    UserService::change_password(payload.into_inner(), &app_state).await?;
+    let audit = AuditContext::from_request(&req);
+    UserService::change_password(payload.into_inner(), &app_state, &audit).await?;
 
     Ok(HttpResponse::Ok().json(serde_json::json!({
         "message": "Senha alterada com sucesso"
@@ -59,8 +80,10 @@ pub async fn change_password(
 pub async fn confirm_email(
     app_state: web::Data<AppState>,
     code: web::Path<String>,
+    req: HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    UserService::confirm_email(code.into_inner(), &app_state).await?;
+    let audit = AuditContext::from_request(&req);
+    UserService::confirm_email(code.into_inner(), &app_state, &audit).await?;
 
     Ok(HttpResponse::Ok().json(serde_json::json!({
         "message": "Email confirmado com sucesso"
@@ -110,9 +133,9 @@ pub async fn list_users(
         ));
     }
 
-    let PaginationParams { limit, offset } = query.into_inner();
+    let page = query.page_request()?;
 
-    let response = UserService::list_users_paginated(limit, offset, &app_state).await?;
+    let response = UserService::list_users_paginated(&page, &app_state).await?;
 
     Ok(HttpResponse::Ok().json(response))
 }
@@ -140,8 +163,9 @@ pub async fn update_user(
         auth_header.to_string()
     };
 
+    let audit = AuditContext::from_request(&req);
     let response =
-        UserService::update_user(user_id, payload.into_inner(), token, &app_state).await?;
+        UserService::update_user(user_id, payload.into_inner(), token, &app_state, &audit).await?;
 
     Ok(HttpResponse::Ok().json(response))
 }
@@ -169,8 +193,10 @@ pub async fn update_profile(
         auth_header.to_string()
     };
 
+    let audit = AuditContext::from_request(&req);
     let response =
-        UserService::update_profile(user_id, payload.into_inner(), token, &app_state).await?;
+        UserService::update_profile(user_id, payload.into_inner(), token, &app_state, &audit)
+            .await?;
 
     Ok(HttpResponse::Ok().json(response))
 }
@@ -183,7 +209,8 @@ pub async fn delete_user(
     // Extrair user_id do token JWT
     let user_id = req.user_id()?;
 
-    UserService::delete_user(user_id, &app_state).await?;
+    let audit = AuditContext::from_request(&req);
+    UserService::delete_user(user_id, &app_state, &audit).await?;
 
     Ok(HttpResponse::NoContent().finish())
 }
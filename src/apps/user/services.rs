@@ -1,5 +1,7 @@
 use crate::app_core::app_error::AppError;
 use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::{AuditAction, AuditContext, AuditEntity};
+use crate::apps::audit::services::AuditService;
 use crate::apps::sync_app::producer::SyncProducer;
 use crate::apps::tenant::repositories::TenantRepository;
 use crate::apps::user::models::{
@@ -9,7 +11,7 @@ use crate::apps::user::models::{
 use crate::apps::user::repositories::{ProfileRepository, TokenRepository, UserRepository};
 use crate::utils::formatter::generate_username_from_email;
 use crate::utils::jwt::{calculate_remaining_expiration, generate_jwt};
-use crate::utils::pagination::PaginatedResponse;
+use crate::utils::pagination::{CursorPage, Page, PageRequest, PaginatedResponse};
 use bcrypt::{DEFAULT_COST, hash};
 use tracing::{error, info};
 use uuid::Uuid;
@@ -24,11 +26,10 @@ impl UserService {
     pub async fn create_user_with_profile(
         request: UserRequest,
         app_state: &AppState,
+        audit: &AuditContext,
     ) -> Result<UserResponse, AppError> {
         // Validar dados de entrada
-        request
-            .validate()
-            .map_err(|e| AppError::bad_request(format!("Dados inválidos: {}", e)))?;
+        request.validate()?;
 
         let repository = UserRepository::new(app_state);
         let _profile_repo = ProfileRepository::new(app_state);
@@ -98,6 +99,17 @@ impl UserService {
         let user_with_profile =
             UserWithProfile::from_user_and_profile_ref(&user, &profile, &tenant);
 
+        AuditService::record(
+            app_state,
+            &audit.clone().with_actor(user.id, Some(tenant.id)),
+            AuditAction::Create,
+            AuditEntity::User,
+            Some(user.id),
+            None,
+            AuditService::snapshot(&user_with_profile),
+        )
+        .await;
+
         // Sync com SQS após criação do usuário
         if let Err(e) = SyncProducer::sync_user(user_with_profile.clone(), app_state, None).await {
             error!("Erro ao sincronizar usuário criado: {}", e);
@@ -113,11 +125,10 @@ impl UserService {
     pub async fn login_user(
         request: LoginRequest,
         app_state: &AppState,
+        audit: &AuditContext,
     ) -> Result<UserResponse, AppError> {
         // Validar dados de entrada
-        request
-            .validate()
-            .map_err(|e| AppError::bad_request(format!("Dados inválidos: {}", e)))?;
+        request.validate()?;
 
         let repository = UserRepository::new(app_state);
         let profile_repo = ProfileRepository::new(app_state);
@@ -157,6 +168,17 @@ impl UserService {
         let user_with_profile =
             UserWithProfile::from_user_and_profile_ref(&user, &profile, &tenant);
 
+        AuditService::record(
+            app_state,
+            &audit.clone().with_actor(user.id, Some(tenant.id)),
+            AuditAction::Login,
+            AuditEntity::User,
+            Some(user.id),
+            None,
+            None,
+        )
+        .await;
+
         Ok(UserResponse::from(user_with_profile, token, expires_in))
     }
 
@@ -165,9 +187,7 @@ impl UserService {
         request: ForgotPasswordRequest,
         app_state: &AppState,
     ) -> Result<(), AppError> {
-        request
-            .validate()
-            .map_err(|e| AppError::bad_request(format!("Dados inválidos: {}", e)))?;
+        request.validate()?;
 
         let repository = UserRepository::new(app_state);
         let token_repo = TokenRepository::new(app_state);
@@ -195,10 +215,9 @@ impl UserService {
     pub async fn change_password(
         request: ChangePasswordRequest,
         app_state: &AppState,
+        audit: &AuditContext,
     ) -> Result<(), AppError> {
-        request
-            .validate()
-            .map_err(|e| AppError::bad_request(format!("Dados inválidos: {}", e)))?;
+        request.validate()?;
 
         let repository = UserRepository::new(app_state);
         let token_repo = TokenRepository::new(app_state);
@@ -221,11 +240,26 @@ impl UserService {
         // Marcar token como consumido
         token_repo.mark_as_consumed(token.id).await?;
 
+        AuditService::record(
+            app_state,
+            &audit.clone().with_actor(token.user_id, None),
+            AuditAction::PasswordChange,
+            AuditEntity::User,
+            Some(token.user_id),
+            None,
+            None,
+        )
+        .await;
+
         Ok(())
     }
 
     /// Confirmar email com código
-    pub async fn confirm_email(code: String, app_state: &AppState) -> Result<(), AppError> {
+    pub async fn confirm_email(
+        code: String,
+        app_state: &AppState,
+        audit: &AuditContext,
+    ) -> Result<(), AppError> {
         let token_repo = TokenRepository::new(app_state);
         let profile_repo = ProfileRepository::new(app_state);
         let user_repo = UserRepository::new(app_state);
@@ -258,6 +292,17 @@ impl UserService {
         let user_with_profile =
             UserWithProfile::from_user_and_profile_ref(&user, &profile, &tenant);
 
+        AuditService::record(
+            app_state,
+            &audit.clone().with_actor(user.id, Some(tenant.id)),
+            AuditAction::EmailConfirm,
+            AuditEntity::Profile,
+            Some(profile.id),
+            None,
+            None,
+        )
+        .await;
+
         // Sync com orchestrators após confirmação de email
         if let Err(e) = SyncProducer::sync_user(user_with_profile, app_state, None).await {
             error!(
@@ -308,20 +353,37 @@ impl UserService {
 
     /// Listar usuários paginados
     pub async fn list_users_paginated(
-        limit: i64,
-        offset: i64,
+        page: &PageRequest,
         app_state: &AppState,
-    ) -> Result<PaginatedResponse<User>, AppError> {
+    ) -> Result<Page<User>, AppError> {
         let repository = UserRepository::new(app_state);
 
-        let count = repository.count_all().await?;
-        let users = repository.find_all_paginated(limit, offset).await?;
-
-        Ok(PaginatedResponse {
-            count,
-            results: users,
-            limit,
-            offset,
+        let users = repository.find_all_paginated(page).await?;
+
+        Ok(match page {
+            PageRequest::Offset { limit, offset } => Page::Offset(PaginatedResponse {
+                count: repository.count_all().await?,
+                results: users,
+                limit: *limit,
+                offset: *offset,
+            }),
+            PageRequest::Cursor {
+                limit,
+                cursor,
+                include_count,
+            } => {
+                let count = match include_count {
+                    true => Some(repository.count_all().await?),
+                    false => None,
+                };
+                Page::Cursor(CursorPage::from_rows(
+                    users,
+                    *limit,
+                    cursor.as_ref(),
+                    |u| (u.dt_created, u.id),
+                    count,
+                ))
+            }
         })
     }
 
@@ -331,15 +393,26 @@ impl UserService {
         request: UpdateUserRequest,
         token: String,
         app_state: &AppState,
+        audit: &AuditContext,
     ) -> Result<UserResponse, AppError> {
-        request
-            .validate()
-            .map_err(|e| AppError::bad_request(format!("Dados inválidos: {}", e)))?;
+        request.validate()?;
 
         let repository = UserRepository::new(app_state);
         let profile_repo = ProfileRepository::new(app_state);
 
+        let before = repository.find_by_id(user_id).await?;
         let user = repository.update_user_fields(user_id, request).await?;
+
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Update,
+            AuditEntity::User,
+            Some(user.id),
+            AuditService::snapshot(&before),
+            AuditService::snapshot(&user),
+        )
+        .await;
         let profile = profile_repo
             .find_by_user_id(user.id)
             .await?
@@ -375,10 +448,9 @@ impl UserService {
         request: UpdateProfileRequest,
         token: String,
         app_state: &AppState,
+        audit: &AuditContext,
     ) -> Result<UserResponse, AppError> {
-        request
-            .validate()
-            .map_err(|e| AppError::bad_request(format!("Dados inválidos: {}", e)))?;
+        request.validate()?;
 
         let repository = UserRepository::new(app_state);
         let profile_repo = ProfileRepository::new(app_state);
@@ -389,6 +461,7 @@ impl UserService {
             .find_by_user_id(user.id)
             .await?
             .ok_or_else(|| AppError::not_found("Perfil não encontrado"))?;
+        let before = AuditService::snapshot(&profile);
 
         // Atualizar campos do perfil
         if let Some(bio) = request.bio {
@@ -413,6 +486,17 @@ impl UserService {
         // Salvar perfil atualizado
         profile_repo.update(user_id, &profile).await?;
 
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Update,
+            AuditEntity::Profile,
+            Some(profile.id),
+            before,
+            AuditService::snapshot(&profile),
+        )
+        .await;
+
         let repository_tenant = TenantRepository::new(app_state);
         let tenant = repository_tenant
             .find_by_user_id(user_id)
@@ -438,9 +522,26 @@ impl UserService {
     }
 
     /// Deletar usuário (soft delete)
-    pub async fn delete_user(user_id: Uuid, app_state: &AppState) -> Result<(), AppError> {
+    pub async fn delete_user(
+        user_id: Uuid,
+        app_state: &AppState,
+        audit: &AuditContext,
+    ) -> Result<(), AppError> {
         let repository = UserRepository::new(app_state);
+        let before = repository.find_by_id(user_id).await?;
         repository.soft_delete(user_id).await?;
+
+        AuditService::record(
+            app_state,
+            audit,
+            AuditAction::Delete,
+            AuditEntity::User,
+            Some(user_id),
+            AuditService::snapshot(&before),
+            None,
+        )
+        .await;
+
         Ok(())
     }
 }
@@ -0,0 +1,172 @@
+use crate::utils::money::Money;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "wishlist_kind", rename_all = "SCREAMING_SNAKE_CASE")]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub enum WishlistKind {
+    WISHLIST,
+    /// Linhas tiradas do carrinho para comprar depois (uma lista por usuário)
+    SAVED_FOR_LATER,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Wishlist {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub kind: WishlistKind,
+    /// Token do link público; vazio quando a lista não está compartilhada
+    pub share_token: Option<String>,
+    pub dt_created: DateTime<Utc>,
+    pub dt_updated: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WishlistItem {
+    pub id: Uuid,
+    pub wishlist_id: Uuid,
+    pub product_id: Uuid,
+    pub variant_id: Option<Uuid>,
+    pub attributes_snapshot: serde_json::Value,
+    pub quantity: i32,
+    pub notify_price_drop: bool,
+    pub notify_back_in_stock: bool,
+    pub dt_created: DateTime<Utc>,
+    pub dt_updated: DateTime<Utc>,
+}
+
+/// Item com o estado atual do produto (preço efetivo na moeda base da loja)
+#[derive(Debug, Serialize, Clone)]
+pub struct WishlistItemWithProduct {
+    #[serde(flatten)]
+    pub item: WishlistItem,
+    pub product_name: String,
+    pub product_slug: String,
+    pub price: Money,
+    pub stock_quantity: i32,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WishlistWithItems {
+    #[serde(flatten)]
+    pub wishlist: Wishlist,
+    pub items: Vec<WishlistItemWithProduct>,
+}
+
+/// GET /wishlists/shared/{token}/ - visão pública, sem dono nem preferências de aviso
+#[derive(Debug, Serialize, Clone)]
+pub struct SharedWishlist {
+    pub name: String,
+    pub items: Vec<SharedWishlistItem>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SharedWishlistItem {
+    pub product_id: Uuid,
+    pub product_name: String,
+    pub product_slug: String,
+    pub variant_id: Option<Uuid>,
+    pub attributes_snapshot: serde_json::Value,
+    pub quantity: i32,
+    pub price: Money,
+    pub in_stock: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ShareWishlistResponse {
+    pub share_token: String,
+    pub path: String,
+}
+
+/// POST /wishlists/ e PATCH /wishlists/{id}/
+#[derive(Debug, Deserialize, Clone)]
+pub struct WishlistRequest {
+    pub name: String,
+}
+
+/// POST /wishlists/{id}/items/ - produto já na lista soma a quantidade
+#[derive(Debug, Deserialize, Clone)]
+pub struct AddWishlistItemRequest {
+    pub product_id: Uuid,
+    pub quantity: Option<i32>,
+    #[serde(default)]
+    pub notify_price_drop: bool,
+    #[serde(default)]
+    pub notify_back_in_stock: bool,
+}
+
+/// PATCH /wishlists/{id}/items/{item_id}/ - só os campos enviados mudam
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct UpdateWishlistItemRequest {
+    pub quantity: Option<i32>,
+    pub notify_price_drop: Option<bool>,
+    pub notify_back_in_stock: Option<bool>,
+}
+
+/// Estado do produto que os avisos acompanham
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductSnapshot {
+    pub price: Money,
+    pub in_stock: bool,
+}
+
+/// Item com aviso ligado e o último estado visto do produto
+#[derive(Debug, Clone)]
+pub struct AlertCandidate {
+    pub item_id: Uuid,
+    pub user_id: Uuid,
+    pub product_id: Uuid,
+    pub notify_price_drop: bool,
+    pub notify_back_in_stock: bool,
+    pub last_seen: Option<ProductSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlertKind {
+    PriceDrop { old_price: Money, new_price: Money },
+    BackInStock,
+}
+
+impl AlertKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertKind::PriceDrop { .. } => "price_drop",
+            AlertKind::BackInStock => "back_in_stock",
+        }
+    }
+}
+
+/// Aviso a gravar para o usuário
+#[derive(Debug, Clone)]
+pub struct NewWishlistNotification {
+    pub user_id: Uuid,
+    pub wishlist_item_id: Uuid,
+    pub product_id: Uuid,
+    pub kind: AlertKind,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WishlistNotification {
+    pub id: Uuid,
+    pub wishlist_item_id: Uuid,
+    pub product_id: Uuid,
+    /// `price_drop` ou `back_in_stock`
+    pub kind: String,
+    pub old_price: Option<Money>,
+    pub new_price: Option<Money>,
+    pub dt_created: DateTime<Utc>,
+    pub dt_read: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct NotificationListParams {
+    /// Só os avisos ainda não lidos
+    #[serde(default)]
+    pub unread: bool,
+}
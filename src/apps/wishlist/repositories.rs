@@ -0,0 +1,620 @@
+use crate::app_core::app_state::AppState;
+use crate::apps::cart::models::{Cart, CartLineChange};
+use crate::apps::cart::repositories::CartRepository;
+use crate::apps::wishlist::models::{
+    AlertCandidate, AlertKind, NewWishlistNotification, ProductSnapshot, Wishlist, WishlistItem,
+    WishlistKind, WishlistNotification,
+};
+use crate::utils::money::Money;
+use chrono::{DateTime, Utc};
+use sqlx::PgExecutor;
+use tracing::instrument;
+use uuid::Uuid;
+
+/// Item a gravar; `last_seen` é o estado do produto quando o aviso foi ligado
+#[derive(Debug, Clone)]
+pub struct NewWishlistItem {
+    pub product_id: Uuid,
+    pub variant_id: Option<Uuid>,
+    pub attributes_snapshot: serde_json::Value,
+    pub quantity: i32,
+    pub notify_price_drop: bool,
+    pub notify_back_in_stock: bool,
+    pub last_seen: Option<ProductSnapshot>,
+}
+
+/// Resultado de mover o item para o carrinho; nada é gravado fora de `Moved`
+#[derive(Debug, PartialEq, Eq)]
+pub enum MoveToCartOutcome {
+    Moved,
+    /// Item já tinha saído da lista
+    ItemGone,
+    /// Carrinho mudou depois da leitura em que a linha foi planejada
+    CartChanged,
+}
+
+pub struct WishlistRepository<'a> {
+    app_state: &'a AppState,
+}
+
+impl<'a> WishlistRepository<'a> {
+    pub fn new(app_state: &'a AppState) -> Self {
+        Self { app_state }
+    }
+
+    #[instrument(name = "WishlistRepository::find_all", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_all(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<Wishlist>, sqlx::Error> {
+        sqlx::query_as!(
+            Wishlist,
+            r#"
+            SELECT
+                id, tenant_id, user_id, name,
+                kind as "kind: WishlistKind",
+                share_token,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            FROM wishlists
+            WHERE tenant_id = $1 AND user_id = $2 AND dt_deleted IS NULL
+            ORDER BY dt_created, id
+            "#,
+            tenant_id,
+            user_id
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "WishlistRepository::find_by_id", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_id(
+        &self,
+        id: Uuid,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Wishlist>, sqlx::Error> {
+        sqlx::query_as!(
+            Wishlist,
+            r#"
+            SELECT
+                id, tenant_id, user_id, name,
+                kind as "kind: WishlistKind",
+                share_token,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            FROM wishlists
+            WHERE id = $1 AND tenant_id = $2 AND user_id = $3 AND dt_deleted IS NULL
+            "#,
+            id,
+            tenant_id,
+            user_id
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "WishlistRepository::find_by_share_token", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_by_share_token(&self, token: &str) -> Result<Option<Wishlist>, sqlx::Error> {
+        sqlx::query_as!(
+            Wishlist,
+            r#"
+            SELECT
+                id, tenant_id, user_id, name,
+                kind as "kind: WishlistKind",
+                share_token,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            FROM wishlists
+            WHERE share_token = $1 AND dt_deleted IS NULL
+            "#,
+            token
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "WishlistRepository::create", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn create(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        name: &str,
+    ) -> Result<Wishlist, sqlx::Error> {
+        let now = Utc::now().naive_utc();
+
+        sqlx::query_as!(
+            Wishlist,
+            r#"
+            INSERT INTO wishlists (id, tenant_id, user_id, name, kind, dt_created, dt_updated)
+            VALUES ($1, $2, $3, $4, 'WISHLIST', $5, $5)
+            RETURNING
+                id, tenant_id, user_id, name,
+                kind as "kind: WishlistKind",
+                share_token,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            "#,
+            Uuid::new_v4(),
+            tenant_id,
+            user_id,
+            name,
+            now
+        )
+        .fetch_one(&self.app_state.db)
+        .await
+    }
+
+    /// Lista "salvos para depois" do usuário, criada na primeira vez
+    #[instrument(name = "WishlistRepository::get_or_create_saved", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn get_or_create_saved(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        name: &str,
+    ) -> Result<Wishlist, sqlx::Error> {
+        let now = Utc::now().naive_utc();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO wishlists (id, tenant_id, user_id, name, kind, dt_created, dt_updated)
+            VALUES ($1, $2, $3, $4, 'SAVED_FOR_LATER', $5, $5)
+            ON CONFLICT DO NOTHING
+            "#,
+            Uuid::new_v4(),
+            tenant_id,
+            user_id,
+            name,
+            now
+        )
+        .execute(&self.app_state.db)
+        .await?;
+
+        sqlx::query_as!(
+            Wishlist,
+            r#"
+            SELECT
+                id, tenant_id, user_id, name,
+                kind as "kind: WishlistKind",
+                share_token,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            FROM wishlists
+            WHERE tenant_id = $1 AND user_id = $2 AND kind = 'SAVED_FOR_LATER'
+                AND dt_deleted IS NULL
+            "#,
+            tenant_id,
+            user_id
+        )
+        .fetch_one(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "WishlistRepository::rename", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn rename(&self, id: Uuid, name: &str) -> Result<Option<Wishlist>, sqlx::Error> {
+        sqlx::query_as!(
+            Wishlist,
+            r#"
+            UPDATE wishlists SET name = $1, dt_updated = $2
+            WHERE id = $3 AND dt_deleted IS NULL
+            RETURNING
+                id, tenant_id, user_id, name,
+                kind as "kind: WishlistKind",
+                share_token,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            "#,
+            name,
+            Utc::now().naive_utc(),
+            id
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    /// Liga (com um token novo) ou desliga o link público
+    #[instrument(name = "WishlistRepository::set_share_token", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn set_share_token(
+        &self,
+        id: Uuid,
+        token: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE wishlists SET share_token = $1, dt_updated = $2
+            WHERE id = $3 AND dt_deleted IS NULL
+            "#,
+            token,
+            Utc::now().naive_utc(),
+            id
+        )
+        .execute(&self.app_state.db)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Remove a lista; o link público deixa de funcionar junto
+    #[instrument(name = "WishlistRepository::delete", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE wishlists SET dt_deleted = $1, share_token = NULL
+            WHERE id = $2 AND dt_deleted IS NULL
+            "#,
+            Utc::now().naive_utc(),
+            id
+        )
+        .execute(&self.app_state.db)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(name = "WishlistRepository::list_items", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn list_items(&self, wishlist_id: Uuid) -> Result<Vec<WishlistItem>, sqlx::Error> {
+        sqlx::query_as!(
+            WishlistItem,
+            r#"
+            SELECT
+                id, wishlist_id, product_id, variant_id, attributes_snapshot, quantity,
+                notify_price_drop, notify_back_in_stock,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            FROM wishlist_items
+            WHERE wishlist_id = $1
+            ORDER BY dt_created, id
+            "#,
+            wishlist_id
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "WishlistRepository::find_item", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_item(
+        &self,
+        wishlist_id: Uuid,
+        item_id: Uuid,
+    ) -> Result<Option<WishlistItem>, sqlx::Error> {
+        sqlx::query_as!(
+            WishlistItem,
+            r#"
+            SELECT
+                id, wishlist_id, product_id, variant_id, attributes_snapshot, quantity,
+                notify_price_drop, notify_back_in_stock,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            FROM wishlist_items
+            WHERE id = $1 AND wishlist_id = $2
+            "#,
+            item_id,
+            wishlist_id
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "WishlistRepository::add_item", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn add_item(
+        &self,
+        wishlist_id: Uuid,
+        item: &NewWishlistItem,
+    ) -> Result<WishlistItem, sqlx::Error> {
+        Self::upsert_item(&self.app_state.db, wishlist_id, item).await
+    }
+
+    /// Mesmo produto, variante e atributos na lista soma a quantidade; avisos só são ligados
+    #[instrument(name = "WishlistRepository::upsert_item", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    async fn upsert_item<'e, E: PgExecutor<'e>>(
+        executor: E,
+        wishlist_id: Uuid,
+        item: &NewWishlistItem,
+    ) -> Result<WishlistItem, sqlx::Error> {
+        let now = Utc::now().naive_utc();
+        let (last_seen_price, last_seen_currency, last_seen_in_stock) = match &item.last_seen {
+            Some(seen) => (
+                Some(seen.price.amount_minor),
+                Some(seen.price.currency.clone()),
+                Some(seen.in_stock),
+            ),
+            None => (None, None, None),
+        };
+
+        sqlx::query_as!(
+            WishlistItem,
+            r#"
+            INSERT INTO wishlist_items (
+                id, wishlist_id, product_id, variant_id, attributes_snapshot, quantity,
+                notify_price_drop, notify_back_in_stock,
+                last_seen_price, last_seen_currency, last_seen_in_stock,
+                dt_created, dt_updated
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $12)
+            ON CONFLICT (
+                wishlist_id,
+                product_id,
+                COALESCE(variant_id, '00000000-0000-0000-0000-000000000000'::uuid),
+                attributes_hash
+            )
+            DO UPDATE SET
+                quantity = wishlist_items.quantity + EXCLUDED.quantity,
+                notify_price_drop = wishlist_items.notify_price_drop OR EXCLUDED.notify_price_drop,
+                notify_back_in_stock = wishlist_items.notify_back_in_stock OR EXCLUDED.notify_back_in_stock,
+                last_seen_price = COALESCE(EXCLUDED.last_seen_price, wishlist_items.last_seen_price),
+                last_seen_currency = COALESCE(EXCLUDED.last_seen_currency, wishlist_items.last_seen_currency),
+                last_seen_in_stock = COALESCE(EXCLUDED.last_seen_in_stock, wishlist_items.last_seen_in_stock),
+                dt_updated = EXCLUDED.dt_updated
+            RETURNING
+                id, wishlist_id, product_id, variant_id, attributes_snapshot, quantity,
+                notify_price_drop, notify_back_in_stock,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            "#,
+            Uuid::new_v4(),
+            wishlist_id,
+            item.product_id,
+            item.variant_id,
+            item.attributes_snapshot,
+            item.quantity,
+            item.notify_price_drop,
+            item.notify_back_in_stock,
+            last_seen_price,
+            last_seen_currency,
+            last_seen_in_stock,
+            now
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    /// Grava quantidade e avisos; `last_seen` reinicia a referência dos avisos
+    #[instrument(name = "WishlistRepository::update_item", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn update_item(
+        &self,
+        item: &WishlistItem,
+        last_seen: Option<&ProductSnapshot>,
+    ) -> Result<Option<WishlistItem>, sqlx::Error> {
+        sqlx::query_as!(
+            WishlistItem,
+            r#"
+            UPDATE wishlist_items SET
+                quantity = $1,
+                notify_price_drop = $2,
+                notify_back_in_stock = $3,
+                last_seen_price = COALESCE($4, last_seen_price),
+                last_seen_currency = COALESCE($5, last_seen_currency),
+                last_seen_in_stock = COALESCE($6, last_seen_in_stock),
+                dt_updated = $7
+            WHERE id = $8
+            RETURNING
+                id, wishlist_id, product_id, variant_id, attributes_snapshot, quantity,
+                notify_price_drop, notify_back_in_stock,
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_updated AT TIME ZONE 'UTC') as "dt_updated!: DateTime<Utc>"
+            "#,
+            item.quantity,
+            item.notify_price_drop,
+            item.notify_back_in_stock,
+            last_seen.map(|seen| seen.price.amount_minor),
+            last_seen.map(|seen| seen.price.currency.clone()),
+            last_seen.map(|seen| seen.in_stock),
+            Utc::now().naive_utc(),
+            item.id
+        )
+        .fetch_optional(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "WishlistRepository::delete_item", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn delete_item(&self, wishlist_id: Uuid, item_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM wishlist_items WHERE id = $1 AND wishlist_id = $2",
+            item_id,
+            wishlist_id
+        )
+        .execute(&self.app_state.db)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Guarda a linha na lista e a tira do carrinho na mesma transação; `None` (sem gravar nada)
+    /// se o carrinho mudou depois da leitura
+    #[instrument(name = "WishlistRepository::save_for_later", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn save_for_later(
+        &self,
+        saved_list_id: Uuid,
+        item: &NewWishlistItem,
+        cart: &Cart,
+        changes: &[CartLineChange],
+        subtotal: &Money,
+    ) -> Result<Option<WishlistItem>, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+
+        let saved = Self::upsert_item(&mut *tx, saved_list_id, item).await?;
+        if !CartRepository::write_line_changes(&mut tx, cart, changes, subtotal).await? {
+            return Ok(None);
+        }
+
+        tx.commit().await?;
+        Ok(Some(saved))
+    }
+
+    /// Devolve o item ao carrinho e o tira da lista na mesma transação
+    #[instrument(name = "WishlistRepository::move_to_cart", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn move_to_cart(
+        &self,
+        wishlist_id: Uuid,
+        item_id: Uuid,
+        cart: &Cart,
+        changes: &[CartLineChange],
+        subtotal: &Money,
+    ) -> Result<MoveToCartOutcome, sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+
+        let removed = sqlx::query!(
+            "DELETE FROM wishlist_items WHERE id = $1 AND wishlist_id = $2",
+            item_id,
+            wishlist_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        if removed.rows_affected() == 0 {
+            return Ok(MoveToCartOutcome::ItemGone);
+        }
+
+        if !CartRepository::write_line_changes(&mut tx, cart, changes, subtotal).await? {
+            return Ok(MoveToCartOutcome::CartChanged);
+        }
+        tx.commit().await?;
+        Ok(MoveToCartOutcome::Moved)
+    }
+
+    /// Itens com algum aviso ligado, em listas ativas
+    #[instrument(name = "WishlistRepository::find_alert_candidates", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn find_alert_candidates(&self) -> Result<Vec<AlertCandidate>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                wi.id, w.user_id, wi.product_id,
+                wi.notify_price_drop, wi.notify_back_in_stock,
+                wi.last_seen_price, wi.last_seen_currency, wi.last_seen_in_stock
+            FROM wishlist_items wi
+            JOIN wishlists w ON w.id = wi.wishlist_id
+            WHERE w.dt_deleted IS NULL
+                AND (wi.notify_price_drop OR wi.notify_back_in_stock)
+            ORDER BY wi.id
+            "#
+        )
+        .fetch_all(&self.app_state.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| AlertCandidate {
+                item_id: row.id,
+                user_id: row.user_id,
+                product_id: row.product_id,
+                notify_price_drop: row.notify_price_drop,
+                notify_back_in_stock: row.notify_back_in_stock,
+                last_seen: match (
+                    row.last_seen_price,
+                    row.last_seen_currency,
+                    row.last_seen_in_stock,
+                ) {
+                    (Some(price), Some(currency), Some(in_stock)) => Some(ProductSnapshot {
+                        price: Money::new(price, currency),
+                        in_stock,
+                    }),
+                    _ => None,
+                },
+            })
+            .collect())
+    }
+
+    /// Grava os avisos gerados e o novo estado visto de cada item numa transação
+    #[instrument(name = "WishlistRepository::record_alerts", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn record_alerts(
+        &self,
+        seen: &[(Uuid, ProductSnapshot)],
+        notifications: &[NewWishlistNotification],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.app_state.db.begin().await?;
+        let now = Utc::now().naive_utc();
+
+        for notification in notifications {
+            let (old_price, new_price) = match &notification.kind {
+                AlertKind::PriceDrop {
+                    old_price,
+                    new_price,
+                } => (Some(old_price), Some(new_price)),
+                AlertKind::BackInStock => (None, None),
+            };
+            sqlx::query!(
+                r#"
+                INSERT INTO wishlist_notifications (
+                    id, user_id, wishlist_item_id, product_id, kind,
+                    old_price, new_price, currency, dt_created
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                "#,
+                Uuid::new_v4(),
+                notification.user_id,
+                notification.wishlist_item_id,
+                notification.product_id,
+                notification.kind.as_str(),
+                old_price.map(|price| price.amount_minor),
+                new_price.map(|price| price.amount_minor),
+                new_price.map(|price| price.currency.clone()),
+                now
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for (item_id, snapshot) in seen {
+            sqlx::query!(
+                r#"
+                UPDATE wishlist_items
+                SET last_seen_price = $1, last_seen_currency = $2, last_seen_in_stock = $3
+                WHERE id = $4
+                "#,
+                snapshot.price.amount_minor,
+                snapshot.price.currency,
+                snapshot.in_stock,
+                item_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await
+    }
+
+    /// Avisos do usuário, mais recentes primeiro
+    #[instrument(name = "WishlistRepository::list_notifications", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn list_notifications(
+        &self,
+        user_id: Uuid,
+        unread: bool,
+        limit: i64,
+    ) -> Result<Vec<WishlistNotification>, sqlx::Error> {
+        sqlx::query_as!(
+            WishlistNotification,
+            r#"
+            SELECT
+                id, wishlist_item_id, product_id, kind,
+                CASE WHEN old_price IS NULL THEN NULL
+                    ELSE ROW(old_price, currency)::money_value END as "old_price?: Money",
+                CASE WHEN new_price IS NULL THEN NULL
+                    ELSE ROW(new_price, currency)::money_value END as "new_price?: Money",
+                (dt_created AT TIME ZONE 'UTC') as "dt_created!: DateTime<Utc>",
+                (dt_read AT TIME ZONE 'UTC') as "dt_read?: DateTime<Utc>"
+            FROM wishlist_notifications
+            WHERE user_id = $1 AND (NOT $2 OR dt_read IS NULL)
+            ORDER BY dt_created DESC, id DESC
+            LIMIT $3
+            "#,
+            user_id,
+            unread,
+            limit
+        )
+        .fetch_all(&self.app_state.db)
+        .await
+    }
+
+    #[instrument(name = "WishlistRepository::mark_notifications_read", skip_all, fields(otel.kind = "client", db.system = "postgresql"))]
+    pub async fn mark_notifications_read(&self, user_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE wishlist_notifications SET dt_read = $1 WHERE user_id = $2 AND dt_read IS NULL",
+            Utc::now().naive_utc(),
+            user_id
+        )
+        .execute(&self.app_state.db)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
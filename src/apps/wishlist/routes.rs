@@ -0,0 +1,245 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_extensions::RequestUserExt;
+use crate::app_core::app_state::AppState;
+use crate::apps::audit::models::AuditContext;
+use crate::apps::wishlist::models::{
+    AddWishlistItemRequest, NotificationListParams, UpdateWishlistItemRequest, WishlistRequest,
+};
+use crate::apps::wishlist::services::WishlistService;
+use actix_web::web::Json;
+use actix_web::{HttpRequest, HttpResponse, Responder, web};
+use uuid::Uuid;
+
+pub async fn list_wishlists(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+
+    let result = WishlistService::list_wishlists(&app_state, tenant_id, user_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /wishlists/ - `{"name": "Presentes"}`
+pub async fn create_wishlist(
+    app_state: web::Data<AppState>,
+    payload: Json<WishlistRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let dto = payload.into_inner();
+
+    let result = WishlistService::create_wishlist(&app_state, tenant_id, user_id, dto).await?;
+
+    Ok(HttpResponse::Created().json(serde_json::json!(result)))
+}
+
+pub async fn get_saved_wishlist(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+
+    let result = WishlistService::get_saved(&app_state, tenant_id, user_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn get_wishlist(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let id = path.into_inner();
+
+    let result = WishlistService::get_wishlist(&app_state, tenant_id, user_id, id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn rename_wishlist(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Json<WishlistRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let id = path.into_inner();
+    let dto = payload.into_inner();
+
+    let result = WishlistService::rename_wishlist(&app_state, tenant_id, user_id, id, dto).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn delete_wishlist(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let id = path.into_inner();
+
+    WishlistService::delete_wishlist(&app_state, tenant_id, user_id, id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// POST /wishlists/{id}/share/ - link público somente leitura
+pub async fn share_wishlist(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let id = path.into_inner();
+
+    let result = WishlistService::share_wishlist(&app_state, tenant_id, user_id, id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn unshare_wishlist(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let id = path.into_inner();
+
+    WishlistService::unshare_wishlist(&app_state, tenant_id, user_id, id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// GET /wishlists/shared/{token}/ - sem autenticação
+pub async fn get_shared_wishlist(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let token = path.into_inner();
+
+    let result = WishlistService::get_shared(&app_state, &token).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /wishlists/{id}/items/ - `{"product_id": "...", "notify_price_drop": true}`
+pub async fn add_wishlist_item(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    payload: Json<AddWishlistItemRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let wishlist_id = path.into_inner();
+    let dto = payload.into_inner();
+
+    let result =
+        WishlistService::add_item(&app_state, tenant_id, user_id, wishlist_id, dto).await?;
+
+    Ok(HttpResponse::Created().json(serde_json::json!(result)))
+}
+
+pub async fn update_wishlist_item(
+    app_state: web::Data<AppState>,
+    path: web::Path<(Uuid, Uuid)>,
+    payload: Json<UpdateWishlistItemRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let (wishlist_id, item_id) = path.into_inner();
+    let dto = payload.into_inner();
+
+    let result =
+        WishlistService::update_item(&app_state, tenant_id, user_id, wishlist_id, item_id, dto)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn delete_wishlist_item(
+    app_state: web::Data<AppState>,
+    path: web::Path<(Uuid, Uuid)>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let (wishlist_id, item_id) = path.into_inner();
+
+    WishlistService::delete_item(&app_state, tenant_id, user_id, wishlist_id, item_id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// POST /wishlists/{id}/items/{item_id}/move-to-cart/ - devolve o carrinho atualizado
+pub async fn move_wishlist_item_to_cart(
+    app_state: web::Data<AppState>,
+    path: web::Path<(Uuid, Uuid)>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let audit = AuditContext::from_request(&req);
+    let (wishlist_id, item_id) = path.into_inner();
+
+    let result =
+        WishlistService::move_to_cart(&app_state, tenant_id, user_id, wishlist_id, item_id, &audit)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// POST /carts/items/{id}/save-for-later/ - devolve o carrinho atualizado
+pub async fn save_cart_item_for_later(
+    app_state: web::Data<AppState>,
+    path: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = req.tenant_id()?;
+    let user_id = req.user_id()?;
+    let audit = AuditContext::from_request(&req);
+    let item_id = path.into_inner();
+
+    let result =
+        WishlistService::save_for_later(&app_state, tenant_id, user_id, item_id, &audit).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+/// GET /wishlists/notifications/ - `?unread=true` só os não lidos
+pub async fn list_wishlist_notifications(
+    app_state: web::Data<AppState>,
+    query: web::Query<NotificationListParams>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = req.user_id()?;
+
+    let result =
+        WishlistService::list_notifications(&app_state, user_id, query.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!(result)))
+}
+
+pub async fn read_wishlist_notifications(
+    app_state: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = req.user_id()?;
+
+    let updated = WishlistService::mark_notifications_read(&app_state, user_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "updated": updated })))
+}
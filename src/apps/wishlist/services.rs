@@ -0,0 +1,657 @@
+use crate::app_core::app_error::AppError;
+use crate::app_core::app_state::AppState;
+use crate::app_core::telemetry::record_cart_operation;
+use crate::app_core::workers::WorkerSupervisor;
+use crate::apps::audit::models::{AuditAction, AuditContext};
+use crate::apps::cart::models::{
+    Cart, CartAuditSnapshot, CartLineSnapshot, CartOwner, CartWithItems,
+};
+use crate::apps::cart::repositories::CartRepository;
+use crate::apps::cart::services::{CartService, cart_changed};
+use crate::apps::product::models::Product;
+use crate::apps::product::repositories::ProductRepository;
+use crate::apps::product_price::services::ProductPriceService;
+use crate::apps::wishlist::models::{
+    AddWishlistItemRequest, AlertCandidate, AlertKind, NewWishlistNotification,
+    NotificationListParams, ProductSnapshot, ShareWishlistResponse, SharedWishlist,
+    SharedWishlistItem, UpdateWishlistItemRequest, Wishlist, WishlistItem, WishlistItemWithProduct,
+    WishlistNotification, WishlistRequest, WishlistWithItems,
+};
+use crate::apps::wishlist::repositories::{MoveToCartOutcome, NewWishlistItem, WishlistRepository};
+use actix_web::web;
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{debug, error};
+use uuid::Uuid;
+
+/// Nome da lista criada no primeiro "salvar para depois"
+const SAVED_FOR_LATER_NAME: &str = "Salvos para depois";
+
+/// Tamanho máximo do nome de uma lista
+const MAX_NAME_LENGTH: usize = 100;
+
+/// Avisos devolvidos por GET /wishlists/notifications/
+const NOTIFICATIONS_LIMIT: i64 = 100;
+
+/// Intervalo entre as varreduras de queda de preço e volta ao estoque
+const ALERT_SCAN_INTERVAL_SECS: u64 = 300;
+
+pub struct WishlistService;
+
+impl WishlistService {
+    pub async fn list_wishlists(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<Wishlist>, AppError> {
+        WishlistRepository::new(app_state)
+            .find_all(tenant_id, user_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    pub async fn create_wishlist(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        request: WishlistRequest,
+    ) -> Result<Wishlist, AppError> {
+        let name = validate_name(&request.name)?;
+
+        WishlistRepository::new(app_state)
+            .create(tenant_id, user_id, &name)
+            .await
+            .map_err(name_conflict)
+    }
+
+    pub async fn get_wishlist(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<WishlistWithItems, AppError> {
+        let wishlist = Self::find_owned(app_state, tenant_id, user_id, id).await?;
+        Self::with_items(app_state, wishlist).await
+    }
+
+    /// GET /wishlists/saved/ - lista "salvos para depois" (criada vazia se ainda não existir)
+    pub async fn get_saved(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<WishlistWithItems, AppError> {
+        let wishlist = WishlistRepository::new(app_state)
+            .get_or_create_saved(tenant_id, user_id, SAVED_FOR_LATER_NAME)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        Self::with_items(app_state, wishlist).await
+    }
+
+    pub async fn rename_wishlist(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        id: Uuid,
+        request: WishlistRequest,
+    ) -> Result<Wishlist, AppError> {
+        let name = validate_name(&request.name)?;
+        Self::find_owned(app_state, tenant_id, user_id, id).await?;
+
+        WishlistRepository::new(app_state)
+            .rename(id, &name)
+            .await
+            .map_err(name_conflict)?
+            .ok_or_else(|| AppError::not_found("Lista não encontrada"))
+    }
+
+    pub async fn delete_wishlist(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<(), AppError> {
+        Self::find_owned(app_state, tenant_id, user_id, id).await?;
+
+        let deleted = WishlistRepository::new(app_state)
+            .delete(id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        match deleted {
+            true => Ok(()),
+            false => Err(AppError::not_found("Lista não encontrada")),
+        }
+    }
+
+    /// POST /wishlists/{id}/share/ - gera um token novo (o link anterior deixa de valer)
+    pub async fn share_wishlist(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<ShareWishlistResponse, AppError> {
+        Self::find_owned(app_state, tenant_id, user_id, id).await?;
+
+        let share_token = Uuid::new_v4().simple().to_string();
+        WishlistRepository::new(app_state)
+            .set_share_token(id, Some(&share_token))
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        Ok(ShareWishlistResponse {
+            path: format!("/api/v1/wishlists/shared/{}/", share_token),
+            share_token,
+        })
+    }
+
+    pub async fn unshare_wishlist(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<(), AppError> {
+        Self::find_owned(app_state, tenant_id, user_id, id).await?;
+
+        WishlistRepository::new(app_state)
+            .set_share_token(id, None)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        Ok(())
+    }
+
+    /// GET /wishlists/shared/{token}/ - somente leitura, só produtos ativos
+    pub async fn get_shared(app_state: &AppState, token: &str) -> Result<SharedWishlist, AppError> {
+        let wishlist = WishlistRepository::new(app_state)
+            .find_by_share_token(token)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Lista não encontrada"))?;
+
+        let items = Self::with_items(app_state, wishlist.clone())
+            .await?
+            .items
+            .into_iter()
+            .filter(|item| item.is_active)
+            .map(|item| SharedWishlistItem {
+                product_id: item.item.product_id,
+                product_name: item.product_name,
+                product_slug: item.product_slug,
+                variant_id: item.item.variant_id,
+                attributes_snapshot: item.item.attributes_snapshot,
+                quantity: item.item.quantity,
+                price: item.price,
+                in_stock: item.stock_quantity > 0,
+            })
+            .collect();
+
+        Ok(SharedWishlist {
+            name: wishlist.name,
+            items,
+        })
+    }
+
+    pub async fn add_item(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        wishlist_id: Uuid,
+        request: AddWishlistItemRequest,
+    ) -> Result<WishlistItem, AppError> {
+        let quantity = request.quantity.unwrap_or(1);
+        if quantity <= 0 {
+            return Err(AppError::bad_request("Quantidade deve ser maior que zero"));
+        }
+        Self::find_owned(app_state, tenant_id, user_id, wishlist_id).await?;
+
+        let product = Self::find_available_product(app_state, request.product_id).await?;
+        let last_seen = match request.notify_price_drop || request.notify_back_in_stock {
+            true => Some(Self::snapshot(app_state, &product).await?),
+            false => None,
+        };
+
+        let item = NewWishlistItem {
+            product_id: product.id,
+            variant_id: None,
+            attributes_snapshot: serde_json::json!({}),
+            quantity,
+            notify_price_drop: request.notify_price_drop,
+            notify_back_in_stock: request.notify_back_in_stock,
+            last_seen,
+        };
+        WishlistRepository::new(app_state)
+            .add_item(wishlist_id, &item)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    /// PATCH /wishlists/{id}/items/{item_id}/ - ligar um aviso toma o estado atual como referência
+    pub async fn update_item(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        wishlist_id: Uuid,
+        item_id: Uuid,
+        request: UpdateWishlistItemRequest,
+    ) -> Result<WishlistItem, AppError> {
+        Self::find_owned(app_state, tenant_id, user_id, wishlist_id).await?;
+        let repository = WishlistRepository::new(app_state);
+        let mut item = repository
+            .find_item(wishlist_id, item_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Item não encontrado na lista"))?;
+
+        if let Some(quantity) = request.quantity {
+            if quantity <= 0 {
+                return Err(AppError::bad_request("Quantidade deve ser maior que zero"));
+            }
+            item.quantity = quantity;
+        }
+        let turned_on = request.notify_price_drop == Some(true) && !item.notify_price_drop
+            || request.notify_back_in_stock == Some(true) && !item.notify_back_in_stock;
+        item.notify_price_drop = request.notify_price_drop.unwrap_or(item.notify_price_drop);
+        item.notify_back_in_stock = request
+            .notify_back_in_stock
+            .unwrap_or(item.notify_back_in_stock);
+
+        let last_seen = match turned_on {
+            true => {
+                let product = Self::find_product(app_state, item.product_id).await?;
+                Some(Self::snapshot(app_state, &product).await?)
+            }
+            false => None,
+        };
+
+        repository
+            .update_item(&item, last_seen.as_ref())
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Item não encontrado na lista"))
+    }
+
+    pub async fn delete_item(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        wishlist_id: Uuid,
+        item_id: Uuid,
+    ) -> Result<(), AppError> {
+        Self::find_owned(app_state, tenant_id, user_id, wishlist_id).await?;
+
+        let deleted = WishlistRepository::new(app_state)
+            .delete_item(wishlist_id, item_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        match deleted {
+            true => Ok(()),
+            false => Err(AppError::not_found("Item não encontrado na lista")),
+        }
+    }
+
+    /// POST /carts/items/{id}/save-for-later/ - tira a linha do carrinho e a guarda, com
+    /// variante e atributos, na lista "salvos para depois"
+    pub async fn save_for_later(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        cart_item_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<CartWithItems, AppError> {
+        let owner = CartOwner::User { tenant_id, user_id };
+        let (cart, cart_item, changes, subtotal) =
+            CartService::plan_line_removal(app_state, owner, cart_item_id).await?;
+        let before = Self::cart_snapshot(app_state, &cart).await?;
+
+        let repository = WishlistRepository::new(app_state);
+        let saved_list = repository
+            .get_or_create_saved(tenant_id, user_id, SAVED_FOR_LATER_NAME)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        let item = NewWishlistItem {
+            product_id: cart_item.product_id,
+            variant_id: cart_item.variant_id,
+            attributes_snapshot: cart_item.attributes_snapshot,
+            quantity: cart_item.quantity,
+            notify_price_drop: false,
+            notify_back_in_stock: false,
+            last_seen: None,
+        };
+        repository
+            .save_for_later(saved_list.id, &item, &cart, &changes, &subtotal)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(cart_changed)?;
+
+        let cart_with_items = CartService::get_cart(app_state, owner).await?;
+        CartService::record_audit(
+            app_state,
+            audit,
+            AuditAction::Update,
+            cart.id,
+            Some(&before),
+            Some(&CartAuditSnapshot::from_cart_with_items(&cart_with_items)),
+        )
+        .await;
+
+        record_cart_operation("save_for_later");
+        Ok(cart_with_items)
+    }
+
+    /// POST /wishlists/{id}/items/{item_id}/move-to-cart/ - preço e estoque atuais valem como
+    /// numa adição comum; o item sai da lista
+    pub async fn move_to_cart(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        wishlist_id: Uuid,
+        item_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<CartWithItems, AppError> {
+        Self::find_owned(app_state, tenant_id, user_id, wishlist_id).await?;
+        let repository = WishlistRepository::new(app_state);
+        let item = repository
+            .find_item(wishlist_id, item_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Item não encontrado na lista"))?;
+
+        let owner = CartOwner::User { tenant_id, user_id };
+        let line = CartLineSnapshot {
+            product_id: item.product_id,
+            variant_id: item.variant_id,
+            attributes_snapshot: item.attributes_snapshot,
+            quantity: item.quantity,
+        };
+        let (cart, changes, subtotal) =
+            CartService::plan_line_restore(app_state, owner, &line, audit).await?;
+        let before = Self::cart_snapshot(app_state, &cart).await?;
+
+        match repository
+            .move_to_cart(wishlist_id, item_id, &cart, &changes, &subtotal)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+        {
+            MoveToCartOutcome::Moved => {}
+            MoveToCartOutcome::ItemGone => {
+                return Err(AppError::not_found("Item não encontrado na lista"));
+            }
+            MoveToCartOutcome::CartChanged => return Err(cart_changed()),
+        }
+
+        let cart_with_items = CartService::get_cart(app_state, owner).await?;
+        CartService::record_audit(
+            app_state,
+            audit,
+            AuditAction::Update,
+            cart.id,
+            Some(&before),
+            Some(&CartAuditSnapshot::from_cart_with_items(&cart_with_items)),
+        )
+        .await;
+
+        record_cart_operation("move_to_cart");
+        Ok(cart_with_items)
+    }
+
+    /// Linhas do carrinho antes de gravar, para a auditoria
+    async fn cart_snapshot(
+        app_state: &AppState,
+        cart: &Cart,
+    ) -> Result<CartAuditSnapshot, AppError> {
+        let items = CartRepository::new(app_state)
+            .list_cart_items(cart.id)
+            .await?;
+        Ok(CartAuditSnapshot::from_cart(cart, &items))
+    }
+
+    pub async fn list_notifications(
+        app_state: &AppState,
+        user_id: Uuid,
+        params: NotificationListParams,
+    ) -> Result<Vec<WishlistNotification>, AppError> {
+        WishlistRepository::new(app_state)
+            .list_notifications(user_id, params.unread, NOTIFICATIONS_LIMIT)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    /// Marca todos os avisos do usuário como lidos; devolve quantos foram marcados
+    pub async fn mark_notifications_read(
+        app_state: &AppState,
+        user_id: Uuid,
+    ) -> Result<u64, AppError> {
+        WishlistRepository::new(app_state)
+            .mark_notifications_read(user_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))
+    }
+
+    /// Compara os itens com aviso ligado com o produto atual, grava os avisos e atualiza a
+    /// referência de cada item. Devolve quantos avisos foram gerados.
+    pub async fn scan_alerts(app_state: &AppState) -> Result<usize, AppError> {
+        let repository = WishlistRepository::new(app_state);
+        let candidates = repository
+            .find_alert_candidates()
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        if candidates.is_empty() {
+            return Ok(0);
+        }
+
+        let mut product_ids: Vec<Uuid> = candidates.iter().map(|c| c.product_id).collect();
+        product_ids.sort();
+        product_ids.dedup();
+        let products = ProductRepository::new(app_state)
+            .find_by_ids_any_status(&product_ids)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+        let snapshots: HashMap<Uuid, ProductSnapshot> = Self::snapshots(app_state, &products)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut seen = Vec::with_capacity(candidates.len());
+        let mut notifications = Vec::new();
+        for candidate in &candidates {
+            // Produto removido de vez: nada a comparar
+            let Some(current) = snapshots.get(&candidate.product_id) else {
+                continue;
+            };
+            notifications.extend(detect_alerts(candidate, current).into_iter().map(|kind| {
+                NewWishlistNotification {
+                    user_id: candidate.user_id,
+                    wishlist_item_id: candidate.item_id,
+                    product_id: candidate.product_id,
+                    kind,
+                }
+            }));
+            if candidate.last_seen.as_ref() != Some(current) {
+                seen.push((candidate.item_id, current.clone()));
+            }
+        }
+
+        if !seen.is_empty() || !notifications.is_empty() {
+            repository
+                .record_alerts(&seen, &notifications)
+                .await
+                .map_err(|e| AppError::database_error(e.to_string()))?;
+        }
+        Ok(notifications.len())
+    }
+
+    async fn find_owned(
+        app_state: &AppState,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Wishlist, AppError> {
+        WishlistRepository::new(app_state)
+            .find_by_id(id, tenant_id, user_id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .ok_or_else(|| AppError::not_found("Lista não encontrada"))
+    }
+
+    async fn with_items(
+        app_state: &AppState,
+        wishlist: Wishlist,
+    ) -> Result<WishlistWithItems, AppError> {
+        let items = WishlistRepository::new(app_state)
+            .list_items(wishlist.id)
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?;
+
+        let product_ids: Vec<Uuid> = items.iter().map(|item| item.product_id).collect();
+        let products = match product_ids.is_empty() {
+            true => vec![],
+            false => ProductRepository::new(app_state)
+                .find_by_ids_any_status(&product_ids)
+                .await
+                .map_err(|e| AppError::database_error(e.to_string()))?,
+        };
+        let prices = ProductPriceService::effective_prices(app_state, &products).await?;
+        let products: HashMap<Uuid, (Product, _)> = products
+            .into_iter()
+            .zip(prices)
+            .map(|(product, price)| (product.id, (product, price.price)))
+            .collect();
+
+        // Itens de produtos removidos de vez somem da lista
+        let items = items
+            .into_iter()
+            .filter_map(|item| {
+                let (product, price) = products.get(&item.product_id)?;
+                Some(WishlistItemWithProduct {
+                    product_name: product.name.clone(),
+                    product_slug: product.slug.clone(),
+                    price: price.clone(),
+                    stock_quantity: product.stock_quantity,
+                    is_active: product.is_active && product.dt_deleted.is_none(),
+                    item,
+                })
+            })
+            .collect();
+
+        Ok(WishlistWithItems { wishlist, items })
+    }
+
+    async fn find_product(app_state: &AppState, product_id: Uuid) -> Result<Product, AppError> {
+        ProductRepository::new(app_state)
+            .find_by_ids_any_status(&[product_id])
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .pop()
+            .ok_or_else(|| AppError::not_found("Produto não encontrado"))
+    }
+
+    async fn find_available_product(
+        app_state: &AppState,
+        product_id: Uuid,
+    ) -> Result<Product, AppError> {
+        ProductRepository::new(app_state)
+            .find_by_ids(&[product_id])
+            .await
+            .map_err(|e| AppError::database_error(e.to_string()))?
+            .pop()
+            .ok_or_else(|| AppError::not_found("Produto não encontrado"))
+    }
+
+    async fn snapshot(
+        app_state: &AppState,
+        product: &Product,
+    ) -> Result<ProductSnapshot, AppError> {
+        Self::snapshots(app_state, std::slice::from_ref(product))
+            .await?
+            .pop()
+            .map(|(_, snapshot)| snapshot)
+            .ok_or_else(|| AppError::not_found("Produto não encontrado"))
+    }
+
+    /// Preço efetivo (moeda base da loja) e disponibilidade de cada produto
+    async fn snapshots(
+        app_state: &AppState,
+        products: &[Product],
+    ) -> Result<Vec<(Uuid, ProductSnapshot)>, AppError> {
+        let prices = ProductPriceService::effective_prices(app_state, products).await?;
+
+        Ok(products
+            .iter()
+            .zip(prices)
+            .map(|(product, price)| {
+                let in_stock =
+                    product.is_active && product.dt_deleted.is_none() && product.stock_quantity > 0;
+                (
+                    product.id,
+                    ProductSnapshot {
+                        price: price.price,
+                        in_stock,
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+/// Worker que gera os avisos de queda de preço e volta ao estoque
+pub fn spawn_wishlist_alerts(supervisor: &WorkerSupervisor, app_state: web::Data<AppState>) {
+    supervisor.spawn_periodic(
+        "wishlist_alerts",
+        Duration::from_secs(ALERT_SCAN_INTERVAL_SECS),
+        move || {
+            let app_state = app_state.clone();
+            async move {
+                match WishlistService::scan_alerts(&app_state).await {
+                    Ok(created) => debug!(created, "Avisos de lista de desejos gerados"),
+                    Err(e) => error!("Erro ao gerar avisos de lista de desejos: {}", e),
+                }
+            }
+        },
+    );
+}
+
+/// Avisos do item frente ao estado atual do produto. Sem referência anterior (ou com troca de
+/// moeda da loja) não há aviso de preço: o estado atual passa a ser a referência.
+pub fn detect_alerts(candidate: &AlertCandidate, current: &ProductSnapshot) -> Vec<AlertKind> {
+    let mut alerts = Vec::new();
+    let Some(last_seen) = &candidate.last_seen else {
+        return alerts;
+    };
+
+    if candidate.notify_price_drop
+        && current.in_stock
+        && last_seen.price.currency == current.price.currency
+        && current.price.amount_minor < last_seen.price.amount_minor
+    {
+        alerts.push(AlertKind::PriceDrop {
+            old_price: last_seen.price.clone(),
+            new_price: current.price.clone(),
+        });
+    }
+    if candidate.notify_back_in_stock && !last_seen.in_stock && current.in_stock {
+        alerts.push(AlertKind::BackInStock);
+    }
+
+    alerts
+}
+
+fn validate_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::bad_request("Nome da lista é obrigatório"));
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(AppError::bad_request(format!(
+            "Nome da lista deve ter no máximo {} caracteres",
+            MAX_NAME_LENGTH
+        )));
+    }
+    Ok(name.to_string())
+}
+
+/// Nome repetido entre as listas do usuário (`uq_wishlist_name_per_user`)
+fn name_conflict(error: sqlx::Error) -> AppError {
+    match error.as_database_error().and_then(|e| e.code()).as_deref() {
+        Some("23505") => AppError::Conflict(Some("Já existe uma lista com esse nome".into())),
+        _ => AppError::database_error(error.to_string()),
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use crate::apps::wishlist::models::{AlertCandidate, AlertKind, ProductSnapshot};
+    use crate::apps::wishlist::services::detect_alerts;
+    use crate::utils::money::Money;
+    use uuid::Uuid;
+
+    fn snapshot(price: i64, currency: &str, in_stock: bool) -> ProductSnapshot {
+        ProductSnapshot {
+            price: Money::new(price, currency),
+            in_stock,
+        }
+    }
+
+    fn candidate(
+        notify_price_drop: bool,
+        notify_back_in_stock: bool,
+        last_seen: Option<ProductSnapshot>,
+    ) -> AlertCandidate {
+        AlertCandidate {
+            item_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            product_id: Uuid::new_v4(),
+            notify_price_drop,
+            notify_back_in_stock,
+            last_seen,
+        }
+    }
+
+    #[test]
+    fn test_detect_price_drop() {
+        let watching = candidate(true, false, Some(snapshot(1000, "BRL", true)));
+
+        assert_eq!(
+            detect_alerts(&watching, &snapshot(900, "BRL", true)),
+            vec![AlertKind::PriceDrop {
+                old_price: Money::new(1000, "BRL"),
+                new_price: Money::new(900, "BRL"),
+            }]
+        );
+        // Alta, preço igual, troca de moeda ou produto indisponível não avisam
+        assert!(detect_alerts(&watching, &snapshot(1100, "BRL", true)).is_empty());
+        assert!(detect_alerts(&watching, &snapshot(1000, "BRL", true)).is_empty());
+        assert!(detect_alerts(&watching, &snapshot(100, "USD", true)).is_empty());
+        assert!(detect_alerts(&watching, &snapshot(900, "BRL", false)).is_empty());
+
+        // Aviso desligado
+        let muted = candidate(false, false, Some(snapshot(1000, "BRL", true)));
+        assert!(detect_alerts(&muted, &snapshot(900, "BRL", true)).is_empty());
+    }
+
+    #[test]
+    fn test_detect_back_in_stock() {
+        let watching = candidate(false, true, Some(snapshot(1000, "BRL", false)));
+
+        assert_eq!(
+            detect_alerts(&watching, &snapshot(1000, "BRL", true)),
+            vec![AlertKind::BackInStock]
+        );
+        assert!(detect_alerts(&watching, &snapshot(1000, "BRL", false)).is_empty());
+
+        // Já estava em estoque: nada mudou
+        let in_stock = candidate(false, true, Some(snapshot(1000, "BRL", true)));
+        assert!(detect_alerts(&in_stock, &snapshot(1000, "BRL", true)).is_empty());
+
+        // Os dois avisos ao mesmo tempo
+        let both = candidate(true, true, Some(snapshot(1000, "BRL", false)));
+        assert_eq!(detect_alerts(&both, &snapshot(800, "BRL", true)).len(), 2);
+    }
+
+    #[test]
+    fn test_detect_alerts_without_reference() {
+        // Sem estado anterior, o atual só passa a ser a referência
+        let fresh = candidate(true, true, None);
+        assert!(detect_alerts(&fresh, &snapshot(1, "BRL", true)).is_empty());
+    }
+}
@@ -3,29 +3,48 @@ mod apps;
 mod utils;
 
 use actix_web::{App, HttpServer, web};
+use clap::Parser;
 use tracing::info;
 use tracing_actix_web::TracingLogger;
 
 use crate::app_core::app_routes::api_v1_scope;
+use crate::app_core::cli::{self, Cli};
 use crate::app_core::databases::postgres::get_db_pool;
+use crate::app_core::rate_limit::spawn_bucket_cleanup;
+use crate::app_core::settings::RateLimitBackend;
+use crate::app_core::telemetry::metrics_handler;
+use crate::app_core::workers::{supervisor, wait_for_shutdown_signal};
 use crate::app_core::{app_state::AppState, init_settings};
+use crate::apps::product_bulk::services::spawn_interrupted_imports;
+use crate::apps::wishlist::services::spawn_wishlist_alerts;
 use dotenvy::dotenv;
+use std::time::Duration;
 
 #[actix_web::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    println!("🚀 Iniciando Actix Web Server...");
+    let args = Cli::parse();
     dotenv().ok();
 
-    // Configurar logging
-    init_settings::setup_development_logging()?;
-
     // Inicializar configurações primeiro
     init_settings::init_settings()?;
     let settings = init_settings::get_settings();
 
+    // Configurar logging (e exportação OTLP, se habilitada)
+    let tracer_provider = init_settings::setup_development_logging(&settings.telemetry)?;
+
     // Inicializar conexões com bancos de dados
     let pool = get_db_pool().await;
 
+    // Subcomandos (importação/exportação de produtos) rodam e encerram sem subir o servidor
+    if let Some(command) = args.command {
+        cli::run(command, &AppState { db: pool })
+            .await
+            .map_err(|e| e.message())?;
+        return Ok(());
+    }
+
+    println!("🚀 Iniciando Actix Web Server...");
+
     info!(
         host = %settings.server.host,
         port = %settings.server.port,
@@ -35,15 +54,50 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
 
     let app_state = web::Data::new(AppState { db: pool });
 
-    HttpServer::new(move || {
+    // Workers em background, parados de forma coordenada com o servidor. Rodam no runtime do
+    // main, que segue vivo depois do servidor até o fim do desligamento.
+    let workers = supervisor();
+    workers.set_runtime(tokio::runtime::Handle::current());
+    if settings.rate_limit.enabled && settings.rate_limit.backend == RateLimitBackend::Postgres {
+        spawn_bucket_cleanup(workers, app_state.clone());
+    }
+    spawn_wishlist_alerts(workers, app_state.clone());
+    spawn_interrupted_imports(workers, app_state.clone());
+
+    let server = HttpServer::new(move || {
         App::new()
             .wrap(TracingLogger::default())
             .app_data(app_state.clone())
+            .route("/metrics", web::get().to(metrics_handler))
             .service(api_v1_scope())
     })
-    .bind((settings.server.host.clone(), settings.server.port))?
-    .run()
-    .await?;
+    .shutdown_timeout(settings.server.shutdown_timeout_secs)
+    // Sinais tratados abaixo para que readiness e workers saibam do desligamento
+    .disable_signals()
+    .bind((settings.server.host, settings.server.port))?
+    .run();
+
+    let server_handle = server.handle();
+    actix_web::rt::spawn(async move {
+        wait_for_shutdown_signal().await;
+        // /health/ready/ passa a responder 503 e os workers param de pegar trabalho novo
+        workers.begin_shutdown();
+        server_handle.stop(true).await;
+    });
+
+    server.await?;
+
+    // O prazo conta desde o sinal, então requisições e workers drenam em paralelo
+    workers
+        .shutdown(Duration::from_secs(settings.server.shutdown_timeout_secs))
+        .await;
+
+    // Envia os spans pendentes antes de encerrar
+    if let Some(provider) = tracer_provider
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::error!("Erro ao finalizar o exporter OTLP: {}", e);
+    }
 
     Ok(())
 }
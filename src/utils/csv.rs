@@ -0,0 +1,139 @@
+//! Leitura e escrita de CSV no formato RFC 4180: campos com separador, aspas ou quebra de
+//! linha vão entre aspas duplas, e `""` representa uma aspa dentro do campo.
+
+use std::fmt;
+
+/// Registro lido, com a linha do arquivo em que começa (1-based)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRecord {
+    pub line: usize,
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "linha {}: {}", self.line, self.message)
+    }
+}
+
+/// Vírgula por padrão; ponto e vírgula quando o cabeçalho usa mais `;` do que `,`
+/// (planilhas exportadas com localização pt-BR)
+fn detect_delimiter(input: &str) -> char {
+    let header = input.lines().next().unwrap_or_default();
+    let count = |delimiter: char| header.chars().filter(|c| *c == delimiter).count();
+
+    if count(';') > count(',') { ';' } else { ',' }
+}
+
+/// Lê todos os registros. Linhas em branco são ignoradas; o BOM do UTF-8 é descartado.
+pub fn parse(input: &str) -> Result<Vec<CsvRecord>, CsvError> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let delimiter = detect_delimiter(input);
+
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut in_quotes = false;
+    // O campo atual já teve as aspas fechadas: só pode vir separador ou fim de linha
+    let mut closed_quotes = false;
+
+    let mut finish_record = |fields: &mut Vec<String>, field: &mut String, record_line: usize| {
+        fields.push(std::mem::take(field));
+        let fields = std::mem::take(fields);
+        if !(fields.len() == 1 && fields[0].is_empty()) {
+            records.push(CsvRecord {
+                line: record_line,
+                fields,
+            });
+        }
+    };
+
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => {
+                    in_quotes = false;
+                    closed_quotes = true;
+                }
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() && !closed_quotes => in_quotes = true,
+            c if c == delimiter => {
+                fields.push(std::mem::take(&mut field));
+                closed_quotes = false;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                finish_record(&mut fields, &mut field, record_line);
+                closed_quotes = false;
+                line += 1;
+                record_line = line;
+            }
+            _ if closed_quotes => {
+                return Err(CsvError {
+                    line,
+                    message: "Conteúdo após o fechamento das aspas".to_string(),
+                });
+            }
+            '"' => {
+                return Err(CsvError {
+                    line,
+                    message: "Aspas no meio de um campo sem aspas".to_string(),
+                });
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(CsvError {
+            line: record_line,
+            message: "Aspas não fechadas".to_string(),
+        });
+    }
+    if !field.is_empty() || !fields.is_empty() || closed_quotes {
+        finish_record(&mut fields, &mut field, record_line);
+    }
+
+    Ok(records)
+}
+
+fn escape(field: &str) -> String {
+    let needs_quotes = field.contains([',', ';', '"', '\n', '\r'])
+        || field.starts_with(' ')
+        || field.ends_with(' ');
+
+    if needs_quotes {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Monta uma linha separada por vírgula, terminada em `\n`
+pub fn write_record<'a>(fields: impl IntoIterator<Item = &'a str>) -> String {
+    let mut line = fields.into_iter().map(escape).collect::<Vec<_>>().join(",");
+    line.push('\n');
+    line
+}
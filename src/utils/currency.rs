@@ -0,0 +1,96 @@
+use crate::app_core::app_error::AppError;
+use bigdecimal::{BigDecimal, ToPrimitive};
+
+/// Moedas ISO 4217 sem casas decimais
+const ZERO_DECIMAL_CURRENCIES: &[&str] = &[
+    "BIF", "CLP", "DJF", "GNF", "ISK", "JPY", "KMF", "KRW", "PYG", "RWF", "UGX", "VND", "VUV",
+    "XAF", "XOF", "XPF",
+];
+
+/// Moedas ISO 4217 com três casas decimais
+const THREE_DECIMAL_CURRENCIES: &[&str] = &["BHD", "IQD", "JOD", "KWD", "LYD", "OMR", "TND"];
+
+/// Código de moeda em maiúsculas ("usd" vira "USD"); aceita três letras
+pub fn normalize_currency(code: &str) -> Result<String, AppError> {
+    let code = code.trim().to_ascii_uppercase();
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(AppError::bad_request(format!(
+            "Moeda inválida: {} (use o código ISO 4217, ex: BRL, USD)",
+            code
+        )));
+    }
+    Ok(code)
+}
+
+/// Casas decimais da unidade mínima da moeda (centavos = 2)
+pub fn minor_units(currency: &str) -> u32 {
+    if ZERO_DECIMAL_CURRENCIES.contains(&currency) {
+        0
+    } else if THREE_DECIMAL_CURRENCIES.contains(&currency) {
+        3
+    } else {
+        2
+    }
+}
+
+/// Regra de arredondamento para a unidade mínima da moeda
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum Rounding {
+    /// Meio para o par (bancário): 0,125 → 0,12 e 0,135 → 0,14. Sem viés em somas de muitas linhas
+    HalfEven,
+    /// Meio para longe do zero: 0,125 → 0,13
+    HalfUp,
+}
+
+/// Arredondamento das conversões entre moedas
+pub const CONVERSION_ROUNDING: Rounding = Rounding::HalfEven;
+
+fn power_of_ten(exponent: u32) -> BigDecimal {
+    BigDecimal::from(10_i64.pow(exponent))
+}
+
+/// Arredonda o valor para as casas decimais da moeda, com a regra informada
+pub fn round_to_currency(amount: &BigDecimal, currency: &str, rounding: Rounding) -> BigDecimal {
+    let scale = minor_units(currency);
+    // with_scale trunca em direção ao zero; o resto decide o arredondamento
+    let truncated = amount.with_scale(scale as i64);
+    let remainder = (amount - &truncated).abs() * power_of_ten(scale);
+    let half = BigDecimal::new(5.into(), 1);
+
+    let away_from_zero = match remainder.cmp(&half) {
+        std::cmp::Ordering::Greater => true,
+        std::cmp::Ordering::Less => false,
+        std::cmp::Ordering::Equal => match rounding {
+            Rounding::HalfUp => true,
+            Rounding::HalfEven => {
+                !(&truncated * power_of_ten(scale) / BigDecimal::from(2)).is_integer()
+            }
+        },
+    };
+
+    if !away_from_zero {
+        return truncated;
+    }
+    let step = BigDecimal::new(1.into(), scale as i64);
+    if amount < &BigDecimal::from(0) {
+        truncated - step
+    } else {
+        truncated + step
+    }
+}
+
+/// Converte `amount` pela cotação (1 unidade de origem = `rate` na moeda de destino)
+pub fn convert(amount: &BigDecimal, rate: &BigDecimal, target_currency: &str) -> BigDecimal {
+    round_to_currency(&(amount * rate), target_currency, CONVERSION_ROUNDING)
+}
+
+/// Valor em unidades mínimas (centavos), já arredondado pela regra informada
+pub fn to_minor_units(amount: &BigDecimal, currency: &str, rounding: Rounding) -> Option<i64> {
+    (round_to_currency(amount, currency, rounding) * power_of_ten(minor_units(currency))).to_i64()
+}
+
+/// Valor decimal a partir das unidades mínimas (centavos)
+pub fn from_minor_units(amount: i64, currency: &str) -> BigDecimal {
+    BigDecimal::new(amount.into(), minor_units(currency) as i64)
+}
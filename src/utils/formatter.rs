@@ -6,3 +6,54 @@ pub fn generate_username_from_email(email: &str) -> String {
         .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
         .collect()
 }
+
+/// Troca letras acentuadas (português) pela base ASCII; demais caracteres passam intactos
+fn transliterate(c: char) -> Option<&'static str> {
+    let ascii = match c {
+        'á' | 'à' | 'â' | 'ã' | 'ä' | 'å' => "a",
+        'é' | 'è' | 'ê' | 'ë' => "e",
+        'í' | 'ì' | 'î' | 'ï' => "i",
+        'ó' | 'ò' | 'ô' | 'õ' | 'ö' => "o",
+        'ú' | 'ù' | 'û' | 'ü' => "u",
+        'ç' => "c",
+        'ñ' => "n",
+        'ý' | 'ÿ' => "y",
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        'ª' => "a",
+        'º' => "o",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+/// Gera um slug (minúsculas, letras/números ASCII separados por hífen).
+/// Acentos são transliterados: "Pão de Açúcar" -> "pao-de-acucar".
+pub fn slugify(value: &str) -> String {
+    let mut slug = String::with_capacity(value.len());
+
+    for c in value.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if let Some(ascii) = transliterate(c) {
+            slug.push_str(ascii);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Primeiro slug livre a partir de `base`: "base", "base-2", "base-3", ...
+pub fn unique_slug(base: &str, taken: &[String]) -> String {
+    if !taken.iter().any(|s| s == base) {
+        return base.to_string();
+    }
+
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| !taken.iter().any(|s| s == candidate))
+        .expect("sequência de sufixos é infinita")
+}
@@ -1,6 +1,7 @@
-use crate::app_core::app_model::Claims;
+use crate::app_core::app_model::{Claims, GuestCartClaims};
 use crate::app_core::init_settings::get_settings;
 use chrono::{Duration, Utc};
+use jsonwebtoken::errors::ErrorKind;
 use jsonwebtoken::{
     Algorithm, DecodingKey, EncodingKey, Header, TokenData, Validation, decode, encode,
 };
@@ -33,6 +34,46 @@ pub fn generate_jwt(
     )
 }
 
+const GUEST_CART_SCOPE: &str = "guest_cart";
+
+pub fn generate_guest_cart_token(guest_id: Uuid) -> Result<String, jsonwebtoken::errors::Error> {
+    let settings = get_settings();
+
+    let exp = Utc::now()
+        .checked_add_signed(Duration::seconds(settings.cart.guest_token_ttl_secs as i64))
+        .expect("Erro ao calcular expiração do token")
+        .timestamp() as usize;
+
+    let claims = GuestCartClaims {
+        sub: guest_id.to_string(),
+        exp,
+        scope: GUEST_CART_SCOPE.to_string(),
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(settings.jwt.secret.as_bytes()),
+    )
+}
+
+/// guest_id de um token de carrinho válido
+pub fn verify_guest_cart_token(token: &str) -> Result<Uuid, jsonwebtoken::errors::Error> {
+    let settings = get_settings();
+
+    let claims = decode::<GuestCartClaims>(
+        token,
+        &DecodingKey::from_secret(settings.jwt.secret.as_bytes()),
+        &Validation::new(Algorithm::HS256),
+    )?
+    .claims;
+
+    if claims.scope != GUEST_CART_SCOPE {
+        return Err(ErrorKind::InvalidToken.into());
+    }
+    Uuid::parse_str(&claims.sub).map_err(|_| ErrorKind::InvalidSubject.into())
+}
+
 #[allow(dead_code)]
 pub fn verify_jwt(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
     let settings = get_settings();
@@ -1,6 +1,9 @@
+pub mod csv;
+pub mod currency;
 pub mod formatter;
 pub mod jwt;
 pub mod logging;
+pub mod money;
 pub mod pagination;
 pub mod validation;
 
@@ -0,0 +1,160 @@
+//! Valor monetário exato: inteiro na unidade mínima da moeda (centavos em BRL, ienes em JPY)
+//! mais o código ISO 4217. Toda conta é verificada: estouro ou moedas diferentes viram erro,
+//! nunca zero.
+
+use crate::app_core::app_error::AppError;
+use crate::utils::currency::{Rounding, from_minor_units, minor_units, to_minor_units};
+use bigdecimal::BigDecimal;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoneyError {
+    /// Resultado não cabe em i64 na unidade mínima
+    Overflow,
+    /// Operação entre moedas diferentes (converta antes pela cotação)
+    CurrencyMismatch(String, String),
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoneyError::Overflow => write!(f, "Valor monetário fora do intervalo suportado"),
+            MoneyError::CurrencyMismatch(left, right) => {
+                write!(f, "Operação entre moedas diferentes: {} e {}", left, right)
+            }
+        }
+    }
+}
+
+impl From<MoneyError> for AppError {
+    fn from(err: MoneyError) -> Self {
+        match err {
+            MoneyError::Overflow => AppError::bad_request(err.to_string()),
+            MoneyError::CurrencyMismatch(..) => AppError::internal(err.to_string()),
+        }
+    }
+}
+
+/// No banco é o tipo composto `money_value` (amount_minor, currency): as colunas continuam
+/// separadas e a leitura monta o valor com `ROW(coluna, currency)::money_value`.
+/// Na API sai como `{"amount_minor": 1999, "currency": "USD", "formatted": "USD 19.99"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, sqlx::Type)]
+#[sqlx(type_name = "money_value")]
+pub struct Money {
+    pub amount_minor: i64,
+    pub currency: String,
+}
+
+impl Money {
+    pub fn new(amount_minor: i64, currency: impl Into<String>) -> Self {
+        Self {
+            amount_minor,
+            currency: currency.into(),
+        }
+    }
+
+    pub fn zero(currency: impl Into<String>) -> Self {
+        Self::new(0, currency)
+    }
+
+    /// Valor decimal arredondado para as casas da moeda pela regra informada
+    pub fn from_decimal(
+        amount: &BigDecimal,
+        currency: &str,
+        rounding: Rounding,
+    ) -> Result<Self, MoneyError> {
+        to_minor_units(amount, currency, rounding)
+            .map(|amount_minor| Self::new(amount_minor, currency))
+            .ok_or(MoneyError::Overflow)
+    }
+
+    pub fn to_decimal(&self) -> BigDecimal {
+        from_minor_units(self.amount_minor, &self.currency)
+    }
+
+    pub fn checked_add(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.same_currency(other)?;
+        self.amount_minor
+            .checked_add(other.amount_minor)
+            .map(|amount_minor| Money::new(amount_minor, self.currency.clone()))
+            .ok_or(MoneyError::Overflow)
+    }
+
+    #[allow(dead_code)]
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.same_currency(other)?;
+        self.amount_minor
+            .checked_sub(other.amount_minor)
+            .map(|amount_minor| Money::new(amount_minor, self.currency.clone()))
+            .ok_or(MoneyError::Overflow)
+    }
+
+    /// Preço unitário vezes quantidade
+    pub fn checked_mul(&self, quantity: i64) -> Result<Money, MoneyError> {
+        self.amount_minor
+            .checked_mul(quantity)
+            .map(|amount_minor| Money::new(amount_minor, self.currency.clone()))
+            .ok_or(MoneyError::Overflow)
+    }
+
+    /// Soma na moeda informada (zero quando não há parcelas)
+    pub fn checked_sum<'a>(
+        values: impl IntoIterator<Item = &'a Money>,
+        currency: &str,
+    ) -> Result<Money, MoneyError> {
+        values
+            .into_iter()
+            .try_fold(Money::zero(currency), |total, value| {
+                total.checked_add(value)
+            })
+    }
+
+    fn same_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency != other.currency {
+            return Err(MoneyError::CurrencyMismatch(
+                self.currency.clone(),
+                other.currency.clone(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// "USD 19.99", sempre com as casas decimais da moeda
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scale = minor_units(&self.currency) as i64;
+        write!(
+            f,
+            "{} {}",
+            self.currency,
+            self.to_decimal().with_scale(scale)
+        )
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Money", 3)?;
+        state.serialize_field("amount_minor", &self.amount_minor)?;
+        state.serialize_field("currency", &self.currency)?;
+        state.serialize_field("formatted", &self.to_string())?;
+        state.end()
+    }
+}
+
+/// Aceita o mesmo formato da saída; `formatted` é ignorado
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            amount_minor: i64,
+            currency: String,
+        }
+
+        let raw = Raw::deserialize(deserializer)?;
+        Ok(Money::new(raw.amount_minor, raw.currency))
+    }
+}
@@ -1,4 +1,10 @@
+use crate::app_core::app_error::AppError;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
 
 #[derive(Serialize)]
 pub struct PaginatedResponse<T> {
@@ -15,6 +21,23 @@ pub struct PaginationParams {
 
     #[serde(default = "default_offset")]
     pub offset: i64,
+
+    /// Presente (vazio na primeira página) troca o offset pela paginação por cursor
+    pub cursor: Option<String>,
+
+    /// Inclui o total na paginação por cursor (custa uma contagem extra)
+    pub include_count: Option<bool>,
+}
+
+impl PaginationParams {
+    pub fn page_request(&self) -> Result<PageRequest, AppError> {
+        PageRequest::new(
+            self.limit,
+            Some(self.offset),
+            self.cursor.as_deref(),
+            self.include_count,
+        )
+    }
 }
 
 fn default_limit() -> i64 {
@@ -24,3 +47,242 @@ fn default_limit() -> i64 {
 fn default_offset() -> i64 {
     0
 }
+
+// ===== PAGINAÇÃO POR CURSOR =====
+
+/// Maior página aceita em qualquer listagem
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+/// Sentido da navegação a partir do cursor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorDirection {
+    Next,
+    Prev,
+}
+
+/// Posição na ordenação (dt_created DESC, id DESC), entregue ao cliente como texto opaco
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub dt_created: DateTime<Utc>,
+    pub id: Uuid,
+    pub direction: CursorDirection,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        let direction = match self.direction {
+            CursorDirection::Next => "n",
+            CursorDirection::Prev => "p",
+        };
+        let raw = format!(
+            "{}|{}|{}",
+            direction,
+            self.dt_created.timestamp_micros(),
+            self.id
+        );
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    pub fn decode(value: &str) -> Result<Self, AppError> {
+        let invalid = || AppError::bad_request("Cursor inválido");
+
+        let raw = URL_SAFE_NO_PAD.decode(value).map_err(|_| invalid())?;
+        let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+        let mut parts = raw.splitn(3, '|');
+
+        let direction = match parts.next() {
+            Some("n") => CursorDirection::Next,
+            Some("p") => CursorDirection::Prev,
+            _ => return Err(invalid()),
+        };
+        let dt_created = parts
+            .next()
+            .and_then(|micros| micros.parse().ok())
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or_else(invalid)?;
+        let id = parts
+            .next()
+            .and_then(|id| Uuid::parse_str(id).ok())
+            .ok_or_else(invalid)?;
+
+        Ok(Self {
+            dt_created,
+            id,
+            direction,
+        })
+    }
+}
+
+/// Como o cliente pediu a página
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageRequest {
+    Offset {
+        limit: i64,
+        offset: i64,
+    },
+    Cursor {
+        limit: i64,
+        cursor: Option<Cursor>,
+        include_count: bool,
+    },
+}
+
+impl PageRequest {
+    /// Sem `cursor`, paginação por offset; com `cursor` (vazio na primeira página), por cursor
+    pub fn new(
+        limit: i64,
+        offset: Option<i64>,
+        cursor: Option<&str>,
+        include_count: Option<bool>,
+    ) -> Result<Self, AppError> {
+        let limit = limit.clamp(1, MAX_PAGE_SIZE);
+
+        match cursor.map(str::trim) {
+            None => Ok(PageRequest::Offset {
+                limit,
+                offset: offset.unwrap_or(0).max(0),
+            }),
+            Some(cursor) => Ok(PageRequest::Cursor {
+                limit,
+                cursor: match cursor {
+                    "" => None,
+                    cursor => Some(Cursor::decode(cursor)?),
+                },
+                include_count: include_count.unwrap_or(false),
+            }),
+        }
+    }
+}
+
+/// Filtro, ordenação e limite da página por cursor sobre (`created_col`, `id_col`).
+/// Deve vir depois do WHERE; busca um item a mais para saber se existe outra página.
+pub fn push_keyset(
+    qb: &mut QueryBuilder<'_, Postgres>,
+    created_col: &str,
+    id_col: &str,
+    cursor: Option<&Cursor>,
+    limit: i64,
+) {
+    let direction = cursor.map(|c| c.direction).unwrap_or(CursorDirection::Next);
+
+    if let Some(cursor) = cursor {
+        let operator = match direction {
+            CursorDirection::Next => "<",
+            CursorDirection::Prev => ">",
+        };
+        qb.push(format!(" AND ({}, {}) {} (", created_col, id_col, operator))
+            .push_bind(cursor.dt_created.naive_utc())
+            .push(", ")
+            .push_bind(cursor.id)
+            .push(")");
+    }
+
+    // Voltando, a busca é em ordem crescente e a página é invertida depois
+    let order = match direction {
+        CursorDirection::Next => "DESC",
+        CursorDirection::Prev => "ASC",
+    };
+    qb.push(format!(
+        " ORDER BY {} {order}, {} {order}",
+        created_col, id_col
+    ));
+    qb.push(" LIMIT ").push_bind(limit + 1);
+}
+
+#[derive(Serialize)]
+pub struct CursorPage<T> {
+    pub results: Vec<T>,
+    pub limit: i64,
+    pub next: Option<String>,
+    pub prev: Option<String>,
+    /// Só quando pedido com `include_count=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<i64>,
+}
+
+impl<T> CursorPage<T> {
+    /// Monta a página a partir das linhas buscadas com `push_keyset` (até `limit + 1`)
+    pub fn from_rows(
+        mut rows: Vec<T>,
+        limit: i64,
+        cursor: Option<&Cursor>,
+        key: impl Fn(&T) -> (DateTime<Utc>, Uuid),
+        count: Option<i64>,
+    ) -> Self {
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit.max(0) as usize);
+
+        let going_back = cursor.is_some_and(|c| c.direction == CursorDirection::Prev);
+        if going_back {
+            rows.reverse();
+        }
+
+        let cursor_at = |item: Option<&T>, direction| {
+            item.map(|item| {
+                let (dt_created, id) = key(item);
+                Cursor {
+                    dt_created,
+                    id,
+                    direction,
+                }
+                .encode()
+            })
+        };
+
+        // Há próxima página se sobrou item indo adiante ou se viemos dela; idem para a anterior
+        let has_next = if going_back { true } else { has_more };
+        let has_prev = if going_back {
+            has_more
+        } else {
+            cursor.is_some()
+        };
+
+        CursorPage {
+            next: has_next
+                .then(|| cursor_at(rows.last(), CursorDirection::Next))
+                .flatten(),
+            prev: has_prev
+                .then(|| cursor_at(rows.first(), CursorDirection::Prev))
+                .flatten(),
+            results: rows,
+            limit,
+            count,
+        }
+    }
+}
+
+/// Página de uma listagem: por offset (padrão) ou por cursor
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum Page<T> {
+    Offset(PaginatedResponse<T>),
+    Cursor(CursorPage<T>),
+}
+
+impl<T> Page<T> {
+    pub fn results(&self) -> &[T] {
+        match self {
+            Page::Offset(page) => &page.results,
+            Page::Cursor(page) => &page.results,
+        }
+    }
+
+    /// Troca os itens mantendo a paginação (ex.: para anexar dados relacionados)
+    pub fn map_results<U>(self, f: impl FnOnce(Vec<T>) -> Vec<U>) -> Page<U> {
+        match self {
+            Page::Offset(page) => Page::Offset(PaginatedResponse {
+                count: page.count,
+                results: f(page.results),
+                limit: page.limit,
+                offset: page.offset,
+            }),
+            Page::Cursor(page) => Page::Cursor(CursorPage {
+                results: f(page.results),
+                limit: page.limit,
+                next: page.next,
+                prev: page.prev,
+                count: page.count,
+            }),
+        }
+    }
+}
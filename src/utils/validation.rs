@@ -8,6 +8,7 @@ lazy_static! {
     static ref PASSWORD_REGEX: Regex = Regex::new(r"[A-Za-z\d@$!%*#?&]{8,}").unwrap();
     static ref PHONE_REGEX: Regex = Regex::new(r"^\+?[1-9]\d{1,14}$").unwrap();
     static ref DOCUMENT_REGEX: Regex = Regex::new(r"^\d{3}\.\d{3}\.\d{3}-\d{2}$").unwrap();
+    static ref SLUG_REGEX: Regex = Regex::new(r"^[a-z0-9]+(?:-[a-z0-9]+)*$").unwrap();
 }
 
 pub fn validate_email(email: &str) -> Result<(), ValidationError> {
@@ -63,10 +64,21 @@ pub fn validate_document(document: &str) -> Result<(), ValidationError> {
 }
 
 pub fn validate_birth_date(birth_date: &str) -> Result<(), ValidationError> {
-    if let Err(_) = chrono::NaiveDate::parse_from_str(birth_date, "%Y-%m-%d") {
+    if chrono::NaiveDate::parse_from_str(birth_date, "%Y-%m-%d").is_err() {
         let mut err = ValidationError::new("invalid_birth_date");
         err.message = Some("Data de nascimento inválida. Use o formato: YYYY-MM-DD".into());
         return Err(err);
     }
     Ok(())
 }
+
+pub fn validate_slug(slug: &str) -> Result<(), ValidationError> {
+    if slug.len() > 120 || !SLUG_REGEX.is_match(slug) {
+        let mut err = ValidationError::new("invalid_slug");
+        err.message = Some(
+            "Slug inválido. Use letras minúsculas, números e hífens (ex: moda-feminina)".into(),
+        );
+        return Err(err);
+    }
+    Ok(())
+}
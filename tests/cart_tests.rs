@@ -0,0 +1,648 @@
+use actix_web::{App, test as actix_test, web};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use rust_template::app_core::app_routes::api_v1_scope;
+
+mod test_utils;
+use test_utils::{create_product, create_test_app_state, register, register_member, send, token};
+
+// ===== TESTS =====
+
+#[actix_web::test]
+async fn test_cart_item_quantity_bulk_and_clear() {
+    let app_state = create_test_app_state().await;
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller = register(&app).await;
+    let pen = create_product(&app, token(&seller), "Caneta", 250).await;
+    let book = create_product(&app, token(&seller), "Livro", 4000).await;
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+
+    let bulk = |operations: Value| {
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/bulk/")
+            .set_json(json!({ "operations": operations }))
+    };
+
+    // Operações aplicadas em ordem; o subtotal sai do estado final
+    let (status, cart) = send(
+        &app,
+        bulk(json!([
+            { "op": "add", "product_id": pen, "quantity": 2 },
+            { "op": "add", "product_id": book, "quantity": 1 },
+            { "op": "add", "product_id": pen, "quantity": 1 }
+        ])),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["unique_products"], 2);
+    assert_eq!(cart["item_count"], 4);
+    assert_eq!(cart["subtotal"]["amount_minor"], 4750);
+
+    // Tudo ou nada: estoque insuficiente descarta também a remoção anterior
+    let (status, _) = send(
+        &app,
+        bulk(json!([
+            { "op": "remove", "product_id": book },
+            { "op": "set", "product_id": pen, "quantity": 11 }
+        ])),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 400);
+    let (_, cart) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/carts/"),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(cart["unique_products"], 2);
+    assert_eq!(cart["subtotal"]["amount_minor"], 4750);
+
+    let (status, _) = send(&app, bulk(json!([])), Some(buyer_token)).await;
+    assert_eq!(status, 400);
+
+    // Quantidade exata da linha
+    let items = cart["items"].as_array().unwrap();
+    let pen_item = items.iter().find(|item| item["product_id"] == pen).unwrap();
+    let item_uri = format!("/api/v1/carts/items/{}/", pen_item["id"].as_str().unwrap());
+    let set = |quantity: i32| {
+        actix_test::TestRequest::patch()
+            .uri(&item_uri)
+            .set_json(json!({ "quantity": quantity }))
+    };
+    let (status, cart) = send(&app, set(5), Some(buyer_token)).await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["item_count"], 6);
+    assert_eq!(cart["subtotal"]["amount_minor"], 5250);
+
+    let (status, _) = send(&app, set(-1), Some(buyer_token)).await;
+    assert_eq!(status, 400);
+
+    // Zero remove a linha; depois disso o item não existe mais
+    let (status, cart) = send(&app, set(0), Some(buyer_token)).await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["unique_products"], 1);
+    assert_eq!(cart["subtotal"]["amount_minor"], 4000);
+    let (status, _) = send(&app, set(1), Some(buyer_token)).await;
+    assert_eq!(status, 404);
+
+    // Outro usuário não enxerga a linha
+    let other = register(&app).await;
+    let book_item = cart["items"][0]["id"].as_str().unwrap();
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::patch()
+            .uri(&format!("/api/v1/carts/items/{}/", book_item))
+            .set_json(json!({ "quantity": 2 })),
+        Some(token(&other)),
+    )
+    .await;
+    assert_eq!(status, 404);
+
+    let (status, cart) = send(
+        &app,
+        actix_test::TestRequest::post().uri("/api/v1/carts/clear/"),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["items"], json!([]));
+    assert_eq!(cart["subtotal"]["amount_minor"], 0);
+
+    // Adições concorrentes: cada uma grava sobre a versão que leu e a que perde a corrida
+    // recebe 409, sem perder incrementos nem deixar o subtotal defasado
+    let attempts = (0..5).map(|_| {
+        send(
+            &app,
+            bulk(json!([{ "op": "add", "product_id": pen, "quantity": 1 }])),
+            Some(buyer_token),
+        )
+    });
+    let statuses: Vec<u16> = futures::future::join_all(attempts)
+        .await
+        .into_iter()
+        .map(|(status, _)| status)
+        .collect();
+    assert!(
+        statuses
+            .iter()
+            .all(|status| *status == 200 || *status == 409),
+        "{:?}",
+        statuses
+    );
+    let added = statuses.iter().filter(|status| **status == 200).count() as i64;
+    assert!(added >= 1);
+
+    let (_, cart) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/carts/"),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(cart["item_count"], added);
+    assert_eq!(cart["subtotal"]["amount_minor"], 250 * added);
+}
+
+#[actix_web::test]
+async fn test_guest_cart_merged_on_register_and_login() {
+    let app_state = create_test_app_state().await;
+    let db = app_state.db.clone();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller = register(&app).await;
+    let pen = create_product(&app, token(&seller), "Caneta", 250).await;
+    let book = create_product(&app, token(&seller), "Livro", 4000).await;
+
+    // Visitante recebe o token no corpo e no cookie
+    let resp = actix_test::call_service(
+        &app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/guest/")
+            .to_request(),
+    )
+    .await;
+    assert_eq!(resp.status().as_u16(), 201);
+    let cookie = resp
+        .response()
+        .cookies()
+        .find(|cookie| cookie.name() == "cart_token")
+        .expect("Cookie do carrinho deveria existir")
+        .into_owned();
+    assert!(cookie.http_only().unwrap_or(false));
+    let body: Value = actix_test::read_body_json(resp).await;
+    let guest_token = body["token"].as_str().unwrap().to_string();
+    assert_eq!(cookie.value(), guest_token);
+    assert!(body["cart"]["guest_id"].is_string());
+    assert!(body["cart"]["user_id"].is_null());
+
+    let guest_bulk = |token: &str, operations: Value| {
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/guest/bulk/")
+            .insert_header(("X-Cart-Token", token.to_string()))
+            .set_json(json!({ "operations": operations }))
+    };
+    let (status, cart) = send(
+        &app,
+        guest_bulk(
+            &guest_token,
+            json!([
+                { "op": "add", "product_id": pen, "quantity": 3 },
+                { "op": "add", "product_id": book, "quantity": 1 }
+            ]),
+        ),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["subtotal"]["amount_minor"], 4750);
+
+    // Sem token, com token adulterado ou usando o token do carrinho como JWT
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/carts/guest/"),
+        None,
+    )
+    .await;
+    assert_eq!(status, 401);
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::get()
+            .uri("/api/v1/carts/guest/")
+            .insert_header(("X-Cart-Token", format!("{}x", guest_token))),
+        None,
+    )
+    .await;
+    assert_eq!(status, 401);
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/carts/"),
+        Some(&guest_token),
+    )
+    .await;
+    assert_eq!(status, 401);
+
+    // Cadastro com o token: o carrinho do visitante vira o carrinho da conta
+    let email = format!("cart_{}@example.com", Uuid::new_v4());
+    let resp = actix_test::call_service(
+        &app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/auth/register/")
+            .insert_header(("X-Cart-Token", guest_token.clone()))
+            .set_json(json!({
+                "email": email,
+                "first_name": "Test",
+                "last_name": "User",
+                "password": "password123"
+            }))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(resp.status().as_u16(), 201);
+    let removal = resp
+        .response()
+        .cookies()
+        .find(|cookie| cookie.name() == "cart_token")
+        .expect("Cookie do carrinho deveria ser apagado")
+        .into_owned();
+    assert_eq!(removal.value(), "");
+    let buyer: Value = actix_test::read_body_json(resp).await;
+    let buyer_token = token(&buyer);
+
+    let (status, cart) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/carts/"),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["unique_products"], 2);
+    assert_eq!(cart["subtotal"]["amount_minor"], 4750);
+    assert_eq!(cart["user_id"], buyer["user"]["id"]);
+    let merge_event: (Option<Uuid>, serde_json::Value) = sqlx::query_as(
+        "SELECT actor_id, after FROM audit_events WHERE entity_type = 'cart' AND entity_id = $1 AND action = 'merge'",
+    )
+    .bind(Uuid::parse_str(cart["id"].as_str().unwrap()).unwrap())
+    .fetch_one(&db)
+    .await
+    .unwrap();
+    assert_eq!(
+        merge_event.0.map(|id| id.to_string()),
+        buyer["user"]["id"].as_str().map(str::to_string)
+    );
+    assert_eq!(merge_event.1["items"].as_array().unwrap().len(), 2);
+
+    // Carrinho juntado não fica mais ativo para o visitante
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::get()
+            .uri("/api/v1/carts/guest/")
+            .insert_header(("X-Cart-Token", guest_token.clone())),
+        None,
+    )
+    .await;
+    assert_eq!(status, 404);
+
+    // Novo carrinho de visitante, junto no login pelo cookie: 3 + 9 passa do estoque (10)
+    let (_, body) = send(
+        &app,
+        actix_test::TestRequest::post().uri("/api/v1/carts/guest/"),
+        None,
+    )
+    .await;
+    let second_token = body["token"].as_str().unwrap().to_string();
+    let (status, _) = send(
+        &app,
+        guest_bulk(
+            &second_token,
+            json!([{ "op": "add", "product_id": pen, "quantity": 9 }]),
+        ),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let (status, login) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/auth/login/")
+            .cookie(actix_web::cookie::Cookie::new("cart_token", second_token))
+            .set_json(json!({ "email": email, "password": "password123" })),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200, "{}", login);
+
+    let (_, cart) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/carts/"),
+        Some(token(&login)),
+    )
+    .await;
+    let items = cart["items"].as_array().unwrap();
+    let pen_item = items.iter().find(|item| item["product_id"] == pen).unwrap();
+    assert_eq!(pen_item["quantity"], 10);
+    assert_eq!(cart["subtotal"]["amount_minor"], 6500);
+
+    // Junção que falha (aqui, o subtotal estoura) mantém o carrinho de visitante e o cookie
+    sqlx::query("UPDATE cart_items SET quantity = 1, unit_price = $1 WHERE id = $2")
+        .bind(i64::MAX)
+        .bind(Uuid::parse_str(pen_item["id"].as_str().unwrap()).unwrap())
+        .execute(&db)
+        .await
+        .unwrap();
+    let (_, body) = send(
+        &app,
+        actix_test::TestRequest::post().uri("/api/v1/carts/guest/"),
+        None,
+    )
+    .await;
+    let third_token = body["token"].as_str().unwrap().to_string();
+    let (status, _) = send(
+        &app,
+        guest_bulk(
+            &third_token,
+            json!([{ "op": "add", "product_id": book, "quantity": 1 }]),
+        ),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    // Outro endereço, para não esbarrar no limite de autenticação dos demais testes
+    let resp = actix_test::call_service(
+        &app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/auth/login/")
+            .peer_addr("10.0.44.1:1234".parse().unwrap())
+            .cookie(actix_web::cookie::Cookie::new(
+                "cart_token",
+                third_token.clone(),
+            ))
+            .set_json(json!({ "email": email, "password": "password123" }))
+            .to_request(),
+    )
+    .await;
+    assert_eq!(resp.status().as_u16(), 200);
+    assert!(
+        resp.response()
+            .cookies()
+            .all(|cookie| cookie.name() != "cart_token")
+    );
+    let (status, guest_cart) = send(
+        &app,
+        actix_test::TestRequest::get()
+            .uri("/api/v1/carts/guest/")
+            .insert_header(("X-Cart-Token", third_token)),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200, "{}", guest_cart);
+    assert_eq!(guest_cart["status"], "ACTIVE");
+}
+
+#[actix_web::test]
+async fn test_cart_revalidation_warnings_and_modes() {
+    let app_state = create_test_app_state().await;
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller = register(&app).await;
+    let seller_token = token(&seller);
+    let pen = create_product(&app, seller_token, "Caneta", 250).await;
+    let book = create_product(&app, seller_token, "Livro", 4000).await;
+    let mug = create_product(&app, seller_token, "Caneca", 1500).await;
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+
+    let (status, cart) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/bulk/")
+            .set_json(json!({ "operations": [
+                { "op": "add", "product_id": pen, "quantity": 4 },
+                { "op": "add", "product_id": book, "quantity": 1 },
+                { "op": "add", "product_id": mug, "quantity": 1 }
+            ] })),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["warnings"], json!([]));
+
+    // Lojista muda preço e estoque da caneta e desativa a caneca
+    let update = |id: &str, body: Value| {
+        actix_test::TestRequest::put()
+            .uri(&format!("/api/v1/products/{}/", id))
+            .set_json(body)
+    };
+    let (status, body) = send(
+        &app,
+        update(&pen, json!({ "price": 300, "stock_quantity": 3 })),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    let (status, body) = send(
+        &app,
+        update(&mug, json!({ "is_active": false })),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+
+    // A caneca inativa continua no carrinho, com aviso
+    let get = || actix_test::TestRequest::get().uri("/api/v1/carts/");
+    let (status, cart) = send(&app, get(), Some(buyer_token)).await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["unique_products"], 3);
+    let warnings = cart["warnings"].as_array().unwrap();
+    let codes = |product: &str| {
+        warnings
+            .iter()
+            .filter(|w| w["product_id"] == product)
+            .map(|w| w["code"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(codes(&pen), vec!["insufficient_stock", "price_changed"]);
+    assert_eq!(codes(&book), Vec::<String>::new());
+    assert_eq!(codes(&mug), vec!["unavailable"]);
+    let price_changed = warnings
+        .iter()
+        .find(|w| w["code"] == "price_changed")
+        .unwrap();
+    assert_eq!(price_changed["old_price"]["amount_minor"], 250);
+    assert_eq!(price_changed["new_price"]["amount_minor"], 300);
+    // O subtotal só muda quando o cliente decide
+    assert_eq!(cart["subtotal"]["amount_minor"], 6500);
+
+    let revalidate = |mode: &str| {
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/revalidate/")
+            .set_json(json!({ "mode": mode }))
+    };
+
+    // Aceitar preços: atualiza o preço e mantém quantidades e linhas
+    let (status, cart) = send(&app, revalidate("accept_prices"), Some(buyer_token)).await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["unique_products"], 3);
+    assert_eq!(cart["subtotal"]["amount_minor"], 6700);
+    let mut codes: Vec<&str> = cart["warnings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["code"].as_str().unwrap())
+        .collect();
+    codes.sort();
+    assert_eq!(codes, vec!["insufficient_stock", "unavailable"]);
+
+    // Ajustar: quantidade limitada ao estoque e linha indisponível removida
+    let (status, cart) = send(&app, revalidate("adjust"), Some(buyer_token)).await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["warnings"], json!([]));
+    assert_eq!(cart["unique_products"], 2);
+    assert_eq!(cart["item_count"], 4);
+    assert_eq!(cart["subtotal"]["amount_minor"], 4900);
+
+    let (status, _) = send(&app, revalidate("ignore"), Some(buyer_token)).await;
+    assert_eq!(status, 400);
+}
+
+#[actix_web::test]
+async fn test_carts_isolated_per_user_within_tenant() {
+    let app_state = create_test_app_state().await;
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller = register(&app).await;
+    let pen = create_product(&app, token(&seller), "Caneta", 250).await;
+
+    let owner = register(&app).await;
+    let tenant_id = owner["user"]["tenant"]["id"].as_str().unwrap().to_string();
+    let alice = register_member(&app, &tenant_id).await;
+    let bob = register_member(&app, &tenant_id).await;
+    assert_eq!(alice["user"]["tenant"]["id"], tenant_id.as_str());
+
+    let add = |quantity: i32| {
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/add-product/")
+            .set_json(json!({ "product_id": pen, "quantity": quantity }))
+    };
+    let (status, alice_cart) = send(&app, add(2), Some(token(&alice))).await;
+    assert_eq!(status, 200, "{}", alice_cart);
+    let (status, bob_cart) = send(&app, add(1), Some(token(&bob))).await;
+    assert_eq!(status, 200, "{}", bob_cart);
+
+    // Membros do mesmo tenant têm carrinhos distintos
+    assert_ne!(alice_cart["id"], bob_cart["id"]);
+    assert_eq!(alice_cart["user_id"], alice["user"]["id"]);
+    assert_eq!(alice_cart["item_count"], 2);
+    assert_eq!(bob_cart["item_count"], 1);
+
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::post().uri("/api/v1/carts/"),
+        Some(token(&alice)),
+    )
+    .await;
+    assert_eq!(status, 409);
+
+    // Bob não remove nem altera o carrinho da Alice
+    let alice_cart_id = alice_cart["id"].as_str().unwrap();
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::delete().uri(&format!("/api/v1/carts/{}/", alice_cart_id)),
+        Some(token(&bob)),
+    )
+    .await;
+    assert_eq!(status, 404);
+    let alice_item = alice_cart["items"][0]["id"].as_str().unwrap();
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::patch()
+            .uri(&format!("/api/v1/carts/items/{}/", alice_item))
+            .set_json(json!({ "quantity": 5 })),
+        Some(token(&bob)),
+    )
+    .await;
+    assert_eq!(status, 404);
+    let (_, cart) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/carts/"),
+        Some(token(&alice)),
+    )
+    .await;
+    assert_eq!(cart["item_count"], 2);
+
+    // Consulta dos carrinhos da loja: só o dono
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/carts/all/"),
+        Some(token(&bob)),
+    )
+    .await;
+    assert_eq!(status, 403);
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/carts/all/{}/", alice_cart_id)),
+        Some(token(&bob)),
+    )
+    .await;
+    assert_eq!(status, 403);
+
+    let (status, carts) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/carts/all/"),
+        Some(token(&owner)),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", carts);
+    assert_eq!(carts["count"], 2);
+    let (_, carts) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&format!(
+            "/api/v1/carts/all/?user_id={}",
+            bob["user"]["id"].as_str().unwrap()
+        )),
+        Some(token(&owner)),
+    )
+    .await;
+    assert_eq!(carts["count"], 1);
+    assert_eq!(carts["results"][0]["id"], bob_cart["id"]);
+
+    let (status, cart) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/carts/all/{}/", alice_cart_id)),
+        Some(token(&owner)),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["items"][0]["product_id"], pen.as_str());
+
+    // Carrinho de outra loja não aparece
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/carts/all/{}/", alice_cart_id)),
+        Some(token(&seller)),
+    )
+    .await;
+    assert_eq!(status, 404);
+
+    // O próprio dono remove; o carrinho do Bob continua ativo
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::delete().uri(&format!("/api/v1/carts/{}/", alice_cart_id)),
+        Some(token(&alice)),
+    )
+    .await;
+    assert_eq!(status, 204);
+    let (status, cart) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/carts/"),
+        Some(token(&bob)),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["id"], bob_cart["id"]);
+}
@@ -0,0 +1,294 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::{App, test, web};
+use serde_json::{Value, json};
+
+use rust_template::app_core::app_routes::api_v1_scope;
+
+mod test_utils;
+use test_utils::{create_test_app_state, register_token, send};
+
+// ===== TEST HELPERS =====
+
+async fn create_category<S, B>(app: &S, token: &str, payload: Value) -> Value
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, body) = send(
+        app,
+        test::TestRequest::post()
+            .uri("/api/v1/categories/")
+            .set_json(payload),
+        Some(token),
+    )
+    .await;
+    assert_eq!(status, 201, "{}", body);
+    body
+}
+
+fn breadcrumb_slugs(breadcrumbs: &Value) -> Vec<String> {
+    breadcrumbs
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|b| b["slug"].as_str().unwrap().to_string())
+        .collect()
+}
+
+// ===== TESTS =====
+
+#[actix_web::test]
+async fn test_category_tree_and_breadcrumbs() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token = register_token(&app).await;
+
+    let moda = create_category(&app, &token, json!({"name": "Moda"})).await;
+    let feminina = create_category(
+        &app,
+        &token,
+        json!({"name": "Feminina", "parent_id": moda["id"]}),
+    )
+    .await;
+    let vestidos = create_category(
+        &app,
+        &token,
+        json!({"name": "Vestidos", "slug": "vestidos", "parent_id": feminina["id"]}),
+    )
+    .await;
+    assert_eq!(moda["slug"], "moda");
+    assert_eq!(vestidos["depth"], 2);
+
+    // Árvore aninhada
+    let (status, tree) = send(
+        &app,
+        test::TestRequest::get().uri("/api/v1/categories/"),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(tree[0]["slug"], "moda");
+    assert_eq!(tree[0]["children"][0]["slug"], "feminina");
+    assert_eq!(tree[0]["children"][0]["children"][0]["slug"], "vestidos");
+
+    // Breadcrumbs da raiz até a categoria
+    let (status, detail) = send(
+        &app,
+        test::TestRequest::get().uri(&format!(
+            "/api/v1/categories/{}/",
+            vestidos["id"].as_str().unwrap()
+        )),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(
+        breadcrumb_slugs(&detail["breadcrumbs"]),
+        vec!["moda", "feminina", "vestidos"]
+    );
+
+    // Slug único por tenant
+    let (status, body) = send(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/v1/categories/")
+            .set_json(json!({"name": "Outra", "slug": "vestidos"})),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 409);
+    assert_eq!(body["code"], "CONFLICT");
+
+    // Categoria com filhas não pode ser removida
+    let (status, _) = send(
+        &app,
+        test::TestRequest::delete().uri(&format!(
+            "/api/v1/categories/{}/",
+            moda["id"].as_str().unwrap()
+        )),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 409);
+}
+
+#[actix_web::test]
+async fn test_move_category_rewrites_subtree() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token = register_token(&app).await;
+
+    let casa = create_category(&app, &token, json!({"name": "Casa"})).await;
+    let cozinha = create_category(
+        &app,
+        &token,
+        json!({"name": "Cozinha", "parent_id": casa["id"]}),
+    )
+    .await;
+    let panelas = create_category(
+        &app,
+        &token,
+        json!({"name": "Panelas", "parent_id": cozinha["id"]}),
+    )
+    .await;
+    let cozinha_id = cozinha["id"].as_str().unwrap();
+
+    // Mover "Cozinha" para a raiz leva junto a subárvore
+    let (status, moved) = send(
+        &app,
+        test::TestRequest::put()
+            .uri(&format!("/api/v1/categories/{}/", cozinha_id))
+            .set_json(json!({"parent_id": null})),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", moved);
+    assert_eq!(moved["depth"], 0);
+
+    let (_, detail) = send(
+        &app,
+        test::TestRequest::get().uri(&format!(
+            "/api/v1/categories/{}/",
+            panelas["id"].as_str().unwrap()
+        )),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(detail["depth"], 1);
+    assert_eq!(
+        breadcrumb_slugs(&detail["breadcrumbs"]),
+        vec!["cozinha", "panelas"]
+    );
+
+    // Não é possível mover uma categoria para dentro da própria subárvore
+    let (status, _) = send(
+        &app,
+        test::TestRequest::put()
+            .uri(&format!("/api/v1/categories/{}/", cozinha_id))
+            .set_json(json!({"parent_id": panelas["id"]})),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 400);
+}
+
+#[actix_web::test]
+async fn test_product_category_filter_includes_descendants() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token = register_token(&app).await;
+
+    let eletronicos = create_category(&app, &token, json!({"name": "Eletronicos"})).await;
+    let celulares = create_category(
+        &app,
+        &token,
+        json!({"name": "Celulares", "parent_id": eletronicos["id"]}),
+    )
+    .await;
+    let livros = create_category(&app, &token, json!({"name": "Livros"})).await;
+
+    let (status, product) = send(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/v1/products/")
+            .set_json(json!({
+                "name": "Smartphone",
+                "price": 199900,
+                "stock_quantity": 5,
+                "is_active": true
+            })),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 201);
+    let product_id = product["id"].as_str().unwrap();
+
+    let (status, assigned) = send(
+        &app,
+        test::TestRequest::put()
+            .uri(&format!("/api/v1/products/{}/categories/", product_id))
+            .set_json(json!({"category_ids": [celulares["id"]]})),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", assigned);
+    assert_eq!(
+        breadcrumb_slugs(&assigned[0]["breadcrumbs"]),
+        vec!["eletronicos", "celulares"]
+    );
+
+    // Filtro pela categoria pai inclui produtos das descendentes
+    let (_, listed) = send(
+        &app,
+        test::TestRequest::get().uri(&format!(
+            "/api/v1/products/?category_id={}",
+            eletronicos["id"].as_str().unwrap()
+        )),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(listed["count"], 1);
+    assert_eq!(listed["results"][0]["id"], product["id"]);
+
+    let (_, listed) = send(
+        &app,
+        test::TestRequest::get().uri(&format!(
+            "/api/v1/products/?category_id={}",
+            livros["id"].as_str().unwrap()
+        )),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(listed["count"], 0);
+
+    // Detalhe do produto traz as categorias com breadcrumbs
+    let (_, detail) = send(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/v1/products/{}/", product_id)),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(detail["categories"][0]["slug"], "celulares");
+    assert_eq!(
+        detail["categories"][0]["breadcrumbs"][0]["slug"],
+        "eletronicos"
+    );
+}
+
+#[actix_web::test]
+async fn test_categories_are_isolated_by_tenant() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let owner = register_token(&app).await;
+    let other = register_token(&app).await;
+
+    let category = create_category(&app, &owner, json!({"name": "Privada"})).await;
+    let uri = format!("/api/v1/categories/{}/", category["id"].as_str().unwrap());
+
+    let (status, _) = send(&app, test::TestRequest::get().uri(&uri), Some(&other)).await;
+    assert_eq!(status, 404);
+
+    let (status, _) = send(&app, test::TestRequest::delete().uri(&uri), Some(&other)).await;
+    assert_eq!(status, 404);
+
+    // O mesmo slug pode existir em outro tenant
+    create_category(&app, &other, json!({"name": "Privada"})).await;
+}
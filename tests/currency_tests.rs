@@ -0,0 +1,348 @@
+use actix_web::{App, test as actix_test, web};
+use bigdecimal::BigDecimal;
+use serde_json::{Value, json};
+use std::str::FromStr;
+
+use rust_template::app_core::app_routes::api_v1_scope;
+use rust_template::apps::exchange_rate::services::ExchangeRateService;
+use rust_template::utils::currency::{
+    Rounding, convert, from_minor_units, minor_units, normalize_currency, round_to_currency,
+    to_minor_units,
+};
+use rust_template::utils::money::{Money, MoneyError};
+
+mod test_utils;
+use test_utils::{create_product, create_test_app_state, register, send, token};
+
+// ===== TEST HELPERS =====
+
+/// Valores monetários chegam como texto; compara pelo número, não pela escala
+fn amount(value: &Value) -> BigDecimal {
+    BigDecimal::from_str(value.as_str().expect("Valor deveria ser texto")).unwrap()
+}
+
+fn amount_of(value: &str) -> BigDecimal {
+    BigDecimal::from_str(value).unwrap()
+}
+
+// ===== TESTS =====
+
+#[test]
+fn test_currency_rounding_rules() {
+    // Meio para o par só desempata no meio exato; HalfUp sempre sobe
+    for (value, half_even, half_up) in [
+        ("0.125", "0.12", "0.13"),
+        ("0.135", "0.14", "0.14"),
+        ("0.1251", "0.13", "0.13"),
+        ("-0.125", "-0.12", "-0.13"),
+        ("2.5", "2.50", "2.50"),
+    ] {
+        let value = amount_of(value);
+        assert_eq!(
+            round_to_currency(&value, "BRL", Rounding::HalfEven),
+            amount_of(half_even)
+        );
+        assert_eq!(
+            round_to_currency(&value, "BRL", Rounding::HalfUp),
+            amount_of(half_up)
+        );
+    }
+
+    // Casas decimais pela moeda (ISO 4217)
+    assert_eq!(minor_units("JPY"), 0);
+    assert_eq!(minor_units("KWD"), 3);
+    assert_eq!(
+        round_to_currency(&amount_of("152.5"), "JPY", Rounding::HalfEven),
+        amount_of("152")
+    );
+    assert_eq!(
+        to_minor_units(&amount_of("19.99"), "USD", Rounding::HalfEven),
+        Some(1999)
+    );
+    assert_eq!(
+        to_minor_units(&amount_of("1.2345"), "KWD", Rounding::HalfEven),
+        Some(1234)
+    );
+    assert_eq!(from_minor_units(1999, "USD"), amount_of("19.99"));
+    assert_eq!(from_minor_units(500, "JPY"), amount_of("500"));
+
+    // Conversão: 10,05 * 0,5 = 5,025 -> 5,02
+    assert_eq!(
+        convert(&amount_of("10.05"), &amount_of("0.5"), "USD"),
+        amount_of("5.02")
+    );
+
+    assert_eq!(normalize_currency(" usd ").unwrap(), "USD");
+    assert!(normalize_currency("US").is_err());
+    assert!(normalize_currency("U$D").is_err());
+}
+
+#[test]
+fn test_money_checked_arithmetic_and_json() {
+    let price = Money::new(1999, "USD");
+    let line = price.checked_mul(3).unwrap();
+    assert_eq!(line, Money::new(5997, "USD"));
+    assert_eq!(line.checked_sub(&price).unwrap(), Money::new(3998, "USD"));
+    assert_eq!(
+        Money::checked_sum([&price, &line], "USD").unwrap(),
+        Money::new(7996, "USD")
+    );
+    assert_eq!(Money::checked_sum([], "USD").unwrap(), Money::zero("USD"));
+
+    // Estouro e moedas diferentes viram erro, nunca valor truncado
+    assert_eq!(
+        Money::new(i64::MAX, "USD").checked_add(&Money::new(1, "USD")),
+        Err(MoneyError::Overflow)
+    );
+    assert_eq!(price.checked_mul(i64::MAX), Err(MoneyError::Overflow));
+    assert_eq!(
+        price.checked_add(&Money::new(100, "BRL")),
+        Err(MoneyError::CurrencyMismatch(
+            "USD".to_string(),
+            "BRL".to_string()
+        ))
+    );
+
+    // Arredonda para as casas da moeda
+    assert_eq!(
+        Money::from_decimal(&amount_of("152.5"), "JPY", Rounding::HalfEven).unwrap(),
+        Money::new(152, "JPY")
+    );
+    assert_eq!(Money::new(1234, "KWD").to_decimal(), amount_of("1.234"));
+
+    let json = serde_json::to_value(&price).unwrap();
+    assert_eq!(
+        json,
+        json!({"amount_minor": 1999, "currency": "USD", "formatted": "USD 19.99"})
+    );
+    assert_eq!(Money::new(500, "JPY").to_string(), "JPY 500");
+    assert_eq!(serde_json::from_value::<Money>(json).unwrap(), price);
+}
+
+#[actix_web::test]
+async fn test_cart_priced_in_chosen_currency() {
+    let app_state = create_test_app_state().await;
+    // XTS e XXX são códigos ISO reservados para testes
+    ExchangeRateService::import_csv(&app_state, "base_currency,quote_currency,rate\nXXX,XTS,2\n")
+        .await
+        .unwrap();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller = register(&app).await;
+    let seller_token = token(&seller);
+    assert_eq!(seller["user"]["tenant"]["base_currency"], "BRL");
+    let (status, tenant) = send(
+        &app,
+        actix_test::TestRequest::patch()
+            .uri("/api/v1/tenant/")
+            .set_json(json!({ "base_currency": "xts" })),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", tenant);
+    assert_eq!(tenant["base_currency"], "XTS");
+    let id = create_product(&app, seller_token, "Caderno", 1005).await;
+
+    // Carrinho na moeda escolhida; sem par direto, vale o inverso de XXX -> XTS
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+    let (status, cart) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/")
+            .set_json(json!({ "currency": "xxx" })),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["currency"], "XXX");
+
+    let add = |quantity: i32| {
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/add-product/")
+            .set_json(json!({ "product_id": id, "quantity": quantity }))
+    };
+    let (status, cart) = send(&app, add(2), Some(buyer_token)).await;
+    assert_eq!(status, 200, "{}", cart);
+    // 10,05 * 0,5 = 5,025 -> 5,02 (meio para o par)
+    assert_eq!(cart["items"][0]["unit_price"]["amount_minor"], 502);
+    // Totais do carrinho ficam na unidade mínima da moeda
+    assert_eq!(cart["subtotal"]["amount_minor"], 1004);
+    assert_eq!(cart["subtotal"]["formatted"], "XXX 10.04");
+
+    // Preço fixo na moeda dispensa a conversão
+    let prices_uri = format!("/api/v1/products/{}/currency-prices/", id);
+    let (status, prices) = send(
+        &app,
+        actix_test::TestRequest::put()
+            .uri(&prices_uri)
+            .set_json(json!([{ "currency": "XXX", "price": 450 }])),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", prices);
+    assert_eq!(amount(&prices[0]["price"]), amount_of("4.50"));
+
+    let (_, cart) = send(&app, add(1), Some(buyer_token)).await;
+    assert_eq!(cart["items"][0]["unit_price"]["amount_minor"], 450);
+    assert_eq!(cart["subtotal"]["amount_minor"], 1350);
+
+    // A moeda base não entra na tabela por moeda
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::put()
+            .uri(&prices_uri)
+            .set_json(json!([{ "currency": "XTS", "price": 900 }])),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 400);
+
+    // Sem cotação nem preço fixo, o produto não entra no carrinho
+    let other = register(&app).await;
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/")
+            .set_json(json!({ "currency": "XBA" })),
+        Some(token(&other)),
+    )
+    .await;
+    assert_eq!(status, 200);
+    let (status, body) = send(&app, add(1), Some(token(&other))).await;
+    assert_eq!(status, 400, "{}", body);
+
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/")
+            .set_json(json!({ "currency": "US" })),
+        Some(token(&other)),
+    )
+    .await;
+    assert_eq!(status, 400);
+}
+
+#[actix_web::test]
+async fn test_base_currency_is_locked_once_prices_exist() {
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let patch = |currency: &str| {
+        actix_test::TestRequest::patch()
+            .uri("/api/v1/tenant/")
+            .set_json(json!({ "base_currency": currency }))
+    };
+
+    let seller = register(&app).await;
+    let seller_token = token(&seller);
+    let (status, _) = send(&app, patch("KWD"), Some(seller_token)).await;
+    assert_eq!(status, 200);
+
+    // Três casas decimais sobrevivem à gravação
+    let id = create_product(&app, seller_token, "Lanterna", 1234).await;
+    let (_, product) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/products/{}/", id)),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(product["price"]["amount_minor"], 1234);
+    assert_eq!(product["price"]["formatted"], "KWD 1.234");
+
+    let (status, body) = send(&app, patch("BRL"), Some(seller_token)).await;
+    assert_eq!(status, 409, "{}", body);
+    // Repetir a moeda atual não é troca
+    let (status, _) = send(&app, patch("KWD"), Some(seller_token)).await;
+    assert_eq!(status, 200);
+
+    // Carrinho aberto com o produto segura a moeda mesmo depois da remoção
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/")
+            .set_json(json!({ "currency": "KWD" })),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200);
+    let (status, cart) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/add-product/")
+            .set_json(json!({ "product_id": id, "quantity": 1 })),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["items"][0]["unit_price"]["amount_minor"], 1234);
+
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::delete().uri(&format!("/api/v1/products/{}/", id)),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 204);
+    let (status, _) = send(&app, patch("BRL"), Some(seller_token)).await;
+    assert_eq!(status, 409);
+
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::post().uri("/api/v1/carts/clear/"),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200);
+    let (status, tenant) = send(&app, patch("BRL"), Some(seller_token)).await;
+    assert_eq!(status, 200, "{}", tenant);
+    assert_eq!(tenant["base_currency"], "BRL");
+}
+
+#[actix_web::test]
+async fn test_exchange_rates_listing_and_import_access() {
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let user = register(&app).await;
+
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/exchange-rates/import/")
+            .set_payload("base_currency,quote_currency,rate\nXTS,XAU,3\n"),
+        Some(token(&user)),
+    )
+    .await;
+    assert_eq!(status, 403);
+
+    let (status, rates) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/exchange-rates/"),
+        Some(token(&user)),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", rates);
+    assert!(rates.as_array().is_some());
+
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/exchange-rates/"),
+        None,
+    )
+    .await;
+    assert_eq!(status, 401);
+}
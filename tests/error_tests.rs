@@ -0,0 +1,132 @@
+use actix_web::{App, ResponseError, test as actix_test, web};
+use validator::Validate;
+
+use rust_template::app_core::{
+    app_error::{AppError, ErrorResponse},
+    app_routes::api_v1_scope,
+};
+use rust_template::apps::user::models::UserRequest;
+
+mod test_utils;
+use test_utils::{init, lazy_app_state};
+
+// ===== APP ERROR =====
+
+#[test]
+fn test_error_codes_are_stable() {
+    let cases = [
+        (AppError::Conflict(None), "CONFLICT", 409),
+        (AppError::not_found("x"), "NOT_FOUND", 404),
+        (AppError::unauthorized("x"), "UNAUTHORIZED", 401),
+        (AppError::forbidden("x"), "FORBIDDEN", 403),
+        (AppError::bad_request("x"), "BAD_REQUEST", 400),
+        (AppError::too_many_requests("x"), "TOO_MANY_REQUESTS", 429),
+        (AppError::payload_too_large("x"), "PAYLOAD_TOO_LARGE", 413),
+        (AppError::internal("x"), "INTERNAL_ERROR", 500),
+        (AppError::database_error("x"), "DATABASE_ERROR", 500),
+    ];
+
+    for (error, code, status) in cases {
+        assert_eq!(error.code(), code);
+        assert_eq!(error.status_code().as_u16(), status);
+    }
+}
+
+#[test]
+fn test_validation_errors_have_field_details() {
+    let request = UserRequest {
+        email: "email-invalido".to_string(),
+        first_name: "Test".to_string(),
+        last_name: "User".to_string(),
+        password: "123".to_string(),
+        profile: None,
+        tenant_id: None,
+    };
+
+    let error = AppError::from(request.validate().unwrap_err());
+    let body = error.to_error_response();
+
+    assert_eq!(body.code, "VALIDATION_ERROR");
+    assert_eq!(error.status_code().as_u16(), 400);
+
+    let details = body.details.expect("Erros de validação devem ter detalhes");
+    assert!(details.get("email").is_some());
+    assert!(details.get("password").is_some());
+    assert!(details["email"][0]["message"].is_string());
+    // O valor informado não deve voltar na resposta
+    assert!(!details.to_string().contains("\"123\""));
+}
+
+// ===== ENVELOPE NAS RESPOSTAS HTTP =====
+
+#[actix_web::test]
+async fn test_invalid_json_uses_error_envelope() {
+    init();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(lazy_app_state()))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let req = actix_test::TestRequest::post()
+        .uri("/api/v1/auth/login/")
+        .insert_header(("Content-Type", "application/json"))
+        .set_payload("{ invalido")
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+
+    let body: ErrorResponse = actix_test::read_body_json(resp).await;
+    assert_eq!(body.code, "INVALID_REQUEST");
+    assert!(body.details.unwrap().get("reason").is_some());
+}
+
+#[actix_web::test]
+async fn test_missing_token_uses_error_envelope() {
+    init();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(lazy_app_state()))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/products/")
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 401);
+
+    let body: ErrorResponse = actix_test::read_body_json(resp).await;
+    assert_eq!(body.code, "UNAUTHORIZED");
+    assert_eq!(body.message, "Token não fornecido");
+}
+
+#[actix_web::test]
+async fn test_invalid_path_uses_error_envelope() {
+    init();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(lazy_app_state()))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let token = rust_template::utils::jwt::generate_jwt(
+        &uuid::Uuid::new_v4().to_string(),
+        "user",
+        uuid::Uuid::new_v4(),
+    )
+    .unwrap();
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/products/nao-e-uuid/")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+
+    let body: ErrorResponse = actix_test::read_body_json(resp).await;
+    assert_eq!(body.code, "INVALID_REQUEST");
+}
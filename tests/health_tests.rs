@@ -0,0 +1,120 @@
+use actix_web::{App, test as actix_test, web};
+use std::collections::BTreeMap;
+
+use rust_template::app_core::{
+    app_routes::api_v1_scope,
+    app_state::AppState,
+    health::{CheckStatus, DependencyCheck, ReadinessResponse, ReadinessStatus},
+};
+
+mod test_utils;
+use test_utils::{get_test_db_pool, init, lazy_app_state, run_migrations};
+
+// ===== TEST SETUP =====
+
+fn check(status: CheckStatus, required: bool) -> DependencyCheck {
+    DependencyCheck {
+        status,
+        required,
+        duration_ms: 1,
+        details: None,
+        error: None,
+    }
+}
+
+// ===== TESTS =====
+
+#[test]
+fn test_readiness_status_aggregation() {
+    let mut checks = BTreeMap::new();
+    checks.insert("postgres".to_string(), check(CheckStatus::Up, true));
+    checks.insert("elasticsearch".to_string(), check(CheckStatus::Up, false));
+    assert_eq!(
+        ReadinessResponse::from_checks(checks.clone()).status,
+        ReadinessStatus::Ok
+    );
+
+    checks.insert("elasticsearch".to_string(), check(CheckStatus::Down, false));
+    assert_eq!(
+        ReadinessResponse::from_checks(checks.clone()).status,
+        ReadinessStatus::Degraded
+    );
+
+    checks.insert("postgres".to_string(), check(CheckStatus::Down, true));
+    assert_eq!(
+        ReadinessResponse::from_checks(checks).status,
+        ReadinessStatus::Unavailable
+    );
+}
+
+#[actix_web::test]
+async fn test_liveness_does_not_touch_dependencies() {
+    init();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(lazy_app_state()))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/health/live/")
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn test_readiness_returns_503_when_postgres_is_down() {
+    init();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(lazy_app_state()))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/health/ready/")
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 503);
+
+    let body: ReadinessResponse = actix_test::read_body_json(resp).await;
+    assert_eq!(body.status, ReadinessStatus::Unavailable);
+    let postgres = &body.checks["postgres"];
+    assert_eq!(postgres.status, CheckStatus::Down);
+    assert!(postgres.required);
+    assert!(postgres.error.is_some());
+}
+
+#[actix_web::test]
+async fn test_readiness_reports_each_dependency() {
+    init();
+    let pool = get_test_db_pool().await;
+    run_migrations(&pool).await;
+
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState { db: pool }))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/health/ready/")
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let body: ReadinessResponse = actix_test::read_body_json(resp).await;
+    assert_eq!(body.checks["postgres"].status, CheckStatus::Up);
+    assert_eq!(body.checks["migrations"].status, CheckStatus::Up);
+    assert_eq!(
+        body.checks["migrations"].details.as_ref().unwrap()["pending"],
+        0
+    );
+    // Elasticsearch é opcional por padrão: fora do ar apenas degrada
+    assert!(!body.checks["elasticsearch"].required);
+    assert_ne!(body.status, ReadinessStatus::Unavailable);
+}
@@ -0,0 +1,334 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::{App, test, web};
+use serde_json::json;
+use std::io::Cursor;
+use uuid::Uuid;
+
+use rust_template::app_core::{app_routes::api_v1_scope, app_state::AppState};
+
+mod test_utils;
+use test_utils::{create_product, register_token, send};
+
+// ===== TEST SETUP =====
+
+const BOUNDARY: &str = "----media-test-boundary";
+const BASE_URL: &str = "http://localhost:8080";
+
+fn init() {
+    test_utils::init_with(|| {
+        let root = std::env::temp_dir().join(format!("media_tests_{}", Uuid::new_v4()));
+        // SAFETY: executado uma única vez, antes de qualquer leitura das configurações
+        unsafe {
+            std::env::set_var("MEDIA_STORAGE_BACKEND", "local");
+            std::env::set_var("MEDIA_LOCAL_ROOT", root);
+            std::env::set_var(
+                "MEDIA_PUBLIC_BASE_URL",
+                format!("{}/api/v1/media/files", BASE_URL),
+            );
+            std::env::set_var("MEDIA_MAX_UPLOAD_BYTES", "200000");
+        }
+    });
+}
+
+async fn create_test_app_state() -> AppState {
+    init();
+    test_utils::create_test_app_state().await
+}
+
+// ===== TEST HELPERS =====
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let image = image::RgbImage::from_pixel(width, height, image::Rgb([20, 120, 200]));
+    let mut data = Vec::new();
+    image::DynamicImage::ImageRgb8(image)
+        .write_to(&mut Cursor::new(&mut data), image::ImageFormat::Png)
+        .unwrap();
+    data
+}
+
+/// Monta o corpo multipart: arquivo no campo "file" mais campos de texto
+fn multipart(file: &[u8], content_type: &str, fields: &[(&str, &str)]) -> Vec<u8> {
+    let mut body = Vec::new();
+    for (name, value) in fields {
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                BOUNDARY, name, value
+            )
+            .as_bytes(),
+        );
+    }
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"foto.png\"\r\nContent-Type: {}\r\n\r\n",
+            BOUNDARY, content_type
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(file);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+    body
+}
+
+fn upload_request(uri: &str, body: Vec<u8>) -> test::TestRequest {
+    test::TestRequest::post()
+        .uri(uri)
+        .insert_header((
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", BOUNDARY),
+        ))
+        .set_payload(body)
+}
+
+/// Baixa um arquivo pela URL devolvida na API (rota pública); devolve status e content-type
+async fn fetch<S, B>(app: &S, url: &str) -> (u16, Option<String>)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let uri = url.strip_prefix(BASE_URL).expect("URL fora da base");
+    let resp = test::call_service(app, test::TestRequest::get().uri(uri).to_request()).await;
+    let content_type = resp
+        .headers()
+        .get("Content-Type")
+        .map(|v| v.to_str().unwrap().to_string());
+    (resp.status().as_u16(), content_type)
+}
+
+// ===== TESTS =====
+
+#[actix_web::test]
+async fn test_product_gallery_upload_reorder_and_delete() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token = register_token(&app).await;
+    let product_id = create_product(&app, &token, "Produto", 4990).await;
+    let images_uri = format!("/api/v1/products/{}/images/", product_id);
+
+    let (status, first) = send(
+        &app,
+        upload_request(
+            &images_uri,
+            multipart(&png(640, 480), "image/png", &[("alt_text", "Frente")]),
+        ),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 201, "{}", first);
+    assert_eq!(first["position"], 0);
+    assert_eq!(first["alt_text"], "Frente");
+    assert_eq!(first["media"]["width"], 640);
+    assert_eq!(first["media"]["visibility"], "public");
+
+    let (status, second) = send(
+        &app,
+        upload_request(
+            &images_uri,
+            multipart(&png(100, 100), "image/png", &[("alt_text", "Verso")]),
+        ),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 201, "{}", second);
+    assert_eq!(second["position"], 1);
+
+    // Miniatura servida pela rota pública
+    let thumbnail_url = first["media"]["thumbnail_url"].as_str().unwrap();
+    assert_eq!(
+        fetch(&app, thumbnail_url).await,
+        (200, Some("image/png".to_string()))
+    );
+
+    // Reordenar
+    let (status, gallery) = send(
+        &app,
+        test::TestRequest::put()
+            .uri(&format!("{}order/", images_uri))
+            .set_json(json!({ "image_ids": [second["id"], first["id"]] })),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", gallery);
+    assert_eq!(gallery[0]["id"], second["id"]);
+    assert_eq!(gallery[1]["id"], first["id"]);
+    assert_eq!(gallery[1]["position"], 1);
+
+    // A ordem precisa conter todas as imagens
+    let (status, _) = send(
+        &app,
+        test::TestRequest::put()
+            .uri(&format!("{}order/", images_uri))
+            .set_json(json!({ "image_ids": [first["id"]] })),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 400);
+
+    // Texto alternativo
+    let (status, updated) = send(
+        &app,
+        test::TestRequest::patch()
+            .uri(&format!("{}{}/", images_uri, first["id"].as_str().unwrap()))
+            .set_json(json!({ "alt_text": "Frente do produto" })),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", updated);
+    assert_eq!(updated[1]["alt_text"], "Frente do produto");
+
+    // Remover compacta as posições
+    let (status, _) = send(
+        &app,
+        test::TestRequest::delete().uri(&format!(
+            "{}{}/",
+            images_uri,
+            second["id"].as_str().unwrap()
+        )),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 204);
+
+    let (status, gallery) = send(
+        &app,
+        test::TestRequest::get().uri(&images_uri),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(gallery.as_array().unwrap().len(), 1);
+    assert_eq!(gallery[0]["id"], first["id"]);
+    assert_eq!(gallery[0]["position"], 0);
+
+    // Conteúdo que não bate com o tipo declarado
+    let (status, body) = send(
+        &app,
+        upload_request(&images_uri, multipart(&png(10, 10), "image/jpeg", &[])),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 400, "{}", body);
+
+    // Outro tenant não mexe na galeria
+    let other_token = register_token(&app).await;
+    let (status, _) = send(
+        &app,
+        upload_request(&images_uri, multipart(&png(10, 10), "image/png", &[])),
+        Some(&other_token),
+    )
+    .await;
+    assert_eq!(status, 404);
+}
+
+#[actix_web::test]
+async fn test_private_media_requires_valid_signature() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token = register_token(&app).await;
+
+    let (status, media) = send(
+        &app,
+        upload_request(
+            "/api/v1/media/",
+            multipart(&png(50, 50), "image/png", &[("visibility", "private")]),
+        ),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 201, "{}", media);
+    assert_eq!(media["visibility"], "private");
+    assert!(media["url_expires_at"].is_string());
+
+    let url = media["url"].as_str().unwrap();
+    assert!(url.contains("signature="));
+    assert_eq!(fetch(&app, url).await.0, 200);
+
+    // Assinatura adulterada ou ausente
+    let tampered = format!("{}00", url);
+    assert_eq!(fetch(&app, &tampered).await.0, 403);
+    let unsigned = url.split('?').next().unwrap();
+    assert_eq!(fetch(&app, unsigned).await.0, 403);
+
+    // Outro tenant não enxerga a mídia
+    let other_token = register_token(&app).await;
+    let media_uri = format!("/api/v1/media/{}/", media["id"].as_str().unwrap());
+    let (status, _) = send(
+        &app,
+        test::TestRequest::get().uri(&media_uri),
+        Some(&other_token),
+    )
+    .await;
+    assert_eq!(status, 404);
+
+    let (status, _) = send(
+        &app,
+        test::TestRequest::delete().uri(&media_uri),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 204);
+    let (status, _) = send(&app, test::TestRequest::get().uri(&media_uri), Some(&token)).await;
+    assert_eq!(status, 404);
+}
+
+#[actix_web::test]
+async fn test_avatar_upload_updates_profile() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token = register_token(&app).await;
+
+    let (status, body) = send(
+        &app,
+        upload_request(
+            "/api/v1/users/avatar/",
+            multipart(&png(400, 400), "image/png", &[]),
+        ),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+
+    let avatar = body["user"]["profile"]["avatar"].as_str().unwrap();
+    assert!(avatar.starts_with(&format!("{}/api/v1/media/files/public/", BASE_URL)));
+    assert_eq!(
+        fetch(&app, avatar).await,
+        (200, Some("image/png".to_string()))
+    );
+}
+
+#[actix_web::test]
+async fn test_upload_too_large_is_rejected() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token = register_token(&app).await;
+
+    let (status, body) = send(
+        &app,
+        upload_request(
+            "/api/v1/media/",
+            multipart(&vec![0u8; 250_000], "image/png", &[]),
+        ),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 413, "{}", body);
+    assert_eq!(body["code"], "PAYLOAD_TOO_LARGE");
+}
@@ -0,0 +1,71 @@
+use actix_web::{App, test as actix_test, web};
+
+use rust_template::app_core::{
+    app_routes::api_v1_scope,
+    telemetry::{metrics, metrics_handler, record_cart_operation},
+};
+
+mod test_utils;
+use test_utils::{init, lazy_app_state};
+
+// ===== TESTS =====
+
+#[actix_web::test]
+async fn test_metrics_endpoint_exposes_route_latency() {
+    init();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(lazy_app_state()))
+            .route("/metrics", web::get().to(metrics_handler))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/health/")
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let req = actix_test::TestRequest::get().uri("/metrics").to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let body = String::from_utf8(actix_test::read_body(resp).await.to_vec()).unwrap();
+    assert!(body.contains("http_request_duration_seconds_bucket"));
+    assert!(body.contains("route=\"/api/v1/health/\""));
+    assert!(body.contains("db_pool_connections"));
+}
+
+#[actix_web::test]
+async fn test_metrics_use_route_pattern_instead_of_path() {
+    init();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(lazy_app_state()))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    // Falha no AuthMiddleware, mas a rota já foi resolvida
+    let req = actix_test::TestRequest::get()
+        .uri(&format!("/api/v1/products/{}/", uuid::Uuid::new_v4()))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 401);
+
+    let body = metrics().render();
+    assert!(body.contains("route=\"/api/v1/products/{id}/\""));
+}
+
+#[test]
+fn test_business_counters_are_registered() {
+    init();
+    record_cart_operation("add_item");
+
+    let value = metrics()
+        .cart_operations_total
+        .with_label_values(&["add_item"])
+        .get();
+    assert!(value >= 1);
+}
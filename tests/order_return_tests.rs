@@ -0,0 +1,543 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::{App, test as actix_test, web};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use rust_template::app_core::app_error::AppError;
+use rust_template::app_core::app_routes::api_v1_scope;
+use rust_template::app_core::app_state::AppState;
+use rust_template::apps::order_return::models::ReturnDecisionRequest;
+use rust_template::apps::order_return::services::ReturnService;
+use rust_template::apps::payment::models::{RefundReceipt, RefundRequest};
+use rust_template::apps::payment::provider::{PaymentProvider, RefundError};
+
+mod test_utils;
+use test_utils::{create_product, create_test_app_state, register, send, token};
+
+// ===== TEST HELPERS =====
+
+/// Estoque atual do produto, visto pelo dono
+async fn stock<S, B>(app: &S, token: &str, id: &str) -> i64
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, body) = send(
+        app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/products/{}/", id)),
+        Some(token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    body["stock_quantity"].as_i64().unwrap()
+}
+
+async fn add_to_cart<S, B>(app: &S, token: &str, product_id: &str, quantity: i32)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, body) = send(
+        app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/add-product/")
+            .set_json(json!({ "product_id": product_id, "quantity": quantity })),
+        Some(token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+}
+
+fn checkout() -> actix_test::TestRequest {
+    actix_test::TestRequest::post().uri("/api/v1/orders/checkout/")
+}
+
+/// Leva o pedido até entregue, pelo dono da loja
+async fn deliver<S, B>(app: &S, token: &str, order_id: &str)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let sale = format!("/api/v1/orders/sales/{}", order_id);
+    for (uri, payload) in [
+        (format!("{}/status/", sale), json!({ "status": "paid" })),
+        (
+            format!("{}/ship/", sale),
+            json!({ "tracking_code": "BR123" }),
+        ),
+        (
+            format!("{}/status/", sale),
+            json!({ "status": "delivered" }),
+        ),
+    ] {
+        let (status, body) = send(
+            app,
+            actix_test::TestRequest::post().uri(&uri).set_json(payload),
+            Some(token),
+        )
+        .await;
+        assert_eq!(status, 200, "{}", body);
+    }
+}
+
+/// Compra os produtos e devolve o pedido (uma única loja) com os itens
+async fn buy<S, B>(app: &S, token: &str, products: &[(&str, i32)]) -> Value
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    for (product_id, quantity) in products {
+        add_to_cart(app, token, product_id, *quantity).await;
+    }
+    let (status, orders) = send(app, checkout(), Some(token)).await;
+    assert_eq!(status, 201, "{}", orders);
+    let order_id = orders[0]["id"].as_str().unwrap().to_string();
+
+    let (status, order) = send(
+        app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/orders/{}/", order_id)),
+        Some(token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", order);
+    order
+}
+
+fn item_id(order: &Value, product_id: &str) -> String {
+    order["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["product_id"] == product_id)
+        .unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+fn request_return(order_id: &str, item_id: &str, quantity: i32) -> actix_test::TestRequest {
+    actix_test::TestRequest::post()
+        .uri(&format!("/api/v1/orders/{}/returns/", order_id))
+        .set_json(json!({
+            "order_item_id": item_id,
+            "quantity": quantity,
+            "reason": "damaged",
+            "description": "Chegou quebrado"
+        }))
+}
+
+fn decide(return_id: &str, action: &str) -> actix_test::TestRequest {
+    actix_test::TestRequest::post().uri(&format!(
+        "/api/v1/orders/sales/returns/{}/{}/",
+        return_id, action
+    ))
+}
+
+/// Gateway que reembolsa mas perde a resposta na primeira chamada de cada reembolso
+/// (timeout depois do pagamento); nas seguintes devolve o recibo do mesmo reembolso
+#[derive(Default)]
+struct TimeoutAfterRefund {
+    refunded: Mutex<Vec<Uuid>>,
+}
+
+#[async_trait]
+impl PaymentProvider for TimeoutAfterRefund {
+    fn name(&self) -> &'static str {
+        "gateway"
+    }
+
+    async fn refund(&self, request: &RefundRequest) -> Result<RefundReceipt, RefundError> {
+        let mut refunded = self.refunded.lock().unwrap();
+        if !refunded.contains(&request.refund_id) {
+            refunded.push(request.refund_id);
+            return Err(RefundError::Unavailable(AppError::InternalError(Some(
+                "Tempo esgotado".into(),
+            ))));
+        }
+        Ok(RefundReceipt {
+            provider_reference: format!("gateway-{}", request.refund_id),
+        })
+    }
+}
+
+// ===== TESTS =====
+
+#[actix_web::test]
+async fn test_partial_return_refund_restock_and_rejection() {
+    let app_state = create_test_app_state().await;
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller = register(&app).await;
+    let seller_token = token(&seller);
+    let mug = create_product(&app, seller_token, "Caneca", 1000).await;
+    let plate = create_product(&app, seller_token, "Prato", 500).await;
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+
+    let order = buy(&app, buyer_token, &[(&mug, 2), (&plate, 1)]).await;
+    let order_id = order["id"].as_str().unwrap();
+    let mug_item = item_id(&order, &mug);
+    let plate_item = item_id(&order, &plate);
+
+    // Só pedido entregue aceita devolução
+    let (status, _) = send(
+        &app,
+        request_return(order_id, &mug_item, 1),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 409);
+    deliver(&app, seller_token, order_id).await;
+
+    let (status, _) = send(
+        &app,
+        request_return(order_id, &mug_item, 3),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 400);
+    let (status, created) = send(
+        &app,
+        request_return(order_id, &mug_item, 1),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 201, "{}", created);
+    assert_eq!(created["status"], "requested");
+    assert_eq!(created["reason"], "damaged");
+    let return_id = created["id"].as_str().unwrap();
+
+    // A quantidade já pedida não pode ser devolvida de novo
+    let (status, body) = send(
+        &app,
+        request_return(order_id, &mug_item, 2),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 400, "{}", body);
+
+    let (status, queue) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/orders/sales/returns/?status=requested"),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", queue);
+    assert_eq!(queue["count"], 1);
+
+    // Comprador não decide devoluções; a loja dele não vendeu o pedido
+    let (status, _) = send(&app, decide(return_id, "approve"), Some(buyer_token)).await;
+    assert_eq!(status, 404);
+    let (status, _) = send(&app, decide(return_id, "receive"), Some(seller_token)).await;
+    assert_eq!(status, 409);
+
+    let (status, approved) = send(
+        &app,
+        decide(return_id, "approve").set_json(json!({ "note": "Troca autorizada" })),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", approved);
+    assert_eq!(approved["return"]["status"], "approved");
+    assert_eq!(approved["refund"]["provider"], "manual");
+    assert_eq!(approved["refund"]["amount"]["amount_minor"], 1000);
+    assert_eq!(approved["credit_note"]["amount"]["amount_minor"], 1000);
+    assert_eq!(approved["order"]["refunded_total"]["amount_minor"], 1000);
+    assert_eq!(approved["order"]["status"], "delivered");
+    assert_eq!(stock(&app, seller_token, &mug).await, 9);
+
+    let (status, _) = send(&app, decide(return_id, "approve"), Some(seller_token)).await;
+    assert_eq!(status, 409);
+    let (status, received) = send(&app, decide(return_id, "receive"), Some(seller_token)).await;
+    assert_eq!(status, 200, "{}", received);
+    assert_eq!(received["status"], "received");
+
+    // Recusa exige motivo e não mexe em estoque nem no total
+    let (status, created) = send(
+        &app,
+        request_return(order_id, &plate_item, 1),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 201, "{}", created);
+    let plate_return = created["id"].as_str().unwrap();
+    let (status, _) = send(
+        &app,
+        decide(plate_return, "reject").set_json(json!({})),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 400);
+    let (status, rejected) = send(
+        &app,
+        decide(plate_return, "reject").set_json(json!({ "note": "Fora do prazo" })),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", rejected);
+    assert_eq!(rejected["status"], "rejected");
+    assert_eq!(stock(&app, seller_token, &plate).await, 9);
+
+    let (status, returns) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/orders/{}/returns/", order_id)),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", returns);
+    assert_eq!(returns.as_array().unwrap().len(), 2);
+
+    let (status, notes) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/orders/{}/credit-notes/", order_id)),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", notes);
+    assert_eq!(notes.as_array().unwrap().len(), 1);
+    assert_eq!(notes[0]["return_request_id"], return_id);
+}
+
+#[actix_web::test]
+async fn test_full_return_refunds_order() {
+    let app_state = create_test_app_state().await;
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller = register(&app).await;
+    let seller_token = token(&seller);
+    let lamp = create_product(&app, seller_token, "Luminária", 700).await;
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+
+    let order = buy(&app, buyer_token, &[(&lamp, 2)]).await;
+    let order_id = order["id"].as_str().unwrap();
+    let lamp_item = item_id(&order, &lamp);
+    deliver(&app, seller_token, order_id).await;
+
+    let (status, created) = send(
+        &app,
+        request_return(order_id, &lamp_item, 2),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 201, "{}", created);
+    let (status, approved) = send(
+        &app,
+        decide(created["id"].as_str().unwrap(), "approve"),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", approved);
+    assert_eq!(approved["order"]["status"], "refunded");
+    assert_eq!(approved["order"]["refunded_total"]["amount_minor"], 1400);
+    assert_eq!(stock(&app, seller_token, &lamp).await, 10);
+
+    let (status, sale) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/orders/sales/{}/", order_id)),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", sale);
+    let last = sale["history"].as_array().unwrap().last().unwrap().clone();
+    assert_eq!(last["from_status"], "delivered");
+    assert_eq!(last["to_status"], "refunded");
+
+    let (status, notes) = send(
+        &app,
+        actix_test::TestRequest::get()
+            .uri(&format!("/api/v1/orders/sales/{}/credit-notes/", order_id)),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", notes);
+    assert_eq!(notes[0]["amount"]["amount_minor"], 1400);
+
+    // Pedido reembolsado não aceita nova devolução
+    let (status, _) = send(
+        &app,
+        request_return(order_id, &lamp_item, 1),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 409);
+}
+
+#[actix_web::test]
+async fn test_approval_resumes_unrecorded_refund() {
+    let app_state = create_test_app_state().await;
+    let db = app_state.db.clone();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller = register(&app).await;
+    let seller_token = token(&seller);
+    let cup = create_product(&app, seller_token, "Xícara", 500).await;
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+
+    let order = buy(&app, buyer_token, &[(&cup, 3)]).await;
+    let order_id = order["id"].as_str().unwrap();
+    let cup_item = item_id(&order, &cup);
+    deliver(&app, seller_token, order_id).await;
+    assert_eq!(stock(&app, seller_token, &cup).await, 7);
+
+    // Simula uma aprovação interrompida depois de gravar o reembolso: sem recibo (antes do
+    // provedor) ou com recibo (registro da nota de crédito falhou)
+    let mut interrupted = Vec::new();
+    for provider_reference in [None, Some("manual-confirmado")] {
+        let (status, created) = send(
+            &app,
+            request_return(order_id, &cup_item, 1),
+            Some(buyer_token),
+        )
+        .await;
+        assert_eq!(status, 201, "{}", created);
+        let return_id = Uuid::parse_str(created["id"].as_str().unwrap()).unwrap();
+        let refund_id = Uuid::new_v4();
+
+        sqlx::query(
+            "UPDATE return_requests SET status = 'APPROVED', dt_decided = now() WHERE id = $1",
+        )
+        .bind(return_id)
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO payment_refunds \
+             (id, order_id, return_request_id, provider, provider_reference, amount, currency) \
+             VALUES ($1, $2, $3, 'manual', $4, 500, 'BRL')",
+        )
+        .bind(refund_id)
+        .bind(Uuid::parse_str(order_id).unwrap())
+        .bind(return_id)
+        .bind(provider_reference)
+        .execute(&db)
+        .await
+        .unwrap();
+        interrupted.push((return_id.to_string(), refund_id, provider_reference));
+    }
+
+    // Aprovar de novo conclui o registro com o mesmo reembolso, sem reembolsar duas vezes.
+    // Antes disso o recebimento é recusado, para o reembolso não ficar pendente para sempre.
+    for (i, (return_id, refund_id, provider_reference)) in interrupted.iter().enumerate() {
+        let (status, body) = send(&app, decide(return_id, "receive"), Some(seller_token)).await;
+        assert_eq!(status, 409, "{}", body);
+
+        let (status, approved) = send(&app, decide(return_id, "approve"), Some(seller_token)).await;
+        assert_eq!(status, 200, "{}", approved);
+        assert_eq!(approved["refund"]["id"], refund_id.to_string());
+        let expected_reference = provider_reference
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("manual-{}", refund_id));
+        assert_eq!(approved["refund"]["provider_reference"], expected_reference);
+        assert_eq!(approved["credit_note"]["amount"]["amount_minor"], 500);
+        assert_eq!(
+            approved["order"]["refunded_total"]["amount_minor"],
+            500 * (i as i64 + 1)
+        );
+
+        let (status, _) = send(&app, decide(return_id, "approve"), Some(seller_token)).await;
+        assert_eq!(status, 409);
+    }
+    assert_eq!(stock(&app, seller_token, &cup).await, 9);
+
+    let (status, notes) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/orders/{}/credit-notes/", order_id)),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", notes);
+    assert_eq!(notes.as_array().unwrap().len(), 2);
+}
+
+#[actix_web::test]
+async fn test_provider_timeout_keeps_pending_refund() {
+    let app_state = create_test_app_state().await;
+    let service_state = AppState {
+        db: app_state.db.clone(),
+    };
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller = register(&app).await;
+    let seller_token = token(&seller);
+    let tenant_id = Uuid::parse_str(seller["user"]["tenant"]["id"].as_str().unwrap()).unwrap();
+    let seller_id = Uuid::parse_str(seller["user"]["id"].as_str().unwrap()).unwrap();
+    let bowl = create_product(&app, seller_token, "Tigela", 800).await;
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+
+    let order = buy(&app, buyer_token, &[(&bowl, 1)]).await;
+    let order_id = order["id"].as_str().unwrap();
+    deliver(&app, seller_token, order_id).await;
+    let (status, created) = send(
+        &app,
+        request_return(order_id, &item_id(&order, &bowl), 1),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 201, "{}", created);
+    let return_id = created["id"].as_str().unwrap();
+
+    let provider = TimeoutAfterRefund::default();
+    let approve = || {
+        ReturnService::approve_return_with(
+            &service_state,
+            &provider,
+            tenant_id,
+            seller_id,
+            "user",
+            Uuid::parse_str(return_id).unwrap(),
+            ReturnDecisionRequest::default(),
+        )
+    };
+
+    // Sem resposta do provedor a devolução segue aprovada, com o reembolso pendente
+    assert!(approve().await.is_err());
+    let (_, returns) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/orders/{}/returns/", order_id)),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(returns[0]["status"], "approved");
+    let (status, _) = send(&app, decide(return_id, "receive"), Some(seller_token)).await;
+    assert_eq!(status, 409);
+
+    // A nova tentativa repete o mesmo reembolso no provedor
+    let approved = approve().await.unwrap();
+    let refunded = provider.refunded.lock().unwrap().clone();
+    assert_eq!(refunded, vec![approved.refund.id]);
+    assert_eq!(
+        approved.refund.provider_reference,
+        format!("gateway-{}", approved.refund.id)
+    );
+    assert_eq!(approved.order.refunded_total.amount_minor, 800);
+
+    let (status, received) = send(&app, decide(return_id, "receive"), Some(seller_token)).await;
+    assert_eq!(status, 200, "{}", received);
+}
@@ -0,0 +1,304 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::{App, test as actix_test, web};
+use serde_json::{Value, json};
+
+use rust_template::app_core::app_routes::api_v1_scope;
+
+mod test_utils;
+use test_utils::{create_product, create_test_app_state, register, register_member, send, token};
+
+// ===== TEST HELPERS =====
+
+/// Estoque atual do produto, visto pelo dono
+async fn stock<S, B>(app: &S, token: &str, id: &str) -> i64
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, body) = send(
+        app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/products/{}/", id)),
+        Some(token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    body["stock_quantity"].as_i64().unwrap()
+}
+
+async fn add_to_cart<S, B>(app: &S, token: &str, product_id: &str, quantity: i32)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, body) = send(
+        app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/add-product/")
+            .set_json(json!({ "product_id": product_id, "quantity": quantity })),
+        Some(token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+}
+
+fn checkout() -> actix_test::TestRequest {
+    actix_test::TestRequest::post().uri("/api/v1/orders/checkout/")
+}
+
+// ===== TESTS =====
+
+#[actix_web::test]
+async fn test_checkout_splits_orders_by_seller_and_reserves_stock() {
+    let app_state = create_test_app_state().await;
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller_a = register(&app).await;
+    let seller_b = register(&app).await;
+    let pen = create_product(&app, token(&seller_a), "Caneta", 250).await;
+    let book = create_product(&app, token(&seller_b), "Livro", 4000).await;
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+
+    // Sem carrinho não há o que finalizar
+    let (status, _) = send(&app, checkout(), Some(buyer_token)).await;
+    assert_eq!(status, 404);
+
+    add_to_cart(&app, buyer_token, &pen, 3).await;
+    add_to_cart(&app, buyer_token, &book, 1).await;
+
+    let (status, orders) = send(&app, checkout(), Some(buyer_token)).await;
+    assert_eq!(status, 201, "{}", orders);
+    let orders = orders.as_array().unwrap();
+    assert_eq!(orders.len(), 2);
+    assert!(orders.iter().all(|o| o["status"] == "pending_payment"));
+    let pen_order = orders
+        .iter()
+        .find(|o| o["seller_tenant_id"] == seller_a["user"]["tenant"]["id"])
+        .unwrap();
+    assert_eq!(pen_order["grand_total"]["amount_minor"], 750);
+
+    // Trilha do carrinho: criação na primeira adição, uma alteração por adição e o checkout
+    let (status, events) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/audit-events/?entity_type=cart"),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", events);
+    let events = events["results"].as_array().unwrap();
+    let mut actions: Vec<&str> = events
+        .iter()
+        .map(|event| event["action"].as_str().unwrap())
+        .collect();
+    actions.sort();
+    assert_eq!(actions, ["checkout", "create", "update", "update"]);
+    let checkout_event = events.iter().find(|e| e["action"] == "checkout").unwrap();
+    assert_eq!(checkout_event["actor_id"], buyer["user"]["id"]);
+    assert_eq!(checkout_event["after"]["status"], "CONVERTED_TO_ORDER");
+    assert_eq!(
+        checkout_event["after"]["order_ids"]
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(checkout_event["changes"]["status"]["before"], "ACTIVE");
+    let first_add = events
+        .iter()
+        .find(|e| e["action"] == "update" && e["before"]["items"] == json!([]))
+        .unwrap();
+    assert_eq!(first_add["after"]["items"][0]["quantity"], 3);
+
+    // Estoque reservado e carrinho fechado
+    assert_eq!(stock(&app, token(&seller_a), &pen).await, 7);
+    assert_eq!(stock(&app, token(&seller_b), &book).await, 9);
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/carts/"),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 404);
+
+    // Comprador vê os dois pedidos; cada loja só a própria venda
+    let (status, list) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/orders/"),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", list);
+    assert_eq!(list["count"], 2);
+    let (_, sales) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/orders/sales/"),
+        Some(token(&seller_a)),
+    )
+    .await;
+    assert_eq!(sales["count"], 1);
+    assert_eq!(sales["results"][0]["id"], pen_order["id"]);
+
+    let pen_order_id = pen_order["id"].as_str().unwrap();
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/orders/sales/{}/", pen_order_id)),
+        Some(token(&seller_b)),
+    )
+    .await;
+    assert_eq!(status, 404);
+
+    let (status, order) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/orders/{}/", pen_order_id)),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", order);
+    assert_eq!(order["items"][0]["quantity"], 3);
+    assert_eq!(order["items"][0]["product_name"], "Caneta");
+    assert_eq!(order["history"][0]["to_status"], "pending_payment");
+    assert_eq!(order["history"][0]["actor_role"], "buyer");
+
+    // Carrinho com avisos é recusado
+    add_to_cart(&app, buyer_token, &pen, 1).await;
+    let (status, body) = send(
+        &app,
+        actix_test::TestRequest::put()
+            .uri(&format!("/api/v1/products/{}/", pen))
+            .set_json(json!({ "price": 300 })),
+        Some(token(&seller_a)),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    let (status, _) = send(&app, checkout(), Some(buyer_token)).await;
+    assert_eq!(status, 409);
+}
+
+#[actix_web::test]
+async fn test_order_lifecycle_shipment_and_cancellation() {
+    let app_state = create_test_app_state().await;
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller = register(&app).await;
+    let seller_token = token(&seller);
+    let pen = create_product(&app, seller_token, "Caneta", 250).await;
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+
+    add_to_cart(&app, buyer_token, &pen, 2).await;
+    let (_, orders) = send(&app, checkout(), Some(buyer_token)).await;
+    let shipped_id = orders[0]["id"].as_str().unwrap().to_string();
+
+    let sale_uri = |action: &str| format!("/api/v1/orders/sales/{}/{}", shipped_id, action);
+    let set_status = |status: &str| {
+        actix_test::TestRequest::post()
+            .uri(&sale_uri("status/"))
+            .set_json(json!({ "status": status }))
+    };
+
+    // Membro que não é dono da loja não gerencia vendas
+    let tenant_id = seller["user"]["tenant"]["id"].as_str().unwrap();
+    let member = register_member(&app, tenant_id).await;
+    let (status, _) = send(&app, set_status("paid"), Some(token(&member))).await;
+    assert_eq!(status, 403);
+
+    // Transições fora do ciclo de vida são recusadas
+    let (status, _) = send(&app, set_status("delivered"), Some(seller_token)).await;
+    assert_eq!(status, 409);
+    let (status, _) = send(&app, set_status("shipped"), Some(seller_token)).await;
+    assert_eq!(status, 400);
+
+    let (status, order) = send(&app, set_status("paid"), Some(seller_token)).await;
+    assert_eq!(status, 200, "{}", order);
+    assert_eq!(order["status"], "paid");
+
+    let ship = |body: Value| {
+        actix_test::TestRequest::post()
+            .uri(&sale_uri("ship/"))
+            .set_json(body)
+    };
+    let (status, _) = send(
+        &app,
+        ship(json!({ "tracking_code": " " })),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 400);
+    let (status, order) = send(
+        &app,
+        ship(json!({ "tracking_code": "BR123456789", "carrier": "Correios" })),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", order);
+    assert_eq!(order["status"], "shipped");
+    assert_eq!(order["tracking_code"], "BR123456789");
+    assert_eq!(order["carrier"], "Correios");
+
+    // Depois do envio o comprador não cancela mais
+    let cancel =
+        |id: &str| actix_test::TestRequest::post().uri(&format!("/api/v1/orders/{}/cancel/", id));
+    let (status, _) = send(&app, cancel(&shipped_id), Some(buyer_token)).await;
+    assert_eq!(status, 409);
+
+    let (status, order) = send(&app, set_status("delivered"), Some(seller_token)).await;
+    assert_eq!(status, 200, "{}", order);
+    let history = order["history"].as_array().unwrap();
+    let steps: Vec<&str> = history
+        .iter()
+        .map(|h| h["to_status"].as_str().unwrap())
+        .collect();
+    assert_eq!(steps, ["pending_payment", "paid", "shipped", "delivered"]);
+    assert_eq!(history[1]["actor_role"], "seller");
+    assert_eq!(history[1]["actor_id"], seller["user"]["id"]);
+    assert_eq!(history[2]["from_status"], "paid");
+    assert_eq!(stock(&app, seller_token, &pen).await, 8);
+
+    // Cancelamento antes do envio devolve o estoque
+    add_to_cart(&app, buyer_token, &pen, 5).await;
+    let (_, orders) = send(&app, checkout(), Some(buyer_token)).await;
+    let cancelled_id = orders[0]["id"].as_str().unwrap().to_string();
+    assert_eq!(stock(&app, seller_token, &pen).await, 3);
+
+    let (status, order) = send(
+        &app,
+        cancel(&cancelled_id).set_json(json!({ "reason": "Comprei errado" })),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", order);
+    assert_eq!(order["status"], "cancelled");
+    assert_eq!(order["history"][1]["note"], "Comprei errado");
+    assert_eq!(stock(&app, seller_token, &pen).await, 8);
+
+    // Cancelar de novo não devolve estoque duas vezes
+    let (status, _) = send(&app, cancel(&cancelled_id), Some(buyer_token)).await;
+    assert_eq!(status, 409);
+    assert_eq!(stock(&app, seller_token, &pen).await, 8);
+
+    // Outro usuário não enxerga nem cancela o pedido
+    let other = register(&app).await;
+    let (status, _) = send(&app, cancel(&shipped_id), Some(token(&other))).await;
+    assert_eq!(status, 404);
+
+    let (_, list) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/orders/?status=cancelled"),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(list["count"], 1);
+    assert_eq!(list["results"][0]["id"], cancelled_id.as_str());
+}
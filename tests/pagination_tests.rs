@@ -0,0 +1,225 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::{App, test as actix_test, web};
+use chrono::{Duration, TimeZone, Utc};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use rust_template::app_core::app_routes::api_v1_scope;
+use rust_template::utils::pagination::{Cursor, CursorDirection, CursorPage, PageRequest};
+
+mod test_utils;
+use test_utils::{create_test_app_state, register_token};
+
+// ===== CURSOR =====
+
+#[test]
+fn test_cursor_roundtrip_and_invalid_values() {
+    let cursor = Cursor {
+        dt_created: Utc.with_ymd_and_hms(2025, 8, 26, 12, 30, 0).unwrap()
+            + Duration::microseconds(123_456),
+        id: Uuid::new_v4(),
+        direction: CursorDirection::Prev,
+    };
+
+    let encoded = cursor.encode();
+    assert!(!encoded.contains(&cursor.id.to_string()));
+    assert_eq!(Cursor::decode(&encoded).unwrap(), cursor);
+
+    for invalid in ["nao-e-cursor", "", "eHx4fHg"] {
+        let error = Cursor::decode(invalid).unwrap_err();
+        assert_eq!(error.code(), "BAD_REQUEST");
+    }
+}
+
+#[test]
+fn test_page_request_modes() {
+    assert_eq!(
+        PageRequest::new(500, Some(-3), None, Some(true)).unwrap(),
+        PageRequest::Offset {
+            limit: 100,
+            offset: 0
+        }
+    );
+    assert_eq!(
+        PageRequest::new(20, None, Some(""), None).unwrap(),
+        PageRequest::Cursor {
+            limit: 20,
+            cursor: None,
+            include_count: false
+        }
+    );
+    assert!(PageRequest::new(20, None, Some("???"), None).is_err());
+}
+
+#[test]
+fn test_cursor_page_links() {
+    let base = Utc.with_ymd_and_hms(2025, 8, 26, 0, 0, 0).unwrap();
+    // Itens em ordem decrescente, como volta de push_keyset
+    let rows: Vec<(chrono::DateTime<Utc>, Uuid)> = (0..4)
+        .map(|i| (base - Duration::minutes(i), Uuid::new_v4()))
+        .collect();
+
+    // Primeira página: sobrou item, então há próxima; não há anterior
+    let first = CursorPage::from_rows(rows.clone(), 3, None, |r| *r, None);
+    assert_eq!(first.results, rows[..3]);
+    assert!(first.prev.is_none());
+    let next = Cursor::decode(first.next.as_deref().unwrap()).unwrap();
+    assert_eq!((next.dt_created, next.id), rows[2]);
+    assert_eq!(next.direction, CursorDirection::Next);
+
+    // Última página: sem sobra, sem próxima; com anterior apontando para o primeiro item
+    let last = CursorPage::from_rows(rows[3..].to_vec(), 3, Some(&next), |r| *r, Some(4));
+    assert!(last.next.is_none());
+    let prev = Cursor::decode(last.prev.as_deref().unwrap()).unwrap();
+    assert_eq!((prev.dt_created, prev.id), rows[3]);
+    assert_eq!(prev.direction, CursorDirection::Prev);
+    assert_eq!(last.count, Some(4));
+
+    // Voltando, as linhas chegam em ordem crescente e são invertidas
+    let ascending: Vec<_> = rows[..3].iter().rev().cloned().collect();
+    let back = CursorPage::from_rows(ascending, 3, Some(&prev), |r| *r, None);
+    assert_eq!(back.results, rows[..3]);
+    assert!(back.prev.is_none());
+    assert!(back.next.is_some());
+}
+
+// ===== LISTAGENS =====
+
+async fn get<S, B>(app: &S, uri: &str, token: &str) -> (u16, Value)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let req = actix_test::TestRequest::get()
+        .uri(uri)
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = actix_test::call_service(app, req).await;
+    let status = resp.status().as_u16();
+    let body = actix_test::read_body(resp).await;
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn names(body: &Value) -> Vec<String> {
+    body["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[actix_web::test]
+async fn test_products_cursor_pagination() {
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token = register_token(&app).await;
+
+    for i in 1..=5 {
+        let req = actix_test::TestRequest::post()
+            .uri("/api/v1/products/")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .set_json(json!({
+                "name": format!("Produto {}", i),
+                "price": 1000,
+                "stock_quantity": 1,
+                "is_active": true
+            }))
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_eq!(resp.status().as_u16(), 201);
+    }
+
+    // Primeira página: sem total, a menos que pedido
+    let (status, page1) = get(&app, "/api/v1/products/?cursor=&limit=2", &token).await;
+    assert_eq!(status, 200, "{}", page1);
+    assert_eq!(names(&page1), vec!["Produto 5", "Produto 4"]);
+    assert!(page1.get("count").is_none());
+    assert!(page1.get("offset").is_none());
+    assert!(page1["prev"].is_null());
+
+    let next = page1["next"].as_str().unwrap();
+    let (_, page2) = get(
+        &app,
+        &format!(
+            "/api/v1/products/?cursor={}&limit=2&include_count=true",
+            next
+        ),
+        &token,
+    )
+    .await;
+    assert_eq!(names(&page2), vec!["Produto 3", "Produto 2"]);
+    assert_eq!(page2["count"], 5);
+
+    let next = page2["next"].as_str().unwrap();
+    let (_, page3) = get(
+        &app,
+        &format!("/api/v1/products/?cursor={}&limit=2", next),
+        &token,
+    )
+    .await;
+    assert_eq!(names(&page3), vec!["Produto 1"]);
+    assert!(page3["next"].is_null());
+
+    // Voltando a partir da última página
+    let prev = page3["prev"].as_str().unwrap();
+    let (_, back) = get(
+        &app,
+        &format!("/api/v1/products/?cursor={}&limit=2", prev),
+        &token,
+    )
+    .await;
+    assert_eq!(names(&back), vec!["Produto 3", "Produto 2"]);
+
+    // Offset continua sendo o padrão
+    let (_, offset) = get(&app, "/api/v1/products/?limit=2&offset=2", &token).await;
+    assert_eq!(offset["count"], 5);
+    assert_eq!(names(&offset), vec!["Produto 3", "Produto 2"]);
+
+    let (status, body) = get(&app, "/api/v1/products/?cursor=invalido", &token).await;
+    assert_eq!(status, 400);
+    assert_eq!(body["code"], "BAD_REQUEST");
+}
+
+#[actix_web::test]
+async fn test_carts_and_audit_accept_cursor() {
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token = register_token(&app).await;
+
+    let req = actix_test::TestRequest::post()
+        .uri("/api/v1/carts/")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let (status, carts) = get(&app, "/api/v1/carts/all/?cursor=", &token).await;
+    assert_eq!(status, 200, "{}", carts);
+    assert_eq!(carts["results"].as_array().unwrap().len(), 1);
+    assert!(carts["next"].is_null());
+
+    let (_, carts) = get(&app, "/api/v1/carts/all/", &token).await;
+    assert_eq!(carts["count"], 1);
+
+    // O dono do tenant vê a própria trilha (cadastro do usuário)
+    let (status, events) = get(
+        &app,
+        "/api/v1/audit-events/?cursor=&limit=1&include_count=true",
+        &token,
+    )
+    .await;
+    assert_eq!(status, 200, "{}", events);
+    assert_eq!(events["results"].as_array().unwrap().len(), 1);
+    assert!(events["count"].as_i64().unwrap() >= 1);
+}
@@ -0,0 +1,346 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::{App, test as actix_test, web};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use rust_template::app_core::app_routes::api_v1_scope;
+
+mod test_utils;
+use test_utils::{create_product, create_test_app_state, register, send, token};
+
+// ===== TEST HELPERS =====
+
+async fn add_to_cart<S, B>(app: &S, token: &str, product_id: &str, quantity: i32)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, body) = send(
+        app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/add-product/")
+            .set_json(json!({ "product_id": product_id, "quantity": quantity })),
+        Some(token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+}
+
+fn checkout() -> actix_test::TestRequest {
+    actix_test::TestRequest::post().uri("/api/v1/orders/checkout/")
+}
+
+/// Leva o pedido até entregue, pelo dono da loja
+async fn deliver<S, B>(app: &S, token: &str, order_id: &str)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let sale = format!("/api/v1/orders/sales/{}", order_id);
+    for (uri, payload) in [
+        (format!("{}/status/", sale), json!({ "status": "paid" })),
+        (
+            format!("{}/ship/", sale),
+            json!({ "tracking_code": "BR123" }),
+        ),
+        (
+            format!("{}/status/", sale),
+            json!({ "status": "delivered" }),
+        ),
+    ] {
+        let (status, body) = send(
+            app,
+            actix_test::TestRequest::post().uri(&uri).set_json(payload),
+            Some(token),
+        )
+        .await;
+        assert_eq!(status, 200, "{}", body);
+    }
+}
+
+/// Compra os produtos e devolve o pedido (uma única loja) com os itens
+async fn buy<S, B>(app: &S, token: &str, products: &[(&str, i32)]) -> Value
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    for (product_id, quantity) in products {
+        add_to_cart(app, token, product_id, *quantity).await;
+    }
+    let (status, orders) = send(app, checkout(), Some(token)).await;
+    assert_eq!(status, 201, "{}", orders);
+    let order_id = orders[0]["id"].as_str().unwrap().to_string();
+
+    let (status, order) = send(
+        app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/orders/{}/", order_id)),
+        Some(token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", order);
+    order
+}
+
+fn item_id(order: &Value, product_id: &str) -> String {
+    order["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["product_id"] == product_id)
+        .unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Token de super_admin para um usuário cadastrado
+fn super_admin_token(user: &Value) -> String {
+    let tenant_id = user["user"]["tenant"]["id"].as_str().unwrap();
+    rust_template::utils::jwt::generate_jwt(
+        user["user"]["id"].as_str().unwrap(),
+        "super_admin",
+        Uuid::parse_str(tenant_id).unwrap(),
+    )
+    .unwrap()
+}
+
+/// Saldo da loja na moeda (available, in_payout, paid_out)
+async fn balance<S, B>(app: &S, token: &str, currency: &str) -> (i64, i64, i64)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, body) = send(
+        app,
+        actix_test::TestRequest::get().uri("/api/v1/payouts/balance/"),
+        Some(token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    body.as_array()
+        .unwrap()
+        .iter()
+        .find(|b| b["currency"] == currency)
+        .map(|b| {
+            (
+                b["available"]["amount_minor"].as_i64().unwrap(),
+                b["in_payout"]["amount_minor"].as_i64().unwrap(),
+                b["paid_out"]["amount_minor"].as_i64().unwrap(),
+            )
+        })
+        .unwrap_or((0, 0, 0))
+}
+
+async fn statement<S, B>(app: &S, token: &str) -> Vec<Value>
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, body) = send(
+        app,
+        actix_test::TestRequest::get().uri("/api/v1/payouts/statement/"),
+        Some(token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    body["results"].as_array().unwrap().clone()
+}
+
+// ===== TESTS =====
+
+#[actix_web::test]
+async fn test_commission_refund_and_payout_reconciliation() {
+    let app_state = create_test_app_state().await;
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let admin = register(&app).await;
+    let admin_token = super_admin_token(&admin);
+    let seller = register(&app).await;
+    let seller_token = token(&seller);
+    let seller_tenant = seller["user"]["tenant"]["id"].as_str().unwrap();
+
+    // Só super_admin define comissões
+    let set_rate = || {
+        actix_test::TestRequest::put()
+            .uri("/api/v1/commissions/")
+            .set_json(json!({ "tenant_id": seller_tenant, "rate_bps": 1500 }))
+    };
+    let (status, _) = send(&app, set_rate(), Some(seller_token)).await;
+    assert_eq!(status, 403);
+    let (status, rate) = send(&app, set_rate(), Some(&admin_token)).await;
+    assert_eq!(status, 200, "{}", rate);
+    assert_eq!(rate["rate_bps"], 1500);
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::put()
+            .uri("/api/v1/commissions/")
+            .set_json(json!({ "tenant_id": seller_tenant, "rate_bps": 10001 })),
+        Some(&admin_token),
+    )
+    .await;
+    assert_eq!(status, 400);
+
+    let mug = create_product(&app, seller_token, "Caneca", 1000).await;
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+    let order = buy(&app, buyer_token, &[(&mug, 2)]).await;
+    let order_id = order["id"].as_str().unwrap();
+    let currency = order["currency"].as_str().unwrap().to_string();
+
+    // Pedido ainda não pago não entra no saldo
+    assert_eq!(balance(&app, seller_token, &currency).await, (0, 0, 0));
+    deliver(&app, seller_token, order_id).await;
+
+    // 2000 de venda, 15% de comissão
+    assert_eq!(balance(&app, seller_token, &currency).await, (1700, 0, 0));
+    let lines = statement(&app, seller_token).await;
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0]["kind"], "sale");
+    assert_eq!(lines[0]["order_id"], order_id);
+    assert_eq!(lines[0]["amount"]["amount_minor"], 1700);
+
+    // Devolução de uma unidade: a loja devolve 1000 menos a comissão dessa unidade
+    let (status, created) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri(&format!("/api/v1/orders/{}/returns/", order_id))
+            .set_json(json!({
+                "order_item_id": item_id(&order, &mug),
+                "quantity": 1,
+                "reason": "damaged"
+            })),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 201, "{}", created);
+    let (status, approved) = send(
+        &app,
+        actix_test::TestRequest::post().uri(&format!(
+            "/api/v1/orders/sales/returns/{}/approve/",
+            created["id"].as_str().unwrap()
+        )),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", approved);
+    assert_eq!(balance(&app, seller_token, &currency).await, (850, 0, 0));
+    let lines = statement(&app, seller_token).await;
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["kind"], "refund");
+    assert_eq!(lines[0]["amount"]["amount_minor"], -850);
+
+    // Lote de repasse: apenas super_admin
+    let create_batch = || {
+        actix_test::TestRequest::post()
+            .uri("/api/v1/payouts/batches/")
+            .set_json(json!({ "currency": currency }))
+    };
+    let (status, _) = send(&app, create_batch(), Some(seller_token)).await;
+    assert_eq!(status, 403);
+    let (status, batch) = send(&app, create_batch(), Some(&admin_token)).await;
+    assert_eq!(status, 201, "{}", batch);
+    assert_eq!(batch["status"], "pending");
+    assert_eq!(batch["ledger_total"], batch["total"]);
+    let payout = batch["payouts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["seller_tenant_id"] == seller_tenant)
+        .expect("Loja com saldo deveria entrar no lote")
+        .clone();
+    assert_eq!(payout["amount"]["amount_minor"], 850);
+    assert_eq!(balance(&app, seller_token, &currency).await, (0, 850, 0));
+
+    let batch_id = batch["id"].as_str().unwrap();
+    let total = batch["total"]["amount_minor"].as_i64().unwrap();
+    let reconcile = |paid_total: i64| {
+        actix_test::TestRequest::post()
+            .uri(&format!("/api/v1/payouts/batches/{}/reconcile/", batch_id))
+            .set_json(json!({ "reference": "TED-0001", "paid_total": paid_total }))
+    };
+
+    // Valor do extrato diferente do lote não concilia
+    let (status, body) = send(&app, reconcile(total - 1), Some(&admin_token)).await;
+    assert_eq!(status, 409, "{}", body);
+    assert_eq!(balance(&app, seller_token, &currency).await, (0, 850, 0));
+
+    let (status, reconciled) = send(&app, reconcile(total), Some(&admin_token)).await;
+    assert_eq!(status, 200, "{}", reconciled);
+    assert_eq!(reconciled["status"], "reconciled");
+    assert_eq!(reconciled["reference"], "TED-0001");
+    let (status, _) = send(&app, reconcile(total), Some(&admin_token)).await;
+    assert_eq!(status, 409);
+
+    assert_eq!(balance(&app, seller_token, &currency).await, (0, 0, 850));
+    let (status, payouts) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/payouts/"),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", payouts);
+    assert_eq!(payouts.as_array().unwrap().len(), 1);
+    assert_eq!(payouts[0]["batch_status"], "reconciled");
+
+    let (status, fetched) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&format!("/api/v1/payouts/batches/{}/", batch_id)),
+        Some(&admin_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", fetched);
+    assert_eq!(fetched["status"], "reconciled");
+}
+
+#[actix_web::test]
+async fn test_cancelled_paid_order_reverses_sale() {
+    let app_state = create_test_app_state().await;
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller = register(&app).await;
+    let seller_token = token(&seller);
+    let lamp = create_product(&app, seller_token, "Luminária", 700).await;
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+    let order = buy(&app, buyer_token, &[(&lamp, 1)]).await;
+    let order_id = order["id"].as_str().unwrap();
+
+    let (status, body) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri(&format!("/api/v1/orders/sales/{}/status/", order_id))
+            .set_json(json!({ "status": "paid" })),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    let (status, body) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri(&format!("/api/v1/orders/{}/cancel/", order_id))
+            .set_json(json!({})),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+
+    // Sem regra para a loja vale a comissão padrão (10%); o estorno anula a venda
+    let lines = statement(&app, seller_token).await;
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["kind"], "sale_reversal");
+    assert_eq!(lines[0]["amount"]["amount_minor"], -630);
+    assert_eq!(lines[1]["kind"], "sale");
+    assert_eq!(lines[1]["amount"]["amount_minor"], 630);
+}
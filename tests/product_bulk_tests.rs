@@ -0,0 +1,323 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::{App, test, web};
+use serde_json::{Value, json};
+use std::time::Duration;
+use uuid::Uuid;
+
+use rust_template::app_core::{
+    app_routes::api_v1_scope, app_state::AppState, workers::WorkerSupervisor,
+};
+use rust_template::apps::audit::models::AuditContext;
+use rust_template::apps::product_bulk::models::TransferFormat;
+use rust_template::apps::product_bulk::services::{ProductBulkService, spawn_interrupted_imports};
+
+mod test_utils;
+use test_utils::{register, register_token, send, token};
+
+// ===== TEST SETUP =====
+
+fn init() {
+    test_utils::init_with(|| {
+        // SAFETY: executado uma única vez, antes de qualquer leitura das configurações
+        unsafe {
+            std::env::set_var("PRODUCT_IMPORT_SYNC_ROWS", "5");
+            std::env::set_var("PRODUCT_IMPORT_MAX_BYTES", "65536");
+        }
+    });
+}
+
+async fn create_test_app_state() -> AppState {
+    init();
+    test_utils::create_test_app_state().await
+}
+
+// ===== TEST HELPERS =====
+
+/// Envia a requisição autenticada; devolve status e corpo bruto
+async fn send_raw<S, B>(app: &S, req: test::TestRequest, token: &str) -> (u16, String)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let req = req
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(app, req).await;
+    let status = resp.status().as_u16();
+    let body = test::read_body(resp).await;
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+async fn import<S, B>(app: &S, token: &str, format: &str, data: String) -> (u16, Value)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    send(
+        app,
+        test::TestRequest::post()
+            .uri(&format!("/api/v1/products/import/?format={}", format))
+            .set_payload(data),
+        Some(token),
+    )
+    .await
+}
+
+async fn export<S, B>(app: &S, token: &str, format: &str) -> String
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, body) = send_raw(
+        app,
+        test::TestRequest::get().uri(&format!("/api/v1/products/export/?format={}", format)),
+        token,
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    body
+}
+
+// ===== TESTS =====
+
+#[actix_web::test]
+async fn test_csv_import_upserts_and_reports_row_errors() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token = register_token(&app).await;
+
+    let csv = "sku,name,price,stock_quantity,attributes\n\
+               CAM-1,Camiseta Azul,49.90,10,\"{\"\"cor\"\": \"\"azul\"\"}\"\n\
+               CAM-2,Camiseta Verde,39,5,\n\
+               CAM-3,,10,1,\n\
+               CAM-4,Camiseta Preta,abc,1,\n";
+    let (status, job) = import(&app, &token, "csv", csv.to_string()).await;
+
+    assert_eq!(status, 200, "{}", job);
+    assert_eq!(job["status"], "completed");
+    assert_eq!(job["total_rows"], 4);
+    assert_eq!(job["created_count"], 2);
+    assert_eq!(job["failed_count"], 2);
+    assert_eq!(job["row_errors"][0]["row"], 4);
+    assert_eq!(job["row_errors"][0]["key"], "CAM-3");
+    assert_eq!(job["row_errors"][0]["errors"][0]["field"], "name");
+    assert_eq!(job["row_errors"][1]["errors"][0]["field"], "price");
+
+    // Mesmo SKU atualiza; slug sem SKU também localiza o produto
+    let csv = "sku,slug,price,stock_quantity\n\
+               CAM-1,,59.90,\n\
+               ,camiseta-verde,,0\n";
+    let (status, job) = import(&app, &token, "csv", csv.to_string()).await;
+    assert_eq!(status, 200, "{}", job);
+    assert_eq!(job["updated_count"], 2, "{}", job);
+    assert_eq!(job["created_count"], 0);
+
+    let exported = export(&app, &token, "csv").await;
+    let lines: Vec<&str> = exported.lines().collect();
+    assert_eq!(
+        lines[0],
+        "sku,slug,name,short_description,description,price,stock_quantity,is_active,attributes"
+    );
+    assert_eq!(lines.len(), 3);
+    assert!(lines[1].starts_with("CAM-1,camiseta-azul,Camiseta Azul,,,59.90,10,true,"));
+    assert!(lines[2].starts_with("CAM-2,camiseta-verde,Camiseta Verde,,,39.00,0,true,"));
+
+    // SKU tem prioridade sobre o slug; a linha inválida não impede as demais
+    let jsonl = format!(
+        "{}\n{}\n{}\n",
+        json!({"sku": "CAM-2", "price": -1}),
+        json!({"sku": "CAM-1", "slug": "camiseta-verde", "stock_quantity": 3}),
+        json!({"sku": "CAM-5", "name": "Boné", "price": 25.5, "is_active": false})
+    );
+    let (status, job) = import(&app, &token, "jsonl", jsonl).await;
+    assert_eq!(status, 200, "{}", job);
+    assert_eq!(job["failed_count"], 1, "{}", job);
+    assert_eq!(job["updated_count"], 1);
+    assert_eq!(job["created_count"], 1);
+    assert_eq!(job["row_errors"][0]["row"], 1);
+    assert_eq!(job["row_errors"][0]["errors"][0]["field"], "price");
+
+    let exported = export(&app, &token, "jsonl").await;
+    let rows: Vec<Value> = exported
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0]["stock_quantity"], 3);
+    assert_eq!(rows[2]["sku"], "CAM-5");
+    assert_eq!(rows[2]["price"], "25.50");
+    assert_eq!(rows[2]["is_active"], false);
+}
+
+#[actix_web::test]
+async fn test_large_import_runs_in_background() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token = register_token(&app).await;
+
+    let mut csv = String::from("sku,name,price,stock_quantity\n");
+    for i in 0..20 {
+        csv.push_str(&format!("BG-{i},Produto {i},{i}.50,{i}\n"));
+    }
+    let (status, job) = import(&app, &token, "csv", csv).await;
+    assert_eq!(status, 202, "{}", job);
+    assert_eq!(job["total_rows"], 20);
+
+    let job_uri = format!("/api/v1/products/import/{}/", job["id"].as_str().unwrap());
+    let mut job = Value::Null;
+    for _ in 0..100 {
+        let (status, body) = send(&app, test::TestRequest::get().uri(&job_uri), Some(&token)).await;
+        assert_eq!(status, 200, "{}", body);
+        job = body;
+        if job["status"] == "completed" {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(job["status"], "completed", "{}", job);
+    assert_eq!(job["processed_rows"], 20);
+    assert_eq!(job["created_count"], 20);
+    assert!(job["dt_finished"].is_string());
+
+    // Exportar e reimportar o catálogo só atualiza
+    let exported = export(&app, &token, "csv").await;
+    assert_eq!(exported.lines().count(), 21);
+    let (status, job) = import(&app, &token, "csv", exported).await;
+    assert_eq!(status, 202, "{}", job);
+
+    // O job pertence ao tenant
+    let other_token = register_token(&app).await;
+    let (status, _) = send(
+        &app,
+        test::TestRequest::get().uri(&job_uri),
+        Some(&other_token),
+    )
+    .await;
+    assert_eq!(status, 404);
+}
+
+#[actix_web::test]
+async fn test_invalid_import_files_are_rejected() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token = register_token(&app).await;
+
+    // Sem formato
+    let (status, _) = send(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/v1/products/import/")
+            .set_payload("sku\nA\n"),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 400);
+
+    // Cabeçalho sem colunas de identificação
+    let (status, body) = import(&app, &token, "csv", "price\n10\n".to_string()).await;
+    assert_eq!(status, 400, "{}", body);
+
+    // Vazio
+    let (status, _) = import(&app, &token, "jsonl", "\n\n".to_string()).await;
+    assert_eq!(status, 400);
+
+    // Acima do limite de bytes
+    let big = format!("sku,name\n{}", "A,B\n".repeat(20_000));
+    let (status, body) = import(&app, &token, "csv", big).await;
+    assert_eq!(status, 413, "{}", body);
+    assert_eq!(body["code"], "PAYLOAD_TOO_LARGE");
+}
+
+#[actix_web::test]
+async fn test_import_interrupted_by_shutdown_resumes() {
+    let app_state = create_test_app_state().await;
+    let db = app_state.db.clone();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState { db: db.clone() }))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let user = register(&app).await;
+    let token = token(&user).to_string();
+    let tenant_id = Uuid::parse_str(user["user"]["tenant"]["id"].as_str().unwrap()).unwrap();
+
+    let mut csv = String::from("sku,name,price,stock_quantity\n");
+    for i in 0..1000 {
+        csv.push_str(&format!("RS-{i},Produto {i},{i}.90,1\n"));
+    }
+    let (job, rows) = ProductBulkService::prepare_import(
+        &app_state,
+        tenant_id,
+        None,
+        TransferFormat::Csv,
+        csv.into_bytes(),
+    )
+    .await
+    .unwrap();
+    let job_uri = format!("/api/v1/products/import/{}/", job.id);
+
+    // O prazo cobre a folga de gravação: a importação segue depois do sinal e para antes
+    // de ser abortada, com o progresso guardado
+    let workers = WorkerSupervisor::new();
+    let audit = AuditContext {
+        tenant_id: Some(tenant_id),
+        ..Default::default()
+    };
+    workers.spawn_task("import", move |signal| async move {
+        ProductBulkService::run_import(&app_state, job, rows, &audit, Some(signal))
+            .await
+            .unwrap();
+    });
+    let report = workers.shutdown(Duration::from_millis(1500)).await;
+    assert_eq!(report.finished, vec!["import".to_string()]);
+
+    let (status, job) = send(&app, test::TestRequest::get().uri(&job_uri), Some(&token)).await;
+    assert_eq!(status, 200, "{}", job);
+    assert_eq!(job["status"], "interrupted", "{}", job);
+    let processed = job["processed_rows"].as_i64().unwrap();
+    assert!(processed > 0 && processed < 1000, "{}", job);
+    assert!(job["dt_finished"].is_null());
+
+    // O próximo servidor continua da linha em que parou
+    let workers = WorkerSupervisor::new();
+    spawn_interrupted_imports(&workers, web::Data::new(AppState { db: db.clone() }));
+    let mut job = Value::Null;
+    for _ in 0..300 {
+        let (_, body) = send(&app, test::TestRequest::get().uri(&job_uri), Some(&token)).await;
+        job = body;
+        if job["status"] == "completed" {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    workers.shutdown(Duration::from_secs(5)).await;
+
+    assert_eq!(job["status"], "completed", "{}", job);
+    assert_eq!(job["processed_rows"], 1000);
+    assert_eq!(job["created_count"], 1000);
+    assert_eq!(job["updated_count"], 0);
+    assert_eq!(export(&app, &token, "csv").await.lines().count(), 1001);
+
+    let source: Option<String> =
+        sqlx::query_scalar("SELECT source FROM product_import_jobs WHERE id = $1")
+            .bind(Uuid::parse_str(job["id"].as_str().unwrap()).unwrap())
+            .fetch_one(&db)
+            .await
+            .unwrap();
+    assert!(source.is_none());
+}
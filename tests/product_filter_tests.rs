@@ -0,0 +1,248 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::{App, test, web};
+use serde_json::{Value, json};
+
+use rust_template::app_core::app_routes::api_v1_scope;
+
+mod test_utils;
+use test_utils::{create_product_with, create_test_app_state, register_store, send};
+
+// ===== TEST HELPERS =====
+
+/// Catálogo de exemplo: camisetas e canecas com atributos variados
+async fn seed_catalog<S, B>(app: &S, token: &str) -> Vec<Value>
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let mut products = Vec::new();
+    for (name, price, stock, attributes) in [
+        (
+            "Camiseta Azul",
+            4990,
+            10,
+            json!({"cor": "azul", "tamanho": 42, "peso": 0.2}),
+        ),
+        (
+            "Camiseta Verde",
+            4990,
+            0,
+            json!({"cor": "verde", "tamanho": "42", "peso": 0.25}),
+        ),
+        (
+            "Caneca Azul",
+            2990,
+            3,
+            json!({"cor": "azul", "peso": 0.4, "termica": true}),
+        ),
+        (
+            "Caneca Branca",
+            1990,
+            7,
+            json!({"cor": "branca", "peso": "pesada"}),
+        ),
+    ] {
+        products.push(
+            create_product_with(
+                app,
+                token,
+                json!({
+                    "name": name,
+                    "price": price,
+                    "stock_quantity": stock,
+                    "attributes": attributes,
+                    "is_active": true
+                }),
+            )
+            .await,
+        );
+    }
+    products
+}
+
+fn names(body: &Value) -> Vec<&str> {
+    body["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect()
+}
+
+// ===== TESTS =====
+
+#[actix_web::test]
+async fn test_products_multi_key_sort() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let (token, store) = register_store(&app).await;
+    seed_catalog(&app, &token).await;
+
+    let (status, body) = send(
+        &app,
+        test::TestRequest::get().uri("/api/v1/products/?sort=-price,name"),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    assert_eq!(
+        names(&body),
+        vec![
+            "Camiseta Azul",
+            "Camiseta Verde",
+            "Caneca Azul",
+            "Caneca Branca"
+        ]
+    );
+
+    let (_, body) = send(
+        &app,
+        test::TestRequest::get().uri("/api/v1/products/?sort=stock&limit=2"),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(names(&body), vec!["Camiseta Verde", "Caneca Azul"]);
+    assert_eq!(body["count"], 4);
+
+    // A vitrine aceita a mesma ordenação
+    let (status, body) = send(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/v1/store/{}/products/?sort=price", store)),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    assert_eq!(names(&body)[0], "Caneca Branca");
+
+    // Fora da lista ou tentando injetar SQL: 400, sem chegar ao banco
+    for sort in [
+        "preco",
+        "price%3BDROP%20TABLE%20products",
+        "p.price",
+        "name,-name",
+    ] {
+        let (status, body) = send(
+            &app,
+            test::TestRequest::get().uri(&format!("/api/v1/products/?sort={}", sort)),
+            Some(&token),
+        )
+        .await;
+        assert_eq!(status, 400, "{}", sort);
+        assert_eq!(body["code"], "BAD_REQUEST");
+    }
+
+    // Cursor só com a ordenação padrão
+    let (status, _) = send(
+        &app,
+        test::TestRequest::get().uri("/api/v1/products/?cursor=&sort=price"),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 400);
+    let (status, _) = send(
+        &app,
+        test::TestRequest::get().uri("/api/v1/products/?cursor=&sort=-created"),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200);
+}
+
+#[actix_web::test]
+async fn test_products_attribute_and_stock_filters() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let (token, store) = register_store(&app).await;
+    let products = seed_catalog(&app, &token).await;
+
+    let list = |query: &str| {
+        test::TestRequest::get().uri(&format!("/api/v1/products/?sort=name&{}", query))
+    };
+
+    let (status, body) = send(&app, list("attr.cor=azul"), Some(&token)).await;
+    assert_eq!(status, 200, "{}", body);
+    assert_eq!(names(&body), vec!["Camiseta Azul", "Caneca Azul"]);
+
+    // Chave repetida: qualquer um dos valores
+    let (_, body) = send(&app, list("attr.cor=verde&attr.cor=branca"), Some(&token)).await;
+    assert_eq!(names(&body), vec!["Camiseta Verde", "Caneca Branca"]);
+
+    // "42" casa com o número e com o texto
+    let (_, body) = send(&app, list("attr.tamanho=42"), Some(&token)).await;
+    assert_eq!(names(&body), vec!["Camiseta Azul", "Camiseta Verde"]);
+
+    let (_, body) = send(&app, list("attr.termica=true"), Some(&token)).await;
+    assert_eq!(names(&body), vec!["Caneca Azul"]);
+
+    // Faixa numérica ignora valores que não são número ("pesada")
+    let (_, body) = send(
+        &app,
+        list("attr.peso.gte=0.25&attr.peso.lt=1"),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(names(&body), vec!["Camiseta Verde", "Caneca Azul"]);
+
+    let (_, body) = send(&app, list("attr.cor=azul&in_stock=true"), Some(&token)).await;
+    assert_eq!(names(&body), vec!["Camiseta Azul", "Caneca Azul"]);
+
+    let (_, body) = send(&app, list("in_stock=false"), Some(&token)).await;
+    assert_eq!(names(&body), vec!["Camiseta Verde"]);
+
+    // Alterados depois do cadastro inicial
+    let caneca = &products[2];
+    let (status, updated) = send(
+        &app,
+        test::TestRequest::put()
+            .uri(&format!(
+                "/api/v1/products/{}/",
+                caneca["id"].as_str().unwrap()
+            ))
+            .set_json(json!({ "stock_quantity": 0 })),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", updated);
+    let since = updated["dt_updated"].as_str().unwrap().replace('+', "%2B");
+    let (status, body) = send(
+        &app,
+        list(&format!("updated_since={}", since)),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    assert_eq!(names(&body), vec!["Caneca Azul"]);
+
+    // Vitrine também filtra por atributo
+    let (_, body) = send(
+        &app,
+        test::TestRequest::get().uri(&format!(
+            "/api/v1/store/{}/products/?attr.cor=azul&in_stock=true",
+            store
+        )),
+        None,
+    )
+    .await;
+    assert_eq!(names(&body), vec!["Camiseta Azul"]);
+
+    for query in [
+        "attr.cor%27%20OR%201%3D1--=azul",
+        "attr.peso.entre=1",
+        "attr.peso.gte=leve",
+        "in_stock=talvez",
+        "updated_since=ontem",
+    ] {
+        let (status, _) = send(&app, list(query), Some(&token)).await;
+        assert_eq!(status, 400, "{}", query);
+    }
+}
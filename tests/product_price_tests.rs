@@ -0,0 +1,320 @@
+use actix_web::{App, test, web};
+use bigdecimal::BigDecimal;
+use chrono::{Duration, Utc};
+use serde_json::{Value, json};
+use std::str::FromStr;
+
+use rust_template::app_core::app_routes::api_v1_scope;
+
+mod test_utils;
+use test_utils::{create_product, create_test_app_state, register, send, token};
+
+// ===== TEST HELPERS =====
+
+/// Valores monetários chegam como texto; compara pelo número, não pela escala
+fn amount(value: &Value) -> BigDecimal {
+    BigDecimal::from_str(value.as_str().expect("Valor deveria ser texto")).unwrap()
+}
+
+fn amount_of(value: &str) -> BigDecimal {
+    BigDecimal::from_str(value).unwrap()
+}
+
+// ===== TESTS =====
+
+#[actix_web::test]
+async fn test_base_price_changes_are_recorded() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let user = register(&app).await;
+    let token = token(&user);
+    let id = create_product(&app, token, "Mochila", 10000).await;
+    let uri = format!("/api/v1/products/{}/", id);
+
+    // Só alterações efetivas de preço entram no histórico
+    for body in [
+        json!({ "price": 8990 }),
+        json!({ "name": "Mochila Urbana" }),
+        json!({ "price": 8990 }),
+    ] {
+        let (status, body) = send(
+            &app,
+            test::TestRequest::put().uri(&uri).set_json(body),
+            Some(token),
+        )
+        .await;
+        assert_eq!(status, 200, "{}", body);
+    }
+
+    let (status, history) = send(
+        &app,
+        test::TestRequest::get().uri(&format!("{}prices/", uri)),
+        Some(token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", history);
+    let history = history.as_array().unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(amount(&history[0]["price"]), amount_of("89.90"));
+    assert_eq!(amount(&history[1]["price"]), amount_of("100.00"));
+    for entry in history {
+        assert_eq!(entry["kind"], "base");
+        assert_eq!(entry["created_by"], user["user"]["id"]);
+    }
+
+    // Histórico é do tenant dono do produto
+    let other = register(&app).await;
+    let (status, _) = send(
+        &app,
+        test::TestRequest::get().uri(&format!("{}prices/", uri)),
+        Some(other["token"].as_str().unwrap()),
+    )
+    .await;
+    assert_eq!(status, 404);
+}
+
+#[actix_web::test]
+async fn test_scheduled_price_is_effective_in_reads_and_cart() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let seller = register(&app).await;
+    let seller_token = token(&seller);
+    let store = seller["user"]["tenant"]["slug"].as_str().unwrap();
+    let id = create_product(&app, seller_token, "Fone Bluetooth", 10000).await;
+    let prices_uri = format!("/api/v1/products/{}/prices/", id);
+    let now = Utc::now();
+
+    // Promoção vigente
+    let (status, sale) = send(
+        &app,
+        test::TestRequest::post().uri(&prices_uri).set_json(json!({
+            "price": 7990,
+            "starts_at": now - Duration::hours(1),
+            "ends_at": now + Duration::hours(1)
+        })),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 201, "{}", sale);
+    assert_eq!(sale["kind"], "scheduled");
+
+    let (_, product) = send(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/v1/products/{}/", id)),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(product["price"]["amount_minor"], 10000);
+    assert_eq!(product["price"]["currency"], "BRL");
+    assert_eq!(product["effective_price"]["amount_minor"], 7990);
+    assert_eq!(product["effective_price"]["currency"], "BRL");
+    assert_eq!(product["compare_at_price"]["amount_minor"], 10000);
+    assert!(product["sale_ends_at"].is_string());
+
+    let (_, listing) = send(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/v1/store/{}/products/", store)),
+        None,
+    )
+    .await;
+    assert_eq!(
+        listing["results"][0]["effective_price"]["amount_minor"],
+        7990
+    );
+
+    // Janelas do mesmo produto não se sobrepõem; agendamento futuro não vale agora
+    let (status, _) = send(
+        &app,
+        test::TestRequest::post().uri(&prices_uri).set_json(json!({
+            "price": 6990,
+            "starts_at": now + Duration::minutes(30),
+            "ends_at": now + Duration::days(1)
+        })),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 409);
+
+    let (status, _) = send(
+        &app,
+        test::TestRequest::post().uri(&prices_uri).set_json(json!({
+            "price": 6990,
+            "compare_at_price": 12990,
+            "starts_at": now + Duration::days(7),
+            "ends_at": now + Duration::days(8)
+        })),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 201);
+
+    let (status, _) = send(
+        &app,
+        test::TestRequest::post().uri(&prices_uri).set_json(json!({
+            "price": 6990,
+            "compare_at_price": 5000,
+            "starts_at": now + Duration::days(10)
+        })),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 400);
+
+    // Carrinho de outro tenant recebe o preço promocional
+    let buyer = register(&app).await;
+    let (status, cart) = send(
+        &app,
+        test::TestRequest::post()
+            .uri("/api/v1/carts/add-product/")
+            .set_json(json!({ "product_id": id, "quantity": 2 })),
+        Some(token(&buyer)),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["items"][0]["unit_price"]["amount_minor"], 7990);
+
+    // Cancelada a promoção, volta o preço base
+    let sale_uri = format!("{}{}/", prices_uri, sale["id"].as_str().unwrap());
+    let (status, _) = send(
+        &app,
+        test::TestRequest::delete().uri(&sale_uri),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 204);
+    let (status, _) = send(
+        &app,
+        test::TestRequest::delete().uri(&sale_uri),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 404);
+
+    let (_, product) = send(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/v1/products/{}/", id)),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(product["effective_price"]["amount_minor"], 10000);
+    assert!(product["compare_at_price"].is_null());
+
+    let (_, history) = send(
+        &app,
+        test::TestRequest::get().uri(&prices_uri),
+        Some(seller_token),
+    )
+    .await;
+    let history = history.as_array().unwrap();
+    assert_eq!(history.len(), 3);
+    assert!(history.iter().any(|p| p["dt_deleted"].is_string()));
+}
+
+#[actix_web::test]
+async fn test_listing_sorts_and_filters_by_effective_price() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let seller = register(&app).await;
+    let seller_token = token(&seller);
+    let store = seller["user"]["tenant"]["slug"].as_str().unwrap();
+    let on_sale = create_product(&app, seller_token, "Caixa de Som", 10000).await;
+    create_product(&app, seller_token, "Carregador", 8000).await;
+    let now = Utc::now();
+
+    let (status, _) = send(
+        &app,
+        test::TestRequest::post()
+            .uri(&format!("/api/v1/products/{}/prices/", on_sale))
+            .set_json(json!({
+                "price": 5000,
+                "starts_at": now - Duration::hours(1),
+                "ends_at": now + Duration::hours(1)
+            })),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(status, 201);
+
+    let names = |body: &Value| -> Vec<String> {
+        body["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let (status, listing) = send(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/v1/store/{}/products/?sort=price", store)),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200, "{}", listing);
+    assert_eq!(names(&listing), vec!["Caixa de Som", "Carregador"]);
+
+    let (_, listing) = send(
+        &app,
+        test::TestRequest::get().uri("/api/v1/products/?max_price=6000"),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(names(&listing), vec!["Caixa de Som"]);
+    assert_eq!(listing["count"], 1);
+
+    let (_, listing) = send(
+        &app,
+        test::TestRequest::get().uri("/api/v1/products/?min_price=6000&cursor=&include_count=true"),
+        Some(seller_token),
+    )
+    .await;
+    assert_eq!(names(&listing), vec!["Carregador"]);
+    assert_eq!(listing["count"], 1);
+}
+
+#[actix_web::test]
+async fn test_concurrent_schedules_do_not_overlap() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let seller = register(&app).await;
+    let seller_token = token(&seller);
+    let id = create_product(&app, seller_token, "Teclado Mecânico", 30000).await;
+    let prices_uri = format!("/api/v1/products/{}/prices/", id);
+    let now = Utc::now();
+
+    // Todas passam pela checagem prévia ao mesmo tempo; só a restrição do banco separa
+    let attempts = (0..5).map(|i| {
+        send(
+            &app,
+            test::TestRequest::post().uri(&prices_uri).set_json(json!({
+                "price": 25000 - i * 100,
+                "starts_at": now + Duration::days(1) + Duration::minutes(i),
+                "ends_at": now + Duration::days(2)
+            })),
+            Some(seller_token),
+        )
+    });
+    let mut statuses: Vec<u16> = futures::future::join_all(attempts)
+        .await
+        .into_iter()
+        .map(|(status, _)| status)
+        .collect();
+    statuses.sort();
+    assert_eq!(statuses, vec![201, 409, 409, 409, 409]);
+}
@@ -0,0 +1,189 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::{App, test, web};
+use serde_json::{Value, json};
+
+use rust_template::app_core::app_routes::api_v1_scope;
+
+mod test_utils;
+use test_utils::{create_test_app_state, register_token, send_with_location};
+
+// ===== TEST HELPERS =====
+
+async fn create_product<S, B>(app: &S, token: &str, name: &str) -> Value
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, _, body) = send_with_location(
+        app,
+        test::TestRequest::post()
+            .uri("/api/v1/products/")
+            .set_json(json!({
+                "name": name,
+                "price": 4990,
+                "stock_quantity": 10,
+                "is_active": true
+            })),
+        Some(token),
+    )
+    .await;
+    assert_eq!(status, 201, "{}", body);
+    body
+}
+
+// ===== TESTS =====
+
+#[actix_web::test]
+async fn test_product_slug_generation_and_collisions() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token = register_token(&app).await;
+
+    let first = create_product(&app, &token, "Pão de Açúcar Orgânico").await;
+    let second = create_product(&app, &token, "Pão de açúcar orgânico!").await;
+    let third = create_product(&app, &token, "PÃO DE AÇÚCAR ORGÂNICO").await;
+
+    assert_eq!(first["slug"], "pao-de-acucar-organico");
+    assert_eq!(second["slug"], "pao-de-acucar-organico-2");
+    assert_eq!(third["slug"], "pao-de-acucar-organico-3");
+
+    let (status, _, body) = send_with_location(
+        &app,
+        test::TestRequest::get().uri("/api/v1/products/by-slug/pao-de-acucar-organico-2/"),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(body["id"], second["id"]);
+
+    let (status, _, _) = send_with_location(
+        &app,
+        test::TestRequest::get().uri("/api/v1/products/by-slug/nao-existe/"),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 404);
+}
+
+#[actix_web::test]
+async fn test_renamed_product_redirects_old_slug() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token = register_token(&app).await;
+
+    let product = create_product(&app, &token, "Café Especial").await;
+    let product_id = product["id"].as_str().unwrap();
+    assert_eq!(product["slug"], "cafe-especial");
+
+    let (status, _, renamed) = send_with_location(
+        &app,
+        test::TestRequest::put()
+            .uri(&format!("/api/v1/products/{}/", product_id))
+            .set_json(json!({"name": "Café Especial Torra Média"})),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(renamed["slug"], "cafe-especial-torra-media");
+
+    // Slug antigo responde 301 para o atual
+    let (status, location, body) = send_with_location(
+        &app,
+        test::TestRequest::get().uri("/api/v1/products/by-slug/cafe-especial/"),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 301);
+    assert_eq!(
+        location.as_deref(),
+        Some("/api/v1/products/by-slug/cafe-especial-torra-media/")
+    );
+    assert_eq!(body["slug"], "cafe-especial-torra-media");
+
+    // Slug antigo continua reservado para o produto renomeado
+    let other = create_product(&app, &token, "Café Especial").await;
+    assert_eq!(other["slug"], "cafe-especial-2");
+
+    // Atualizar sem trocar o nome mantém o slug
+    let (status, _, updated) = send_with_location(
+        &app,
+        test::TestRequest::put()
+            .uri(&format!("/api/v1/products/{}/", product_id))
+            .set_json(json!({"stock_quantity": 3})),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(updated["slug"], "cafe-especial-torra-media");
+
+    // Voltar ao nome original recupera o slug antigo e desfaz o redirect
+    let (status, _, restored) = send_with_location(
+        &app,
+        test::TestRequest::put()
+            .uri(&format!("/api/v1/products/{}/", product_id))
+            .set_json(json!({"name": "Café Especial"})),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(restored["slug"], "cafe-especial");
+
+    let (status, _, body) = send_with_location(
+        &app,
+        test::TestRequest::get().uri("/api/v1/products/by-slug/cafe-especial/"),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(body["id"], product["id"]);
+
+    let (status, location, _) = send_with_location(
+        &app,
+        test::TestRequest::get().uri("/api/v1/products/by-slug/cafe-especial-torra-media/"),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 301);
+    assert_eq!(
+        location.as_deref(),
+        Some("/api/v1/products/by-slug/cafe-especial/")
+    );
+}
+
+#[actix_web::test]
+async fn test_product_slugs_are_unique_per_tenant() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let token_a = register_token(&app).await;
+    let token_b = register_token(&app).await;
+
+    let product_a = create_product(&app, &token_a, "Tênis de Corrida").await;
+    let product_b = create_product(&app, &token_b, "Tênis de Corrida").await;
+
+    // Mesmo slug em tenants diferentes
+    assert_eq!(product_a["slug"], "tenis-de-corrida");
+    assert_eq!(product_b["slug"], "tenis-de-corrida");
+
+    let (status, _, body) = send_with_location(
+        &app,
+        test::TestRequest::get().uri("/api/v1/products/by-slug/tenis-de-corrida/"),
+        Some(&token_b),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(body["id"], product_b["id"]);
+}
@@ -0,0 +1,125 @@
+use actix_web::{App, test as actix_test, web};
+
+use rust_template::app_core::{
+    app_routes::api_v1_scope,
+    app_state::AppState,
+    rate_limit::TokenBucket,
+    settings::{RateLimitKey, RateLimitRule},
+};
+
+mod test_utils;
+
+// ===== TEST SETUP =====
+
+fn init() {
+    test_utils::init_with(|| {
+        // SAFETY: executado uma única vez, antes de qualquer leitura das configurações
+        unsafe {
+            std::env::set_var("RATE_LIMIT_ENABLED", "true");
+            std::env::set_var("RATE_LIMIT_BACKEND", "memory");
+            std::env::set_var("RATE_LIMIT_AUTH_REQUESTS", "2");
+            std::env::set_var("RATE_LIMIT_AUTH_PERIOD", "60");
+            std::env::set_var("RATE_LIMIT_AUTH_KEY", "ip");
+        }
+    });
+}
+
+fn rule(requests: u32, period: u64) -> RateLimitRule {
+    RateLimitRule {
+        requests,
+        period,
+        key: RateLimitKey::Ip,
+    }
+}
+
+// ===== TOKEN BUCKET =====
+
+#[test]
+fn test_bucket_allows_up_to_capacity() {
+    let rule = rule(3, 60);
+    let mut bucket = TokenBucket::full(&rule);
+
+    let remaining: Vec<u32> = (0..3)
+        .map(|_| {
+            let decision = bucket.take(0.0, &rule);
+            assert!(decision.allowed);
+            decision.remaining
+        })
+        .collect();
+    assert_eq!(remaining, vec![2, 1, 0]);
+
+    let denied = bucket.take(0.0, &rule);
+    assert!(!denied.allowed);
+    assert_eq!(denied.limit, 3);
+    assert_eq!(denied.remaining, 0);
+    assert_eq!(denied.retry_after, Some(20));
+    assert_eq!(denied.reset_after, 60);
+}
+
+#[test]
+fn test_bucket_refills_over_time() {
+    let rule = rule(10, 10);
+    let mut bucket = TokenBucket { tokens: 0.0 };
+
+    assert!(!bucket.take(0.5, &rule).allowed);
+
+    let decision = bucket.take(0.5, &rule);
+    assert!(decision.allowed);
+    assert_eq!(decision.remaining, 0);
+}
+
+#[test]
+fn test_bucket_never_exceeds_capacity() {
+    let rule = rule(5, 60);
+    let mut bucket = TokenBucket::full(&rule);
+
+    let decision = bucket.take(3600.0, &rule);
+    assert!(decision.allowed);
+    assert_eq!(decision.remaining, 4);
+    assert_eq!(decision.retry_after, None);
+}
+
+// ===== MIDDLEWARE =====
+
+#[actix_web::test]
+async fn test_auth_routes_are_rate_limited_by_ip() {
+    init();
+    let pool = sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(AppState { db: pool }))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let peer = "10.0.0.1:1234".parse().unwrap();
+    for expected_remaining in ["1", "0"] {
+        let req = actix_test::TestRequest::post()
+            .uri("/api/v1/auth/login/")
+            .peer_addr(peer)
+            .to_request();
+        let resp = actix_test::call_service(&app, req).await;
+        assert_ne!(resp.status(), 429);
+        assert_eq!(resp.headers().get("RateLimit-Limit").unwrap(), "2");
+        assert_eq!(
+            resp.headers().get("RateLimit-Remaining").unwrap(),
+            expected_remaining
+        );
+    }
+
+    let req = actix_test::TestRequest::post()
+        .uri("/api/v1/auth/login/")
+        .peer_addr(peer)
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 429);
+    assert!(resp.headers().contains_key("Retry-After"));
+
+    // Outro IP possui seu próprio bucket
+    let req = actix_test::TestRequest::post()
+        .uri("/api/v1/auth/login/")
+        .peer_addr("10.0.0.2:1234".parse().unwrap())
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_ne!(resp.status(), 429);
+}
@@ -0,0 +1,102 @@
+use actix_web::{App, test as actix_test, web};
+
+use rust_template::app_core::{
+    app_error::ErrorResponse,
+    app_routes::api_v1_scope,
+    request_id::{current_request_id, sanitize_request_id},
+};
+
+mod test_utils;
+use test_utils::{init, lazy_app_state};
+
+// ===== TESTS =====
+
+#[test]
+fn test_sanitize_request_id() {
+    assert_eq!(
+        sanitize_request_id(Some("abc-123_x.y")),
+        Some("abc-123_x.y".to_string())
+    );
+    assert_eq!(sanitize_request_id(Some("  ")), None);
+    assert_eq!(sanitize_request_id(Some("id com espaço")), None);
+    assert_eq!(sanitize_request_id(Some(&"a".repeat(129))), None);
+    assert_eq!(sanitize_request_id(None), None);
+    assert_eq!(current_request_id(), None);
+}
+
+#[actix_web::test]
+async fn test_request_id_is_echoed_back() {
+    init();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(lazy_app_state()))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/health/")
+        .insert_header(("X-Request-Id", "req-123"))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+
+    assert_eq!(resp.headers().get("X-Request-Id").unwrap(), "req-123");
+}
+
+#[actix_web::test]
+async fn test_request_id_is_generated_when_missing_or_invalid() {
+    init();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(lazy_app_state()))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/health/")
+        .insert_header(("X-Request-Id", "inválido com espaços"))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+
+    let request_id = resp
+        .headers()
+        .get("X-Request-Id")
+        .unwrap()
+        .to_str()
+        .unwrap();
+    assert!(uuid::Uuid::parse_str(request_id).is_ok());
+}
+
+#[actix_web::test]
+async fn test_request_id_is_included_in_error_bodies() {
+    init();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(lazy_app_state()))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    // Erro gerado pelo AuthMiddleware
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/products/")
+        .insert_header(("X-Request-Id", "req-auth"))
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 401);
+    let body: ErrorResponse = actix_test::read_body_json(resp).await;
+    assert_eq!(body.request_id.as_deref(), Some("req-auth"));
+
+    // Erro gerado por um extractor dentro do handler
+    let req = actix_test::TestRequest::post()
+        .uri("/api/v1/auth/login/")
+        .insert_header(("X-Request-Id", "req-json"))
+        .insert_header(("Content-Type", "application/json"))
+        .set_payload("{")
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.headers().get("X-Request-Id").unwrap(), "req-json");
+    let body: ErrorResponse = actix_test::read_body_json(resp).await;
+    assert_eq!(body.request_id.as_deref(), Some("req-json"));
+}
@@ -0,0 +1,270 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::{App, test, web};
+use serde_json::{Value, json};
+
+use rust_template::app_core::app_routes::api_v1_scope;
+
+mod test_utils;
+use test_utils::{create_test_app_state, register_store, send_with_location};
+
+// ===== TEST HELPERS =====
+
+async fn create_product<S, B>(app: &S, token: &str, name: &str, is_active: bool) -> Value
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, _, body) = send_with_location(
+        app,
+        test::TestRequest::post()
+            .uri("/api/v1/products/")
+            .set_json(json!({
+                "name": name,
+                "price": 1990,
+                "stock_quantity": 5,
+                "is_active": is_active
+            })),
+        Some(token),
+    )
+    .await;
+    assert_eq!(status, 201, "{}", body);
+    body
+}
+
+fn names(body: &Value) -> Vec<&str> {
+    body["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect()
+}
+
+// ===== TESTS =====
+
+#[actix_web::test]
+async fn test_admin_listing_is_scoped_to_tenant() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let (token_a, _) = register_store(&app).await;
+    let (token_b, _) = register_store(&app).await;
+
+    let active = create_product(&app, &token_a, "Caneca Ativa", true).await;
+    let inactive = create_product(&app, &token_a, "Caneca Inativa", false).await;
+    let deleted = create_product(&app, &token_a, "Caneca Excluída", true).await;
+    create_product(&app, &token_b, "Produto de Outra Loja", true).await;
+
+    let (status, _, _) = send_with_location(
+        &app,
+        test::TestRequest::delete().uri(&format!(
+            "/api/v1/products/{}/",
+            deleted["id"].as_str().unwrap()
+        )),
+        Some(&token_a),
+    )
+    .await;
+    assert_eq!(status, 204);
+
+    // Lojista vê ativos e inativos do próprio tenant; excluídos só com include_deleted
+    let (status, _, body) = send_with_location(
+        &app,
+        test::TestRequest::get().uri("/api/v1/products/"),
+        Some(&token_a),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(body["count"], 2);
+    assert_eq!(names(&body), vec!["Caneca Inativa", "Caneca Ativa"]);
+
+    let (_, _, body) = send_with_location(
+        &app,
+        test::TestRequest::get().uri("/api/v1/products/?include_deleted=true"),
+        Some(&token_a),
+    )
+    .await;
+    assert_eq!(body["count"], 3);
+
+    // Detalhe por id: qualquer estado no próprio tenant, 404 para os demais
+    for product in [&active, &inactive, &deleted] {
+        let uri = format!("/api/v1/products/{}/", product["id"].as_str().unwrap());
+        let (status, _, _) =
+            send_with_location(&app, test::TestRequest::get().uri(&uri), Some(&token_a)).await;
+        assert_eq!(status, 200);
+        let (status, _, _) =
+            send_with_location(&app, test::TestRequest::get().uri(&uri), Some(&token_b)).await;
+        assert_eq!(status, 404);
+    }
+
+    // Outro tenant não altera nem exclui o produto
+    let uri = format!("/api/v1/products/{}/", active["id"].as_str().unwrap());
+    let (status, _, _) = send_with_location(
+        &app,
+        test::TestRequest::put()
+            .uri(&uri)
+            .set_json(json!({ "name": "Invadido" })),
+        Some(&token_b),
+    )
+    .await;
+    assert_eq!(status, 404);
+    let (status, _, _) =
+        send_with_location(&app, test::TestRequest::delete().uri(&uri), Some(&token_b)).await;
+    assert_eq!(status, 404);
+}
+
+#[actix_web::test]
+async fn test_storefront_shows_only_active_products() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let (token_a, store_a) = register_store(&app).await;
+    let (token_b, store_b) = register_store(&app).await;
+
+    create_product(&app, &token_a, "Vela Aromática", true).await;
+    let hidden = create_product(&app, &token_a, "Vela Rascunho", false).await;
+    create_product(&app, &token_b, "Sabonete", true).await;
+
+    // Sem autenticação
+    let (status, _, body) = send_with_location(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/v1/store/{}/products/", store_a)),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    assert_eq!(names(&body), vec!["Vela Aromática"]);
+
+    // O filtro is_active da administração não expõe inativos na vitrine
+    let (_, _, body) = send_with_location(
+        &app,
+        test::TestRequest::get().uri(&format!(
+            "/api/v1/store/{}/products/?is_active=false&include_deleted=true",
+            store_a
+        )),
+        None,
+    )
+    .await;
+    assert_eq!(names(&body), vec!["Vela Aromática"]);
+
+    let (_, _, body) = send_with_location(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/v1/store/{}/products/", store_b)),
+        None,
+    )
+    .await;
+    assert_eq!(names(&body), vec!["Sabonete"]);
+
+    // Detalhe pelo slug
+    let (status, _, body) = send_with_location(
+        &app,
+        test::TestRequest::get().uri(&format!(
+            "/api/v1/store/{}/products/vela-aromatica/",
+            store_a
+        )),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(body["name"], "Vela Aromática");
+
+    let hidden_uri = format!(
+        "/api/v1/store/{}/products/{}/",
+        store_a,
+        hidden["slug"].as_str().unwrap()
+    );
+    let (status, _, _) =
+        send_with_location(&app, test::TestRequest::get().uri(&hidden_uri), None).await;
+    assert_eq!(status, 404);
+
+    // Na administração o produto inativo continua acessível pelo slug
+    let (status, _, body) = send_with_location(
+        &app,
+        test::TestRequest::get().uri(&format!(
+            "/api/v1/products/by-slug/{}/",
+            hidden["slug"].as_str().unwrap()
+        )),
+        Some(&token_a),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    assert_eq!(body["is_active"], false);
+
+    // Mas não pelo slug de outro tenant
+    let (status, _, _) = send_with_location(
+        &app,
+        test::TestRequest::get().uri(&format!(
+            "/api/v1/products/by-slug/{}/",
+            hidden["slug"].as_str().unwrap()
+        )),
+        Some(&token_b),
+    )
+    .await;
+    assert_eq!(status, 404);
+
+    // Produto de outra loja não aparece sob este slug
+    let (status, _, _) = send_with_location(
+        &app,
+        test::TestRequest::get().uri(&format!("/api/v1/store/{}/products/sabonete/", store_a)),
+        None,
+    )
+    .await;
+    assert_eq!(status, 404);
+
+    let (status, _, body) = send_with_location(
+        &app,
+        test::TestRequest::get().uri("/api/v1/store/loja-inexistente/products/"),
+        None,
+    )
+    .await;
+    assert_eq!(status, 404);
+    assert_eq!(body["code"], "NOT_FOUND");
+}
+
+#[actix_web::test]
+async fn test_storefront_redirects_renamed_product() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(create_test_app_state().await))
+            .service(api_v1_scope()),
+    )
+    .await;
+    let (token, store) = register_store(&app).await;
+
+    let product = create_product(&app, &token, "Luminária Antiga", true).await;
+    let (status, _, _) = send_with_location(
+        &app,
+        test::TestRequest::put()
+            .uri(&format!(
+                "/api/v1/products/{}/",
+                product["id"].as_str().unwrap()
+            ))
+            .set_json(json!({ "name": "Luminária Nova" })),
+        Some(&token),
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let (status, location, body) = send_with_location(
+        &app,
+        test::TestRequest::get().uri(&format!(
+            "/api/v1/store/{}/products/luminaria-antiga/",
+            store
+        )),
+        None,
+    )
+    .await;
+    assert_eq!(status, 301);
+    assert_eq!(
+        location.as_deref(),
+        Some(format!("/api/v1/store/{}/products/luminaria-nova/", store).as_str())
+    );
+    assert_eq!(body["slug"], "luminaria-nova");
+}
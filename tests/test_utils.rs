@@ -1,5 +1,47 @@
+// Compartilhado entre os testes de integração; nem todo binário usa todas as funções
+#![allow(dead_code)]
+
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::test as actix_test;
+use rust_template::app_core::{app_state::AppState, init_settings::init_settings};
+use serde_json::{Value, json};
 use sqlx::PgPool;
 use std::env;
+use std::sync::Once;
+use uuid::Uuid;
+
+static INIT: Once = Once::new();
+
+/// Carrega o `.env` e as configurações uma única vez por binário de teste
+pub fn init() {
+    init_with(|| {});
+}
+
+/// Como `init`, mas roda `setup` antes de ler as configurações (variáveis de ambiente do binário)
+pub fn init_with(setup: impl FnOnce()) {
+    INIT.call_once(|| {
+        dotenvy::dotenv().ok();
+        setup();
+        init_settings().expect("Falha ao inicializar settings");
+    });
+}
+
+/// Estado da aplicação sobre o banco de testes já migrado
+pub async fn create_test_app_state() -> AppState {
+    init();
+    let pool = get_test_db_pool().await;
+    run_migrations(&pool).await;
+    AppState { db: pool }
+}
+
+/// Estado com pool preguiçoso, para testes que não chegam ao banco
+pub fn lazy_app_state() -> AppState {
+    AppState {
+        db: sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap(),
+    }
+}
 
 pub async fn get_test_db_pool() -> PgPool {
     let db_url = env::var("DATABASE_URL_TEST").expect("DATABASE_URL_TEST não definida");
@@ -17,6 +59,9 @@ pub async fn run_migrations(db: &PgPool) {
 
 pub async fn clean_test_db(db: &PgPool) {
     // Truncar tabelas na ordem correta (respeitando foreign keys)
+    let _ = sqlx::query!("TRUNCATE TABLE audit_events")
+        .execute(db)
+        .await;
     let _ = sqlx::query!("TRUNCATE TABLE user_tokens CASCADE")
         .execute(db)
         .await;
@@ -34,3 +79,144 @@ pub async fn setup_test_db() -> PgPool {
     clean_test_db(&pool).await;
     pool
 }
+
+// ===== HTTP =====
+
+/// Registra um usuário (com tenant próprio); devolve o corpo do cadastro
+pub async fn register<S, B>(app: &S) -> Value
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let req = actix_test::TestRequest::post()
+        .uri("/api/v1/auth/register/")
+        .set_json(json!({
+            "email": format!("test_{}@example.com", Uuid::new_v4()),
+            "first_name": "Test",
+            "last_name": "User",
+            "password": "password123"
+        }))
+        .to_request();
+    actix_test::call_and_read_body_json(app, req).await
+}
+
+/// Registra um membro no tenant de outro usuário
+pub async fn register_member<S, B>(app: &S, tenant_id: &str) -> Value
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let req = actix_test::TestRequest::post()
+        .uri("/api/v1/auth/register/")
+        .set_json(json!({
+            "email": format!("member_{}@example.com", Uuid::new_v4()),
+            "first_name": "Test",
+            "last_name": "Member",
+            "password": "password123",
+            "tenant_id": tenant_id
+        }))
+        .to_request();
+    actix_test::call_and_read_body_json(app, req).await
+}
+
+/// `register` quando só o token interessa
+pub async fn register_token<S, B>(app: &S) -> String
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    token(&register(app).await).to_string()
+}
+
+/// `register` devolvendo o token e o slug da loja
+pub async fn register_store<S, B>(app: &S) -> (String, String)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let user = register(app).await;
+    let slug = user["user"]["tenant"]["slug"]
+        .as_str()
+        .expect("Slug da loja deveria existir");
+    (token(&user).to_string(), slug.to_string())
+}
+
+pub fn token(user: &Value) -> &str {
+    user["token"].as_str().expect("Token deveria existir")
+}
+
+/// Envia a requisição (autenticada quando há token); devolve status e corpo
+pub async fn send<S, B>(app: &S, req: actix_test::TestRequest, token: Option<&str>) -> (u16, Value)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, _, body) = send_with_location(app, req, token).await;
+    (status, body)
+}
+
+/// Como `send`, devolvendo também o header Location
+pub async fn send_with_location<S, B>(
+    app: &S,
+    req: actix_test::TestRequest,
+    token: Option<&str>,
+) -> (u16, Option<String>, Value)
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let req = match token {
+        Some(token) => req.insert_header(("Authorization", format!("Bearer {}", token))),
+        None => req,
+    };
+    let resp = actix_test::call_service(app, req.to_request()).await;
+    let status = resp.status().as_u16();
+    let location = resp
+        .headers()
+        .get("Location")
+        .map(|v| v.to_str().unwrap().to_string());
+    let body = actix_test::read_body(resp).await;
+    (
+        status,
+        location,
+        serde_json::from_slice(&body).unwrap_or(Value::Null),
+    )
+}
+
+/// Cria o produto com o corpo informado; devolve o produto criado
+pub async fn create_product_with<S, B>(app: &S, token: &str, body: Value) -> Value
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let (status, body) = send(
+        app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/products/")
+            .set_json(body),
+        Some(token),
+    )
+    .await;
+    assert_eq!(status, 201, "{}", body);
+    body
+}
+
+/// Produto ativo com 10 unidades em estoque; devolve o id
+pub async fn create_product<S, B>(app: &S, token: &str, name: &str, price: i64) -> String
+where
+    S: Service<Request, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    B: MessageBody,
+{
+    let product = create_product_with(
+        app,
+        token,
+        json!({
+            "name": name,
+            "price": price,
+            "stock_quantity": 10,
+            "is_active": true
+        }),
+    )
+    .await;
+    product["id"].as_str().unwrap().to_string()
+}
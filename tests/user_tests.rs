@@ -115,6 +115,52 @@ async fn test_create_user() {
     clean_test_db(&pool).await;
 }
 
+#[actix_web::test]
+async fn test_create_user_records_audit_event() {
+    init();
+
+    let pool = setup_test_db().await;
+    let app_state = create_test_app_state(pool.clone());
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let user_request = create_test_user_request();
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/register/")
+        .insert_header(("User-Agent", "audit-test"))
+        .set_json(&user_request)
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let event = sqlx::query!(
+        r#"
+        SELECT a.action, a.entity_type, a.actor_id, a.user_agent, a.after
+        FROM audit_events a
+        JOIN users u ON u.id = a.entity_id
+        WHERE u.email = $1
+        "#,
+        user_request.email
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("Evento de auditoria deveria existir no banco");
+
+    assert_eq!(event.action, "create");
+    assert_eq!(event.entity_type, "user");
+    assert!(event.actor_id.is_some());
+    assert_eq!(event.user_agent.as_deref(), Some("audit-test"));
+    assert!(!event.after.unwrap().to_string().contains("password"));
+
+    clean_test_db(&pool).await;
+}
+
 #[actix_web::test]
 async fn test_login_user() {
     init();
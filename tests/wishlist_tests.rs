@@ -0,0 +1,350 @@
+use actix_web::{App, test as actix_test, web};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use rust_template::app_core::{app_routes::api_v1_scope, app_state::AppState};
+use rust_template::apps::wishlist::services::WishlistService;
+
+mod test_utils;
+use test_utils::{create_product, create_test_app_state, register, send, token};
+
+// ===== TESTS =====
+
+#[actix_web::test]
+async fn test_wishlists_items_and_share_link() {
+    let app_state = create_test_app_state().await;
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller = register(&app).await;
+    let pen = create_product(&app, token(&seller), "Caneta", 250).await;
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+
+    let create = |name: &str| {
+        actix_test::TestRequest::post()
+            .uri("/api/v1/wishlists/")
+            .set_json(json!({ "name": name }))
+    };
+    let (status, wishlist) = send(&app, create("Presentes"), Some(buyer_token)).await;
+    assert_eq!(status, 201, "{}", wishlist);
+    assert_eq!(wishlist["kind"], "WISHLIST");
+    assert!(wishlist["share_token"].is_null());
+    let (status, _) = send(&app, create("presentes"), Some(buyer_token)).await;
+    assert_eq!(status, 409);
+    let (status, _) = send(&app, create("  "), Some(buyer_token)).await;
+    assert_eq!(status, 400);
+
+    let wishlist_uri = format!("/api/v1/wishlists/{}/", wishlist["id"].as_str().unwrap());
+    let add = |quantity: i32| {
+        actix_test::TestRequest::post()
+            .uri(&format!("{}items/", wishlist_uri))
+            .set_json(json!({ "product_id": pen, "quantity": quantity }))
+    };
+    let (status, item) = send(&app, add(1), Some(buyer_token)).await;
+    assert_eq!(status, 201, "{}", item);
+    // Mesmo produto soma a quantidade na mesma linha
+    let (status, again) = send(&app, add(2), Some(buyer_token)).await;
+    assert_eq!(status, 201, "{}", again);
+    assert_eq!(again["id"], item["id"]);
+    assert_eq!(again["quantity"], 3);
+    let (status, _) = send(&app, add(0), Some(buyer_token)).await;
+    assert_eq!(status, 400);
+
+    let (status, body) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&wishlist_uri),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    assert_eq!(body["items"][0]["product_name"], "Caneta");
+    assert_eq!(body["items"][0]["price"]["amount_minor"], 250);
+
+    // Lista de outro usuário não existe para ele
+    let other = register(&app).await;
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::get().uri(&wishlist_uri),
+        Some(token(&other)),
+    )
+    .await;
+    assert_eq!(status, 404);
+
+    // Link público: somente leitura, sem dono nem preferências de aviso
+    let (status, share) = send(
+        &app,
+        actix_test::TestRequest::post().uri(&format!("{}share/", wishlist_uri)),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", share);
+    let shared_uri = share["path"].as_str().unwrap().to_string();
+    let (status, shared) = send(&app, actix_test::TestRequest::get().uri(&shared_uri), None).await;
+    assert_eq!(status, 200, "{}", shared);
+    assert_eq!(shared["name"], "Presentes");
+    assert_eq!(shared["items"][0]["quantity"], 3);
+    assert_eq!(shared["items"][0]["in_stock"], true);
+    assert!(shared.get("user_id").is_none());
+    assert!(shared["items"][0].get("notify_price_drop").is_none());
+
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::delete().uri(&format!("{}share/", wishlist_uri)),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 204);
+    let (status, _) = send(&app, actix_test::TestRequest::get().uri(&shared_uri), None).await;
+    assert_eq!(status, 404);
+
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::delete().uri(&wishlist_uri),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 204);
+    let (_, lists) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/wishlists/"),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(lists, json!([]));
+}
+
+#[actix_web::test]
+async fn test_save_for_later_keeps_variant_and_attributes() {
+    let app_state = create_test_app_state().await;
+    let db = app_state.db.clone();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller = register(&app).await;
+    let pen = create_product(&app, token(&seller), "Caneta", 250).await;
+    let book = create_product(&app, token(&seller), "Livro", 4000).await;
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+
+    let (status, cart) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/carts/bulk/")
+            .set_json(json!({ "operations": [
+                { "op": "add", "product_id": pen, "quantity": 3 },
+                { "op": "add", "product_id": book, "quantity": 1 }
+            ] })),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", cart);
+    let pen_item = cart["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["product_id"] == pen.as_str())
+        .unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // A API ainda não grava variantes: a linha recebe uma direto no banco
+    let variant_id = Uuid::new_v4();
+    sqlx::query("UPDATE cart_items SET variant_id = $1, attributes_snapshot = $2 WHERE id = $3")
+        .bind(variant_id)
+        .bind(json!({ "cor": "azul" }))
+        .bind(Uuid::parse_str(&pen_item).unwrap())
+        .execute(&db)
+        .await
+        .unwrap();
+
+    let (status, cart) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri(&format!("/api/v1/carts/items/{}/save-for-later/", pen_item)),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["unique_products"], 1);
+    assert_eq!(cart["subtotal"]["amount_minor"], 4000);
+
+    let (status, saved) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/wishlists/saved/"),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", saved);
+    assert_eq!(saved["kind"], "SAVED_FOR_LATER");
+    let saved_item = &saved["items"][0];
+    assert_eq!(saved_item["product_id"], pen.as_str());
+    assert_eq!(saved_item["quantity"], 3);
+    assert_eq!(saved_item["variant_id"], variant_id.to_string());
+    assert_eq!(saved_item["attributes_snapshot"], json!({ "cor": "azul" }));
+
+    // A linha já saiu do carrinho
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri(&format!("/api/v1/carts/items/{}/save-for-later/", pen_item)),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 404);
+
+    // De volta ao carrinho, com preço atual, variante e atributos
+    let move_uri = format!(
+        "/api/v1/wishlists/{}/items/{}/move-to-cart/",
+        saved["id"].as_str().unwrap(),
+        saved_item["id"].as_str().unwrap()
+    );
+    let (status, cart) = send(
+        &app,
+        actix_test::TestRequest::post().uri(&move_uri),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", cart);
+    assert_eq!(cart["unique_products"], 2);
+    assert_eq!(cart["subtotal"]["amount_minor"], 4750);
+    let restored = cart["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["product_id"] == pen.as_str())
+        .unwrap();
+    assert_eq!(restored["variant_id"], variant_id.to_string());
+    assert_eq!(restored["attributes_snapshot"], json!({ "cor": "azul" }));
+
+    let (_, saved) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/wishlists/saved/"),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(saved["items"], json!([]));
+    let (status, _) = send(
+        &app,
+        actix_test::TestRequest::post().uri(&move_uri),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 404);
+}
+
+#[actix_web::test]
+async fn test_price_drop_and_back_in_stock_notifications() {
+    let app_state = create_test_app_state().await;
+    let scanner = AppState {
+        db: app_state.db.clone(),
+    };
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    let seller = register(&app).await;
+    let seller_token = token(&seller);
+    let pen = create_product(&app, seller_token, "Caneta", 250).await;
+    let buyer = register(&app).await;
+    let buyer_token = token(&buyer);
+
+    let (_, wishlist) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri("/api/v1/wishlists/")
+            .set_json(json!({ "name": "Avisos" })),
+        Some(buyer_token),
+    )
+    .await;
+    let (status, item) = send(
+        &app,
+        actix_test::TestRequest::post()
+            .uri(&format!(
+                "/api/v1/wishlists/{}/items/",
+                wishlist["id"].as_str().unwrap()
+            ))
+            .set_json(json!({
+                "product_id": pen,
+                "notify_price_drop": true,
+                "notify_back_in_stock": true
+            })),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 201, "{}", item);
+    assert_eq!(item["notify_price_drop"], true);
+
+    let update = |body: Value| {
+        actix_test::TestRequest::put()
+            .uri(&format!("/api/v1/products/{}/", pen))
+            .set_json(body)
+    };
+    let notifications = || actix_test::TestRequest::get().uri("/api/v1/wishlists/notifications/");
+
+    // Nada mudou desde a inclusão
+    WishlistService::scan_alerts(&scanner).await.unwrap();
+    let (_, body) = send(&app, notifications(), Some(buyer_token)).await;
+    assert_eq!(body, json!([]));
+
+    let (status, _) = send(&app, update(json!({ "price": 200 })), Some(seller_token)).await;
+    assert_eq!(status, 200);
+    WishlistService::scan_alerts(&scanner).await.unwrap();
+    // A mesma queda não avisa duas vezes
+    WishlistService::scan_alerts(&scanner).await.unwrap();
+
+    let (_, body) = send(&app, notifications(), Some(buyer_token)).await;
+    assert_eq!(body.as_array().unwrap().len(), 1, "{}", body);
+    assert_eq!(body[0]["kind"], "price_drop");
+    assert_eq!(body[0]["old_price"]["amount_minor"], 250);
+    assert_eq!(body[0]["new_price"]["amount_minor"], 200);
+
+    // Esgota e volta ao estoque
+    send(
+        &app,
+        update(json!({ "stock_quantity": 0 })),
+        Some(seller_token),
+    )
+    .await;
+    WishlistService::scan_alerts(&scanner).await.unwrap();
+    send(
+        &app,
+        update(json!({ "stock_quantity": 5 })),
+        Some(seller_token),
+    )
+    .await;
+    WishlistService::scan_alerts(&scanner).await.unwrap();
+
+    let (_, body) = send(&app, notifications(), Some(buyer_token)).await;
+    assert_eq!(body.as_array().unwrap().len(), 2, "{}", body);
+    assert_eq!(body[0]["kind"], "back_in_stock");
+    assert!(body[0]["new_price"].is_null());
+
+    let (status, body) = send(
+        &app,
+        actix_test::TestRequest::post().uri("/api/v1/wishlists/notifications/read/"),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", body);
+    assert_eq!(body["updated"], 2);
+    let (_, body) = send(
+        &app,
+        actix_test::TestRequest::get().uri("/api/v1/wishlists/notifications/?unread=true"),
+        Some(buyer_token),
+    )
+    .await;
+    assert_eq!(body, json!([]));
+}
@@ -0,0 +1,136 @@
+use actix_web::{App, HttpResponse, test as actix_test, web};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use rust_template::app_core::{
+    app_routes::api_v1_scope,
+    health::{CheckStatus, ReadinessResponse},
+    workers::{WorkerSupervisor, supervisor},
+};
+
+mod test_utils;
+use test_utils::{init, lazy_app_state};
+
+// ===== TESTS =====
+
+#[tokio::test]
+async fn test_workers_stop_when_signalled() {
+    let workers = WorkerSupervisor::new();
+    let done = Arc::new(AtomicUsize::new(0));
+
+    let counter = done.clone();
+    workers.spawn("cooperative", move |mut signal| async move {
+        signal.cancelled().await;
+        // Simula o trabalho de finalização (ex: flush de uma fila)
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    assert!(workers.status()[0].running);
+
+    let report = workers.shutdown(Duration::from_secs(2)).await;
+    assert_eq!(report.finished, vec!["cooperative".to_string()]);
+    assert!(report.aborted.is_empty());
+    assert_eq!(done.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_workers_are_aborted_after_deadline() {
+    let workers = WorkerSupervisor::new();
+
+    workers.spawn("stubborn", |_signal| async move {
+        // Ignora o sinal de desligamento
+        tokio::time::sleep(Duration::from_secs(60)).await;
+    });
+
+    let report = workers.shutdown(Duration::from_millis(50)).await;
+    assert!(report.finished.is_empty());
+    assert_eq!(report.aborted, vec!["stubborn".to_string()]);
+}
+
+#[tokio::test]
+async fn test_periodic_worker_finishes_current_run() {
+    let workers = WorkerSupervisor::new();
+    let runs = Arc::new(AtomicUsize::new(0));
+
+    let counter = runs.clone();
+    workers.spawn_periodic("periodic", Duration::from_millis(10), move || {
+        let counter = counter.clone();
+        async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+
+    tokio::time::sleep(Duration::from_millis(35)).await;
+    let report = workers.shutdown(Duration::from_secs(1)).await;
+
+    assert_eq!(report.finished.len(), 1);
+    assert!(runs.load(Ordering::SeqCst) >= 1);
+
+    // Depois do desligamento nenhum worker novo é aceito
+    workers.spawn("late", |_signal| async {});
+    assert!(workers.status().is_empty());
+}
+
+#[tokio::test]
+async fn test_task_started_in_handler_is_drained() {
+    let workers = Arc::new(WorkerSupervisor::new());
+    workers.set_runtime(tokio::runtime::Handle::current());
+    let done = Arc::new(AtomicUsize::new(0));
+
+    // Handler num runtime próprio, como o de um worker do actix, descartado antes do
+    // desligamento do supervisor
+    let (supervisor, counter) = (workers.clone(), done.clone());
+    std::thread::spawn(move || {
+        actix_web::rt::System::new().block_on(async move {
+            let app = actix_test::init_service(App::new().route(
+                "/import/",
+                web::post().to(move || {
+                    let (supervisor, counter) = (supervisor.clone(), counter.clone());
+                    async move {
+                        supervisor.spawn_task("import", move |mut signal| async move {
+                            signal.cancelled().await;
+                            tokio::time::sleep(Duration::from_millis(20)).await;
+                            counter.fetch_add(1, Ordering::SeqCst);
+                        });
+                        HttpResponse::Accepted().finish()
+                    }
+                }),
+            ))
+            .await;
+            let req = actix_test::TestRequest::post().uri("/import/").to_request();
+            let resp = actix_test::call_service(&app, req).await;
+            assert_eq!(resp.status(), 202);
+        });
+    })
+    .join()
+    .unwrap();
+
+    let report = workers.shutdown(Duration::from_secs(2)).await;
+    assert_eq!(report.finished, vec!["import".to_string()]);
+    assert_eq!(done.load(Ordering::SeqCst), 1);
+}
+
+#[actix_web::test]
+async fn test_readiness_fails_during_shutdown() {
+    init();
+    let app = actix_test::init_service(
+        App::new()
+            .app_data(web::Data::new(lazy_app_state()))
+            .service(api_v1_scope()),
+    )
+    .await;
+
+    // Supervisor global: só este teste deste binário o desliga
+    supervisor().begin_shutdown();
+
+    let req = actix_test::TestRequest::get()
+        .uri("/api/v1/health/ready/")
+        .to_request();
+    let resp = actix_test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 503);
+
+    let body: ReadinessResponse = actix_test::read_body_json(resp).await;
+    assert_eq!(body.checks["workers"].status, CheckStatus::Down);
+}